@@ -0,0 +1,151 @@
+use std::{collections::HashMap, time::Duration};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use oxidd_viz_rust::{
+    types::util::{
+        drawing::{
+            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            layout_rules::LayoutRules,
+            layouts::{
+                layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
+                layer_orderings::{
+                    combinators::sequence_ordering::SequenceOrdering,
+                    edge_layer_ordering::EdgeLayerOrdering,
+                    pseudo_random_layer_ordering::PseudoRandomLayerOrdering,
+                    sugiyama_ordering::SugiyamaOrdering,
+                },
+                layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+                layered_layout::{LayeredLayout, LayoutPhaseDurations},
+                layered_layout_traits::WidthLabel,
+            },
+        },
+        graph_structure::{
+            graph_manipulators::label_adjusters::group_label_adjuster::GroupLabelAdjuster,
+            grouped_graph_structure::GroupedGraphStructure,
+            oxidd_graph_structure::OxiddGraphStructure,
+        },
+        group_manager::GroupManager,
+    },
+    util::{
+        dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+        graph_builder::GraphBuilder,
+        transition::Interpolatable,
+    },
+};
+
+#[derive(Clone)]
+struct BenchNode {
+    width: f32,
+}
+impl Interpolatable for BenchNode {
+    fn mix(&self, other: &Self, frac: f32) -> Self {
+        BenchNode {
+            width: self.width * (1. - frac) + other.width * frac,
+        }
+    }
+}
+impl NodeStyle for BenchNode {}
+impl WidthLabel for BenchNode {
+    fn get_width(&self) -> f32 {
+        self.width
+    }
+}
+
+#[derive(Clone)]
+struct BenchLayer;
+impl Interpolatable for BenchLayer {
+    fn mix(&self, _other: &Self, _frac: f32) -> Self {
+        BenchLayer
+    }
+}
+impl LayerStyle for BenchLayer {
+    fn squash(_layers: Vec<Self>) -> Self {
+        BenchLayer
+    }
+}
+
+type Graph = GroupLabelAdjuster<
+    BenchNode,
+    BenchLayer,
+    GroupManager<OxiddGraphStructure<(), DummyBDDFunction, String>>,
+>;
+type Layout = LayeredLayout<
+    Graph,
+    SequenceOrdering<
+        Graph,
+        PseudoRandomLayerOrdering,
+        SequenceOrdering<Graph, EdgeLayerOrdering, SugiyamaOrdering>,
+    >,
+    OrderingGroupAlignment,
+    BrandesKopfPositioningCorrected,
+>;
+
+/// The (levels, nodes per level) of the synthetic diagrams
+const SIZES: [(u32, usize); 3] = [(8, 4), (16, 8), (32, 16)];
+
+/// The phases of the layered layout that are measured separately
+const PHASES: [(&str, fn(&LayoutPhaseDurations) -> f64); 5] = [
+    ("setup", |durations| durations.setup),
+    ("ordering", |durations| durations.ordering),
+    ("aligning", |durations| durations.aligning),
+    ("positioning", |durations| durations.positioning),
+    ("formatting", |durations| durations.formatting),
+];
+
+fn create_graph(levels: u32, width: usize) -> Graph {
+    let builder = GraphBuilder::generate_random_bdd(levels, levels as usize * width, 0);
+    let manager = DummyBDDManager::new();
+    let (roots, level_labels) =
+        DummyBDDFunction::from_builder(&mut DummyBDDManagerRef::from(&manager), &builder);
+    let mut group_manager = GroupManager::new(OxiddGraphStructure::new(roots, level_labels));
+    group_manager.reveal_bounded(usize::MAX, usize::MAX);
+    GroupLabelAdjuster::new(
+        group_manager,
+        |_, nodes| BenchNode {
+            width: if nodes.len() > 1 { 2. } else { 1. },
+        },
+        |_| BenchLayer,
+    )
+}
+
+fn create_layout() -> Layout {
+    LayeredLayout::new(
+        SequenceOrdering::new(
+            PseudoRandomLayerOrdering::new(2, 0),
+            SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
+        ),
+        OrderingGroupAlignment,
+        BrandesKopfPositioningCorrected,
+        0.3,
+    )
+}
+
+fn layout_phases(c: &mut Criterion) {
+    for (phase, get_duration) in PHASES {
+        let mut group = c.benchmark_group(format!("layered layout {}", phase));
+        for (levels, width) in SIZES {
+            let mut graph = create_graph(levels, width);
+            let tracker = graph.create_node_tracker();
+            let old = DiagramLayout {
+                groups: HashMap::new(),
+                layers: Vec::new(),
+            };
+            let size = format!("{}x{}", levels, width);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &graph, |b, graph| {
+                b.iter_custom(|iterations| {
+                    let mut total = 0.;
+                    for _ in 0..iterations {
+                        let mut layout = create_layout();
+                        black_box(layout.layout(graph, &old, &tracker, 0).unwrap());
+                        total += get_duration(&layout.get_phase_durations());
+                    }
+                    Duration::from_secs_f64(total / 1000.)
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, layout_phases);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! The identifiers and options through which the engine is controlled, which frontends such as the wasm bindings translate their own types into.
+
+/// The identifier of a node in a graph structure
+pub type NodeID = usize;
+/// The identifier of a group of nodes in a grouped graph structure
+pub type NodeGroupID = usize;
+
+/// Whether a target refers to a node or to a group of nodes
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TargetIDType {
+    NodeID,
+    NodeGroupID,
+}
+
+/// A node or group of nodes, e.g. that is moved into another group
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TargetID(pub TargetIDType, pub NodeID);
+impl TargetID {
+    pub fn new(id_type: TargetIDType, id: NodeID) -> TargetID {
+        TargetID(id_type, id)
+    }
+}
+
+/// The animation of groups that appear in or disappear from the diagram
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppearanceAnimation {
+    Fade,
+    /// Grows from or shrinks to the center of the group
+    Grow,
+    /// Flies in from above while fading in, or flies up while fading out
+    Fly,
+}
+
+/// The side of a group that is aligned with the other groups, which is its center for Center
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HorizontalAlignment {
+    Left,
+    Center,
+    Right,
+}
@@ -0,0 +1,81 @@
+//! The visualization engine of OxiDD-viz, for frontends such as the wasm bindings of `oxidd-viz-rust`, or native tools such as TUI or egui applications. It contains the graph structures, manipulators, layouts and exporters, and does not depend on the browser.
+//!
+//! The types that make up the API are re-exported at the root of the crate. The main building blocks are:
+//!
+//! - A [`GraphStructure`] describing the nodes and edges of a decision diagram, such as the [`OxiddGraphStructure`] of an oxidd function
+//! - A stack of graph manipulators on top of it, such as the [`GroupPresenceAdjuster`] which yields a [`GroupedGraphStructure`]
+//! - A [`LayoutRules`] implementation, such as the [`LayeredLayout`], which lays out the grouped graph as a [`DiagramLayout`]
+//! - A [`DiagramRenderer`] drawing the layout, such as the [`SvgRenderer`] and [`LatexRenderer`] exporters, or a renderer provided by the consumer
+//!
+//! Messages are written to the standard error and time is measured using `Instant`, which frontends can replace through [`set_log_sink`] and [`set_clock`], e.g. on platforms without a system clock.
+
+pub mod interface;
+pub mod types;
+pub mod util;
+
+pub use interface::{
+    AppearanceAnimation, HorizontalAlignment, NodeGroupID, NodeID, TargetID, TargetIDType,
+};
+
+pub use crate::types::util::{
+    drawing::{
+        diagram_layout::{
+            DiagramLayout, EdgeLayout, EdgePoint, LayerLayout, LayerStyle, NodeGroupLayout,
+            NodeStyle,
+        },
+        layout_rules::LayoutRules,
+        layouts::{
+            layer_group_sorting::{
+                average_group_alignment::AverageGroupAlignment,
+                ordering_group_alignment::OrderingGroupAlignment,
+            },
+            layer_orderings::{
+                combinators::sequence_ordering::SequenceOrdering,
+                edge_layer_ordering::EdgeLayerOrdering, sugiyama_ordering::SugiyamaOrdering,
+            },
+            layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+            layered_layout::LayeredLayout,
+            layered_layout_traits::{
+                LayerGroupSorting, LayerOrdering, NodePositioning, WidthLabel,
+            },
+            toggle_layout::ToggleLayout,
+            transition::transition_layout::TransitionLayout,
+        },
+        legend::{Legend, LegendEntry, LegendSymbol},
+        pagination::{Page, PaperSize},
+        renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        renderers::{
+            latex_renderer::{LatexLayerStyle, LatexNodeStyle, LatexRenderer},
+            render_styles::{
+                EdgeRenderingType, LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle,
+            },
+            svg_renderer::SvgRenderer,
+        },
+    },
+    graph_structure::{
+        graph_manipulators::{
+            group_presence_adjuster::GroupPresenceAdjuster,
+            label_adjusters::{
+                group_label_adjuster::GroupLabelAdjuster, level_label_adjuster::LevelLabelAdjuster,
+            },
+            node_presence_adjuster::{NodePresenceAdjuster, PresencePredicate, PresenceRemainder},
+            pointer_node_adjuster::PointerNodeAdjuster,
+            rc_graph::RCGraph,
+            terminal_level_adjuster::TerminalLevelAdjuster,
+        },
+        graph_structure::{DrawTag, EdgeType, GraphStructure},
+        grouped_graph_structure::{EdgeData, GroupedGraphStructure, NodeTracker},
+        oxidd_graph_structure::{NodeType, OxiddGraphStructure},
+    },
+    structural_hasher::StructuralHasher,
+};
+pub use crate::util::{
+    clock::set_clock,
+    color::{Color, TransparentColor},
+    logging::{set_log_level, set_log_sink, LogLevel, LogSink},
+    point::Point,
+    rectangle::Rectangle,
+    transformation::Transformation,
+    transition::Transition,
+    viz_error::VizError,
+};
@@ -0,0 +1,222 @@
+use std::io::{Cursor, Error, ErrorKind, Result};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    interface::NodeID,
+    types::util::storage::state_storage::Serializable,
+    util::{color::Color, point::Point, transition::Transition},
+};
+
+pub type AnnotationID = usize;
+
+/// The color of arrows and highlighted regions, shared by all renderers such that exports look like the visualization
+pub const ANNOTATION_COLOR: Color = Color(0.9, 0.45, 0.1);
+pub const HIGHLIGHT_OPACITY: f32 = 0.3;
+pub const ARROW_WIDTH: f32 = 0.15;
+pub const ARROW_HEAD_SIZE: f32 = 0.6;
+/// The length of the dashed stubs that mark hidden structure, as well as the length and spacing of their dashes
+pub const STUB_LENGTH: f32 = 0.8;
+/// The label of stubs that mark edges to hidden nodes
+pub const STUB_ELLIPSIS: &str = "…";
+pub const STUB_DASH_LENGTH: f32 = 0.15;
+pub const STUB_OPACITY: f32 = 0.6;
+/// The horizontal period and vertical amplitude of the zig-zag line that marks elided levels
+pub const BREAK_PERIOD: f32 = 0.4;
+pub const BREAK_AMPLITUDE: f32 = 0.12;
+/// The space between the end of a break's zig-zag line and its label
+pub const BREAK_LABEL_GAP: f32 = 0.3;
+
+#[derive(Clone)]
+pub enum AnnotationKind {
+    /// A text note centered at the annotation's position
+    Note(String),
+    /// An arrow from the annotation's position, pointing in the given direction
+    Arrow(Point),
+    /// A translucent region centered at the annotation's position, with the given size
+    Highlight(Point),
+    /// A dashed stub from the annotation's position in the given direction, marking hidden structure, which is labeled at its end with the given text
+    Stub(Point, String),
+    /// A horizontal zig-zag line of the given width centered at the annotation's position, marking a band of elided levels, which is labeled to the right of its end with the given text
+    Break(f32, String),
+}
+
+/// A user placed annotation, which moves along with the group containing its anchor node
+#[derive(Clone)]
+pub struct Annotation {
+    /// The node that the annotation is attached to, or None if it is placed at a fixed position
+    pub anchor: Option<NodeID>,
+    /// The position relative to the center of the anchor's group, or the absolute position if there is no anchor
+    pub offset: Point,
+    pub kind: AnnotationKind,
+}
+
+/// An annotation resolved to diagram coordinates, as drawn by the renderers
+#[derive(Clone)]
+pub struct AnnotationShape {
+    pub position: Transition<Point>,
+    pub exists: Transition<f32>,
+    pub kind: AnnotationKind,
+}
+
+/// Obtains the dashes of a stub starting at the given point, as the start and end of every dash
+pub fn get_stub_dashes(start: Point, delta: Point) -> Vec<(Point, Point)> {
+    let length = delta.length();
+    if length <= 0. {
+        return Vec::new();
+    }
+    let dir = delta * (1. / length);
+    let dash_count = (length / (2. * STUB_DASH_LENGTH)).ceil() as usize;
+    (0..dash_count)
+        .map(|index| {
+            let from = 2. * STUB_DASH_LENGTH * index as f32;
+            let to = (from + STUB_DASH_LENGTH).min(length);
+            (start + dir * from, start + dir * to)
+        })
+        .collect()
+}
+
+/// Obtains the corners of a horizontal zig-zag line of the given width centered at the given point, from left to right
+pub fn get_break_zigzag(center: Point, width: f32) -> Vec<Point> {
+    let segments = ((width / (0.5 * BREAK_PERIOD)).ceil() as usize).max(2);
+    let step = width / segments as f32;
+    (0..=segments)
+        .map(|index| {
+            let y = if index % 2 == 0 {
+                BREAK_AMPLITUDE
+            } else {
+                -BREAK_AMPLITUDE
+            };
+            center
+                + Point {
+                    x: -0.5 * width + step * index as f32,
+                    y,
+                }
+        })
+        .collect()
+}
+
+/// Obtains the direction of the stub that marks an edge of the given type towards a hidden node, where the first two edge types point to the right and left as then and else edges usually do, and other edge types point downwards
+pub fn get_edge_stub_delta(edge_index: i32) -> Point {
+    let x = match edge_index {
+        0 => 0.5,
+        1 => -0.5,
+        _ => 0.,
+    };
+    STUB_LENGTH * Point { x, y: -1. }
+}
+
+/// Obtains the outline of an arrow starting at the given point, as a polygon of the tail, the head and the tip
+pub fn get_arrow_outline(start: Point, delta: Point) -> [Point; 7] {
+    let length = delta.length();
+    if length <= 0. {
+        return [start; 7];
+    }
+    let dir = delta * (1. / length);
+    let orth = Point {
+        x: -dir.y,
+        y: dir.x,
+    };
+    let head_size = ARROW_HEAD_SIZE.min(length);
+    let neck = start + dir * (length - head_size);
+    let shaft = orth * (0.5 * ARROW_WIDTH);
+    let head = orth * (0.5 * head_size);
+    [
+        start + shaft,
+        neck + shaft,
+        neck + head,
+        start + delta,
+        neck - head,
+        neck - shaft,
+        start - shaft,
+    ]
+}
+
+impl Serializable for Annotation {
+    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+        match self.anchor {
+            Some(anchor) => {
+                stream.write_u8(1)?;
+                stream.write_u32::<LittleEndian>(anchor as u32)?;
+            }
+            None => stream.write_u8(0)?,
+        }
+        stream.write_f32::<LittleEndian>(self.offset.x)?;
+        stream.write_f32::<LittleEndian>(self.offset.y)?;
+        match &self.kind {
+            AnnotationKind::Note(text) => {
+                stream.write_u8(0)?;
+                text.serialize(stream)
+            }
+            AnnotationKind::Arrow(point) | AnnotationKind::Highlight(point) => {
+                let kind = if let AnnotationKind::Arrow(_) = self.kind {
+                    1
+                } else {
+                    2
+                };
+                stream.write_u8(kind)?;
+                stream.write_f32::<LittleEndian>(point.x)?;
+                stream.write_f32::<LittleEndian>(point.y)
+            }
+            AnnotationKind::Stub(delta, label) => {
+                stream.write_u8(3)?;
+                stream.write_f32::<LittleEndian>(delta.x)?;
+                stream.write_f32::<LittleEndian>(delta.y)?;
+                label.serialize(stream)
+            }
+            AnnotationKind::Break(width, label) => {
+                stream.write_u8(4)?;
+                stream.write_f32::<LittleEndian>(*width)?;
+                label.serialize(stream)
+            }
+        }
+    }
+
+    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<Annotation> {
+        let anchor = if stream.read_u8()? == 1 {
+            Some(stream.read_u32::<LittleEndian>()? as NodeID)
+        } else {
+            None
+        };
+        let offset = Point {
+            x: stream.read_f32::<LittleEndian>()?,
+            y: stream.read_f32::<LittleEndian>()?,
+        };
+        let kind = match stream.read_u8()? {
+            0 => AnnotationKind::Note(String::deserialize(stream)?),
+            kind @ (1 | 2) => {
+                let point = Point {
+                    x: stream.read_f32::<LittleEndian>()?,
+                    y: stream.read_f32::<LittleEndian>()?,
+                };
+                if kind == 1 {
+                    AnnotationKind::Arrow(point)
+                } else {
+                    AnnotationKind::Highlight(point)
+                }
+            }
+            3 => {
+                let delta = Point {
+                    x: stream.read_f32::<LittleEndian>()?,
+                    y: stream.read_f32::<LittleEndian>()?,
+                };
+                AnnotationKind::Stub(delta, String::deserialize(stream)?)
+            }
+            4 => {
+                let width = stream.read_f32::<LittleEndian>()?;
+                AnnotationKind::Break(width, String::deserialize(stream)?)
+            }
+            kind => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Unknown annotation kind: {}", kind),
+                ))
+            }
+        };
+        Ok(Annotation {
+            anchor,
+            offset,
+            kind,
+        })
+    }
+}
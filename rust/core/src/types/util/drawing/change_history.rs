@@ -0,0 +1,64 @@
+use crate::types::util::graph_structure::graph_structure::{Change, DrawTag};
+
+use super::diagram_layout::{DiagramLayout, LayerStyle, NodeStyle};
+
+/// The maximum number of states that are kept, after which the oldest states are discarded
+const MAX_ENTRIES: usize = 200;
+
+/// A state of the diagram, as laid out after the underlying graph changed
+pub struct HistoryEntry<T: DrawTag, S: NodeStyle, LS: LayerStyle> {
+    /// The time at which the changes were processed, in ms
+    pub time: u32,
+    /// The changes of the underlying graph since the previous state
+    pub changes: Vec<Change>,
+    pub layout: DiagramLayout<T, S, LS>,
+}
+
+/// The evolution of a diagram, which allows showing earlier states of the diagram again
+pub struct ChangeHistory<T: DrawTag, S: NodeStyle, LS: LayerStyle> {
+    entries: Vec<HistoryEntry<T, S, LS>>,
+    /// The index of the entry that is shown instead of the latest layout, if any
+    position: Option<usize>,
+}
+
+impl<T: DrawTag, S: NodeStyle, LS: LayerStyle> ChangeHistory<T, S, LS> {
+    pub fn new() -> ChangeHistory<T, S, LS> {
+        ChangeHistory {
+            entries: Vec::new(),
+            position: None,
+        }
+    }
+
+    pub fn record(&mut self, time: u32, changes: Vec<Change>, layout: DiagramLayout<T, S, LS>) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(HistoryEntry {
+            time,
+            changes,
+            layout,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get_entries(&self) -> &Vec<HistoryEntry<T, S, LS>> {
+        &self.entries
+    }
+
+    /// Selects the state to show, where none or the last index refer to the latest state
+    pub fn scrub(&mut self, index: Option<usize>) {
+        self.position = index.filter(|&index| index + 1 < self.entries.len());
+    }
+
+    /// Retrieves the layout of the earlier state that is shown, if any
+    pub fn get_shown(&self) -> Option<&DiagramLayout<T, S, LS>> {
+        Some(&self.entries[self.position?].layout)
+    }
+
+    pub fn get_position(&self) -> Option<usize> {
+        self.position
+    }
+}
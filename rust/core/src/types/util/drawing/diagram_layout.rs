@@ -0,0 +1,204 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+    rc::Rc,
+};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+use oxidd_core::Tag;
+
+use crate::{
+    interface::{NodeGroupID, NodeID},
+    types::util::graph_structure::{graph_structure::DrawTag, grouped_graph_structure::EdgeData},
+    util::{
+        point::Point,
+        rectangle::Rectangle,
+        transition::{Interpolatable, Transition},
+    },
+};
+
+#[derive(Clone)]
+pub struct NodeGroupLayout<T: DrawTag, S: NodeStyle> {
+    /// Bottom center point of the node
+    pub position: Transition<Point>,
+    pub size: Transition<Point>,
+    pub exists: Transition<f32>, // A number between 0 and 1 of whether this node is visible (0-1)
+    pub edges: HashMap<EdgeData<T>, EdgeLayout>,
+    pub level_range: (LevelNo, LevelNo),
+    pub style: Transition<S>,
+    /// Offset of the center of the label relative to the center of the node, labels with no offset are drawn inside of the node
+    pub label_offset: Transition<Point>,
+    pub label_exists: Transition<f32>, // A number between 0 and 1 of whether the label is visible, labels may be hidden to prevent overlap
+}
+impl<T: DrawTag, S: NodeStyle> NodeGroupLayout<T, S> {
+    // TODO: possibly consider the selection time? (animations should be quick and not have a huge effect however)
+
+    pub fn get_rect(&self, time: Option<u32>) -> Rectangle {
+        match time {
+            Some(time) => {
+                let pos = self.position.get(time);
+                let size = self.size.get(time);
+                Rectangle::new(pos.x - 0.5 * size.x, pos.y, size.x, size.y)
+            }
+            _ => {
+                let pos = self.position.new;
+                let size = self.size.new;
+                Rectangle::new(pos.x - 0.5 * size.x, pos.y, size.x, size.y)
+            }
+        }
+    }
+}
+
+/// Where the label of a node is placed relative to the node
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LabelPlacement {
+    Inside,
+    Above,
+    Below,
+}
+
+#[derive(Clone)]
+pub struct EdgeLayout {
+    pub start_offset: Transition<Point>,
+    pub end_offset: Transition<Point>,
+    pub points: Vec<EdgePoint>,
+    pub exists: Transition<f32>, // Transition for newly created edges
+    pub curve_offset: Transition<f32>, // If no bendpoints are used, this curve offset can be used for curving the edge, in the range [-1, 1], 0 represents no bend
+}
+
+#[derive(Copy, Clone)]
+pub struct EdgePoint {
+    pub point: Transition<Point>,
+    // TODO: give more thought to jumps
+    // pub is_jump: Transition<f32>, // Whether this point represents an edge crossing, or just a bend
+    pub exists: Transition<f32>, // Whether this point actually exists in the output (it might be used only to transition shape)
+}
+
+#[derive(Clone)]
+pub struct LayerLayout<S: LayerStyle> {
+    pub start_layer: LevelNo,
+    pub end_layer: LevelNo,
+    pub top: Transition<f32>,
+    pub bottom: Transition<f32>,
+    pub index: Transition<f32>,
+    pub exists: Transition<f32>,
+    pub style: Transition<S>,
+    /// Whether the layer consists of levels without any visible nodes, which were collapsed into a thin band
+    pub elided: bool,
+}
+
+#[derive(Clone)]
+pub struct DiagramLayout<T: DrawTag, S: NodeStyle, LS: LayerStyle> {
+    pub groups: HashMap<NodeGroupID, NodeGroupLayout<T, S>>,
+    /// Note: this vector has to be sorted in increasing order of start_layer
+    pub layers: Vec<LayerLayout<LS>>,
+}
+
+impl<T: DrawTag, S: NodeStyle, LS: LayerStyle> DiagramLayout<T, S, LS> {
+    /// Splits the shown groups of every layer with more groups than the budget into at most that many runs of horizontally adjacent groups, where a layer consists of the groups starting at its level
+    pub fn get_layer_runs(&self, budget: usize) -> Vec<Vec<NodeGroupID>> {
+        let budget = budget.max(1);
+        self.groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.)
+            .map(|(&group_id, group)| (group.level_range.0, group.position.new.x, group_id))
+            .sorted_by(|(level1, x1, id1), (level2, x2, id2)| {
+                level1
+                    .cmp(level2)
+                    .then(x1.total_cmp(x2))
+                    .then(id1.cmp(id2))
+            })
+            .group_by(|&(level, _, _)| level)
+            .into_iter()
+            .flat_map(|(_, groups)| {
+                let groups = groups.map(|(_, _, group_id)| group_id).collect_vec();
+                if groups.len() <= budget {
+                    return Vec::new();
+                }
+                let run_size = (groups.len() + budget - 1) / budget;
+                groups.chunks(run_size).map(|run| run.to_vec()).collect_vec()
+            })
+            .collect()
+    }
+
+    /// The time at which all transitions of the layout have finished, after which drawing it again yields the same image
+    pub fn get_animation_end(&self) -> u32 {
+        let group_ends = self.groups.values().flat_map(|group| {
+            let edge_ends = group.edges.values().flat_map(|edge| {
+                let point_ends = edge.points.iter().flat_map(|edge_point| {
+                    [
+                        edge_point.point.get_end_time(),
+                        edge_point.exists.get_end_time(),
+                    ]
+                });
+                IntoIterator::into_iter([
+                    edge.start_offset.get_end_time(),
+                    edge.end_offset.get_end_time(),
+                    edge.exists.get_end_time(),
+                    edge.curve_offset.get_end_time(),
+                ])
+                .chain(point_ends)
+            });
+            IntoIterator::into_iter([
+                group.position.get_end_time(),
+                group.size.get_end_time(),
+                group.exists.get_end_time(),
+                group.style.get_end_time(),
+                group.label_offset.get_end_time(),
+                group.label_exists.get_end_time(),
+            ])
+            .chain(edge_ends)
+        });
+        let layer_ends = self.layers.iter().flat_map(|layer| {
+            [
+                layer.top.get_end_time(),
+                layer.bottom.get_end_time(),
+                layer.index.get_end_time(),
+                layer.exists.get_end_time(),
+                layer.style.get_end_time(),
+            ]
+        });
+        group_ends.chain(layer_ends).max().unwrap_or(0)
+    }
+
+    /// Moves the positions of the groups, edges and layers onto a grid of the given spacing, such that exports contain round coordinates
+    pub fn snap_to_grid(&mut self, grid: f32) {
+        let snap_point = |point: Point| Point {
+            x: snap_to_grid(point.x, grid),
+            y: snap_to_grid(point.y, grid),
+        };
+        for group in self.groups.values_mut() {
+            group.position.old = snap_point(group.position.old);
+            group.position.new = snap_point(group.position.new);
+            for edge in group.edges.values_mut() {
+                for offset in [&mut edge.start_offset, &mut edge.end_offset] {
+                    offset.old = snap_point(offset.old);
+                    offset.new = snap_point(offset.new);
+                }
+                for edge_point in edge.points.iter_mut() {
+                    edge_point.point.old = snap_point(edge_point.point.old);
+                    edge_point.point.new = snap_point(edge_point.point.new);
+                }
+            }
+        }
+        for layer in self.layers.iter_mut() {
+            for bound in [&mut layer.top, &mut layer.bottom] {
+                bound.old = snap_to_grid(bound.old, grid);
+                bound.new = snap_to_grid(bound.new, grid);
+            }
+        }
+    }
+}
+
+/// Rounds the value to the closest multiple of the grid spacing, where dividing by the inverse of the spacing keeps decimal grids such as 0.1 exact when printed
+fn snap_to_grid(value: f32, grid: f32) -> f32 {
+    (value / grid).round() / grid.recip()
+}
+
+pub trait LayerStyle: Interpolatable + Clone + Sized {
+    fn squash(layers: Vec<Self>) -> Self;
+}
+pub trait NodeStyle: Interpolatable + Clone {}
@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use oxidd::LevelNo;
+
+use crate::{interface::NodeGroupID, types::util::graph_structure::graph_structure::DrawTag};
+
+use super::diagram_layout::{DiagramLayout, LayerStyle, NodeStyle};
+
+/// The part of the visible width that the materialized window extends beyond the visible range on either side, such that small pans do not require new geometry
+const WINDOW_MARGIN: f32 = 1.;
+
+/// Limits the geometry of layers with very many groups to the groups within a horizontal window around the camera, such that the renderer never holds the geometry of the off-screen part of a giant layer. The groups of other layers are always materialized, and edges to groups outside of the window are not drawn.
+pub struct LayerWindow {
+    /// The number of groups that a layer needs to exceed before it is windowed, or None if no layers are windowed
+    threshold: Option<usize>,
+    /// The groups of every windowed layer, sorted by the horizontal position of their center as obtained from the positioning
+    layers: Vec<Vec<(f32, NodeGroupID)>>,
+    /// The range of x coordinates whose groups are currently materialized, if any
+    window: Option<(f32, f32)>,
+}
+
+impl LayerWindow {
+    pub fn new() -> LayerWindow {
+        LayerWindow {
+            threshold: None,
+            layers: Vec::new(),
+            window: None,
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: Option<usize>) {
+        self.threshold = threshold;
+    }
+
+    /// Whether any layer of the most recently indexed layout is windowed
+    pub fn is_active(&self) -> bool {
+        !self.layers.is_empty()
+    }
+
+    /// Indexes the groups of the layers that exceed the threshold by their horizontal position, which invalidates the materialized window
+    pub fn index<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+        &mut self,
+        layout: &DiagramLayout<T, S, LS>,
+    ) {
+        self.window = None;
+        self.layers.clear();
+        let Some(threshold) = self.threshold else {
+            return;
+        };
+        let mut layers: HashMap<LevelNo, Vec<(f32, NodeGroupID)>> = HashMap::new();
+        for (&group_id, group) in &layout.groups {
+            layers
+                .entry(group.level_range.0)
+                .or_default()
+                .push((group.position.new.x, group_id));
+        }
+        self.layers = layers
+            .into_values()
+            .filter(|groups| groups.len() > threshold)
+            .map(|mut groups| {
+                groups.sort_by(|(x1, _), (x2, _)| x1.total_cmp(x2));
+                groups
+            })
+            .collect();
+    }
+
+    /// Checks whether the given visible range of x coordinates is not covered by the materialized window
+    pub fn needs_update(&self, visible: (f32, f32)) -> bool {
+        match self.window {
+            Some((min, max)) => self.is_active() && (visible.0 < min || visible.1 > max),
+            None => self.is_active(),
+        }
+    }
+
+    /// Materializes a window around the given visible range of x coordinates, and obtains the layout without the groups of windowed layers outside of this window. Returns None if no layer is windowed, in which case the whole layout should be rendered.
+    pub fn materialize<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+        &mut self,
+        layout: &DiagramLayout<T, S, LS>,
+        visible: (f32, f32),
+    ) -> Option<DiagramLayout<T, S, LS>> {
+        if !self.is_active() {
+            return None;
+        }
+        let margin = WINDOW_MARGIN * (visible.1 - visible.0);
+        let (min, max) = (visible.0 - margin, visible.1 + margin);
+        self.window = Some((min, max));
+
+        let excluded: HashSet<NodeGroupID> = self
+            .layers
+            .iter()
+            .flat_map(|groups| {
+                let start = groups.partition_point(|&(x, _)| x < min);
+                let end = groups.partition_point(|&(x, _)| x <= max);
+                groups[..start].iter().chain(&groups[end..])
+            })
+            .map(|&(_, group_id)| group_id)
+            .collect();
+        Some(DiagramLayout {
+            groups: layout
+                .groups
+                .iter()
+                .filter(|(group_id, _)| !excluded.contains(group_id))
+                .map(|(&group_id, group)| (group_id, group.clone()))
+                .collect(),
+            layers: layout.layers.clone(),
+        })
+    }
+}
@@ -2,14 +2,14 @@ use oxidd::{Function, Manager};
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         graph_structure::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
         group_manager::GroupManager,
     },
-    util::transition::Interpolatable,
-    wasm_interface::NodeGroupID,
+    util::{transition::Interpolatable, viz_error::VizError},
 };
 
 use super::diagram_layout::{DiagramLayout, LayerStyle, NodeStyle};
@@ -32,5 +32,5 @@ pub trait LayoutRules {
         /* Sources for new nodes that did not yet exist in the previous layout iteration */
         new_sources: &Self::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS>;
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError>;
 }
@@ -5,6 +5,7 @@ use num_rational::Ratio;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerGroupSorting,
@@ -14,7 +15,6 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    wasm_interface::NodeGroupID,
 };
 
 pub struct AverageGroupAlignment;
@@ -5,6 +5,7 @@ use num_rational::Ratio;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerGroupSorting,
@@ -15,7 +16,6 @@ use crate::{
         },
     },
     util::logging::console,
-    wasm_interface::NodeGroupID,
 };
 
 pub struct OrderingGroupAlignment;
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerOrdering,
@@ -12,7 +13,6 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    wasm_interface::NodeGroupID,
 };
 
 pub struct DummyLayerOrdering;
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerOrdering,
@@ -14,7 +14,6 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    wasm_interface::NodeGroupID,
 };
 
 /// Sorts nodes according to incoming and outgoing edge ordering values
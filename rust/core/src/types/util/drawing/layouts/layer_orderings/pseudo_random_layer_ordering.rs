@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd_core::Tag;
 use seeded_random::{Random, Seed};
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerOrdering,
@@ -16,7 +16,6 @@ use crate::{
         },
     },
     util::logging::console,
-    wasm_interface::NodeGroupID,
 };
 
 pub struct PseudoRandomLayerOrdering {
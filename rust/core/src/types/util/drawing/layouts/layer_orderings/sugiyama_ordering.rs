@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::{NodeGroupID, NodeID},
     types::util::{
         drawing::layouts::{
             layered_layout_traits::LayerOrdering,
@@ -16,7 +17,6 @@ use crate::{
         },
     },
     util::logging::console,
-    wasm_interface::{NodeGroupID, NodeID},
 };
 
 pub struct SugiyamaOrdering {
@@ -91,7 +91,7 @@ fn hierarchical_barycenter_order(
         if let Some(new_orders) = reverse_equal_nodes(&orders, edges, reversed_edges, ordering) {
             orders = new_orders;
         } else {
-            console::log!("used {} phase 2 iters", i + 1);
+            console::log_debug!("used {} phase 2 iters", i + 1);
             break;
         }
     }
@@ -103,7 +103,7 @@ fn hierarchical_barycenter_order(
     if new_crossings <= old_crossings {
         orders = new_orders;
     }
-    console::log!("crossings old: {}, new: {}", old_crossings, new_crossings);
+    console::log_debug!("crossings old: {}, new: {}", old_crossings, new_crossings);
 
     orders
 }
@@ -139,7 +139,7 @@ fn apply_barycenter_iterative(
         }
 
         if old_crossings == crossings {
-            console::log!("used {} phase 1 iters", i + 1);
+            console::log_debug!("used {} phase 1 iters", i + 1);
             break; // No change detected
         }
     }
@@ -8,6 +8,7 @@ use oxidd::LevelNo;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout::{is_edge_dummy, is_group_dummy},
@@ -22,7 +23,6 @@ use crate::{
         },
     },
     util::{logging::console, point::Point},
-    wasm_interface::NodeGroupID,
 };
 
 pub struct BrandesKopfPositioning;
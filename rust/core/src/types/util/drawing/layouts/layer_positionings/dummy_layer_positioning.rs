@@ -5,6 +5,7 @@ use oxidd::LevelNo;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::layouts::{
             layered_layout_traits::{NodePositioning, WidthLabel},
@@ -15,7 +16,6 @@ use crate::{
         },
     },
     util::point::Point,
-    wasm_interface::NodeGroupID,
 };
 
 pub struct DummyLayerPositioning;
@@ -1,3 +1,4 @@
 pub mod brandes_kopf_positioning;
 pub mod brandes_kopf_positioning_corrected;
 pub mod dummy_layer_positioning;
+pub mod pinned_positioning;
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    interface::{HorizontalAlignment, NodeGroupID, NodeID},
+    types::util::{
+        drawing::{
+            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            layouts::{
+                layered_layout::{get_positioning_x, is_edge_dummy},
+                layered_layout_traits::NodePositioning,
+                util::layered::layer_orderer::{get_sequence, EdgeMap, Order},
+            },
+        },
+        graph_structure::{
+            graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
+        },
+    },
+    util::point::Point,
+};
+
+/// The horizontal space between the sides of neighboring nodes in a layer
+const NODE_SPACING: f32 = 1.;
+
+/// A positioning that places the groups of pinned nodes at fixed horizontal positions, and positions all other nodes using the given positioning, shifting them only as far as needed to not overlap the pinned nodes
+pub struct PinnedPositioning<P> {
+    positioning: P,
+    /// The horizontal position of the group of every pinned node
+    pins: HashMap<NodeID, f32>,
+    /// The side of the layout that the group of every node pinned to a column is placed at
+    columns: HashMap<NodeID, HorizontalAlignment>,
+}
+
+impl<P> PinnedPositioning<P> {
+    pub fn new(positioning: P) -> PinnedPositioning<P> {
+        PinnedPositioning {
+            positioning,
+            pins: HashMap::new(),
+            columns: HashMap::new(),
+        }
+    }
+    pub fn get_positioning(&mut self) -> &mut P {
+        &mut self.positioning
+    }
+    pub fn pin(&mut self, pins: HashMap<NodeID, f32>) {
+        for node in pins.keys() {
+            self.columns.remove(node);
+        }
+        self.pins.extend(pins);
+    }
+    /// Pins the given nodes to a column at the given side of the layout, which is kept regardless of how wide the remainder of the layout is
+    pub fn pin_to_column(&mut self, nodes: &[NodeID], column: HorizontalAlignment) {
+        for &node in nodes {
+            self.pins.remove(&node);
+            self.columns.insert(node, column);
+        }
+    }
+    pub fn unpin(&mut self, nodes: &[NodeID]) {
+        for node in nodes {
+            self.pins.remove(node);
+            self.columns.remove(node);
+        }
+    }
+    pub fn is_pinned(&self, node: NodeID) -> bool {
+        self.pins.contains_key(&node) || self.columns.contains_key(&node)
+    }
+}
+
+impl<G: GroupedGraphStructure, P: NodePositioning<G>> NodePositioning<G> for PinnedPositioning<P> {
+    fn position_nodes(
+        &self,
+        graph: &G,
+        layers: &Vec<Order>,
+        edges: &EdgeMap,
+        node_widths: &HashMap<NodeGroupID, f32>,
+        dummy_group_start_id: NodeGroupID,
+        dummy_edge_start_id: NodeGroupID,
+        owners: &HashMap<NodeGroupID, NodeGroupID>,
+    ) -> (HashMap<NodeGroupID, Point>, HashMap<LevelNo, f32>) {
+        let (mut positions, layer_positions) = self.positioning.position_nodes(
+            graph,
+            layers,
+            edges,
+            node_widths,
+            dummy_group_start_id,
+            dummy_edge_start_id,
+            owners,
+        );
+        if self.pins.is_empty() && self.columns.is_empty() {
+            return (positions, layer_positions);
+        }
+        let width = |id: &NodeGroupID| node_widths.get(id).cloned().unwrap_or(0.);
+
+        // Columns are placed at the sides of the unconstrained layout, after which they act as regular pins
+        let mut pins = self.pins.clone();
+        if !self.columns.is_empty() {
+            let left = positions
+                .iter()
+                .map(|(id, position)| position.x - 0.5 * width(id))
+                .fold(f32::INFINITY, f32::min);
+            let right = positions
+                .iter()
+                .map(|(id, position)| position.x + 0.5 * width(id))
+                .fold(f32::NEG_INFINITY, f32::max);
+            for (&node, column) in &self.columns {
+                let node_width = width(&graph.get_group(node));
+                let x = match column {
+                    HorizontalAlignment::Left => left + 0.5 * node_width,
+                    HorizontalAlignment::Center => 0.5 * (left + right),
+                    HorizontalAlignment::Right => right - 0.5 * node_width,
+                };
+                if x.is_finite() {
+                    pins.insert(node, x);
+                }
+            }
+        }
+
+        // If multiple nodes of a group are pinned, the node with the lowest ID decides the position
+        let mut group_pins = HashMap::<NodeGroupID, f32>::new();
+        for (&node, &x) in pins.iter().sorted_by_key(|(&node, _)| node) {
+            group_pins.entry(graph.get_group(node)).or_insert(x);
+        }
+        // The dummy nodes that make up the layers of a group stay below the pinned group
+        let fixed = positions
+            .keys()
+            .filter_map(|&id| {
+                let owner = if is_edge_dummy(id, dummy_edge_start_id) {
+                    id
+                } else {
+                    owners.get(&id).cloned().unwrap_or(id)
+                };
+                Some((id, *group_pins.get(&owner)?))
+            })
+            .collect::<HashMap<_, _>>();
+        if fixed.is_empty() {
+            return (positions, layer_positions);
+        }
+
+        // Move the unpinned nodes along with the pinned nodes on average, such that the remainder of the layout stays close to the pins
+        let shift = fixed
+            .iter()
+            .map(|(id, &x)| x - positions[id].x)
+            .sum::<f32>()
+            / fixed.len() as f32;
+        for (id, position) in positions.iter_mut() {
+            position.x = fixed.get(id).cloned().unwrap_or(position.x + shift);
+        }
+
+        // Resolve overlap by pushing unpinned nodes away from their left and right neighbors
+        for layer in layers {
+            let sequence = get_sequence(layer)
+                .into_iter()
+                .filter(|id| positions.contains_key(id))
+                .collect_vec();
+            for (left, right) in sequence.iter().tuple_windows() {
+                let min_x = positions[left].x + NODE_SPACING + 0.5 * (width(left) + width(right));
+                if !fixed.contains_key(right) && positions[right].x < min_x {
+                    positions.get_mut(right).unwrap().x = min_x;
+                }
+            }
+            for (right, left) in sequence.iter().rev().tuple_windows() {
+                let max_x = positions[right].x - NODE_SPACING - 0.5 * (width(left) + width(right));
+                if !fixed.contains_key(left) && positions[left].x > max_x {
+                    positions.get_mut(left).unwrap().x = max_x;
+                }
+            }
+        }
+
+        (positions, layer_positions)
+    }
+}
+
+/// Retrieves the positions to pin the given nodes at, such that the groups containing them stay where they are shown in the given layout
+pub fn get_pin_positions<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+    layout: &DiagramLayout<T, S, LS>,
+    nodes: &[NodeID],
+    get_group: impl Fn(NodeID) -> NodeGroupID,
+) -> HashMap<NodeID, f32> {
+    nodes
+        .iter()
+        .filter_map(|&node| {
+            let group = layout.groups.get(&get_group(node))?;
+            Some((node, get_positioning_x(group.position.new.x)))
+        })
+        .collect()
+}
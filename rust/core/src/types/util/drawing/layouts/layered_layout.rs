@@ -0,0 +1,1006 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter::FromIterator,
+    marker::PhantomData,
+};
+
+use itertools::Itertools;
+use num_rational::Ratio;
+use oxidd::{LevelNo, NodeID};
+use oxidd_core::Tag;
+
+use crate::{
+    interface::NodeGroupID,
+    types::util::{
+        drawing::{
+            diagram_layout::{
+                DiagramLayout, EdgeLayout, EdgePoint, LabelPlacement, LayerStyle, NodeGroupLayout,
+                NodeStyle,
+            },
+            layout_rules::LayoutRules,
+        },
+        graph_structure::{
+            graph_structure::DrawTag,
+            grouped_graph_structure::{EdgeCountData, EdgeData, GroupedGraphStructure},
+        },
+    },
+    util::{
+        clock::now,
+        logging::console,
+        point::Point,
+        rectangle::Rectangle,
+        transition::{Interpolatable, Transition},
+        viz_error::VizError,
+    },
+};
+
+use super::{
+    layered_layout_traits::{LayerGroupSorting, LayerOrdering, NodePositioning, WidthLabel},
+    util::{
+        compute_layers_layout::compute_layers_layout,
+        hide_overlapping_labels::hide_overlapping_labels,
+        layered::layer_orderer::{get_sequence, EdgeLayoutData, EdgeMap, Order},
+        remove_redundant_bendpoints::remove_redundant_bendpoints,
+    },
+};
+pub struct LayeredLayout<
+    G: GroupedGraphStructure,
+    O: LayerOrdering<G>,
+    GS: LayerGroupSorting<G>,
+    P: NodePositioning<G>,
+> {
+    ordering: O,
+    group_aligning: GS,
+    positioning: P,
+    max_curve_offset: f32,
+    group_edge_data: EdgeLayoutData,
+    /// Whether to weigh edges by the number of edges they represent during ordering
+    weighted_edges: bool,
+    label_placement: LabelPlacement,
+    /// Whether to hide labels that overlap other labels or nodes
+    hide_overlapping_labels: bool,
+    /// Whether runs of consecutive levels without any groups are collapsed into a single thin layer
+    elide_empty_layers: bool,
+    /// The nodes whose groups are kept in this relative order from left to right within their layer
+    root_order: Vec<NodeID>,
+    phase_durations: LayoutPhaseDurations,
+    graph: PhantomData<G>,
+}
+
+/// The time in milliseconds spent in each phase of the most recent layout, such that the phases can be benchmarked separately
+#[derive(Clone, Copy, Default)]
+pub struct LayoutPhaseDurations {
+    /// Adding the groups and edges to the layers, including the dummy nodes for groups and edges spanning multiple layers
+    pub setup: f64,
+    pub ordering: f64,
+    /// Sorting the groups spanning multiple layers, such that they do not cross
+    pub aligning: f64,
+    pub positioning: f64,
+    /// Turning the positions into the layout of the groups, edges and layers
+    pub formatting: f64,
+}
+
+impl<
+        G: GroupedGraphStructure,
+        O: LayerOrdering<G>,
+        GS: LayerGroupSorting<G>,
+        P: NodePositioning<G>,
+    > LayeredLayout<G, O, GS, P>
+{
+    pub fn new(ordering: O, group_aligning: GS, positioning: P, max_curve_offset: f32) -> Self {
+        LayeredLayout {
+            ordering,
+            group_aligning,
+            positioning,
+            max_curve_offset,
+            weighted_edges: false,
+            label_placement: LabelPlacement::Inside,
+            hide_overlapping_labels: false,
+            elide_empty_layers: false,
+            root_order: Vec::new(),
+            phase_durations: LayoutPhaseDurations::default(),
+            graph: PhantomData,
+            group_edge_data: EdgeLayoutData {
+                weight: 1000,
+                order: -1,
+            }, // TODO: make configurable
+        }
+    }
+
+    pub fn get_ordering(&mut self) -> &mut O {
+        &mut self.ordering
+    }
+    pub fn get_group_aligning(&mut self) -> &mut GS {
+        &mut self.group_aligning
+    }
+    pub fn get_positioning_aligning(&mut self) -> &mut P {
+        &mut self.positioning
+    }
+    pub fn set_weighted_edges(&mut self, weighted: bool) {
+        self.weighted_edges = weighted;
+    }
+    pub fn set_label_placement(&mut self, placement: LabelPlacement) {
+        self.label_placement = placement;
+    }
+    pub fn set_hide_overlapping_labels(&mut self, hide: bool) {
+        self.hide_overlapping_labels = hide;
+    }
+    pub fn set_elide_empty_layers(&mut self, elide: bool) {
+        self.elide_empty_layers = elide;
+    }
+    pub fn set_root_order(&mut self, nodes: Vec<NodeID>) {
+        self.root_order = nodes;
+    }
+    pub fn get_phase_durations(&self) -> LayoutPhaseDurations {
+        self.phase_durations
+    }
+}
+
+pub fn is_group_dummy(
+    node: NodeGroupID,
+    dummy_group_start_id: NodeGroupID,
+    dummy_edge_start_id: NodeGroupID,
+) -> bool {
+    node >= dummy_group_start_id && node < dummy_edge_start_id
+}
+pub fn is_edge_dummy(node: NodeGroupID, dummy_edge_start_id: NodeGroupID) -> bool {
+    node >= dummy_edge_start_id
+}
+
+/// The size of a node, where 1 is the unit size
+const NODE_SIZE: f32 = 1.; // TODO: make configurable
+
+/// Retrieves the horizontal position that a node positioning assigned to a group, given the horizontal position of the group in the resulting layout
+pub fn get_positioning_x(layout_x: f32) -> f32 {
+    layout_x + 0.5 * NODE_SIZE
+}
+
+impl<
+        G: GroupedGraphStructure,
+        O: LayerOrdering<G>,
+        S: LayerGroupSorting<G>,
+        P: NodePositioning<G>,
+    > LayoutRules for LayeredLayout<G, O, S, P>
+where
+    G::GL: NodeStyle + WidthLabel,
+    G::LL: LayerStyle,
+{
+    type T = G::T;
+    type NS = G::GL;
+    type LS = G::LL;
+    type Tracker = G::Tracker;
+    type G = G;
+
+    fn layout(
+        &mut self,
+        graph: &G,
+        old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
+        sources: &G::Tracker,
+        time: u32,
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
+        let start = now();
+
+        // Setup the layers and edges, and a way of adding o them
+        let mut layers: Vec<Order> = Vec::new();
+        let mut edges: EdgeMap = HashMap::new();
+
+        let mut dummy_owners: HashMap<NodeGroupID, NodeGroupID> = HashMap::new();
+        let mut next_free_id = 0; // uninitialized, will be initialized by add_groups
+
+        let (dummy_group_start_id, group_layers) = add_groups_with_dummies(
+            graph,
+            &mut layers,
+            &mut edges,
+            self.group_edge_data,
+            &mut dummy_owners,
+            &mut next_free_id,
+        );
+        let dummy_edge_start_id = next_free_id;
+
+        let (edge_bend_nodes, edge_connection_nodes) = add_edges_with_dummies(
+            graph,
+            &mut layers,
+            &mut edges,
+            &mut dummy_owners,
+            &group_layers,
+            &mut next_free_id,
+            self.weighted_edges,
+        );
+
+        let node_widths = &layers
+            .iter()
+            .flatten()
+            .map(|(&node, _)| {
+                (
+                    node,
+                    get_node_width(
+                        node,
+                        graph,
+                        &dummy_owners,
+                        dummy_group_start_id,
+                        self.label_placement,
+                    ),
+                )
+            })
+            .collect();
+
+        let ordering_start = now();
+
+        // Perform node positioning, where the pinned root order is used both as starting point and as constraint
+        let layers = apply_root_order(graph, layers, &self.root_order);
+        let layers = self.ordering.order_nodes(
+            graph,
+            &layers,
+            &edges,
+            dummy_group_start_id,
+            dummy_edge_start_id,
+            &dummy_owners,
+        );
+        let layers = apply_root_order(graph, layers, &self.root_order);
+        let aligning_start = now();
+
+        // Sort the groupings, such that they never cross each-other, and remove other edges that cross groups
+        let layers = self.group_aligning.align_cross_layer_nodes(
+            graph,
+            &layers,
+            &edges,
+            dummy_group_start_id,
+            dummy_edge_start_id,
+            &dummy_owners,
+        );
+        remove_group_crossings(&layers, &mut edges, &dummy_owners);
+        let positioning_start = now();
+
+        // Perform node-positioning
+        let (mut node_positions, mut layer_positions) = self.positioning.position_nodes(
+            graph,
+            &layers,
+            &edges,
+            &node_widths,
+            dummy_group_start_id,
+            dummy_edge_start_id,
+            &dummy_owners,
+        );
+
+        let elided_levels = if self.elide_empty_layers {
+            elide_empty_levels(
+                graph,
+                &layers,
+                dummy_group_start_id,
+                &mut node_positions,
+                &mut layer_positions,
+            )
+        } else {
+            HashSet::new()
+        };
+
+        let formatting_start = now();
+
+        let layout = format_layout(
+            graph,
+            self.max_curve_offset,
+            node_positions,
+            &node_widths,
+            layer_positions,
+            edge_bend_nodes,
+            edge_connection_nodes,
+            dummy_group_start_id,
+            self.label_placement,
+            self.hide_overlapping_labels,
+            &elided_levels,
+        )?;
+        self.phase_durations = LayoutPhaseDurations {
+            setup: ordering_start - start,
+            ordering: aligning_start - ordering_start,
+            aligning: positioning_start - aligning_start,
+            positioning: formatting_start - positioning_start,
+            formatting: now() - formatting_start,
+        };
+        Ok(layout)
+    }
+}
+
+fn get_node_width<G: GroupedGraphStructure>(
+    node: NodeGroupID,
+    graph: &G,
+    owners: &HashMap<NodeGroupID, NodeGroupID>,
+    dummy_group_start_id: NodeGroupID,
+    label_placement: LabelPlacement,
+) -> f32
+where
+    G::GL: WidthLabel,
+{
+    let owner = owners.get(&node).cloned().unwrap_or(node);
+    if owner < dummy_group_start_id {
+        get_label_width(&graph.get_group_label(owner), label_placement)
+    } else {
+        0.
+    }
+}
+
+/// Retrieves the width of a node, which includes its text label if this is placed inside of the node
+fn get_label_width<L: WidthLabel>(label: &L, label_placement: LabelPlacement) -> f32 {
+    match (label_placement, label.get_label_size()) {
+        (LabelPlacement::Inside, Some(label_size)) => label.get_width() + label_size.x,
+        _ => label.get_width(),
+    }
+}
+
+/// Retrieves the offset of the center of the text label of a node relative to the center of the node
+fn get_label_offset<L: WidthLabel>(
+    label: &L,
+    node_size: Point,
+    label_placement: LabelPlacement,
+) -> Point {
+    let spacing = 0.1; // TODO: make configurable
+    let label_height = label.get_label_size().map(|size| size.y).unwrap_or(0.);
+    let y = 0.5 * (node_size.y + label_height) + spacing;
+    match label_placement {
+        LabelPlacement::Inside => Point { x: 0., y: 0. },
+        LabelPlacement::Above => Point { x: 0., y },
+        LabelPlacement::Below => Point { x: 0., y: -y },
+    }
+}
+
+/// Reorders the groups of the given nodes within every layer to match the order of the nodes, while the other groups keep their positions
+fn apply_root_order<G: GroupedGraphStructure>(
+    graph: &G,
+    layers: Vec<Order>,
+    root_order: &[NodeID],
+) -> Vec<Order> {
+    if root_order.is_empty() {
+        return layers;
+    }
+    let mut ranks = HashMap::<NodeGroupID, usize>::new();
+    for (rank, &node) in root_order.iter().enumerate() {
+        ranks.entry(graph.get_group(node)).or_insert(rank);
+    }
+    layers
+        .into_iter()
+        .map(|layer| {
+            let mut sequence = get_sequence(&layer);
+            let positions = (0..sequence.len())
+                .filter(|&index| ranks.contains_key(&sequence[index]))
+                .collect_vec();
+            let ordered = positions
+                .iter()
+                .map(|&index| sequence[index])
+                .sorted_by_key(|group| ranks[group])
+                .collect_vec();
+            for (index, group) in positions.into_iter().zip(ordered) {
+                sequence[index] = group;
+            }
+            sequence
+                .into_iter()
+                .enumerate()
+                .map(|(index, group)| (group, index))
+                .collect()
+        })
+        .collect()
+}
+
+fn add_to_layer(layers: &mut Vec<Order>, layer: usize, id: NodeGroupID) {
+    while layer >= layers.len() {
+        layers.push(HashMap::new());
+    }
+    let layer = &mut layers[layer];
+    layer.insert(id, layer.len());
+}
+
+fn add_to_edges(edges: &mut EdgeMap, from: NodeGroupID, to: NodeGroupID, data: EdgeLayoutData) {
+    edges
+        .entry(from)
+        .or_insert_with(|| HashMap::new())
+        .insert(to, data);
+}
+
+fn add_groups_with_dummies<G: GroupedGraphStructure>(
+    graph: &G,
+    layers: &mut Vec<Order>,
+    edges: &mut EdgeMap,
+    group_edge_data: EdgeLayoutData,
+    dummy_owners: &mut HashMap<NodeGroupID, NodeGroupID>,
+    next_free_id: &mut NodeGroupID,
+) -> (NodeGroupID, HashMap<NodeGroupID, HashMap<u32, usize>>)
+where
+    G::GL: NodeStyle,
+    G::LL: LayerStyle,
+{
+    let mut group_layers: HashMap<NodeGroupID, HashMap<u32, usize>> = HashMap::new();
+    for group in graph.get_all_groups() {
+        let (start, _end) = graph.get_level_range(group);
+        add_to_layer(layers, start as usize, group);
+        group_layers.insert(group, HashMap::from([(start, group)]));
+        if group >= *next_free_id {
+            *next_free_id = group + 1;
+        }
+    }
+    let dummy_group_start_id = *next_free_id;
+
+    for group in graph.get_all_groups() {
+        let (start, end) = graph.get_level_range(group);
+        dummy_owners.insert(group, group);
+        let mut prev = group;
+        for layer in start + 1..=end {
+            let layer_group_id = *next_free_id;
+            *next_free_id += 1;
+            add_to_edges(edges, prev, layer_group_id, group_edge_data);
+            dummy_owners.insert(layer_group_id, group);
+            add_to_layer(layers, layer as usize, layer_group_id);
+            group_layers
+                .entry(group)
+                .or_default()
+                .insert(layer, layer_group_id);
+            prev = layer_group_id;
+        }
+    }
+
+    (dummy_group_start_id, group_layers)
+}
+
+fn add_edges_with_dummies<G: GroupedGraphStructure>(
+    graph: &G,
+    layers: &mut Vec<Order>,
+    edges: &mut EdgeMap,
+    dummy_owners: &mut HashMap<NodeGroupID, NodeGroupID>,
+    group_layers: &HashMap<NodeGroupID, HashMap<u32, usize>>,
+    next_free_id: &mut NodeGroupID,
+    weighted: bool,
+) -> (
+    HashMap<(NodeGroupID, EdgeData<G::T>), Vec<NodeGroupID>>,
+    HashMap<(NodeGroupID, EdgeData<G::T>), (NodeGroupID, NodeGroupID)>,
+)
+where
+    G::GL: NodeStyle,
+    G::LL: LayerStyle,
+{
+    let mut edge_bend_nodes: HashMap<(NodeGroupID, EdgeData<G::T>), Vec<NodeGroupID>> =
+        HashMap::new();
+    let mut edge_connection_nodes: HashMap<
+        (NodeGroupID, EdgeData<G::T>),
+        (NodeGroupID, NodeGroupID),
+    > = HashMap::new();
+
+    for group in graph.get_all_groups() {
+        // let (parent_start_level, parent_end_level) = graph.get_level_range(group);
+
+        for EdgeCountData {
+            to: to_group,
+            from_level: edge_start_level,
+            to_level: edge_end_level,
+            edge_type,
+            count,
+        } in graph.get_children(group)
+        {
+            let weight = if weighted { count } else { 1 };
+            let edge_data = EdgeData::new(to_group, edge_start_level, edge_end_level, edge_type);
+
+            let Some(group_connections) = group_layers.get(&group) else {
+                continue;
+            };
+            let Some(group_connection) = group_connections.get(&edge_start_level) else {
+                continue;
+            };
+
+            let mut prev = *group_connection;
+            let mut bends = Vec::new();
+            let first_bend_id = *next_free_id;
+
+            for layer in (edge_start_level + 1)..edge_end_level {
+                let id = *next_free_id;
+                *next_free_id += 1;
+                dummy_owners.insert(id, first_bend_id);
+                bends.push(id);
+                add_to_layer(layers, layer as usize, id);
+                add_to_edges(
+                    edges,
+                    prev,
+                    id,
+                    EdgeLayoutData {
+                        weight,
+                        order: edge_type.index,
+                    },
+                );
+                prev = id;
+            }
+            edge_bend_nodes.insert((group, edge_data.clone()), bends);
+
+            let Some(to_group_connections) = group_layers.get(&to_group) else {
+                console::log_warn!(
+                    "Non existent target group: {};{} -> {};{}",
+                    group,
+                    edge_start_level,
+                    to_group,
+                    edge_end_level
+                );
+                continue;
+            };
+            let Some(&to_group_connection) = to_group_connections.get(&edge_end_level) else {
+                console::log_warn!(
+                    "Non existent target level: {};{} -> {};{}",
+                    group,
+                    edge_start_level,
+                    to_group,
+                    edge_end_level
+                );
+                continue;
+            };
+            edge_connection_nodes
+                .insert((group, edge_data), (*group_connection, to_group_connection));
+            add_to_edges(
+                edges,
+                prev,
+                to_group_connection,
+                EdgeLayoutData {
+                    weight,
+                    order: edge_type.index,
+                },
+            );
+        }
+    }
+
+    (edge_bend_nodes, edge_connection_nodes)
+}
+
+fn remove_group_crossings(
+    layers: &Vec<Order>,
+    edges: &mut EdgeMap,
+    dummy_owners: &HashMap<NodeGroupID, NodeGroupID>,
+) {
+    if layers.len() == 0 {
+        return;
+    }
+
+    let layer_order = layers.iter().map(get_sequence).collect_vec();
+    let all_layer_group_indices = layers
+        .iter()
+        .map(|l| {
+            l.iter()
+                .filter_map(|(node, index)| dummy_owners.get(node).map(|owner| (*owner, *index)))
+                .collect::<HashMap<_, _>>()
+        })
+        .collect_vec();
+
+    for i in 0..(layers.len() - 1) {
+        let layer = &layer_order[i];
+        let next_layer = &layers[i + 1];
+
+        let next_layer_groups = &all_layer_group_indices[i + 1];
+        let mut shared_layer_groups = all_layer_group_indices[i]
+            .iter()
+            .filter_map(|(group, from_index)| {
+                next_layer_groups
+                    .get(group)
+                    .map(|to_index| (*group, *from_index, *to_index))
+            })
+            .sorted_by_key(|(_, from_index, _)| *from_index)
+            .collect_vec();
+
+        // Remove left to right downwards crossings
+        let mut node_index = 0;
+        for &(_group, from_index, to_index) in &shared_layer_groups {
+            // For each node to the left of from_index, remove any edges to the right of to_index (keep everything that's to the left of to_index)
+            while node_index < from_index {
+                let node = layer[node_index];
+                if let Some(node_edges) = edges.get_mut(&node) {
+                    node_edges.retain(|to_node, _| {
+                        next_layer
+                            .get(to_node)
+                            .map(|&index| index <= to_index)
+                            .unwrap_or(false)
+                    });
+                }
+                node_index += 1;
+            }
+        }
+
+        // Remove right to left downwards crossings
+        shared_layer_groups.reverse();
+        if layer.len() == 0 {
+            continue;
+        }
+        node_index = layer.len() - 1;
+        for &(_group, from_index, to_index) in &shared_layer_groups {
+            while node_index > from_index {
+                let node = layer[node_index];
+                if let Some(node_edges) = edges.get_mut(&node) {
+                    node_edges.retain(|to_node, _| {
+                        next_layer
+                            .get(to_node)
+                            .map(|&index| index >= to_index)
+                            .unwrap_or(false)
+                    });
+                }
+
+                node_index -= 1;
+            }
+        }
+    }
+}
+
+/// The height of the band that a run of elided levels is collapsed into, relative to the average space between the levels of the run
+const ELIDED_GAP: f32 = 0.5;
+
+/// Collapses every run of at least two consecutive levels that contain no groups, and that lies between levels that do, into a band whose height is a fraction of the original distance. The levels below the run move up accordingly, along with their nodes and the bend points of their edges. Returns the elided levels.
+fn elide_empty_levels<G: GroupedGraphStructure>(
+    graph: &G,
+    layers: &Vec<Order>,
+    dummy_group_start_id: NodeGroupID,
+    node_positions: &mut HashMap<NodeGroupID, Point>,
+    layer_positions: &mut HashMap<LevelNo, f32>,
+) -> HashSet<LevelNo> {
+    let covered: HashSet<LevelNo> = layers
+        .iter()
+        .flat_map(|order| order.keys())
+        .filter(|&&id| id < dummy_group_start_id)
+        .flat_map(|&id| {
+            let (start, end) = graph.get_level_range(id);
+            start..=end
+        })
+        .collect();
+    let levels = layer_positions.keys().cloned().sorted().collect_vec();
+
+    let mut elided = HashSet::new();
+    let mut shifts = HashMap::new();
+    let mut shift = 0.;
+    let mut run: Vec<LevelNo> = Vec::new();
+    let mut prev_covered: Option<LevelNo> = None;
+    for &level in &levels {
+        if !covered.contains(&level) {
+            if prev_covered.is_some() {
+                run.push(level);
+            } else {
+                shifts.insert(level, shift);
+            }
+            continue;
+        }
+
+        match prev_covered {
+            Some(above) if run.len() >= 2 => {
+                // The run is spread evenly over a band halfway between the surrounding levels
+                let top = layer_positions[&above];
+                let span = top - layer_positions[&level];
+                let spacing = span / (run.len() + 1) as f32;
+                let band = ELIDED_GAP * spacing;
+                for (index, &elided_level) in run.iter().enumerate() {
+                    let offset = 0.5 * spacing + band * (index as f32 + 0.5) / run.len() as f32;
+                    let y = top + shift - offset;
+                    shifts.insert(elided_level, y - layer_positions[&elided_level]);
+                    elided.insert(elided_level);
+                }
+                shift += span - (1. + ELIDED_GAP) * spacing;
+            }
+            _ => {
+                for &run_level in &run {
+                    shifts.insert(run_level, shift);
+                }
+            }
+        }
+        run.clear();
+        shifts.insert(level, shift);
+        prev_covered = Some(level);
+    }
+    for &run_level in &run {
+        shifts.insert(run_level, shift);
+    }
+
+    if elided.is_empty() {
+        return elided;
+    }
+    for (level, y) in layer_positions.iter_mut() {
+        *y += shifts.get(level).cloned().unwrap_or(0.);
+    }
+    for (level, order) in layers.iter().enumerate() {
+        let Some(&level_shift) = shifts.get(&(level as LevelNo)) else {
+            continue;
+        };
+        for node in order.keys() {
+            if let Some(position) = node_positions.get_mut(node) {
+                position.y += level_shift;
+            }
+        }
+    }
+    elided
+}
+
+fn format_layout<G: GroupedGraphStructure>(
+    graph: &G,
+    max_curve_offset: f32,
+    node_positions: HashMap<usize, Point>,
+    node_widths: &HashMap<NodeGroupID, f32>,
+    layer_positions: HashMap<LevelNo, f32>,
+    edge_bend_nodes: HashMap<(NodeGroupID, EdgeData<G::T>), Vec<NodeGroupID>>,
+    edge_connection_nodes: HashMap<(NodeGroupID, EdgeData<G::T>), (NodeGroupID, NodeGroupID)>,
+    dummy_group_start_id: usize,
+    label_placement: LabelPlacement,
+    hide_overlapping: bool,
+    elided_levels: &HashSet<LevelNo>,
+) -> Result<DiagramLayout<G::T, G::GL, G::LL>, VizError>
+where
+    G::GL: NodeStyle + WidthLabel,
+    G::LL: LayerStyle,
+{
+    let node_size = NODE_SIZE;
+    let node_size_shift = -0.5
+        * Point {
+            x: node_size,
+            y: node_size,
+        };
+    let node_positions: HashMap<usize, Point> = node_positions
+        .iter()
+        .map(|(&group_id, &pos)| (group_id, pos + node_size_shift))
+        .collect();
+    let bottom_node_positions: HashMap<usize, Point> = node_positions
+        .iter()
+        .map(|(&group_id, pos)| {
+            (
+                group_id,
+                (if group_id >= dummy_group_start_id {
+                    *pos
+                } else {
+                    let (s, e) = graph.get_level_range(group_id);
+                    Point {
+                        x: pos.x,
+                        y: pos.y
+                            - (layer_positions.get(&s).unwrap_or(&0.)
+                                - layer_positions.get(&e).unwrap_or(&0.)),
+                    }
+                }),
+            )
+        })
+        .collect();
+
+    // Map to a diagram layout
+    let layers = compute_layers_layout(
+        graph,
+        node_positions
+            .iter()
+            .filter(|(&group_id, _)| group_id < dummy_group_start_id)
+            .map(|(&group_id, pos)| {
+                let (s, e) = graph.get_level_range(group_id);
+
+                let start_layer_y = layer_positions.get(&s).unwrap_or(&0.);
+                let prev_layer_y = (if s > 0 {
+                    layer_positions.get(&(s - 1)).cloned()
+                } else {
+                    None
+                })
+                .unwrap_or(start_layer_y + 2. * node_size);
+                let start_y = (start_layer_y + prev_layer_y) / 2.0;
+
+                let end_layer_y = *layer_positions.get(&e).unwrap_or(&0.);
+                let next_layer_y = layer_positions
+                    .get(&(e + 1))
+                    .cloned()
+                    .unwrap_or(end_layer_y - 2. * node_size);
+                let end_y = (end_layer_y + next_layer_y) / 2.0;
+                (group_id, Rectangle::new(0., end_y, 0., start_y - end_y))
+            }),
+        elided_levels,
+    );
+    let mut groups: HashMap<NodeGroupID, NodeGroupLayout<G::T, G::GL>> = graph
+        .get_all_groups()
+        .iter()
+        .map(|&group_id| {
+            let (s, e) = graph.get_level_range(group_id);
+            let label = graph.get_group_label(group_id);
+            let Some(&position) = bottom_node_positions.get(&group_id) else {
+                return Err(VizError::Internal(format!(
+                    "The group {} was not positioned",
+                    group_id
+                )));
+            };
+            let size = Point {
+                x: get_label_width(&label, label_placement),
+                y: node_size
+                    + (layer_positions.get(&s).unwrap_or(&0.)
+                        - layer_positions.get(&e).unwrap_or(&0.))
+                        * node_size,
+            };
+            Ok((
+                group_id,
+                NodeGroupLayout {
+                    label_offset: Transition::plain(get_label_offset(
+                        &label,
+                        size,
+                        label_placement,
+                    )),
+                    label_exists: Transition::plain(1.),
+                    style: Transition::plain(label),
+                    position: Transition::plain(position),
+                    size: Transition::plain(size),
+                    level_range: (s, e),
+                    exists: Transition::plain(1.),
+                    edges: graph
+                        .get_children(group_id)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, ed)| {
+                            (
+                                (
+                                    ed.to,
+                                    ed.from_level,
+                                    ed.to_level,
+                                    // An extra value such that grouping only occurs if the level delta is 1
+                                    if (ed.to_level as i32) - (ed.from_level as i32) == 1 {
+                                        // (In a correct graph, this cannot be negative, but handy to not throw-errors when visually debugging wrong graphs)
+                                        0
+                                    } else {
+                                        index
+                                    },
+                                ),
+                                ed,
+                            )
+                        })
+                        .sorted_by_key(|(g, _ed)| *g)
+                        .group_by(|(g, _ed)| *g)
+                        .into_iter()
+                        .flat_map(|(_g, edge_datas)| {
+                            let edge_datas = edge_datas.map(|(_g, ed)| ed).sorted().collect_vec();
+                            let len = edge_datas.len();
+                            edge_datas
+                                .iter()
+                                .enumerate()
+                                .map(|(index, edge_data)| {
+                                    Ok::<_, VizError>((
+                                        edge_data.drop_count(),
+                                        format_edge(
+                                            &edge_data,
+                                            if len > 1 {
+                                                ((index as f32 / (len - 1) as f32) - 0.5)
+                                                    * 2.0
+                                                    * max_curve_offset
+                                            } else {
+                                                0.
+                                            },
+                                            group_id,
+                                            &node_positions,
+                                            &bottom_node_positions,
+                                            &edge_bend_nodes,
+                                            &edge_connection_nodes,
+                                            node_size,
+                                        )?,
+                                    ))
+                                })
+                                .collect_vec()
+                        })
+                        .collect::<Result<_, VizError>>()?,
+                },
+            ))
+        })
+        .collect::<Result<_, VizError>>()?;
+
+    if hide_overlapping {
+        // Labels of nodes higher up and further to the left take priority
+        let labels = groups
+            .iter()
+            .filter_map(|(&group_id, group)| {
+                let label_size = group.style.new.get_label_size()?;
+                let center = group.position.new
+                    + Point {
+                        x: 0.,
+                        y: 0.5 * group.size.new.y,
+                    }
+                    + group.label_offset.new;
+                Some((
+                    group_id,
+                    Rectangle::new(
+                        center.x - 0.5 * label_size.x,
+                        center.y - 0.5 * label_size.y,
+                        label_size.x,
+                        label_size.y,
+                    ),
+                ))
+            })
+            .sorted_by(|(id1, rect1), (id2, rect2)| {
+                rect2
+                    .y
+                    .total_cmp(&rect1.y)
+                    .then(rect1.x.total_cmp(&rect2.x))
+                    .then(id1.cmp(id2))
+            })
+            .collect_vec();
+        let nodes = groups
+            .iter()
+            .map(|(&group_id, group)| (group_id, group.get_rect(None)))
+            .collect();
+        for group_id in hide_overlapping_labels(&labels, &nodes) {
+            if let Some(group) = groups.get_mut(&group_id) {
+                group.label_exists = Transition::plain(0.);
+            }
+        }
+    }
+
+    Ok(DiagramLayout { layers, groups })
+}
+
+fn format_edge<T: DrawTag>(
+    edge: &EdgeCountData<T>,
+    curve_offset: f32,
+    group_id: NodeGroupID,
+    node_positions: &HashMap<usize, Point>,
+    bottom_node_positions: &HashMap<usize, Point>,
+    edge_bend_nodes: &HashMap<(NodeGroupID, EdgeData<T>), Vec<NodeGroupID>>,
+    edge_connection_nodes: &HashMap<(NodeGroupID, EdgeData<T>), (NodeGroupID, NodeGroupID)>,
+    node_size: f32,
+) -> Result<EdgeLayout, VizError> {
+    let EdgeCountData {
+        to,
+        from_level,
+        to_level,
+        edge_type: _,
+        count: _,
+    } = edge;
+    let edge_data = edge.drop_count();
+
+    let (start_pos, end_pos) = edge_connection_nodes
+        .get(&(group_id, edge_data.clone()))
+        .map_or_else(
+            || (None, None),
+            |(start_id, end_id)| {
+                (
+                    node_positions.get(&start_id).cloned(),
+                    node_positions.get(&end_id).cloned(),
+                )
+            },
+        );
+
+    let start_offset = start_pos
+        .and_then(|start_point| {
+            bottom_node_positions
+                .get(&group_id)
+                .map(|base_point| start_point - *base_point)
+        })
+        .unwrap_or_default();
+
+    let end_offset = end_pos
+        .and_then(|end_point| {
+            bottom_node_positions
+                .get(&to)
+                .map(|base_point| end_point - *base_point)
+        })
+        .unwrap_or_default();
+
+    let edge_center_offset = Point {
+        x: 0.,
+        y: node_size,
+    } * 0.5;
+
+    let bend_nodes = edge_bend_nodes
+        .get(&(group_id, edge_data))
+        .map_or(&[][..], |nodes| &nodes[..]);
+    let bend_points = bend_nodes
+        .iter()
+        .map(|dummy_id| {
+            node_positions
+                .get(dummy_id)
+                .map(|&point| point + edge_center_offset)
+                .ok_or_else(|| {
+                    VizError::Internal(format!("The bend point {} was not positioned", dummy_id))
+                })
+        })
+        .collect::<Result<Vec<_>, VizError>>()?;
+
+    // // We can consider the start/end points when reducing, but this can cause nasty animations when bend points are introduced in the first layers
+    // let all_bend_points = (Some(start_pos.unwrap_or_default() + edge_center_offset))
+    //     .into_iter()
+    //     .chain(bend_points)
+    //     .chain(Some(end_pos.unwrap_or_default() + edge_center_offset));
+    // let reduced_points = remove_redundant_bendpoints(&all_bend_points.collect());
+    // let reduced_bend_points = reduced_points[1..reduced_points.len() - 1];
+    let reduced_bend_points = remove_redundant_bendpoints(&bend_points);
+
+    Ok(EdgeLayout {
+        start_offset: Transition::plain(start_offset + edge_center_offset),
+        end_offset: Transition::plain(end_offset + edge_center_offset),
+        points: reduced_bend_points
+            .iter()
+            .map(|&point| EdgePoint {
+                point: Transition::plain(point),
+                exists: Transition::plain(1.),
+            })
+            .collect(),
+        exists: Transition::plain(1.),
+        curve_offset: Transition::plain(curve_offset),
+    })
+}
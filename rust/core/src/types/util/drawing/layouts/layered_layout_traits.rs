@@ -3,11 +3,11 @@ use std::collections::HashMap;
 use oxidd::LevelNo;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::graph_structure::{
         graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
     },
     util::point::Point,
-    wasm_interface::NodeGroupID,
 };
 
 use super::util::layered::layer_orderer::{EdgeMap, Order};
@@ -64,5 +64,10 @@ pub trait LayerGroupSorting<G: GroupedGraphStructure> {
 
 /// A trait for node tags to specify the width of the node, where 1 is the unit size
 pub trait WidthLabel {
+    /// The width of the node, excluding its text label
     fn get_width(&self) -> f32;
+    /// The size of the text label of the node, if it has any
+    fn get_label_size(&self) -> Option<Point> {
+        None
+    }
 }
@@ -2,12 +2,12 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use itertools::Itertools;
-use js_sys::Math::random;
 use oxidd::{Edge, Function, InnerNode, Manager};
 use oxidd_core::{DiagramRules, Tag};
 
 use crate::types::util::drawing::diagram_layout::{LayerStyle, NodeStyle};
 use crate::util::point::Point;
+use crate::util::random::random;
 use crate::util::transition::Interpolatable;
 use crate::{
     types::util::{
@@ -19,7 +19,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::transition::Transition,
+    util::{transition::Transition, viz_error::VizError},
 };
 
 pub struct RandomTestLayout<G: GroupedGraphStructure>(PhantomData<G>);
@@ -46,9 +46,9 @@ where
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &G::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
         let groups = graph.get_all_groups();
-        DiagramLayout {
+        Ok(DiagramLayout {
             groups: groups
                 .iter()
                 .map(|&group_id| {
@@ -78,6 +78,8 @@ where
                                 duration: 1000,
                             },
                             exists: Transition::plain(1.),
+                            label_offset: Transition::plain(Point { x: 0., y: 0. }),
+                            label_exists: Transition::plain(1.),
                             edges: graph
                                 .get_children(group_id)
                                 .into_iter()
@@ -112,6 +114,6 @@ where
                 })
                 .collect(),
             layers: Vec::new(),
-        }
+        })
     }
 }
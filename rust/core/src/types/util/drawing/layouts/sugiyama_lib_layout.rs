@@ -2,6 +2,7 @@ use oxidd::LevelNo;
 use oxidd_core::Tag;
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::{
             diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
@@ -11,8 +12,9 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::{logging::console, point::Point, transition::Interpolatable},
-    wasm_interface::NodeGroupID,
+    util::{
+        logging::console, point::Point, transition::Interpolatable, viz_error::VizError,
+    },
 };
 use rust_sugiyama::from_edges;
 use std::{collections::HashMap, convert::TryInto};
@@ -65,7 +67,7 @@ where
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &G::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
         self.layout.layout(graph, old, sources, time)
     }
 }
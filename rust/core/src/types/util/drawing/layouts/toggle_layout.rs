@@ -12,7 +12,7 @@ use crate::{
             graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
         },
     },
-    util::transition::Interpolatable,
+    util::{transition::Interpolatable, viz_error::VizError},
 };
 
 ///
@@ -105,7 +105,7 @@ impl<L: LayoutRules> LayoutRules for ToggleLayoutUnit<L> {
         /* Sources for new nodes that did not yet exist in the previous layout iteration */
         new_sources: &Self::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
         self.layout.layout(graph, old, new_sources, time)
     }
 }
@@ -126,7 +126,7 @@ impl<
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         new_sources: &Self::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
         if self.is_layout_one_selected() {
             self.layout1.layout(graph, old, new_sources, time)
         } else {
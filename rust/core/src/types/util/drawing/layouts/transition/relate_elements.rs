@@ -11,6 +11,7 @@ use oxidd::{Edge, Function, InnerNode, LevelNo, Manager};
 use oxidd_core::{DiagramRules, Tag};
 
 use crate::{
+    interface::NodeGroupID,
     types::util::{
         drawing::{
             diagram_layout::{
@@ -25,7 +26,6 @@ use crate::{
         },
     },
     util::{logging::console, point::Point},
-    wasm_interface::NodeGroupID,
 };
 
 pub struct ElementRelations<T: DrawTag> {
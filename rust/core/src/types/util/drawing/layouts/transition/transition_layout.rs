@@ -11,6 +11,7 @@ use oxidd::{Edge, Function, InnerNode, LevelNo, Manager};
 use oxidd_core::{DiagramRules, Tag};
 
 use crate::{
+    interface::{AppearanceAnimation, NodeGroupID},
     types::util::{
         drawing::{
             diagram_layout::{
@@ -24,8 +25,7 @@ use crate::{
             grouped_graph_structure::{EdgeData, GroupedGraphStructure, SourceReader},
         },
     },
-    util::{logging::console, point::Point, transition::Transition},
-    wasm_interface::NodeGroupID,
+    util::{logging::console, point::Point, transition::Transition, viz_error::VizError},
 };
 
 use super::{
@@ -40,8 +40,14 @@ use super::{
 pub struct TransitionLayout<L: LayoutRules> {
     layout: L,
     durations: TransitionDurations,
+    /// The animations of groups that are inserted and removed without relating to other groups
+    entrance: AppearanceAnimation,
+    exit: AppearanceAnimation,
 }
 
+/// The vertical distance in diagram units from which groups fly in, and to which they fly out
+const FLY_DISTANCE: f32 = 5.;
+
 impl<L: LayoutRules> TransitionLayout<L> {
     pub fn new(layout: L) -> Self {
         let speed_modifier = 1; // for testing
@@ -54,11 +60,21 @@ impl<L: LayoutRules> TransitionLayout<L> {
                 transition_duration: 600 * speed_modifier,
                 delete_duration: 300 * speed_modifier,
             },
+            entrance: AppearanceAnimation::Fade,
+            exit: AppearanceAnimation::Fade,
         }
     }
     pub fn get_layout_rules(&mut self) -> &mut L {
         &mut self.layout
     }
+    pub fn set_appearance_animations(
+        &mut self,
+        entrance: AppearanceAnimation,
+        exit: AppearanceAnimation,
+    ) {
+        self.entrance = entrance;
+        self.exit = exit;
+    }
 }
 
 #[derive(Clone)]
@@ -81,10 +97,10 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
         old: &DiagramLayout<Self::T, Self::NS, Self::LS>,
         sources: &Self::Tracker,
         time: u32,
-    ) -> DiagramLayout<Self::T, Self::NS, Self::LS> {
+    ) -> Result<DiagramLayout<Self::T, Self::NS, Self::LS>, VizError> {
         let duration = self.durations.transition_duration;
         let old_time = time;
-        let new = self.layout.layout(graph, old, sources, time);
+        let new = self.layout.layout(graph, old, sources, time)?;
 
         let relations = relate_elements(graph, old, &new, sources, time);
         let ElementRelations {
@@ -150,6 +166,7 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
                         &some_updated_parents,
                         &updated_groups,
                         &self.durations,
+                        self.entrance,
                         time,
                     ),
                 )
@@ -166,6 +183,7 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
                     target_group,
                     &new,
                     &self.durations,
+                    self.exit,
                     &relations,
                     time,
                 ),
@@ -177,10 +195,10 @@ impl<L: LayoutRules> LayoutRules for TransitionLayout<L> {
             .chain(updated_groups.into_iter())
             .chain(old_groups.clone())
             .collect::<HashMap<_, _>>();
-        DiagramLayout {
+        Ok(DiagramLayout {
             groups,
             layers: transition_layers(&old.layers, &new.layers, duration, old_time, time),
-        }
+        })
     }
 }
 
@@ -235,6 +253,18 @@ fn layout_updated_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
             new: group.exists.new,
         },
         level_range: group.level_range.clone(),
+        label_offset: Transition {
+            old_time,
+            duration,
+            old: old_group.label_offset.get(time),
+            new: group.label_offset.new,
+        },
+        label_exists: Transition {
+            old_time,
+            duration,
+            old: old_group.label_exists.get(time),
+            new: group.label_exists.new,
+        },
         edges: group
             .edges
             .iter()
@@ -264,6 +294,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
     some_updated_parents: &HashMap<usize, usize>,
     updated_groups: &HashMap<usize, NodeGroupLayout<T, S>>,
     durations: &TransitionDurations,
+    entrance: AppearanceAnimation,
     time: u32,
 ) -> NodeGroupLayout<T, S> {
     let old_time = time;
@@ -313,13 +344,7 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
             ..group.clone()
         }
     } else {
-        NodeGroupLayout {
-            exists: Transition {
-                old_time,
-                duration: durations.insert_duration,
-                old: 0.,
-                new: group.exists.new,
-            },
+        let group = NodeGroupLayout {
             edges: group
                 .edges
                 .iter()
@@ -339,7 +364,8 @@ fn layout_added_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 })
                 .collect(),
             ..group.clone()
-        }
+        };
+        animate_entrance(group, entrance, old_time, durations.insert_duration)
     }
 }
 
@@ -349,6 +375,7 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
     target_data: &Option<TargetGroup>,
     new: &DiagramLayout<T, S, LS>,
     durations: &TransitionDurations,
+    exit: AppearanceAnimation,
     relations: &ElementRelations<T>,
     time: u32,
 ) -> NodeGroupLayout<T, S> {
@@ -409,15 +436,127 @@ fn layout_removed_group<T: DrawTag, S: NodeStyle, LS: LayerStyle>(
                 ..group.clone()
             }
         }
-        _ => NodeGroupLayout {
+        _ => animate_exit(
+            NodeGroupLayout {
+                edges: deleted_edges_layout,
+                ..group.clone()
+            },
+            exit,
+            old_time,
+            durations.delete_duration,
+            time,
+        ),
+    }
+}
+
+/// Animates the given group from not being shown to its state in the new layout
+fn animate_entrance<T: DrawTag, S: NodeStyle>(
+    group: NodeGroupLayout<T, S>,
+    animation: AppearanceAnimation,
+    old_time: u32,
+    duration: u32,
+) -> NodeGroupLayout<T, S> {
+    match animation {
+        AppearanceAnimation::Fade => NodeGroupLayout {
+            exists: Transition {
+                old_time,
+                duration,
+                old: 0.,
+                new: group.exists.new,
+            },
+            ..group
+        },
+        AppearanceAnimation::Grow => NodeGroupLayout {
+            // Grow from the center of the group, rather than from its bottom
+            position: Transition {
+                old_time,
+                duration,
+                old: group.position.new + Point { x: 0., y: 0.5 * group.size.new.y },
+                new: group.position.new,
+            },
+            size: Transition {
+                old_time,
+                duration,
+                old: Point::default(),
+                new: group.size.new,
+            },
+            ..group
+        },
+        AppearanceAnimation::Fly => NodeGroupLayout {
+            position: Transition {
+                old_time,
+                duration,
+                old: group.position.new + Point { x: 0., y: FLY_DISTANCE },
+                new: group.position.new,
+            },
             exists: Transition {
                 old_time,
-                duration: durations.delete_duration,
-                old: group.exists.get(time),
+                duration,
+                old: 0.,
+                new: group.exists.new,
+            },
+            ..group
+        },
+    }
+}
+
+/// Animates the given group from its current state to not being shown, after which it can be discarded
+fn animate_exit<T: DrawTag, S: NodeStyle>(
+    group: NodeGroupLayout<T, S>,
+    animation: AppearanceAnimation,
+    old_time: u32,
+    duration: u32,
+    time: u32,
+) -> NodeGroupLayout<T, S> {
+    let position = group.position.get(time);
+    let size = group.size.get(time);
+    let exists = group.exists.get(time);
+    match animation {
+        AppearanceAnimation::Fade => NodeGroupLayout {
+            exists: Transition {
+                old_time,
+                duration,
+                old: exists,
                 new: 0.,
             },
-            edges: deleted_edges_layout,
-            ..group.clone()
+            ..group
+        },
+        AppearanceAnimation::Grow => NodeGroupLayout {
+            position: Transition {
+                old_time,
+                duration,
+                old: position,
+                new: position + Point { x: 0., y: 0.5 * size.y },
+            },
+            size: Transition {
+                old_time,
+                duration,
+                old: size,
+                new: Point::default(),
+            },
+            // Only stop showing the group once it has shrunk entirely
+            exists: Transition {
+                old_time: old_time + duration,
+                duration: 0,
+                old: exists,
+                new: 0.,
+            },
+            ..group
+        },
+        AppearanceAnimation::Fly => NodeGroupLayout {
+            position: Transition {
+                old_time,
+                duration,
+                old: position,
+                new: position + Point { x: 0., y: FLY_DISTANCE },
+            },
+            exists: Transition {
+                old_time,
+                duration,
+                old: exists,
+                new: 0.,
+            },
+            ..group
         },
     }
 }
@@ -25,6 +25,7 @@ use crate::{
 pub fn compute_layers_layout<G: GroupedGraphStructure, I: Iterator<Item = (usize, Rectangle)>>(
     graph: &G,
     node_positions: I,
+    elided_levels: &HashSet<LevelNo>,
 ) -> Vec<LayerLayout<G::LL>>
 where
     G::GL: NodeStyle,
@@ -91,6 +92,7 @@ where
                     .map(|level| graph.get_level_label(level))
                     .collect_vec(),
             )),
+            elided: (start_layer..end_layer).all(|level| elided_levels.contains(&level)),
         });
         index += 1;
     }
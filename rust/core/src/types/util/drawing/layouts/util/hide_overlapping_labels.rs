@@ -0,0 +1,49 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{interface::NodeGroupID, util::rectangle::Rectangle};
+
+/// The size of the cells used to look up nearby rectangles
+const CELL_SIZE: f32 = 2.;
+
+/// Selects the labels to hide, such that none of the remaining labels overlap each other or nodes other than their own.
+/// Labels are considered in the given order, such that earlier labels take priority over later labels.
+pub fn hide_overlapping_labels(
+    labels: &Vec<(NodeGroupID, Rectangle)>,
+    nodes: &HashMap<NodeGroupID, Rectangle>,
+) -> HashSet<NodeGroupID> {
+    let cell = |v: f32| (v / CELL_SIZE).floor() as i32;
+    let cells = |rect: &Rectangle| {
+        (cell(rect.x)..=cell(rect.x + rect.width))
+            .cartesian_product(cell(rect.y)..=cell(rect.y + rect.height))
+    };
+
+    // The grid contains all obstacles, together with the node that they belong to if they are nodes
+    let mut grid: HashMap<(i32, i32), Vec<(Option<NodeGroupID>, Rectangle)>> = HashMap::new();
+    for (&node, rect) in nodes {
+        for c in cells(rect) {
+            grid.entry(c).or_default().push((Some(node), rect.clone()));
+        }
+    }
+
+    let mut hidden = HashSet::new();
+    for (node, rect) in labels {
+        let overlaps = cells(rect).any(|c| {
+            grid.get(&c).map_or(false, |obstacles| {
+                obstacles
+                    .iter()
+                    .any(|(owner, obstacle)| *owner != Some(*node) && obstacle.overlaps(rect))
+            })
+        });
+        if overlaps {
+            hidden.insert(*node);
+            continue;
+        }
+
+        for c in cells(rect) {
+            grid.entry(c).or_default().push((None, rect.clone()));
+        }
+    }
+    hidden
+}
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::{util::logging::console, wasm_interface::NodeID};
+use crate::{interface::NodeID, util::logging::console};
 
 use super::layer_orderer::{
     count_crossings, get_sequence, swap_edges, EdgeLayoutData, EdgeMap, LayerOrderer, Order,
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::{util::logging::console, wasm_interface::NodeID};
+use crate::{interface::NodeID, util::logging::console};
 
 pub trait LayerOrderer {
     ///
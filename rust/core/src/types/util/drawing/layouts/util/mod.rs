@@ -1,3 +1,4 @@
 pub mod compute_layers_layout;
+pub mod hide_overlapping_labels;
 pub mod layered;
 pub mod remove_redundant_bendpoints;
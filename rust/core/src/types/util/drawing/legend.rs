@@ -0,0 +1,183 @@
+use crate::util::{color::Color, point::Point, rectangle::Rectangle, transition::Interpolatable};
+
+/// The space between the exported region and the legend next to it
+pub const LEGEND_MARGIN: f32 = 1.;
+const ROW_HEIGHT: f32 = 1.5;
+const SYMBOL_WIDTH: f32 = 3.;
+const SYMBOL_HEIGHT: f32 = 1.;
+const TEXT_OFFSET: f32 = 0.5;
+/// An estimate of the width of a character, since the svg and latex exports can not measure text
+const CHAR_WIDTH: f32 = 0.6;
+/// The number of discrete steps in which a color scale is drawn
+const SCALE_STEPS: usize = 8;
+
+/// The visual encoding of a legend entry
+#[derive(Clone)]
+pub enum LegendSymbol {
+    /// A line with the given color, width and dash pattern
+    Edge {
+        color: Color,
+        width: f32,
+        dash_solid: f32,
+        dash_transparent: f32,
+    },
+    /// A node drawn in the given color
+    Node { color: Color },
+    /// A range of values that is mapped onto the colors between the given colors
+    Scale {
+        from: Color,
+        to: Color,
+        min: f32,
+        max: f32,
+    },
+}
+
+#[derive(Clone)]
+pub struct LegendEntry {
+    pub symbol: LegendSymbol,
+    pub meaning: String,
+}
+
+/// The mapping of the visual encodings currently in effect to their meaning
+#[derive(Clone, Default)]
+pub struct Legend {
+    pub entries: Vec<LegendEntry>,
+}
+
+/// Retrieves the entries explaining the colors of the given variables, by which nodes are outlined
+pub fn get_variable_entries(variable_colors: &[(String, Color)]) -> Vec<LegendEntry> {
+    variable_colors
+        .iter()
+        .map(|(name, color)| LegendEntry {
+            symbol: LegendSymbol::Node { color: *color },
+            meaning: format!("variable {}", name),
+        })
+        .collect()
+}
+
+/// A primitive of a laid out legend, in diagram coordinates
+#[derive(Clone)]
+pub enum LegendShape {
+    Rect { rect: Rectangle, color: Color },
+    /// A text whose left side is vertically centered at the given position
+    Text { position: Point, text: String },
+}
+
+impl Legend {
+    pub fn get_size(&self) -> Point {
+        let text_length = self
+            .entries
+            .iter()
+            .map(|entry| entry.get_text().chars().count())
+            .max()
+            .unwrap_or(0);
+        Point {
+            x: SYMBOL_WIDTH + TEXT_OFFSET + text_length as f32 * CHAR_WIDTH,
+            y: self.entries.len() as f32 * ROW_HEIGHT,
+        }
+    }
+
+    /// Lays out the legend as rows of a symbol followed by its meaning, starting at the given top left corner
+    pub fn get_shapes(&self, top_left: Point) -> Vec<LegendShape> {
+        self.entries
+            .iter()
+            .enumerate()
+            .flat_map(|(index, entry)| {
+                let center_y = top_left.y - (index as f32 + 0.5) * ROW_HEIGHT;
+                let mut shapes = entry.get_symbol_shapes(top_left.x, center_y);
+                shapes.push(LegendShape::Text {
+                    position: Point {
+                        x: top_left.x + SYMBOL_WIDTH + TEXT_OFFSET,
+                        y: center_y,
+                    },
+                    text: entry.get_text(),
+                });
+                shapes
+            })
+            .collect()
+    }
+
+    /// Places the legend to the right of the given area, and retrieves its shapes together with the area enlarged to contain the legend
+    pub fn place_next_to(&self, area: &Rectangle) -> (Vec<LegendShape>, Rectangle) {
+        let size = self.get_size();
+        let top = area.y + area.height;
+        let bottom = area.y.min(top - size.y - 2. * LEGEND_MARGIN);
+        let shapes = self.get_shapes(Point {
+            x: area.x + area.width + LEGEND_MARGIN,
+            y: top - LEGEND_MARGIN,
+        });
+        let area = Rectangle::new(
+            area.x,
+            bottom,
+            area.width + size.x + 2. * LEGEND_MARGIN,
+            top - bottom,
+        );
+        (shapes, area)
+    }
+}
+
+impl LegendEntry {
+    fn get_text(&self) -> String {
+        match self.symbol {
+            LegendSymbol::Scale { min, max, .. } => {
+                format!("{} ({} to {})", self.meaning, min, max)
+            }
+            _ => self.meaning.clone(),
+        }
+    }
+
+    fn get_symbol_shapes(&self, x: f32, center_y: f32) -> Vec<LegendShape> {
+        match self.symbol {
+            LegendSymbol::Edge {
+                color,
+                width,
+                dash_solid,
+                dash_transparent,
+            } => {
+                // Without a transparent part, the line consists of a single dash
+                let (dash, period) = if dash_solid > 0. && dash_transparent > 0. {
+                    (dash_solid, dash_solid + dash_transparent)
+                } else {
+                    (SYMBOL_WIDTH, SYMBOL_WIDTH)
+                };
+                (0..(SYMBOL_WIDTH / period).ceil() as usize)
+                    .map(|index| {
+                        let start = index as f32 * period;
+                        LegendShape::Rect {
+                            rect: Rectangle::new(
+                                x + start,
+                                center_y - 0.5 * width,
+                                dash.min(SYMBOL_WIDTH - start),
+                                width,
+                            ),
+                            color,
+                        }
+                    })
+                    .collect()
+            }
+            LegendSymbol::Node { color } => vec![LegendShape::Rect {
+                rect: Rectangle::new(
+                    x,
+                    center_y - 0.5 * SYMBOL_HEIGHT,
+                    SYMBOL_WIDTH,
+                    SYMBOL_HEIGHT,
+                ),
+                color,
+            }],
+            LegendSymbol::Scale { from, to, .. } => {
+                let step = SYMBOL_WIDTH / SCALE_STEPS as f32;
+                (0..SCALE_STEPS)
+                    .map(|index| LegendShape::Rect {
+                        rect: Rectangle::new(
+                            x + index as f32 * step,
+                            center_y - 0.5 * SYMBOL_HEIGHT,
+                            step,
+                            SYMBOL_HEIGHT,
+                        ),
+                        color: from.mix(&to, index as f32 / (SCALE_STEPS - 1) as f32),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod annotations;
+pub mod change_history;
+pub mod diagram_layout;
+pub mod layer_window;
+pub mod layout_rules;
+pub mod layouts;
+pub mod legend;
+pub mod node_metric;
+pub mod pagination;
+pub mod renderer;
+pub mod renderers;
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::{
+    interface::NodeID,
+    util::{color::Color, transition::Interpolatable},
+};
+
+use super::legend::{LegendEntry, LegendSymbol};
+
+/// The color of the nodes with the smallest value
+pub const METRIC_LOW_COLOR: Color = Color(0.2, 0.4, 0.9);
+/// The color of the nodes with the largest value
+pub const METRIC_HIGH_COLOR: Color = Color(0.95, 0.25, 0.1);
+/// How strongly the color of a value covers the color of a node
+const METRIC_OPACITY: f32 = 0.8;
+
+/// Values of source nodes pushed by external tools, such as hit counts of a solver, which are drawn as a color overlay normalized between the smallest and largest value
+#[derive(Default)]
+pub struct NodeMetric {
+    values: HashMap<NodeID, f64>,
+    /// The values of the local nodes that represent source nodes with a value
+    local_values: HashMap<NodeID, f64>,
+    range: (f64, f64),
+}
+
+impl NodeMetric {
+    pub fn new() -> NodeMetric {
+        NodeMetric::default()
+    }
+
+    /// Sets the values of the given source nodes, where values that are not finite are left out
+    pub fn set_values(&mut self, values: HashMap<NodeID, f64>) {
+        self.values = values
+            .into_iter()
+            .filter(|(_, value)| value.is_finite())
+            .collect();
+        self.range = self
+            .values
+            .values()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        self.local_values.clear();
+    }
+
+    /// Assigns the values to the local nodes that currently represent the source nodes
+    pub fn update_local_values(&mut self, source_to_local: impl Fn(NodeID) -> Vec<NodeID>) {
+        self.local_values = self
+            .values
+            .iter()
+            .flat_map(|(&source, &value)| {
+                source_to_local(source)
+                    .into_iter()
+                    .map(move |node| (node, value))
+            })
+            .collect();
+    }
+
+    /// Retrieves the color of the given local node with the overlay of its value applied, if it has a value
+    pub fn get_color(&self, node: NodeID, color: Color) -> Color {
+        let Some(&value) = self.local_values.get(&node) else {
+            return color;
+        };
+        let (min, max) = self.range;
+        let per = if max > min {
+            ((value - min) / (max - min)) as f32
+        } else {
+            1.
+        };
+        color.mix(&METRIC_LOW_COLOR.mix(&METRIC_HIGH_COLOR, per), METRIC_OPACITY)
+    }
+
+    /// Retrieves the entry explaining the overlay colors, if any values are set
+    pub fn get_legend_entry(&self) -> Option<LegendEntry> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let (min, max) = self.range;
+        Some(LegendEntry {
+            symbol: LegendSymbol::Scale {
+                from: METRIC_LOW_COLOR,
+                to: METRIC_HIGH_COLOR,
+                min: min as f32,
+                max: max as f32,
+            },
+            meaning: "node metric".into(),
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use crate::util::{point::Point, rectangle::Rectangle};
+
+/// The space around the printable area of a page, in cm
+const PAGE_MARGIN: f32 = 1.;
+/// The space below the printable area that is reserved for the page label, in diagram units
+pub const PAGE_LABEL_HEIGHT: f32 = 1.;
+
+/// The size of a sheet of paper in cm, where one diagram unit is exported as one cm
+#[derive(Clone, Copy)]
+pub struct PaperSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PaperSize {
+    pub const A4: PaperSize = PaperSize {
+        width: 21.,
+        height: 29.7,
+    };
+    pub const LETTER: PaperSize = PaperSize {
+        width: 21.59,
+        height: 27.94,
+    };
+
+    pub fn landscape(self) -> PaperSize {
+        PaperSize {
+            width: self.width.max(self.height),
+            height: self.width.min(self.height),
+        }
+    }
+
+    /// Retrieves the size of the region of the diagram that fits on a single page
+    fn get_content_size(&self) -> Point {
+        Point {
+            x: (self.width - 2. * PAGE_MARGIN).max(1.),
+            y: (self.height - 2. * PAGE_MARGIN - PAGE_LABEL_HEIGHT).max(1.),
+        }
+    }
+}
+
+/// A single tile of a diagram that is split across multiple pages
+#[derive(Clone)]
+pub struct Page {
+    /// The region of the diagram shown on this page, in diagram coordinates
+    pub region: Rectangle,
+    pub row: usize,
+    pub column: usize,
+    pub rows: usize,
+    pub columns: usize,
+    /// The width of the strips that are shown on both this page and its neighbours
+    pub overlap: f32,
+}
+
+impl Page {
+    /// Retrieves the label that identifies the page and its position in the grid of pages
+    pub fn get_label(&self) -> String {
+        format!(
+            "Page {} of {} (row {}, column {})",
+            self.row * self.columns + self.column + 1,
+            self.rows * self.columns,
+            self.row + 1,
+            self.column + 1
+        )
+    }
+
+    /// Retrieves the lines (as start and end points) that bound the parts of the page that are also shown on a neighbouring page
+    pub fn get_overlap_marks(&self) -> Vec<(Point, Point)> {
+        let Rectangle {
+            x,
+            y,
+            width,
+            height,
+        } = self.region;
+        let (left, right, bottom, top) = (x, x + width, y, y + height);
+        let vertical = |x: f32| (Point { x, y: bottom }, Point { x, y: top });
+        let horizontal = |y: f32| (Point { x: left, y }, Point { x: right, y });
+        let mut marks = Vec::new();
+        if self.column > 0 {
+            marks.push(vertical(left + self.overlap));
+        }
+        if self.column + 1 < self.columns {
+            marks.push(vertical(right - self.overlap));
+        }
+        if self.row > 0 {
+            marks.push(horizontal(top - self.overlap));
+        }
+        if self.row + 1 < self.rows {
+            marks.push(horizontal(bottom + self.overlap));
+        }
+        marks
+    }
+
+    /// Retrieves the position of the page label, centered below the shown region
+    pub fn get_label_position(&self) -> Point {
+        Point {
+            x: self.region.x + 0.5 * self.region.width,
+            y: self.region.y - 0.5 * PAGE_LABEL_HEIGHT,
+        }
+    }
+}
+
+/// Splits the given area into a grid of pages of the given paper size, ordered row by row from the top left, where neighbouring pages share the given overlap
+pub fn paginate(area: &Rectangle, paper: PaperSize, overlap: f32) -> Vec<Page> {
+    let size = paper.get_content_size();
+    let overlap = overlap.max(0.).min(0.5 * size.x.min(size.y));
+    let count = |length: f32, page_length: f32| {
+        ((length - overlap) / (page_length - overlap)).ceil().max(1.) as usize
+    };
+    let columns = count(area.width, size.x);
+    let rows = count(area.height, size.y);
+    let top = area.y + area.height;
+    (0..rows)
+        .flat_map(|row| {
+            (0..columns).map(move |column| Page {
+                region: Rectangle::new(
+                    area.x + column as f32 * (size.x - overlap),
+                    top - size.y - row as f32 * (size.y - overlap),
+                    size.x,
+                    size.y,
+                ),
+                row,
+                column,
+                rows,
+                columns,
+                overlap,
+            })
+        })
+        .collect()
+}
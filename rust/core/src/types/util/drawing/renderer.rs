@@ -0,0 +1,145 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Cursor, Result},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use oxidd_core::Tag;
+
+use crate::{
+    interface::NodeGroupID,
+    types::util::{
+        graph_structure::{
+            graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
+        },
+        storage::state_storage::Serializable,
+    },
+    util::{
+        color::Color, rectangle::Rectangle, transformation::Transformation,
+        transition::Interpolatable,
+    },
+};
+
+use super::{
+    annotations::AnnotationShape,
+    diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+    layout_rules::LayoutRules,
+};
+
+/// A backend that draws the layouts computed by a `Drawer`, which other crates can implement to show diagrams with their own graphics stack while reusing the graph and layout machinery.
+///
+/// The drawer uploads every new layout with `update_layout`, sets the camera with `set_transform`, forwards the selection, edge styles and annotations whenever they change, and calls `render` once per frame. All positions are in diagram coordinates, and all transitions are evaluated at the time passed to `render`.
+pub trait DiagramRenderer<L: LayoutRules> {
+    /// Sets the camera, mapping diagram coordinates to the screen
+    fn set_transform(&mut self, transform: Transformation);
+    /// Uploads a new layout, whose transitions start at the times stored in the layout
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>);
+    /// Updates only the given groups and their edges to the given layout, where the rest of the layout is unchanged. Returns false if this is not supported for these changes, in which case the full layout has to be updated instead.
+    fn update_groups(
+        &mut self,
+        _layout: &DiagramLayout<L::T, L::NS, L::LS>,
+        _groups: &HashSet<NodeGroupID>,
+    ) -> bool {
+        false
+    }
+    /// Draws a frame at the given time
+    fn render(&mut self, time: u32);
+    /// Retrieves the groups drawn in the given area in diagram coordinates, or None to select the groups whose layout overlaps the area. Backends that draw groups with other shapes or at other positions than the layout can override this.
+    fn pick(&self, _area: &Rectangle) -> Option<Vec<NodeGroupID>> {
+        None
+    }
+    fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection);
+    /// Sets the style overrides of individual edges, and the edges that are selected themselves
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    );
+    /// Sets the annotations to draw on top of the diagram, in diagram coordinates
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>);
+}
+
+pub type GroupSelection<'a> = (
+    // Selected groups
+    &'a [NodeGroupID],
+    // Partially selected groups
+    &'a [NodeGroupID],
+    // Hovered groups
+    &'a [NodeGroupID],
+    // Partially hovered groups
+    &'a [NodeGroupID],
+);
+
+/// Identifies the edges of a given type index between a source and target group
+pub type GroupEdgeID = (NodeGroupID, NodeGroupID, i32);
+
+/// User provided overrides of the appearance of an edge, where None indicates the edge type's default is used
+#[derive(Clone, Default, PartialEq)]
+pub struct EdgeStyle {
+    pub color: Option<Color>,
+    pub width: Option<f32>,
+    pub label: Option<String>,
+}
+
+impl EdgeStyle {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.width.is_none() && self.label.is_none()
+    }
+
+    /// Combines the overrides, where the values of this style take precedence over those of the fallback
+    pub fn or(&self, fallback: &EdgeStyle) -> EdgeStyle {
+        EdgeStyle {
+            color: self.color.or(fallback.color),
+            width: self.width.or(fallback.width),
+            label: self.label.clone().or_else(|| fallback.label.clone()),
+        }
+    }
+}
+
+impl Serializable for EdgeStyle {
+    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+        let flags = self.color.is_some() as u8
+            | (self.width.is_some() as u8) << 1
+            | (self.label.is_some() as u8) << 2;
+        stream.write_u8(flags)?;
+        if let Some(Color(r, g, b)) = self.color {
+            stream.write_f32::<LittleEndian>(r)?;
+            stream.write_f32::<LittleEndian>(g)?;
+            stream.write_f32::<LittleEndian>(b)?;
+        }
+        if let Some(width) = self.width {
+            stream.write_f32::<LittleEndian>(width)?;
+        }
+        if let Some(label) = &self.label {
+            label.serialize(stream)?;
+        }
+        Ok(())
+    }
+    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<EdgeStyle> {
+        let flags = stream.read_u8()?;
+        let color = if flags & 1 != 0 {
+            Some(Color(
+                stream.read_f32::<LittleEndian>()?,
+                stream.read_f32::<LittleEndian>()?,
+                stream.read_f32::<LittleEndian>()?,
+            ))
+        } else {
+            None
+        };
+        let width = if flags & 2 != 0 {
+            Some(stream.read_f32::<LittleEndian>()?)
+        } else {
+            None
+        };
+        let label = if flags & 4 != 0 {
+            Some(String::deserialize(stream)?)
+        } else {
+            None
+        };
+        Ok(EdgeStyle {
+            color,
+            width,
+            label,
+        })
+    }
+}
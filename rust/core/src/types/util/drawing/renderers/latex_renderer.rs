@@ -0,0 +1,777 @@
+use core::f32;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::{
+    interface::{NodeGroupID, NodeID},
+    types::util::{
+        drawing::{
+            annotations::{
+                get_arrow_outline, get_break_zigzag, AnnotationKind, AnnotationShape,
+                ANNOTATION_COLOR, HIGHLIGHT_OPACITY, STUB_OPACITY,
+            },
+            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            layout_rules::LayoutRules,
+            legend::{Legend, LegendShape},
+            pagination::{Page, PAGE_LABEL_HEIGHT},
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        },
+        graph_structure::{
+            graph_structure::DrawTag, grouped_graph_structure::GroupedGraphStructure,
+        },
+    },
+    util::{
+        color::Color, logging::console, rectangle::Rectangle, transformation::Transformation,
+    },
+};
+
+use super::util::{edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides};
+
+pub struct LatexRenderer<L: LayoutRules>
+where
+    L::NS: LatexNodeStyle,
+    L::LS: LatexLayerStyle,
+{
+    output: String,
+    layout: Option<DiagramLayout<L::T, L::NS, L::LS>>,
+    label_sides: LayerLabelSides,
+    rotated_layer_labels: bool,
+    /// Whether to fill the layers with alternating background colors
+    layer_backgrounds: bool,
+    /// The line widths of edges per edge type index, in world units
+    edge_widths: HashMap<i32, f32>,
+    arrows: EdgeArrows,
+    /// User provided TikZ style overrides (lists of TikZ options such as `fill=red, thick`) per node, level and edge type index
+    node_styles: HashMap<NodeID, String>,
+    level_styles: HashMap<LevelNo, String>,
+    edge_styles: HashMap<i32, String>,
+    /// The style overrides of individual edges
+    edge_overrides: HashMap<GroupEdgeID, EdgeStyle>,
+    annotations: Vec<AnnotationShape>,
+    /// The legend to place to the right of the diagram, if any
+    legend: Option<Legend>,
+    /// The pages to split the diagram across, or an empty list to output a single picture
+    pages: Vec<Page>,
+    /// The node style overrides combined per group, obtained from `resolve_node_styles`
+    group_styles: HashMap<NodeGroupID, String>,
+    /// Whether to wrap nodes and edges in beamer `\onslide` commands according to the recorded stages
+    staged: bool,
+    /// The stage (starting at 1) in which each group was first recorded
+    group_stages: HashMap<NodeGroupID, usize>,
+    stage_count: usize,
+}
+
+impl<L: LayoutRules> LatexRenderer<L>
+where
+    L::NS: LatexNodeStyle,
+    L::LS: LatexLayerStyle,
+{
+    pub fn new() -> LatexRenderer<L> {
+        LatexRenderer {
+            output: "".into(),
+            layout: None,
+            label_sides: LayerLabelSides::Left,
+            rotated_layer_labels: false,
+            layer_backgrounds: false,
+            edge_widths: HashMap::new(),
+            arrows: EdgeArrows::None,
+            node_styles: HashMap::new(),
+            level_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            edge_overrides: HashMap::new(),
+            annotations: Vec::new(),
+            legend: None,
+            pages: Vec::new(),
+            group_styles: HashMap::new(),
+            staged: false,
+            group_stages: HashMap::new(),
+            stage_count: 0,
+        }
+    }
+
+    pub fn get_output(&self) -> String {
+        self.output.clone()
+    }
+
+    pub fn set_layer_label_sides(&mut self, sides: LayerLabelSides) {
+        self.label_sides = sides;
+    }
+
+    pub fn set_rotated_layer_labels(&mut self, rotated: bool) {
+        self.rotated_layer_labels = rotated;
+    }
+
+    pub fn set_layer_backgrounds(&mut self, backgrounds: bool) {
+        self.layer_backgrounds = backgrounds;
+    }
+
+    pub fn set_edge_width(&mut self, edge_type_index: i32, width: f32) {
+        self.edge_widths.insert(edge_type_index, width);
+    }
+
+    pub fn set_edge_arrows(&mut self, arrows: EdgeArrows) {
+        self.arrows = arrows;
+    }
+
+    /// Sets the TikZ options applied to the given node, or removes them if the style is empty
+    pub fn set_node_style(&mut self, node: NodeID, style: String) {
+        set_style(&mut self.node_styles, node, style);
+    }
+
+    /// Sets the TikZ options applied to all nodes of the given level, or removes them if the style is empty
+    pub fn set_level_style(&mut self, level: LevelNo, style: String) {
+        set_style(&mut self.level_styles, level, style);
+    }
+
+    /// Sets the TikZ options applied to all edges of the given type, or removes them if the style is empty
+    pub fn set_edge_style(&mut self, edge_type_index: i32, style: String) {
+        set_style(&mut self.edge_styles, edge_type_index, style);
+    }
+
+    /// Combines the node styles per group, this has to be called before rendering whenever the groups or node styles changed
+    pub fn resolve_node_styles(&mut self, get_group: impl Fn(NodeID) -> NodeGroupID) {
+        let mut group_styles = HashMap::<NodeGroupID, Vec<&String>>::new();
+        for (&node, style) in self.node_styles.iter().sorted_by_key(|&(&node, _)| node) {
+            group_styles.entry(get_group(node)).or_default().push(style);
+        }
+        self.group_styles = group_styles
+            .into_iter()
+            .map(|(group, styles)| (group, styles.into_iter().join(", ")))
+            .collect();
+    }
+
+    pub fn set_legend(&mut self, legend: Option<Legend>) {
+        self.legend = legend;
+    }
+
+    /// Sets the pages to split the diagram across, each of which is output as a separate picture on its own page
+    pub fn set_pages(&mut self, pages: Vec<Page>) {
+        self.pages = pages;
+    }
+
+    pub fn set_staged(&mut self, staged: bool) {
+        self.staged = staged;
+    }
+
+    /// Records the groups that are currently shown as a new stage, and returns the number of stages
+    pub fn record_stage(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) -> usize {
+        self.stage_count += 1;
+        for (&id, group) in layout.groups.iter() {
+            if group.exists.new > 0. {
+                self.group_stages.entry(id).or_insert(self.stage_count);
+            }
+        }
+        self.stage_count
+    }
+
+    pub fn clear_stages(&mut self) {
+        self.group_stages.clear();
+        self.stage_count = 0;
+    }
+
+    /// Creates the `\tikzset` definitions of all styles that are referenced by the drawn nodes and edges
+    fn get_style_definitions(&self) -> String {
+        let edge_types = self
+            .layout
+            .iter()
+            .flat_map(|layout| layout.groups.values())
+            .flat_map(|group| group.edges.keys())
+            .map(|edge_data| edge_data.edge_type.index)
+            .chain(self.edge_widths.keys().cloned())
+            .chain(self.edge_styles.keys().cloned())
+            .unique()
+            .sorted();
+        let arrow_style = match self.arrows {
+            EdgeArrows::None => "",
+            EdgeArrows::End => ", ->",
+            EdgeArrows::Middle => ", midArrow",
+        };
+        let edge_styles = edge_types.map(|index| {
+            let width = self
+                .edge_widths
+                .get(&index)
+                .map(|width| format!(", line width={}*\\unit cm", width))
+                .unwrap_or_default();
+            let style = self
+                .edge_styles
+                .get(&index)
+                .map(|style| format!(", {}", style))
+                .unwrap_or_default();
+            format!(
+                "edge{}/.style={{choice{}{}{}{}}}",
+                index, index, width, arrow_style, style
+            )
+        });
+        let level_styles = self
+            .level_styles
+            .iter()
+            .sorted_by_key(|&(&level, _)| level)
+            .map(|(level, style)| format!("level{}/.style={{{}}}", level, style));
+        let group_styles = self
+            .group_styles
+            .iter()
+            .sorted_by_key(|&(&group, _)| group)
+            .map(|(group, style)| format!("node{}/.style={{{}}}", group, style));
+
+        let styles = edge_styles
+            .chain(level_styles)
+            .chain(group_styles)
+            .join(",\n        ");
+        format!("\\tikzset{{\n        {}\n    }}", styles)
+    }
+
+    /// Retrieves the additional styles that should be applied to the given group
+    fn get_node_style_overrides(&self, group_id: NodeGroupID, level: LevelNo) -> String {
+        let mut styles = String::new();
+        if self.level_styles.contains_key(&level) {
+            styles += &format!(", level{}", level);
+        }
+        if self.group_styles.contains_key(&group_id) {
+            styles += &format!(", node{}", group_id);
+        }
+        styles
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for LatexRenderer<L>
+where
+    L::NS: LatexNodeStyle,
+    L::LS: LatexLayerStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {
+        todo!()
+    }
+
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.layout = Some(layout.clone());
+    }
+
+    fn render(&mut self, time: u32) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        // Groups that were never recorded are shown after all recorded stages
+        let group_stages = &self.group_stages;
+        let last_stage = self.stage_count + 1;
+        let get_stage =
+            |group: &NodeGroupID| group_stages.get(group).cloned().unwrap_or(last_stage);
+        let staged = self.staged;
+        let on_slide = move |stage: usize, content: String| {
+            if staged {
+                format!("\\onslide<{}->{{{}}}", stage, content)
+            } else {
+                content
+            }
+        };
+
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        // The groups and edges are drawn in a fixed order, such that exports of the same layout are identical
+        let nodes = layout
+            .groups
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .filter_map(|(id, group)| {
+                if group.exists.get(time) < 1.0 {
+                    return None;
+                }
+                let pos = group.position.get(time);
+                let size = group.size.get(time);
+                if pos.x - 0.5*size.x < min_x {
+                    min_x = pos.x - 0.5*size.x;
+                }
+                if pos.x + 0.5*size.x > max_x {
+                    max_x = pos.x + 0.5*size.x;
+                }
+                let style = group.style.get(time);
+                let label = if group.label_exists.get(time) < 1.0 {
+                    "".into()
+                } else {
+                    sanitize(style.get_label().unwrap_or_default())
+                };
+
+                let x = pos.x;
+                let y = pos.y + 0.5 * size.y;
+                let overrides = self.get_node_style_overrides(*id, group.level_range.0);
+
+                // Labels that are not at the center of the node are drawn separately
+                let label_offset = group.label_offset.get(time);
+                let (label, outside_label) = if label_offset.length() == 0. || label.is_empty() {
+                    (label, "".into())
+                } else {
+                    (
+                        "".into(),
+                        format!(
+                            "\n    \\node[nodeLabel] at ({}, {}) {{{}}};",
+                            x + label_offset.x,
+                            y + label_offset.y,
+                            label
+                        ),
+                    )
+                };
+                let node = if let Some((terminal_type, terminal_label)) = style.is_terminal() {
+                    Some(format!(
+                        "\\node[{}{}] (n{}) at ({}, {}) {{{}}};",
+                        terminal_type, overrides, id, x, y, terminal_label.unwrap_or_else(|| format!("\\pgfkeysvalueof{{/tikz/{}/label}}", terminal_type))
+                    ))
+                } else if style.is_group() {
+                    Some(format!(
+                        "\\node[group, minimum width={}*\\unit cm, minimum height={}*\\unit cm{}] (n{}) at ({}, {}) {{{}}};",
+                        size.x, size.y, overrides, id, x, y, label
+                    ))
+                } else if (size.y - size.x).abs() <= f32::EPSILON {
+                    Some(format!(
+                        "\\node[inner, minimum size={}*\\unit cm{}] (n{}) at ({}, {}) {{{}}};",
+                        size.y, overrides, id, x, y,  label
+                    ))
+                } else {
+                    Some(format!(
+                        "\\node[innerSized, minimum width={}*\\unit cm, minimum height={}*\\unit cm{}] (n{}) at ({}, {}) {{{}}};",
+                        size.x, size.y, overrides, id, x, y, label
+                    ))
+                };
+                node.map(|node| on_slide(get_stage(id), node + &outside_label))
+            })
+            .join("\n    ");
+
+        let edge_overrides = &self.edge_overrides;
+        let edges = layout
+            .groups
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .flat_map(|(group_id, group)| {
+                for (_, edge) in group.edges.iter() {
+                    for p in edge.points.iter() {
+                        let p = p.point.get(time);
+                        if p.x > max_x {
+                            max_x = p.x;
+                        }
+                        if p.x < min_x {
+                            min_x = p.x;
+                        }
+                    }
+                }
+                let edges = group.edges.iter().sorted_by(|(a, _), (b, _)| a.cmp(b));
+                edges.filter_map(move |(edge_data, edge)| {
+                    if edge.exists.get(time) < 1.0 {
+                        return None;
+                    }
+                    let Some(target_group) = layout.groups.get(&edge_data.to) else {
+                        return None;
+                    };
+                    let start_pos = group.position.get(time) + edge.start_offset.get(time);
+                    let end_pos = target_group.position.get(time) + edge.end_offset.get(time);
+                    let start_next_pos = edge
+                        .points
+                        .get(0)
+                        .map(|e| e.point.get(time))
+                        .unwrap_or(end_pos);
+                    let end_previous_pos = Some(edge.points.len())
+                        .filter(|&v| v > 0)
+                        .and_then(|v| edge.points.get(v - 1))
+                        .map(|e| e.point.get(time))
+                        .unwrap_or(start_pos);
+
+                    let start_delta = start_next_pos - start_pos;
+                    let (is_start_side, start_side) = if group.level_range.0 == group.level_range.1
+                    {
+                        (false, "")
+                    } else if start_delta.x > 0. {
+                        (true, ".east")
+                    } else if start_delta.x < 0. {
+                        (true, ".west")
+                    } else {
+                        (false, ".south")
+                    };
+                    let start_offset = if is_start_side {
+                        edge.start_offset.get(time).y - 0.5 * group.size.get(time).y
+                    } else {
+                        0.0
+                    };
+                    let start_offset = if start_offset == 0.0 {
+                        ""
+                    } else {
+                        &format!("[yshift={}*\\unit cm] ", start_offset)
+                    };
+
+                    let end_delta = end_pos - end_previous_pos;
+                    let (is_end_side, end_side) =
+                        if target_group.level_range.0 == target_group.level_range.1 {
+                            (false, "")
+                        } else if end_delta.x > 0. {
+                            (true, ".west")
+                        } else if end_delta.x < 0. {
+                            (true, ".east")
+                        } else {
+                            (false, ".north")
+                        };
+                    let end_offset = if is_end_side {
+                        edge.end_offset.get(time).y - 0.5 * target_group.size.get(time).y
+                    } else {
+                        0.0
+                    };
+                    let end_offset = if end_offset == 0.0 {
+                        ""
+                    } else {
+                        &format!("[yshift={}*\\unit cm] ", end_offset)
+                    };
+
+                    let intermediate_points = edge
+                        .points
+                        .iter()
+                        .filter_map(|p| {
+                            if p.exists.get(time) < 1.0 {
+                                return None;
+                            }
+                            let p = p.point.get(time);
+                            Some(format!("({}, {}) to ", p.x, p.y))
+                        })
+                        .join("");
+
+                    let id = (*group_id, edge_data.to, edge_data.edge_type.index);
+                    let (overrides, label) = edge_overrides
+                        .get(&id)
+                        .map(get_edge_style_overrides)
+                        .unwrap_or_default();
+                    let edge = format!(
+                        "\\draw[edge{}{}] ({}n{}{}) to[bend left={}] {}{}({}n{}{});",
+                        edge_data.edge_type.index,
+                        overrides,
+                        start_offset,
+                        group_id,
+                        start_side,
+                        edge.curve_offset.get(time) * 45.0,
+                        label,
+                        intermediate_points,
+                        end_offset,
+                        edge_data.to,
+                        end_side
+                    );
+                    // Edges are revealed once both of their end points are shown
+                    let stage = get_stage(group_id).max(get_stage(&edge_data.to));
+                    Some(on_slide(stage, edge))
+                })
+            })
+            .join("\n    ");
+
+        let left_end = if self.label_sides.has_left() {
+            "-\\margin-\\ts"
+        } else {
+            "-\\margin"
+        };
+        let right_end = if self.label_sides.has_right() {
+            "+\\margin+\\ts"
+        } else {
+            "+\\margin"
+        };
+        let layers = layout
+            .layers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, layer)| {
+                if layer.exists.get(time) < 1.0 {
+                    return None;
+                }
+                let top = layer.top.get(time);
+                let bottom = layer.bottom.get(time);
+                let style = layer.style.get(time);
+                console::log_trace!("minX: {}, maxX: {}", min_x, max_x);
+                let label_text = sanitize(style.get_label());
+                let y = 0.5 * (top + bottom);
+                let rotation = if self.rotated_layer_labels {
+                    ", rotate=90"
+                } else {
+                    ""
+                };
+                let mut label = Vec::new();
+                if self.label_sides.has_left() {
+                    label.push(format!(
+                        "\\node[layerLabel{}] (l-{}) at ({}-\\ts, {}) {{{}}};",
+                        rotation, index, min_x, y, label_text
+                    ));
+                }
+                if self.label_sides.has_right() {
+                    label.push(format!(
+                        "\\node[layerLabel{}] (r-{}) at ({}+\\ts, {}) {{{}}};",
+                        rotation, index, max_x, y, label_text
+                    ));
+                }
+                let label = label.join("\n    ");
+                let bottom_divider = format!(
+                    "\\draw[layerDivider] ({}{}, {}) -- ({}{}, {});",
+                    min_x, left_end, bottom, max_x, right_end, bottom
+                );
+                let top_divider = format!(
+                    "\\draw[layerDivider] ({}{}, {}) -- ({}{}, {});",
+                    min_x, left_end, top, max_x, right_end, top
+                );
+                let label = if self.layer_backgrounds {
+                    format!(
+                        "\\fill[layerBackground{}] ({}{}, {}) rectangle ({}{}, {});\n    {}",
+                        index % 2 + 1,
+                        min_x,
+                        left_end,
+                        top,
+                        max_x,
+                        right_end,
+                        bottom,
+                        label
+                    )
+                } else {
+                    label
+                };
+                if index == 0 {
+                    Some(format!(
+                        "{}\n    {}\n    {}",
+                        top_divider, label, bottom_divider
+                    ))
+                } else {
+                    Some(format!("{}\n    {}", label, bottom_divider))
+                }
+            })
+            .join("\n    ");
+
+        let annotations = self
+            .annotations
+            .iter()
+            .filter(|annotation| annotation.exists.get(time) >= 1.0)
+            .map(|annotation| {
+                let pos = annotation.position.get(time);
+                let color = to_tikz_color(&ANNOTATION_COLOR);
+                match &annotation.kind {
+                    AnnotationKind::Note(text) => format!(
+                        "\\node at ({}, {}) {{{}}};",
+                        pos.x,
+                        pos.y,
+                        sanitize(text.clone())
+                    ),
+                    AnnotationKind::Arrow(delta) => format!(
+                        "\\fill[fill={}] {} -- cycle;",
+                        color,
+                        get_arrow_outline(pos, *delta)
+                            .iter()
+                            .map(|p| format!("({}, {})", p.x, p.y))
+                            .join(" -- ")
+                    ),
+                    AnnotationKind::Highlight(size) => format!(
+                        "\\fill[fill={}, fill opacity={}] ({}, {}) rectangle ({}, {});",
+                        color,
+                        HIGHLIGHT_OPACITY,
+                        pos.x - 0.5 * size.x,
+                        pos.y - 0.5 * size.y,
+                        pos.x + 0.5 * size.x,
+                        pos.y + 0.5 * size.y
+                    ),
+                    AnnotationKind::Stub(delta, label) => format!(
+                        "\\draw[draw={}, draw opacity={}, dashed] ({}, {}) -- ({}, {}) node[below] {{{}}};",
+                        color,
+                        STUB_OPACITY,
+                        pos.x,
+                        pos.y,
+                        pos.x + delta.x,
+                        pos.y + delta.y,
+                        sanitize(label.clone())
+                    ),
+                    AnnotationKind::Break(width, label) => format!(
+                        "\\draw[draw={}] {} node[right] {{{}}};",
+                        color,
+                        get_break_zigzag(pos, *width)
+                            .iter()
+                            .map(|p| format!("({}, {})", p.x, p.y))
+                            .join(" -- "),
+                        sanitize(label.clone())
+                    ),
+                }
+            })
+            .join("\n    ");
+
+        // The legend is placed to the right of the layer labels, whose spacing is given by the margin and ts macros
+        let legend = self
+            .legend
+            .iter()
+            .flat_map(|legend| {
+                let (bottom, top) = layout
+                    .layers
+                    .iter()
+                    .filter(|layer| layer.exists.get(time) >= 1.0)
+                    .map(|layer| (layer.bottom.get(time), layer.top.get(time)))
+                    .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+                    .unwrap_or((0., 0.));
+                let right = if self.label_sides.has_right() {
+                    2.5
+                } else {
+                    0.5
+                };
+                let area = Rectangle::new(min_x, bottom, max_x + right - min_x, top - bottom);
+                legend.place_next_to(&area).0
+            })
+            .map(|shape| match shape {
+                LegendShape::Rect { rect, color } => format!(
+                    "\\fill[fill={}] ({}, {}) rectangle ({}, {});",
+                    to_tikz_color(&color),
+                    rect.x,
+                    rect.y,
+                    rect.x + rect.width,
+                    rect.y + rect.height
+                ),
+                LegendShape::Text { position, text } => format!(
+                    "\\node[anchor=west] at ({}, {}) {{{}}};",
+                    position.x,
+                    position.y,
+                    sanitize(text)
+                ),
+            })
+            .join("\n    ");
+
+        let styles = self.get_style_definitions();
+        let picture = |page_start: String, page_end: String| {
+            format!(
+                "\\begin{{tikzpicture}}\n    \
+                \\pgfmathsetmacro{{\\margin}}{{0.5}} % spacing around diagram on left and right \n    \
+                \\pgfmathsetmacro{{\\ts}}{{2}} % the spacing available for variables \n    \
+                \n    \
+                \\pgfmathsetmacro{{\\unit}}{{veclen(0,1)}}\n    \
+                \n    \
+                % Styles \n    \
+                {}\n    \
+                {}\
+                \n    \
+                % Layers \n    \
+                {}\n    \
+                \n    \
+                % Nodes \n    \
+                {}\n    \
+                \n    \
+                % Edges \n    \
+                {}\n    \
+                \n    \
+                % Annotations \n    \
+                {}\n    \
+                \n    \
+                % Legend \n    \
+                {}\n\
+                {}\
+                \\end{{tikzpicture}}",
+                styles, page_start, layers, nodes, edges, annotations, legend, page_end
+            )
+        };
+
+        // Every page shows the same picture, clipped to the region of that page
+        let out = if self.pages.is_empty() {
+            picture("".into(), "".into())
+        } else {
+            self.pages
+                .iter()
+                .map(|page| {
+                    let region = &page.region;
+                    let (left, bottom) = (region.x, region.y);
+                    let (right, top) = (region.x + region.width, region.y + region.height);
+                    let label = page.get_label_position();
+                    let page_start = format!(
+                        "\n    \
+                        % Page \n    \
+                        \\node at ({}, {}) {{{}}};\n    \
+                        \\useasboundingbox ({}, {}) rectangle ({}, {});\n    \
+                        \\clip ({}, {}) rectangle ({}, {});\n    ",
+                        label.x,
+                        label.y,
+                        page.get_label(),
+                        left,
+                        bottom - PAGE_LABEL_HEIGHT,
+                        right,
+                        top,
+                        left,
+                        bottom,
+                        right,
+                        top
+                    );
+                    let page_end = page
+                        .get_overlap_marks()
+                        .into_iter()
+                        .map(|(start, end)| {
+                            format!(
+                                "    \\draw[dashed, very thin, gray] ({}, {}) -- ({}, {});\n",
+                                start.x, start.y, end.x, end.y
+                            )
+                        })
+                        .join("");
+                    picture(page_start, page_end)
+                })
+                .join("\n\\clearpage\n")
+        };
+
+        self.output = out;
+    }
+
+    fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {
+        todo!()
+    }
+
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.edge_overrides = styles.clone();
+    }
+
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>) {
+        self.annotations = annotations.clone();
+    }
+}
+
+fn to_tikz_color(Color(r, g, b): &Color) -> String {
+    format!(
+        "{{rgb,255:red,{};green,{};blue,{}}}",
+        (r * 255.).round(),
+        (g * 255.).round(),
+        (b * 255.).round()
+    )
+}
+
+/// Creates the TikZ options and the label node of the given edge style overrides
+fn get_edge_style_overrides(style: &EdgeStyle) -> (String, String) {
+    let mut options = String::new();
+    if let Some(color) = &style.color {
+        options += &format!(", draw={}", to_tikz_color(color));
+    }
+    if let Some(width) = style.width {
+        options += &format!(", line width={}*\\unit cm", width);
+    }
+    let label = style
+        .label
+        .as_ref()
+        .map(|label| {
+            format!("node[midway, sloped, above] {{{}}} ", sanitize(label.clone()))
+        })
+        .unwrap_or_default();
+    (options, label)
+}
+
+fn set_style<K: Eq + Hash>(styles: &mut HashMap<K, String>, key: K, style: String) {
+    if style.trim().is_empty() {
+        styles.remove(&key);
+    } else {
+        styles.insert(key, style);
+    }
+}
+
+fn sanitize(text: String) -> String {
+    text.replace("_", "\\_")
+}
+
+pub trait LatexNodeStyle: NodeStyle {
+    /// Retrieves whether the given node is a terminal, and if so: retrieves the terminal type, and optionally a label
+    fn is_terminal(&self) -> Option<(String, Option<String>)>;
+    fn is_group(&self) -> bool;
+    fn get_label(&self) -> Option<String>;
+}
+pub trait LatexLayerStyle: LayerStyle {
+    fn get_label(&self) -> String;
+}
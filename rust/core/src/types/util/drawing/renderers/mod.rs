@@ -0,0 +1,4 @@
+pub mod latex_renderer;
+pub mod render_styles;
+pub mod svg_renderer;
+pub mod util;
@@ -0,0 +1,33 @@
+use crate::{
+    types::util::drawing::diagram_layout::{LayerStyle, NodeStyle},
+    util::color::{Color, TransparentColor},
+};
+
+/// The appearance of the edges of a type, which the screen renderers and the SVG exporter share such that exports look the same as the visualization
+#[derive(Clone)]
+pub struct EdgeRenderingType {
+    pub color: Color,
+    pub select_color: Color,
+    pub partial_select_color: Color,
+    pub hover_color: Color,
+    pub partial_hover_color: Color,
+    pub width: f32,
+    pub dash_solid: f32, // The distance per period over which this dash should be solid
+    pub dash_transparent: f32, // The distance per
+}
+
+#[derive(Clone)]
+pub struct LayerRenderingColorConfig {
+    pub background1: TransparentColor,
+    pub background2: TransparentColor,
+    pub text: Color,
+}
+
+pub trait WebglNodeStyle: NodeStyle {
+    fn get_color(&self) -> Color;
+    fn get_outline_color(&self) -> TransparentColor;
+    fn get_label(&self) -> Option<String>;
+}
+pub trait WebglLayerStyle: LayerStyle {
+    fn get_label(&self) -> String;
+}
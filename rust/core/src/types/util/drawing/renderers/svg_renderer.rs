@@ -0,0 +1,734 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{
+    types::util::{
+        drawing::{
+            annotations::{
+                get_arrow_outline, get_break_zigzag, AnnotationKind, AnnotationShape,
+                ANNOTATION_COLOR, ARROW_WIDTH, BREAK_LABEL_GAP, HIGHLIGHT_OPACITY, STUB_DASH_LENGTH,
+                STUB_OPACITY,
+            },
+            diagram_layout::DiagramLayout,
+            layout_rules::LayoutRules,
+            legend::{Legend, LegendShape},
+            pagination::{Page, PAGE_LABEL_HEIGHT},
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        },
+        graph_structure::graph_structure::EdgeType,
+    },
+    util::{
+        color::{Color, TransparentColor},
+        point::Point,
+        rectangle::Rectangle,
+        transformation::Transformation,
+    },
+};
+
+use super::{
+    render_styles::{
+        EdgeRenderingType, LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle,
+    },
+    util::{
+        edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides, layer_pairing::LayerPairing,
+    },
+};
+
+// These mirror the (currently hardcoded) values of the webgl renderer, such that exports look the same as the visualization
+const CORNER_RADIUS: f32 = 0.3;
+const BORDER_OFFSET: f32 = 0.3;
+const BORDER_WIDTH: f32 = 0.2;
+const ARROW_SIZE: f32 = 0.3;
+const ARROW_END_OFFSET: f32 = 0.5;
+/// The horizontal space reserved for layer labels next to the diagram
+const LABEL_SPACE: f32 = 2.;
+/// The line width and dash length of the marks that indicate the overlap with neighbouring pages
+const PAGE_MARK_WIDTH: f32 = 0.05;
+const PAGE_MARK_DASH: f32 = 0.2;
+
+/// A renderer that outputs the (final state of the) layout as an svg document, optionally cropped to a given region
+pub struct SvgRenderer<L: LayoutRules>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    output: String,
+    layout: Option<DiagramLayout<L::T, L::NS, L::LS>>,
+    /// The region of the diagram to export, in diagram coordinates, or None to export the whole diagram
+    region: Option<Rectangle>,
+    edge_types: HashMap<EdgeType<L::T>, EdgeRenderingType>,
+    hidden_edge_types: HashSet<EdgeType<L::T>>,
+    /// The style overrides of individual edges
+    edge_overrides: HashMap<GroupEdgeID, EdgeStyle>,
+    annotations: Vec<AnnotationShape>,
+    /// The legend to place to the right of the exported region, if any
+    legend: Option<Legend>,
+    /// The page to export when the diagram is split across multiple pages, which replaces the region
+    page: Option<Page>,
+    layer_colors: LayerRenderingColorConfig,
+    text_color: Color,
+    text_size: f32,
+    arrows: EdgeArrows,
+    label_sides: LayerLabelSides,
+    alternating_layer_backgrounds: bool,
+    layer_pairing: LayerPairing,
+}
+
+impl<L: LayoutRules> SvgRenderer<L>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    pub fn new(
+        edge_types: HashMap<EdgeType<L::T>, EdgeRenderingType>,
+        layer_colors: LayerRenderingColorConfig,
+        text_color: Color,
+        text_size: f32,
+    ) -> SvgRenderer<L> {
+        SvgRenderer {
+            output: "".into(),
+            layout: None,
+            region: None,
+            edge_types,
+            hidden_edge_types: HashSet::new(),
+            edge_overrides: HashMap::new(),
+            annotations: Vec::new(),
+            legend: None,
+            page: None,
+            layer_colors,
+            text_color,
+            text_size,
+            arrows: EdgeArrows::None,
+            label_sides: LayerLabelSides::Left,
+            alternating_layer_backgrounds: true,
+            layer_pairing: LayerPairing::Separate,
+        }
+    }
+
+    pub fn get_output(&self) -> String {
+        self.output.clone()
+    }
+
+    /// Sets the region of the diagram to export, in diagram coordinates
+    pub fn set_region(&mut self, region: Option<Rectangle>) {
+        self.region = region;
+    }
+
+    pub fn set_legend(&mut self, legend: Option<Legend>) {
+        self.legend = legend;
+    }
+
+    pub fn set_page(&mut self, page: Option<Page>) {
+        self.page = page;
+    }
+
+    /// Retrieves the region containing the whole diagram and its layer labels, which is exported if no region is set
+    pub fn get_bounds(&self, time: u32) -> Rectangle {
+        let Some(layout) = &self.layout else {
+            return Rectangle::new(0., 0., 0., 0.);
+        };
+        let (min_x, max_x) = layout
+            .groups
+            .values()
+            .filter(|group| group.exists.get(time) >= 1.0)
+            .map(|group| group.get_rect(Some(time)))
+            .map(|rect| (rect.x, rect.x + rect.width))
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+            .unwrap_or((0., 0.));
+        let (min_y, max_y) = layout
+            .layers
+            .iter()
+            .map(|layer| (layer.bottom.get(time), layer.top.get(time)))
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+            .unwrap_or((0., 0.));
+        let left = if self.label_sides.has_left() {
+            LABEL_SPACE
+        } else {
+            0.5
+        };
+        let right = if self.label_sides.has_right() {
+            LABEL_SPACE
+        } else {
+            0.5
+        };
+        Rectangle::new(
+            min_x - left,
+            min_y,
+            max_x - min_x + left + right,
+            max_y - min_y,
+        )
+    }
+
+    /// Retrieves the edge types that are currently drawn, sorted by their index
+    pub fn get_visible_edge_types(&self) -> Vec<(i32, EdgeRenderingType)> {
+        self.edge_types
+            .iter()
+            .filter(|(edge_type, rendering)| {
+                !self.hidden_edge_types.contains(edge_type) && rendering.width > 0.
+            })
+            .map(|(edge_type, rendering)| (edge_type.index, rendering.clone()))
+            .sorted_by_key(|&(index, _)| index)
+            .collect()
+    }
+
+    pub fn set_edge_type_visibility(&mut self, edge_type: EdgeType<L::T>, visible: bool) {
+        if visible {
+            self.hidden_edge_types.remove(&edge_type);
+        } else {
+            self.hidden_edge_types.insert(edge_type);
+        }
+    }
+
+    pub fn set_edge_type_width(&mut self, edge_type: EdgeType<L::T>, width: f32) {
+        if let Some(edge_type) = self.edge_types.get_mut(&edge_type) {
+            edge_type.width = width;
+        }
+    }
+
+    pub fn set_edge_arrows(&mut self, arrows: EdgeArrows) {
+        self.arrows = arrows;
+    }
+
+    pub fn set_layer_label_sides(&mut self, sides: LayerLabelSides) {
+        self.label_sides = sides;
+    }
+
+    pub fn set_alternating_layer_backgrounds(&mut self, alternating: bool) {
+        self.alternating_layer_backgrounds = alternating;
+    }
+
+    pub fn set_layer_pairing(&mut self, pairing: LayerPairing) {
+        self.layer_pairing = pairing;
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for SvgRenderer<L>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {}
+
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.layout = Some(layout.clone());
+    }
+
+    fn render(&mut self, time: u32) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        let node_rects = layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.get(time) >= 1.0)
+            .map(|(&id, group)| (id, group.get_rect(Some(time))))
+            .collect::<HashMap<_, _>>();
+        let (min_x, max_x) = node_rects
+            .values()
+            .map(|rect| (rect.x, rect.x + rect.width))
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+            .unwrap_or((0., 0.));
+        let region = self.page.as_ref().map(|page| page.region.clone());
+        let region = region
+            .or(self.region.clone())
+            .unwrap_or_else(|| self.get_bounds(time));
+        let (legend, mut view) = match &self.legend {
+            Some(legend) => legend.place_next_to(&region),
+            None => (Vec::new(), region.clone()),
+        };
+        // The page label is placed below the region of the page
+        if self.page.is_some() {
+            view.y -= PAGE_LABEL_HEIGHT;
+            view.height += PAGE_LABEL_HEIGHT;
+        }
+        let region = &region;
+        let node_rects = &node_rects;
+        // Svg coordinates grow downwards, hence all y coordinates are negated
+        let pos = |p: Point| format!("{} {}", p.x, -p.y);
+
+        // Paired layers are marked by brackets at both sides of the exported region
+        let brackets = if self.layer_pairing.has_brackets() {
+            let tick = 0.3 * self.text_size;
+            let (left, right) = (region.x, region.x + region.width);
+            layout
+                .layers
+                .iter()
+                .tuples()
+                .filter(|(first, second)| {
+                    first.exists.get(time) >= 1.0 && second.exists.get(time) >= 1.0
+                })
+                .map(|(first, second)| {
+                    let (top, bottom) = (-first.top.get(time), -second.bottom.get(time));
+                    format!(
+                        "<path d=\"M {} {} h {} V {} h {} M {} {} h {} V {} h {}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>",
+                        left + tick,
+                        top,
+                        -tick,
+                        bottom,
+                        tick,
+                        right - tick,
+                        top,
+                        tick,
+                        bottom,
+                        -tick,
+                        to_svg_color(&self.layer_colors.text),
+                        0.1 * self.text_size
+                    )
+                })
+                .collect_vec()
+        } else {
+            Vec::new()
+        };
+        let layers = layout
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| layer.exists.get(time) >= 1.0)
+            .map(|(index, layer)| {
+                let top = layer.top.get(time);
+                let bottom = layer.bottom.get(time);
+                let band = self.layer_pairing.get_band(index);
+                let background = if band % 2 == 1 && self.alternating_layer_backgrounds {
+                    &self.layer_colors.background2
+                } else {
+                    &self.layer_colors.background1
+                };
+                let mut out = vec![format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+                    region.x,
+                    -top,
+                    region.width,
+                    top - bottom,
+                    fill(background)
+                )];
+                let label = sanitize(layer.style.get(time).get_label());
+                let label_color = to_svg_color(&self.layer_colors.text);
+                let y = -0.5 * (top + bottom);
+                if self.label_sides.has_left() {
+                    out.push(format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"{}\">{}</text>",
+                        min_x - 0.5,
+                        y,
+                        label_color,
+                        label
+                    ));
+                }
+                if self.label_sides.has_right() {
+                    out.push(format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"start\" fill=\"{}\">{}</text>",
+                        max_x + 0.5,
+                        y,
+                        label_color,
+                        label
+                    ));
+                }
+                out.join("\n    ")
+            })
+            .chain(brackets)
+            .join("\n    ");
+
+        let (edge_types, hidden_edge_types, edge_overrides, arrows) = (
+            &self.edge_types,
+            &self.hidden_edge_types,
+            &self.edge_overrides,
+            self.arrows,
+        );
+        // The groups and edges are drawn in a fixed order, such that exports of the same layout are identical
+        let edges = layout
+            .groups
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .filter(|(id, _)| node_rects.contains_key(*id))
+            .flat_map(|(group_id, group)| {
+                let edges = group.edges.iter().sorted_by(|(a, _), (b, _)| a.cmp(b));
+                edges.filter_map(move |(edge_data, edge)| {
+                    if edge.exists.get(time) < 1.0
+                        || hidden_edge_types.contains(&edge_data.edge_type)
+                    {
+                        return None;
+                    }
+                    let edge_type = edge_types.get(&edge_data.edge_type)?;
+                    let target_group = layout.groups.get(&edge_data.to)?;
+                    if !node_rects.contains_key(&edge_data.to) || edge_type.width <= 0. {
+                        return None;
+                    }
+                    let mut points = vec![group.position.get(time) + edge.start_offset.get(time)];
+                    points.extend(
+                        edge.points
+                            .iter()
+                            .filter(|p| p.exists.get(time) >= 1.0)
+                            .map(|p| p.point.get(time)),
+                    );
+                    points.push(target_group.position.get(time) + edge.end_offset.get(time));
+
+                    let bounds = points
+                        .iter()
+                        .map(|p| (p.x, p.y, p.x, p.y))
+                        .reduce(|(x1, y1, x2, y2), (x3, y3, x4, y4)| {
+                            (x1.min(x3), y1.min(y3), x2.max(x4), y2.max(y4))
+                        })?;
+                    let bounds = Rectangle::new(
+                        bounds.0 - 1.,
+                        bounds.1 - 1.,
+                        bounds.2 - bounds.0 + 2.,
+                        bounds.3 - bounds.1 + 2.,
+                    );
+                    if !bounds.overlaps(&region) {
+                        return None;
+                    }
+
+                    let segment_count = points.len() - 1;
+                    let mut path = format!("M {}", pos(points[0]));
+                    for (index, (&start, &end)) in points.iter().tuple_windows().enumerate() {
+                        let is_middle = index == segment_count / 2;
+                        let mid_arrow = arrows == EdgeArrows::Middle && is_middle;
+                        if segment_count == 1 && edge.curve_offset.get(time) != 0. {
+                            path += &arc(start, end, edge.curve_offset.get(time), mid_arrow);
+                        } else {
+                            // For an even number of segments, the middle arrow is placed on a bend point instead
+                            if mid_arrow && segment_count % 2 == 1 {
+                                path += &format!(" L {}", pos((start + end) * 0.5));
+                            }
+                            path += &format!(" L {}", pos(end));
+                        }
+                    }
+
+                    let overrides =
+                        edge_overrides.get(&(*group_id, edge_data.to, edge_data.edge_type.index));
+                    let color = overrides
+                        .and_then(|style| style.color)
+                        .unwrap_or(edge_type.color);
+                    let width = overrides
+                        .and_then(|style| style.width)
+                        .unwrap_or(edge_type.width);
+                    // For an odd number of points the middle point is used, otherwise the middle of the middle segment
+                    let label = overrides
+                        .and_then(|style| style.label.clone())
+                        .map(|label| {
+                            let center = (points[segment_count / 2]
+                                + points[(segment_count + 1) / 2])
+                                * 0.5;
+                            format!(
+                                "\n    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                                center.x,
+                                -center.y,
+                                sanitize(label)
+                            )
+                        })
+                        .unwrap_or_default();
+                    let dash = if edge_type.dash_transparent > 0. {
+                        format!(
+                            " stroke-dasharray=\"{} {}\"",
+                            edge_type.dash_solid, edge_type.dash_transparent
+                        )
+                    } else {
+                        "".into()
+                    };
+                    let index = edge_data.edge_type.index;
+                    let marker = match arrows {
+                        EdgeArrows::None => "".into(),
+                        EdgeArrows::End => format!(" marker-end=\"url(#endArrow{})\"", index),
+                        EdgeArrows::Middle => format!(" marker-mid=\"url(#midArrow{})\"", index),
+                    };
+                    Some(format!(
+                        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"{}{}/>{}",
+                        path,
+                        to_svg_color(&color),
+                        width,
+                        dash,
+                        marker,
+                        label
+                    ))
+                })
+            })
+            .join("\n    ");
+
+        let markers = self
+            .edge_types
+            .keys()
+            .sorted_by_key(|edge_type| edge_type.index)
+            .map(|edge_type| {
+                let color = to_svg_color(&self.edge_types[edge_type].color);
+                let head = |name: &str, offset: f32| {
+                    format!(
+                        "<marker id=\"{}{}\" markerUnits=\"userSpaceOnUse\" orient=\"auto\" overflow=\"visible\">\
+                        <path d=\"M {} {} L {} 0 L {} {} Z\" fill=\"{}\"/></marker>",
+                        name,
+                        edge_type.index,
+                        offset - ARROW_SIZE,
+                        -0.5 * ARROW_SIZE,
+                        offset,
+                        offset - ARROW_SIZE,
+                        0.5 * ARROW_SIZE,
+                        color
+                    )
+                };
+                format!(
+                    "{}\n    {}",
+                    head("endArrow", -ARROW_END_OFFSET),
+                    head("midArrow", 0.5 * ARROW_SIZE)
+                )
+            })
+            .join("\n    ");
+
+        let nodes = layout
+            .groups
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .filter_map(|(id, group)| {
+                let rect = node_rects.get(id)?;
+                if !rect.overlaps(&region) {
+                    return None;
+                }
+                let style = group.style.get(time);
+                let radius = CORNER_RADIUS.min(0.5 * rect.width).min(0.5 * rect.height);
+                let mut out = vec![format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+                    rect.x,
+                    -(rect.y + rect.height),
+                    rect.width,
+                    rect.height,
+                    radius,
+                    fill(&style.get_color().into())
+                )];
+                let outline = style.get_outline_color();
+                if outline.3 > 0. {
+                    let inset = BORDER_OFFSET + 0.5 * BORDER_WIDTH;
+                    out.push(format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"{}\"/>",
+                        rect.x + inset,
+                        -(rect.y + rect.height - inset),
+                        rect.width - 2. * inset,
+                        rect.height - 2. * inset,
+                        (radius - inset).max(0.),
+                        to_svg_color(&Color(outline.0, outline.1, outline.2)),
+                        outline.3,
+                        BORDER_WIDTH
+                    ));
+                }
+                let label = style.get_label().filter(|_| group.label_exists.get(time) >= 1.0);
+                if let Some(label) = label {
+                    let center = Point {
+                        x: rect.x + 0.5 * rect.width,
+                        y: rect.y + 0.5 * rect.height,
+                    };
+                    let label_pos = center + group.label_offset.get(time);
+                    out.push(format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                        label_pos.x,
+                        -label_pos.y,
+                        sanitize(label)
+                    ));
+                }
+                Some(out.join("\n    "))
+            })
+            .join("\n    ");
+
+        let annotations = self
+            .annotations
+            .iter()
+            .filter(|annotation| annotation.exists.get(time) >= 1.0)
+            .map(|annotation| {
+                let pos = annotation.position.get(time);
+                let color = to_svg_color(&ANNOTATION_COLOR);
+                match &annotation.kind {
+                    AnnotationKind::Note(text) => format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                        pos.x,
+                        -pos.y,
+                        sanitize(text.clone())
+                    ),
+                    AnnotationKind::Arrow(delta) => format!(
+                        "<polygon points=\"{}\" fill=\"{}\"/>",
+                        get_arrow_outline(pos, *delta)
+                            .iter()
+                            .map(|p| format!("{},{}", p.x, -p.y))
+                            .join(" "),
+                        color
+                    ),
+                    AnnotationKind::Highlight(size) => format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>",
+                        pos.x - 0.5 * size.x,
+                        -(pos.y + 0.5 * size.y),
+                        size.x,
+                        size.y,
+                        color,
+                        HIGHLIGHT_OPACITY
+                    ),
+                    AnnotationKind::Stub(delta, label) => format!(
+                        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"{}\" stroke-dasharray=\"{}\"/>\n    \
+                        <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"hanging\">{}</text>",
+                        pos.x,
+                        -pos.y,
+                        pos.x + delta.x,
+                        -(pos.y + delta.y),
+                        color,
+                        STUB_OPACITY,
+                        0.5 * ARROW_WIDTH,
+                        STUB_DASH_LENGTH,
+                        pos.x + delta.x,
+                        -(pos.y + delta.y),
+                        sanitize(label.clone())
+                    ),
+                    AnnotationKind::Break(width, label) => format!(
+                        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n    \
+                        <text x=\"{}\" y=\"{}\" text-anchor=\"start\" dominant-baseline=\"middle\">{}</text>",
+                        get_break_zigzag(pos, *width)
+                            .iter()
+                            .map(|p| format!("{},{}", p.x, -p.y))
+                            .join(" "),
+                        color,
+                        0.5 * ARROW_WIDTH,
+                        pos.x + 0.5 * width + BREAK_LABEL_GAP,
+                        -pos.y,
+                        sanitize(label.clone())
+                    ),
+                }
+            })
+            .join("\n    ");
+
+        let legend = legend
+            .into_iter()
+            .map(|shape| match shape {
+                LegendShape::Rect { rect, color } => format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    rect.x,
+                    -(rect.y + rect.height),
+                    rect.width,
+                    rect.height,
+                    to_svg_color(&color)
+                ),
+                LegendShape::Text { position, text } => format!(
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"start\">{}</text>",
+                    position.x,
+                    -position.y,
+                    sanitize(text)
+                ),
+            })
+            .join("\n    ");
+
+        let page = self
+            .page
+            .iter()
+            .flat_map(|page| {
+                let marks = page.get_overlap_marks().into_iter().map(|(start, end)| {
+                    format!(
+                        "<path d=\"M {} {} L {} {}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-dasharray=\"{} {}\"/>",
+                        start.x,
+                        -start.y,
+                        end.x,
+                        -end.y,
+                        to_svg_color(&self.layer_colors.text),
+                        PAGE_MARK_WIDTH,
+                        PAGE_MARK_DASH,
+                        PAGE_MARK_DASH
+                    )
+                });
+                let position = page.get_label_position();
+                let label = format!(
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                    position.x,
+                    -position.y,
+                    sanitize(page.get_label())
+                );
+                marks.chain([label])
+            })
+            .join("\n    ");
+
+        self.output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}cm\" height=\"{}cm\">\n    \
+            <defs>\n    \
+            {}\n    \
+            </defs>\n    \
+            <g font-family=\"Roboto, sans-serif\" font-weight=\"bold\" font-size=\"{}\" dominant-baseline=\"central\" fill=\"{}\">\n    \
+            <!-- Layers -->\n    \
+            {}\n    \
+            <!-- Edges -->\n    \
+            {}\n    \
+            <!-- Nodes -->\n    \
+            {}\n    \
+            <!-- Annotations -->\n    \
+            {}\n    \
+            <!-- Legend -->\n    \
+            {}\n    \
+            <!-- Page -->\n    \
+            {}\n    \
+            </g>\n\
+            </svg>",
+            view.x,
+            -(view.y + view.height),
+            view.width,
+            view.height,
+            view.width,
+            view.height,
+            markers,
+            self.text_size,
+            to_svg_color(&self.text_color),
+            layers,
+            edges,
+            nodes,
+            annotations,
+            legend,
+            page
+        );
+    }
+
+    fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {}
+
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.edge_overrides = styles.clone();
+    }
+
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>) {
+        self.annotations = annotations.clone();
+    }
+}
+
+/// Creates a circular arc to the given end point, bulging sideways by the given curve offset like in the webgl edge shader
+fn arc(start: Point, end: Point, curve_offset: f32, split: bool) -> String {
+    let half_length = 0.5 * start.distance(&end);
+    let curve_width = curve_offset.abs().min(half_length);
+    let radius = (curve_width * curve_width + half_length * half_length) / (2. * curve_width);
+    // Positive offsets bulge to the left of the edge direction, which is a counter-clockwise sweep in svg coordinates
+    let sweep = if curve_offset > 0. { 0 } else { 1 };
+    let segment = |to: Point| format!(" A {} {} 0 0 {} {} {}", radius, radius, sweep, to.x, -to.y);
+    if split {
+        let delta = end - start;
+        let orth = Point {
+            x: -delta.y,
+            y: delta.x,
+        } * (curve_offset.signum() * curve_width / delta.length());
+        segment((start + end) * 0.5 + orth) + &segment(end)
+    } else {
+        segment(end)
+    }
+}
+
+fn to_svg_color(color: &Color) -> String {
+    format!(
+        "rgb({}, {}, {})",
+        (color.0 * 255.).round(),
+        (color.1 * 255.).round(),
+        (color.2 * 255.).round()
+    )
+}
+
+fn fill(color: &TransparentColor) -> String {
+    format!(
+        "fill=\"{}\" fill-opacity=\"{}\"",
+        to_svg_color(&Color(color.0, color.1, color.2)),
+        color.3
+    )
+}
+
+fn sanitize(text: String) -> String {
+    text.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+}
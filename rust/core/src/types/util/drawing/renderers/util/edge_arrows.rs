@@ -0,0 +1,9 @@
+/// Where arrows are drawn on the edges, to indicate their direction
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EdgeArrows {
+    None,
+    /// An arrowhead at the end of every edge
+    End,
+    /// An arrow halfway along every edge
+    Middle,
+}
@@ -0,0 +1,16 @@
+/// The sides of the diagram on which the level labels are shown
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LayerLabelSides {
+    Left,
+    Right,
+    Both,
+}
+
+impl LayerLabelSides {
+    pub fn has_left(&self) -> bool {
+        *self != LayerLabelSides::Right
+    }
+    pub fn has_right(&self) -> bool {
+        *self != LayerLabelSides::Left
+    }
+}
@@ -0,0 +1,24 @@
+/// How adjacent levels are paired up, such as the current and next state variables (x, x') of a transition relation
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LayerPairing {
+    /// Every level is shown on its own
+    Separate,
+    /// Every pair of levels is marked by brackets at the sides of the diagram
+    Bracketed,
+    /// Every pair of levels is marked by brackets and shares one background band
+    Merged,
+}
+
+impl LayerPairing {
+    pub fn has_brackets(&self) -> bool {
+        *self != LayerPairing::Separate
+    }
+    /// Retrieves the index of the background band that the layer with the given index belongs to
+    pub fn get_band(&self, index: usize) -> usize {
+        if *self == LayerPairing::Merged {
+            index / 2
+        } else {
+            index
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod Font;
+pub mod edge_arrows;
+pub mod layer_label_sides;
+pub mod layer_pairing;
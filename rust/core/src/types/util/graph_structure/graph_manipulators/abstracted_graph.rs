@@ -7,6 +7,7 @@ use std::{
 use oxidd::LevelNo;
 
 use crate::{
+    interface::NodeID,
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphStructure,
@@ -14,7 +15,6 @@ use crate::{
         storage::state_storage::StateStorage,
     },
     util::rc_refcell::MutRcRefCell,
-    wasm_interface::NodeID,
 };
 
 // A graph wrapper that abstracts over exact typeing
@@ -53,8 +53,12 @@ impl<T: DrawTag, NL: Clone, LL: Clone> AbstractedGraph<T, NL, LL> {
 }
 
 impl<T: DrawTag, NL: Clone, LL: Clone> StateStorage for AbstractedGraph<T, NL, LL> {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)
     }
     fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
         self.graph.write(stream)
@@ -1,20 +1,17 @@
-use std::{
-    collections::{HashMap, HashSet},
-    marker::PhantomData,
-};
+use std::{collections::HashSet, marker::PhantomData};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::Itertools;
 
 use crate::{
+    interface::NodeID,
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
         storage::state_storage::{Serializable, StateStorage},
     },
-    util::logging::console,
-    wasm_interface::NodeID,
+    util::{arena::ArenaMap, logging::console},
 };
 
 ///
@@ -26,7 +23,7 @@ pub struct ChildEdgeAdjuster<G: GraphStructure> {
     enabled: bool,
     event_writer: GraphEventsWriter,
     graph_events: GraphEventsReader,
-    replacement_cache: HashMap<NodeID, Option<Vec<(EdgeType<G::T>, NodeID)>>>,
+    replacement_cache: ArenaMap<NodeID, Option<Vec<(EdgeType<G::T>, NodeID)>>>,
 }
 
 impl<G: GraphStructure> ChildEdgeAdjuster<G> {
@@ -41,7 +38,7 @@ impl<G: GraphStructure> ChildEdgeAdjuster<G> {
             adjuster: replacer,
             enabled: true,
             event_writer: GraphEventsWriter::new(),
-            replacement_cache: HashMap::new(),
+            replacement_cache: ArenaMap::new(),
         }
     }
 
@@ -150,7 +147,7 @@ impl<G: GraphStructure> GraphStructure for ChildEdgeAdjuster<G> {
         if replacement_parent_nodes.len() == 0 {
             return regular_parent_nodes;
         }
-        console::log!("Get known parents");
+        console::log_trace!("Get known parents");
 
         // Filter out the parents that made replacements from the original results, and add newly calculated edges based on the child edges of the parents that made replacements
         regular_parent_nodes
@@ -227,8 +224,12 @@ where
         stream.write_u8(self.enabled as u8)?;
         Ok(())
     }
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)?;
 
         self.enabled = stream.read_u8()? > 0;
         Ok(())
@@ -7,13 +7,13 @@ use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::Itertools;
 
 use crate::{
+    interface::NodeID,
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
         storage::state_storage::{Serializable, StateStorage},
     },
-    wasm_interface::NodeID,
 };
 
 pub struct EdgeToAdjuster<G: GraphStructure> {
@@ -170,8 +170,12 @@ where
         }
         Ok(())
     }
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)?;
 
         let count = stream.read_u32::<LittleEndian>()?;
         let mut remove_edges = HashSet::new();
@@ -9,20 +9,23 @@ use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use oxidd::LevelNo;
 
 use crate::{
+    interface::{NodeGroupID, NodeID},
     types::util::{
         graph_structure::{
-            graph_structure::{DrawTag, GraphStructure},
+            graph_structure::{Change, DrawTag, EdgeType, GraphStructure},
             grouped_graph_structure::{EdgeCountData, GroupedGraphStructure},
         },
         storage::state_storage::StateStorage,
     },
     util::rc_refcell::MutRcRefCell,
-    wasm_interface::{NodeGroupID, NodeID},
 };
 
 pub struct GroupPresenceAdjuster<G: GroupedGraphStructure> {
     graph: MutRcRefCell<G>,
     hidden_groups: HashSet<NodeGroupID>,
+    /// If specified, only these groups are shown
+    focus: Option<HashSet<NodeGroupID>>,
+    hidden_edge_types: HashSet<EdgeType<G::T>>,
 }
 
 impl<G: GroupedGraphStructure> GroupPresenceAdjuster<G> {
@@ -33,6 +36,8 @@ impl<G: GroupedGraphStructure> GroupPresenceAdjuster<G> {
         GroupPresenceAdjuster {
             graph,
             hidden_groups: HashSet::new(),
+            focus: None,
+            hidden_edge_types: HashSet::new(),
         }
     }
 
@@ -43,6 +48,28 @@ impl<G: GroupedGraphStructure> GroupPresenceAdjuster<G> {
     pub fn hide(&mut self, group: NodeGroupID) {
         self.hidden_groups.insert(group);
     }
+
+    /// Restricts the shown groups to the given set of groups, or shows all groups again if none is given
+    pub fn set_focus(&mut self, groups: Option<HashSet<NodeGroupID>>) {
+        self.focus = groups;
+    }
+
+    /// Removes or restores all edges of the given type, such that layouts are computed without the removed edges
+    pub fn set_edge_type_presence(&mut self, edge_type: EdgeType<G::T>, present: bool) {
+        if present {
+            self.hidden_edge_types.remove(&edge_type);
+        } else {
+            self.hidden_edge_types.insert(edge_type);
+        }
+    }
+
+    fn is_shown(&self, group: &NodeGroupID) -> bool {
+        !self.hidden_groups.contains(group)
+            && self
+                .focus
+                .as_ref()
+                .map_or(true, |focus| focus.contains(group))
+    }
 }
 
 impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G> {
@@ -56,7 +83,7 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
             .read()
             .get_roots()
             .iter()
-            .filter(|node| !self.hidden_groups.contains(node))
+            .filter(|node| self.is_shown(node))
             .cloned()
             .collect()
     }
@@ -66,18 +93,27 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
             .read()
             .get_all_groups()
             .iter()
-            .filter(|node| !self.hidden_groups.contains(node))
+            .filter(|node| self.is_shown(node))
             .cloned()
             .collect()
     }
 
     fn get_hidden(&self) -> Vec<NodeGroupID> {
-        self.graph
-            .read()
+        let graph = self.graph.read();
+        let unfocused = match self.focus {
+            Some(ref focus) => graph
+                .get_all_groups()
+                .into_iter()
+                .filter(|group| !focus.contains(group))
+                .collect(),
+            None => Vec::new(),
+        };
+        graph
             .get_hidden()
             .iter()
             .chain(self.hidden_groups.iter())
             .cloned()
+            .chain(unfocused)
             .collect()
     }
 
@@ -94,7 +130,7 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
             .read()
             .get_parents(group)
             .into_iter()
-            .filter(|ed| !self.hidden_groups.contains(&ed.to))
+            .filter(|ed| self.is_shown(&ed.to) && !self.hidden_edge_types.contains(&ed.edge_type))
             .collect()
     }
 
@@ -103,7 +139,7 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
             .read()
             .get_children(group)
             .into_iter()
-            .filter(|ed| !self.hidden_groups.contains(&ed.to))
+            .filter(|ed| self.is_shown(&ed.to) && !self.hidden_edge_types.contains(&ed.edge_type))
             .collect()
     }
 
@@ -119,8 +155,8 @@ impl<G: GroupedGraphStructure> GroupedGraphStructure for GroupPresenceAdjuster<G
         self.graph.read().get_level_label(level)
     }
 
-    fn refresh(&mut self) {
-        self.graph.get().refresh();
+    fn refresh(&mut self) -> Vec<Change> {
+        self.graph.get().refresh()
     }
 
     fn create_node_tracker(&mut self) -> Self::Tracker {
@@ -139,14 +175,14 @@ impl<G: GroupedGraphStructure + StateStorage> StateStorage for GroupPresenceAdju
         Ok(())
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> Result<()> {
         self.hidden_groups.clear();
         let group_count = stream.read_u32::<LittleEndian>()?;
         for _ in 0..group_count {
             let group_id = stream.read_u32::<LittleEndian>()? as usize;
             self.hidden_groups.insert(group_id);
         }
-        self.graph.get().read(stream)?;
+        self.graph.get().read(stream, version)?;
         Ok(())
     }
 }
@@ -7,34 +7,40 @@ use std::{
 use oxidd::LevelNo;
 
 use crate::{
+    interface::{NodeGroupID, NodeID},
     types::util::{
         graph_structure::{
-            graph_structure::DrawTag,
+            graph_structure::{Change, DrawTag},
             grouped_graph_structure::{EdgeCountData, GroupedGraphStructure},
         },
         storage::state_storage::StateStorage,
     },
     util::rc_refcell::MutRcRefCell,
-    wasm_interface::{NodeGroupID, NodeID},
 };
 
 pub struct GroupLabelAdjuster<NGL, NLL, G: GroupedGraphStructure> {
     graph: MutRcRefCell<G>,
-    node_adjuster: Box<dyn Fn(G::GL) -> NGL>,
-    level_adjuster: Box<dyn Fn(G::LL) -> NLL>,
+    node_adjuster: Box<dyn Fn(NodeGroupID, G::GL) -> NGL>,
+    level_adjuster: Box<dyn Fn(LevelNo, G::LL) -> NLL>,
     new_group_label: PhantomData<NGL>,
     new_level_label: PhantomData<NLL>,
 }
 
 impl<G: GroupedGraphStructure, NGL, NLL> GroupLabelAdjuster<NGL, NLL, G> {
-    pub fn new<A: Fn(G::GL) -> NGL + 'static, B: Fn(G::LL) -> NLL + 'static>(
+    pub fn new<
+        A: Fn(NodeGroupID, G::GL) -> NGL + 'static,
+        B: Fn(LevelNo, G::LL) -> NLL + 'static,
+    >(
         graph: G,
         node_adjuster: A,
         level_adjuster: B,
     ) -> GroupLabelAdjuster<NGL, NLL, G> {
         GroupLabelAdjuster::new_shared(MutRcRefCell::new(graph), node_adjuster, level_adjuster)
     }
-    pub fn new_shared<A: Fn(G::GL) -> NGL + 'static, B: Fn(G::LL) -> NLL + 'static>(
+    pub fn new_shared<
+        A: Fn(NodeGroupID, G::GL) -> NGL + 'static,
+        B: Fn(LevelNo, G::LL) -> NLL + 'static,
+    >(
         graph: MutRcRefCell<G>,
         node_adjuster: A,
         level_adjuster: B,
@@ -72,7 +78,7 @@ impl<G: GroupedGraphStructure, NGL, NLL> GroupedGraphStructure for GroupLabelAdj
     }
 
     fn get_group_label(&self, node: NodeID) -> NGL {
-        (self.node_adjuster)(self.graph.read().get_group_label(node))
+        (self.node_adjuster)(node, self.graph.read().get_group_label(node))
     }
 
     fn get_parents(&self, group: NodeGroupID) -> Vec<EdgeCountData<G::T>> {
@@ -92,10 +98,10 @@ impl<G: GroupedGraphStructure, NGL, NLL> GroupedGraphStructure for GroupLabelAdj
     }
 
     fn get_level_label(&self, level: LevelNo) -> NLL {
-        (self.level_adjuster)(self.graph.read().get_level_label(level))
+        (self.level_adjuster)(level, self.graph.read().get_level_label(level))
     }
 
-    fn refresh(&mut self) {
+    fn refresh(&mut self) -> Vec<Change> {
         self.graph.get().refresh()
     }
 
@@ -112,8 +118,8 @@ impl<G: GroupedGraphStructure + StateStorage, NGL, NLL> StateStorage
         Ok(())
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        self.graph.get().read(stream)?;
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> Result<()> {
+        self.graph.get().read(stream, version)?;
         Ok(())
     }
 }
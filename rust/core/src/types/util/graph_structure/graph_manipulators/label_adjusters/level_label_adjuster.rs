@@ -7,15 +7,15 @@ use std::{
 use oxidd::LevelNo;
 
 use crate::{
+    interface::{NodeGroupID, NodeID},
     types::util::{
         graph_structure::{
-            graph_structure::DrawTag,
+            graph_structure::{Change, DrawTag},
             grouped_graph_structure::{EdgeCountData, GroupedGraphStructure},
         },
         storage::state_storage::StateStorage,
     },
     util::rc_refcell::MutRcRefCell,
-    wasm_interface::{NodeGroupID, NodeID},
 };
 
 pub struct LevelLabelAdjuster<NLL, G: GroupedGraphStructure> {
@@ -86,7 +86,7 @@ impl<G: GroupedGraphStructure, NLL> GroupedGraphStructure for LevelLabelAdjuster
         (self.adjuster)(self.graph.read().get_level_label(level))
     }
 
-    fn refresh(&mut self) {
+    fn refresh(&mut self) -> Vec<Change> {
         self.graph.get().refresh()
     }
 
@@ -101,8 +101,8 @@ impl<G: GroupedGraphStructure + StateStorage, NLL> StateStorage for LevelLabelAd
         Ok(())
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
-        self.graph.get().read(stream)?;
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> Result<()> {
+        self.graph.get().read(stream, version)?;
         Ok(())
     }
 }
@@ -6,4 +6,7 @@ pub mod label_adjusters;
 pub mod node_presence_adjuster;
 pub mod pointer_node_adjuster;
 pub mod rc_graph;
+pub mod redundant_node_adjuster;
+pub mod sourced_node_ids;
+pub mod terminal_bin_adjuster;
 pub mod terminal_level_adjuster;
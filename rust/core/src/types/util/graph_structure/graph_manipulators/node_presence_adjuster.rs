@@ -12,18 +12,22 @@ use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::{Either, Itertools};
 use multimap::MultiMap;
 use oxidd::{LevelNo, NodeID};
-use wasm_bindgen::prelude::*;
+use regex::Regex;
 
 use crate::{
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
-        storage::state_storage::{Serializable, StateStorage},
+        storage::state_storage::{
+            read_node_id, write_node_id, Serializable, StateStorage, LEGACY_STATE_FORMAT_VERSION,
+        },
     },
-    util::{free_id_manager::FreeIdManager, logging::console},
+    util::{arena::ArenaMap, free_id_manager::FreeIdManager, logging::console},
 };
 
+use super::sourced_node_ids::SourcedNodeIDs;
+
 /// The NodePresenceAdjuster allows nodes to be hidden or duplicated in order to improve structural properties of the graph for better layouting.
 
 // We distinguish 2 different nodeID kinds:
@@ -33,21 +37,25 @@ use crate::{
 // The source node IDs are distinguished into 2 labeled kinds:
 // - left node IDs, corresponding to the underlying graph we are wrapping
 // - right node IDs, corresponding to the created virtual nodes
+//
+// Values on the right side are only used for nodes that are being adjusted to be duplicated. Output IDs of both sides are allocated by an explicit translation table, such that copies never collide with nodes of the underlying graph, whatever IDs that graph uses.
 pub struct NodePresenceAdjuster<G: GraphStructure> {
     graph: G,
     event_writer: GraphEventsWriter,
     graph_events: GraphEventsReader,
 
     /*  All the adjustment data */
-    adjustments: HashMap<NodeID, PresenceGroups<G::T>>, // Specifies the adjustments for the left source node ID
-    sources: HashMap<NodeID, NodeID>, // Maps the right source nodeID to the corresponding left source node ID
+    adjustments: ArenaMap<NodeID, PresenceGroups<G::T>>, // Specifies the adjustments for the left source node ID
+    sources: ArenaMap<NodeID, NodeID>, // Maps the right source nodeID to the corresponding left source node ID
     images: MultiMap<NodeID, NodeID>, // Maps the left source nodeID to all of the corresponding right source node IDs
     // node_group: HashMap<NodeID, PresenceGroup>, // Maps the left source nodeID to the presence group it represents
     replacements: HashMap<(NodeID, EdgeConstraint<G::T>, NodeID), NodeID>, // For a combination of parent output nodeID and a child left source nodeID, the replacement child right source nodeID
-    parent_nodes: HashMap<NodeID, HashSet<NodeID>>, // The parent nodes (output node IDs) of a right source nodeID.
-    known_parents: HashMap<NodeID, Vec<(EdgeType<G::T>, NodeID)>>, // The parents (output node IDs) and edge type of a right source nodeID. Note that these are the known parents, because we may for sure these are the only parents that can exist for the created node, but can not be sure these are the only edge types.
-    children: HashMap<NodeID, Vec<(EdgeType<G::T>, NodeID)>>, // The children (output node IDs) and edge type of a output nodeID
+    parent_nodes: ArenaMap<NodeID, HashSet<NodeID>>, // The parent nodes (output node IDs) of a right source nodeID.
+    known_parents: ArenaMap<NodeID, Vec<(EdgeType<G::T>, NodeID)>>, // The parents (output node IDs) and edge type of a right source nodeID. Note that these are the known parents, because we may for sure these are the only parents that can exist for the created node, but can not be sure these are the only edge types.
+    children: ArenaMap<NodeID, Vec<(EdgeType<G::T>, NodeID)>>, // The children (output node IDs) and edge type of a output nodeID
     free_id: FreeIdManager<usize>,
+    ids: SourcedNodeIDs,
+    threshold_duplicates: Vec<NodeID>, // The left source node IDs that were duplicated for having a low in-degree
 }
 
 #[derive(Eq, PartialEq, Clone)]
@@ -84,8 +92,7 @@ impl<T: DrawTag> Display for EdgeConstraint<T> {
     }
 }
 
-#[wasm_bindgen]
-#[derive(Eq, PartialEq, Clone)]
+#[derive(Eq, PartialEq, Clone, Debug)]
 pub enum PresenceRemainder {
     // Show this unique terminal the regular way (default)
     Show,
@@ -97,19 +104,31 @@ pub enum PresenceRemainder {
     DuplicateParent,
 }
 
-// Values on the right side should only be used for nodes that are being adjusted to be duplicated, everything else retains the left version of the ID
-type SourcedNodeID = Either<NodeID, NodeID>;
-fn to_sourced(id: NodeID) -> SourcedNodeID {
-    if id % 2 == 0 {
-        Either::Left(id / 2)
-    } else {
-        Either::Right(id / 2)
-    }
+/// A condition on nodes, by which presence adjustments are applied in bulk. Nodes match if they meet every given criterion.
+#[derive(Clone, Default)]
+pub struct PresencePredicate {
+    level_range: Option<(LevelNo, LevelNo)>,
+    label_pattern: Option<String>,
+    min_in_degree: Option<usize>,
 }
-fn from_sourced(id: SourcedNodeID) -> NodeID {
-    match id {
-        Either::Left(id) => id * 2,
-        Either::Right(id) => id * 2 + 1,
+impl PresencePredicate {
+    pub fn new() -> PresencePredicate {
+        PresencePredicate::default()
+    }
+    /// Only matches the nodes in the given inclusive range of levels
+    pub fn with_level_range(mut self, min: LevelNo, max: LevelNo) -> PresencePredicate {
+        self.level_range = Some((min, max));
+        self
+    }
+    /// Only matches the nodes whose level label, the name of their variable, matches the given regular expression
+    pub fn with_label_pattern(mut self, pattern: String) -> PresencePredicate {
+        self.label_pattern = Some(pattern);
+        self
+    }
+    /// Only matches the nodes with at least the given number of incoming edges
+    pub fn with_min_in_degree(mut self, degree: usize) -> PresencePredicate {
+        self.min_in_degree = Some(degree);
+        self
     }
 }
 
@@ -119,14 +138,16 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
             graph_events: graph.create_event_reader(),
             graph,
             event_writer: GraphEventsWriter::new(),
-            adjustments: HashMap::new(),
-            sources: HashMap::new(),
+            adjustments: ArenaMap::new(),
+            sources: ArenaMap::new(),
             images: MultiMap::new(),
             replacements: HashMap::new(),
-            parent_nodes: HashMap::new(),
-            known_parents: HashMap::new(),
-            children: HashMap::new(),
+            parent_nodes: ArenaMap::new(),
+            known_parents: ArenaMap::new(),
+            children: ArenaMap::new(),
             free_id: FreeIdManager::new(0),
+            ids: SourcedNodeIDs::new(),
+            threshold_duplicates: Vec::new(),
         }
     }
 
@@ -161,7 +182,7 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
         }
 
         // Create an event for the replaced node
-        let owner_out = from_sourced(Either::Left(owner));
+        let owner_out = self.ids.from_sourced(Either::Left(owner));
         if presence.remainder == PresenceRemainder::Show {
             self.add_insert_node_events(owner_out, owner_out);
         }
@@ -172,6 +193,105 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
         self.adjustments.get(&owner).cloned()
     }
 
+    /// Retrieves the presence remainder of every adjusted node, keyed by the source nodes of the underlying graph. Parent groups refer to output nodes that only exist in this session, and are hence left out.
+    pub fn get_source_presences(&self) -> Vec<(NodeID, PresenceRemainder)> {
+        self.adjustments
+            .iter()
+            .flat_map(|(&owner, presence)| {
+                self.graph
+                    .local_nodes_to_sources(vec![owner])
+                    .into_iter()
+                    .map(|source| (source, presence.remainder.clone()))
+            })
+            .sorted_by_key(|(source, _)| *source)
+            .collect()
+    }
+
+    /// Sets the presence remainder of the nodes representing the given source node of the underlying graph
+    pub fn set_source_presence(&mut self, source: NodeID, remainder: PresenceRemainder) {
+        for node in self.graph.source_nodes_to_local(vec![source]) {
+            let out_node = self.ids.from_sourced(Either::Left(node));
+            self.set_node_presence(out_node, PresenceGroups::remainder(remainder.clone()));
+        }
+    }
+
+    /// Duplicates every inner node with at least 2 but fewer than `threshold` incoming edges, such that each of these edges leads to a copy of its own, which reduces edge crossings at the cost of more nodes. The nodes duplicated for a previous threshold are shown again unless they were adjusted since, hence a threshold of at most 2 removes the duplication. Returns the number of duplicated nodes and created copies.
+    pub fn set_duplication_threshold(&mut self, threshold: usize) -> (usize, usize) {
+        let show = PresenceGroups::remainder(PresenceRemainder::Show);
+        let duplicate = PresenceGroups::remainder(PresenceRemainder::Duplicate);
+        for node in std::mem::take(&mut self.threshold_duplicates) {
+            if self.adjustments.get(&node) == Some(&duplicate) {
+                self.set_node_presence(self.ids.from_sourced(Either::Left(node)), show.clone());
+            }
+        }
+
+        let terminals = self.graph.get_terminals();
+        let nodes = self
+            .discover_nodes()
+            .into_iter()
+            .filter(|node| {
+                let adjusted = self.adjustments.get(node).is_some_and(|presence| *presence != show);
+                !terminals.contains(node) && !adjusted
+            })
+            .collect_vec();
+        let graph = &mut self.graph;
+        let nodes = nodes
+            .into_iter()
+            .filter(|&node| (2..threshold).contains(&graph.get_known_parents(node).len()))
+            .sorted()
+            .collect_vec();
+        for &node in &nodes {
+            self.set_node_presence(self.ids.from_sourced(Either::Left(node)), duplicate.clone());
+        }
+
+        let copies = nodes
+            .iter()
+            .map(|node| self.images.get_vec(node).map_or(0, |images| images.len()))
+            .sum();
+        self.threshold_duplicates = nodes;
+        (self.threshold_duplicates.len(), copies)
+    }
+
+    /// Discovers all nodes of the underlying graph that are reachable from its roots, such that their known parents are complete
+    fn discover_nodes(&mut self) -> HashSet<NodeID> {
+        let mut nodes = HashSet::new();
+        let mut queue = self.graph.get_roots();
+        while let Some(node) = queue.pop() {
+            if nodes.insert(node) {
+                queue.extend(self.graph.get_children(node).into_iter().map(|(_, child)| child));
+            }
+        }
+        nodes
+    }
+
+    /// Retrieves the children of the given node that are hidden, as source node IDs. Hidden terminals may be left out, since hiding these is a common convention rather than a partial view.
+    pub fn get_hidden_children(
+        &mut self,
+        out_node: NodeID,
+        include_terminals: bool,
+    ) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        let owner = self.get_owner_id(out_node);
+        let terminals = self.graph.get_terminals();
+        self.graph
+            .get_children(owner)
+            .into_iter()
+            .filter(|(edge_type, child)| {
+                let replaced = self
+                    .replacements
+                    .contains_key(&(out_node, EdgeConstraint::Exact(*edge_type), *child))
+                    || self
+                        .replacements
+                        .contains_key(&(out_node, EdgeConstraint::Any, *child));
+                let hidden = self
+                    .adjustments
+                    .get(child)
+                    .is_some_and(|adjustment| adjustment.remainder == PresenceRemainder::Hide);
+                !replaced && hidden && (include_terminals || !terminals.contains(child))
+            })
+            .collect()
+    }
+
     fn update_children_of_parents(&mut self, left_node_id: NodeID) {
         let source_parents = self.graph.get_known_parents(left_node_id);
         let parents = source_parents
@@ -208,14 +328,14 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
                             .write(Change::NodeConnectionsChange { node: node_copy });
 
                         self.update_children(node_copy);
-                        if let Either::Right(copy_id) = to_sourced(node_copy) {
+                        if let Some(Either::Right(copy_id)) = self.ids.to_sourced(node_copy) {
                             self.update_parents(copy_id);
                         }
                     }
                 }
                 Change::NodeRemoval { node } => {
                     for node_copy in self.get_all_copies(node) {
-                        if let Either::Right(copy_id) = to_sourced(node_copy) {
+                        if let Some(Either::Right(copy_id)) = self.ids.to_sourced(node_copy) {
                             self.delete_replacement(copy_id);
                         } else {
                             self.event_writer
@@ -233,7 +353,7 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
                 }
                 Change::ParentDiscover { child } => {
                     self.event_writer.write(Change::ParentDiscover {
-                        child: from_sourced(Either::Right(child)),
+                        child: self.ids.from_sourced(Either::Left(child)),
                     });
                 }
             }
@@ -271,14 +391,15 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
     }
 
     fn get_owner_id(&self, id: NodeID) -> NodeID {
-        match to_sourced(id) {
-            Either::Left(id) => id,
-            Either::Right(id) => {
+        match self.ids.to_sourced(id) {
+            Some(Either::Left(id)) => id,
+            Some(Either::Right(id)) => {
                 let Some(original_id) = self.sources.get(&id) else {
                     return 0; // Case should not be reachable
                 };
                 *original_id
             }
+            None => 0, // Case should not be reachable
         }
     }
     fn create_replacement(
@@ -290,8 +411,9 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
         self.create_replacement_without_events(parents, child_to_be_replaced, id);
 
         // Create a creation event
-        let out_id = from_sourced(Either::Right(id));
-        self.add_insert_node_events(out_id, from_sourced(Either::Left(child_to_be_replaced)));
+        let out_id = self.ids.from_sourced(Either::Right(id));
+        let source_out = self.ids.from_sourced(Either::Left(child_to_be_replaced));
+        self.add_insert_node_events(out_id, source_out);
 
         id
     }
@@ -315,14 +437,14 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
 
         // Calculate the connections
         self.update_parents(id);
-        let out_id = from_sourced(Either::Right(id));
+        let out_id = self.ids.from_sourced(Either::Right(id));
         self.update_children(out_id);
 
         id
     }
 
     fn delete_replacement(&mut self, node: NodeID) {
-        let out_node_id = from_sourced(Either::Right(node));
+        let out_node_id = self.ids.from_sourced(Either::Right(node));
         let parents = self.get_known_parents(out_node_id);
         let Some(&source) = self.sources.get(&node) else {
             return;
@@ -351,13 +473,18 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
 
         self.event_writer
             .write(Change::NodeRemoval { node: out_node_id });
+        self.ids.remove(Either::Right(node));
     }
 
     fn update_parents(&mut self, right_node_id: NodeID) {
-        let source_id = self.get_owner_id(from_sourced(Either::Right(right_node_id)));
+        let source_id = self.get_owner_id(self.ids.from_sourced(Either::Right(right_node_id)));
 
         let parent_images: MultiMap<NodeID, NodeID> = {
-            let parent_nodes = self.parent_nodes.get(&right_node_id).unwrap();
+            let Some(parent_nodes) = self.parent_nodes.get(&right_node_id) else {
+                // Every copy is created together with its parents, so this indicates an internal error
+                console::log_error!("The copy {} has no registered parents", right_node_id);
+                return;
+            };
             parent_nodes
                 .iter()
                 .map(|&parent| (self.get_owner_id(parent), parent))
@@ -420,14 +547,14 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
         let mut out = Vec::new();
         // Analyze the children and store them for future use
         for (edge_type, child) in children {
-            let out_child = from_sourced(Either::Left(child));
+            let out_child = self.ids.from_sourced(Either::Left(child));
             let remainder = {
                 if let Some(&replacement) =
                     self.replacements
                         .get(&(out_node_id, EdgeConstraint::Exact(edge_type), child))
                 {
                     self.update_parents(replacement);
-                    out.push((edge_type, from_sourced(Either::Right(replacement))));
+                    out.push((edge_type, self.ids.from_sourced(Either::Right(replacement))));
                     continue;
                 }
 
@@ -436,7 +563,7 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
                         .get(&(out_node_id, EdgeConstraint::Any, child))
                 {
                     self.update_parents(replacement);
-                    out.push((edge_type, from_sourced(Either::Right(replacement))));
+                    out.push((edge_type, self.ids.from_sourced(Either::Right(replacement))));
                     continue;
                 }
 
@@ -450,34 +577,34 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
             match remainder {
                 PresenceRemainder::Show => out.push((edge_type, out_child)),
                 PresenceRemainder::Hide => {}
-                PresenceRemainder::Duplicate => out.push((
-                    edge_type,
-                    from_sourced(Either::Right(self.create_replacement(
+                PresenceRemainder::Duplicate => {
+                    let replacement = self.create_replacement(
                         Vec::from([(EdgeConstraint::Exact(edge_type), out_node_id)]),
                         child,
-                    ))),
-                )),
-                PresenceRemainder::DuplicateParent => out.push((
-                    edge_type,
-                    from_sourced(Either::Right(self.create_replacement(
+                    );
+                    out.push((edge_type, self.ids.from_sourced(Either::Right(replacement))));
+                }
+                PresenceRemainder::DuplicateParent => {
+                    let replacement = self.create_replacement(
                         Vec::from([(EdgeConstraint::Any, out_node_id)]),
                         child,
-                    ))),
-                )),
+                    );
+                    out.push((edge_type, self.ids.from_sourced(Either::Right(replacement))));
+                }
             }
         }
         self.children.insert(out_node_id, out);
     }
 
     fn get_all_copies(&self, left_source_node: NodeID) -> Vec<NodeID> {
-        let source_out = from_sourced(Either::Left(left_source_node));
+        let source_out = self.ids.from_sourced(Either::Left(left_source_node));
         let maybe_images = self.images.get_vec(&left_source_node).cloned();
         if let Some(images) = maybe_images {
             let mut out_images = vec![source_out];
             out_images.extend(
                 images
                     .into_iter()
-                    .map(|image| from_sourced(Either::Right(image))),
+                    .map(|image| self.ids.from_sourced(Either::Right(image))),
             );
             out_images
         } else {
@@ -486,6 +613,46 @@ impl<G: GraphStructure> NodePresenceAdjuster<G> {
     }
 }
 
+impl<G: GraphStructure<LL = String>> NodePresenceAdjuster<G> {
+    /// Sets the presence remainder of all nodes of the underlying graph that match the predicate, discovering the whole graph such that in-degrees are complete. Returns the number of adjusted nodes, or an error if the label pattern is invalid.
+    pub fn set_presence_where(
+        &mut self,
+        predicate: &PresencePredicate,
+        remainder: PresenceRemainder,
+    ) -> Result<usize, String> {
+        let label_regex = predicate
+            .label_pattern
+            .as_ref()
+            .map(|pattern| Regex::new(pattern))
+            .transpose()
+            .map_err(|err| err.to_string())?;
+
+        let nodes = self.discover_nodes();
+        let graph = &mut self.graph;
+        let matches = nodes
+            .into_iter()
+            .filter(|&node| {
+                let level = graph.get_level(node);
+                predicate
+                    .level_range
+                    .map_or(true, |(min, max)| min <= level && level <= max)
+                    && label_regex
+                        .as_ref()
+                        .map_or(true, |regex| regex.is_match(&graph.get_level_label(level)))
+                    && predicate
+                        .min_in_degree
+                        .map_or(true, |degree| graph.get_known_parents(node).len() >= degree)
+            })
+            .sorted()
+            .collect_vec();
+        for &node in &matches {
+            let out_node = self.ids.from_sourced(Either::Left(node));
+            self.set_node_presence(out_node, PresenceGroups::remainder(remainder.clone()));
+        }
+        Ok(matches.len())
+    }
+}
+
 #[derive(PartialEq, Eq, Clone)]
 pub struct PresenceLabel<LL> {
     pub original_label: LL,
@@ -500,7 +667,7 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
         self.graph
             .get_roots()
             .iter()
-            .map(|&root| from_sourced(Either::Left(root)))
+            .map(|&root| self.ids.from_sourced(Either::Left(root)))
             .collect()
     }
     fn get_terminals(&self) -> Vec<NodeID> {
@@ -513,8 +680,8 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
 
     fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
         self.process_graph_changes();
-        let parents = match to_sourced(node) {
-            Either::Left(id) => {
+        let parents = match self.ids.to_sourced(node) {
+            Some(Either::Left(id)) => {
                 let known_parents = self.graph.get_known_parents(id);
 
                 // Check if this node may be shown at all (only adjusted nodes with remainder=Show can get shown themselves, instead of a copy)
@@ -530,7 +697,7 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
                 // Filter parents to remove any parents that use a replacement node instead
                 known_parents
                     .into_iter()
-                    .map(|(edge, parent)| (edge, from_sourced(Either::Left(parent))))
+                    .map(|(edge, parent)| (edge, self.ids.from_sourced(Either::Left(parent))))
                     .filter(|&(edge, out_parent)| {
                         let replaced = self.replacements.contains_key(&(
                             out_parent,
@@ -545,11 +712,12 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
                     })
                     .collect()
             }
-            Either::Right(id) => self
+            Some(Either::Right(id)) => self
                 .known_parents
                 .get(&id)
                 .cloned()
                 .unwrap_or_else(|| Vec::new()),
+            None => Vec::new(),
         };
         parents
     }
@@ -560,12 +728,12 @@ impl<G: GraphStructure> GraphStructure for NodePresenceAdjuster<G> {
             return children.clone();
         }
 
-        match to_sourced(node) {
-            Either::Left(_) => {
+        match self.ids.to_sourced(node) {
+            Some(Either::Left(_)) => {
                 self.update_children(node);
-                return self.children.get(&node).cloned().unwrap();
+                return self.children.get(&node).cloned().unwrap_or_default();
             }
-            Either::Right(_) => {
+            Some(Either::Right(_)) | None => {
                 // This should not be able to happen, since any such node should have registered children
                 return Vec::new();
             }
@@ -638,8 +806,8 @@ where
         self.graph.write(stream)?;
         let adjustment_count = self.adjustments.len();
         stream.write_u32::<LittleEndian>(adjustment_count as u32)?;
-        for (&node_id, presence) in &self.adjustments {
-            stream.write_u32::<LittleEndian>(node_id as u32)?;
+        for (&node_id, presence) in self.adjustments.iter() {
+            write_node_id(&self.graph, node_id, stream)?;
 
             stream.write_u8(match presence.remainder {
                 PresenceRemainder::Hide => 0,
@@ -655,7 +823,7 @@ where
                 stream.write_u32::<LittleEndian>(group_size as u32)?;
 
                 for (constraint, parent) in group {
-                    stream.write_u32::<LittleEndian>(*parent as u32)?;
+                    write_node_id(self, *parent, stream)?;
                     write_constraint(stream, constraint)?;
                 }
             }
@@ -664,16 +832,20 @@ where
         let replacement_count = self.replacements.len();
         stream.write_u32::<LittleEndian>(replacement_count as u32)?;
         for ((parent, constraint, node), replacement) in &self.replacements {
-            stream.write_u32::<LittleEndian>(*parent as u32)?;
+            write_node_id(self, *parent, stream)?;
             write_constraint(stream, constraint)?;
-            stream.write_u32::<LittleEndian>(*node as u32)?;
+            write_node_id(&self.graph, *node, stream)?;
             stream.write_u32::<LittleEndian>(*replacement as u32)?;
         }
 
         Ok(())
     }
 
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
         let read_constraint =
             |stream: &mut std::io::Cursor<&Vec<u8>>| -> std::io::Result<EdgeConstraint<G::T>> {
                 Ok(match stream.read_u8()? {
@@ -686,12 +858,24 @@ where
                 })
             };
 
-        self.graph.read(stream)?;
+        // Parents are output nodes, which legacy states stored by their ID in the underlying graph
+        let read_parent = |adjuster: &Self,
+                           stream: &mut std::io::Cursor<&Vec<u8>>|
+         -> std::io::Result<Option<NodeID>> {
+            if version == LEGACY_STATE_FORMAT_VERSION {
+                let parent = read_node_id(&adjuster.graph, stream, version)?;
+                Ok(parent.map(|parent| adjuster.ids.from_sourced(Either::Left(parent))))
+            } else {
+                read_node_id(adjuster, stream, version)
+            }
+        };
+
+        self.graph.read(stream, version)?;
         let adjustment_count = stream.read_u32::<LittleEndian>()?;
 
         let mut adjustments = HashMap::new();
         for _ in 0..adjustment_count {
-            let node_id = stream.read_u32::<LittleEndian>()? as usize;
+            let node_id = read_node_id(&self.graph, stream, version)?;
             let remainder = match stream.read_u8()? {
                 0 => PresenceRemainder::Hide,
                 1 => PresenceRemainder::Show,
@@ -705,16 +889,21 @@ where
                 let group_size = stream.read_u32::<LittleEndian>()?;
                 let mut group = Vec::new();
                 for _ in 0..group_size {
-                    let parent = stream.read_u32::<LittleEndian>()? as usize;
+                    let parent = read_parent(self, stream)?;
                     let constraint = read_constraint(stream)?;
-                    group.push((constraint, parent));
+                    if let Some(parent) = parent {
+                        group.push((constraint, parent));
+                    }
                 }
                 groups.push(group);
             }
 
             let group = PresenceGroups { groups, remainder };
 
-            adjustments.insert(node_id, group);
+            // Adjustments of nodes whose source is no longer part of the graph are dropped
+            if let Some(node_id) = node_id {
+                adjustments.insert(node_id, group);
+            }
         }
 
         let replacement_count = stream.read_u32::<LittleEndian>()?;
@@ -723,10 +912,13 @@ where
             HashMap<NodeID, Vec<(EdgeConstraint<G::T>, NodeID)>>,
         > = HashMap::new();
         for _ in 0..replacement_count {
-            let parent = stream.read_u32::<LittleEndian>()? as usize;
+            let parent = read_parent(self, stream)?;
             let constraint = read_constraint(stream)?;
-            let node = stream.read_u32::<LittleEndian>()? as usize;
+            let node = read_node_id(&self.graph, stream, version)?;
             let replacement = stream.read_u32::<LittleEndian>()? as usize;
+            let (Some(parent), Some(node)) = (parent, node) else {
+                continue;
+            };
             replacements
                 .entry(node)
                 .or_insert_with(HashMap::new)
@@ -735,6 +927,10 @@ where
                 .push((constraint, parent));
         }
 
+        for (&copy, _) in self.sources.iter() {
+            self.ids.remove(Either::Right(copy));
+        }
+        self.free_id = FreeIdManager::new(0);
         self.known_parents.clear();
         self.children.clear();
         self.adjustments.clear();
@@ -742,6 +938,7 @@ where
         self.sources.clear();
         self.parent_nodes.clear();
         self.replacements.clear();
+        self.threshold_duplicates.clear();
         for (node, adjustment) in adjustments.clone() {
             let node_replacements = replacements
                 .remove_entry(&node)
@@ -750,6 +947,7 @@ where
 
             self.adjustments.insert(node, adjustment);
             for (replacement, parents) in node_replacements {
+                self.free_id.claim(replacement);
                 self.create_replacement_without_events(parents, node, replacement);
             }
             self.update_children_of_parents(node);
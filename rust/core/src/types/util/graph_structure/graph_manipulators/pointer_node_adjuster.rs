@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashSet,
     iter::repeat,
     marker::PhantomData,
     ops::Deref,
@@ -15,9 +15,11 @@ use crate::{
         },
         storage::state_storage::StateStorage,
     },
-    util::{free_id_manager::FreeIdManager, logging::console},
+    util::{arena::ArenaMap, free_id_manager::FreeIdManager, logging::console},
 };
 
+use super::sourced_node_ids::SourcedNodeIDs;
+
 /// The LabelNodeAdjuster inserts new nodes with some label text to be used as pointers, according to pointer labels provided for each node
 ///
 // We distinguish 2 different nodeID kinds:
@@ -27,6 +29,8 @@ use crate::{
 // The source node IDs are distinguished into 2 labeled kinds:
 // - left node IDs, corresponding to the underlying graph we are wrapping
 // - right node IDs, corresponding to the created pointer nodes
+//
+// Output node IDs are translated to source node IDs by an explicit table, since no encoding of both kinds into a single ID can cover the full ID space of the underlying graph
 pub struct PointerNodeAdjuster<G: GraphStructure> {
     graph: G,
     event_writer: GraphEventsWriter,
@@ -36,29 +40,16 @@ pub struct PointerNodeAdjuster<G: GraphStructure> {
     transfer_root_pointers: bool,
     dummy_level_label: G::LL,
 
-    pointers_of: HashMap<NodeID, HashSet<NodeID>>, // Maps left nodes to right nodes
-    pointers: HashMap<NodeID, PointerNode>,        // Maps right nodes to their pointer data
+    pointers_of: ArenaMap<NodeID, HashSet<NodeID>>, // Maps left nodes to right nodes
+    pointers: ArenaMap<NodeID, PointerNode>,        // Maps right nodes to their pointer data
     free_id: FreeIdManager<usize>,
+    ids: SourcedNodeIDs,
 }
 
 pub trait WithPointerLabels {
     fn get_pointer_labels(&self) -> Vec<String>;
 }
 
-type SourcedNodeID = Either<NodeID, NodeID>;
-fn to_sourced(id: NodeID) -> SourcedNodeID {
-    if id % 2 == 0 {
-        Either::Left(id / 2)
-    } else {
-        Either::Right(id / 2)
-    }
-}
-fn from_sourced(id: SourcedNodeID) -> NodeID {
-    match id {
-        Either::Left(id) => id * 2,
-        Either::Right(id) => id * 2 + 1,
-    }
-}
 
 #[derive(Clone)]
 pub struct PointerNode {
@@ -103,9 +94,10 @@ where
             pointer_edge,
             transfer_root_pointers,
             dummy_level_label,
-            pointers_of: HashMap::new(),
-            pointers: HashMap::new(),
+            pointers_of: ArenaMap::new(),
+            pointers: ArenaMap::new(),
             free_id: FreeIdManager::new(0),
+            ids: SourcedNodeIDs::new(),
         };
         for node in adjuster.graph.get_roots() {
             adjuster.add_labels(node);
@@ -145,17 +137,17 @@ where
                         self.remove_pointer(id);
                     }
                     self.event_writer.write(Change::NodeLabelChange {
-                        node: from_sourced(Either::Left(node)),
+                        node: self.ids.from_sourced(Either::Left(node)),
                     });
                 }
                 Change::LevelChange { node } => {
                     self.event_writer.write(Change::LevelChange {
-                        node: from_sourced(Either::Left(node)),
+                        node: self.ids.from_sourced(Either::Left(node)),
                     });
                     if let Some(pointers) = self.pointers_of.get(&node) {
                         for &id in pointers {
                             self.event_writer.write(Change::LevelChange {
-                                node: from_sourced(Either::Right(id)),
+                                node: self.ids.from_sourced(Either::Right(id)),
                             });
                         }
                     }
@@ -165,17 +157,17 @@ where
                 }
                 Change::NodeConnectionsChange { node } => {
                     self.event_writer.write(Change::NodeConnectionsChange {
-                        node: from_sourced(Either::Left(node)),
+                        node: self.ids.from_sourced(Either::Left(node)),
                     });
                 }
                 Change::ParentDiscover { child } => {
                     self.event_writer.write(Change::ParentDiscover {
-                        child: from_sourced(Either::Left(child)),
+                        child: self.ids.from_sourced(Either::Left(child)),
                     });
                 }
                 Change::NodeRemoval { node } => {
                     self.event_writer.write(Change::NodeRemoval {
-                        node: from_sourced(Either::Left(node)),
+                        node: self.ids.from_sourced(Either::Left(node)),
                     });
                     if let Some(pointers) = self.pointers_of.get(&node) {
                         for id in pointers.clone().iter().cloned() {
@@ -185,8 +177,8 @@ where
                 }
                 Change::NodeInsertion { node, source } => {
                     self.event_writer.write(Change::NodeInsertion {
-                        node: from_sourced(Either::Left(node)),
-                        source: source.map(|s| from_sourced(Either::Left(s))),
+                        node: self.ids.from_sourced(Either::Left(node)),
+                        source: source.map(|s| self.ids.from_sourced(Either::Left(s))),
                     });
                     self.add_labels(node);
                 }
@@ -210,16 +202,15 @@ where
 
         self.pointers.insert(id, pointer);
         self.pointers_of
-            .entry(to)
-            .or_insert_with(|| HashSet::new())
+            .get_or_insert_with(to, HashSet::new)
             .insert(id);
 
         self.event_writer.write(Change::NodeInsertion {
-            node: from_sourced(Either::Right(id)),
+            node: self.ids.from_sourced(Either::Right(id)),
             source: None,
         });
         self.event_writer.write(Change::ParentDiscover {
-            child: from_sourced(Either::Left(to)),
+            child: self.ids.from_sourced(Either::Left(to)),
         });
     }
 
@@ -238,8 +229,9 @@ where
         }
 
         self.event_writer.write(Change::NodeRemoval {
-            node: from_sourced(Either::Right(id)),
+            node: self.ids.from_sourced(Either::Right(id)),
         });
+        self.ids.remove(Either::Right(id));
     }
 }
 
@@ -259,18 +251,18 @@ where
                 .flat_map(|&node| match self.pointers_of.get(&node) {
                     Some(labels) => labels
                         .iter()
-                        .map(|&p| from_sourced(Either::Right(p)))
+                        .map(|&p| self.ids.from_sourced(Either::Right(p)))
                         .collect_vec(),
-                    None => vec![from_sourced(Either::Left(node))],
+                    None => vec![self.ids.from_sourced(Either::Left(node))],
                 })
                 .collect_vec();
-            console::log!("roots: {}", p.iter().join(", "));
+            console::log_trace!("roots: {}", p.iter().join(", "));
             p
         } else {
             self.graph
                 .get_roots()
                 .iter()
-                .map(|&node| from_sourced(Either::Left(node)))
+                .map(|&node| self.ids.from_sourced(Either::Left(node)))
                 .collect()
         }
     }
@@ -279,56 +271,58 @@ where
         self.graph
             .get_terminals()
             .iter()
-            .map(|&node| from_sourced(Either::Left(node)))
+            .map(|&node| self.ids.from_sourced(Either::Left(node)))
             .collect()
     }
 
     fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
         self.process_graph_changes();
-        match to_sourced(node) {
-            Either::Left(node) => {
+        match self.ids.to_sourced(node) {
+            Some(Either::Left(node)) => {
                 let or_parents = self
                     .graph
                     .get_known_parents(node)
                     .into_iter()
-                    .map(|(edge, node)| (edge, from_sourced(Either::Left(node))));
+                    .map(|(edge, node)| (edge, self.ids.from_sourced(Either::Left(node))));
                 match self.pointers_of.get(&node) {
                     Some(pointers) => or_parents
-                        .chain(
-                            repeat(self.pointer_edge)
-                                .zip(pointers.iter().map(|&p| from_sourced(Either::Right(p)))),
-                        )
+                        .chain(repeat(self.pointer_edge).zip(
+                            pointers
+                                .iter()
+                                .map(|&p| self.ids.from_sourced(Either::Right(p))),
+                        ))
                         .collect(),
                     None => or_parents.collect(),
                 }
             }
-            Either::Right(_) => vec![],
+            Some(Either::Right(_)) | None => vec![],
         }
     }
 
     fn get_children(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
         self.process_graph_changes();
-        match to_sourced(node) {
-            Either::Left(node) => self
+        match self.ids.to_sourced(node) {
+            Some(Either::Left(node)) => self
                 .graph
                 .get_children(node)
                 .into_iter()
-                .map(|(edge, node)| (edge, from_sourced(Either::Left(node))))
+                .map(|(edge, node)| (edge, self.ids.from_sourced(Either::Left(node))))
                 .collect(),
-            Either::Right(node) => match self.pointers.get(&node) {
+            Some(Either::Right(node)) => match self.pointers.get(&node) {
                 Some(pointer) => vec![(
                     self.pointer_edge,
-                    from_sourced(Either::Left(pointer.pointer_for)),
+                    self.ids.from_sourced(Either::Left(pointer.pointer_for)),
                 )],
                 None => vec![],
             },
+            None => vec![],
         }
     }
 
     fn get_level(&mut self, node: NodeID) -> oxidd::LevelNo {
-        match to_sourced(node) {
-            Either::Left(node) => self.graph.get_level(node) + 1,
-            Either::Right(node) => match self.pointers.get(&node) {
+        match self.ids.to_sourced(node) {
+            Some(Either::Left(node)) => self.graph.get_level(node) + 1,
+            Some(Either::Right(node)) => match self.pointers.get(&node) {
                 Some(pointer) => {
                     let l = self.graph.get_level(pointer.pointer_for);
                     if self.graph.get_terminals().contains(&pointer.pointer_for) && l > u32::MAX / 2
@@ -341,16 +335,18 @@ where
                 }
                 None => 0,
             },
+            None => 0,
         }
     }
 
     fn get_node_label(&self, node: NodeID) -> PointerLabel<G::NL> {
-        match to_sourced(node) {
-            Either::Left(node) => PointerLabel::Node(self.graph.get_node_label(node)),
-            Either::Right(node) => match self.pointers.get(&node) {
+        match self.ids.to_sourced(node) {
+            Some(Either::Left(node)) => PointerLabel::Node(self.graph.get_node_label(node)),
+            Some(Either::Right(node)) => match self.pointers.get(&node) {
                 Some(pointer) => PointerLabel::Pointer(pointer.text.clone()),
                 None => PointerLabel::Pointer("".to_string()),
             },
+            None => PointerLabel::Pointer("".to_string()),
         }
     }
 
@@ -372,28 +368,30 @@ where
     }
 
     fn local_nodes_to_sources(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
-        nodes
+        let nodes = nodes
             .into_iter()
-            .filter_map(|node| match to_sourced(node) {
+            .filter_map(|node| match self.ids.to_sourced(node)? {
                 Either::Left(node) => Some(node),
                 Either::Right(node) => self.pointers.get(&node).map(|pointer| pointer.pointer_for),
             })
-            .collect()
+            .collect();
+        self.graph.local_nodes_to_sources(nodes)
     }
 
     fn source_nodes_to_local(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
         // For each node, map it to the output format and add its pointers
-        nodes
+        self.graph
+            .source_nodes_to_local(nodes)
             .into_iter()
             .flat_map(|node| {
-                Some(from_sourced(Either::Left(node))).into_iter().chain(
+                Some(self.ids.from_sourced(Either::Left(node))).into_iter().chain(
                     self.pointers_of
                         .get(&node)
                         .iter()
                         .flat_map(|pointers| {
                             pointers
                                 .iter()
-                                .map(|&pointer| from_sourced(Either::Right(pointer)))
+                                .map(|&pointer| self.ids.from_sourced(Either::Right(pointer)))
                         })
                         .collect_vec()
                         .into_iter(),
@@ -404,8 +402,12 @@ where
 }
 
 impl<G: GraphStructure + StateStorage> StateStorage for PointerNodeAdjuster<G> {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)
     }
     fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
         self.graph.write(stream)
@@ -7,6 +7,7 @@ use std::{
 use oxidd::LevelNo;
 
 use crate::{
+    interface::NodeID,
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphStructure,
@@ -14,7 +15,6 @@ use crate::{
         storage::state_storage::StateStorage,
     },
     util::rc_refcell::MutRcRefCell,
-    wasm_interface::NodeID,
 };
 
 // A cloneable graph such that multiple places can share ownership
@@ -49,8 +49,12 @@ impl<G: GraphStructure> StateStorage for RCGraph<G>
 where
     G: StateStorage,
 {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.get().read(stream)
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.get().read(stream, version)
     }
     fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
         self.graph.read().write(stream)
@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::{
+    interface::NodeID,
+    types::util::{
+        graph_structure::graph_structure::{
+            Change, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
+        },
+        storage::state_storage::StateStorage,
+    },
+    util::arena::ArenaMap,
+};
+
+///
+/// Detects redundant (don't-care) nodes, whose children are all the same node, and can bypass them such that their parents connect to this child directly. Roots are never bypassed.
+pub struct RedundantNodeAdjuster<G: GraphStructure> {
+    graph: G,
+
+    bypass: bool,
+    event_writer: GraphEventsWriter,
+    graph_events: GraphEventsReader,
+    redundant_cache: ArenaMap<NodeID, Option<NodeID>>,
+}
+
+impl<G: GraphStructure> RedundantNodeAdjuster<G> {
+    pub fn new(mut graph: G) -> Self {
+        RedundantNodeAdjuster {
+            graph_events: graph.create_event_reader(),
+            graph,
+
+            bypass: false,
+            event_writer: GraphEventsWriter::new(),
+            redundant_cache: ArenaMap::new(),
+        }
+    }
+
+    /// Retrieves the redundant nodes that are reachable from the roots
+    pub fn get_redundant_nodes(&mut self) -> Vec<NodeID> {
+        self.process_graph_changes();
+        let mut node_queue = self.graph.get_roots();
+        let mut seen = HashSet::new();
+        let mut redundant = Vec::new();
+        while let Some(node) = node_queue.pop() {
+            if !seen.insert(node) {
+                continue;
+            }
+            if self.get_redundant_child(node).is_some() {
+                redundant.push(node);
+            }
+            node_queue.extend(
+                self.graph
+                    .get_children(node)
+                    .into_iter()
+                    .map(|(_, child)| child),
+            );
+        }
+        redundant.sort();
+        redundant
+    }
+
+    pub fn set_bypass(&mut self, bypass: bool) {
+        if self.bypass == bypass {
+            return;
+        }
+        self.bypass = bypass;
+
+        // The parents of redundant nodes and the children they are connected to instead change connections
+        for node in self.get_redundant_nodes() {
+            let child = self.get_redundant_child(node).unwrap();
+            let parents = self.graph.get_known_parents(node);
+            for affected in parents
+                .into_iter()
+                .map(|(_, parent)| parent)
+                .chain([node, child])
+                .sorted()
+                .dedup()
+            {
+                self.event_writer
+                    .write(Change::NodeConnectionsChange { node: affected });
+            }
+        }
+    }
+
+    /// Retrieves the child of the given node if the node is redundant
+    fn get_redundant_child(&mut self, node: NodeID) -> Option<NodeID> {
+        if let Some(hit) = self.redundant_cache.get(&node) {
+            return *hit;
+        }
+        let children = self.graph.get_children(node);
+        let result = match &children[..] {
+            [(_, first), rest @ ..] if rest.len() > 0 && rest.iter().all(|(_, c)| c == first) => {
+                Some(*first)
+            }
+            _ => None,
+        };
+        self.redundant_cache.insert(node, result);
+        result
+    }
+
+    /// Follows the chain of redundant nodes starting at the given node, to the first node that is not bypassed
+    fn resolve(&mut self, mut node: NodeID) -> NodeID {
+        let roots = self.graph.get_roots();
+        while !roots.contains(&node) {
+            let Some(child) = self.get_redundant_child(node) else {
+                break;
+            };
+            node = child;
+        }
+        node
+    }
+
+    /// Retrieves the parents of the given node, where redundant parents are replaced by their own parents
+    fn get_bypassed_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        let roots = self.graph.get_roots();
+        let mut parents = Vec::new();
+        let mut redundant_parents = HashSet::new();
+        for (edge, parent) in self.graph.get_known_parents(node) {
+            if roots.contains(&parent) || self.get_redundant_child(parent).is_none() {
+                parents.push((edge, parent));
+            } else if redundant_parents.insert(parent) {
+                parents.extend(self.get_bypassed_parents(parent));
+            }
+        }
+        parents
+    }
+
+    fn process_graph_changes(&mut self) {
+        let events = self.graph.consume_events(&self.graph_events);
+        for event in events {
+            match event {
+                Change::NodeConnectionsChange { node } => {
+                    self.redundant_cache.remove(&node);
+                }
+                Change::NodeInsertion { node, source: _ } => {
+                    self.redundant_cache.remove(&node);
+                }
+                Change::NodeRemoval { node } => {
+                    self.redundant_cache.remove(&node);
+                }
+                _ => {}
+            };
+            self.event_writer.write(event);
+        }
+    }
+}
+
+impl<G: GraphStructure> GraphStructure for RedundantNodeAdjuster<G> {
+    type T = G::T;
+    type NL = G::NL;
+    type LL = G::LL;
+
+    fn get_roots(&self) -> Vec<NodeID> {
+        self.graph.get_roots()
+    }
+
+    fn get_terminals(&self) -> Vec<NodeID> {
+        self.graph.get_terminals()
+    }
+
+    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        if !self.bypass {
+            return self.graph.get_known_parents(node);
+        }
+        if !self.graph.get_roots().contains(&node) && self.get_redundant_child(node).is_some() {
+            return Vec::new();
+        }
+        self.get_bypassed_parents(node)
+    }
+
+    fn get_children(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        let children = self.graph.get_children(node);
+        if !self.bypass {
+            return children;
+        }
+
+        children
+            .into_iter()
+            .map(|(edge, child)| (edge, self.resolve(child)))
+            .collect()
+    }
+
+    fn get_level(&mut self, node: NodeID) -> oxidd::LevelNo {
+        self.graph.get_level(node)
+    }
+
+    fn get_node_label(&self, node: NodeID) -> G::NL {
+        self.graph.get_node_label(node)
+    }
+
+    fn get_level_label(&self, level: oxidd::LevelNo) -> G::LL {
+        self.graph.get_level_label(level)
+    }
+
+    fn create_event_reader(&mut self) -> GraphEventsReader {
+        self.event_writer.create_reader()
+    }
+
+    fn consume_events(&mut self, reader: &GraphEventsReader) -> Vec<Change> {
+        self.process_graph_changes();
+        self.event_writer.read(reader)
+    }
+
+    fn local_nodes_to_sources(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph.local_nodes_to_sources(nodes)
+    }
+
+    fn source_nodes_to_local(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph.source_nodes_to_local(nodes)
+    }
+}
+
+/// Whether redundant nodes are bypassed is a view setting, which is therefore not stored
+impl<G: GraphStructure + StateStorage> StateStorage for RedundantNodeAdjuster<G> {
+    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
+        self.graph.write(stream)
+    }
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)
+    }
+}
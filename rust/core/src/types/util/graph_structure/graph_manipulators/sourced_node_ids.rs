@@ -0,0 +1,70 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use itertools::Either;
+use oxidd::NodeID;
+
+use crate::util::free_id_manager::FreeIdManager;
+
+/// The source of a node of a manipulator that inserts nodes, which is either a node of the underlying graph (left) or a node created by the manipulator itself (right)
+pub type SourcedNodeID = Either<NodeID, NodeID>;
+
+/// Translates between the source IDs of a manipulator and the output IDs it exposes. Output IDs are allocated when a source ID is first translated, such that left and right nodes never share an output ID, regardless of how large or sparse the IDs of the underlying graph are.
+pub struct SourcedNodeIDs {
+    // Allocation happens on translation, which also happens during read-only queries of the manipulator
+    table: RefCell<TranslationTable>,
+}
+
+struct TranslationTable {
+    output_by_source: HashMap<SourcedNodeID, NodeID>,
+    // Output IDs are allocated densely, hence they index this list directly
+    source_by_output: Vec<Option<SourcedNodeID>>,
+    free_id: FreeIdManager<NodeID>,
+}
+
+impl SourcedNodeIDs {
+    pub fn new() -> SourcedNodeIDs {
+        SourcedNodeIDs {
+            table: RefCell::new(TranslationTable {
+                output_by_source: HashMap::new(),
+                source_by_output: Vec::new(),
+                free_id: FreeIdManager::new(0),
+            }),
+        }
+    }
+
+    /// Retrieves the output ID of the given source, which is allocated if the source was not translated before
+    pub fn from_sourced(&self, id: SourcedNodeID) -> NodeID {
+        let mut table = self.table.borrow_mut();
+        if let Some(&out) = table.output_by_source.get(&id) {
+            return out;
+        }
+
+        let out = table.free_id.get_next();
+        if table.source_by_output.len() <= out {
+            table.source_by_output.resize(out + 1, None);
+        }
+        table.source_by_output[out] = Some(id);
+        table.output_by_source.insert(id, out);
+        out
+    }
+
+    /// Retrieves the source of the given output ID, or None if the ID was not handed out by this table
+    pub fn to_sourced(&self, out: NodeID) -> Option<SourcedNodeID> {
+        self.table.borrow().source_by_output.get(out).cloned().flatten()
+    }
+
+    /// Releases the output ID of the given source, such that it can be reused for another source
+    pub fn remove(&mut self, id: SourcedNodeID) {
+        let table = self.table.get_mut();
+        if let Some(out) = table.output_by_source.remove(&id) {
+            table.source_by_output[out] = None;
+            table.free_id.make_available(out);
+        }
+    }
+}
+
+impl Default for SourcedNodeIDs {
+    fn default() -> Self {
+        SourcedNodeIDs::new()
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::{
+    interface::NodeID,
+    types::util::{
+        graph_structure::graph_structure::{
+            Change, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
+        },
+        storage::state_storage::StateStorage,
+    },
+    util::arena::ArenaMap,
+};
+
+/// Merges the terminals whose values fall into the same bin into a single terminal, such that diagrams with many distinct terminal values remain readable. The bins are given by sorted boundaries, where each pair of consecutive boundaries forms a half-open range.
+///
+/// The merged terminal reuses the id of the terminal with the lowest id in the bin, and the other terminals of the bin are removed. Terminals outside of all bins are left untouched.
+pub struct TerminalBinAdjuster<G: GraphStructure> {
+    graph: G,
+    /// Retrieves the value of a terminal from its label, or None for inner nodes
+    get_value: fn(&G::NL) -> Option<f32>,
+    /// Creates the label of a merged terminal from the labels of the terminals it represents and the range of its bin
+    merge_labels: fn(Vec<G::NL>, (f32, f32)) -> G::NL,
+    bounds: Vec<f32>,
+    /// The terminals and range of every non-empty bin, indexed by the terminal that represents the bin
+    bins: ArenaMap<NodeID, (Vec<NodeID>, (f32, f32))>,
+    /// The representative of every terminal that is part of a bin
+    representatives: ArenaMap<NodeID, NodeID>,
+
+    event_writer: GraphEventsWriter,
+    graph_events: GraphEventsReader,
+}
+
+impl<G: GraphStructure> TerminalBinAdjuster<G> {
+    pub fn new(
+        mut graph: G,
+        get_value: fn(&G::NL) -> Option<f32>,
+        merge_labels: fn(Vec<G::NL>, (f32, f32)) -> G::NL,
+    ) -> Self {
+        TerminalBinAdjuster {
+            graph_events: graph.create_event_reader(),
+            graph,
+            get_value,
+            merge_labels,
+            bounds: Vec::new(),
+            bins: ArenaMap::new(),
+            representatives: ArenaMap::new(),
+            event_writer: GraphEventsWriter::new(),
+        }
+    }
+
+    pub fn get_bounds(&self) -> &Vec<f32> {
+        &self.bounds
+    }
+
+    /// Sets the boundaries of the bins, where an empty list of boundaries disables the binning
+    pub fn set_bounds(&mut self, mut bounds: Vec<f32>) {
+        self.process_graph_changes();
+        bounds.retain(|bound| bound.is_finite());
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+        self.bounds = bounds;
+
+        let old_representatives = std::mem::take(&mut self.representatives);
+        self.bins = self.compute_bins();
+        for (&representative, (terminals, _)) in self.bins.iter() {
+            for &terminal in terminals {
+                self.representatives.insert(terminal, representative);
+            }
+        }
+
+        for terminal in self.graph.get_terminals() {
+            let old = old_representatives.get(&terminal).cloned().unwrap_or(terminal);
+            let new = self.representatives.get(&terminal).cloned().unwrap_or(terminal);
+            for (_, parent) in self.graph.get_known_parents(terminal) {
+                self.event_writer
+                    .write(Change::NodeConnectionsChange { node: parent });
+            }
+            match (old == terminal, new == terminal) {
+                (true, false) => self
+                    .event_writer
+                    .write(Change::NodeRemoval { node: terminal }),
+                (false, true) => self.event_writer.write(Change::NodeInsertion {
+                    node: terminal,
+                    source: Some(old),
+                }),
+                _ => {}
+            }
+            if new == terminal {
+                self.event_writer
+                    .write(Change::NodeLabelChange { node: terminal });
+                self.event_writer
+                    .write(Change::LevelChange { node: terminal });
+            }
+        }
+    }
+
+    fn compute_bins(&self) -> ArenaMap<NodeID, (Vec<NodeID>, (f32, f32))> {
+        let ranges = self
+            .bounds
+            .iter()
+            .tuple_windows()
+            .map(|(&start, &end)| (start, end))
+            .collect_vec();
+        let mut terminals_per_bin = HashMap::<usize, Vec<NodeID>>::new();
+        for terminal in self.graph.get_terminals() {
+            let Some(value) = (self.get_value)(&self.graph.get_node_label(terminal)) else {
+                continue;
+            };
+            let bin = ranges
+                .iter()
+                .position(|&(start, end)| start <= value && value < end);
+            if let Some(bin) = bin {
+                terminals_per_bin.entry(bin).or_default().push(terminal);
+            }
+        }
+        terminals_per_bin
+            .into_iter()
+            .map(|(bin, mut terminals)| {
+                terminals.sort();
+                (terminals[0], (terminals, ranges[bin]))
+            })
+            .collect()
+    }
+
+    fn is_merged_away(&self, node: NodeID) -> bool {
+        self.representatives
+            .get(&node)
+            .map_or(false, |&representative| representative != node)
+    }
+
+    fn process_graph_changes(&mut self) {
+        let events = self.graph.consume_events(&self.graph_events);
+        self.event_writer.write_vec(events);
+    }
+}
+
+impl<G: GraphStructure> GraphStructure for TerminalBinAdjuster<G> {
+    type T = G::T;
+    type NL = G::NL;
+    type LL = G::LL;
+
+    fn get_roots(&self) -> Vec<NodeID> {
+        self.graph
+            .get_roots()
+            .into_iter()
+            .map(|root| self.representatives.get(&root).cloned().unwrap_or(root))
+            .collect()
+    }
+
+    fn get_terminals(&self) -> Vec<NodeID> {
+        self.graph
+            .get_terminals()
+            .into_iter()
+            .filter(|&terminal| !self.is_merged_away(terminal))
+            .collect()
+    }
+
+    fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        if let Some((terminals, _)) = self.bins.get(&node).cloned() {
+            return terminals
+                .into_iter()
+                .flat_map(|terminal| self.graph.get_known_parents(terminal))
+                .collect();
+        }
+        if self.is_merged_away(node) {
+            return Vec::new();
+        }
+        self.graph.get_known_parents(node)
+    }
+
+    fn get_children(&mut self, node: NodeID) -> Vec<(EdgeType<G::T>, NodeID)> {
+        self.process_graph_changes();
+        self.graph
+            .get_children(node)
+            .into_iter()
+            .map(|(edge, child)| {
+                let child = self.representatives.get(&child).cloned().unwrap_or(child);
+                (edge, child)
+            })
+            .collect()
+    }
+
+    fn get_level(&mut self, node: NodeID) -> oxidd::LevelNo {
+        self.graph.get_level(node)
+    }
+
+    fn get_node_label(&self, node: NodeID) -> G::NL {
+        match self.bins.get(&node) {
+            Some((terminals, range)) => (self.merge_labels)(
+                terminals
+                    .iter()
+                    .map(|&terminal| self.graph.get_node_label(terminal))
+                    .collect(),
+                *range,
+            ),
+            None => self.graph.get_node_label(node),
+        }
+    }
+
+    fn get_level_label(&self, level: oxidd::LevelNo) -> G::LL {
+        self.graph.get_level_label(level)
+    }
+
+    fn create_event_reader(&mut self) -> GraphEventsReader {
+        self.event_writer.create_reader()
+    }
+
+    fn consume_events(&mut self, reader: &GraphEventsReader) -> Vec<Change> {
+        self.process_graph_changes();
+        self.event_writer.read(reader)
+    }
+
+    fn local_nodes_to_sources(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        // A merged terminal represents all terminals of its bin
+        let nodes = nodes
+            .into_iter()
+            .flat_map(|node| match self.bins.get(&node) {
+                Some((terminals, _)) => terminals.clone(),
+                None => vec![node],
+            })
+            .collect();
+        self.graph.local_nodes_to_sources(nodes)
+    }
+
+    fn source_nodes_to_local(&self, nodes: Vec<NodeID>) -> Vec<NodeID> {
+        self.graph
+            .source_nodes_to_local(nodes)
+            .into_iter()
+            .map(|node| self.representatives.get(&node).cloned().unwrap_or(node))
+            .unique()
+            .collect()
+    }
+}
+
+// The bins are not stored, since they are provided by the user for every session
+impl<G: GraphStructure + StateStorage> StateStorage for TerminalBinAdjuster<G> {
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)
+    }
+    fn write(&self, stream: &mut std::io::Cursor<&mut Vec<u8>>) -> std::io::Result<()> {
+        self.graph.write(stream)
+    }
+}
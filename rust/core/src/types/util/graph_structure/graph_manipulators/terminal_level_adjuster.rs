@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashSet,
     default,
     iter::FromIterator,
     marker::PhantomData,
@@ -9,14 +9,14 @@ use itertools::Itertools;
 use oxidd::LevelNo;
 
 use crate::{
+    interface::NodeID,
     types::util::{
         graph_structure::graph_structure::{
             Change, DrawTag, EdgeType, GraphEventsReader, GraphEventsWriter, GraphStructure,
         },
         storage::state_storage::StateStorage,
     },
-    util::{logging::console, rc_refcell::MutRcRefCell},
-    wasm_interface::NodeID,
+    util::{arena::ArenaMap, logging::console, rc_refcell::MutRcRefCell},
 };
 
 pub struct TerminalLevelAdjuster<G: GraphStructure + 'static> {
@@ -24,17 +24,17 @@ pub struct TerminalLevelAdjuster<G: GraphStructure + 'static> {
     event_writer: GraphEventsWriter,
     graph_events: GraphEventsReader,
 
-    level_cache: HashMap<NodeID, LevelNo>,
-    terminal_parents_cache: HashMap<NodeID, HashSet<NodeID>>,
+    level_cache: ArenaMap<NodeID, LevelNo>,
+    terminal_parents_cache: ArenaMap<NodeID, HashSet<NodeID>>,
 }
 
 impl<G: GraphStructure> TerminalLevelAdjuster<G> {
     pub fn new(mut graph: G) -> TerminalLevelAdjuster<G> {
         let mut ta = TerminalLevelAdjuster {
-            level_cache: HashMap::new(),
+            level_cache: ArenaMap::new(),
             graph_events: graph.create_event_reader(),
             event_writer: GraphEventsWriter::new(),
-            terminal_parents_cache: HashMap::new(),
+            terminal_parents_cache: ArenaMap::new(),
             graph,
         };
         ta.init_terminals_cache();
@@ -101,8 +101,12 @@ impl<G: GraphStructure> StateStorage for TerminalLevelAdjuster<G>
 where
     G: StateStorage,
 {
-    fn read(&mut self, stream: &mut std::io::Cursor<&Vec<u8>>) -> std::io::Result<()> {
-        self.graph.read(stream)?;
+    fn read(
+        &mut self,
+        stream: &mut std::io::Cursor<&Vec<u8>>,
+        version: u32,
+    ) -> std::io::Result<()> {
+        self.graph.read(stream, version)?;
         self.init_terminals_cache();
         Ok(())
     }
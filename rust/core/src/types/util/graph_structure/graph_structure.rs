@@ -9,13 +9,12 @@ use std::{
     usize,
 };
 
-use js_sys::Math::random;
 use oxidd::{Edge, Function, InnerNode, LevelNo, Manager};
 use oxidd_core::{DiagramRules, HasLevel, Node, Tag};
 
 use crate::{
+    interface::NodeID,
     util::{logging::console, rc_refcell::MutRcRefCell},
-    wasm_interface::NodeID,
 };
 
 use super::oxidd_graph_structure::OxiddGraphStructure;
@@ -88,6 +87,21 @@ pub enum Change {
         child: NodeID,
     },
 }
+impl Change {
+    /// Retrieves the node that this event is about, if any
+    fn get_node(&self) -> Option<NodeID> {
+        match self {
+            Change::NodeLabelChange { node }
+            | Change::LevelChange { node }
+            | Change::NodeConnectionsChange { node }
+            | Change::NodeRemoval { node }
+            | Change::NodeInsertion { node, .. } => Some(*node),
+            Change::ParentDiscover { child } => Some(*child),
+            Change::LevelLabelChange { .. } => None,
+        }
+    }
+}
+
 impl Display for Change {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
@@ -156,12 +170,60 @@ impl GraphEventsWriter {
             reader.get().events.push(event.clone());
         }
     }
+    /// Retrieves the events written since the last read of this reader, coalescing events that would only cause redundant processing
     pub fn read(&mut self, reader: &GraphEventsReader) -> Vec<Change> {
         let mut inner = reader.inner.get();
-        let events = inner.events.clone();
-        inner.events.clear();
-        events
+        let events = std::mem::take(&mut inner.events);
+        coalesce_events(events)
+    }
+}
+
+/// Removes events that do not affect the outcome of processing the events in order:
+/// - Nodes that were inserted and removed again are omitted entirely, including all events about them in between
+/// - Repeated identical events are only reported once, at their last occurrence
+fn coalesce_events(events: Vec<Change>) -> Vec<Change> {
+    if events.len() <= 1 {
+        return events;
+    }
+
+    let mut dropped = vec![false; events.len()];
+    // The indices of the events of a node since it was inserted in this batch
+    let mut since_insertion = HashMap::<NodeID, Vec<usize>>::new();
+    for (index, event) in events.iter().enumerate() {
+        let Some(node) = event.get_node() else {
+            continue;
+        };
+        match event {
+            Change::NodeInsertion { .. } => {
+                since_insertion.insert(node, vec![index]);
+            }
+            Change::NodeRemoval { .. } => {
+                if let Some(indices) = since_insertion.remove(&node) {
+                    for i in indices {
+                        dropped[i] = true;
+                    }
+                    dropped[index] = true;
+                }
+            }
+            _ => {
+                if let Some(indices) = since_insertion.get_mut(&node) {
+                    indices.push(index);
+                }
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut out = Vec::with_capacity(events.len());
+    for (index, event) in events.into_iter().enumerate().rev() {
+        if dropped[index] || seen.contains(&event) {
+            continue;
+        }
+        seen.insert(event.clone());
+        out.push(event);
     }
+    out.reverse();
+    out
 }
 
 impl Drop for GraphEventsReader {
@@ -3,9 +3,9 @@ use std::{borrow::Borrow, cell::RefCell, collections::HashSet, hash::Hash, rc::R
 use oxidd::LevelNo;
 use oxidd_core::Tag;
 
-use crate::wasm_interface::{NodeGroupID, NodeID};
+use crate::interface::{NodeGroupID, NodeID};
 
-use super::graph_structure::{DrawTag, EdgeType};
+use super::graph_structure::{Change, DrawTag, EdgeType};
 
 pub trait GroupedGraphStructure {
     type T: DrawTag;
@@ -22,13 +22,13 @@ pub trait GroupedGraphStructure {
     fn get_nodes_of_group(&self, group: NodeGroupID) -> Vec<NodeID>;
     fn get_level_range(&self, group: NodeGroupID) -> (LevelNo, LevelNo);
     fn get_level_label(&self, level: LevelNo) -> Self::LL;
-    /// Refreshes the node groups according to changes of the underlying graph
-    fn refresh(&mut self);
+    /// Refreshes the node groups according to changes of the underlying graph, returning these changes
+    fn refresh(&mut self) -> Vec<Change>;
     /// Retrieves a node-tracker that for every node tracks its source (that it got created from), and whether it and its source ids can be reused
     fn create_node_tracker(&mut self) -> Self::Tracker;
 }
 
-#[derive(PartialEq, Eq, Clone, Hash)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub struct EdgeData<T: DrawTag> {
     pub to: NodeGroupID,
     pub from_level: LevelNo,
@@ -1,15 +1,15 @@
-use std::{
-    borrow::Borrow,
-    collections::{HashMap, HashSet},
-    iter::FromIterator,
-    marker::PhantomData,
-    rc::Rc,
-};
+use std::{borrow::Borrow, iter::FromIterator, marker::PhantomData, rc::Rc};
 
 use oxidd::{Edge, Function, InnerNode, LevelNo, Manager, NodeID};
 use oxidd_core::{DiagramRules, HasLevel, Node};
 
-use crate::{types::util::storage::state_storage::StateStorage, util::logging::console};
+use crate::{
+    types::util::storage::state_storage::StateStorage,
+    util::{
+        arena::ArenaMap,
+        logging::console,
+    },
+};
 
 use super::{
     graph_manipulators::pointer_node_adjuster::WithPointerLabels,
@@ -23,14 +23,33 @@ where
     for<'id> F::Manager<'id>: Manager<EdgeTag = DT, Terminal = T>,
 {
     roots: Vec<F>,
-    node_by_id: HashMap<NodeID, F>,
-    pointers: HashMap<NodeID, Vec<String>>,
-    node_parents: HashMap<NodeID, HashSet<(EdgeType<DT>, NodeID)>>,
+    /// Every discovered node by its node id in the diagram, node ids may be sparse and are hence only used as keys of the arena
+    nodes: ArenaMap<NodeID, NodeEntry<DT, F>>,
     level_labels: Vec<String>,
     terminal: PhantomData<T>,
     event_writer: GraphEventsWriter,
 }
 
+/// The data of a node that has been discovered, all data of a node is stored together to prevent a lookup per property
+struct NodeEntry<DT: DrawTag, F> {
+    function: F,
+    pointers: Vec<String>,
+    parents: Vec<(EdgeType<DT>, NodeID)>,
+    /// Whether the children of this node have been retrieved before, and were registered as parents of the children
+    children_known: bool,
+}
+
+impl<DT: DrawTag, F> NodeEntry<DT, F> {
+    fn new(function: F) -> NodeEntry<DT, F> {
+        NodeEntry {
+            function,
+            pointers: Vec::new(),
+            parents: Vec::new(),
+            children_known: false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct NodeLabel<T> {
     pub pointers: Vec<String>,
@@ -56,50 +75,63 @@ where
         roots: Vec<(F, Vec<String>)>,
         level_labels: Vec<String>,
     ) -> OxiddGraphStructure<DT, F, T> {
-        OxiddGraphStructure {
-            node_by_id: roots
-                .iter()
-                .map(|(root, _)| {
-                    (
-                        root.with_manager_shared(|_, edge| edge.node_id()),
-                        root.clone(),
-                    )
-                })
-                .collect(),
+        let mut out = OxiddGraphStructure {
             roots: roots.iter().map(|(f, _)| f.clone()).collect(),
-            pointers: roots
-                .iter()
-                .map(|(f, pointers)| {
-                    (
-                        f.with_manager_shared(|_, edge| edge.node_id()),
-                        pointers.clone(),
-                    )
-                })
-                .collect(),
+            nodes: ArenaMap::new(),
             level_labels,
-            node_parents: HashMap::new(),
             event_writer: GraphEventsWriter::new(),
             terminal: PhantomData,
+        };
+        for (root, pointers) in roots {
+            let id = out.get_id_by_node(&root);
+            if let Some(entry) = out.get_entry_mut(id) {
+                entry.pointers = pointers;
+            }
         }
+        out
+    }
+
+    /// Renames the given level, such as to replace the meaningless variable names of an imported dump
+    pub fn set_level_label(&mut self, level: LevelNo, label: String) {
+        let index = level as usize;
+        if self.level_labels.len() <= index {
+            self.level_labels.resize(index + 1, String::new());
+        }
+        self.level_labels[index] = label;
+        self.event_writer.write(Change::LevelLabelChange { level });
+    }
+    pub fn get_level_count(&self) -> LevelNo {
+        self.level_labels.len() as LevelNo
+    }
+    /// Retrieves the function of the given node, if the node has been discovered
+    pub fn get_function(&self, node: NodeID) -> Option<F> {
+        self.get_node_by_id(node).cloned()
+    }
+
+    /// Retrieves the id of the given node, which is discovered if it is not reachable from the roots, such that it can be used like the nodes of the graph
+    pub fn discover(&mut self, node: &F) -> NodeID {
+        self.get_id_by_node(node)
     }
 
     fn get_id_by_node(&mut self, node: &F) -> NodeID {
-        node.with_manager_shared(|manager, edge| {
-            let id = edge.node_id();
-            self.node_by_id.insert(id, node.clone());
-            return id;
-        })
+        let id = node.with_manager_shared(|_, edge| edge.node_id());
+        self.nodes.get_or_insert_with(id, || NodeEntry::new(node.clone()));
+        id
+    }
+    fn get_entry(&self, id: NodeID) -> Option<&NodeEntry<DT, F>> {
+        self.nodes.get(&id)
+    }
+    fn get_entry_mut(&mut self, id: NodeID) -> Option<&mut NodeEntry<DT, F>> {
+        self.nodes.get_mut(&id)
     }
     fn get_node_by_id(&self, id: NodeID) -> Option<&F> {
-        return self.node_by_id.get(&id);
+        self.get_entry(id).map(|entry| &entry.function)
     }
 
     fn add_parent(&mut self, node: NodeID, parent: NodeID, edge_type: EdgeType<DT>) {
-        let parents = self
-            .node_parents
-            .entry(node)
-            .or_insert_with(|| HashSet::new());
-        parents.insert((edge_type, parent));
+        if let Some(entry) = self.get_entry_mut(node) {
+            entry.parents.push((edge_type, parent));
+        }
         self.event_writer
             .write(Change::ParentDiscover { child: node });
     }
@@ -153,8 +185,8 @@ where
     }
 
     fn get_known_parents(&mut self, node: NodeID) -> Vec<(EdgeType<ET>, NodeID)> {
-        if let Some(edges) = self.node_parents.get(&node) {
-            return Vec::from_iter(edges.iter().map(|&r| r));
+        if let Some(entry) = self.get_entry(node) {
+            return entry.parents.clone();
         }
         return Vec::new();
     }
@@ -176,15 +208,25 @@ where
                 }
             });
             if let Some(cofactors) = cofactors {
-                let out = cofactors.iter().map(|(i, f)| {
-                    f.with_manager_shared(|manager, edge| {
-                        let edge_type = EdgeType::new(edge.tag(), *i as i32);
-                        let child_id = self.get_id_by_node(&f);
-                        self.add_parent(child_id, node_id, edge_type);
-                        (edge_type, child_id)
+                let children: Vec<_> = cofactors
+                    .iter()
+                    .map(|(i, f)| {
+                        let tag = f.with_manager_shared(|_, edge| edge.tag());
+                        (EdgeType::new(tag, *i as i32), self.get_id_by_node(f))
                     })
-                });
-                return out.collect();
+                    .collect();
+
+                // The parents only have to be registered the first time the children are retrieved
+                let first_discovery = match self.get_entry_mut(node_id) {
+                    Some(entry) => !std::mem::replace(&mut entry.children_known, true),
+                    None => false,
+                };
+                if first_discovery {
+                    for &(edge_type, child_id) in &children {
+                        self.add_parent(child_id, node_id, edge_type);
+                    }
+                }
+                return children;
             }
         }
         return Vec::new();
@@ -195,7 +237,7 @@ where
             let r = node.with_manager_shared(|manager, edge| manager.get_node(edge).level());
             return r;
         }
-        console::log!("Unknown node's level requested: {}", node_id);
+        console::log_warn!("Unknown node's level requested: {}", node_id);
         0
     }
 
@@ -217,7 +259,10 @@ where
         };
 
         NodeLabel {
-            pointers: self.pointers.get(&node).cloned().unwrap_or_else(|| vec![]),
+            pointers: self
+                .get_entry(node)
+                .map(|entry| entry.pointers.clone())
+                .unwrap_or_else(|| vec![]),
             kind,
         }
     }
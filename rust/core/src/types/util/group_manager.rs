@@ -18,8 +18,8 @@ use oxidd_core::{DiagramRules, Node, Tag};
 use priority_queue::PriorityQueue;
 
 use crate::{
+    interface::{NodeGroupID, NodeID, TargetID, TargetIDType},
     util::{free_id_manager::FreeIdManager, logging::console, rc_refcell::MutRcRefCell},
-    wasm_interface::{NodeGroupID, NodeID, TargetID, TargetIDType},
 };
 
 use super::{
@@ -29,7 +29,10 @@ use super::{
         oxidd_graph_structure::NodeLabel,
     },
     node_tracker_manager::{NodeTrackerM, NodeTrackerManager},
-    storage::state_storage::StateStorage,
+    storage::state_storage::{
+        read_node_id, write_node_id, Serializable, StateStorage, LEGACY_STATE_FORMAT_VERSION,
+    },
+    structural_hasher::StructuralHasher,
 };
 
 pub struct GroupManager<G: GraphStructure> {
@@ -45,6 +48,49 @@ pub struct GroupManager<G: GraphStructure> {
     // returned_ids: HashSet<usize>,
     /// Source trackers to manage sources obtained from the groupedGraphStructure
     group_ids: NodeTrackerManager,
+    /// Custom names and notes that the user attached to groups
+    group_names: HashMap<NodeGroupID, GroupName>,
+    /// The group that each group is nested in, forming a hierarchy of groups on top of the partition of the nodes
+    group_parents: HashMap<NodeGroupID, NodeGroupID>,
+    /// The groups that collect the nodes that were left out of a bounded reveal, which are named after their number of nodes
+    placeholder_groups: HashSet<NodeGroupID>,
+    /// The groups that summarize subfunctions or runs of adjacent groups while zoomed out, together with the grouping that they replaced
+    summaries: HashMap<NodeGroupID, Vec<SummarizedGroup>>,
+}
+
+/// How many of the nodes of a diagram are revealed when it is first drawn
+#[derive(Clone, Copy)]
+pub enum InitialReveal {
+    /// Reveals all nodes of small diagrams
+    Default,
+    /// Only reveals the roots, other nodes are only discovered once they are expanded
+    Lazy,
+    /// Reveals the nodes in breadth first order up to the given depth (in edges) and number of nodes, collecting the remaining nodes in a placeholder group
+    Bounded { max_depth: usize, max_nodes: usize },
+}
+
+/// A user provided display name and free-text note of a group
+#[derive(Clone)]
+pub struct GroupName {
+    pub name: String,
+    pub note: String,
+}
+impl GroupName {
+    /// The text to show inside of the group
+    pub fn get_text(&self) -> String {
+        match (self.name.is_empty(), self.note.is_empty()) {
+            (_, true) => self.name.clone(),
+            (true, false) => self.note.clone(),
+            (false, false) => format!("{}: {}", self.name, self.note),
+        }
+    }
+}
+
+/// The nodes of a group that were moved into a summary group, such that the group can be restored
+struct SummarizedGroup {
+    group: NodeGroupID,
+    nodes: Vec<NodeID>,
+    name: Option<GroupName>,
 }
 
 type EdgeCounts<T: DrawTag> = HashMap<EdgeData<T>, usize>;
@@ -89,13 +135,13 @@ impl<T: DrawTag> Display for NodeGroup<T> {
 
 // Helper methods
 impl<G: GraphStructure> GroupManager<G> {
-    fn process_graph_events(&mut self) {
+    fn process_graph_events(&mut self) -> Vec<Change> {
         let events = self.graph.consume_events(&self.graph_events);
 
         let mut removed_from = HashMap::<NodeID, NodeGroupID>::new();
         let mut used_sources = HashSet::<NodeID>::new();
         let mut refresh_data = HashSet::<NodeID>::new();
-        for event in events {
+        for event in events.iter().cloned() {
             match event {
                 Change::NodeLabelChange { node } => {
                     refresh_data.insert(node);
@@ -200,11 +246,12 @@ impl<G: GraphStructure> GroupManager<G> {
                 self.add_node_to_group(node, group);
             }
         }
-        console::log!("after events");
+        console::log_trace!("after events");
 
         for group_id in removed_from.values().cloned() {
             self.remove_group_if_empty(group_id);
         }
+        events
     }
 
     fn get_node_group_id(&self, node: NodeID) -> Option<NodeGroupID> {
@@ -217,6 +264,23 @@ impl<G: GraphStructure> GroupManager<G> {
 
     fn remove_group(&mut self, id: NodeGroupID) {
         self.group_by_id.remove(&id);
+        self.group_names.remove(&id);
+        self.placeholder_groups.remove(&id);
+        self.summaries.remove(&id);
+        // Move the nested groups up to the parent of the removed group
+        let parent = self.group_parents.remove(&id);
+        let nested = self
+            .group_parents
+            .iter()
+            .filter(|&(_, &group_parent)| group_parent == id)
+            .map(|(&group, _)| group)
+            .collect_vec();
+        for group in nested {
+            match parent {
+                Some(parent) => self.group_parents.insert(group, parent),
+                None => self.group_parents.remove(&group),
+            };
+        }
         self.group_ids.make_available(id);
     }
 
@@ -408,7 +472,7 @@ impl<G: GraphStructure> GroupManager<G> {
         let from_empty = cur_group.nodes.is_empty();
         if from_empty {
             self.remove_group(group_id);
-            console::log!("removed {}", group_id);
+            console::log_trace!("removed {}", group_id);
         }
     }
 
@@ -522,6 +586,10 @@ impl<G: GraphStructure> GroupManager<G> {
             group_by_id: HashMap::new(),
             graph,
             group_ids: NodeTrackerManager::new(1),
+            group_names: HashMap::new(),
+            group_parents: HashMap::new(),
+            placeholder_groups: HashSet::new(),
+            summaries: HashMap::new(),
         };
         gm.reset();
         gm
@@ -534,6 +602,10 @@ impl<G: GraphStructure> GroupManager<G> {
         }
         self.group_id_by_node.clear();
         self.group_by_id.clear();
+        self.group_names.clear();
+        self.group_parents.clear();
+        self.placeholder_groups.clear();
+        self.summaries.clear();
         let layer_min = root_ids
             .iter()
             .map(|&root_id| (root_id, Reverse(self.graph.get_level(root_id))))
@@ -565,10 +637,76 @@ impl<G: GraphStructure> GroupManager<G> {
         &self.group_by_id
     }
 
+    /// Attaches the given name and note to a group, or removes them if both are empty. Returns false if the group does not exist.
+    pub fn set_group_name(&mut self, group_id: NodeGroupID, name: String, note: String) -> bool {
+        if !self.group_by_id.contains_key(&group_id) {
+            return false;
+        }
+        if name.is_empty() && note.is_empty() {
+            self.group_names.remove(&group_id);
+        } else {
+            self.group_names.insert(group_id, GroupName { name, note });
+        }
+        true
+    }
+
+    pub fn get_group_name(&self, group_id: NodeGroupID) -> Option<&GroupName> {
+        self.group_names.get(&group_id)
+    }
+
+    /// Nests the given group inside of the parent group, or moves it to the top of the hierarchy if no parent is given. Returns false if either group does not exist, or if the nesting would form a cycle.
+    pub fn set_group_parent(&mut self, group_id: NodeGroupID, parent: Option<NodeGroupID>) -> bool {
+        if !self.group_by_id.contains_key(&group_id) {
+            return false;
+        }
+        let Some(parent) = parent else {
+            self.group_parents.remove(&group_id);
+            return true;
+        };
+        if !self.group_by_id.contains_key(&parent) {
+            return false;
+        }
+        let mut ancestor = Some(parent);
+        while let Some(ancestor_id) = ancestor {
+            if ancestor_id == group_id {
+                return false;
+            }
+            ancestor = self.group_parents.get(&ancestor_id).cloned();
+        }
+        self.group_parents.insert(group_id, parent);
+        true
+    }
+
+    /// Nests all of the given groups inside of the parent group, see `set_group_parent`
+    pub fn set_groups_parent(&mut self, groups: &[NodeGroupID], parent: NodeGroupID) {
+        for &group in groups {
+            self.set_group_parent(group, Some(parent));
+        }
+    }
+
+    pub fn get_group_parent(&self, group_id: NodeGroupID) -> Option<NodeGroupID> {
+        self.group_parents.get(&group_id).cloned()
+    }
+
+    /// Retrieves the given group together with all groups that are (transitively) nested inside of it
+    pub fn get_descendant_groups(&self, group_id: NodeGroupID) -> HashSet<NodeGroupID> {
+        let mut descendants = HashSet::from([group_id]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (&group, parent) in &self.group_parents {
+                if descendants.contains(parent) && descendants.insert(group) {
+                    changed = true;
+                }
+            }
+        }
+        descendants
+    }
+
     pub fn set_group(
         &mut self,
-        from: Vec<crate::wasm_interface::TargetID>,
-        to: crate::wasm_interface::NodeGroupID,
+        from: Vec<crate::interface::TargetID>,
+        to: crate::interface::NodeGroupID,
     ) -> bool {
         if let None = self.group_by_id.get_mut(&to) {
             return false;
@@ -633,8 +771,8 @@ impl<G: GraphStructure> GroupManager<G> {
 
     pub fn create_group(
         &mut self,
-        from: Vec<crate::wasm_interface::TargetID>,
-    ) -> crate::wasm_interface::NodeGroupID {
+        from: Vec<crate::interface::TargetID>,
+    ) -> crate::interface::NodeGroupID {
         let sources = from
             .iter()
             .map(|&TargetID(target_type, id)| match target_type {
@@ -663,6 +801,427 @@ impl<G: GraphStructure> GroupManager<G> {
         new_id
     }
 
+    /// Expands the top `depth` levels of the given group in place: their nodes are moved into groups of their own that are nested inside of the original group, while the remaining nodes stay collapsed in it. If no nodes remain, the nested groups take the place of the original group in the hierarchy. Returns the newly created groups.
+    pub fn expand_group(&mut self, group_id: NodeGroupID, depth: LevelNo) -> Vec<NodeGroupID> {
+        let Some(group) = self.group_by_id.get(&group_id) else {
+            return Vec::new();
+        };
+        let nodes = group.nodes.keys().cloned().collect_vec();
+        let (min_level, _) = self.get_level_range(group_id);
+        let max_level = min_level.saturating_add(depth);
+        let outer_parent = self.get_group_parent(group_id);
+        let mut created = Vec::new();
+        for node in nodes {
+            if self.graph.get_level(node) < max_level {
+                let new_group = self.create_group(vec![TargetID(TargetIDType::NodeID, node)]);
+                // The original group is removed once its last node is moved out of it
+                let parent = if self.group_by_id.contains_key(&group_id) {
+                    Some(group_id)
+                } else {
+                    outer_parent
+                };
+                self.set_group_parent(new_group, parent);
+                created.push(new_group);
+            }
+        }
+        self.update_placeholder_name(group_id);
+        created
+    }
+
+    /// Moves the descendants of the given node that are up to `depth` edges away and not yet shown into groups of their own, discovering them from the graph only now. Returns the newly created groups.
+    pub fn expand_node(&mut self, node: NodeID, depth: usize) -> Vec<NodeGroupID> {
+        let mut created = Vec::new();
+        let mut placeholders = HashSet::new();
+        let mut reveal = |manager: &mut Self, node: NodeID| {
+            let group = manager.get_node_group_id(node).unwrap_or(0);
+            if group == 0 || manager.placeholder_groups.contains(&group) {
+                placeholders.insert(group);
+                created.push(manager.create_group(vec![TargetID(TargetIDType::NodeID, node)]));
+            }
+        };
+        reveal(self, node);
+
+        let mut found: HashSet<NodeID> = HashSet::from_iter([node]);
+        let mut frontier = vec![node];
+        for _ in 0..depth {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                for (_, child) in self.graph.get_children(node) {
+                    if found.insert(child) {
+                        reveal(self, child);
+                        next_frontier.push(child);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+        for group in placeholders {
+            self.update_placeholder_name(group);
+        }
+        created
+    }
+
+    /// Reveals the nodes below the roots in breadth first order, up to the given depth (in edges) and number of nodes, and collects all remaining nodes in a single placeholder group. Returns the newly created groups.
+    pub fn reveal_bounded(&mut self, max_depth: usize, max_nodes: usize) -> Vec<NodeGroupID> {
+        let mut created = Vec::new();
+        let mut remaining = Vec::new();
+        let roots = self.graph.get_roots();
+        let mut found: HashSet<NodeID> = roots.iter().cloned().collect();
+        let mut frontier = roots;
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                if self.get_node_group_id(node).unwrap_or(0) == 0 {
+                    if depth <= max_depth && created.len() < max_nodes {
+                        let target = TargetID(TargetIDType::NodeID, node);
+                        created.push(self.create_group(vec![target]));
+                    } else {
+                        remaining.push(TargetID(TargetIDType::NodeID, node));
+                    }
+                }
+                for (_, child) in self.graph.get_children(node) {
+                    if found.insert(child) {
+                        next_frontier.push(child);
+                    }
+                }
+            }
+            frontier = next_frontier;
+            depth += 1;
+        }
+
+        if !remaining.is_empty() {
+            let placeholder = self.create_group(remaining);
+            self.placeholder_groups.insert(placeholder);
+            self.update_placeholder_name(placeholder);
+            created.push(placeholder);
+        }
+        created
+    }
+
+    /// Names a placeholder group after the number of nodes that it still contains
+    fn update_placeholder_name(&mut self, group_id: NodeGroupID) {
+        if !self.placeholder_groups.contains(&group_id) {
+            return;
+        }
+        let Some(group) = self.group_by_id.get(&group_id) else {
+            return;
+        };
+        let name = format!("…and {} more nodes", group.nodes.len());
+        self.set_group_name(group_id, name, String::new());
+    }
+
+    /// Collects the shown inner nodes more than `detail_levels` levels below the top root into one summary group per subfunction, named after the levels and number of nodes it spans. Subfunctions are claimed from the top down, and the replaced grouping is remembered such that it can be restored. Returns the created summary groups.
+    pub fn summarize(&mut self, detail_levels: LevelNo) -> Vec<NodeGroupID>
+    where
+        G::LL: ToString,
+    {
+        let roots = self.graph.get_roots();
+        let Some(top_level) = roots.iter().map(|&root| self.graph.get_level(root)).min() else {
+            return Vec::new();
+        };
+        let cutoff = top_level + detail_levels;
+        let terminals: HashSet<NodeID> = self.graph.get_terminals().into_iter().collect();
+        let is_shown =
+            |manager: &Self, node: NodeID| manager.get_node_group_id(node).unwrap_or(0) != 0;
+
+        // Find the shown nodes below the cutoff that are reached from shown nodes above it
+        let mut boundary = Vec::new();
+        let mut found = HashSet::new();
+        let mut frontier = roots;
+        while let Some(node) = frontier.pop() {
+            if terminals.contains(&node) || !is_shown(self, node) || !found.insert(node) {
+                continue;
+            }
+            let level = self.graph.get_level(node);
+            if level >= cutoff {
+                boundary.push((level, node));
+                continue;
+            }
+            frontier.extend(self.graph.get_children(node).into_iter().map(|(_, child)| child));
+        }
+        boundary.sort();
+
+        let mut claimed = HashSet::new();
+        let mut created = Vec::new();
+        for (_, boundary_node) in boundary {
+            let mut subfunction = Vec::new();
+            let mut frontier = vec![boundary_node];
+            while let Some(node) = frontier.pop() {
+                if terminals.contains(&node) || !is_shown(self, node) || !claimed.insert(node) {
+                    continue;
+                }
+                subfunction.push(node);
+                frontier.extend(self.graph.get_children(node).into_iter().map(|(_, child)| child));
+            }
+            // A single node is shown as is
+            if subfunction.len() < 2 {
+                continue;
+            }
+
+            let levels = subfunction
+                .iter()
+                .map(|&node| self.graph.get_level(node))
+                .collect_vec();
+            let min_level = *levels.iter().min().unwrap();
+            let max_level = *levels.iter().max().unwrap();
+            let level_names = if min_level == max_level {
+                self.graph.get_level_label(min_level).to_string()
+            } else {
+                format!(
+                    "{}..{}",
+                    self.graph.get_level_label(min_level).to_string(),
+                    self.graph.get_level_label(max_level).to_string()
+                )
+            };
+            let name = format!("subfunction over {}, {} nodes", level_names, subfunction.len());
+            created.push(self.create_summary(subfunction, name));
+        }
+        created
+    }
+
+    /// Collects every run of at least two groups into one summary group named after the number of nodes it contains, such as the runs of horizontally adjacent groups of a layer with too many groups. Returns the created summary groups.
+    pub fn summarize_runs(&mut self, runs: Vec<Vec<NodeGroupID>>) -> Vec<NodeGroupID> {
+        let mut created = Vec::new();
+        for run in runs.into_iter().filter(|run| run.len() >= 2) {
+            let nodes = run
+                .into_iter()
+                .flat_map(|group| self.get_nodes_of_group(group))
+                .collect_vec();
+            let name = format!("{} nodes", nodes.len());
+            created.push(self.create_summary(nodes, name));
+        }
+        created
+    }
+
+    /// Moves the given nodes into a new group with the given name, and remembers the grouping that it replaced such that it can be restored
+    fn create_summary(&mut self, nodes: Vec<NodeID>, name: String) -> NodeGroupID {
+        let mut replaced = HashMap::<NodeGroupID, Vec<NodeID>>::new();
+        for &node in &nodes {
+            let group = self.get_node_group_id(node).unwrap_or(0);
+            replaced.entry(group).or_insert_with(Vec::new).push(node);
+        }
+        let replaced = replaced
+            .into_iter()
+            .map(|(group, nodes)| SummarizedGroup {
+                group,
+                nodes,
+                name: self.group_names.get(&group).cloned(),
+            })
+            .collect_vec();
+
+        let targets = nodes
+            .into_iter()
+            .map(|node| TargetID(TargetIDType::NodeID, node))
+            .collect();
+        let group_id = self.create_group(targets);
+        self.set_group_name(group_id, name, String::new());
+        self.summaries.insert(group_id, replaced);
+        group_id
+    }
+
+    /// Moves the nodes that are still in summary groups back into the groups they were in before being summarized, recreating the groups that no longer exist. Returns the recreated groups.
+    pub fn restore_summaries(&mut self) -> Vec<NodeGroupID> {
+        let summaries = self.summaries.keys().cloned().collect_vec();
+        self.restore_summary_groups(&summaries)
+    }
+
+    /// Restores the grouping that the given summary groups replaced, like `restore_summaries`, while other summaries are kept. Returns the recreated groups.
+    pub fn restore_summary_groups(&mut self, groups: &[NodeGroupID]) -> Vec<NodeGroupID> {
+        let summaries: HashMap<NodeGroupID, Vec<SummarizedGroup>> = groups
+            .iter()
+            .filter_map(|&group| Some((group, self.summaries.remove(&group)?)))
+            .collect();
+        let mut created = Vec::new();
+        for (summary, replaced) in &summaries {
+            for SummarizedGroup { group, nodes, name } in replaced {
+                let targets = nodes
+                    .iter()
+                    .filter(|&&node| self.get_node_group_id(node) == Some(*summary))
+                    .map(|&node| TargetID(TargetIDType::NodeID, node))
+                    .collect_vec();
+                if targets.is_empty() {
+                    continue;
+                }
+                // The ids of removed groups may have been reused by other summaries
+                let is_summary =
+                    summaries.contains_key(group) || self.summaries.contains_key(group);
+                if self.group_by_id.contains_key(group) && !is_summary {
+                    self.set_group(targets, *group);
+                    continue;
+                }
+                let group_id = self.create_group(targets);
+                if let Some(GroupName { name, note }) = name.clone() {
+                    self.set_group_name(group_id, name, note);
+                }
+                created.push(group_id);
+            }
+        }
+        created
+    }
+
+    /// Turns every subfunction that is referenced by at least `min_occurrences` edges and consists of at least `min_size` inner nodes into a group, labeled with a generated function name. The references of structurally identical subfunctions, which the given hasher identifies by the source nodes of their nodes, are counted together, and their groups share their name. Subfunctions are claimed from the top down, such that shared nodes inside of an earlier group stay part of that group. Returns the newly created groups.
+    pub fn group_shared_subfunctions<H: GraphStructure>(
+        &mut self,
+        min_occurrences: usize,
+        min_size: usize,
+        hasher: &mut StructuralHasher<H>,
+        get_source: impl Fn(NodeID) -> Option<NodeID>,
+    ) -> Vec<NodeGroupID>
+    where
+        H::LL: ToString,
+    {
+        let terminals: HashSet<NodeID> = self.graph.get_terminals().into_iter().collect();
+        let mut reference_counts = HashMap::<NodeID, usize>::new();
+        let mut children = HashMap::<NodeID, Vec<NodeID>>::new();
+        let mut frontier = self.graph.get_roots();
+        while let Some(node) = frontier.pop() {
+            if children.contains_key(&node) {
+                continue;
+            }
+            let node_children = self
+                .graph
+                .get_children(node)
+                .into_iter()
+                .map(|(_, child)| child)
+                .collect_vec();
+            for &child in &node_children {
+                *reference_counts.entry(child).or_insert(0) += 1;
+                frontier.push(child);
+            }
+            children.insert(node, node_children);
+        }
+
+        // Every node is classified by the first node of its class, where nodes without a source are only identical to themselves. Nodes whose hashes collide are told apart by comparing their structure.
+        let mut representatives = HashMap::<u64, Vec<(NodeID, NodeID)>>::new();
+        let mut classes = HashMap::<NodeID, NodeID>::new();
+        for &node in reference_counts.keys() {
+            let Some(source) = get_source(node) else {
+                classes.insert(node, node);
+                continue;
+            };
+            let candidates = representatives.entry(hasher.get_node_hash(source)).or_default();
+            let class = candidates
+                .iter()
+                .find(|&&(other, _)| hasher.is_identical(other, source))
+                .map(|&(_, class)| class);
+            let class = class.unwrap_or_else(|| {
+                candidates.push((source, node));
+                node
+            });
+            classes.insert(node, class);
+        }
+        let mut class_counts = HashMap::<NodeID, usize>::new();
+        for (node, count) in &reference_counts {
+            *class_counts.entry(classes[node]).or_insert(0) += count;
+        }
+        let mut shared = reference_counts
+            .into_keys()
+            .filter(|node| class_counts[&classes[node]] >= min_occurrences)
+            .filter(|node| !terminals.contains(node))
+            .map(|node| (self.graph.get_level(node), node))
+            .collect_vec();
+        shared.sort();
+
+        let mut claimed = HashSet::new();
+        let mut class_names = HashMap::<NodeID, String>::new();
+        let mut created = Vec::new();
+        for (_, shared_node) in shared {
+            if claimed.contains(&shared_node) {
+                continue;
+            }
+            let mut subfunction = HashSet::new();
+            let mut frontier = vec![shared_node];
+            while let Some(node) = frontier.pop() {
+                if terminals.contains(&node) || claimed.contains(&node) {
+                    continue;
+                }
+                if !subfunction.insert(node) {
+                    continue;
+                }
+                frontier.extend(children.get(&node).into_iter().flatten());
+            }
+            if subfunction.len() < min_size {
+                continue;
+            }
+
+            let targets = subfunction
+                .iter()
+                .map(|&node| TargetID(TargetIDType::NodeID, node))
+                .collect();
+            claimed.extend(subfunction);
+            let group_id = self.create_group(targets);
+            let name = class_names
+                .entry(classes[&shared_node])
+                .or_insert_with(|| format!("f{}", group_id))
+                .clone();
+            self.set_group_name(group_id, name, String::new());
+            created.push(group_id);
+        }
+        created
+    }
+
+    /// Moves all inner nodes reachable from the roots whose level label satisfies the given predicate into a single group with the given name. Returns the created group, if any node lies on such a level.
+    pub fn group_levels<M: Fn(&G::LL) -> bool>(
+        &mut self,
+        name: String,
+        matches: M,
+    ) -> Option<NodeGroupID> {
+        let terminals: HashSet<NodeID> = self.graph.get_terminals().into_iter().collect();
+        let mut level_matches = HashMap::<LevelNo, bool>::new();
+        let mut visited = HashSet::new();
+        let mut targets = Vec::new();
+        let mut frontier = self.graph.get_roots();
+        while let Some(node) = frontier.pop() {
+            if terminals.contains(&node) || !visited.insert(node) {
+                continue;
+            }
+            let level = self.graph.get_level(node);
+            let graph = &self.graph;
+            let matched = *level_matches
+                .entry(level)
+                .or_insert_with(|| matches(&graph.get_level_label(level)));
+            if matched {
+                targets.push(TargetID(TargetIDType::NodeID, node));
+            }
+            frontier.extend(self.graph.get_children(node).into_iter().map(|(_, child)| child));
+        }
+        if targets.is_empty() {
+            return None;
+        }
+        let group_id = self.create_group(targets);
+        self.set_group_name(group_id, name, String::new());
+        Some(group_id)
+    }
+
+    /// Moves the subtree below the child reached through the edge with the given index into a single group, leaving terminals in their own groups. Returns the created group, if the node has such a child.
+    pub fn collapse_subtree(&mut self, node: NodeID, edge_index: i32) -> Option<NodeGroupID> {
+        let terminals: HashSet<NodeID> = self.graph.get_terminals().into_iter().collect();
+        let (_, child) = self
+            .graph
+            .get_children(node)
+            .into_iter()
+            .find(|(edge_type, _)| edge_type.index == edge_index)?;
+
+        let mut subtree = HashSet::new();
+        let mut frontier = vec![child];
+        while let Some(node) = frontier.pop() {
+            if terminals.contains(&node) || !subtree.insert(node) {
+                continue;
+            }
+            frontier.extend(self.graph.get_children(node).into_iter().map(|(_, child)| child));
+        }
+        if subtree.is_empty() {
+            return None;
+        }
+
+        let targets = subtree
+            .into_iter()
+            .map(|node| TargetID(TargetIDType::NodeID, node))
+            .collect();
+        Some(self.create_group(targets))
+    }
+
     pub fn split_edges(&mut self, node_ids: &[NodeID], fully: bool) {
         // TODO: come up with a better splitting approach that considers nodes together
         let mut split = HashSet::new();
@@ -821,8 +1380,8 @@ impl<G: GraphStructure> GroupedGraphStructure for GroupManager<G> {
         )
     }
 
-    fn refresh(&mut self) {
-        self.process_graph_events();
+    fn refresh(&mut self) -> Vec<Change> {
+        self.process_graph_events()
     }
 }
 
@@ -836,26 +1395,37 @@ impl<G: GraphStructure + StateStorage> StateStorage for GroupManager<G> {
             let node_count = nodes.len();
             stream.write_u32::<LittleEndian>(*group_id as u32)?;
             stream.write_u32::<LittleEndian>(node_count as u32)?;
-            for node in nodes {
-                stream.write_u32::<LittleEndian>(*node as u32)?;
+            for &node in nodes {
+                write_node_id(&self.graph, node, stream)?;
             }
         }
+        stream.write_u32::<LittleEndian>(self.group_names.len() as u32)?;
+        for (group_id, GroupName { name, note }) in &self.group_names {
+            stream.write_u32::<LittleEndian>(*group_id as u32)?;
+            name.serialize(stream)?;
+            note.serialize(stream)?;
+        }
+        stream.write_u32::<LittleEndian>(self.group_parents.len() as u32)?;
+        for (&group_id, &parent) in &self.group_parents {
+            stream.write_u32::<LittleEndian>(group_id as u32)?;
+            stream.write_u32::<LittleEndian>(parent as u32)?;
+        }
         Ok(())
     }
 
-    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> Result<()> {
         self.graph.consume_events(&self.graph_events);
         self.reset();
 
-        self.graph.read(stream)?;
+        self.graph.read(stream, version)?;
         // No events should be created, but just in case, throw away events
         let events = self.graph.consume_events(&self.graph_events);
         if events.len() > 0 {
-            console::log!(
+            console::log_warn!(
                 "Deserialization should not have caused any events, Event count: {}",
                 events.len()
             );
-            console::log!("Created events: {}", events.iter().join(",\n"));
+            console::log_debug!("Created events: {}", events.iter().join(",\n"));
         }
 
         let mut all_found_nodes = HashSet::new();
@@ -867,7 +1437,10 @@ impl<G: GraphStructure + StateStorage> StateStorage for GroupManager<G> {
 
             let mut targets = Vec::<TargetID>::new();
             for _ in 0..node_count {
-                let node = stream.read_u32::<LittleEndian>()? as usize;
+                // Nodes whose source is no longer part of the graph are left out
+                let Some(node) = read_node_id(&self.graph, stream, version)? else {
+                    continue;
+                };
                 targets.push(TargetID::new(TargetIDType::NodeID, node));
                 all_found_nodes.insert(node);
             }
@@ -895,6 +1468,25 @@ impl<G: GraphStructure + StateStorage> StateStorage for GroupManager<G> {
         for (targets, group_id) in to_add {
             self.set_group(targets, group_id);
         }
+
+        // Legacy states end after the groups, since groups had no names or parents yet
+        if version == LEGACY_STATE_FORMAT_VERSION {
+            return Ok(());
+        }
+        let name_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..name_count {
+            let group_id = stream.read_u32::<LittleEndian>()? as usize;
+            let name = String::deserialize(stream)?;
+            let note = String::deserialize(stream)?;
+            self.set_group_name(group_id, name, note);
+        }
+
+        let parent_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..parent_count {
+            let group_id = stream.read_u32::<LittleEndian>()? as usize;
+            let parent = stream.read_u32::<LittleEndian>()? as usize;
+            self.set_group_parent(group_id, Some(parent));
+        }
         Ok(())
     }
 }
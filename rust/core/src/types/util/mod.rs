@@ -0,0 +1,6 @@
+pub mod drawing;
+pub mod graph_structure;
+pub mod group_manager;
+pub mod node_tracker_manager;
+pub mod storage;
+pub mod structural_hasher;
@@ -7,8 +7,8 @@ use itertools::Itertools;
 use multimap::MultiMap;
 
 use crate::{
+    interface::NodeGroupID,
     util::{free_id_manager::FreeIdManager, logging::console, rc_refcell::MutRcRefCell},
-    wasm_interface::NodeGroupID,
 };
 
 use super::graph_structure::grouped_graph_structure::NodeTracker;
@@ -0,0 +1,113 @@
+use std::io::{Cursor, Error, ErrorKind, Read, Result, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{interface::NodeID, types::util::graph_structure::graph_structure::GraphStructure};
+
+pub trait StateStorage {
+    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+        Ok(())
+    }
+    /// Reads the state written by `write`, or by the given earlier version of the format
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The version of the format of serialized section states, which is increased whenever the format changes
+pub const STATE_FORMAT_VERSION: u32 = 1;
+/// The version of the states that were stored before states were versioned, which only consist of the manipulated graph and the groups, which identify their nodes by their local IDs
+pub const LEGACY_STATE_FORMAT_VERSION: u32 = 0;
+/// Precedes the version at the start of a state, which distinguishes states from those that were stored before states were versioned
+const STATE_FORMAT_MAGIC: [u8; 4] = *b"OXVS";
+
+/// Writes the header that a serialized section state starts with, which identifies the version of its format
+pub fn write_state_header(stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+    stream.write_all(&STATE_FORMAT_MAGIC)?;
+    stream.write_u32::<LittleEndian>(STATE_FORMAT_VERSION)
+}
+
+/// Reads the header written by [`write_state_header`] and returns the version of the format of the state. States without a header were stored before states were versioned, which are read from their start as [`LEGACY_STATE_FORMAT_VERSION`]. Fails for other versions than these, since the remainder of the state would be misread.
+pub fn read_state_header(stream: &mut Cursor<&Vec<u8>>) -> Result<u32> {
+    let mut magic = [0; 4];
+    if stream.read_exact(&mut magic).is_err() || magic != STATE_FORMAT_MAGIC {
+        stream.set_position(0);
+        return Ok(LEGACY_STATE_FORMAT_VERSION);
+    }
+    let version = stream.read_u32::<LittleEndian>()?;
+    if version != STATE_FORMAT_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "the state has format version {}, while versions {} and {} are supported",
+                version, LEGACY_STATE_FORMAT_VERSION, STATE_FORMAT_VERSION
+            ),
+        ));
+    }
+    Ok(version)
+}
+
+// TODO: remove this, and rely on something like serde
+pub trait Serializable: Sized {
+    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()>;
+    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<Self>;
+}
+
+/// Writes a node of the given graph in terms of the source graph node it represents, such that the state remains valid when the same source graph is imported again and the manipulated graph numbers its nodes differently. Nodes that are not the primary local node of their source node, such as copies of duplicated nodes and pointer nodes, are written by their own ID instead.
+pub fn write_node_id<G: GraphStructure>(
+    graph: &G,
+    node: NodeID,
+    stream: &mut Cursor<&mut Vec<u8>>,
+) -> Result<()> {
+    let sources = graph.local_nodes_to_sources(vec![node]);
+    match sources[..] {
+        [source] if graph.source_nodes_to_local(vec![source]).first() == Some(&node) => {
+            stream.write_u8(0)?;
+            stream.write_u32::<LittleEndian>(source as u32)
+        }
+        _ => {
+            stream.write_u8(1)?;
+            stream.write_u32::<LittleEndian>(node as u32)
+        }
+    }
+}
+
+/// Reads a node written by [`write_node_id`], which is none if its source node is no longer part of the graph. Legacy states only contain the local ID of the node.
+pub fn read_node_id<G: GraphStructure>(
+    graph: &G,
+    stream: &mut Cursor<&Vec<u8>>,
+    version: u32,
+) -> Result<Option<NodeID>> {
+    if version == LEGACY_STATE_FORMAT_VERSION {
+        return Ok(Some(stream.read_u32::<LittleEndian>()? as usize));
+    }
+    let is_source = stream.read_u8()? == 0;
+    let id = stream.read_u32::<LittleEndian>()? as usize;
+    Ok(if is_source {
+        graph.source_nodes_to_local(vec![id]).first().cloned()
+    } else {
+        Some(id)
+    })
+}
+
+impl Serializable for () {
+    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<()> {
+        Ok(())
+    }
+    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Serializable for String {
+    fn deserialize(stream: &mut Cursor<&Vec<u8>>) -> Result<String> {
+        let len = stream.read_u32::<LittleEndian>()?;
+        let mut bytes = vec![0; len as usize];
+        stream.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+    fn serialize(&self, stream: &mut Cursor<&mut Vec<u8>>) -> Result<()> {
+        stream.write_u32::<LittleEndian>(self.len() as u32)?;
+        stream.write_all(self.as_bytes())
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use crate::interface::NodeID;
+
+use super::graph_structure::{
+    graph_structure::{Change, GraphEventsReader, GraphStructure},
+    oxidd_graph_structure::{NodeLabel, NodeType},
+};
+
+/// Computes canonical hashes of the subfunctions represented by the nodes of a graph, such that nodes of different diagrams, or of different imports of the same diagram, can be matched up by their structure rather than their ids.
+///
+/// The hash of an inner node is derived from the label of its level, the name of its variable rather than the level number, and the types and hashes of its children in order. Nodes without children are hashed by the key that the given function obtains from their label, such as the value of a terminal. Hashes are only comparable between hashers of the same build, and are cached until the change events of the graph invalidate them. Since different subfunctions may share a hash, equal hashes only indicate identical subfunctions, which `is_identical` confirms by comparing their structure.
+pub struct StructuralHasher<G: GraphStructure> {
+    graph: G,
+    graph_events: GraphEventsReader,
+    terminal_key: Box<dyn Fn(&G::NL) -> String>,
+    hashes: HashMap<NodeID, u64>,
+    /// The parents whose cached hash was computed from the hash of the node
+    dependents: HashMap<NodeID, HashSet<NodeID>>,
+    /// The pairs of distinct nodes whose structure was found to be identical, which are kept until the graph changes
+    identical: HashSet<(NodeID, NodeID)>,
+}
+
+impl<G: GraphStructure> StructuralHasher<G>
+where
+    G::LL: ToString,
+{
+    pub fn new<F: Fn(&G::NL) -> String + 'static>(
+        mut graph: G,
+        terminal_key: F,
+    ) -> StructuralHasher<G> {
+        StructuralHasher {
+            graph_events: graph.create_event_reader(),
+            graph,
+            terminal_key: Box::new(terminal_key),
+            hashes: HashMap::new(),
+            dependents: HashMap::new(),
+            identical: HashSet::new(),
+        }
+    }
+
+    /// Retrieves the graph whose nodes are hashed, e.g. to discover nodes that are not reachable from its roots
+    pub fn get_graph(&mut self) -> &mut G {
+        &mut self.graph
+    }
+
+    /// Retrieves the hash of the subfunction represented by the given node
+    pub fn get_node_hash(&mut self, node: NodeID) -> u64 {
+        self.process_graph_events();
+
+        // The nodes whose children are being hashed, which are skipped when reached again through a cycle
+        let mut in_progress = HashSet::new();
+        let mut stack = vec![(node, false)];
+        while let Some((current, expanded)) = stack.pop() {
+            if self.hashes.contains_key(&current) || (!expanded && in_progress.contains(&current))
+            {
+                continue;
+            }
+            let children = self.graph.get_children(current);
+            if !expanded {
+                in_progress.insert(current);
+                stack.push((current, true));
+                stack.extend(
+                    children
+                        .iter()
+                        .filter(|(_, child)| {
+                            !self.hashes.contains_key(child) && !in_progress.contains(child)
+                        })
+                        .map(|&(_, child)| (child, false)),
+                );
+                continue;
+            }
+            in_progress.remove(&current);
+
+            let mut hasher = DefaultHasher::new();
+            if children.is_empty() {
+                (self.terminal_key)(&self.graph.get_node_label(current)).hash(&mut hasher);
+            } else {
+                let level = self.graph.get_level(current);
+                self.graph.get_level_label(level).to_string().hash(&mut hasher);
+                for (edge_type, child) in children {
+                    edge_type.hash(&mut hasher);
+                    // Children on a cycle, which only occur in malformed graphs, have no hash yet
+                    self.hashes.get(&child).cloned().unwrap_or(0).hash(&mut hasher);
+                    self.dependents.entry(child).or_default().insert(current);
+                }
+            }
+            self.hashes.insert(current, hasher.finish());
+        }
+        self.hashes.get(&node).cloned().unwrap_or(0)
+    }
+
+    /// Checks whether the given nodes represent structurally identical subfunctions, where the hashes rule out most different nodes before their structure is compared
+    pub fn is_identical(&mut self, a: NodeID, b: NodeID) -> bool {
+        self.process_graph_events();
+
+        // Pairs reached again, e.g. through a cycle, are assumed identical while they are being compared
+        let mut compared = HashSet::new();
+        let mut pairs = vec![(a, b)];
+        while let Some((a, b)) = pairs.pop() {
+            if a == b || self.identical.contains(&(a, b)) || !compared.insert((a, b)) {
+                continue;
+            }
+            if self.get_node_hash(a) != self.get_node_hash(b) {
+                return false;
+            }
+            let (children_a, children_b) = (self.graph.get_children(a), self.graph.get_children(b));
+            if children_a.len() != children_b.len() {
+                return false;
+            }
+            if children_a.is_empty() {
+                let key_a = (self.terminal_key)(&self.graph.get_node_label(a));
+                if key_a != (self.terminal_key)(&self.graph.get_node_label(b)) {
+                    return false;
+                }
+                continue;
+            }
+            let (level_a, level_b) = (self.graph.get_level(a), self.graph.get_level(b));
+            let label_a = self.graph.get_level_label(level_a).to_string();
+            if label_a != self.graph.get_level_label(level_b).to_string() {
+                return false;
+            }
+            for ((type_a, child_a), (type_b, child_b)) in children_a.into_iter().zip(children_b) {
+                if type_a != type_b {
+                    return false;
+                }
+                pairs.push((child_a, child_b));
+            }
+        }
+        self.identical.extend(compared);
+        true
+    }
+
+    fn process_graph_events(&mut self) {
+        for event in self.graph.consume_events(&self.graph_events) {
+            match event {
+                Change::LevelLabelChange { .. } => {
+                    self.hashes.clear();
+                    self.dependents.clear();
+                    self.identical.clear();
+                }
+                Change::ParentDiscover { .. } => {}
+                Change::NodeLabelChange { node }
+                | Change::LevelChange { node }
+                | Change::NodeConnectionsChange { node }
+                | Change::NodeRemoval { node }
+                | Change::NodeInsertion { node, .. } => {
+                    self.invalidate(node);
+                    self.identical.clear();
+                }
+            }
+        }
+    }
+
+    /// Removes the cached hashes of the node and all nodes whose hash was derived from it
+    fn invalidate(&mut self, node: NodeID) {
+        let mut frontier = vec![node];
+        while let Some(node) = frontier.pop() {
+            self.hashes.remove(&node);
+            frontier.extend(self.dependents.remove(&node).into_iter().flatten());
+        }
+    }
+}
+
+/// Retrieves the key by which the nodes of a decision diagram without children are hashed, which is the value of the terminal
+pub fn get_terminal_key<T: ToString>(label: &NodeLabel<T>) -> String {
+    match &label.kind {
+        NodeType::Terminal(value) => value.to_string(),
+        NodeType::Inner(id) => id.clone(),
+    }
+}
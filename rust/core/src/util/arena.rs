@@ -0,0 +1,231 @@
+use std::{collections::HashMap, hash::Hash, iter::FromIterator, mem};
+
+/// An identifier of an item in an arena, the generation prevents a removed item's id from accessing an item that reused its slot
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ArenaID {
+    index: u32,
+    generation: u32,
+}
+
+enum Slot<T> {
+    Occupied { generation: u32, value: T },
+    // Free slots form a linked list of slots that can be reused
+    Free { generation: u32, next_free: Option<u32> },
+}
+
+/// A contiguous storage of items, indexed by generational ids
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    first_free: Option<u32>,
+    len: usize,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Arena<T> {
+        Arena {
+            slots: Vec::new(),
+            first_free: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, value: T) -> ArenaID {
+        self.len += 1;
+        if let Some(index) = self.first_free {
+            let slot = &mut self.slots[index as usize];
+            let Slot::Free {
+                generation,
+                next_free,
+            } = *slot
+            else {
+                unreachable!("Free list points to an occupied slot");
+            };
+            self.first_free = next_free;
+            *slot = Slot::Occupied { generation, value };
+            return ArenaID { index, generation };
+        }
+
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot::Occupied {
+            generation: 0,
+            value,
+        });
+        ArenaID {
+            index,
+            generation: 0,
+        }
+    }
+
+    /// Removes the item with the given id, after which the id (and any copies of it) no longer refer to any item
+    pub fn remove(&mut self, id: ArenaID) -> Option<T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        match slot {
+            Slot::Occupied { generation, .. } if *generation == id.generation => {}
+            _ => return None,
+        }
+        let free = Slot::Free {
+            generation: id.generation.wrapping_add(1),
+            next_free: self.first_free,
+        };
+        let Slot::Occupied { value, .. } = mem::replace(slot, free) else {
+            unreachable!();
+        };
+        self.first_free = Some(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, id: ArenaID) -> Option<&T> {
+        match self.slots.get(id.index as usize)? {
+            Slot::Occupied { generation, value } if *generation == id.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, id: ArenaID) -> Option<&mut T> {
+        match self.slots.get_mut(id.index as usize)? {
+            Slot::Occupied { generation, value } if *generation == id.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.first_free = None;
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaID, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                Slot::Occupied { generation, value } => Some((
+                    ArenaID {
+                        index: index as u32,
+                        generation: *generation,
+                    },
+                    value,
+                )),
+                Slot::Free { .. } => None,
+            })
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena::new()
+    }
+}
+
+/// A map whose entries are stored contiguously in an arena, such that only a small arena id is hashed per key. Keys may be sparse, and removed entries free their slot for reuse.
+pub struct ArenaMap<K: Hash + Eq + Copy, V> {
+    index: HashMap<K, ArenaID>,
+    entries: Arena<(K, V)>,
+}
+
+impl<K: Hash + Eq + Copy, V> ArenaMap<K, V> {
+    pub fn new() -> ArenaMap<K, V> {
+        ArenaMap {
+            index: HashMap::new(),
+            entries: Arena::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let id = *self.index.get(key)?;
+        self.entries.get(id).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let id = *self.index.get(key)?;
+        self.entries.get_mut(id).map(|(_, value)| value)
+    }
+
+    /// Inserts the value for the given key, returning the value that was previously stored for it
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(mem::replace(old, value));
+        }
+        let id = self.entries.insert((key, value));
+        self.index.insert(key, id);
+        None
+    }
+
+    /// Retrieves the value for the given key, inserting the value created by `init` if the key is not present yet
+    pub fn get_or_insert_with(&mut self, key: K, init: impl FnOnce() -> V) -> &mut V {
+        let id = match self.index.get(&key) {
+            Some(&id) => id,
+            None => {
+                let id = self.entries.insert((key, init()));
+                self.index.insert(key, id);
+                id
+            }
+        };
+        &mut self.entries.get_mut(id).unwrap().1
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let id = self.index.remove(key)?;
+        self.entries.remove(id).map(|(_, value)| value)
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(_, (key, value))| (key, value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: Hash + Eq + Copy, V> Default for ArenaMap<K, V> {
+    fn default() -> Self {
+        ArenaMap::new()
+    }
+}
+
+impl<K: Hash + Eq + Copy, V: Clone> Clone for ArenaMap<K, V> {
+    fn clone(&self) -> Self {
+        self.iter().map(|(&key, value)| (key, value.clone())).collect()
+    }
+}
+
+impl<K: Hash + Eq + Copy, V> FromIterator<(K, V)> for ArenaMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = ArenaMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
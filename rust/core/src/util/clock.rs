@@ -0,0 +1,22 @@
+use std::{cell::Cell, sync::OnceLock, time::Instant};
+
+/// The instant from which the default clock measures time
+static START: OnceLock<Instant> = OnceLock::new();
+
+thread_local! {
+    static CLOCK: Cell<fn() -> f64> = Cell::new(measure_instant);
+}
+
+fn measure_instant() -> f64 {
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.
+}
+
+/// Replaces the clock that measures the current time in milliseconds, which is needed on platforms without `Instant`, such as the browser
+pub fn set_clock(clock: fn() -> f64) {
+    CLOCK.with(|current| current.set(clock));
+}
+
+/// Retrieves the current time in milliseconds, such that durations can be measured, e.g. in benchmarks. Only differences between two times are meaningful, since the default clock starts at its first use.
+pub fn now() -> f64 {
+    CLOCK.with(|clock| clock.get())()
+}
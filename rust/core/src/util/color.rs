@@ -24,6 +24,24 @@ impl Into<TransparentColor> for Color {
     }
 }
 
+/// Retrieves the color with the given index in a palette of distinguishable colors, whose hues are spread by the golden angle such that every index keeps its color however many colors are used
+pub fn get_palette_color(index: usize) -> Color {
+    let hue = (index as f32 * 0.381966).fract() * 6.;
+    let (saturation, lightness) = (0.65, 0.5);
+    let chroma = (1. - (2. * lightness - 1.f32).abs()) * saturation;
+    let x = chroma * (1. - (hue % 2. - 1.).abs());
+    let (r, g, b) = match hue as usize {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+    let m = lightness - 0.5 * chroma;
+    Color(r + m, g + m, b + m)
+}
+
 #[derive(Clone, PartialEq, Copy)]
 pub struct TransparentColor(pub f32, pub f32, pub f32, pub f32);
 impl Interpolatable for TransparentColor {
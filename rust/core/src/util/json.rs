@@ -0,0 +1,251 @@
+use std::fmt::Display;
+
+/// A minimal JSON value, used for small human readable exchange formats
+#[derive(Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// The entries of an object in the order they were given
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Parses the given text as a single JSON value, or describes where the text is invalid
+    pub fn parse(text: &str) -> Result<JsonValue, String> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            index: 0,
+        };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.index < parser.chars.len() {
+            return Err(parser.error("Unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{}", value),
+            JsonValue::Number(number) if number.is_finite() => write!(f, "{}", number),
+            JsonValue::Number(_) => write!(f, "null"),
+            JsonValue::String(text) => write_string(f, text),
+            JsonValue::Array(items) => {
+                write!(f, "[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(entries) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for char in text.chars() {
+        match char {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            char if (char as u32) < 0x20 => write!(f, "\\u{:04x}", char as u32)?,
+            char => write!(f, "{}", char)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> String {
+        format!("{} at character {}", message, self.index)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.index).is_some_and(|c| c.is_whitespace()) {
+            self.index += 1;
+        }
+    }
+
+    /// Skips the given characters if they are next, returning whether they were
+    fn consume(&mut self, expected: &str) -> bool {
+        let matches = expected
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.chars.get(self.index + offset) == Some(&c));
+        if matches {
+            self.index += expected.chars().count();
+        }
+        matches
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.chars.get(self.index) {
+            None => Err(self.error("Unexpected end of input")),
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some(_) if self.consume("null") => Ok(JsonValue::Null),
+            Some(_) if self.consume("true") => Ok(JsonValue::Bool(true)),
+            Some(_) if self.consume("false") => Ok(JsonValue::Bool(false)),
+            Some(_) => Err(self.error("Unexpected character")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.index += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.consume("}") {
+            return Ok(JsonValue::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.chars.get(self.index) != Some(&'"') {
+                return Err(self.error("Expected a key"));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if !self.consume(":") {
+                return Err(self.error("Expected ':'"));
+            }
+            entries.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            if self.consume("}") {
+                return Ok(JsonValue::Object(entries));
+            }
+            if !self.consume(",") {
+                return Err(self.error("Expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        self.index += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.consume("]") {
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            if self.consume("]") {
+                return Ok(JsonValue::Array(items));
+            }
+            if !self.consume(",") {
+                return Err(self.error("Expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.index += 1;
+        let mut text = String::new();
+        loop {
+            let Some(&char) = self.chars.get(self.index) else {
+                return Err(self.error("Unterminated string"));
+            };
+            self.index += 1;
+            match char {
+                '"' => return Ok(text),
+                '\\' => {
+                    let Some(&escaped) = self.chars.get(self.index) else {
+                        return Err(self.error("Unterminated string"));
+                    };
+                    self.index += 1;
+                    match escaped {
+                        'n' => text.push('\n'),
+                        'r' => text.push('\r'),
+                        't' => text.push('\t'),
+                        'b' => text.push('\u{8}'),
+                        'f' => text.push('\u{c}'),
+                        'u' => {
+                            let code = self
+                                .chars
+                                .get(self.index..self.index + 4)
+                                .map(|digits| digits.iter().collect::<String>())
+                                .and_then(|digits| u32::from_str_radix(&digits, 16).ok())
+                                .ok_or_else(|| self.error("Invalid unicode escape"))?;
+                            self.index += 4;
+                            text.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        other => text.push(other),
+                    }
+                }
+                char => text.push(char),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let start = self.index;
+        while self
+            .chars
+            .get(self.index)
+            .is_some_and(|c| matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+        {
+            self.index += 1;
+        }
+        let text = self.chars[start..self.index].iter().collect::<String>();
+        text.parse()
+            .map(JsonValue::Number)
+            .map_err(|_| self.error("Invalid number"))
+    }
+}
@@ -0,0 +1,21 @@
+/// Fills in the placeholders of a label template such as `{var}@{level} (#{id})`, retrieving the text of every placeholder by its name. Placeholders without a text, and braces that do not enclose a placeholder, are kept as they are.
+pub fn fill_label_template(template: &str, get_text: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(length) = rest.find('}') else {
+            break;
+        };
+        let placeholder = &rest[..length + 1];
+        match get_text(&placeholder[1..length]) {
+            Some(text) => out.push_str(&text),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[length + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
@@ -0,0 +1,142 @@
+use std::{cell::RefCell, collections::VecDeque};
+
+/// Writes a message of the given level, which a frontend can replace to forward messages elsewhere, such as to the browser console
+pub type LogSink = fn(LogLevel, &str);
+
+// Outside of a frontend that sets a sink, such as in native benchmarks, messages are written to the
+// standard error
+fn write_stderr(level: LogLevel, message: &str) {
+    eprintln!("[{:?}] {}", level, message);
+}
+
+/// The severity of a log message, ordered from the most to the least severe. Messages are only written to the sink if their level does not exceed the configured level.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LogLevel {
+    /// Writes no messages at all
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// The number of warnings that are kept for the UI, older warnings are dropped first
+const MAX_BUFFERED_WARNINGS: usize = 100;
+
+struct LogState {
+    level: LogLevel,
+    sink: LogSink,
+    /// The errors and warnings that were not yet cleared, regardless of the configured level
+    warnings: VecDeque<String>,
+}
+
+thread_local! {
+    static LOG_STATE: RefCell<LogState> = RefCell::new(LogState {
+        level: LogLevel::Warn,
+        sink: write_stderr,
+        warnings: VecDeque::new(),
+    });
+}
+
+/// Sets the most verbose level of the messages that are written to the sink
+pub fn set_log_level(level: LogLevel) {
+    LOG_STATE.with(|state| state.borrow_mut().level = level);
+}
+
+/// Sets the function that the enabled messages are written to
+pub fn set_log_sink(sink: LogSink) {
+    LOG_STATE.with(|state| state.borrow_mut().sink = sink);
+}
+
+/// Writes the message regardless of the configured level
+pub fn log(message: &str) {
+    let sink = LOG_STATE.with(|state| state.borrow().sink);
+    sink(LogLevel::Info, message);
+}
+
+/// Whether a message of the given level has any effect, such that formatting it can be skipped otherwise
+pub fn is_recorded(level: LogLevel) -> bool {
+    level <= LogLevel::Warn || LOG_STATE.with(|state| level <= state.borrow().level)
+}
+
+/// Writes the message to the sink if its level is enabled, and keeps errors and warnings in the warning buffer
+pub fn record(level: LogLevel, message: String) {
+    if level == LogLevel::Off {
+        return;
+    }
+    let sink = LOG_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if level <= LogLevel::Warn {
+            if state.warnings.len() >= MAX_BUFFERED_WARNINGS {
+                state.warnings.pop_front();
+            }
+            state.warnings.push_back(message.clone());
+        }
+        (level <= state.level).then_some(state.sink)
+    });
+    if let Some(sink) = sink {
+        sink(level, &message);
+    }
+}
+
+/// Retrieves the buffered errors and warnings, from the oldest to the newest
+pub fn get_warnings() -> Vec<String> {
+    LOG_STATE.with(|state| state.borrow().warnings.iter().cloned().collect())
+}
+
+pub fn clear_warnings() {
+    LOG_STATE.with(|state| state.borrow_mut().warnings.clear());
+}
+
+#[macro_export]
+macro_rules! log {
+    ($($t:tt)*) => (
+        $crate::util::logging::log(&format_args!($($t)*).to_string())
+    )
+}
+
+#[macro_export]
+macro_rules! log_at {
+    ($level:expr, $($t:tt)*) => ({
+        let level = $level;
+        if $crate::util::logging::is_recorded(level) {
+            $crate::util::logging::record(level, format!($($t)*));
+        }
+    })
+}
+#[macro_export]
+macro_rules! log_error {
+    ($($t:tt)*) => (
+        $crate::util::logging::console::log_at!($crate::util::logging::LogLevel::Error, $($t)*)
+    )
+}
+#[macro_export]
+macro_rules! log_warn {
+    ($($t:tt)*) => (
+        $crate::util::logging::console::log_at!($crate::util::logging::LogLevel::Warn, $($t)*)
+    )
+}
+#[macro_export]
+macro_rules! log_info {
+    ($($t:tt)*) => (
+        $crate::util::logging::console::log_at!($crate::util::logging::LogLevel::Info, $($t)*)
+    )
+}
+#[macro_export]
+macro_rules! log_debug {
+    ($($t:tt)*) => (
+        $crate::util::logging::console::log_at!($crate::util::logging::LogLevel::Debug, $($t)*)
+    )
+}
+#[macro_export]
+macro_rules! log_trace {
+    ($($t:tt)*) => (
+        $crate::util::logging::console::log_at!($crate::util::logging::LogLevel::Trace, $($t)*)
+    )
+}
+
+/// The macros writing to the log, such as `console::log_warn!`, of which the errors and warnings are also kept for the UI
+pub mod console {
+    pub use crate::{log, log_at, log_debug, log_error, log_info, log_trace, log_warn};
+}
@@ -0,0 +1,15 @@
+pub mod arena;
+pub mod clock;
+pub mod color;
+pub mod free_id_manager;
+pub mod json;
+pub mod label_template;
+pub mod logging;
+pub mod matrix4;
+pub mod point;
+pub mod random;
+pub mod rc_refcell;
+pub mod rectangle;
+pub mod transformation;
+pub mod transition;
+pub mod viz_error;
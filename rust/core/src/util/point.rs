@@ -25,6 +25,20 @@ impl Point {
         (self.x * self.x + self.y * self.y).sqrt()
     }
 }
+/// Whether the two segments cross each other, where segments that only touch (such as edges of a shared node) or overlap collinearly do not cross
+pub fn segments_cross((a1, a2): (Point, Point), (b1, b2): (Point, Point)) -> bool {
+    let orientation = |o: Point, a: Point, b: Point| {
+        let value = (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x);
+        if value.abs() <= 1e-6 {
+            0.
+        } else {
+            value.signum()
+        }
+    };
+    orientation(a1, a2, b1) * orientation(a1, a2, b2) < 0.
+        && orientation(b1, b2, a1) * orientation(b1, b2, a2) < 0.
+}
+
 impl Add for Point {
     type Output = Point;
 
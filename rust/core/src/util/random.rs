@@ -0,0 +1,26 @@
+use std::{
+    cell::RefCell,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use seeded_random::{Random, Seed};
+
+use super::clock;
+
+thread_local! {
+    static RANDOM: RefCell<Random> =
+        RefCell::new(Random::from_seed(Seed::unsafe_new(create_seed())));
+}
+
+/// Derives a seed that differs per run, where the clock is mixed in for platforms without randomly keyed hashers
+fn create_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(clock::now().to_bits());
+    hasher.finish()
+}
+
+/// Retrieves a pseudo-random number in the range [0, 1), for the layouts that are randomized on every run. Layouts that have to be reproducible use a `Random` of an explicit seed instead.
+pub fn random() -> f64 {
+    RANDOM.with(|random| random.borrow_mut().range(0, u32::MAX) as f64 / u32::MAX as f64)
+}
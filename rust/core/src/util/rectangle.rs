@@ -41,6 +41,30 @@ impl Rectangle {
             && other.y + other.height >= self.y
     }
 
+    /// Checks whether the line segment between the given points passes through this rectangle
+    pub fn overlaps_segment(&self, start: Point, end: Point) -> bool {
+        let delta = end - start;
+        let (mut t_min, mut t_max) = (0f32, 1f32);
+        // Clip the segment against each of the four sides
+        for (p, q) in [
+            (-delta.x, start.x - self.x),
+            (delta.x, self.x + self.width - start.x),
+            (-delta.y, start.y - self.y),
+            (delta.y, self.y + self.height - start.y),
+        ] {
+            if p == 0. {
+                if q < 0. {
+                    return false;
+                }
+            } else if p < 0. {
+                t_min = t_min.max(q / p);
+            } else {
+                t_max = t_max.min(q / p);
+            }
+        }
+        t_min <= t_max
+    }
+
     pub fn contains(&self, other: &Rectangle) -> bool {
         self.x + self.width >= other.x + other.width
             && self.x <= other.x
@@ -20,6 +20,22 @@ impl Transformation {
             scale: 1.0,
         }
     }
+    /// Retrieves the range of x coordinates in diagram space that is shown, which spans the corners of the rotated view
+    pub fn get_visible_x_range(&self) -> (f32, f32) {
+        let half_width = self.width / self.scale;
+        let half_height = self.height / self.scale;
+        let (sin, cos) = f32::sin_cos(self.angle);
+        [(-1., -1.), (-1., 1.), (1., -1.), (1., 1.)]
+            .iter()
+            .map(|&(dx, dy)| {
+                let x = dx * half_width - self.position.x;
+                let y = dy * half_height - self.position.y;
+                cos * x + sin * y
+            })
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), x| {
+                (min.min(x), max.max(x))
+            })
+    }
     pub fn get_matrix(&self) -> Matrix4 {
         let asx = 1.0 / self.width;
         let asy = 1.0 / self.height;
@@ -35,6 +35,12 @@ impl<T: Interpolatable + Clone> Transition<T> {
         f32::max(0.0, f32::min(per, 1.0))
     }
 }
+impl<T: Interpolatable + Clone> Transition<T> {
+    /// The time at which the transition has reached its new value
+    pub fn get_end_time(&self) -> u32 {
+        self.old_time.saturating_add(self.duration)
+    }
+}
 impl<T: Interpolatable + Clone> Transition<T> {
     pub fn plain(val: T) -> Transition<T> {
         Transition {
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+/// The reasons why an operation of the public interface failed, where the kind of the error allows frontends to show actionable messages, e.g. as the name of a thrown JS error
+#[derive(Clone, Debug, PartialEq)]
+pub enum VizError {
+    /// The input could not be parsed, with a description of the problem
+    ParseError(String),
+    /// The input or operation is not supported by the diagram type
+    UnsupportedFormat(String),
+    /// The graph does not have the structure required by the diagram type
+    InconsistentGraph(String),
+    /// An invariant of the tool itself was violated, after which the section can be recovered to its last consistent state
+    Internal(String),
+    OutOfMemory,
+    Cancelled,
+}
+
+impl VizError {
+    /// The name of the kind of the error, which is also the name of the thrown JS error
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            VizError::ParseError(_) => "ParseError",
+            VizError::UnsupportedFormat(_) => "UnsupportedFormat",
+            VizError::InconsistentGraph(_) => "InconsistentGraph",
+            VizError::Internal(_) => "InternalError",
+            VizError::OutOfMemory => "OutOfMemory",
+            VizError::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl Display for VizError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VizError::ParseError(message) => {
+                write!(f, "The input could not be parsed: {}", message)
+            }
+            VizError::UnsupportedFormat(message) => write!(f, "{}", message),
+            VizError::InconsistentGraph(message) => {
+                write!(f, "The graph is not a valid diagram: {}", message)
+            }
+            VizError::Internal(message) => write!(f, "An internal error occurred: {}", message),
+            VizError::OutOfMemory => write!(f, "The diagram does not fit into memory"),
+            VizError::Cancelled => write!(f, "The operation was cancelled"),
+        }
+    }
+}
+
+impl From<oxidd::util::OutOfMemory> for VizError {
+    fn from(_: oxidd::util::OutOfMemory) -> VizError {
+        VizError::OutOfMemory
+    }
+}
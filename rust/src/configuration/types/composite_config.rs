@@ -95,6 +95,12 @@ impl<A: Abstractable + Clone + 'static> GetConfigChildren for A {
     }
 }
 
+impl<A: GetConfigChildren> GetConfigChildren for Vec<A> {
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        self.iter().flat_map(|child| child.get_children()).collect()
+    }
+}
+
 impl<A: GetConfigChildren, B: GetConfigChildren> GetConfigChildren for (A, B) {
     fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
         let mut out = self.0.get_children();
@@ -256,3 +262,131 @@ impl<
         out
     }
 }
+impl<
+        A: GetConfigChildren,
+        B: GetConfigChildren,
+        C: GetConfigChildren,
+        D: GetConfigChildren,
+        E: GetConfigChildren,
+        F: GetConfigChildren,
+        G: GetConfigChildren,
+        H: GetConfigChildren,
+        I: GetConfigChildren,
+        J: GetConfigChildren,
+        K: GetConfigChildren,
+    > GetConfigChildren for (A, B, C, D, E, F, G, H, I, J, K)
+{
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        let mut out = self.0.get_children();
+        out.extend(self.1.get_children());
+        out.extend(self.2.get_children());
+        out.extend(self.3.get_children());
+        out.extend(self.4.get_children());
+        out.extend(self.5.get_children());
+        out.extend(self.6.get_children());
+        out.extend(self.7.get_children());
+        out.extend(self.8.get_children());
+        out.extend(self.9.get_children());
+        out.extend(self.10.get_children());
+        out
+    }
+}
+impl<
+        A: GetConfigChildren,
+        B: GetConfigChildren,
+        C: GetConfigChildren,
+        D: GetConfigChildren,
+        E: GetConfigChildren,
+        F: GetConfigChildren,
+        G: GetConfigChildren,
+        H: GetConfigChildren,
+        I: GetConfigChildren,
+        J: GetConfigChildren,
+        K: GetConfigChildren,
+        L: GetConfigChildren,
+    > GetConfigChildren for (A, B, C, D, E, F, G, H, I, J, K, L)
+{
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        let mut out = self.0.get_children();
+        out.extend(self.1.get_children());
+        out.extend(self.2.get_children());
+        out.extend(self.3.get_children());
+        out.extend(self.4.get_children());
+        out.extend(self.5.get_children());
+        out.extend(self.6.get_children());
+        out.extend(self.7.get_children());
+        out.extend(self.8.get_children());
+        out.extend(self.9.get_children());
+        out.extend(self.10.get_children());
+        out.extend(self.11.get_children());
+        out
+    }
+}
+impl<
+        A: GetConfigChildren,
+        B: GetConfigChildren,
+        C: GetConfigChildren,
+        D: GetConfigChildren,
+        E: GetConfigChildren,
+        F: GetConfigChildren,
+        G: GetConfigChildren,
+        H: GetConfigChildren,
+        I: GetConfigChildren,
+        J: GetConfigChildren,
+        K: GetConfigChildren,
+        L: GetConfigChildren,
+        M: GetConfigChildren,
+    > GetConfigChildren for (A, B, C, D, E, F, G, H, I, J, K, L, M)
+{
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        let mut out = self.0.get_children();
+        out.extend(self.1.get_children());
+        out.extend(self.2.get_children());
+        out.extend(self.3.get_children());
+        out.extend(self.4.get_children());
+        out.extend(self.5.get_children());
+        out.extend(self.6.get_children());
+        out.extend(self.7.get_children());
+        out.extend(self.8.get_children());
+        out.extend(self.9.get_children());
+        out.extend(self.10.get_children());
+        out.extend(self.11.get_children());
+        out.extend(self.12.get_children());
+        out
+    }
+}
+impl<
+        A: GetConfigChildren,
+        B: GetConfigChildren,
+        C: GetConfigChildren,
+        D: GetConfigChildren,
+        E: GetConfigChildren,
+        F: GetConfigChildren,
+        G: GetConfigChildren,
+        H: GetConfigChildren,
+        I: GetConfigChildren,
+        J: GetConfigChildren,
+        K: GetConfigChildren,
+        L: GetConfigChildren,
+        M: GetConfigChildren,
+        N: GetConfigChildren,
+    > GetConfigChildren for (A, B, C, D, E, F, G, H, I, J, K, L, M, N)
+{
+    fn get_children(&self) -> Vec<Box<dyn Abstractable>> {
+        let mut out = self.0.get_children();
+        out.extend(self.1.get_children());
+        out.extend(self.2.get_children());
+        out.extend(self.3.get_children());
+        out.extend(self.4.get_children());
+        out.extend(self.5.get_children());
+        out.extend(self.6.get_children());
+        out.extend(self.7.get_children());
+        out.extend(self.8.get_children());
+        out.extend(self.9.get_children());
+        out.extend(self.10.get_children());
+        out.extend(self.11.get_children());
+        out.extend(self.12.get_children());
+        out.extend(self.13.get_children());
+        out
+    }
+}
@@ -1,15 +1,23 @@
 mod configuration;
 mod traits;
-mod types;
-mod util;
-mod wasm_interface;
+// The graph structures and layouts are public such that they can be benchmarked and tested natively
+pub mod types;
+pub mod util;
+pub mod wasm_interface;
+
+pub use oxidd_viz_core::interface;
 
 use std::collections::BTreeMap;
 
 use itertools::Itertools;
 // use js_sys::Uint32Array;
 use oxidd::{bdd::BDDManagerRef, ManagerRef};
-use util::{logging::console, panic_hook::set_panic_hook};
+use util::{
+    browser,
+    logging::{self, console},
+    panic_hook::set_panic_hook,
+    profiler,
+};
 // use utils::*;
 use wasm_bindgen::prelude::*;
 use web_sys::{Document, Element, HtmlElement, Window};
@@ -17,6 +25,10 @@ use web_sys::{Document, Element, HtmlElement, Window};
 use configuration::configuration_object::ConfigurationObject;
 use oxidd::{bdd::BDDFunction, util::AllocResult, BooleanFunction};
 use types::{mtbdd::mtbdd_drawer::MTBDDDiagram, qdd::qdd_drawer::QDDDiagram};
+#[cfg(feature = "webgpu")]
+use types::util::drawing::renderers::webgpu_renderer::WebgpuContext;
+#[cfg(feature = "webgpu")]
+use web_sys::HtmlCanvasElement;
 
 use swash::{
     proxy::{CharmapProxy, MetricsProxy},
@@ -26,10 +38,20 @@ use swash::{
 };
 
 use crate::{
-    util::dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
-    wasm_interface::DiagramBox,
+    util::{
+        dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+        graph_builder::GraphBuilder,
+        import_batch::ImportBatch,
+    },
+    wasm_interface::{DiagramBox, LogLevel},
 };
 
+/// Connects the logging and the clock of the engine to the browser once the module is loaded
+#[wasm_bindgen(start)]
+pub fn start() {
+    browser::connect();
+}
+
 #[wasm_bindgen]
 pub fn create_qdd_diagram() -> Option<DiagramBox> // And some DD type param
 {
@@ -37,6 +59,59 @@ pub fn create_qdd_diagram() -> Option<DiagramBox> // And some DD type param
     Some(DiagramBox::new(Box::new(QDDDiagram::new())))
 }
 
+/// Creates an empty graph, which can be filled with nodes and edges and then shown as a section of a diagram
+#[wasm_bindgen]
+pub fn create_graph_builder() -> GraphBuilder {
+    GraphBuilder::new()
+}
+
+/// Creates an empty batch of dump files, which can be filled with the contents of files and then imported into a single section of a diagram
+#[wasm_bindgen]
+pub fn create_import_batch() -> ImportBatch {
+    ImportBatch::new()
+}
+
+/// Sets the most verbose level of the messages that are written to the browser console, where errors and warnings are always kept for `get_log_warnings`
+#[wasm_bindgen]
+pub fn set_log_level(level: LogLevel) {
+    logging::set_log_level(level.into());
+}
+
+/// Retrieves the errors and warnings reported since they were last cleared, from the oldest to the newest, such that they can be shown to the user
+#[wasm_bindgen]
+pub fn get_log_warnings() -> Vec<String> {
+    logging::get_warnings()
+}
+
+#[wasm_bindgen]
+pub fn clear_log_warnings() {
+    logging::clear_warnings();
+}
+
+/// Retrieves the durations of the parse, layout and render phases of the recent frames as JSON, such that it can be attached to bug reports. The durations are only recorded if the crate is built with the `profiling` feature, which the `enabled` entry of the profile indicates.
+#[wasm_bindgen]
+pub fn get_profile() -> String {
+    profiler::get_profile()
+}
+
+#[wasm_bindgen]
+pub fn clear_profile() {
+    profiler::clear_profile();
+}
+
+/// Creates a graph holding a reduced BDD of pseudo-random structure, which is determined by the seed, e.g. to demonstrate the tools on diagrams of a given size
+#[wasm_bindgen]
+pub fn generate_random_bdd(vars: oxidd::LevelNo, nodes: usize, seed: u32) -> GraphBuilder {
+    GraphBuilder::generate_random_bdd(vars, nodes, seed)
+}
+
+/// Requests a webgpu device that draws to the given canvas, which a section drawer uses instead of webgl after passing it to `use_webgpu`. Fails if the browser does not support webgpu.
+#[cfg(feature = "webgpu")]
+#[wasm_bindgen]
+pub async fn request_webgpu_context(canvas: HtmlCanvasElement) -> Result<WebgpuContext, JsValue> {
+    WebgpuContext::request(canvas).await
+}
+
 #[wasm_bindgen]
 pub fn create_mtbdd_diagram() -> Option<DiagramBox> // And some DD type param
 {
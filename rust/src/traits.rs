@@ -1,58 +1,467 @@
+use std::collections::HashMap;
+
 use crate::{
     configuration::configuration_object::AbstractConfigurationObject,
-    types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
-    util::rectangle::Rectangle, wasm_interface::NodeID,
+    interface::{AppearanceAnimation, HorizontalAlignment, TargetID},
+    types::{
+        qdd::edge_weights::{Complex, Matrix, NodeEdgeID},
+        util::{
+            drawing::{
+                annotations::{AnnotationID, AnnotationKind},
+                drawer::{SectionGeometry, ViewID},
+                legend::Legend,
+                pagination::PaperSize,
+                renderer::{EdgeStyle, GroupEdgeID},
+            },
+            graph_structure::graph_manipulators::node_presence_adjuster::{
+                PresencePredicate, PresenceRemainder,
+            },
+        },
+    },
+    util::{
+        color::{get_palette_color, Color},
+        graph_builder::GraphBuilder,
+        import_batch::ImportBatch,
+        normalization::Normalization,
+        point::Point,
+        rectangle::Rectangle,
+        variable_registry::VariableRegistry,
+        viz_error::VizError,
+    },
+    wasm_interface::NodeID,
+};
+
+use super::wasm_interface::{
+    AdjacencyData, ComparisonData, DuplicationData, ElementsData, EquivalenceData, FocusData,
+    HistoryEntryData, LayoutData, LayoutMetricsData, LevelInfoData, NavigationDirection,
+    NodeDetailsData, NodeGroupID, PathData, PathMetricsData, Quantifier, ReductionData,
+    RootSorting, SelectionOperation, StepData, ValidationWarning,
 };
+use web_sys::{HtmlCanvasElement, WebGl2RenderingContext, WebGlFramebuffer};
 
-use super::wasm_interface::{NodeGroupID, StepData, TargetID};
-use web_sys::HtmlCanvasElement;
+#[cfg(feature = "webgpu")]
+use crate::types::util::drawing::renderers::webgpu_renderer::WebgpuContext;
 
 pub trait Diagram {
-    fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<Box<dyn DiagramSection>>; // TODO: error type
+    /// Sets whether dumps imported afterwards are repaired, fixing missing terminals, duplicated node ids and self-loops
+    fn set_import_repair(&mut self, repair: bool);
+    /// Sets whether the nodes of dumps imported afterwards are numbered by a canonical traversal of the diagram rather than by the ids of the dump, such that serialized states, diffs and remembered positions do not depend on the order in which the dump lists its nodes
+    fn set_import_canonical_ids(&mut self, canonical: bool);
+    /// Sets the normal form into which the graphs imported afterwards are brought by the reduction rules of the diagram type, such as fully reduced diagrams without duplicate nodes and redundant tests or quasi-reduced diagrams whose paths visit every level, such that raw graphs of other tools are drawn like the canonical diagram they represent. The sections report the size of their graph before and after the normalization unless it is kept verbatim.
+    fn set_import_normalization(&mut self, normalization: Normalization);
+    /// Sets whether the levels of the sections imported afterwards are compacted, skipping the levels without any node such that dumps with sparse levels are not drawn with many empty layers. The sections keep the level that each of their levels had in the dump.
+    fn set_level_compaction(&mut self, compact: bool);
+    /// Sets whether the sections imported afterwards number their levels by a variable order shared by all sections imported this way, where variables are identified by name, such that the levels of the same variables line up in side-by-side views. Sections whose variables are ordered differently keep their own levels and report a warning.
+    fn set_level_unification(&mut self, unify: bool);
+    /// Retrieves the registry of the variables of all sections imported into this diagram, which assigns every variable name a canonical level
+    fn get_variables(&self) -> &VariableRegistry;
+    fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
     fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn DiagramSection>>; // TODO: error type
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+    /// Creates a single section of the functions of all files of the given batch, which are added to the manager of this diagram under distinct node ids. The levels of the files are numbered by a variable order that merges the orders of the files by the names of the variables, where files whose variables are ordered differently than in the files before them keep their own levels and report a warning. Structurally identical nodes of different files are merged afterwards if the levels of all files are aligned, which are further brought into the normal form of imports if one is set, and the reduction of the section reports the effect.
+    fn create_section_from_batch(
+        &mut self,
+        batch: &ImportBatch,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+    /// Creates a section of the function described by an arithmetic expression over Boolean variables such as `2*a + 3*b - c`, or describes why the expression could not be used
+    fn create_section_from_expression(
+        &mut self,
+        expression: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+    /// Creates a section of the zero-suppressed diagram of a family of sets, given as one set per line with items separated by spaces
+    fn create_section_from_family(
+        &mut self,
+        family: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+    /// Creates a section of the graph built programmatically, or describes why the graph could not be used for this diagram type
+    fn create_section_from_graph(
+        &mut self,
+        graph: &GraphBuilder,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
     fn create_section_from_ids(
         &self,
         id: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
-    ) -> Option<Box<dyn DiagramSection>>;
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+    /// Creates a section of the given nodes that initially only reveals the nodes within the given depth (in edges) and number of nodes from the roots, collecting the remaining nodes in an expandable placeholder group
+    fn create_preview_section_from_ids(
+        &self,
+        id: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Result<Box<dyn DiagramSection>, VizError>;
+
+    /** Analysis */
+    /// Checks whether the given nodes represent the same functions, comparing the nodes of both lists pairwise. Fails if the lists differ in length or contain nodes that are not part of the diagram.
+    fn check_equivalence(
+        &self,
+        nodes_a: &[oxidd::NodeID],
+        nodes_b: &[oxidd::NodeID],
+    ) -> Result<EquivalenceData, VizError>;
+    /// Retrieves up to max_count assignments for which the given root evaluates to the given terminal
+    fn find_path_to_terminal(
+        &self,
+        root: oxidd::NodeID,
+        terminal: String,
+        max_count: usize,
+    ) -> Vec<PathData>;
+    /// Retrieves the shortest and longest paths from the given root to any terminal
+    fn get_path_metrics(&self, root: oxidd::NodeID) -> PathMetricsData;
+
+    /// Outlines the inner nodes of the given drawer in the color of the variable of their level, which is derived from the canonical level of the variable such that every variable has the same color in all drawers of this diagram, or removes the outlines
+    fn set_variable_coloring(&self, drawer: &mut dyn DiagramSectionDrawer, enabled: bool) {
+        let colors = if enabled {
+            drawer
+                .get_level_labels()
+                .into_iter()
+                .filter_map(|label| {
+                    let level = self.get_variables().get_level(&label)?;
+                    Some((label, get_palette_color(level as usize)))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        drawer.set_variable_colors(colors);
+    }
+
+    /** Linked views */
+    /// Retrieves the local nodes of the `to` drawer that represent the same source nodes as the given local nodes of the `from` drawer, where both drawers show sections of this diagram
+    fn link_nodes(
+        &self,
+        nodes: &[NodeID],
+        from: &dyn DiagramSectionDrawer,
+        to: &dyn DiagramSectionDrawer,
+    ) -> Vec<NodeID> {
+        to.source_nodes_to_local(&from.local_nodes_to_sources(nodes))
+    }
+    /// Computes how to place the `right` drawer next to the `left` drawer, separated by the given gap, such that the levels shared by both drawers are vertically aligned, and retrieves the correspondence edges between nodes that represent the same source nodes
+    fn get_comparison_layout(
+        &self,
+        left: &dyn DiagramSectionDrawer,
+        right: &dyn DiagramSectionDrawer,
+        gap: f32,
+    ) -> ComparisonData {
+        let left_geometry = left.get_geometry();
+        let right_geometry = right.get_geometry();
+
+        let level_offsets = left_geometry
+            .levels
+            .iter()
+            .filter_map(|(level, &y)| Some(y - right_geometry.levels.get(level)?))
+            .collect::<Vec<_>>();
+        let offset_y = if level_offsets.is_empty() {
+            0.
+        } else {
+            level_offsets.iter().sum::<f32>() / level_offsets.len() as f32
+        };
+        let offset_x =
+            left_geometry.bounds.x + left_geometry.bounds.width + gap - right_geometry.bounds.x;
+
+        let mut out = ComparisonData {
+            offset_x,
+            offset_y,
+            left_nodes: Vec::new(),
+            right_nodes: Vec::new(),
+            lines: Vec::new(),
+        };
+        let mut left_nodes = left_geometry.nodes.iter().collect::<Vec<_>>();
+        left_nodes.sort_by_key(|&(&node, _)| node);
+        for (&left_node, left_pos) in left_nodes {
+            let mut right_nodes = self.link_nodes(&[left_node], left, right);
+            right_nodes.sort();
+            right_nodes.dedup();
+            for right_node in right_nodes {
+                let Some(right_pos) = right_geometry.nodes.get(&right_node) else {
+                    continue;
+                };
+                out.left_nodes.push(left_node);
+                out.right_nodes.push(right_node);
+                out.lines.extend([
+                    left_pos.x,
+                    left_pos.y,
+                    right_pos.x + offset_x,
+                    right_pos.y + offset_y,
+                ]);
+            }
+        }
+        out
+    }
 }
 
 pub trait DiagramSection {
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer>;
+    /// Creates a drawer that initially only shows the roots, such that further nodes are only discovered once they are expanded
+    fn create_lazy_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer>;
     fn get_level_labels(&self) -> Vec<String>;
+    /// Renames the given level, which is used by the drawers and sections created from this section afterwards
+    fn set_level_label(&mut self, level: oxidd::LevelNo, name: String);
+    /// Retrieves the name of every level together with its variable index, whether the roots of this section depend on it and how many of their nodes lie on it
+    fn get_level_info(&self) -> Vec<LevelInfoData>;
     fn get_node_labels(&self, node: NodeID) -> Vec<String>;
+    /// Retrieves the structural problems of the imported graph, such as edges pointing upwards or nodes that can not be reached from any root
+    fn get_warnings(&self) -> Vec<ValidationWarning>;
+    /// Retrieves descriptions of the fixes applied to the dump this section was imported from, which is empty unless import repair was enabled
+    fn get_repairs(&self) -> Vec<String>;
+    /// Retrieves the sizes of the imported graph before and after it was normalized on import, which is None if the graph was kept verbatim
+    fn get_reduction(&self) -> Option<ReductionData>;
+    /// Retrieves the level in the dump of every level of this section, which is empty unless level compaction was enabled during import
+    fn get_true_levels(&self) -> Vec<oxidd::LevelNo>;
+
+    /** Operations */
+    /// Creates a new section representing the roots of this section with the variable of the given level fixed to the given value, without modifying this section
+    fn preview_cofactor(&self, var: oxidd::LevelNo, value: bool) -> Option<Box<dyn DiagramSection>>;
+    /// Creates a new section representing the roots of this section with the variables of the given levels quantified, without modifying this section, or None if the terminals of this section can not be combined by the quantifier
+    fn preview_quantify(
+        &self,
+        vars: &[oxidd::LevelNo],
+        quantifier: Quantifier,
+    ) -> Option<Box<dyn DiagramSection>>;
 }
 
 pub trait DiagramSectionDrawer {
     fn render(&mut self, time: u32) -> ();
-    fn layout(&mut self, time: u32) -> ();
-    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> ();
+    /// Lays out the diagram again, where the previous layout remains shown if this fails
+    fn layout(&mut self, time: u32) -> Result<(), VizError>;
+    /// Updates the view, returning whether the groups changed due to semantic zoom such that the diagram has to be laid out again
+    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> bool;
+    /// Sets the zoom scale (in pixels per unit) below which subfunctions are replaced by summaries, a threshold of 0 disables the summaries. Returns whether the groups changed.
+    fn set_semantic_zoom(&mut self, threshold: f32) -> bool;
+    /// Sets the number of groups that every layer may show while zoomed out further than the given scale (in pixels per unit), where runs of adjacent groups of larger layers are summarized into blocks labeled with their number of nodes, which are expanded again once zoomed in. A budget of 0 disables the summaries. Returns whether the groups changed.
+    fn set_layer_summaries(&mut self, budget: usize, threshold: f32) -> bool;
+    /// Sets the number of groups that a layer needs to exceed before only the geometry of its groups around the camera is kept by the renderer, which is updated while panning, or disables this if no threshold is given
+    fn set_layer_virtualization(&mut self, threshold: Option<usize>) -> ();
+    /// Sets how groups are animated when they are inserted into or removed from the diagram by subsequent layouts
+    fn set_appearance_animations(
+        &mut self,
+        entrance: AppearanceAnimation,
+        exit: AppearanceAnimation,
+    ) -> ();
     fn set_step(&mut self, step: i32) -> Option<StepData>;
 
+    /** Views */
+    /// Adds a view rendering to the given canvas with its own camera, which shares the layout, groups and selection of this drawer
+    fn add_view(&mut self, canvas: HtmlCanvasElement) -> ViewID;
+    /// Adds a view rendering to the given framebuffer of another application's context, or the default framebuffer of that context if none is given
+    fn add_framebuffer_view(
+        &mut self,
+        context: WebGl2RenderingContext,
+        framebuffer: Option<WebGlFramebuffer>,
+        width: u32,
+        height: u32,
+    ) -> ViewID;
+    /// Replaces the renderer of the main canvas by one that draws through the given webgpu context
+    #[cfg(feature = "webgpu")]
+    fn use_webgpu(&mut self, context: WebgpuContext);
+    fn remove_view(&mut self, view: ViewID) -> bool;
+    fn set_view_transform(
+        &mut self,
+        view: ViewID,
+        width: u32,
+        height: u32,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) -> ();
+    fn render_view(&mut self, view: ViewID, time: u32) -> ();
+    /// Retrieves the nodes in the given rectangle in screen space of the given view, expanding each node group up to at most max_group_expansion nodes
+    fn get_view_nodes(
+        &self,
+        view: ViewID,
+        area: Rectangle,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID>;
+
+    /** History */
+    /// Retrieves the recorded states of the diagram after every change of its graph, in chronological order
+    fn get_history(&self) -> Vec<HistoryEntryData>;
+    /// Renders the state at the given index of the history instead of the current state, or the current state again if no index is given. Returns whether an earlier state is shown.
+    fn scrub_history(&mut self, index: Option<usize>) -> bool;
+
     /* Grouping */
     fn set_group(&mut self, from: Vec<TargetID>, to: NodeGroupID) -> bool;
     fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID;
+    /// Attaches a display name and note to the given group, or removes them if both are empty
+    fn set_group_label(&mut self, group: NodeGroupID, name: String, note: String) -> bool;
+    /// Renames the given level in the drawn diagram, which is shown after the next layout
+    fn set_level_label(&mut self, level: oxidd::LevelNo, name: String);
+    /// Sets whether the labels of the layers include the level that the dump assigned to them, if levels were compacted on import, which is shown after the next layout
+    fn set_true_level_labels(&mut self, show: bool);
+    /// Sets the template of the labels of inner nodes, in which `{id}`, `{level}`, `{var}` and `{func_names}` are replaced by the node id, level number, variable name and names of the functions rooted in the node, or removes the labels if no template is given
+    fn set_node_label_template(&mut self, template: Option<String>);
+    /// Retrieves the name of every level of the drawn diagram
+    fn get_level_labels(&self) -> Vec<String>;
+    /// Outlines the inner nodes in the color of the variable of their level, given by the variable names, or removes the outlines if no colors are given, which is shown after the next layout
+    fn set_variable_colors(&mut self, colors: Vec<(String, Color)>) -> ();
+    /// Sets a value of every given source node, such as a hit count of an external solver, which is drawn as a color overlay between the colors of the smallest and largest value after the next layout. Nodes without a value keep their color, and an empty map removes the overlay.
+    fn set_node_metric(&mut self, values: HashMap<NodeID, f64>) -> ();
+    /// Reveals the nodes in the top depth levels of the given group, keeping the rest of the group collapsed
+    fn expand_group(&mut self, group: NodeGroupID, depth: oxidd::LevelNo) -> Vec<NodeGroupID>;
+    /// Reveals the not yet shown descendants of the given node that are at most depth edges away, discovering them from the source diagram
+    fn expand_node(&mut self, node: NodeID, depth: usize) -> Vec<NodeGroupID>;
+    /// Groups the subfunctions that are referenced from multiple parents, naming each group after a generated function name
+    fn group_shared_subfunctions(
+        &mut self,
+        min_occurrences: usize,
+        min_size: usize,
+    ) -> Vec<NodeGroupID>;
+    /// Moves the nodes on the levels whose label matches the given regular expression into a single group named after the expression, returning the group if any node matched, or describes why the expression is invalid
+    fn group_by_level_pattern(&mut self, pattern: String) -> Result<Option<NodeGroupID>, String>;
+    /// Nests the given group inside of the parent group, or moves it to the top of the hierarchy if no parent is given
+    fn set_group_parent(&mut self, group: NodeGroupID, parent: Option<NodeGroupID>) -> bool;
+    /// Makes the given group the temporary root of the view, showing only it and the groups nested inside of it
+    fn enter_group(&mut self, group: NodeGroupID) -> bool;
+    /// Returns to the previously entered group, or to the full diagram
+    fn exit_group(&mut self) -> bool;
+    /// Retrieves the path of entered groups, starting from the outermost group
+    fn get_entered_groups(&self) -> Vec<NodeGroupID>;
+    /// Groups the subtree below the low (false) child of the given node into a single placeholder
+    fn collapse_low(&mut self, node: NodeID) -> Option<NodeGroupID>;
+    /// Groups the subtree below the high (true) child of the given node into a single placeholder
+    fn collapse_high(&mut self, node: NodeID) -> Option<NodeGroupID>;
 
     /** Tools */
+    /// Hides or shows all edges of the given type, either recomputing the layout without these edges or only hiding their geometry
+    fn set_edge_visibility(&mut self, edge_type: i32, visible: bool, relayout: bool) -> ();
     /// Splits the edges of a given group such that each edge type goes to a unique group, if fully is specified it also ensures that each group that an edge goes to only contains a single node
     fn split_edges(&mut self, nodes: &[NodeID], fully: bool) -> ();
+    /// Retrieves the redundant (don't-care) nodes, whose children are all the same node, which may occur in quasi-reduced diagrams
+    fn get_redundant_nodes(&mut self) -> Vec<NodeID>;
+    /// Sets whether redundant nodes are bypassed, such that their parents connect to their child directly
+    fn set_redundant_bypass(&mut self, bypass: bool) -> ();
+    /// Sets whether the connections from shown nodes to nodes hidden by presence adjustments are marked by dashed stubs labeled with the number of hidden nodes, which are updated at the next layout
+    fn set_hidden_markers(&mut self, enabled: bool) -> ();
+    /// Sets whether the edges from shown nodes to hidden nodes (including hidden terminals) are drawn as short stubs ending in an ellipsis rather than being dropped, which are updated at the next layout
+    fn set_hidden_edge_stubs(&mut self, enabled: bool) -> ();
+    /// Sets whether runs of consecutive levels without visible nodes are collapsed into a single thin band, marked by a zig-zag line labeled with the number of elided levels, which takes effect at the next layout
+    fn set_empty_layer_elision(&mut self, enabled: bool) -> ();
+    /// Applies the given operation to all selected nodes at once, such that the next layout shows the result of the whole operation as a single change. Returns the groups created by the operation.
+    fn apply_to_selection(&mut self, operation: SelectionOperation) -> Vec<NodeGroupID>;
+    /// Applies the presence remainder to all nodes matching the predicate at once, which are evaluated on the whole diagram rather than only the discovered nodes. Returns the number of adjusted nodes, or an error if the predicate is invalid.
+    fn set_presence_where(
+        &mut self,
+        predicate: PresencePredicate,
+        remainder: PresenceRemainder,
+    ) -> Result<usize, String>;
+    /// Duplicates the inner nodes with multiple but fewer than `threshold` incoming edges across the whole diagram, giving each incoming edge a copy of its own in order to reduce crossings. Applying a new threshold replaces the duplication of the previous one.
+    fn set_duplication_threshold(&mut self, threshold: usize) -> DuplicationData;
+    /// Merges the terminals whose values fall into the same range of consecutive boundaries into a single terminal, or removes the merging if no boundaries are given. Only diagram types with numeric terminals (MTBDDs) support this.
+    fn set_terminal_bins(&mut self, bounds: Vec<f32>) -> ();
+    /// Keeps the given roots (or other nodes) in the given order from left to right within their layers during layouts, or removes this constraint if no nodes are given
+    fn set_root_order(&mut self, roots: &[NodeID]) -> ();
+    /// Keeps the roots in the order given by the sorting during layouts, returning the roots in this order
+    fn sort_roots(&mut self, sorting: RootSorting) -> Vec<NodeID>;
+    /// Pins the groups of the given nodes to their current horizontal positions, such that subsequent layouts keep them in place, or releases them again if pinned is false
+    fn pin_nodes(&mut self, nodes: &[NodeID], pinned: bool) -> ();
+    /// Pins the groups of the given terminals to a column at the given side of the bottom layer during subsequent layouts, such as TRUE at the right and FALSE at the left, where nodes that are not terminals are ignored. The terminals are released again using `pin_nodes`.
+    fn pin_terminals(&mut self, terminals: &[NodeID], column: HorizontalAlignment) -> ();
+    /// Moves the given group by the given offset in diagram coordinates, rerouting its edges accordingly. The group returns to its computed position on the next layout, unless it contains pinned nodes, in which case these are pinned at the new position.
+    fn move_group(&mut self, group: NodeGroupID, dx: f32, dy: f32) -> ();
+    /// Aligns the groups containing the given nodes horizontally, where these positions override the positions computed by subsequent layouts
+    fn align_groups(&mut self, nodes: &[NodeID], alignment: HorizontalAlignment) -> ();
+    /// Spaces the centers of the groups containing the given nodes evenly between the leftmost and rightmost of these groups, where these positions override the positions computed by subsequent layouts
+    fn distribute_groups(&mut self, nodes: &[NodeID]) -> ();
+    /// Removes the overridden positions of the groups containing the given nodes
+    fn reset_group_positions(&mut self, nodes: &[NodeID]) -> ();
 
     /** Node interaction */
     /// Retrieves the nodes in the given rectangle, expanding each node group up to at most max_group_expansion nodes of the nodes it contains
     fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID>;
+    /// Retrieves the nodes, group boxes and edges in the given rectangle, expanding each node group up to at most max_group_expansion nodes
+    fn get_elements(&self, area: Rectangle, max_group_expansion: usize) -> ElementsData;
     /// The selected and hover _ids are node ids, not node group ids
     fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]);
+    /// Saves the selected nodes under the given name in the section state, replacing the selection saved under this name before
+    fn save_selection(&mut self, name: String) -> ();
+    /// Selects the nodes saved under the given name, returning them if a selection of this name was saved
+    fn apply_selection(&mut self, name: String) -> Option<Vec<NodeID>>;
+    /// Retrieves the names of the saved selections in alphabetical order
+    fn get_selection_names(&self) -> Vec<String>;
+    /// Sets the color, width and label overrides of the given edge, which are stored in the section state, or removes them if the style is empty
+    fn set_edge_style(&mut self, edge: GroupEdgeID, style: EdgeStyle) -> ();
+    /// Highlights the given edges themselves, independent of the selected nodes
+    fn set_selected_edges(&mut self, edges: &[GroupEdgeID]) -> ();
+    /// Sets the weights of individual edges given by their source node, target node and edge type, which are shown according to the edge configuration. Only diagram types with weighted edges (QDDs) show them.
+    fn set_edge_weights(&mut self, weights: HashMap<NodeEdgeID, Complex>) -> ();
+    /// Reconstructs the matrix or vector represented by the given node from its paths and edge weights, truncated to at most max_dim rows and columns. Only diagram types that represent matrices (QDDs) support this.
+    fn get_matrix(&self, node: NodeID, max_dim: usize) -> Option<Matrix>;
+    /// Adds a note, arrow or highlighted region at the given position in diagram coordinates, which moves along with the anchor node if given, returning the id of the annotation
+    fn add_annotation(
+        &mut self,
+        anchor: Option<NodeID>,
+        position: Point,
+        kind: AnnotationKind,
+    ) -> AnnotationID;
+    /// Removes the given annotation, returning whether it existed
+    fn remove_annotation(&mut self, id: AnnotationID) -> bool;
+    /// Moves the keyboard focus to the parent, low child, high child or neighbouring sibling on the same level of the focused node, returning the newly focused node and its screen coordinates
+    fn navigate(&mut self, direction: NavigationDirection) -> Option<FocusData>;
     /// Retrieves the sources (nodes of the source diagram) of the modified diagram
     fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID>;
     /// Retrieves the local nodes representing the collection of sources
     fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID>;
+    /// Retrieves the properties of the given node to show in its tooltip, where the properties of the function of the node are computed on the first request
+    fn get_node_details(&mut self, node: NodeID) -> NodeDetailsData;
+    /// Retrieves a canonical hash of the subfunction of the source node represented by the given node, which is equal for the nodes of any section that represent the same subfunction, such that nodes can be matched up across sections and imports. Returns none if the node does not represent a source node.
+    fn get_node_hash(&mut self, node: NodeID) -> Option<u64>;
+
+    /** Export */
+    /// Exports the given area of the diagram (in diagram coordinates) as an svg document
+    fn export_region_svg(&mut self, area: Rectangle) -> String;
+    /// Exports the given area of the diagram (in diagram coordinates) as a png data url, rendered with the given number of pixels per unit
+    fn export_region_png(&mut self, area: Rectangle, pixels_per_unit: f32) -> String;
+    /// Splits the whole diagram across pages of the given paper size that share the given overlap, and exports every page as a separate svg document
+    fn export_paged_svg(&mut self, paper: PaperSize, overlap: f32) -> Vec<String>;
+    /// Splits the whole diagram across pages of the given paper size, and exports it as a sequence of TikZ pictures separated by page breaks
+    fn export_paged_tikz(&mut self, paper: PaperSize, overlap: f32) -> String;
+    /// Retrieves the meaning of the edge styles, node colors and color scales that are currently in effect
+    fn get_legend(&self) -> Legend;
+    /// Sets the spacing of the grid that coordinates are snapped to in svg and TikZ exports, or disables snapping if none is given
+    fn set_export_grid(&mut self, grid: Option<f32>) -> ();
+    /// Sets whether the svg, png and TikZ exports include a rendered legend
+    fn set_export_legend(&mut self, enabled: bool) -> ();
+    /// Sets the TikZ options (e.g. `fill=red, thick`) used for the given node in latex exports, or removes them if the style is empty
+    fn set_tikz_node_style(&mut self, node: NodeID, style: String) -> ();
+    /// Sets the TikZ options used for all nodes of the given level in latex exports, or removes them if the style is empty
+    fn set_tikz_level_style(&mut self, level: oxidd::LevelNo, style: String) -> ();
+    /// Sets the TikZ options used for all edges of the given type in latex exports, or removes them if the style is empty
+    fn set_tikz_edge_style(&mut self, edge_type: i32, style: String) -> ();
+    /// Records the currently shown nodes as the next stage of a staged TikZ export, returning the number of recorded stages
+    fn record_tikz_stage(&mut self) -> usize;
+    /// Removes all recorded stages
+    fn clear_tikz_stages(&mut self) -> ();
+    /// Exports the diagram as TikZ in which nodes and edges are wrapped in beamer `\onslide` commands, such that they are revealed in the order of the recorded stages
+    fn export_staged_tikz(&mut self) -> String;
+    /// Retrieves the visible groups and the edges between them as a compressed sparse adjacency structure
+    fn get_adjacency(&self) -> AdjacencyData;
+    /// Counts the pairs of edge segments that cross in the current layout, such that manipulations and layout heuristics can be compared by their effect on readability
+    fn get_crossing_count(&self) -> usize;
+    /// Measures the readability of the current layout by its crossings, total edge length, bends, aspect ratio and overlapping nodes
+    fn get_layout_metrics(&self) -> LayoutMetricsData;
+    /// Lays out the diagram like `layout` without rendering the new layout, and retrieves the visible groups, edges and layers of the resulting layout
+    fn compute_layout(&mut self, time: u32) -> Result<LayoutData, VizError>;
+    /// Retrieves the positions of the visible levels and nodes in diagram coordinates
+    fn get_geometry(&self) -> SectionGeometry;
+    /// Describes the visible graph as an indented text outline, listing for every node the variable it tests and its low and high subtrees, such that it can be read by screen readers
+    fn export_outline(&self) -> String;
 
     /** Storage */
     fn serialize_state(&self) -> Vec<u8>;
+    /// Restores a state retrieved from `serialize_state`, where states stored without a format version or by a different version are rejected
     fn deserialize_state(&mut self, state: Vec<u8>) -> ();
+    /// Resets the section to the state of its last successful layout after an internal error, returning whether it could be laid out again
+    fn recover(&mut self) -> bool;
+    /// Recreates all rendering resources after the webgl context of the canvas was restored from a context loss, returning whether this succeeded
+    fn restore_context(&mut self) -> bool;
+    /// Exports the presence adjustments, and the groups that contain multiple nodes or are named, as a JSON view patch keyed by source node IDs, which can be applied to a freshly loaded copy of the same diagram
+    fn export_view_patch(&self) -> String;
+    /// Applies a view patch created by `export_view_patch`, ignoring the nodes that are absent from this diagram, or describes why the patch can not be read
+    fn import_view_patch(&mut self, patch: String) -> Result<(), String>;
 
     /** Settings */
     fn get_configuration(&self) -> AbstractConfigurationObject;
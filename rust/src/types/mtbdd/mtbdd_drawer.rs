@@ -1,8 +1,15 @@
 use itertools::Itertools;
-use std::{collections::HashMap, io::Cursor, rc::Rc, sync::Arc};
-use web_sys::HtmlCanvasElement;
+use regex::Regex;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    io::Cursor,
+    rc::Rc,
+    sync::Arc,
+};
+use web_sys::{HtmlCanvasElement, WebGl2RenderingContext, WebGlFramebuffer};
 
-use oxidd::{Edge, Function, InnerNode, Manager, ManagerRef, NodeID};
+use oxidd::{Edge, Function, InnerNode, LevelNo, Manager, ManagerRef, NodeID};
 use oxidd_core::{DiagramRules, HasLevel};
 
 use crate::{
@@ -21,13 +28,21 @@ use crate::{
             text_output_config::TextOutputConfig,
         },
     },
+    interface::{AppearanceAnimation, HorizontalAlignment, TargetID, TargetIDType},
     traits::{Diagram, DiagramSection, DiagramSectionDrawer},
     types::{
-        qdd::qdd_drawer::QDDDiagramDrawer,
+        qdd::{
+            edge_weights::{Complex, Matrix, NodeEdgeID},
+            qdd_drawer::QDDDiagramDrawer,
+        },
         util::{
             drawing::{
-                diagram_layout::{LayerStyle, NodeStyle},
-                drawer::Drawer,
+                annotations::{
+                    get_edge_stub_delta, Annotation, AnnotationID, AnnotationKind, STUB_ELLIPSIS,
+                    STUB_LENGTH,
+                },
+                diagram_layout::{LabelPlacement, LayerStyle, NodeStyle},
+                drawer::{Drawer, SectionGeometry, ViewID},
                 layout_rules::LayoutRules,
                 layouts::{
                     layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
@@ -37,20 +52,31 @@ use crate::{
                         pseudo_random_layer_ordering::PseudoRandomLayerOrdering,
                         sugiyama_ordering::SugiyamaOrdering,
                     },
-                    layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+                    layer_positionings::{
+                        brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+                        pinned_positioning::{get_pin_positions, PinnedPositioning},
+                    },
                     layered_layout::LayeredLayout,
                     layered_layout_traits::WidthLabel,
                     transition::transition_layout::TransitionLayout,
                 },
-                renderer::Renderer,
+                legend::{get_variable_entries, Legend, LegendEntry, LegendSymbol},
+                node_metric::NodeMetric,
+                pagination::{paginate, Page, PaperSize},
+                renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID},
                 renderers::{
                     latex_renderer::{LatexLayerStyle, LatexNodeStyle, LatexRenderer},
-                    util::Font::Font,
+                    svg_renderer::SvgRenderer,
+                    util::{
+                        edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides,
+                        layer_pairing::LayerPairing, Font::Font,
+                    },
                     webgl::{
                         edge_renderer::EdgeRenderingType, node_renderer::NodeRenderingColorConfig,
                     },
+                    screen_renderer::ScreenRenderer,
                     webgl_renderer::{
-                        LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle, WebglRenderer,
+                        LayerRenderingColorConfig, RenderSurface, WebglLayerStyle, WebglNodeStyle,
                     },
                 },
             },
@@ -59,18 +85,30 @@ use crate::{
                     group_presence_adjuster::GroupPresenceAdjuster,
                     label_adjusters::group_label_adjuster::GroupLabelAdjuster,
                     node_presence_adjuster::{
-                        NodePresenceAdjuster, PresenceGroups, PresenceLabel, PresenceRemainder,
+                        NodePresenceAdjuster, PresenceGroups, PresenceLabel, PresencePredicate,
+                        PresenceRemainder,
                     },
                     pointer_node_adjuster::{PointerLabel, PointerNodeAdjuster},
                     rc_graph::RCGraph,
+                    redundant_node_adjuster::RedundantNodeAdjuster,
+                    terminal_bin_adjuster::TerminalBinAdjuster,
                     terminal_level_adjuster::TerminalLevelAdjuster,
                 },
                 graph_structure::{DrawTag, EdgeType, GraphStructure},
                 grouped_graph_structure::GroupedGraphStructure,
                 oxidd_graph_structure::{NodeLabel, NodeType, OxiddGraphStructure},
             },
-            group_manager::GroupManager,
-            storage::state_storage::{Serializable, StateStorage},
+            group_manager::{GroupManager, InitialReveal},
+            root_order::get_sorted_roots,
+            section_derivation::{cofactor_roots, quantify_roots},
+            section_import::{
+                compact_levels, import_batch, normalize_import, register_variables, ImportManager,
+            },
+            storage::state_storage::{
+                read_state_header, write_state_header, Serializable, StateStorage,
+            },
+            structural_hasher::{get_terminal_key, StructuralHasher},
+            view_patch::{ViewPatch, ViewPatchGroup},
         },
     },
     util::{
@@ -79,34 +117,165 @@ use crate::{
             DummyMTBDDEdge, DummyMTBDDFunction, DummyMTBDDManager, DummyMTBDDManagerRef,
             MTBDDTerminal,
         },
+        graph_builder::GraphBuilder,
+        import_batch::{ImportBatch, ImportFile},
+        label_template::fill_label_template,
         logging::console,
+        normalization::Normalization,
+        point::Point,
         rc_refcell::MutRcRefCell,
         rectangle::Rectangle,
         transition::Interpolatable,
+        variable_registry::VariableRegistry,
+        viz_error::VizError,
+    },
+    wasm_interface::{
+        AdjacencyData, DuplicationData, ElementsData, EquivalenceData, FocusData,
+        HistoryEntryData, LayoutData, LayoutMetricsData, LevelInfoData, NavigationDirection,
+        NodeDetailsData, NodeGroupID, PathData, PathMetricsData, Quantifier, ReductionData,
+        RootSorting, SelectionOperation, StepData, ValidationWarning,
     },
-    wasm_interface::{NodeGroupID, StepData, TargetID, TargetIDType},
 };
+#[cfg(feature = "webgpu")]
+use crate::types::util::drawing::renderers::webgpu_renderer::WebgpuContext;
 
 pub struct MTBDDDiagram<MR: ManagerRef>
 where
     for<'id> <<MR as oxidd::ManagerRef>::Manager<'id> as Manager>::InnerNode: HasLevel,
 {
     manager_ref: MR,
+    /// Whether common defects of imported dumps are fixed rather than drawn as they are
+    repair_imports: bool,
+    /// Whether the nodes of imported dumps are numbered by a canonical traversal rather than by the ids of the dump
+    canonical_import_ids: bool,
+    /// The normal form into which imported graphs are brought
+    import_normalization: Normalization,
+    /// Whether the levels without any imported node are skipped, rather than drawn as empty layers
+    compact_imported_levels: bool,
+    /// Whether the levels of imported sections are numbered by the shared variable order
+    unify_levels: bool,
+    /// The variables of all imported sections, by which the levels are unified
+    variables: VariableRegistry,
+    /// The structural hashes of the nodes of the manager that equivalence checks compared, which are kept until the next import
+    equivalence_hasher: RefCell<Option<StructuralHasher<BaseGraph>>>,
 }
 impl MTBDDDiagram<DummyMTBDDManagerRef> {
     pub fn new() -> MTBDDDiagram<DummyMTBDDManagerRef> {
         let manager_ref = DummyMTBDDManagerRef::from(&DummyMTBDDManager::new());
-        MTBDDDiagram { manager_ref }
+        MTBDDDiagram {
+            manager_ref,
+            repair_imports: false,
+            canonical_import_ids: false,
+            import_normalization: Normalization::Verbatim,
+            compact_imported_levels: false,
+            unify_levels: false,
+            variables: VariableRegistry::new(),
+            equivalence_hasher: RefCell::new(None),
+        }
+    }
+}
+
+impl MTBDDDiagram<DummyMTBDDManagerRef> {
+    fn section_from_ids(
+        &self,
+        sources: &[(oxidd::NodeID, &Box<dyn crate::traits::DiagramSection>)],
+    ) -> MTBDDDiagramSection<DummyMTBDDFunction> {
+        let mut levels = Vec::new();
+        let roots = sources
+            .iter()
+            .map(|&(id, section)| {
+                let root_edge = DummyMTBDDEdge::new(Arc::new(id), self.manager_ref.clone());
+                levels = section.get_level_labels();
+                (DummyMTBDDFunction(root_edge), section.get_node_labels(id))
+            })
+            .collect_vec();
+        MTBDDDiagramSection::new(roots, levels)
+    }
+
+    /// Retrieves the nodes of the manager before an import, which only these nodes are known by afterwards. The hashes of equivalence checks are dropped, since imports may replace nodes and add levels.
+    fn prepare_import(&mut self) -> HashSet<NodeID> {
+        *self.equivalence_hasher.get_mut() = None;
+        self.manager_ref.get_node_ids()
+    }
+
+    /// Renumbers the levels of the nodes that were imported for the given section, which are all nodes except for the given known nodes, such that levels without any of these nodes are skipped if level compaction is enabled. The section keeps the level that each of its levels had in the dump.
+    fn compact_levels(
+        &self,
+        section: &mut MTBDDDiagramSection<DummyMTBDDFunction>,
+        known: &HashSet<NodeID>,
+    ) {
+        if self.compact_imported_levels {
+            let (levels, true_levels) = (&mut section.levels, &mut section.true_levels);
+            compact_levels(&self.manager_ref, known, levels, true_levels);
+        }
+    }
+
+    /// Brings the graph of the given imported functions, which consists of all nodes except for the given known nodes, into the normal form of imports by the reduction rules of MTBDDs, see `normalize_import`
+    fn normalize_import(
+        &self,
+        roots: Vec<(DummyMTBDDFunction, Vec<String>)>,
+        known: &HashSet<NodeID>,
+    ) -> (Vec<(DummyMTBDDFunction, Vec<String>)>, Option<ReductionData>) {
+        normalize_import(&self.manager_ref, roots, known, self.import_normalization)
+    }
+
+    /// Registers the variables of the given section, and numbers the levels of the nodes that were imported for it, which are all nodes except for the given known nodes, by their canonical levels if levels are unified
+    fn register_variables(
+        &mut self,
+        section: &mut MTBDDDiagramSection<DummyMTBDDFunction>,
+        known: &HashSet<NodeID>,
+    ) {
+        register_variables(
+            &self.manager_ref,
+            known,
+            &mut self.variables,
+            self.unify_levels,
+            &mut section.levels,
+            &mut section.warnings,
+        );
     }
 }
 
 impl Diagram for MTBDDDiagram<DummyMTBDDManagerRef> {
+    fn set_import_repair(&mut self, repair: bool) {
+        self.repair_imports = repair;
+    }
+    fn set_import_canonical_ids(&mut self, canonical: bool) {
+        self.canonical_import_ids = canonical;
+    }
+    fn set_import_normalization(&mut self, normalization: Normalization) {
+        self.import_normalization = normalization;
+    }
+    fn set_level_compaction(&mut self, compact: bool) {
+        self.compact_imported_levels = compact;
+    }
+    fn set_level_unification(&mut self, unify: bool) {
+        self.unify_levels = unify;
+    }
+    fn get_variables(&self) -> &VariableRegistry {
+        &self.variables
+    }
     fn create_section_from_dddmp(
         &mut self,
         dddmp: String,
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
-        let (roots, levels) = DummyMTBDDFunction::from_dddmp(&mut self.manager_ref, &dddmp);
-        Some(Box::new(MTBDDDiagramSection::new(roots, levels)))
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (roots, levels, repairs) =
+            DummyMTBDDFunction::from_dddmp(
+                &mut self.manager_ref,
+                &dddmp,
+                self.repair_imports,
+                self.canonical_import_ids,
+            )
+            .map_err(VizError::ParseError)?;
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = MTBDDDiagramSection::new(roots, levels);
+        section.warnings = self.manager_ref.validate(&section.roots, &known);
+        section.repairs = repairs;
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
     }
 
     // Does not support other imports
@@ -114,24 +283,180 @@ impl Diagram for MTBDDDiagram<DummyMTBDDManagerRef> {
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
-        todo!()
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        Err(VizError::UnsupportedFormat(
+            "Buddy dumps can only be imported as BDDs".into(),
+        ))
+    }
+
+    fn create_section_from_batch(
+        &mut self,
+        batch: &ImportBatch,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (repair, canonical_ids) = (self.repair_imports, self.canonical_import_ids);
+        let import = import_batch(
+            &self.manager_ref,
+            batch,
+            self.import_normalization,
+            |index, file, manager_ref| match file {
+                ImportFile::Dddmp(dddmp) => {
+                    DummyMTBDDFunction::from_dddmp(manager_ref, dddmp, repair, canonical_ids)
+                        .map_err(|error| {
+                            VizError::ParseError(format!("File {}: {}", index, error))
+                        })
+                }
+                ImportFile::Buddy { .. } => Err(VizError::UnsupportedFormat(format!(
+                    "File {}: Buddy dumps can only be imported as BDDs",
+                    index
+                ))),
+            },
+        )?;
+        let mut section = MTBDDDiagramSection::new(import.roots, import.levels);
+        section.warnings = import.warnings;
+        section.repairs = import.repairs;
+        section.reduction = import.reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
+    }
+
+    fn create_section_from_expression(
+        &mut self,
+        expression: String,
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (root, levels) =
+            DummyMTBDDFunction::from_expression(&mut self.manager_ref, &expression)
+                .map_err(VizError::ParseError)?;
+        let name = expression.trim().to_string();
+        let (roots, reduction) = self.normalize_import(vec![(root, vec![name])], &known);
+        let mut section = MTBDDDiagramSection::new(roots, levels);
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
+    }
+
+    // Families of sets are only supported by the BDD diagram
+    fn create_section_from_family(
+        &mut self,
+        family: String,
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        Err(VizError::UnsupportedFormat(
+            "Families of sets can only be imported as BDDs".into(),
+        ))
+    }
+
+    fn create_section_from_graph(
+        &mut self,
+        graph: &GraphBuilder,
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (roots, levels) = DummyMTBDDFunction::from_builder(&mut self.manager_ref, graph)
+            .map_err(VizError::InconsistentGraph)?;
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = MTBDDDiagramSection::new(roots, levels);
+        section.warnings = self.manager_ref.validate(&section.roots, &known);
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
     }
 
     fn create_section_from_ids(
         &self,
         sources: &[(oxidd::NodeID, &Box<dyn crate::traits::DiagramSection>)],
-    ) -> Option<Box<dyn crate::traits::DiagramSection>> {
-        let mut levels = Vec::new();
-        let roots = sources
-            .iter()
-            .map(|&(id, section)| {
-                let root_edge = DummyMTBDDEdge::new(Arc::new(id), self.manager_ref.clone());
-                levels = section.get_level_labels();
-                (DummyMTBDDFunction(root_edge), section.get_node_labels(id))
-            })
-            .collect_vec();
-        Some(Box::new(MTBDDDiagramSection::new(roots, levels)))
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        Ok(Box::new(self.section_from_ids(sources)))
+    }
+
+    fn create_preview_section_from_ids(
+        &self,
+        sources: &[(oxidd::NodeID, &Box<dyn crate::traits::DiagramSection>)],
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Result<Box<dyn crate::traits::DiagramSection>, VizError> {
+        let mut section = self.section_from_ids(sources);
+        section.reveal = InitialReveal::Bounded {
+            max_depth,
+            max_nodes,
+        };
+        Ok(Box::new(section))
+    }
+
+    fn check_equivalence(
+        &self,
+        nodes_a: &[oxidd::NodeID],
+        nodes_b: &[oxidd::NodeID],
+    ) -> Result<EquivalenceData, VizError> {
+        if nodes_a.len() != nodes_b.len() {
+            return Err(VizError::UnsupportedFormat(
+                "Only lists of the same length can be compared pairwise".to_string(),
+            ));
+        }
+        let function = |id: oxidd::NodeID| {
+            DummyMTBDDFunction(DummyMTBDDEdge::new(Arc::new(id), self.manager_ref.clone()))
+        };
+        // Structurally identical subfunctions are equivalent, hence their search is skipped. The levels are labeled by their number, as the functions share the levels of the manager.
+        let mut cached_hasher = self.equivalence_hasher.borrow_mut();
+        let hasher = cached_hasher.get_or_insert_with(|| {
+            let level_count = self
+                .manager_ref
+                .get_levels(&HashSet::new())
+                .last()
+                .map_or(0, |&level| level + 1);
+            let labels = (0..level_count).map(|level| level.to_string()).collect();
+            StructuralHasher::new(BaseGraph::new(Vec::new(), labels), get_terminal_key)
+        });
+        for (&a, &b) in nodes_a.iter().zip(nodes_b) {
+            let (a, b) = (function(a), function(b));
+            hasher.get_graph().discover(&a);
+            hasher.get_graph().discover(&b);
+            let difference = a
+                .find_difference(&b, &mut |a, b| hasher.is_identical(a, b))
+                .map_err(VizError::UnsupportedFormat)?;
+            if let Some(witness) = difference {
+                return Ok(EquivalenceData::different(
+                    &witness,
+                    a.follow_assignment(&witness),
+                    b.follow_assignment(&witness),
+                ));
+            }
+        }
+        Ok(EquivalenceData::equivalent())
+    }
+
+    fn find_path_to_terminal(
+        &self,
+        root: oxidd::NodeID,
+        terminal: String,
+        max_count: usize,
+    ) -> Vec<PathData> {
+        let Ok(terminal) = terminal.parse::<f32>() else {
+            return Vec::new();
+        };
+        let root = DummyMTBDDFunction(DummyMTBDDEdge::new(
+            Arc::new(root),
+            self.manager_ref.clone(),
+        ));
+        root.find_paths_to(&MTBDDTerminal(terminal), max_count)
+            .into_iter()
+            .map(|(assignment, nodes)| PathData::new(&assignment, nodes))
+            .collect()
+    }
+
+    fn get_path_metrics(&self, root: oxidd::NodeID) -> PathMetricsData {
+        let root = DummyMTBDDFunction(DummyMTBDDEdge::new(
+            Arc::new(root),
+            self.manager_ref.clone(),
+        ));
+        let (shortest_assignment, shortest_nodes) = root.find_extreme_path(false);
+        let (longest_assignment, longest_nodes) = root.find_extreme_path(true);
+        PathMetricsData::new(
+            PathData::new(&shortest_assignment, shortest_nodes),
+            PathData::new(&longest_assignment, longest_nodes),
+        )
     }
 }
 
@@ -142,6 +467,16 @@ where
     roots: Vec<(F, Vec<String>)>,
     labels: HashMap<NodeID, Vec<String>>,
     levels: Vec<String>,
+    /// The nodes that drawers of this section reveal initially
+    reveal: InitialReveal,
+    /// The structural problems found when importing the graph of this section
+    warnings: Vec<ValidationWarning>,
+    /// The fixes applied to the dump this section was imported from
+    repairs: Vec<String>,
+    /// The sizes of the imported graph before and after it was normalized on import, if it was normalized
+    reduction: Option<ReductionData>,
+    /// The level in the dump of every level of this section, if levels were compacted on import
+    true_levels: Vec<LevelNo>,
 }
 impl<F: Function> MTBDDDiagramSection<F>
 where
@@ -160,8 +495,13 @@ where
                 .collect(),
             roots,
             levels,
+            reveal: InitialReveal::Default,
+            warnings: Vec::new(),
+            repairs: Vec::new(),
+            reduction: None,
+            true_levels: Vec::new(),
         };
-        console::log!(
+        console::log_debug!(
             "init {}",
             s.labels
                 .iter()
@@ -181,6 +521,7 @@ struct MTBDDColors {
     node_false: Color,
     node_group: Color,
     node_default: Color,
+    node_redundant: Color,
     node_text: Color,
     node_label: Color,
     layer_background1: Color,
@@ -200,6 +541,7 @@ impl MTBDDColors {
         node_false: Color(0.835, 0.341, 0.341),
         node_group: Color(0.45, 0.45, 0.45),
         node_default: Color(0.35, 0.35, 0.35),
+        node_redundant: Color(0.7, 0.55, 0.2),
         node_text: Color(0.0, 0.0, 0.0),
         node_label: Color(0.5, 0.5, 1.0),
         layer_background1: Color(0.125, 0.125, 0.125),
@@ -219,6 +561,7 @@ impl MTBDDColors {
         node_false: Color(1.0, 0.2, 0.2),
         node_group: Color(0.45, 0.45, 0.45),
         node_default: Color(0.1, 0.1, 0.1),
+        node_redundant: Color(0.6, 0.45, 0.1),
         node_text: Color(0.0, 0.0, 0.0),
         node_label: Color(0.5, 0.5, 1.0),
         layer_background1: Color(0.98, 0.98, 0.98),
@@ -235,15 +578,83 @@ impl DiagramSection for MTBDDDiagramSection<DummyMTBDDFunction> {
     fn get_level_labels(&self) -> Vec<String> {
         self.levels.clone()
     }
+    fn set_level_label(&mut self, level: LevelNo, name: String) {
+        let index = level as usize;
+        if self.levels.len() <= index {
+            self.levels.resize(index + 1, String::new());
+        }
+        self.levels[index] = name;
+    }
+    fn get_level_info(&self) -> Vec<LevelInfoData> {
+        let mut visited = HashSet::new();
+        let mut counts = HashMap::new();
+        for (root, _) in &self.roots {
+            root.count_level_nodes(&mut visited, &mut counts);
+        }
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let node_count = counts.get(&(index as LevelNo)).cloned().unwrap_or(0);
+                LevelInfoData {
+                    name: name.clone(),
+                    index: index as LevelNo,
+                    in_support: node_count > 0,
+                    node_count,
+                }
+            })
+            .collect()
+    }
     fn get_node_labels(&self, node: NodeID) -> Vec<String> {
         self.labels.get(&node).cloned().unwrap_or_else(|| vec![])
     }
+    fn get_warnings(&self) -> Vec<ValidationWarning> {
+        self.warnings.clone()
+    }
+    fn get_repairs(&self) -> Vec<String> {
+        self.repairs.clone()
+    }
+    fn get_reduction(&self) -> Option<ReductionData> {
+        self.reduction.clone()
+    }
+    fn get_true_levels(&self) -> Vec<LevelNo> {
+        self.true_levels.clone()
+    }
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
         let graph =
             OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
-        let diagram = MTBDDDiagramDrawer::new(graph, canvas);
+        let functions = self.roots.iter().map(|(f, _)| f.clone()).collect();
+        let diagram = MTBDDDiagramDrawer::new(graph, functions, &self.levels, canvas, self.reveal);
+        *diagram.true_levels.get() = self.true_levels.clone();
+        Box::new(diagram)
+    }
+    fn create_lazy_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
+        let graph =
+            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
+        let functions = self.roots.iter().map(|(f, _)| f.clone()).collect();
+        let diagram = MTBDDDiagramDrawer::new(
+            graph,
+            functions,
+            &self.levels,
+            canvas,
+            InitialReveal::Lazy,
+        );
+        *diagram.true_levels.get() = self.true_levels.clone();
         Box::new(diagram)
     }
+    fn preview_cofactor(&self, var: LevelNo, value: bool) -> Option<Box<dyn DiagramSection>> {
+        let roots = cofactor_roots::<DummyMTBDDManagerRef>(&self.roots, &self.levels, var, value);
+        Some(Box::new(MTBDDDiagramSection::new(roots, self.levels.clone())))
+    }
+    fn preview_quantify(
+        &self,
+        vars: &[LevelNo],
+        quantifier: Quantifier,
+    ) -> Option<Box<dyn DiagramSection>> {
+        let roots =
+            quantify_roots::<DummyMTBDDManagerRef>(&self.roots, &self.levels, vars, quantifier)?;
+        Some(Box::new(MTBDDDiagramSection::new(roots, self.levels.clone())))
+    }
 }
 
 #[derive(Clone)]
@@ -252,9 +663,19 @@ pub struct NodeData {
     border_color: TransparentColor,
     width: f32,
     name: Option<String>,
+    secondary_label: Option<String>,
+    label_size: Option<Point>,
     is_terminal: Option<MTBDDTerminal>,
     is_group: bool,
 }
+impl NodeData {
+    fn get_full_label(&self) -> Option<String> {
+        match (&self.name, &self.secondary_label) {
+            (Some(name), Some(secondary)) => Some(format!("{} {}", name, secondary)),
+            (name, secondary) => name.clone().or_else(|| secondary.clone()),
+        }
+    }
+}
 
 impl Interpolatable for NodeData {
     fn mix(&self, other: &Self, frac: f32) -> Self {
@@ -263,6 +684,8 @@ impl Interpolatable for NodeData {
             border_color: self.border_color.mix(&other.border_color, frac),
             width: self.width * (1.0 - frac) + other.width * frac,
             name: other.name.clone(),
+            secondary_label: other.secondary_label.clone(),
+            label_size: other.label_size,
             is_terminal: other.is_terminal.clone(),
             is_group: other.is_group,
         }
@@ -279,7 +702,7 @@ impl LatexNodeStyle for NodeData {
     }
 
     fn get_label(&self) -> Option<String> {
-        self.name.clone()
+        self.get_full_label()
     }
 }
 impl WebglNodeStyle for NodeData {
@@ -292,13 +715,17 @@ impl WebglNodeStyle for NodeData {
     }
 
     fn get_label(&self) -> Option<String> {
-        self.name.clone()
+        self.get_full_label()
     }
 }
 impl WidthLabel for NodeData {
     fn get_width(&self) -> f32 {
         self.width
     }
+
+    fn get_label_size(&self) -> Option<Point> {
+        self.label_size
+    }
 }
 impl NodeStyle for NodeData {}
 
@@ -335,7 +762,10 @@ type GroupedGraph =
     GroupPresenceAdjuster<GroupLabelAdjuster<NodeData, LayerData, GroupManager<Graph>>>;
 type Graph = RCGraph<TerminalLevelAdjuster<PresenceAdjuster>>;
 type PresenceAdjuster =
-    RCGraph<NodePresenceAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>>;
+    RCGraph<NodePresenceAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BinAdjuster>>>>;
+type BinAdjuster = RCGraph<TerminalBinAdjuster<RedundancyAdjuster>>;
+type RedundancyAdjuster = RCGraph<RedundantNodeAdjuster<RCBaseGraph>>;
+type RCBaseGraph = RCGraph<BaseGraph>;
 type BaseGraph = OxiddGraphStructure<(), DummyMTBDDFunction, MTBDDTerminal>;
 
 type Layout = TransitionLayout<
@@ -343,16 +773,69 @@ type Layout = TransitionLayout<
         GroupedGraph,
         SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
         OrderingGroupAlignment,
-        BrandesKopfPositioningCorrected,
+        PinnedPositioning<BrandesKopfPositioningCorrected>,
     >,
 >;
 
+/// The zoom scale (in pixels per unit) below which subfunctions are summarized by default
+const DEFAULT_SEMANTIC_ZOOM_THRESHOLD: f32 = 2.;
+/// The number of levels below the top root that stay detailed when subfunctions are summarized
+const SEMANTIC_ZOOM_DETAIL_LEVELS: LevelNo = 2;
+/// The number of groups that a layer may show by default while zoomed out further than the default scale (in pixels per unit), before runs of adjacent groups are summarized
+const DEFAULT_LAYER_SUMMARY_BUDGET: usize = 1000;
+const DEFAULT_LAYER_SUMMARY_THRESHOLD: f32 = 4.;
+
 pub struct MTBDDDiagramDrawer {
     graph: Graph,
     group_manager: MutRcRefCell<GroupManager<Graph>>,
     presence_adjuster: PresenceAdjuster,
+    /// The graph of the section, which holds the labels of the levels
+    source_graph: RCBaseGraph,
+    /// Detects the redundant nodes of the section, and bypasses them if enabled
+    redundancy_adjuster: RedundancyAdjuster,
+    /// Whether the connections to nodes hidden by the presence adjuster are marked
+    hidden_markers: bool,
+    /// Whether the edges to nodes hidden by the presence adjuster are drawn as stubs
+    hidden_edge_stubs: bool,
     time: MutRcRefCell<u32>,
-    drawer: MutRcRefCell<Drawer<WebglRenderer<()>, Layout, GroupedGraph>>,
+    drawer: MutRcRefCell<Drawer<ScreenRenderer<()>, Layout, GroupedGraph>>,
+    svg_renderer: MutRcRefCell<SvgRenderer<Layout>>,
+    latex_renderer: MutRcRefCell<LatexRenderer<Layout>>,
+    /// Creates the renderers of additional views of the section
+    create_renderer: Box<dyn Fn(RenderSurface) -> ScreenRenderer<()>>,
+    grouped_graph: MutRcRefCell<GroupedGraph>,
+    /// The path of groups that were entered, the last of which is currently shown
+    entered_groups: Vec<NodeGroupID>,
+    /// The zoom scale below which subfunctions are summarized, and whether they currently are
+    semantic_zoom_threshold: f32,
+    summarized: bool,
+    /// The number of groups that a layer may show while zoomed out further than the threshold, and the groups summarizing the runs of larger layers if they are shown
+    layer_summary_budget: usize,
+    layer_summary_threshold: f32,
+    layer_summaries: Option<Vec<NodeGroupID>>,
+    /// Whether the exports include a legend
+    export_legend: MutRcRefCell<bool>,
+    /// The template of the labels of inner nodes, which are not labeled if there is none
+    label_template: MutRcRefCell<Option<String>>,
+    /// The level in the dump of every level of the section, if levels were compacted on import
+    true_levels: MutRcRefCell<Vec<LevelNo>>,
+    /// Whether the labels of the layers show the levels in the dump that were compacted
+    show_true_levels: MutRcRefCell<bool>,
+    /// The colors of the variables, by which inner nodes are outlined
+    variable_colors: MutRcRefCell<Vec<(String, Color)>>,
+    /// The values of source nodes provided externally, which are drawn as a color overlay
+    node_metric: MutRcRefCell<NodeMetric>,
+    /// The satisfying assignment counts and supports of the source nodes whose details were requested
+    details_cache: HashMap<NodeID, (f64, Vec<LevelNo>)>,
+    /// Computes the structural hashes of the subfunctions of the source diagram
+    structural_hasher: StructuralHasher<RCBaseGraph>,
+    /// The serialized state of the last successful layout, which the section is reset to after an internal error
+    consistent_state: Option<Vec<u8>>,
+    /// The range of terminal values that is mapped onto the terminal colors
+    terminal_range: (FloatConfig, FloatConfig),
+    /// Merges terminals by value ranges, of which the boundaries are shared with the node labels
+    bin_adjuster: BinAdjuster,
+    terminal_bins: MutRcRefCell<Vec<f32>>,
     config: Configuration<
         LocationConfig<
             PanelConfig<
@@ -368,6 +851,36 @@ pub struct MTBDDDiagramDrawer {
                             LabelConfig<CompositeConfig<(FloatConfig, FloatConfig)>>,
                         )>,
                     >,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<CompositeConfig<Vec<LabelConfig<FloatConfig>>>>,
+                        )>,
+                    >,
+                    LabelConfig<ChoiceConfig<bool>>,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<LayerLabelSides>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<LayerPairing>>,
+                        )>,
+                    >,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<LabelPlacement>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                        )>,
+                    >,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<EdgeArrows>>,
+                            LabelConfig<FloatConfig>,
+                            LabelConfig<FloatConfig>,
+                            LabelConfig<FloatConfig>,
+                        )>,
+                    >,
                 )>,
             >,
         >,
@@ -375,7 +888,13 @@ pub struct MTBDDDiagramDrawer {
 }
 
 impl MTBDDDiagramDrawer {
-    pub fn new(graph: BaseGraph, canvas: HtmlCanvasElement) -> Self {
+    pub fn new(
+        graph: BaseGraph,
+        functions: Vec<DummyMTBDDFunction>,
+        level_labels: &[String],
+        canvas: HtmlCanvasElement,
+        reveal: InitialReveal,
+    ) -> Self {
         let colors = &MTBDDColors::LIGHT;
 
         let edge_rendering_type =
@@ -394,9 +913,7 @@ impl MTBDDDiagramDrawer {
             include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
             1.0,
         ));
-        let renderer = WebglRenderer::from_canvas(
-            canvas,
-            HashMap::from([
+        let edge_types = HashMap::from([
                 // True edge
                 (
                     EdgeType::new((), 0),
@@ -417,42 +934,71 @@ impl MTBDDDiagramDrawer {
                     EdgeType::new((), 2),
                     edge_rendering_type(colors.edge_label, 0.15, 1.0, 0.0),
                 ),
-            ]),
-            NodeRenderingColorConfig {
-                select: colors.selection,
-                partial_select: colors.selection_partial,
-                hover: colors.selection_hover,
-                partial_hover: colors.selection_hover_partial,
-                text: colors.node_text,
-            },
-            LayerRenderingColorConfig {
-                background1: colors.layer_background1.into(),
-                background2: colors.layer_background2.into(),
-                text: colors.layer_text,
-            },
-            font.clone(),
-        )
-        .unwrap();
+            ]);
+        let layer_colors = LayerRenderingColorConfig {
+            background1: colors.layer_background1.into(),
+            background2: colors.layer_background2.into(),
+            text: colors.layer_text,
+        };
+        let svg_renderer = MutRcRefCell::new(SvgRenderer::new(
+            edge_types.clone(),
+            layer_colors.clone(),
+            colors.node_text,
+            font.text_size(),
+        ));
+        let node_colors = NodeRenderingColorConfig {
+            select: colors.selection,
+            partial_select: colors.selection_partial,
+            hover: colors.selection_hover,
+            partial_hover: colors.selection_hover_partial,
+            text: colors.node_text,
+        };
+        let renderer_font = font.clone();
+        let create_renderer = move |surface| {
+            ScreenRenderer::from_surface(
+                surface,
+                edge_types.clone(),
+                node_colors.clone(),
+                layer_colors.clone(),
+                renderer_font.clone(),
+            )
+            .unwrap()
+        };
+        let renderer = create_renderer(RenderSurface::Canvas(canvas));
         let layout = LayeredLayout::new(
             // SugiyamaOrdering::new(2, 2),
             SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
             // AverageGroupAlignment,
             OrderingGroupAlignment,
             // BrandesKopfPositioning,
-            BrandesKopfPositioningCorrected,
+            PinnedPositioning::new(BrandesKopfPositioningCorrected),
             // DummyLayerPositioning,
             0.3,
         );
         let layout = TransitionLayout::new(layout);
 
-        let original_roots = graph.get_roots().clone();
-        let base_graph = TerminalLevelAdjuster::new(graph); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
+        let source_graph = RCGraph::new(graph);
+        let structural_hasher =
+            StructuralHasher::new(source_graph.clone(), get_terminal_key);
+        let original_roots = source_graph.get_roots().clone();
+        let redundancy_adjuster = RCGraph::new(RedundantNodeAdjuster::new(source_graph.clone()));
+        let bin_adjuster = RCGraph::new(TerminalBinAdjuster::new(
+            redundancy_adjuster.clone(),
+            get_terminal_value,
+            merge_terminal_labels,
+        ));
+        let base_graph = TerminalLevelAdjuster::new(bin_adjuster.clone()); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
         let pointer_adjuster = PointerNodeAdjuster::new(
             base_graph,
             EdgeType { tag: (), index: 2 },
             true,
             "".to_string(),
         );
+        // Redundant nodes are styled by their id below the presence adjuster, which does not change when they are bypassed
+        let redundant_nodes: HashSet<NodeID> = pointer_adjuster
+            .source_nodes_to_local(redundancy_adjuster.get().get_redundant_nodes())
+            .into_iter()
+            .collect();
         let presence_adjuster = RCGraph::new(NodePresenceAdjuster::new(pointer_adjuster));
         let modified_graph = RCGraph::new(TerminalLevelAdjuster::new(presence_adjuster.clone()));
         let roots = modified_graph.get_roots();
@@ -460,9 +1006,25 @@ impl MTBDDDiagramDrawer {
 
         let (terminal_min, terminal_max) = (FloatConfig::new(0.), FloatConfig::new(1.));
         let (terminal_min_ref, terminal_max_ref) = (terminal_min.clone(), terminal_max.clone());
+        let terminal_range = (terminal_min.clone(), terminal_max.clone());
+        let expected_values = MutRcRefCell::new(HashMap::<NodeID, f32>::new());
+        let expected_values_ref = expected_values.clone();
+        let terminal_bins = MutRcRefCell::new(Vec::<f32>::new());
+        let terminal_bins_ref = terminal_bins.clone();
+        let named_groups = group_manager.clone();
+        let label_template = MutRcRefCell::new(None::<String>);
+        let true_levels = MutRcRefCell::new(Vec::<LevelNo>::new());
+        let show_true_levels = MutRcRefCell::new(false);
+        let layer_true_levels = true_levels.clone();
+        let layer_show_true_levels = show_true_levels.clone();
+        let node_label_template = label_template.clone();
+        let variable_colors = MutRcRefCell::new(Vec::<(String, Color)>::new());
+        let node_variable_colors = variable_colors.clone();
+        let node_metric = MutRcRefCell::new(NodeMetric::new());
+        let node_metric_ref = node_metric.clone();
         let mut grouped_graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new_shared(
             group_manager.clone(),
-            move |nodes| {
+            move |group, nodes| {
                 let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
                     (
                         Some(&PresenceLabel {
@@ -491,6 +1053,11 @@ impl MTBDDDiagramDrawer {
                         }),
                         None,
                     ) => (None, false, colors.node_label),
+                    (Some(&PresenceLabel { original_id, .. }), None)
+                        if redundant_nodes.contains(&original_id) =>
+                    {
+                        (None, false, colors.node_redundant)
+                    }
                     (Some(_), None) => (None, false, colors.node_default),
                     _ => (None, true, colors.node_group),
                 };
@@ -502,29 +1069,120 @@ impl MTBDDDiagramDrawer {
                         }),
                         None,
                     ) => Some(text.clone()),
+                    (
+                        Some(&PresenceLabel {
+                            original_label:
+                                PointerLabel::Node(NodeLabel {
+                                    ref pointers,
+                                    kind: NodeType::Inner(ref id),
+                                }),
+                            original_id: _,
+                        }),
+                        None,
+                    ) => node_label_template.read().as_ref().map(|template| {
+                        let groups = named_groups.read();
+                        let (level, _) = groups.get_level_range(group);
+                        fill_label_template(template, |placeholder| match placeholder {
+                            "id" => Some(id.clone()),
+                            "level" => Some(level.to_string()),
+                            "var" => Some(groups.get_level_label(level)),
+                            "func_names" => Some(pointers.join(", ")),
+                            _ => None,
+                        })
+                    }),
                     _ => None,
                 }
-                .or_else(|| is_terminal.map(|t| format!("{}", t)));
+                .or_else(|| is_terminal.map(|t| format_terminal(t, &terminal_bins_ref.read())));
+                let name = named_groups
+                    .read()
+                    .get_group_name(group)
+                    .map(|group_name| group_name.get_text())
+                    .or(name);
+                // The inner node label holds the id of the source node, for which the expected value was computed
+                let secondary_label = match (nodes.get(0), nodes.get(1)) {
+                    (
+                        Some(&PresenceLabel {
+                            original_label:
+                                PointerLabel::Node(NodeLabel {
+                                    pointers: _,
+                                    kind: NodeType::Inner(ref id),
+                                }),
+                            original_id: _,
+                        }),
+                        None,
+                    ) => id
+                        .parse::<NodeID>()
+                        .ok()
+                        .and_then(|id| expected_values_ref.read().get(&id).cloned()),
+                    _ => None,
+                }
+                .map(|value| format!("E={:.3}", value));
 
-                NodeData {
+                // Inner nodes are outlined in the color of the variable of their level, if variables are colored
+                let border_color = match (nodes.get(0), nodes.get(1)) {
+                    (
+                        Some(&PresenceLabel {
+                            original_label:
+                                PointerLabel::Node(NodeLabel {
+                                    kind: NodeType::Inner(_),
+                                    ..
+                                }),
+                            ..
+                        }),
+                        None,
+                    ) => {
+                        let groups = named_groups.read();
+                        let (level, _) = groups.get_level_range(group);
+                        let variable = groups.get_level_label(level);
+                        node_variable_colors
+                            .read()
+                            .iter()
+                            .find(|(name, _)| *name == variable)
+                            .map(|&(_, color)| color.into())
+                    }
+                    _ => None,
+                }
+                .unwrap_or(TransparentColor(0.0, 0.0, 0.0, 0.0));
+                // The values of external metrics are drawn on top of the color of single nodes
+                let color = match &named_groups.read().get_nodes_of_group(group)[..] {
+                    &[node] => node_metric_ref.read().get_color(node, color),
+                    _ => color,
+                };
+
+                let mut data = NodeData {
                     color,
-                    border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
-                    width: 1.
-                        + match name {
-                            Some(ref text) => font.measure_width(&text),
-                            None => 0.,
-                        },
+                    border_color,
+                    width: 1.,
                     name,
+                    secondary_label,
+                    label_size: None,
                     is_terminal,
                     is_group,
-                }
+                };
+                data.label_size = data.get_full_label().map(|text| Point {
+                    x: font.measure_width(&text),
+                    y: font.measure_height(&text),
+                });
+                data
             },
-            move |layer_label| LayerData {
-                name: layer_label.clone(),
+            move |level, layer_label| LayerData {
+                name: match (
+                    *layer_show_true_levels.read(),
+                    layer_true_levels.read().get(level as usize),
+                ) {
+                    (true, Some(true_level)) => format!("{} [{}]", layer_label, true_level),
+                    _ => layer_label.clone(),
+                },
             },
         ));
         grouped_graph.hide(0);
+        let grouped_graph = MutRcRefCell::new(grouped_graph);
 
+        let width_config = |width: f32| {
+            let mut c = FloatConfig::new(width);
+            c.set_min(Some(0.)).commit();
+            c
+        };
         let composite_config = CompositeConfig::new((
             ButtonConfig::new_labeled("Generate latex"),
             TextOutputConfig::new(true),
@@ -560,6 +1218,121 @@ impl MTBDDDiagramDrawer {
                     ),
                 )),
             ),
+            LabelConfig::new_styled(
+                "Expected value",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "visibility",
+                        ChoiceConfig::new([
+                            Choice::new(false, "hide"),
+                            Choice::new(true, "show"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "probabilities",
+                        CompositeConfig::new(
+                            level_labels
+                                .iter()
+                                .map(|label| {
+                                    let mut probability = FloatConfig::new(0.5);
+                                    probability.set_min(Some(0.)).commit();
+                                    probability.set_max(Some(1.)).commit();
+                                    LabelConfig::new(label, probability)
+                                })
+                                .collect_vec(),
+                        ),
+                    ),
+                )),
+            ),
+            LabelConfig::new(
+                "Weighted edges",
+                ChoiceConfig::new([
+                    Choice::new(false, "disabled"),
+                    Choice::new(true, "enabled"),
+                ]),
+            ),
+            LabelConfig::new_styled(
+                "Layers",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "labels",
+                        ChoiceConfig::new([
+                            Choice::new(LayerLabelSides::Left, "left"),
+                            Choice::new(LayerLabelSides::Right, "right"),
+                            Choice::new(LayerLabelSides::Both, "both"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "sticky labels",
+                        ChoiceConfig::new([
+                            Choice::new(true, "enabled"),
+                            Choice::new(false, "disabled"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "backgrounds",
+                        ChoiceConfig::new([
+                            Choice::new(true, "alternating"),
+                            Choice::new(false, "plain"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "label rotation",
+                        ChoiceConfig::new([
+                            Choice::new(false, "horizontal"),
+                            Choice::new(true, "vertical"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "variable pairs",
+                        ChoiceConfig::new([
+                            Choice::new(LayerPairing::Separate, "separate"),
+                            Choice::new(LayerPairing::Bracketed, "bracketed"),
+                            Choice::new(LayerPairing::Merged, "merged"),
+                        ]),
+                    ),
+                )),
+            ),
+            LabelConfig::new_styled(
+                "Labels",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "placement",
+                        ChoiceConfig::new([
+                            Choice::new(LabelPlacement::Inside, "inside"),
+                            Choice::new(LabelPlacement::Above, "above"),
+                            Choice::new(LabelPlacement::Below, "below"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "overlapping",
+                        ChoiceConfig::new([
+                            Choice::new(false, "show"),
+                            Choice::new(true, "hide"),
+                        ]),
+                    ),
+                )),
+            ),
+            LabelConfig::new_styled(
+                "Edges",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "arrows",
+                        ChoiceConfig::new([
+                            Choice::new(EdgeArrows::None, "none"),
+                            Choice::new(EdgeArrows::End, "end"),
+                            Choice::new(EdgeArrows::Middle, "middle"),
+                        ]),
+                    ),
+                    LabelConfig::new("true width", width_config(0.2)),
+                    LabelConfig::new("false width", width_config(0.2)),
+                    LabelConfig::new("label width", width_config(0.15)),
+                )),
+            ),
         ));
         let config = Configuration::new(LocationConfig::new(
             Location::BOTTOM_RIGHT,
@@ -577,38 +1350,106 @@ impl MTBDDDiagramDrawer {
             group_manager,
             graph: modified_graph,
             presence_adjuster,
+            source_graph,
+            redundancy_adjuster,
+            hidden_markers: true,
+            hidden_edge_stubs: false,
             time: MutRcRefCell::new(0),
-            drawer: MutRcRefCell::new(Drawer::new(
-                renderer,
-                layout,
-                MutRcRefCell::new(grouped_graph),
-            )),
+            drawer: MutRcRefCell::new(Drawer::new(renderer, layout, grouped_graph.clone())),
+            svg_renderer,
+            latex_renderer: MutRcRefCell::new(LatexRenderer::new()),
+            create_renderer: Box::new(create_renderer),
+            grouped_graph,
+            entered_groups: Vec::new(),
+            semantic_zoom_threshold: DEFAULT_SEMANTIC_ZOOM_THRESHOLD,
+            summarized: false,
+            layer_summary_budget: DEFAULT_LAYER_SUMMARY_BUDGET,
+            layer_summary_threshold: DEFAULT_LAYER_SUMMARY_THRESHOLD,
+            layer_summaries: None,
+            export_legend: MutRcRefCell::new(false),
+            label_template,
+            true_levels,
+            show_true_levels,
+            variable_colors,
+            node_metric,
+            details_cache: HashMap::new(),
+            structural_hasher,
+            consistent_state: None,
+            terminal_range,
+            bin_adjuster,
+            terminal_bins,
             config,
         };
 
-        let (generate_latex, latex_output, expand_all, terminals) = &*composite_config;
+        let (
+            generate_latex,
+            latex_output,
+            expand_all,
+            terminals,
+            expected_value,
+            weighted_edges,
+            layers,
+            labels,
+            edges,
+        ) = &*composite_config;
         let (expand_terminals, zero_visibility, one_visibility, terminal_range) = &***terminals;
         let (terminal_range_start, terminal_range_end) = &***terminal_range;
+        let (expected_value_visibility, probabilities) = &***expected_value;
+        let (label_sides, sticky_labels, layer_backgrounds, layer_label_rotation, layer_pairing) =
+            &***layers;
+        let (label_placement, hide_overlapping_labels) = &***labels;
+        let (arrows, true_width, false_width, label_width) = &***edges;
 
         let drawer = out.drawer.clone();
-        let mut latex_renderer = LatexRenderer::<Layout>::new();
+        let latex_renderer = out.latex_renderer.clone();
+        let svg_renderer = out.svg_renderer.clone();
+        let export_legend = out.export_legend.clone();
+        let variable_colors = out.variable_colors.clone();
+        let node_metric = out.node_metric.clone();
+        let (range_start, range_end) = out.terminal_range.clone();
         let mut output = latex_output.clone();
         generate_latex.clone().add_press_listener(move || {
-            latex_renderer.update_layout(&drawer.get().get_current_layout());
-            latex_renderer.render(u32::MAX);
-            let out = latex_renderer.get_output();
+            let legend = if *export_legend.read() {
+                let range = (range_start.get(), range_end.get());
+                Some(get_legend(
+                    &svg_renderer.read(),
+                    range,
+                    &variable_colors.read(),
+                    &node_metric.read(),
+                ))
+            } else {
+                None
+            };
+            let out = render_latex(&drawer, &latex_renderer, legend, Vec::new(), false);
             output.set(out.into()).commit();
         });
 
-        let from = out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
-        // let from = 0;
+        // Unless all nodes are revealed, the remaining nodes stay undiscovered in the hidden group until they are expanded
+        let from = match reveal {
+            InitialReveal::Default => {
+                out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)])
+            }
+            _ => 0,
+        };
         for root in roots {
             out.create_group(vec![TargetID(TargetIDType::NodeID, root)]);
         }
 
-        let max = 500;
-        if out.group_manager.read().get_nodes_of_group(from).len() < max {
-            reveal_all(&out.group_manager, from, max);
+        match reveal {
+            InitialReveal::Default => {
+                let max = 500;
+                if out.group_manager.read().get_nodes_of_group(from).len() < max {
+                    reveal_all(&out.group_manager, from, max);
+                }
+            }
+            InitialReveal::Lazy => {}
+            InitialReveal::Bounded {
+                max_depth,
+                max_nodes,
+            } => {
+                let groups = out.group_manager.get().reveal_bounded(max_depth, max_nodes);
+                out.adopt_groups(&groups);
+            }
         }
 
         let group_manager = out.group_manager.clone();
@@ -669,15 +1510,298 @@ impl MTBDDDiagramDrawer {
             );
         });
 
+        let visibility_config = expected_value_visibility.clone();
+        let probability_configs = (&***probabilities).clone();
+        let _ = on_configuration_change(expected_value, move || {
+            let mut values = expected_values.get();
+            values.clear();
+            if !visibility_config.get() {
+                return;
+            }
+            let probabilities: HashMap<LevelNo, f32> = probability_configs
+                .iter()
+                .enumerate()
+                .map(|(level, probability)| (level as LevelNo, probability.get()))
+                .collect();
+            for function in &functions {
+                function.expected_values(&probabilities, &mut *values);
+            }
+        });
+
+        let drawer = out.drawer.clone();
+        let weighted_config = weighted_edges.clone();
+        let _ = on_configuration_change(weighted_edges, move || {
+            drawer
+                .get()
+                .get_layout_rules()
+                .get_layout_rules()
+                .set_weighted_edges(weighted_config.get());
+        });
+
+        let drawer = out.drawer.clone();
+        let (label_sides, sticky_labels, layer_backgrounds, layer_label_rotation, layer_pairing) = (
+            label_sides.clone(),
+            sticky_labels.clone(),
+            layer_backgrounds.clone(),
+            layer_label_rotation.clone(),
+            layer_pairing.clone(),
+        );
+        let svg_renderer = out.svg_renderer.clone();
+        let latex_renderer = out.latex_renderer.clone();
+        let _ = on_configuration_change(layers, move || {
+            let settings = (
+                label_sides.get(),
+                sticky_labels.get(),
+                layer_backgrounds.get(),
+                layer_label_rotation.get(),
+                layer_pairing.get(),
+            );
+            drawer.get().configure_renderers("layers", move |renderer| {
+                renderer.set_layer_label_sides(settings.0);
+                renderer.set_sticky_layer_labels(settings.1);
+                renderer.set_alternating_layer_backgrounds(settings.2);
+                renderer.set_rotated_layer_labels(settings.3);
+                renderer.set_layer_pairing(settings.4);
+            });
+            let mut svg_renderer = svg_renderer.get();
+            svg_renderer.set_layer_label_sides(label_sides.get());
+            svg_renderer.set_alternating_layer_backgrounds(layer_backgrounds.get());
+            svg_renderer.set_layer_pairing(layer_pairing.get());
+            let mut latex_renderer = latex_renderer.get();
+            latex_renderer.set_layer_label_sides(label_sides.get());
+            latex_renderer.set_layer_backgrounds(layer_backgrounds.get());
+            latex_renderer.set_rotated_layer_labels(layer_label_rotation.get());
+        });
+
+        let drawer = out.drawer.clone();
+        let (label_placement, hide_overlapping_labels) =
+            (label_placement.clone(), hide_overlapping_labels.clone());
+        let _ = on_configuration_change(labels, move || {
+            let mut drawer = drawer.get();
+            let layout = drawer.get_layout_rules().get_layout_rules();
+            layout.set_label_placement(label_placement.get());
+            layout.set_hide_overlapping_labels(hide_overlapping_labels.get());
+        });
+
+        let drawer = out.drawer.clone();
+        let arrows = arrows.clone();
+        let widths = [true_width.clone(), false_width.clone(), label_width.clone()];
+        let svg_renderer = out.svg_renderer.clone();
+        let latex_renderer = out.latex_renderer.clone();
+        let _ = on_configuration_change(edges, move || {
+            let arrow_style = arrows.get();
+            let width_values = widths.iter().map(|width| width.get()).collect_vec();
+            drawer.get().configure_renderers("edges", move |renderer| {
+                renderer.set_edge_arrows(arrow_style);
+                for (index, &width) in width_values.iter().enumerate() {
+                    renderer.set_edge_type_width(EdgeType::new((), index as i32), width);
+                }
+            });
+            let mut svg_renderer = svg_renderer.get();
+            let mut latex_renderer = latex_renderer.get();
+            svg_renderer.set_edge_arrows(arrows.get());
+            latex_renderer.set_edge_arrows(arrows.get());
+            for (index, width) in widths.iter().enumerate() {
+                let edge_type = EdgeType::new((), index as i32);
+                svg_renderer.set_edge_type_width(edge_type, width.get());
+                latex_renderer.set_edge_width(index as i32, width.get());
+            }
+        });
+
         // Redraw on interaction
         let time = out.time.clone();
         let drawer = out.drawer.clone();
         let _ = after_configuration_change(&composite_config, move || {
-            drawer.get().layout(*time.get());
+            if let Err(error) = drawer.get().layout(*time.get()) {
+                console::log_error!("The diagram could not be laid out: {}", error);
+            }
         });
 
         out
     }
+
+    /// Retrieves the legend to include in exports, if enabled
+    fn get_export_legend(&self) -> Option<Legend> {
+        if *self.export_legend.read() {
+            Some(DiagramSectionDrawer::get_legend(self))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the summed number of satisfying assignments and the combined support of the given source nodes, which are only computed once per node
+    fn get_source_details(&mut self, sources: &[NodeID]) -> (f64, Vec<LevelNo>) {
+        let source_graph = self.source_graph.read();
+        let var_count = source_graph.get_level_count();
+        let mut sat_count = 0.;
+        let mut support = Vec::new();
+        for &source in sources {
+            if !self.details_cache.contains_key(&source) {
+                let Some(function) = source_graph.get_function(source) else {
+                    continue;
+                };
+                let mut counts = HashMap::new();
+                function.count_level_nodes(&mut HashSet::new(), &mut counts);
+                let levels = counts.into_keys().sorted().collect();
+                let details = (function.count_satisfying(var_count), levels);
+                self.details_cache.insert(source, details);
+            }
+            let (count, levels) = &self.details_cache[&source];
+            sat_count += count;
+            support.extend(levels.iter().cloned());
+        }
+        (sat_count, support.into_iter().sorted().dedup().collect())
+    }
+
+    /// Marks every group with children that are hidden by the presence adjuster with a stub labeled by the number of these children, such that a partial view is not mistaken for the whole diagram. Optionally also marks every edge from a single node to a hidden node by a stub.
+    fn update_hidden_markers(&mut self) {
+        let mut markers = Vec::new();
+        if self.hidden_markers || self.hidden_edge_stubs {
+            let group_manager = self.group_manager.read();
+            let mut presence_adjuster = self.presence_adjuster.get();
+            for group in group_manager.get_all_groups() {
+                let nodes = group_manager.get_nodes_of_group(group);
+                let Some(&anchor) = nodes.first() else {
+                    continue;
+                };
+                let stub = |delta: Point, label: String| Annotation {
+                    anchor: Some(anchor),
+                    offset: Point { x: 0., y: -0.5 },
+                    kind: AnnotationKind::Stub(delta, label),
+                };
+                if self.hidden_markers {
+                    let hidden = nodes
+                        .iter()
+                        .flat_map(|&node| presence_adjuster.get_hidden_children(node, false))
+                        .map(|(_, child)| child)
+                        .unique()
+                        .count();
+                    if hidden > 0 {
+                        let delta = Point {
+                            x: 0.,
+                            y: -STUB_LENGTH,
+                        };
+                        markers.push(stub(delta, hidden.to_string()));
+                    }
+                }
+                // The edges of groups of multiple nodes are combined, hence only edges of single nodes get stubs
+                if self.hidden_edge_stubs && nodes.len() == 1 {
+                    for (edge_type, _) in presence_adjuster.get_hidden_children(anchor, true) {
+                        let delta = get_edge_stub_delta(edge_type.index);
+                        markers.push(stub(delta, STUB_ELLIPSIS.to_string()));
+                    }
+                }
+            }
+        }
+        self.drawer.get().set_markers(markers);
+    }
+
+    /// Nests the given groups inside of the currently entered group, such that they remain visible
+    fn adopt_groups(&mut self, groups: &[NodeGroupID]) {
+        let Some(&current) = self.entered_groups.last() else {
+            return;
+        };
+        self.group_manager.get().set_groups_parent(groups, current);
+        self.update_focus();
+    }
+
+    /// Summarizes the subfunctions below the top levels when zoomed out further than the semantic zoom threshold, and restores them once zoomed back in. Returns whether the groups changed.
+    fn update_semantic_zoom(&mut self) -> bool {
+        let scale = self.drawer.read().get_transform().scale;
+        let summarize = scale < self.semantic_zoom_threshold;
+        if summarize == self.summarized {
+            return false;
+        }
+        self.summarized = summarize;
+        let groups = if summarize {
+            self.group_manager
+                .get()
+                .summarize(SEMANTIC_ZOOM_DETAIL_LEVELS)
+        } else {
+            // The runs of giant layers are restored as well, and summarized again based on the next layout
+            self.layer_summaries = None;
+            self.group_manager.get().restore_summaries()
+        };
+        self.adopt_groups(&groups);
+        true
+    }
+
+    /// Summarizes runs of adjacent groups of the layers with more groups than the budget when zoomed out further than the threshold, such that every layer shows at most the budget of blocks labeled with their number of nodes, and restores them once zoomed back in. The runs are obtained from the current layout. Returns whether the groups changed.
+    fn update_layer_summaries(&mut self) -> bool {
+        let scale = self.drawer.read().get_transform().scale;
+        let summarize = self.layer_summary_budget > 0 && scale < self.layer_summary_threshold;
+        if summarize == self.layer_summaries.is_some() {
+            return false;
+        }
+        let (groups, changed) = if summarize {
+            let runs = self
+                .drawer
+                .read()
+                .get_current_layout()
+                .get_layer_runs(self.layer_summary_budget)
+                .into_iter()
+                .map(|run| run.into_iter().filter(|&group| group != 0).collect())
+                .collect();
+            let summaries = self.group_manager.get().summarize_runs(runs);
+            let changed = !summaries.is_empty();
+            self.layer_summaries = Some(summaries.clone());
+            (summaries, changed)
+        } else {
+            let summaries = self.layer_summaries.take().unwrap_or_default();
+            let groups = self.group_manager.get().restore_summary_groups(&summaries);
+            (groups, !summaries.is_empty())
+        };
+        self.adopt_groups(&groups);
+        changed
+    }
+
+    /// Lays out the graph again, where the new layout is only sent to the renderers if requested
+    fn layout_graph(&mut self, time: u32, render: bool) -> Result<(), VizError> {
+        self.update_hidden_markers();
+        // The nodes representing the source nodes may have changed since the values were set
+        let graph = &self.graph;
+        self.node_metric
+            .get()
+            .update_local_values(|source| graph.source_nodes_to_local(vec![source]));
+        let result = if render {
+            self.drawer.get().layout(time)
+        } else {
+            self.drawer.get().compute_layout(time)
+        };
+        match &result {
+            Ok(()) => self.consistent_state = Some(self.serialize_state()),
+            Err(error) => console::log_error!("The diagram could not be laid out: {}", error),
+        }
+        result
+    }
+
+    /// Only shows the entered group and the groups nested inside of it, dropping groups that no longer exist from the path
+    fn update_focus(&mut self) {
+        let group_manager = self.group_manager.read();
+        self.entered_groups.retain(|group| group_manager.get_groups().contains_key(group));
+        let focus = self
+            .entered_groups
+            .last()
+            .map(|&group| group_manager.get_descendant_groups(group));
+        self.grouped_graph.get().set_focus(focus);
+    }
+
+    /// Restores the groups and drawer state of a serialized state, without laying out the diagram. Returns false if the state was stored by an unsupported version of the format, in which case nothing is restored.
+    fn read_state(&mut self, state: &Vec<u8>) -> bool {
+        let mut stream = Cursor::new(state);
+        let version = match read_state_header(&mut stream) {
+            Ok(version) => version,
+            Err(error) => {
+                console::log_error!("The state could not be restored: {}", error);
+                return false;
+            }
+        };
+        let _ = self.group_manager.get().read(&mut stream, version);
+        let _ = self.drawer.get().read(&mut stream, version);
+        self.entered_groups.clear();
+        self.update_focus();
+        true
+    }
 }
 
 fn reveal_all<G: GraphStructure>(
@@ -707,18 +1831,149 @@ fn reveal_all<G: GraphStructure>(
     }
 }
 
+fn get_terminal_value(label: &NodeLabel<MTBDDTerminal>) -> Option<f32> {
+    match label.kind {
+        NodeType::Terminal(terminal) => Some(terminal.0),
+        NodeType::Inner(_) => None,
+    }
+}
+
+/// Merges the terminals of a bin into a terminal with the center value of the bin, which keeps the pointers to all of them
+fn merge_terminal_labels(
+    labels: Vec<NodeLabel<MTBDDTerminal>>,
+    (start, end): (f32, f32),
+) -> NodeLabel<MTBDDTerminal> {
+    NodeLabel {
+        pointers: labels.into_iter().flat_map(|label| label.pointers).collect(),
+        kind: NodeType::Terminal(MTBDDTerminal(0.5 * (start + end))),
+    }
+}
+
+/// Formats the value of a terminal, or the range of the bin that contains it if terminals are binned
+fn format_terminal(terminal: MTBDDTerminal, bins: &[f32]) -> String {
+    bins.iter()
+        .tuple_windows()
+        .find(|&(&start, &end)| start <= terminal.0 && terminal.0 < end)
+        .map(|(start, end)| format!("[{}, {})", start, end))
+        .unwrap_or_else(|| format!("{}", terminal))
+}
+
+/// Retrieves the meaning of the visible edge types, the node colors and the scale of the terminal colors
+fn get_legend(
+    svg_renderer: &SvgRenderer<Layout>,
+    (min, max): (f32, f32),
+    variable_colors: &[(String, Color)],
+    node_metric: &NodeMetric,
+) -> Legend {
+    let colors = &MTBDDColors::LIGHT;
+    let edges = svg_renderer
+        .get_visible_edge_types()
+        .into_iter()
+        .map(|(index, edge_type)| LegendEntry {
+            symbol: LegendSymbol::Edge {
+                color: edge_type.color,
+                width: edge_type.width,
+                dash_solid: edge_type.dash_solid,
+                dash_transparent: edge_type.dash_transparent,
+            },
+            meaning: match index {
+                0 => "true edge",
+                1 => "false edge",
+                _ => "pointer edge",
+            }
+            .into(),
+        });
+    let nodes = [
+        (colors.node_default, "inner node"),
+        (colors.node_redundant, "redundant node"),
+        (colors.node_label, "pointer"),
+        (colors.node_group, "group of nodes"),
+    ]
+    .into_iter()
+    .map(|(color, meaning)| LegendEntry {
+        symbol: LegendSymbol::Node { color },
+        meaning: meaning.into(),
+    });
+    let terminals = LegendEntry {
+        symbol: LegendSymbol::Scale {
+            from: colors.node_false,
+            to: colors.node_true,
+            min,
+            max,
+        },
+        meaning: "terminal value".into(),
+    };
+    Legend {
+        entries: edges
+            .chain(nodes)
+            .chain([terminals])
+            .chain(get_variable_entries(variable_colors))
+            .chain(node_metric.get_legend_entry())
+            .collect(),
+    }
+}
+
+/// Renders the current layout of the drawer as TikZ, optionally annotated with beamer overlays for the recorded stages
+fn render_latex(
+    drawer: &MutRcRefCell<Drawer<ScreenRenderer<()>, Layout, GroupedGraph>>,
+    latex_renderer: &MutRcRefCell<LatexRenderer<Layout>>,
+    legend: Option<Legend>,
+    pages: Vec<Page>,
+    staged: bool,
+) -> String {
+    let drawer = drawer.read();
+    let mut latex_renderer = latex_renderer.get();
+    latex_renderer.resolve_node_styles(|node| drawer.get_node_group(node));
+    latex_renderer.set_legend(legend);
+    latex_renderer.set_pages(pages);
+    latex_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+    latex_renderer.set_annotations(&drawer.get_annotation_shapes());
+    latex_renderer.update_layout(&drawer.get_export_layout());
+    latex_renderer.set_staged(staged);
+    latex_renderer.render(u32::MAX);
+    latex_renderer.get_output()
+}
+
 impl DiagramSectionDrawer for MTBDDDiagramDrawer {
     fn render(&mut self, time: u32) -> () {
         *self.time.get() = time;
         self.drawer.get().render(time);
     }
 
-    fn layout(&mut self, time: u32) -> () {
-        self.drawer.get().layout(time);
+    fn layout(&mut self, time: u32) -> Result<(), VizError> {
+        self.layout_graph(time, true)
     }
 
-    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
+    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> bool {
         self.drawer.get().set_transform(width, height, x, y, scale);
+        // The layer summaries are derived from the layout, hence they wait for the layout of changed subfunction summaries
+        self.update_semantic_zoom() || self.update_layer_summaries()
+    }
+
+    fn set_semantic_zoom(&mut self, threshold: f32) -> bool {
+        self.semantic_zoom_threshold = threshold;
+        self.update_semantic_zoom()
+    }
+
+    fn set_layer_summaries(&mut self, budget: usize, threshold: f32) -> bool {
+        self.layer_summary_budget = budget;
+        self.layer_summary_threshold = threshold;
+        self.update_layer_summaries()
+    }
+
+    fn set_layer_virtualization(&mut self, threshold: Option<usize>) {
+        self.drawer.get().set_layer_window_threshold(threshold);
+    }
+
+    fn set_appearance_animations(
+        &mut self,
+        entrance: AppearanceAnimation,
+        exit: AppearanceAnimation,
+    ) {
+        self.drawer
+            .get()
+            .get_layout_rules()
+            .set_appearance_animations(entrance, exit);
     }
 
     fn set_step(&mut self, step: i32) -> Option<StepData> {
@@ -730,20 +1985,387 @@ impl DiagramSectionDrawer for MTBDDDiagramDrawer {
     }
 
     fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
-        self.group_manager.get().create_group(from)
+        let group = self.group_manager.get().create_group(from);
+        self.adopt_groups(&[group]);
+        group
+    }
+
+    fn set_group_label(&mut self, group: NodeGroupID, name: String, note: String) -> bool {
+        self.group_manager.get().set_group_name(group, name, note)
+    }
+
+    fn set_level_label(&mut self, level: LevelNo, name: String) {
+        self.source_graph.get().set_level_label(level, name);
+    }
+
+    fn set_true_level_labels(&mut self, show: bool) {
+        *self.show_true_levels.get() = show;
+    }
+
+    fn set_node_label_template(&mut self, template: Option<String>) {
+        *self.label_template.get() = template;
+    }
+
+    fn get_level_labels(&self) -> Vec<String> {
+        let source_graph = self.source_graph.read();
+        (0..source_graph.get_level_count())
+            .map(|level| source_graph.get_level_label(level))
+            .collect()
+    }
+
+    fn set_variable_colors(&mut self, colors: Vec<(String, Color)>) {
+        *self.variable_colors.get() = colors;
+    }
+
+    fn set_node_metric(&mut self, values: HashMap<NodeID, f64>) {
+        self.node_metric.get().set_values(values);
+    }
+
+    fn expand_group(&mut self, group: NodeGroupID, depth: LevelNo) -> Vec<NodeGroupID> {
+        // The expanded groups are nested in place of the group, rather than in the entered group
+        let groups = self.group_manager.get().expand_group(group, depth);
+        self.update_focus();
+        groups
+    }
+
+    fn expand_node(&mut self, node: NodeID, depth: usize) -> Vec<NodeGroupID> {
+        let groups = self.group_manager.get().expand_node(node, depth);
+        self.adopt_groups(&groups);
+        groups
+    }
+
+    fn group_shared_subfunctions(
+        &mut self,
+        min_occurrences: usize,
+        min_size: usize,
+    ) -> Vec<NodeGroupID> {
+        let graph = &self.graph;
+        let groups = self.group_manager.get().group_shared_subfunctions(
+            min_occurrences,
+            min_size,
+            &mut self.structural_hasher,
+            |node| graph.local_nodes_to_sources(vec![node]).first().cloned(),
+        );
+        self.adopt_groups(&groups);
+        groups
+    }
+
+    fn group_by_level_pattern(&mut self, pattern: String) -> Result<Option<NodeGroupID>, String> {
+        let regex = Regex::new(&pattern).map_err(|err| err.to_string())?;
+        let group = self
+            .group_manager
+            .get()
+            .group_levels(pattern, |label| regex.is_match(label));
+        if let Some(group) = group {
+            self.adopt_groups(&[group]);
+        }
+        Ok(group)
+    }
+
+    fn set_group_parent(&mut self, group: NodeGroupID, parent: Option<NodeGroupID>) -> bool {
+        let nested = self.group_manager.get().set_group_parent(group, parent);
+        self.update_focus();
+        nested
+    }
+
+    fn enter_group(&mut self, group: NodeGroupID) -> bool {
+        if !self.group_manager.read().get_groups().contains_key(&group) {
+            return false;
+        }
+        self.entered_groups.push(group);
+        self.update_focus();
+        true
+    }
+
+    fn exit_group(&mut self) -> bool {
+        let exited = self.entered_groups.pop().is_some();
+        self.update_focus();
+        exited
+    }
+
+    fn get_entered_groups(&self) -> Vec<NodeGroupID> {
+        self.entered_groups.clone()
+    }
+
+    fn collapse_low(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        // Edge index 1 represents the false edge
+        let group = self.group_manager.get().collapse_subtree(node, 1)?;
+        self.adopt_groups(&[group]);
+        Some(group)
+    }
+
+    fn collapse_high(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        // Edge index 0 represents the true edge
+        let group = self.group_manager.get().collapse_subtree(node, 0)?;
+        self.adopt_groups(&[group]);
+        Some(group)
+    }
+
+    fn set_edge_visibility(&mut self, edge_type: i32, visible: bool, relayout: bool) {
+        let edge_type = EdgeType::new((), edge_type);
+        self.grouped_graph
+            .get()
+            .set_edge_type_presence(edge_type, visible || !relayout);
+        self.drawer.get().configure_renderers(
+            &format!("edge visibility {}", edge_type.index),
+            move |renderer| renderer.set_edge_type_visibility(edge_type, visible || relayout),
+        );
+        self.svg_renderer
+            .get()
+            .set_edge_type_visibility(edge_type, visible);
     }
 
     fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
         self.group_manager.get().split_edges(nodes, fully);
     }
+    fn get_redundant_nodes(&mut self) -> Vec<NodeID> {
+        let sources = self.redundancy_adjuster.get().get_redundant_nodes();
+        self.graph.source_nodes_to_local(sources)
+    }
+    fn set_redundant_bypass(&mut self, bypass: bool) {
+        self.redundancy_adjuster.get().set_bypass(bypass);
+    }
+    fn set_hidden_markers(&mut self, enabled: bool) {
+        self.hidden_markers = enabled;
+    }
+    fn set_hidden_edge_stubs(&mut self, enabled: bool) {
+        self.hidden_edge_stubs = enabled;
+    }
+    fn set_empty_layer_elision(&mut self, enabled: bool) {
+        self.drawer
+            .get()
+            .get_layout_rules()
+            .get_layout_rules()
+            .set_elide_empty_layers(enabled);
+    }
+    fn apply_to_selection(&mut self, operation: SelectionOperation) -> Vec<NodeGroupID> {
+        let nodes = self.drawer.read().get_selected_nodes();
+        if nodes.is_empty() {
+            return Vec::new();
+        }
+        let remainder = match operation {
+            SelectionOperation::Show => PresenceRemainder::Show,
+            SelectionOperation::Hide => PresenceRemainder::Hide,
+            SelectionOperation::Duplicate => PresenceRemainder::Duplicate,
+            SelectionOperation::Group => {
+                let targets = nodes
+                    .iter()
+                    .map(|&node| TargetID(TargetIDType::NodeID, node))
+                    .collect();
+                return vec![self.create_group(targets)];
+            }
+            SelectionOperation::Split | SelectionOperation::SplitFully => {
+                let fully = operation == SelectionOperation::SplitFully;
+                self.group_manager.get().split_edges(&nodes, fully);
+                return Vec::new();
+            }
+        };
+        let mut adjuster = self.presence_adjuster.get();
+        for &node in &nodes {
+            adjuster.set_node_presence(node, PresenceGroups::remainder(remainder.clone()));
+        }
+        Vec::new()
+    }
+    fn set_presence_where(
+        &mut self,
+        predicate: PresencePredicate,
+        remainder: PresenceRemainder,
+    ) -> Result<usize, String> {
+        self.presence_adjuster
+            .get()
+            .set_presence_where(&predicate, remainder)
+    }
+    fn set_duplication_threshold(&mut self, threshold: usize) -> DuplicationData {
+        let (duplicated_nodes, created_copies) = self
+            .presence_adjuster
+            .get()
+            .set_duplication_threshold(threshold);
+        DuplicationData {
+            duplicated_nodes,
+            created_copies,
+        }
+    }
+    fn set_terminal_bins(&mut self, bounds: Vec<f32>) {
+        let mut bin_adjuster = self.bin_adjuster.get();
+        bin_adjuster.set_bounds(bounds);
+        *self.terminal_bins.get() = bin_adjuster.get_bounds().clone();
+    }
+    fn set_root_order(&mut self, roots: &[NodeID]) {
+        self.drawer
+            .get()
+            .get_layout_rules()
+            .get_layout_rules()
+            .set_root_order(roots.to_vec());
+    }
+    fn pin_nodes(&mut self, nodes: &[NodeID], pinned: bool) {
+        let mut drawer = self.drawer.get();
+        let pins = get_pin_positions(&drawer.get_current_layout(), nodes, |node| {
+            drawer.get_node_group(node)
+        });
+        let positioning = drawer
+            .get_layout_rules()
+            .get_layout_rules()
+            .get_positioning_aligning();
+        if pinned {
+            positioning.pin(pins);
+        } else {
+            positioning.unpin(nodes);
+        }
+    }
+    fn pin_terminals(&mut self, terminals: &[NodeID], column: HorizontalAlignment) {
+        let all_terminals = self.graph.get_terminals();
+        let terminals = terminals
+            .iter()
+            .cloned()
+            .filter(|terminal| all_terminals.contains(terminal))
+            .collect_vec();
+        self.drawer
+            .get()
+            .get_layout_rules()
+            .get_layout_rules()
+            .get_positioning_aligning()
+            .pin_to_column(&terminals, column);
+    }
+    fn move_group(&mut self, group: NodeGroupID, dx: f32, dy: f32) {
+        let pinned = {
+            let mut drawer = self.drawer.get();
+            drawer.move_group(group, dx, dy);
+            let positioning = drawer
+                .get_layout_rules()
+                .get_layout_rules()
+                .get_positioning_aligning();
+            self.group_manager
+                .read()
+                .get_nodes_of_group(group)
+                .into_iter()
+                .filter(|&node| positioning.is_pinned(node))
+                .collect_vec()
+        };
+        // Pinned nodes stay where they were moved to, other nodes return to their computed position on the next layout
+        self.pin_nodes(&pinned, true);
+    }
+    fn align_groups(&mut self, nodes: &[NodeID], alignment: HorizontalAlignment) {
+        self.drawer.get().align_groups(nodes, alignment);
+    }
+    fn distribute_groups(&mut self, nodes: &[NodeID]) {
+        self.drawer.get().distribute_groups(nodes);
+    }
+    fn reset_group_positions(&mut self, nodes: &[NodeID]) {
+        self.drawer.get().reset_group_positions(nodes);
+    }
+    fn sort_roots(&mut self, sorting: RootSorting) -> Vec<NodeID> {
+        let roots = get_sorted_roots(&mut self.graph, sorting, |label| match label.original_label {
+            PointerLabel::Pointer(name) => Some(name),
+            _ => None,
+        });
+        self.set_root_order(&roots);
+        roots
+    }
+
+    fn add_view(&mut self, canvas: HtmlCanvasElement) -> ViewID {
+        let renderer = (self.create_renderer)(RenderSurface::Canvas(canvas));
+        self.drawer.get().add_view(renderer)
+    }
+    fn add_framebuffer_view(
+        &mut self,
+        context: WebGl2RenderingContext,
+        framebuffer: Option<WebGlFramebuffer>,
+        width: u32,
+        height: u32,
+    ) -> ViewID {
+        let renderer = (self.create_renderer)(RenderSurface::Framebuffer {
+            context,
+            framebuffer,
+            width,
+            height,
+        });
+        self.drawer.get().add_view(renderer)
+    }
+    #[cfg(feature = "webgpu")]
+    fn use_webgpu(&mut self, context: WebgpuContext) {
+        let renderer = (self.create_renderer)(RenderSurface::Webgpu(context));
+        self.drawer.get().set_renderer(renderer);
+    }
+    fn remove_view(&mut self, view: ViewID) -> bool {
+        self.drawer.get().remove_view(view)
+    }
+    fn set_view_transform(
+        &mut self,
+        view: ViewID,
+        width: u32,
+        height: u32,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) {
+        self.drawer
+            .get()
+            .set_view_transform(view, width, height, x, y, scale);
+    }
+    fn render_view(&mut self, view: ViewID, time: u32) {
+        self.drawer.get().render_view(view, time);
+    }
+    fn get_view_nodes(
+        &self,
+        view: ViewID,
+        area: Rectangle,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID> {
+        self.drawer
+            .read()
+            .get_view_nodes(view, area, max_group_expansion)
+    }
+
+    fn get_history(&self) -> Vec<HistoryEntryData> {
+        self.drawer.read().get_history()
+    }
+    fn scrub_history(&mut self, index: Option<usize>) -> bool {
+        self.drawer.get().scrub_history(index)
+    }
 
     fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
         self.drawer.read().get_nodes(area, max_group_expansion)
     }
+    fn get_elements(&self, area: Rectangle, max_group_expansion: usize) -> ElementsData {
+        self.drawer.read().get_elements(area, max_group_expansion)
+    }
 
     fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
         self.drawer.get().select_nodes(selected_ids, hovered_ids);
     }
+    fn save_selection(&mut self, name: String) {
+        self.drawer.get().save_selection(name);
+    }
+    fn apply_selection(&mut self, name: String) -> Option<Vec<NodeID>> {
+        self.drawer.get().apply_selection(&name)
+    }
+    fn get_selection_names(&self) -> Vec<String> {
+        self.drawer.read().get_selection_names()
+    }
+    fn set_edge_style(&mut self, edge: GroupEdgeID, style: EdgeStyle) {
+        self.drawer.get().set_edge_style(edge, style);
+    }
+    fn set_selected_edges(&mut self, edges: &[GroupEdgeID]) {
+        self.drawer.get().select_edges(edges);
+    }
+    fn set_edge_weights(&mut self, weights: HashMap<NodeEdgeID, Complex>) {}
+    fn get_matrix(&self, node: NodeID, max_dim: usize) -> Option<Matrix> {
+        None
+    }
+    fn add_annotation(
+        &mut self,
+        anchor: Option<NodeID>,
+        position: Point,
+        kind: AnnotationKind,
+    ) -> AnnotationID {
+        self.drawer.get().add_annotation(anchor, position, kind)
+    }
+    fn remove_annotation(&mut self, id: AnnotationID) -> bool {
+        self.drawer.get().remove_annotation(id)
+    }
+    fn navigate(&mut self, direction: NavigationDirection) -> Option<FocusData> {
+        self.drawer.get().navigate(direction)
+    }
 
     fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
         self.graph
@@ -754,16 +2376,233 @@ impl DiagramSectionDrawer for MTBDDDiagramDrawer {
         self.graph
             .source_nodes_to_local(nodes.iter().cloned().collect())
     }
+
+    fn get_node_hash(&mut self, node: NodeID) -> Option<u64> {
+        let source = *self.graph.local_nodes_to_sources(vec![node]).first()?;
+        Some(self.structural_hasher.get_node_hash(source))
+    }
+
+    fn get_node_details(&mut self, node: NodeID) -> NodeDetailsData {
+        let mut graph = self.graph.clone();
+        let level = graph.get_level(node);
+        let sources = graph.local_nodes_to_sources(vec![node]);
+        let (sat_count, support) = self.get_source_details(&sources);
+        NodeDetailsData {
+            level,
+            label: graph.get_level_label(level),
+            out_degree: graph.get_children(node).len(),
+            in_degree: graph.get_known_parents(node).len(),
+            sat_count,
+            support,
+            group: self.group_manager.read().get_group(node),
+            sources,
+        }
+    }
+
+    fn export_region_svg(&mut self, area: Rectangle) -> String {
+        let legend = self.get_export_legend();
+        let mut svg_renderer = self.svg_renderer.get();
+        svg_renderer.set_region(Some(area));
+        svg_renderer.set_legend(legend);
+        let drawer = self.drawer.read();
+        svg_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+        svg_renderer.set_annotations(&drawer.get_annotation_shapes());
+        svg_renderer.update_layout(&drawer.get_export_layout());
+        svg_renderer.render(u32::MAX);
+        svg_renderer.get_output()
+    }
+
+    fn export_region_png(&mut self, area: Rectangle, pixels_per_unit: f32) -> String {
+        let time = *self.time.get();
+        let legend = self.get_export_legend();
+        self.drawer
+            .get()
+            .export_region_png(area, pixels_per_unit, legend.as_ref(), time)
+            .unwrap_or_default()
+    }
+
+    fn export_paged_svg(&mut self, paper: PaperSize, overlap: f32) -> Vec<String> {
+        let mut svg_renderer = self.svg_renderer.get();
+        let drawer = self.drawer.read();
+        svg_renderer.set_legend(None);
+        svg_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+        svg_renderer.set_annotations(&drawer.get_annotation_shapes());
+        svg_renderer.update_layout(&drawer.get_export_layout());
+        let pages = paginate(&svg_renderer.get_bounds(u32::MAX), paper, overlap);
+        let out = pages
+            .into_iter()
+            .map(|page| {
+                svg_renderer.set_page(Some(page));
+                svg_renderer.render(u32::MAX);
+                svg_renderer.get_output()
+            })
+            .collect();
+        svg_renderer.set_page(None);
+        out
+    }
+
+    fn export_paged_tikz(&mut self, paper: PaperSize, overlap: f32) -> String {
+        let bounds = {
+            let mut svg_renderer = self.svg_renderer.get();
+            svg_renderer.update_layout(&self.drawer.read().get_export_layout());
+            svg_renderer.get_bounds(u32::MAX)
+        };
+        let pages = paginate(&bounds, paper, overlap);
+        render_latex(&self.drawer, &self.latex_renderer, None, pages, false)
+    }
+
+    fn get_legend(&self) -> Legend {
+        let (start, end) = &self.terminal_range;
+        let range = (start.get(), end.get());
+        get_legend(
+            &self.svg_renderer.read(),
+            range,
+            &self.variable_colors.read(),
+            &self.node_metric.read(),
+        )
+    }
+
+    fn set_export_grid(&mut self, grid: Option<f32>) {
+        self.drawer.get().set_export_grid(grid);
+    }
+
+    fn set_export_legend(&mut self, enabled: bool) {
+        *self.export_legend.get() = enabled;
+    }
+
+    fn set_tikz_node_style(&mut self, node: NodeID, style: String) {
+        self.latex_renderer.get().set_node_style(node, style);
+    }
+
+    fn set_tikz_level_style(&mut self, level: LevelNo, style: String) {
+        self.latex_renderer.get().set_level_style(level, style);
+    }
+
+    fn set_tikz_edge_style(&mut self, edge_type: i32, style: String) {
+        self.latex_renderer.get().set_edge_style(edge_type, style);
+    }
+
+    fn record_tikz_stage(&mut self) -> usize {
+        let layout = self.drawer.read().get_export_layout();
+        self.latex_renderer.get().record_stage(&layout)
+    }
+
+    fn clear_tikz_stages(&mut self) {
+        self.latex_renderer.get().clear_stages();
+    }
+
+    fn export_staged_tikz(&mut self) -> String {
+        let legend = self.get_export_legend();
+        render_latex(&self.drawer, &self.latex_renderer, legend, Vec::new(), true)
+    }
+
+    fn get_adjacency(&self) -> AdjacencyData {
+        self.drawer.read().get_adjacency()
+    }
+    fn get_crossing_count(&self) -> usize {
+        self.drawer.read().get_crossing_count()
+    }
+    fn get_layout_metrics(&self) -> LayoutMetricsData {
+        self.drawer.read().get_layout_metrics()
+    }
+    fn compute_layout(&mut self, time: u32) -> Result<LayoutData, VizError> {
+        self.layout_graph(time, false)?;
+        Ok(self.drawer.read().get_layout_data())
+    }
+    fn get_geometry(&self) -> SectionGeometry {
+        self.drawer.read().get_geometry()
+    }
+    fn export_outline(&self) -> String {
+        self.drawer.read().export_outline()
+    }
     fn serialize_state(&self) -> Vec<u8> {
         let mut out = Vec::new();
-        let _ = self.group_manager.read().write(&mut Cursor::new(&mut out));
+        let mut stream = Cursor::new(&mut out);
+        let _ = write_state_header(&mut stream)
+            .and_then(|_| self.group_manager.read().write(&mut stream))
+            .and_then(|_| self.drawer.read().write(&mut stream));
         out
     }
 
     fn deserialize_state(&mut self, state: Vec<u8>) -> () {
-        let _ = self.group_manager.get().read(&mut Cursor::new(&state));
+        if !self.read_state(&state) {
+            return;
+        }
+        let time = *self.time.get();
+        // A failed layout is already reported, and keeps showing the previous layout
+        let _ = self.layout(time);
+    }
+
+    fn recover(&mut self) -> bool {
+        let Some(state) = self.consistent_state.clone() else {
+            return false;
+        };
+        if !self.read_state(&state) {
+            return false;
+        }
         let time = *self.time.get();
-        self.layout(time);
+        self.layout(time).is_ok()
+    }
+
+    fn restore_context(&mut self) -> bool {
+        match self.drawer.get().restore_context() {
+            Ok(()) => true,
+            Err(error) => {
+                console::log_error!("The rendering context could not be restored: {:?}", error);
+                false
+            }
+        }
+    }
+
+    fn export_view_patch(&self) -> String {
+        let group_manager = self.group_manager.read();
+        let hidden = group_manager.get_hidden();
+        let groups = group_manager
+            .get_all_groups()
+            .into_iter()
+            .filter(|group| !hidden.contains(group))
+            .filter_map(|group| {
+                let nodes = group_manager.get_nodes_of_group(group);
+                let name = group_manager.get_group_name(group).cloned();
+                if nodes.len() < 2 && name.is_none() {
+                    return None;
+                }
+                let nodes = self.graph.local_nodes_to_sources(nodes);
+                Some(ViewPatchGroup { nodes, name })
+            })
+            .collect();
+        ViewPatch {
+            presence: self.presence_adjuster.read().get_source_presences(),
+            groups,
+        }
+        .to_json()
+    }
+
+    fn import_view_patch(&mut self, patch: String) -> Result<(), String> {
+        let patch = ViewPatch::from_json(&patch)?;
+        {
+            let mut adjuster = self.presence_adjuster.get();
+            for (source, remainder) in patch.presence {
+                adjuster.set_source_presence(source, remainder);
+            }
+        }
+        // Groups whose nodes are all absent from this diagram are skipped
+        for group in patch.groups {
+            let targets = self
+                .graph
+                .source_nodes_to_local(group.nodes)
+                .into_iter()
+                .map(|node| TargetID(TargetIDType::NodeID, node))
+                .collect_vec();
+            if targets.is_empty() {
+                continue;
+            }
+            let id = self.create_group(targets);
+            if let Some(name) = group.name {
+                self.set_group_label(id, name.name, name.note);
+            }
+        }
+        Ok(())
     }
 
     fn get_configuration(&self) -> AbstractConfigurationObject {
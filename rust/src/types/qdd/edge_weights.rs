@@ -0,0 +1,194 @@
+use std::{collections::HashMap, f32::consts::PI, ops::Mul};
+
+use crate::{
+    types::util::drawing::renderer::{EdgeStyle, GroupEdgeID},
+    util::color::Color,
+    wasm_interface::{NodeGroupID, NodeID},
+};
+
+/// The edge widths that weights with magnitude 0 and 1 are drawn with when encoded by thickness
+const MIN_WEIGHT_WIDTH: f32 = 0.05;
+const MAX_WEIGHT_WIDTH: f32 = 0.4;
+/// The number of decimals shown in weight labels
+const LABEL_PRECISION: usize = 3;
+
+/// Identifies the edge of a given type index between a source and target node
+pub type NodeEdgeID = (NodeID, NodeID, i32);
+
+/// A complex edge weight, such as the amplitude of a quantum decision diagram
+#[derive(Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { re: 0., im: 0. };
+    pub const ONE: Complex = Complex { re: 1., im: 0. };
+
+    pub fn magnitude(&self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    /// Retrieves the argument of the weight, between 0 and 2 pi
+    pub fn phase(&self) -> f32 {
+        self.im.atan2(self.re).rem_euclid(2. * PI)
+    }
+
+    /// Formats the weight such as `0.707-0.707i`, omitting parts that are zero
+    pub fn format(&self) -> String {
+        let number = |value: f32| {
+            let text = format!("{:.*}", LABEL_PRECISION, value);
+            let text = text.trim_end_matches('0').trim_end_matches('.');
+            if text == "-0" {
+                "0".to_string()
+            } else {
+                text.to_string()
+            }
+        };
+        let (re, im) = (number(self.re), number(self.im));
+        match (re.as_str(), im.as_str()) {
+            (_, "0") => re,
+            ("0", _) => format!("{}i", im),
+            _ if im.starts_with('-') => format!("{}{}i", re, im),
+            _ => format!("{}+{}i", re, im),
+        }
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+/// How the weights of edges are shown
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WeightDisplay {
+    Hidden,
+    /// The formatted weight is placed next to the edge
+    Labels,
+    /// The magnitude of the weight determines the edge width
+    Thickness,
+    /// The phase of the weight determines the edge color
+    Hue,
+}
+
+/// Derives the styles of the group edges from the weights of the edges they consist of. Group edges combining edges with different weights are left unstyled, since no single weight applies.
+pub fn get_weight_styles(
+    weights: &HashMap<NodeEdgeID, Complex>,
+    display: WeightDisplay,
+    get_group: impl Fn(NodeID) -> NodeGroupID,
+) -> HashMap<GroupEdgeID, EdgeStyle> {
+    if display == WeightDisplay::Hidden {
+        return HashMap::new();
+    }
+    let mut group_weights = HashMap::<GroupEdgeID, Option<Complex>>::new();
+    for (&(from, to, edge_type), &weight) in weights {
+        group_weights
+            .entry((get_group(from), get_group(to), edge_type))
+            .and_modify(|existing| {
+                if *existing != Some(weight) {
+                    *existing = None;
+                }
+            })
+            .or_insert(Some(weight));
+    }
+    group_weights
+        .into_iter()
+        .filter_map(|(edge, weight)| Some((edge, get_weight_style(weight?, display))))
+        .collect()
+}
+
+fn get_weight_style(weight: Complex, display: WeightDisplay) -> EdgeStyle {
+    match display {
+        WeightDisplay::Hidden => EdgeStyle::default(),
+        WeightDisplay::Labels => EdgeStyle {
+            label: Some(weight.format()),
+            ..EdgeStyle::default()
+        },
+        WeightDisplay::Thickness => {
+            let magnitude = weight.magnitude().min(1.);
+            EdgeStyle {
+                width: Some(MIN_WEIGHT_WIDTH + (MAX_WEIGHT_WIDTH - MIN_WEIGHT_WIDTH) * magnitude),
+                ..EdgeStyle::default()
+            }
+        }
+        WeightDisplay::Hue => EdgeStyle {
+            color: Some(get_hue_color(weight.phase() / (2. * PI))),
+            ..EdgeStyle::default()
+        },
+    }
+}
+
+/// Converts a hue between 0 and 1 to a saturated color
+fn get_hue_color(hue: f32) -> Color {
+    let (saturation, value) = (0.8, 0.85);
+    let sector = hue * 6.;
+    let frac = sector - sector.floor();
+    let (p, q, t) = (
+        value * (1. - saturation),
+        value * (1. - saturation * frac),
+        value * (1. - saturation * (1. - frac)),
+    );
+    match sector.floor() as i32 % 6 {
+        0 => Color(value, t, p),
+        1 => Color(q, value, p),
+        2 => Color(p, value, t),
+        3 => Color(p, q, value),
+        4 => Color(t, p, value),
+        _ => Color(value, p, q),
+    }
+}
+
+/// A matrix or vector represented by a node, of which the entries are stored row by row
+pub struct Matrix {
+    pub rows: usize,
+    pub columns: usize,
+    pub entries: Vec<Complex>,
+}
+
+/// Reconstructs the matrix over the given number of variables, truncated to at most max_dim rows and columns. The variables alternate between row and column bits, starting with the most significant row bit, such that an even number of variables forms a square matrix and an odd number forms a column vector. The entry for an assignment is obtained from evaluate, which receives the value of every variable in order.
+pub fn reconstruct_matrix(
+    variables: usize,
+    max_dim: usize,
+    mut evaluate: impl FnMut(&[bool]) -> Complex,
+) -> Matrix {
+    let is_vector = variables % 2 == 1;
+    let (row_bits, column_bits) = if is_vector {
+        (variables, 0)
+    } else {
+        (variables / 2, variables / 2)
+    };
+    let dim = |bits: usize| 1usize.checked_shl(bits as u32).unwrap_or(usize::MAX).min(max_dim);
+    let (rows, columns) = (dim(row_bits), dim(column_bits));
+    // Retrieves the bit at the given position counted from the most significant of the given bits
+    let bit = |value: usize, bits: usize, position: usize| {
+        value.checked_shr((bits - 1 - position) as u32).unwrap_or(0) & 1 == 1
+    };
+    let mut assignment = vec![false; variables];
+    let mut entries = Vec::with_capacity(rows * columns);
+    for row in 0..rows {
+        for column in 0..columns {
+            for (index, value) in assignment.iter_mut().enumerate() {
+                *value = if is_vector {
+                    bit(row, row_bits, index)
+                } else if index % 2 == 0 {
+                    bit(row, row_bits, index / 2)
+                } else {
+                    bit(column, column_bits, index / 2)
+                };
+            }
+            entries.push(evaluate(&assignment));
+        }
+    }
+    Matrix {
+        rows,
+        columns,
+        entries,
+    }
+}
@@ -1 +1,2 @@
+pub mod edge_weights;
 pub mod qdd_drawer;
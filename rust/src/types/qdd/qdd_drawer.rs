@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use oxidd_core::DiagramRules;
+use regex::Regex;
 use std::borrow::Borrow;
 use std::borrow::BorrowMut;
 use std::cell::RefCell;
@@ -27,24 +28,40 @@ use crate::configuration::types::choice_config::Choice;
 use crate::configuration::types::choice_config::ChoiceConfig;
 use crate::configuration::types::composite_config;
 use crate::configuration::types::composite_config::CompositeConfig;
+use crate::configuration::types::float_config::FloatConfig;
 use crate::configuration::types::int_config::IntConfig;
 use crate::configuration::types::label_config::LabelConfig;
+use crate::configuration::types::label_config::LabelStyle;
 use crate::configuration::types::location_config::Location;
 use crate::configuration::types::location_config::LocationConfig;
 use crate::configuration::types::panel_config::OpenSide;
 use crate::configuration::types::panel_config::PanelConfig;
 use crate::configuration::types::text_output_config::TextOutputConfig;
+use crate::interface::AppearanceAnimation;
+use crate::interface::HorizontalAlignment;
+use crate::interface::TargetID;
+use crate::interface::TargetIDType;
 use crate::traits::Diagram;
 use crate::traits::DiagramSection;
 use crate::traits::DiagramSectionDrawer;
 use crate::types::util::drawing::layouts::layer_orderings::edge_layer_ordering::EdgeLayerOrdering;
+use crate::types::util::drawing::renderers::util::edge_arrows::EdgeArrows;
+use crate::types::util::drawing::renderers::util::layer_label_sides::LayerLabelSides;
+use crate::types::util::drawing::renderers::util::layer_pairing::LayerPairing;
 use crate::types::util::drawing::renderers::webgl_renderer::LayerRenderingColorConfig;
 use crate::types::util::drawing::renderers::webgl_renderer::WebglLayerStyle;
 use crate::types::util::graph_structure::graph_manipulators::child_edge_adjuster::ChildEdgeAdjuster;
 use crate::types::util::graph_structure::graph_manipulators::edge_to_adjuster::EdgeToAdjuster;
 use crate::types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceGroups;
+use crate::types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresencePredicate;
 use crate::types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder;
+use crate::types::util::graph_structure::graph_manipulators::redundant_node_adjuster::RedundantNodeAdjuster;
 use crate::types::util::graph_structure::oxidd_graph_structure::NodeType;
+use crate::types::util::section_derivation::{cofactor_roots, quantify_roots};
+use crate::types::util::section_import::{
+    compact_levels, import_batch, normalize_import, register_variables, ImportManager,
+};
+use crate::types::util::structural_hasher::{get_terminal_key, StructuralHasher};
 use crate::util::color::Color;
 use crate::util::color::TransparentColor;
 use crate::util::dummy_bdd::DummyBDDEdge;
@@ -53,32 +70,58 @@ use crate::util::dummy_bdd::DummyBDDManager;
 use crate::util::dummy_bdd::DummyBDDManagerRef;
 use crate::util::dummy_bdd::DummyBDDNode;
 use crate::util::free_id_manager::FreeIdManager;
+use crate::util::graph_builder::GraphBuilder;
+use crate::util::import_batch::ImportBatch;
+use crate::util::import_batch::ImportFile;
+use crate::util::label_template::fill_label_template;
 use crate::util::logging::console;
+use crate::util::normalization::Normalization;
+use crate::util::point::Point;
 use crate::util::rc_refcell::MutRcRefCell;
 use crate::util::rectangle::Rectangle;
+use crate::util::variable_registry::VariableRegistry;
+use crate::util::viz_error::VizError;
 use crate::util::transition::Interpolatable;
+use crate::wasm_interface::AdjacencyData;
+use crate::wasm_interface::DuplicationData;
+use crate::wasm_interface::ElementsData;
+use crate::wasm_interface::EquivalenceData;
+use crate::wasm_interface::FocusData;
+use crate::wasm_interface::HistoryEntryData;
+use crate::wasm_interface::LayoutData;
+use crate::wasm_interface::LayoutMetricsData;
+use crate::wasm_interface::LevelInfoData;
+use crate::wasm_interface::NavigationDirection;
+use crate::wasm_interface::NodeDetailsData;
 use crate::wasm_interface::NodeGroupID;
 use crate::wasm_interface::NodeID;
+use crate::wasm_interface::PathData;
+use crate::wasm_interface::PathMetricsData;
+use crate::wasm_interface::Quantifier;
+use crate::wasm_interface::ReductionData;
+use crate::wasm_interface::RootSorting;
+use crate::wasm_interface::SelectionOperation;
 use crate::wasm_interface::StepData;
-use crate::wasm_interface::TargetID;
-use crate::wasm_interface::TargetIDType;
+use crate::wasm_interface::ValidationWarning;
 use oxidd::bdd::BDDFunction;
 use oxidd::util::Borrowed;
 use oxidd::BooleanFunction;
 use oxidd::Edge;
 use oxidd::Function;
 use oxidd::InnerNode;
+use oxidd::LevelNo;
 use oxidd::{Manager, ManagerRef};
 use oxidd_core::HasApplyCache;
 use oxidd_core::HasLevel;
 use oxidd_core::Node;
 use oxidd_core::{util::DropWith, Tag};
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlCanvasElement, WebGl2RenderingContext};
+use web_sys::{HtmlCanvasElement, WebGl2RenderingContext, WebGlFramebuffer};
 
+use super::super::util::drawing::diagram_layout::LabelPlacement;
 use super::super::util::drawing::diagram_layout::LayerStyle;
 use super::super::util::drawing::diagram_layout::NodeStyle;
-use super::super::util::drawing::drawer::Drawer;
+use super::super::util::drawing::drawer::{Drawer, SectionGeometry, ViewID};
 use super::super::util::drawing::layout_rules::LayoutRules;
 use super::super::util::drawing::layouts::layer_group_sorting::average_group_alignment::AverageGroupAlignment;
 use super::super::util::drawing::layouts::layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment;
@@ -89,6 +132,8 @@ use super::super::util::drawing::layouts::layer_orderings::sugiyama_ordering::Su
 use super::super::util::drawing::layouts::layer_positionings::brandes_kopf_positioning::BrandesKopfPositioning;
 use super::super::util::drawing::layouts::layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected;
 use super::super::util::drawing::layouts::layer_positionings::dummy_layer_positioning::DummyLayerPositioning;
+use super::super::util::drawing::layouts::layer_positionings::pinned_positioning::get_pin_positions;
+use super::super::util::drawing::layouts::layer_positionings::pinned_positioning::PinnedPositioning;
 use super::super::util::drawing::layouts::layered_layout::LayeredLayout;
 use super::super::util::drawing::layouts::layered_layout_traits::WidthLabel;
 use super::super::util::drawing::layouts::random_test_layout::RandomTestLayout;
@@ -97,15 +142,41 @@ use super::super::util::drawing::layouts::toggle_layout::IndexedSelect;
 use super::super::util::drawing::layouts::toggle_layout::ToggleLayout;
 use super::super::util::drawing::layouts::toggle_layout::ToggleLayoutUnit;
 use super::super::util::drawing::layouts::transition::transition_layout::TransitionLayout;
-use super::super::util::drawing::renderer::Renderer;
+use super::super::util::drawing::legend::get_variable_entries;
+use super::super::util::drawing::legend::Legend;
+use super::super::util::drawing::legend::LegendEntry;
+use super::super::util::drawing::legend::LegendSymbol;
+use super::super::util::drawing::node_metric::NodeMetric;
+use super::super::util::drawing::pagination::paginate;
+use super::edge_weights::get_weight_styles;
+use super::edge_weights::reconstruct_matrix;
+use super::edge_weights::Complex;
+use super::edge_weights::Matrix;
+use super::edge_weights::NodeEdgeID;
+use super::edge_weights::WeightDisplay;
+use super::super::util::drawing::pagination::Page;
+use super::super::util::drawing::pagination::PaperSize;
+use super::super::util::drawing::annotations::Annotation;
+use super::super::util::drawing::annotations::AnnotationID;
+use super::super::util::drawing::annotations::AnnotationKind;
+use super::super::util::drawing::annotations::get_edge_stub_delta;
+use super::super::util::drawing::annotations::STUB_ELLIPSIS;
+use super::super::util::drawing::annotations::STUB_LENGTH;
+use super::super::util::drawing::renderer::EdgeStyle;
+use super::super::util::drawing::renderer::GroupEdgeID;
+use super::super::util::drawing::renderer::DiagramRenderer;
 use super::super::util::drawing::renderers::latex_renderer::LatexLayerStyle;
 use super::super::util::drawing::renderers::latex_renderer::LatexNodeStyle;
 use super::super::util::drawing::renderers::latex_renderer::LatexRenderer;
+use super::super::util::drawing::renderers::svg_renderer::SvgRenderer;
 use super::super::util::drawing::renderers::util::Font::Font;
 use super::super::util::drawing::renderers::webgl::edge_renderer::EdgeRenderingType;
 use super::super::util::drawing::renderers::webgl::node_renderer::NodeRenderingColorConfig;
+use super::super::util::drawing::renderers::webgl_renderer::RenderSurface;
 use super::super::util::drawing::renderers::webgl_renderer::WebglNodeStyle;
-use super::super::util::drawing::renderers::webgl_renderer::WebglRenderer;
+use super::super::util::drawing::renderers::screen_renderer::ScreenRenderer;
+#[cfg(feature = "webgpu")]
+use super::super::util::drawing::renderers::webgpu_renderer::WebgpuContext;
 use super::super::util::graph_structure::graph_manipulators::group_presence_adjuster::GroupPresenceAdjuster;
 use super::super::util::graph_structure::graph_manipulators::label_adjusters::group_label_adjuster::GroupLabelAdjuster;
 use super::super::util::graph_structure::graph_manipulators::node_presence_adjuster::NodePresenceAdjuster;
@@ -119,8 +190,13 @@ use super::super::util::graph_structure::grouped_graph_structure::GroupedGraphSt
 use super::super::util::graph_structure::oxidd_graph_structure::NodeLabel;
 use super::super::util::graph_structure::oxidd_graph_structure::OxiddGraphStructure;
 use super::super::util::group_manager::GroupManager;
+use super::super::util::group_manager::InitialReveal;
+use super::super::util::view_patch::ViewPatch;
+use super::super::util::view_patch::ViewPatchGroup;
+use super::super::util::root_order::get_sorted_roots;
 use super::super::util::storage::state_storage::Serializable;
 use super::super::util::storage::state_storage::StateStorage;
+use super::super::util::storage::state_storage::{read_state_header, write_state_header};
 
 // The drawers for QDD and BDD decision diagrams
 // Note that we should eventually add reusable helper structure to reduce the perceived complexity of the entries to different diagram visualization implementations
@@ -129,43 +205,313 @@ where
     for<'id> <<MR as oxidd::ManagerRef>::Manager<'id> as Manager>::InnerNode: HasLevel,
 {
     manager_ref: MR,
+    /// Whether common defects of imported dumps are fixed rather than drawn as they are
+    repair_imports: bool,
+    /// Whether the nodes of imported dumps are numbered by a canonical traversal rather than by the ids of the dump
+    canonical_import_ids: bool,
+    /// The normal form into which imported graphs are brought
+    import_normalization: Normalization,
+    /// Whether the levels without any imported node are skipped, rather than drawn as empty layers
+    compact_imported_levels: bool,
+    /// Whether the levels of imported sections are numbered by the shared variable order
+    unify_levels: bool,
+    /// The variables of all imported sections, by which the levels are unified
+    variables: VariableRegistry,
+    /// The structural hashes of the nodes of the manager that equivalence checks compared, which are kept until the next import
+    equivalence_hasher: RefCell<Option<StructuralHasher<BaseGraph>>>,
 }
 impl QDDDiagram<DummyBDDManagerRef> {
     pub fn new() -> QDDDiagram<DummyBDDManagerRef> {
         let manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
-        QDDDiagram { manager_ref }
+        QDDDiagram {
+            manager_ref,
+            repair_imports: false,
+            canonical_import_ids: false,
+            import_normalization: Normalization::Verbatim,
+            compact_imported_levels: false,
+            unify_levels: false,
+            variables: VariableRegistry::new(),
+            equivalence_hasher: RefCell::new(None),
+        }
+    }
+}
+
+impl QDDDiagram<DummyBDDManagerRef> {
+    fn section_from_ids(
+        &self,
+        sources: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
+    ) -> QDDDiagramSection<DummyBDDFunction> {
+        let mut levels = Vec::new();
+        let roots = sources
+            .iter()
+            .map(|&(id, section)| {
+                let root_edge = DummyBDDEdge::new(Arc::new(id), self.manager_ref.clone());
+                levels = section.get_level_labels();
+                (DummyBDDFunction(root_edge), section.get_node_labels(id))
+            })
+            .collect_vec();
+        QDDDiagramSection::new(roots, levels)
+    }
+
+    /// Retrieves the nodes of the manager before an import, which only these nodes are known by afterwards. The hashes of equivalence checks are dropped, since imports may replace nodes and add levels.
+    fn prepare_import(&mut self) -> HashSet<NodeID> {
+        *self.equivalence_hasher.get_mut() = None;
+        self.manager_ref.get_node_ids()
+    }
+
+    /// Renumbers the levels of the nodes that were imported for the given section, which are all nodes except for the given known nodes, such that levels without any of these nodes are skipped if level compaction is enabled. The section keeps the level that each of its levels had in the dump.
+    fn compact_levels(
+        &self,
+        section: &mut QDDDiagramSection<DummyBDDFunction>,
+        known: &HashSet<NodeID>,
+    ) {
+        if self.compact_imported_levels {
+            let (levels, true_levels) = (&mut section.levels, &mut section.true_levels);
+            compact_levels(&self.manager_ref, known, levels, true_levels);
+        }
+    }
+
+    /// Brings the graph of the given imported functions, which consists of all nodes except for the given known nodes, into the normal form of imports by the reduction rules of BDDs, see `normalize_import`
+    fn normalize_import(
+        &self,
+        roots: Vec<(DummyBDDFunction, Vec<String>)>,
+        known: &HashSet<NodeID>,
+    ) -> (Vec<(DummyBDDFunction, Vec<String>)>, Option<ReductionData>) {
+        normalize_import(&self.manager_ref, roots, known, self.import_normalization)
+    }
+
+    /// Registers the variables of the given section, and numbers the levels of the nodes that were imported for it, which are all nodes except for the given known nodes, by their canonical levels if levels are unified
+    fn register_variables(
+        &mut self,
+        section: &mut QDDDiagramSection<DummyBDDFunction>,
+        known: &HashSet<NodeID>,
+    ) {
+        register_variables(
+            &self.manager_ref,
+            known,
+            &mut self.variables,
+            self.unify_levels,
+            &mut section.levels,
+            &mut section.warnings,
+        );
     }
 }
 
 impl Diagram for QDDDiagram<DummyBDDManagerRef> {
-    fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<Box<dyn DiagramSection>> {
-        let (roots, levels) = DummyBDDFunction::from_dddmp(&mut self.manager_ref, &dddmp);
-        Some(Box::new(QDDDiagramSection::new(roots, levels)))
+    fn set_import_repair(&mut self, repair: bool) {
+        self.repair_imports = repair;
+    }
+    fn set_import_canonical_ids(&mut self, canonical: bool) {
+        self.canonical_import_ids = canonical;
+    }
+    fn set_import_normalization(&mut self, normalization: Normalization) {
+        self.import_normalization = normalization;
+    }
+    fn set_level_compaction(&mut self, compact: bool) {
+        self.compact_imported_levels = compact;
+    }
+    fn set_level_unification(&mut self, unify: bool) {
+        self.unify_levels = unify;
+    }
+    fn get_variables(&self) -> &VariableRegistry {
+        &self.variables
+    }
+    fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (roots, levels, repairs) =
+            DummyBDDFunction::from_dddmp(
+                &mut self.manager_ref,
+                &dddmp,
+                self.repair_imports,
+                self.canonical_import_ids,
+            )
+            .map_err(VizError::ParseError)?;
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = QDDDiagramSection::new(roots, levels);
+        section.warnings = self.manager_ref.validate(&section.roots, &known);
+        section.repairs = repairs;
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
     }
     // Other == Buddy
     fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<Box<dyn DiagramSection>> {
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let known = self.prepare_import();
         let (roots, levels) =
             DummyBDDFunction::from_buddy(&mut self.manager_ref, &data, vars.as_deref());
-        Some(Box::new(QDDDiagramSection::new(roots, levels)))
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = QDDDiagramSection::new(roots, levels);
+        section.warnings = self.manager_ref.validate(&section.roots, &known);
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
+    }
+    fn create_section_from_batch(
+        &mut self,
+        batch: &ImportBatch,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (repair, canonical_ids) = (self.repair_imports, self.canonical_import_ids);
+        let import = import_batch(
+            &self.manager_ref,
+            batch,
+            self.import_normalization,
+            |index, file, manager_ref| match file {
+                ImportFile::Dddmp(dddmp) => {
+                    DummyBDDFunction::from_dddmp(manager_ref, dddmp, repair, canonical_ids)
+                        .map_err(|error| {
+                            VizError::ParseError(format!("File {}: {}", index, error))
+                        })
+                }
+                ImportFile::Buddy { data, vars } => {
+                    let (roots, levels) =
+                        DummyBDDFunction::from_buddy(manager_ref, data, vars.as_deref());
+                    Ok((roots, levels, Vec::new()))
+                }
+            },
+        )?;
+        let mut section = QDDDiagramSection::new(import.roots, import.levels);
+        section.warnings = import.warnings;
+        section.repairs = import.repairs;
+        section.reduction = import.reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
+    }
+    fn create_section_from_expression(
+        &mut self,
+        expression: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        Err(VizError::UnsupportedFormat(
+            "Arithmetic expressions can only be used for MTBDDs".into(),
+        ))
+    }
+    fn create_section_from_family(
+        &mut self,
+        family: String,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        // There is no dedicated ZDD type yet, the BDD drawer shows the structure of the ZDD
+        let known = self.prepare_import();
+        let (roots, levels) = DummyBDDFunction::from_family(&mut self.manager_ref, &family);
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = QDDDiagramSection::new(roots, levels);
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
+    }
+    fn create_section_from_graph(
+        &mut self,
+        graph: &GraphBuilder,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let known = self.prepare_import();
+        let (roots, levels) = DummyBDDFunction::from_builder(&mut self.manager_ref, graph);
+        let (roots, reduction) = self.normalize_import(roots, &known);
+        let mut section = QDDDiagramSection::new(roots, levels);
+        section.warnings = self.manager_ref.validate(&section.roots, &known);
+        section.reduction = reduction;
+        self.compact_levels(&mut section, &known);
+        self.register_variables(&mut section, &known);
+        Ok(Box::new(section))
     }
     fn create_section_from_ids(
         &self,
         sources: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
-    ) -> Option<Box<dyn DiagramSection>> {
-        let mut levels = Vec::new();
-        let roots = sources
-            .iter()
-            .map(|&(id, section)| {
-                let root_edge = DummyBDDEdge::new(Arc::new(id), self.manager_ref.clone());
-                levels = section.get_level_labels();
-                (DummyBDDFunction(root_edge), section.get_node_labels(id))
-            })
-            .collect_vec();
-        Some(Box::new(QDDDiagramSection::new(roots, levels)))
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        Ok(Box::new(self.section_from_ids(sources)))
+    }
+    fn create_preview_section_from_ids(
+        &self,
+        sources: &[(oxidd::NodeID, &Box<dyn DiagramSection>)],
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Result<Box<dyn DiagramSection>, VizError> {
+        let mut section = self.section_from_ids(sources);
+        section.reveal = InitialReveal::Bounded {
+            max_depth,
+            max_nodes,
+        };
+        Ok(Box::new(section))
+    }
+
+    fn check_equivalence(
+        &self,
+        nodes_a: &[oxidd::NodeID],
+        nodes_b: &[oxidd::NodeID],
+    ) -> Result<EquivalenceData, VizError> {
+        if nodes_a.len() != nodes_b.len() {
+            return Err(VizError::UnsupportedFormat(
+                "Only lists of the same length can be compared pairwise".to_string(),
+            ));
+        }
+        let function = |id: oxidd::NodeID| {
+            DummyBDDFunction(DummyBDDEdge::new(Arc::new(id), self.manager_ref.clone()))
+        };
+        // Structurally identical subfunctions are equivalent, hence their search is skipped. The levels are labeled by their number, as the functions share the levels of the manager.
+        let mut cached_hasher = self.equivalence_hasher.borrow_mut();
+        let hasher = cached_hasher.get_or_insert_with(|| {
+            let level_count = self
+                .manager_ref
+                .get_levels(&HashSet::new())
+                .last()
+                .map_or(0, |&level| level + 1);
+            let labels = (0..level_count).map(|level| level.to_string()).collect();
+            StructuralHasher::new(BaseGraph::new(Vec::new(), labels), get_terminal_key)
+        });
+        for (&a, &b) in nodes_a.iter().zip(nodes_b) {
+            let (a, b) = (function(a), function(b));
+            hasher.get_graph().discover(&a);
+            hasher.get_graph().discover(&b);
+            let difference = a
+                .find_difference(&b, &mut |a, b| hasher.is_identical(a, b))
+                .map_err(VizError::UnsupportedFormat)?;
+            if let Some(witness) = difference {
+                return Ok(EquivalenceData::different(
+                    &witness,
+                    a.follow_assignment(&witness),
+                    b.follow_assignment(&witness),
+                ));
+            }
+        }
+        Ok(EquivalenceData::equivalent())
+    }
+
+    fn find_path_to_terminal(
+        &self,
+        root: oxidd::NodeID,
+        terminal: String,
+        max_count: usize,
+    ) -> Vec<PathData> {
+        let root = DummyBDDFunction(DummyBDDEdge::new(
+            Arc::new(root),
+            self.manager_ref.clone(),
+        ));
+        root.find_paths_to(&terminal, max_count)
+            .into_iter()
+            .map(|(assignment, nodes)| PathData::new(&assignment, nodes))
+            .collect()
+    }
+
+    fn get_path_metrics(&self, root: oxidd::NodeID) -> PathMetricsData {
+        let root = DummyBDDFunction(DummyBDDEdge::new(
+            Arc::new(root),
+            self.manager_ref.clone(),
+        ));
+        let (shortest_assignment, shortest_nodes) = root.find_extreme_path(false);
+        let (longest_assignment, longest_nodes) = root.find_extreme_path(true);
+        PathMetricsData::new(
+            PathData::new(&shortest_assignment, shortest_nodes),
+            PathData::new(&longest_assignment, longest_nodes),
+        )
     }
 }
 
@@ -176,6 +522,16 @@ where
     roots: Vec<(F, Vec<String>)>,
     labels: HashMap<NodeID, Vec<String>>,
     levels: Vec<String>,
+    /// The nodes that drawers of this section reveal initially
+    reveal: InitialReveal,
+    /// The structural problems found when importing the graph of this section
+    warnings: Vec<ValidationWarning>,
+    /// The fixes applied to the dump this section was imported from
+    repairs: Vec<String>,
+    /// The sizes of the imported graph before and after it was normalized on import, if it was normalized
+    reduction: Option<ReductionData>,
+    /// The level in the dump of every level of this section, if levels were compacted on import
+    true_levels: Vec<LevelNo>,
 }
 
 impl<F: Function> QDDDiagramSection<F>
@@ -195,8 +551,13 @@ where
                 .collect(),
             roots,
             levels,
+            reveal: InitialReveal::Default,
+            warnings: Vec::new(),
+            repairs: Vec::new(),
+            reduction: None,
+            true_levels: Vec::new(),
         };
-        console::log!(
+        console::log_debug!(
             "init {}",
             s.labels
                 .iter()
@@ -216,6 +577,7 @@ struct QDDColors {
     node_false: Color,
     node_group: Color,
     node_default: Color,
+    node_redundant: Color,
     node_text: Color,
     node_label: Color,
     layer_background1: Color,
@@ -235,6 +597,7 @@ impl QDDColors {
         node_false: Color(0.835, 0.341, 0.341),
         node_group: Color(0.45, 0.45, 0.45),
         node_default: Color(0.35, 0.35, 0.35),
+        node_redundant: Color(0.7, 0.55, 0.2),
         node_text: Color(0.0, 0.0, 0.0),
         node_label: Color(0.5, 0.5, 1.0),
         layer_background1: Color(0.125, 0.125, 0.125),
@@ -254,6 +617,7 @@ impl QDDColors {
         node_false: Color(1.0, 0.2, 0.2),
         node_group: Color(0.45, 0.45, 0.45),
         node_default: Color(0.1, 0.1, 0.1),
+        node_redundant: Color(0.6, 0.45, 0.1),
         node_text: Color(0.0, 0.0, 0.0),
         node_label: Color(0.5, 0.5, 1.0),
         layer_background1: Color(0.98, 0.98, 0.98),
@@ -270,16 +634,77 @@ impl DiagramSection for QDDDiagramSection<DummyBDDFunction> {
     fn get_level_labels(&self) -> Vec<String> {
         self.levels.clone()
     }
+    fn set_level_label(&mut self, level: LevelNo, name: String) {
+        let index = level as usize;
+        if self.levels.len() <= index {
+            self.levels.resize(index + 1, String::new());
+        }
+        self.levels[index] = name;
+    }
+    fn get_level_info(&self) -> Vec<LevelInfoData> {
+        let mut visited = HashSet::new();
+        let mut counts = HashMap::new();
+        for (root, _) in &self.roots {
+            root.count_level_nodes(&mut visited, &mut counts);
+        }
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let node_count = counts.get(&(index as LevelNo)).cloned().unwrap_or(0);
+                LevelInfoData {
+                    name: name.clone(),
+                    index: index as LevelNo,
+                    in_support: node_count > 0,
+                    node_count,
+                }
+            })
+            .collect()
+    }
     fn get_node_labels(&self, node: NodeID) -> Vec<String> {
         self.labels.get(&node).cloned().unwrap_or_else(|| vec![])
     }
+    fn get_warnings(&self) -> Vec<ValidationWarning> {
+        self.warnings.clone()
+    }
+    fn get_repairs(&self) -> Vec<String> {
+        self.repairs.clone()
+    }
+    fn get_reduction(&self) -> Option<ReductionData> {
+        self.reduction.clone()
+    }
+    fn get_true_levels(&self) -> Vec<LevelNo> {
+        self.true_levels.clone()
+    }
     fn create_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
         let graph =
             OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
 
-        let diagram = QDDDiagramDrawer::new(graph, canvas);
+        let diagram = QDDDiagramDrawer::new(graph, canvas, self.reveal);
+        *diagram.true_levels.get() = self.true_levels.clone();
+        Box::new(diagram)
+    }
+    fn create_lazy_drawer(&self, canvas: HtmlCanvasElement) -> Box<dyn DiagramSectionDrawer> {
+        let graph =
+            OxiddGraphStructure::new(self.roots.iter().cloned().collect(), self.levels.clone());
+
+        let diagram = QDDDiagramDrawer::new(graph, canvas, InitialReveal::Lazy);
+        *diagram.true_levels.get() = self.true_levels.clone();
         Box::new(diagram)
     }
+    fn preview_cofactor(&self, var: LevelNo, value: bool) -> Option<Box<dyn DiagramSection>> {
+        let roots = cofactor_roots::<DummyBDDManagerRef>(&self.roots, &self.levels, var, value);
+        Some(Box::new(QDDDiagramSection::new(roots, self.levels.clone())))
+    }
+    fn preview_quantify(
+        &self,
+        vars: &[LevelNo],
+        quantifier: Quantifier,
+    ) -> Option<Box<dyn DiagramSection>> {
+        let roots =
+            quantify_roots::<DummyBDDManagerRef>(&self.roots, &self.levels, vars, quantifier)?;
+        Some(Box::new(QDDDiagramSection::new(roots, self.levels.clone())))
+    }
 }
 
 #[derive(Clone)]
@@ -288,6 +713,7 @@ pub struct NodeData {
     border_color: TransparentColor,
     width: f32,
     name: Option<String>,
+    label_size: Option<Point>,
     is_terminal: Option<usize>,
     is_group: bool,
 }
@@ -299,6 +725,7 @@ impl Interpolatable for NodeData {
             border_color: self.border_color.mix(&other.border_color, frac),
             width: self.width * (1.0 - frac) + other.width * frac,
             name: other.name.clone(),
+            label_size: other.label_size,
             is_terminal: other.is_terminal.clone(),
             is_group: other.is_group,
         }
@@ -334,6 +761,10 @@ impl WidthLabel for NodeData {
     fn get_width(&self) -> f32 {
         self.width
     }
+
+    fn get_label_size(&self) -> Option<Point> {
+        self.label_size
+    }
 }
 impl NodeStyle for NodeData {}
 
@@ -373,11 +804,17 @@ type PresenceAdjuster = RCGraph<
     NodePresenceAdjuster<
         RCGraph<
             EdgeToAdjuster<
-                RCGraph<ChildEdgeAdjuster<PointerNodeAdjuster<TerminalLevelAdjuster<BaseGraph>>>>,
+                RCGraph<
+                    ChildEdgeAdjuster<
+                        PointerNodeAdjuster<TerminalLevelAdjuster<RedundancyAdjuster>>,
+                    >,
+                >,
             >,
         >,
     >,
 >;
+type RedundancyAdjuster = RCGraph<RedundantNodeAdjuster<RCBaseGraph>>;
+type RCBaseGraph = RCGraph<BaseGraph>;
 type BaseGraph = OxiddGraphStructure<(), DummyBDDFunction, String>;
 type Layout = TransitionLayout<ToggleLayout<Layout1, ToggleLayoutUnit<Layout2>>>;
 type Layout1 = LayeredLayout<
@@ -388,7 +825,7 @@ type Layout1 = LayeredLayout<
         SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
     >,
     OrderingGroupAlignment,
-    BrandesKopfPositioningCorrected,
+    PinnedPositioning<BrandesKopfPositioningCorrected>,
 >;
 type Layout2 = LayeredLayout<
     GroupedGraph,
@@ -398,15 +835,66 @@ type Layout2 = LayeredLayout<
         SequenceOrdering<GroupedGraph, EdgeLayerOrdering, SugiyamaOrdering>,
     >,
     OrderingGroupAlignment,
-    BrandesKopfPositioning,
+    PinnedPositioning<BrandesKopfPositioning>,
 >;
 
+/// The zoom scale (in pixels per unit) below which subfunctions are summarized by default
+const DEFAULT_SEMANTIC_ZOOM_THRESHOLD: f32 = 2.;
+/// The number of levels below the top root that stay detailed when subfunctions are summarized
+const SEMANTIC_ZOOM_DETAIL_LEVELS: LevelNo = 2;
+/// The number of groups that a layer may show by default while zoomed out further than the default scale (in pixels per unit), before runs of adjacent groups are summarized
+const DEFAULT_LAYER_SUMMARY_BUDGET: usize = 1000;
+const DEFAULT_LAYER_SUMMARY_THRESHOLD: f32 = 4.;
+
 pub struct QDDDiagramDrawer {
     graph: Graph,
     group_manager: MutRcRefCell<GroupManager<Graph>>,
     presence_adjuster: PresenceAdjuster,
+    /// The graph of the section, which holds the labels of the levels
+    source_graph: RCBaseGraph,
+    /// Detects the redundant nodes of the section, and bypasses them if enabled
+    redundancy_adjuster: RedundancyAdjuster,
+    /// Whether the connections to nodes hidden by the presence adjuster are marked
+    hidden_markers: bool,
+    /// Whether the edges to nodes hidden by the presence adjuster are drawn as stubs
+    hidden_edge_stubs: bool,
     time: MutRcRefCell<u32>,
-    drawer: MutRcRefCell<Drawer<WebglRenderer<()>, Layout, GroupedGraph>>,
+    drawer: MutRcRefCell<Drawer<ScreenRenderer<()>, Layout, GroupedGraph>>,
+    svg_renderer: MutRcRefCell<SvgRenderer<Layout>>,
+    latex_renderer: MutRcRefCell<LatexRenderer<Layout>>,
+    /// Creates the renderers of additional views of the section
+    create_renderer: Box<dyn Fn(RenderSurface) -> ScreenRenderer<()>>,
+    grouped_graph: MutRcRefCell<GroupedGraph>,
+    /// The path of groups that were entered, the last of which is currently shown
+    entered_groups: Vec<NodeGroupID>,
+    /// The zoom scale below which subfunctions are summarized, and whether they currently are
+    semantic_zoom_threshold: f32,
+    summarized: bool,
+    /// The number of groups that a layer may show while zoomed out further than the threshold, and the groups summarizing the runs of larger layers if they are shown
+    layer_summary_budget: usize,
+    layer_summary_threshold: f32,
+    layer_summaries: Option<Vec<NodeGroupID>>,
+    /// Whether the exports include a legend
+    export_legend: MutRcRefCell<bool>,
+    /// The template of the labels of inner nodes, which are not labeled if there is none
+    label_template: MutRcRefCell<Option<String>>,
+    /// The level in the dump of every level of the section, if levels were compacted on import
+    true_levels: MutRcRefCell<Vec<LevelNo>>,
+    /// Whether the labels of the layers show the levels in the dump that were compacted
+    show_true_levels: MutRcRefCell<bool>,
+    /// The colors of the variables, by which inner nodes are outlined
+    variable_colors: MutRcRefCell<Vec<(String, Color)>>,
+    /// The values of source nodes provided externally, which are drawn as a color overlay
+    node_metric: MutRcRefCell<NodeMetric>,
+    /// The satisfying assignment counts and supports of the source nodes whose details were requested
+    details_cache: HashMap<NodeID, (f64, Vec<LevelNo>)>,
+    /// Computes the structural hashes of the subfunctions of the source diagram
+    structural_hasher: StructuralHasher<RCBaseGraph>,
+    /// The serialized state of the last successful layout, which the section is reset to after an internal error
+    consistent_state: Option<Vec<u8>>,
+    /// The weights of individual edges provided by the user, and how they are shown
+    edge_weights: MutRcRefCell<HashMap<NodeEdgeID, Complex>>,
+    weight_display: ChoiceConfig<WeightDisplay>,
     config: Configuration<
         LocationConfig<
             PanelConfig<
@@ -421,6 +909,31 @@ pub struct QDDDiagramDrawer {
                     ButtonConfig,
                     TextOutputConfig,
                     ButtonConfig,
+                    LabelConfig<ChoiceConfig<bool>>,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<LayerLabelSides>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                            LabelConfig<ChoiceConfig<LayerPairing>>,
+                        )>,
+                    >,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<LabelPlacement>>,
+                            LabelConfig<ChoiceConfig<bool>>,
+                        )>,
+                    >,
+                    LabelConfig<
+                        CompositeConfig<(
+                            LabelConfig<ChoiceConfig<EdgeArrows>>,
+                            LabelConfig<FloatConfig>,
+                            LabelConfig<FloatConfig>,
+                            LabelConfig<FloatConfig>,
+                            LabelConfig<ChoiceConfig<WeightDisplay>>,
+                        )>,
+                    >,
                 )>,
             >,
         >,
@@ -428,7 +941,7 @@ pub struct QDDDiagramDrawer {
 }
 
 impl QDDDiagramDrawer {
-    pub fn new(graph: BaseGraph, canvas: HtmlCanvasElement) -> Self {
+    pub fn new(graph: BaseGraph, canvas: HtmlCanvasElement, reveal: InitialReveal) -> Self {
         let colors = &QDDColors::LIGHT;
         let edge_rendering_type =
             |color: Color, width: f32, dash_solid: f32, dash_transparent: f32| EdgeRenderingType {
@@ -445,9 +958,7 @@ impl QDDDiagramDrawer {
             include_bytes!("../../../resources/Roboto-Bold.ttf").to_vec(),
             1.0,
         ));
-        let renderer = WebglRenderer::from_canvas(
-            canvas,
-            HashMap::from([
+        let edge_types = HashMap::from([
                 // True edge
                 (
                     EdgeType::new((), 0),
@@ -468,22 +979,37 @@ impl QDDDiagramDrawer {
                     EdgeType::new((), 2),
                     edge_rendering_type(colors.edge_both, 0.15, 1.0, 0.0),
                 ),
-            ]),
-            NodeRenderingColorConfig {
-                select: colors.selection,
-                partial_select: colors.selection_partial,
-                hover: colors.selection_hover,
-                partial_hover: colors.selection_hover_partial,
-                text: colors.node_text,
-            },
-            LayerRenderingColorConfig {
-                background1: colors.layer_background1.into(),
-                background2: colors.layer_background2.into(),
-                text: colors.layer_text,
-            },
-            font.clone(),
-        )
-        .unwrap();
+            ]);
+        let layer_colors = LayerRenderingColorConfig {
+            background1: colors.layer_background1.into(),
+            background2: colors.layer_background2.into(),
+            text: colors.layer_text,
+        };
+        let svg_renderer = MutRcRefCell::new(SvgRenderer::new(
+            edge_types.clone(),
+            layer_colors.clone(),
+            colors.node_text,
+            font.text_size(),
+        ));
+        let node_colors = NodeRenderingColorConfig {
+            select: colors.selection,
+            partial_select: colors.selection_partial,
+            hover: colors.selection_hover,
+            partial_hover: colors.selection_hover_partial,
+            text: colors.node_text,
+        };
+        let renderer_font = font.clone();
+        let create_renderer = move |surface| {
+            ScreenRenderer::from_surface(
+                surface,
+                edge_types.clone(),
+                node_colors.clone(),
+                layer_colors.clone(),
+                renderer_font.clone(),
+            )
+            .unwrap()
+        };
+        let renderer = create_renderer(RenderSurface::Canvas(canvas));
 
         let layout_opt1: Layout1 = LayeredLayout::new(
             // SugiyamaOrdering::new(2, 2),
@@ -494,7 +1020,7 @@ impl QDDDiagramDrawer {
             // AverageGroupAlignment,
             OrderingGroupAlignment,
             // BrandesKopfPositioning,
-            BrandesKopfPositioningCorrected,
+            PinnedPositioning::new(BrandesKopfPositioningCorrected),
             // DummyLayerPositioning,
             0.3,
         );
@@ -507,21 +1033,30 @@ impl QDDDiagramDrawer {
             // AverageGroupAlignment,
             OrderingGroupAlignment,
             // BrandesKopfPositioning,
-            BrandesKopfPositioning,
+            PinnedPositioning::new(BrandesKopfPositioning),
             // DummyLayerPositioning,
             0.1,
         );
         let layout = ToggleLayout::new(layout_opt1, ToggleLayoutUnit::new(layout_opt2));
         let layout: Layout = TransitionLayout::new(layout);
 
-        let original_roots = graph.get_roots().clone();
-        let base_graph = TerminalLevelAdjuster::new(graph); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
+        let source_graph = RCGraph::new(graph);
+        let structural_hasher =
+            StructuralHasher::new(source_graph.clone(), get_terminal_key);
+        let original_roots = source_graph.get_roots().clone();
+        let redundancy_adjuster = RCGraph::new(RedundantNodeAdjuster::new(source_graph.clone()));
+        let base_graph = TerminalLevelAdjuster::new(redundancy_adjuster.clone()); // Make sure that terminal levels make sense before possibly adding pointers to these terminals
         let pointer_adjuster = PointerNodeAdjuster::new(
             base_graph,
             EdgeType { tag: (), index: 2 },
             true,
             "".to_string(),
         );
+        // Redundant nodes are styled by their id below the presence adjuster, which does not change when they are bypassed
+        let redundant_nodes: HashSet<NodeID> = pointer_adjuster
+            .source_nodes_to_local(redundancy_adjuster.get().get_redundant_nodes())
+            .into_iter()
+            .collect();
         let child_edge_adjuster =
             RCGraph::new(ChildEdgeAdjuster::new(pointer_adjuster, move_shared_edge));
         let edge_to_adjuster = RCGraph::new(EdgeToAdjuster::new(child_edge_adjuster.clone()));
@@ -532,9 +1067,20 @@ impl QDDDiagramDrawer {
         let roots = modified_graph.get_roots();
         let group_manager = MutRcRefCell::new(GroupManager::new(modified_graph.clone()));
 
+        let named_groups = group_manager.clone();
+        let label_template = MutRcRefCell::new(None::<String>);
+        let true_levels = MutRcRefCell::new(Vec::<LevelNo>::new());
+        let show_true_levels = MutRcRefCell::new(false);
+        let layer_true_levels = true_levels.clone();
+        let layer_show_true_levels = show_true_levels.clone();
+        let node_label_template = label_template.clone();
+        let variable_colors = MutRcRefCell::new(Vec::<(String, Color)>::new());
+        let node_variable_colors = variable_colors.clone();
+        let node_metric = MutRcRefCell::new(NodeMetric::new());
+        let node_metric_ref = node_metric.clone();
         let mut grouped_graph = GroupPresenceAdjuster::new(GroupLabelAdjuster::new_shared(
             group_manager.clone(),
-            move |nodes| {
+            move |group, nodes| {
                 // TODO: make this adjuster lazy, e.g. don't recompute for the same list of nodes
                 let (is_terminal, is_group, color) = match (nodes.get(0), nodes.get(1)) {
                     (
@@ -561,6 +1107,11 @@ impl QDDDiagramDrawer {
                         }),
                         None,
                     ) => (None, false, colors.node_label),
+                    (Some(&PresenceLabel { original_id, .. }), None)
+                        if redundant_nodes.contains(&original_id) =>
+                    {
+                        (None, false, colors.node_redundant)
+                    }
                     (Some(_), None) => (None, false, colors.node_default),
                     _ => (None, true, colors.node_group),
                 };
@@ -572,28 +1123,97 @@ impl QDDDiagramDrawer {
                         }),
                         None,
                     ) => Some(text.clone()),
+                    (
+                        Some(&PresenceLabel {
+                            original_label:
+                                PointerLabel::Node(NodeLabel {
+                                    ref pointers,
+                                    kind: NodeType::Inner(ref id),
+                                }),
+                            original_id: _,
+                        }),
+                        None,
+                    ) => node_label_template.read().as_ref().map(|template| {
+                        let groups = named_groups.read();
+                        let (level, _) = groups.get_level_range(group);
+                        fill_label_template(template, |placeholder| match placeholder {
+                            "id" => Some(id.clone()),
+                            "level" => Some(level.to_string()),
+                            "var" => Some(groups.get_level_label(level)),
+                            "func_names" => Some(pointers.join(", ")),
+                            _ => None,
+                        })
+                    }),
                     _ => None,
                 };
+                let name = named_groups
+                    .read()
+                    .get_group_name(group)
+                    .map(|group_name| group_name.get_text())
+                    .or(name);
+
+                // Inner nodes are outlined in the color of the variable of their level, if variables are colored
+                let border_color = match (nodes.get(0), nodes.get(1)) {
+                    (
+                        Some(&PresenceLabel {
+                            original_label:
+                                PointerLabel::Node(NodeLabel {
+                                    kind: NodeType::Inner(_),
+                                    ..
+                                }),
+                            ..
+                        }),
+                        None,
+                    ) => {
+                        let groups = named_groups.read();
+                        let (level, _) = groups.get_level_range(group);
+                        let variable = groups.get_level_label(level);
+                        node_variable_colors
+                            .read()
+                            .iter()
+                            .find(|(name, _)| *name == variable)
+                            .map(|&(_, color)| color.into())
+                    }
+                    _ => None,
+                }
+                .unwrap_or(TransparentColor(0.0, 0.0, 0.0, 0.0));
+                // The values of external metrics are drawn on top of the color of single nodes
+                let color = match &named_groups.read().get_nodes_of_group(group)[..] {
+                    &[node] => node_metric_ref.read().get_color(node, color),
+                    _ => color,
+                };
 
                 NodeData {
                     color,
-                    border_color: TransparentColor(0.0, 0.0, 0.0, 0.0),
-                    width: 1.
-                        + match name {
-                            Some(ref text) => font.measure_width(&text),
-                            None => 0.,
-                        },
+                    border_color,
+                    width: 1.,
+                    label_size: name.as_ref().map(|text| Point {
+                        x: font.measure_width(text),
+                        y: font.measure_height(text),
+                    }),
                     name,
                     is_terminal,
                     is_group,
                 }
             },
-            move |layer_label| LayerData {
-                name: layer_label.clone(),
+            move |level, layer_label| LayerData {
+                name: match (
+                    *layer_show_true_levels.read(),
+                    layer_true_levels.read().get(level as usize),
+                ) {
+                    (true, Some(true_level)) => format!("{} [{}]", layer_label, true_level),
+                    _ => layer_label.clone(),
+                },
             },
         ));
         grouped_graph.hide(0);
+        let grouped_graph = MutRcRefCell::new(grouped_graph);
 
+        let width_config = |width: f32| {
+            let mut c = FloatConfig::new(width);
+            c.set_min(Some(0.)).commit();
+            c
+        };
         let composite_config = CompositeConfig::new((
             LabelConfig::new(
                 "Layout",
@@ -634,6 +1254,103 @@ impl QDDDiagramDrawer {
             ButtonConfig::new_labeled("Generate latex"),
             TextOutputConfig::new(true),
             ButtonConfig::new_labeled("Expand all"),
+            LabelConfig::new(
+                "Weighted edges",
+                ChoiceConfig::new([
+                    Choice::new(false, "disabled"),
+                    Choice::new(true, "enabled"),
+                ]),
+            ),
+            LabelConfig::new_styled(
+                "Layers",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "labels",
+                        ChoiceConfig::new([
+                            Choice::new(LayerLabelSides::Left, "left"),
+                            Choice::new(LayerLabelSides::Right, "right"),
+                            Choice::new(LayerLabelSides::Both, "both"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "sticky labels",
+                        ChoiceConfig::new([
+                            Choice::new(true, "enabled"),
+                            Choice::new(false, "disabled"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "backgrounds",
+                        ChoiceConfig::new([
+                            Choice::new(true, "alternating"),
+                            Choice::new(false, "plain"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "label rotation",
+                        ChoiceConfig::new([
+                            Choice::new(false, "horizontal"),
+                            Choice::new(true, "vertical"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "variable pairs",
+                        ChoiceConfig::new([
+                            Choice::new(LayerPairing::Separate, "separate"),
+                            Choice::new(LayerPairing::Bracketed, "bracketed"),
+                            Choice::new(LayerPairing::Merged, "merged"),
+                        ]),
+                    ),
+                )),
+            ),
+            LabelConfig::new_styled(
+                "Labels",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "placement",
+                        ChoiceConfig::new([
+                            Choice::new(LabelPlacement::Inside, "inside"),
+                            Choice::new(LabelPlacement::Above, "above"),
+                            Choice::new(LabelPlacement::Below, "below"),
+                        ]),
+                    ),
+                    LabelConfig::new(
+                        "overlapping",
+                        ChoiceConfig::new([
+                            Choice::new(false, "show"),
+                            Choice::new(true, "hide"),
+                        ]),
+                    ),
+                )),
+            ),
+            LabelConfig::new_styled(
+                "Edges",
+                LabelStyle::Above,
+                CompositeConfig::new((
+                    LabelConfig::new(
+                        "arrows",
+                        ChoiceConfig::new([
+                            Choice::new(EdgeArrows::None, "none"),
+                            Choice::new(EdgeArrows::End, "end"),
+                            Choice::new(EdgeArrows::Middle, "middle"),
+                        ]),
+                    ),
+                    LabelConfig::new("true width", width_config(0.2)),
+                    LabelConfig::new("false width", width_config(0.2)),
+                    LabelConfig::new("shared width", width_config(0.15)),
+                    LabelConfig::new(
+                        "weights",
+                        ChoiceConfig::new([
+                            Choice::new(WeightDisplay::Hidden, "hide"),
+                            Choice::new(WeightDisplay::Labels, "labels"),
+                            Choice::new(WeightDisplay::Thickness, "thickness"),
+                            Choice::new(WeightDisplay::Hue, "hue"),
+                        ]),
+                    ),
+                )),
+            ),
         ));
         let config = Configuration::new(LocationConfig::new(
             Location::BOTTOM_RIGHT,
@@ -647,16 +1364,38 @@ impl QDDDiagramDrawer {
                 .build(composite_config.clone()),
         ));
 
+        let (_, _, _, _, weight_display) = &**composite_config.13;
         let mut out = QDDDiagramDrawer {
             group_manager,
             presence_adjuster,
+            source_graph,
+            redundancy_adjuster,
+            hidden_markers: true,
+            hidden_edge_stubs: false,
             graph: modified_graph,
             time: MutRcRefCell::new(0),
-            drawer: MutRcRefCell::new(Drawer::new(
-                renderer,
-                layout,
-                MutRcRefCell::new(grouped_graph),
-            )),
+            drawer: MutRcRefCell::new(Drawer::new(renderer, layout, grouped_graph.clone())),
+            svg_renderer,
+            latex_renderer: MutRcRefCell::new(LatexRenderer::new()),
+            create_renderer: Box::new(create_renderer),
+            grouped_graph,
+            entered_groups: Vec::new(),
+            semantic_zoom_threshold: DEFAULT_SEMANTIC_ZOOM_THRESHOLD,
+            summarized: false,
+            layer_summary_budget: DEFAULT_LAYER_SUMMARY_BUDGET,
+            layer_summary_threshold: DEFAULT_LAYER_SUMMARY_THRESHOLD,
+            layer_summaries: None,
+            export_legend: MutRcRefCell::new(false),
+            label_template,
+            true_levels,
+            show_true_levels,
+            variable_colors,
+            node_metric,
+            details_cache: HashMap::new(),
+            structural_hasher,
+            consistent_state: None,
+            edge_weights: MutRcRefCell::new(HashMap::new()),
+            weight_display: (**weight_display).clone(),
             config,
         };
 
@@ -693,24 +1432,152 @@ impl QDDDiagramDrawer {
         });
 
         let drawer = out.drawer.clone();
-        let mut latex_renderer = LatexRenderer::<Layout>::new();
+        let weighted_config = composite_config.10.clone();
+        let _ = on_configuration_change(&composite_config.10, move || {
+            let mut drawer = drawer.get();
+            let p = drawer.get_layout_rules().get_layout_rules();
+            p.get_layout_rules1()
+                .set_weighted_edges(weighted_config.get());
+            p.get_layout_rules2()
+                .get_layout_rules()
+                .set_weighted_edges(weighted_config.get());
+        });
+
+        let (label_sides, sticky_labels, layer_backgrounds, layer_label_rotation, layer_pairing) =
+            &**composite_config.11;
+        let drawer = out.drawer.clone();
+        let (label_sides, sticky_labels, layer_backgrounds, layer_label_rotation, layer_pairing) = (
+            label_sides.clone(),
+            sticky_labels.clone(),
+            layer_backgrounds.clone(),
+            layer_label_rotation.clone(),
+            layer_pairing.clone(),
+        );
+        let svg_renderer = out.svg_renderer.clone();
+        let latex_renderer = out.latex_renderer.clone();
+        let _ = on_configuration_change(&composite_config.11, move || {
+            let settings = (
+                label_sides.get(),
+                sticky_labels.get(),
+                layer_backgrounds.get(),
+                layer_label_rotation.get(),
+                layer_pairing.get(),
+            );
+            drawer.get().configure_renderers("layers", move |renderer| {
+                renderer.set_layer_label_sides(settings.0);
+                renderer.set_sticky_layer_labels(settings.1);
+                renderer.set_alternating_layer_backgrounds(settings.2);
+                renderer.set_rotated_layer_labels(settings.3);
+                renderer.set_layer_pairing(settings.4);
+            });
+            let mut svg_renderer = svg_renderer.get();
+            svg_renderer.set_layer_label_sides(label_sides.get());
+            svg_renderer.set_alternating_layer_backgrounds(layer_backgrounds.get());
+            svg_renderer.set_layer_pairing(layer_pairing.get());
+            let mut latex_renderer = latex_renderer.get();
+            latex_renderer.set_layer_label_sides(label_sides.get());
+            latex_renderer.set_layer_backgrounds(layer_backgrounds.get());
+            latex_renderer.set_rotated_layer_labels(layer_label_rotation.get());
+        });
+
+        let (label_placement, hide_overlapping_labels) = &**composite_config.12;
+        let drawer = out.drawer.clone();
+        let (label_placement, hide_overlapping_labels) =
+            (label_placement.clone(), hide_overlapping_labels.clone());
+        let _ = on_configuration_change(&composite_config.12, move || {
+            let mut drawer = drawer.get();
+            let p = drawer.get_layout_rules().get_layout_rules();
+            p.get_layout_rules1()
+                .set_label_placement(label_placement.get());
+            p.get_layout_rules1()
+                .set_hide_overlapping_labels(hide_overlapping_labels.get());
+            let p2 = p.get_layout_rules2().get_layout_rules();
+            p2.set_label_placement(label_placement.get());
+            p2.set_hide_overlapping_labels(hide_overlapping_labels.get());
+        });
+
+        let (arrows, true_width, false_width, shared_width, weight_display) =
+            &**composite_config.13;
+        let drawer = out.drawer.clone();
+        let edge_weights = out.edge_weights.clone();
+        let (arrows, true_width, false_width, shared_width, weight_display) = (
+            arrows.clone(),
+            true_width.clone(),
+            false_width.clone(),
+            shared_width.clone(),
+            weight_display.clone(),
+        );
+        let svg_renderer = out.svg_renderer.clone();
+        let latex_renderer = out.latex_renderer.clone();
+        let _ = on_configuration_change(&composite_config.13, move || {
+            update_weight_styles(&drawer, &edge_weights.read(), weight_display.get());
+            let arrow_style = arrows.get();
+            let widths = [true_width.get(), false_width.get(), shared_width.get()];
+            drawer.get().configure_renderers("edges", move |renderer| {
+                renderer.set_edge_arrows(arrow_style);
+                for (index, &width) in widths.iter().enumerate() {
+                    renderer.set_edge_type_width(EdgeType::new((), index as i32), width);
+                }
+            });
+            let mut svg_renderer = svg_renderer.get();
+            svg_renderer.set_edge_arrows(arrows.get());
+            svg_renderer.set_edge_type_width(EdgeType::new((), 0), true_width.get());
+            svg_renderer.set_edge_type_width(EdgeType::new((), 1), false_width.get());
+            svg_renderer.set_edge_type_width(EdgeType::new((), 2), shared_width.get());
+            let mut latex_renderer = latex_renderer.get();
+            latex_renderer.set_edge_arrows(arrows.get());
+            latex_renderer.set_edge_width(0, true_width.get());
+            latex_renderer.set_edge_width(1, false_width.get());
+            latex_renderer.set_edge_width(2, shared_width.get());
+        });
+
+        let drawer = out.drawer.clone();
+        let latex_renderer = out.latex_renderer.clone();
+        let svg_renderer = out.svg_renderer.clone();
+        let export_legend = out.export_legend.clone();
+        let variable_colors = out.variable_colors.clone();
+        let node_metric = out.node_metric.clone();
         let mut output = composite_config.8.clone();
         composite_config.7.clone().add_press_listener(move || {
-            latex_renderer.update_layout(&drawer.get().get_current_layout());
-            latex_renderer.render(u32::MAX);
-            let out = latex_renderer.get_output();
+            let legend = if *export_legend.read() {
+                Some(get_legend(
+                    &svg_renderer.read(),
+                    &variable_colors.read(),
+                    &node_metric.read(),
+                ))
+            } else {
+                None
+            };
+            let out = render_latex(&drawer, &latex_renderer, legend, Vec::new(), false);
             output.set(out.into()).commit();
         });
 
-        let from = out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)]);
-        // let from = 0;
+        // Unless all nodes are revealed, the remaining nodes stay undiscovered in the hidden group until they are expanded
+        let from = match reveal {
+            InitialReveal::Default => {
+                out.create_group(vec![TargetID(TargetIDType::NodeGroupID, 0)])
+            }
+            _ => 0,
+        };
         for root in roots {
             out.create_group(vec![TargetID(TargetIDType::NodeID, root)]);
         }
 
-        let max = 500;
-        if out.group_manager.read().get_nodes_of_group(from).len() < max {
-            reveal_all(&out.group_manager, from, max);
+        match reveal {
+            InitialReveal::Default => {
+                let max = 500;
+                if out.group_manager.read().get_nodes_of_group(from).len() < max {
+                    reveal_all(&out.group_manager, from, max);
+                }
+            }
+            InitialReveal::Lazy => {}
+            InitialReveal::Bounded {
+                max_depth,
+                max_nodes,
+            } => {
+                let groups = out.group_manager.get().reveal_bounded(max_depth, max_nodes);
+                out.adopt_groups(&groups);
+            }
         }
 
         let group_manager = out.group_manager.clone();
@@ -786,11 +1653,182 @@ impl QDDDiagramDrawer {
         });
 
         let _ = after_configuration_change(&composite_config, move || {
-            drawer.get().layout(*time.get());
+            if let Err(error) = drawer.get().layout(*time.get()) {
+                console::log_error!("The diagram could not be laid out: {}", error);
+            }
         });
 
         out
     }
+
+    /// Retrieves the legend to include in exports, if enabled
+    fn get_export_legend(&self) -> Option<Legend> {
+        if *self.export_legend.read() {
+            Some(DiagramSectionDrawer::get_legend(self))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the summed number of satisfying assignments and the combined support of the given source nodes, which are only computed once per node
+    fn get_source_details(&mut self, sources: &[NodeID]) -> (f64, Vec<LevelNo>) {
+        let source_graph = self.source_graph.read();
+        let var_count = source_graph.get_level_count();
+        let mut sat_count = 0.;
+        let mut support = Vec::new();
+        for &source in sources {
+            if !self.details_cache.contains_key(&source) {
+                let Some(function) = source_graph.get_function(source) else {
+                    continue;
+                };
+                let mut counts = HashMap::new();
+                function.count_level_nodes(&mut HashSet::new(), &mut counts);
+                let levels = counts.into_keys().sorted().collect();
+                let details = (function.count_satisfying(var_count), levels);
+                self.details_cache.insert(source, details);
+            }
+            let (count, levels) = &self.details_cache[&source];
+            sat_count += count;
+            support.extend(levels.iter().cloned());
+        }
+        (sat_count, support.into_iter().sorted().dedup().collect())
+    }
+
+    /// Marks every group with children that are hidden by the presence adjuster with a stub labeled by the number of these children, such that a partial view is not mistaken for the whole diagram. Optionally also marks every edge from a single node to a hidden node by a stub.
+    fn update_hidden_markers(&mut self) {
+        let mut markers = Vec::new();
+        if self.hidden_markers || self.hidden_edge_stubs {
+            let group_manager = self.group_manager.read();
+            let mut presence_adjuster = self.presence_adjuster.get();
+            for group in group_manager.get_all_groups() {
+                let nodes = group_manager.get_nodes_of_group(group);
+                let Some(&anchor) = nodes.first() else {
+                    continue;
+                };
+                let stub = |delta: Point, label: String| Annotation {
+                    anchor: Some(anchor),
+                    offset: Point { x: 0., y: -0.5 },
+                    kind: AnnotationKind::Stub(delta, label),
+                };
+                if self.hidden_markers {
+                    let hidden = nodes
+                        .iter()
+                        .flat_map(|&node| presence_adjuster.get_hidden_children(node, false))
+                        .map(|(_, child)| child)
+                        .unique()
+                        .count();
+                    if hidden > 0 {
+                        let delta = Point {
+                            x: 0.,
+                            y: -STUB_LENGTH,
+                        };
+                        markers.push(stub(delta, hidden.to_string()));
+                    }
+                }
+                // The edges of groups of multiple nodes are combined, hence only edges of single nodes get stubs
+                if self.hidden_edge_stubs && nodes.len() == 1 {
+                    for (edge_type, _) in presence_adjuster.get_hidden_children(anchor, true) {
+                        let delta = get_edge_stub_delta(edge_type.index);
+                        markers.push(stub(delta, STUB_ELLIPSIS.to_string()));
+                    }
+                }
+            }
+        }
+        self.drawer.get().set_markers(markers);
+    }
+
+    /// Nests the given groups inside of the currently entered group, such that they remain visible
+    fn adopt_groups(&mut self, groups: &[NodeGroupID]) {
+        let Some(&current) = self.entered_groups.last() else {
+            return;
+        };
+        self.group_manager.get().set_groups_parent(groups, current);
+        self.update_focus();
+    }
+
+    /// Summarizes the subfunctions below the top levels when zoomed out further than the semantic zoom threshold, and restores them once zoomed back in. Returns whether the groups changed.
+    fn update_semantic_zoom(&mut self) -> bool {
+        let scale = self.drawer.read().get_transform().scale;
+        let summarize = scale < self.semantic_zoom_threshold;
+        if summarize == self.summarized {
+            return false;
+        }
+        self.summarized = summarize;
+        let groups = if summarize {
+            self.group_manager
+                .get()
+                .summarize(SEMANTIC_ZOOM_DETAIL_LEVELS)
+        } else {
+            // The runs of giant layers are restored as well, and summarized again based on the next layout
+            self.layer_summaries = None;
+            self.group_manager.get().restore_summaries()
+        };
+        self.adopt_groups(&groups);
+        true
+    }
+
+    /// Summarizes runs of adjacent groups of the layers with more groups than the budget when zoomed out further than the threshold, such that every layer shows at most the budget of blocks labeled with their number of nodes, and restores them once zoomed back in. The runs are obtained from the current layout. Returns whether the groups changed.
+    fn update_layer_summaries(&mut self) -> bool {
+        let scale = self.drawer.read().get_transform().scale;
+        let summarize = self.layer_summary_budget > 0 && scale < self.layer_summary_threshold;
+        if summarize == self.layer_summaries.is_some() {
+            return false;
+        }
+        let (groups, changed) = if summarize {
+            let runs = self
+                .drawer
+                .read()
+                .get_current_layout()
+                .get_layer_runs(self.layer_summary_budget)
+                .into_iter()
+                .map(|run| run.into_iter().filter(|&group| group != 0).collect())
+                .collect();
+            let summaries = self.group_manager.get().summarize_runs(runs);
+            let changed = !summaries.is_empty();
+            self.layer_summaries = Some(summaries.clone());
+            (summaries, changed)
+        } else {
+            let summaries = self.layer_summaries.take().unwrap_or_default();
+            let groups = self.group_manager.get().restore_summary_groups(&summaries);
+            (groups, !summaries.is_empty())
+        };
+        self.adopt_groups(&groups);
+        changed
+    }
+
+    /// Lays out the graph again, where the new layout is only sent to the renderers if requested
+    fn layout_graph(&mut self, time: u32, render: bool) -> Result<(), VizError> {
+        // The groups may have changed, which changes the group edges that the weights apply to
+        let weights = self.edge_weights.read();
+        update_weight_styles(&self.drawer, &weights, self.weight_display.get());
+        self.update_hidden_markers();
+        // The nodes representing the source nodes may have changed since the values were set
+        let graph = &self.graph;
+        self.node_metric
+            .get()
+            .update_local_values(|source| graph.source_nodes_to_local(vec![source]));
+        let result = if render {
+            self.drawer.get().layout(time)
+        } else {
+            self.drawer.get().compute_layout(time)
+        };
+        match &result {
+            Ok(()) => self.consistent_state = Some(self.serialize_state()),
+            Err(error) => console::log_error!("The diagram could not be laid out: {}", error),
+        }
+        result
+    }
+
+    /// Only shows the entered group and the groups nested inside of it, dropping groups that no longer exist from the path
+    fn update_focus(&mut self) {
+        let group_manager = self.group_manager.read();
+        self.entered_groups.retain(|group| group_manager.get_groups().contains_key(group));
+        let focus = self
+            .entered_groups
+            .last()
+            .map(|&group| group_manager.get_descendant_groups(group));
+        self.grouped_graph.get().set_focus(focus);
+    }
 }
 
 fn reveal_all<G: GraphStructure>(
@@ -817,18 +1855,161 @@ fn reveal_all<G: GraphStructure>(
     }
 }
 
+/// Derives the styles of the edges from their weights, according to the chosen display
+fn update_weight_styles(
+    drawer: &MutRcRefCell<Drawer<ScreenRenderer<()>, Layout, GroupedGraph>>,
+    weights: &HashMap<NodeEdgeID, Complex>,
+    display: WeightDisplay,
+) {
+    let mut drawer = drawer.get();
+    let styles = get_weight_styles(weights, display, |node| drawer.get_node_group(node));
+    drawer.set_default_edge_styles(styles);
+}
+
+/// Follows the path from the given root for the given assignment of the levels below it, and retrieves the product of the weights along the path if it ends in the true terminal, or zero otherwise
+fn evaluate_path(
+    graph: &mut Graph,
+    weight: &impl Fn(NodeID, NodeID, i32) -> Complex,
+    root: NodeID,
+    root_level: LevelNo,
+    assignment: &[bool],
+) -> Complex {
+    let mut node = root;
+    let mut value = Complex::ONE;
+    for (offset, &bit) in assignment.iter().enumerate() {
+        // Levels that are skipped by the path do not influence the value
+        if graph.get_level(node) > root_level + offset as LevelNo {
+            continue;
+        }
+        let edge_type = if bit { 0 } else { 1 };
+        let child = graph
+            .get_children(node)
+            .into_iter()
+            .find(|(edge, _)| edge.index == edge_type)
+            .map(|(_, child)| child);
+        // Edges to hidden terminals are absent, and are considered to lead to zero
+        let Some(child) = child else {
+            return Complex::ZERO;
+        };
+        value = value * weight(node, child, edge_type);
+        node = child;
+    }
+    match graph.get_node_label(node).original_label {
+        PointerLabel::Node(NodeLabel {
+            pointers: _,
+            kind: NodeType::Terminal(t),
+        }) if t == "T" => value,
+        _ => Complex::ZERO,
+    }
+}
+
+/// Retrieves the meaning of the visible edge types and the node colors
+fn get_legend(
+    svg_renderer: &SvgRenderer<Layout>,
+    variable_colors: &[(String, Color)],
+    node_metric: &NodeMetric,
+) -> Legend {
+    let colors = &QDDColors::LIGHT;
+    let edges = svg_renderer
+        .get_visible_edge_types()
+        .into_iter()
+        .map(|(index, edge_type)| LegendEntry {
+            symbol: LegendSymbol::Edge {
+                color: edge_type.color,
+                width: edge_type.width,
+                dash_solid: edge_type.dash_solid,
+                dash_transparent: edge_type.dash_transparent,
+            },
+            meaning: match index {
+                0 => "true edge",
+                1 => "false edge",
+                _ => "pointer edge",
+            }
+            .into(),
+        });
+    let nodes = [
+        (colors.node_default, "inner node"),
+        (colors.node_redundant, "redundant node"),
+        (colors.node_true, "true terminal"),
+        (colors.node_false, "false terminal"),
+        (colors.node_label, "pointer"),
+        (colors.node_group, "group of nodes"),
+    ]
+    .into_iter()
+    .map(|(color, meaning)| LegendEntry {
+        symbol: LegendSymbol::Node { color },
+        meaning: meaning.into(),
+    });
+    Legend {
+        entries: edges
+            .chain(nodes)
+            .chain(get_variable_entries(variable_colors))
+            .chain(node_metric.get_legend_entry())
+            .collect(),
+    }
+}
+
+/// Renders the current layout of the drawer as TikZ, optionally annotated with beamer overlays for the recorded stages
+fn render_latex(
+    drawer: &MutRcRefCell<Drawer<ScreenRenderer<()>, Layout, GroupedGraph>>,
+    latex_renderer: &MutRcRefCell<LatexRenderer<Layout>>,
+    legend: Option<Legend>,
+    pages: Vec<Page>,
+    staged: bool,
+) -> String {
+    let drawer = drawer.read();
+    let mut latex_renderer = latex_renderer.get();
+    latex_renderer.resolve_node_styles(|node| drawer.get_node_group(node));
+    latex_renderer.set_legend(legend);
+    latex_renderer.set_pages(pages);
+    latex_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+    latex_renderer.set_annotations(&drawer.get_annotation_shapes());
+    latex_renderer.update_layout(&drawer.get_export_layout());
+    latex_renderer.set_staged(staged);
+    latex_renderer.render(u32::MAX);
+    latex_renderer.get_output()
+}
+
 impl DiagramSectionDrawer for QDDDiagramDrawer {
     fn render(&mut self, time: u32) -> () {
         *self.time.get() = time;
         self.drawer.get().render(time);
     }
 
-    fn layout(&mut self, time: u32) -> () {
-        self.drawer.get().layout(time);
+    fn layout(&mut self, time: u32) -> Result<(), VizError> {
+        self.layout_graph(time, true)
     }
 
-    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
+    fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> bool {
         self.drawer.get().set_transform(width, height, x, y, scale);
+        // The layer summaries are derived from the layout, hence they wait for the layout of changed subfunction summaries
+        self.update_semantic_zoom() || self.update_layer_summaries()
+    }
+
+    fn set_semantic_zoom(&mut self, threshold: f32) -> bool {
+        self.semantic_zoom_threshold = threshold;
+        self.update_semantic_zoom()
+    }
+
+    fn set_layer_summaries(&mut self, budget: usize, threshold: f32) -> bool {
+        self.layer_summary_budget = budget;
+        self.layer_summary_threshold = threshold;
+        self.update_layer_summaries()
+    }
+
+    fn set_layer_virtualization(&mut self, threshold: Option<usize>) {
+        self.drawer.get().set_layer_window_threshold(threshold);
+    }
+
+    fn set_appearance_animations(
+        &mut self,
+        entrance: AppearanceAnimation,
+        exit: AppearanceAnimation,
+    ) {
+        self.drawer
+            .get()
+            .get_layout_rules()
+            .set_appearance_animations(entrance, exit);
     }
 
     fn set_step(&mut self, step: i32) -> Option<StepData> {
@@ -840,21 +2021,419 @@ impl DiagramSectionDrawer for QDDDiagramDrawer {
     }
 
     fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
-        // self.config.
-        self.group_manager.get().create_group(from)
+        let group = self.group_manager.get().create_group(from);
+        self.adopt_groups(&[group]);
+        group
+    }
+
+    fn set_group_label(&mut self, group: NodeGroupID, name: String, note: String) -> bool {
+        self.group_manager.get().set_group_name(group, name, note)
+    }
+
+    fn set_level_label(&mut self, level: LevelNo, name: String) {
+        self.source_graph.get().set_level_label(level, name);
+    }
+
+    fn set_true_level_labels(&mut self, show: bool) {
+        *self.show_true_levels.get() = show;
+    }
+
+    fn set_node_label_template(&mut self, template: Option<String>) {
+        *self.label_template.get() = template;
+    }
+
+    fn get_level_labels(&self) -> Vec<String> {
+        let source_graph = self.source_graph.read();
+        (0..source_graph.get_level_count())
+            .map(|level| source_graph.get_level_label(level))
+            .collect()
+    }
+
+    fn set_variable_colors(&mut self, colors: Vec<(String, Color)>) {
+        *self.variable_colors.get() = colors;
+    }
+
+    fn set_node_metric(&mut self, values: HashMap<NodeID, f64>) {
+        self.node_metric.get().set_values(values);
+    }
+
+    fn expand_group(&mut self, group: NodeGroupID, depth: LevelNo) -> Vec<NodeGroupID> {
+        // The expanded groups are nested in place of the group, rather than in the entered group
+        let groups = self.group_manager.get().expand_group(group, depth);
+        self.update_focus();
+        groups
+    }
+
+    fn expand_node(&mut self, node: NodeID, depth: usize) -> Vec<NodeGroupID> {
+        let groups = self.group_manager.get().expand_node(node, depth);
+        self.adopt_groups(&groups);
+        groups
+    }
+
+    fn group_shared_subfunctions(
+        &mut self,
+        min_occurrences: usize,
+        min_size: usize,
+    ) -> Vec<NodeGroupID> {
+        let graph = &self.graph;
+        let groups = self.group_manager.get().group_shared_subfunctions(
+            min_occurrences,
+            min_size,
+            &mut self.structural_hasher,
+            |node| graph.local_nodes_to_sources(vec![node]).first().cloned(),
+        );
+        self.adopt_groups(&groups);
+        groups
+    }
+
+    fn group_by_level_pattern(&mut self, pattern: String) -> Result<Option<NodeGroupID>, String> {
+        let regex = Regex::new(&pattern).map_err(|err| err.to_string())?;
+        let group = self
+            .group_manager
+            .get()
+            .group_levels(pattern, |label| regex.is_match(label));
+        if let Some(group) = group {
+            self.adopt_groups(&[group]);
+        }
+        Ok(group)
+    }
+
+    fn set_group_parent(&mut self, group: NodeGroupID, parent: Option<NodeGroupID>) -> bool {
+        let nested = self.group_manager.get().set_group_parent(group, parent);
+        self.update_focus();
+        nested
+    }
+
+    fn enter_group(&mut self, group: NodeGroupID) -> bool {
+        if !self.group_manager.read().get_groups().contains_key(&group) {
+            return false;
+        }
+        self.entered_groups.push(group);
+        self.update_focus();
+        true
+    }
+
+    fn exit_group(&mut self) -> bool {
+        let exited = self.entered_groups.pop().is_some();
+        self.update_focus();
+        exited
+    }
+
+    fn get_entered_groups(&self) -> Vec<NodeGroupID> {
+        self.entered_groups.clone()
+    }
+
+    fn collapse_low(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        // Edge index 1 represents the false edge
+        let group = self.group_manager.get().collapse_subtree(node, 1)?;
+        self.adopt_groups(&[group]);
+        Some(group)
+    }
+
+    fn collapse_high(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        // Edge index 0 represents the true edge
+        let group = self.group_manager.get().collapse_subtree(node, 0)?;
+        self.adopt_groups(&[group]);
+        Some(group)
+    }
+
+    fn set_edge_visibility(&mut self, edge_type: i32, visible: bool, relayout: bool) {
+        let edge_type = EdgeType::new((), edge_type);
+        self.grouped_graph
+            .get()
+            .set_edge_type_presence(edge_type, visible || !relayout);
+        self.drawer.get().configure_renderers(
+            &format!("edge visibility {}", edge_type.index),
+            move |renderer| renderer.set_edge_type_visibility(edge_type, visible || relayout),
+        );
+        self.svg_renderer
+            .get()
+            .set_edge_type_visibility(edge_type, visible);
     }
 
     fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
         self.group_manager.get().split_edges(nodes, fully);
     }
+    fn get_redundant_nodes(&mut self) -> Vec<NodeID> {
+        let sources = self.redundancy_adjuster.get().get_redundant_nodes();
+        self.graph.source_nodes_to_local(sources)
+    }
+    fn set_redundant_bypass(&mut self, bypass: bool) {
+        self.redundancy_adjuster.get().set_bypass(bypass);
+    }
+    fn set_hidden_markers(&mut self, enabled: bool) {
+        self.hidden_markers = enabled;
+    }
+    fn set_hidden_edge_stubs(&mut self, enabled: bool) {
+        self.hidden_edge_stubs = enabled;
+    }
+    fn set_empty_layer_elision(&mut self, enabled: bool) {
+        let mut drawer = self.drawer.get();
+        let p = drawer.get_layout_rules().get_layout_rules();
+        p.get_layout_rules1().set_elide_empty_layers(enabled);
+        p.get_layout_rules2()
+            .get_layout_rules()
+            .set_elide_empty_layers(enabled);
+    }
+    fn apply_to_selection(&mut self, operation: SelectionOperation) -> Vec<NodeGroupID> {
+        let nodes = self.drawer.read().get_selected_nodes();
+        if nodes.is_empty() {
+            return Vec::new();
+        }
+        let remainder = match operation {
+            SelectionOperation::Show => PresenceRemainder::Show,
+            SelectionOperation::Hide => PresenceRemainder::Hide,
+            SelectionOperation::Duplicate => PresenceRemainder::Duplicate,
+            SelectionOperation::Group => {
+                let targets = nodes
+                    .iter()
+                    .map(|&node| TargetID(TargetIDType::NodeID, node))
+                    .collect();
+                return vec![self.create_group(targets)];
+            }
+            SelectionOperation::Split | SelectionOperation::SplitFully => {
+                let fully = operation == SelectionOperation::SplitFully;
+                self.group_manager.get().split_edges(&nodes, fully);
+                return Vec::new();
+            }
+        };
+        let mut adjuster = self.presence_adjuster.get();
+        for &node in &nodes {
+            adjuster.set_node_presence(node, PresenceGroups::remainder(remainder.clone()));
+        }
+        Vec::new()
+    }
+    fn set_presence_where(
+        &mut self,
+        predicate: PresencePredicate,
+        remainder: PresenceRemainder,
+    ) -> Result<usize, String> {
+        self.presence_adjuster
+            .get()
+            .set_presence_where(&predicate, remainder)
+    }
+    fn set_duplication_threshold(&mut self, threshold: usize) -> DuplicationData {
+        let (duplicated_nodes, created_copies) = self
+            .presence_adjuster
+            .get()
+            .set_duplication_threshold(threshold);
+        DuplicationData {
+            duplicated_nodes,
+            created_copies,
+        }
+    }
+    fn set_terminal_bins(&mut self, bounds: Vec<f32>) {}
+    fn set_root_order(&mut self, roots: &[NodeID]) {
+        let mut drawer = self.drawer.get();
+        let p = drawer.get_layout_rules().get_layout_rules();
+        p.get_layout_rules1().set_root_order(roots.to_vec());
+        p.get_layout_rules2()
+            .get_layout_rules()
+            .set_root_order(roots.to_vec());
+    }
+    fn pin_nodes(&mut self, nodes: &[NodeID], pinned: bool) {
+        let mut drawer = self.drawer.get();
+        let pins = get_pin_positions(&drawer.get_current_layout(), nodes, |node| {
+            drawer.get_node_group(node)
+        });
+        let p = drawer.get_layout_rules().get_layout_rules();
+        let positioning1 = p.get_layout_rules1().get_positioning_aligning();
+        if pinned {
+            positioning1.pin(pins.clone());
+        } else {
+            positioning1.unpin(nodes);
+        }
+        let positioning2 = p
+            .get_layout_rules2()
+            .get_layout_rules()
+            .get_positioning_aligning();
+        if pinned {
+            positioning2.pin(pins);
+        } else {
+            positioning2.unpin(nodes);
+        }
+    }
+    fn pin_terminals(&mut self, terminals: &[NodeID], column: HorizontalAlignment) {
+        let all_terminals = self.graph.get_terminals();
+        let terminals = terminals
+            .iter()
+            .cloned()
+            .filter(|terminal| all_terminals.contains(terminal))
+            .collect_vec();
+        let mut drawer = self.drawer.get();
+        let p = drawer.get_layout_rules().get_layout_rules();
+        p.get_layout_rules1()
+            .get_positioning_aligning()
+            .pin_to_column(&terminals, column);
+        p.get_layout_rules2()
+            .get_layout_rules()
+            .get_positioning_aligning()
+            .pin_to_column(&terminals, column);
+    }
+    fn move_group(&mut self, group: NodeGroupID, dx: f32, dy: f32) {
+        let pinned = {
+            let mut drawer = self.drawer.get();
+            drawer.move_group(group, dx, dy);
+            let p = drawer.get_layout_rules().get_layout_rules();
+            let positioning = p.get_layout_rules1().get_positioning_aligning();
+            self.group_manager
+                .read()
+                .get_nodes_of_group(group)
+                .into_iter()
+                .filter(|&node| positioning.is_pinned(node))
+                .collect_vec()
+        };
+        // Pinned nodes stay where they were moved to, other nodes return to their computed position on the next layout
+        self.pin_nodes(&pinned, true);
+    }
+    fn align_groups(&mut self, nodes: &[NodeID], alignment: HorizontalAlignment) {
+        self.drawer.get().align_groups(nodes, alignment);
+    }
+    fn distribute_groups(&mut self, nodes: &[NodeID]) {
+        self.drawer.get().distribute_groups(nodes);
+    }
+    fn reset_group_positions(&mut self, nodes: &[NodeID]) {
+        self.drawer.get().reset_group_positions(nodes);
+    }
+    fn sort_roots(&mut self, sorting: RootSorting) -> Vec<NodeID> {
+        let roots = get_sorted_roots(&mut self.graph, sorting, |label| match label.original_label {
+            PointerLabel::Pointer(name) => Some(name),
+            _ => None,
+        });
+        self.set_root_order(&roots);
+        roots
+    }
+
+    fn add_view(&mut self, canvas: HtmlCanvasElement) -> ViewID {
+        let renderer = (self.create_renderer)(RenderSurface::Canvas(canvas));
+        self.drawer.get().add_view(renderer)
+    }
+    fn add_framebuffer_view(
+        &mut self,
+        context: WebGl2RenderingContext,
+        framebuffer: Option<WebGlFramebuffer>,
+        width: u32,
+        height: u32,
+    ) -> ViewID {
+        let renderer = (self.create_renderer)(RenderSurface::Framebuffer {
+            context,
+            framebuffer,
+            width,
+            height,
+        });
+        self.drawer.get().add_view(renderer)
+    }
+    #[cfg(feature = "webgpu")]
+    fn use_webgpu(&mut self, context: WebgpuContext) {
+        let renderer = (self.create_renderer)(RenderSurface::Webgpu(context));
+        self.drawer.get().set_renderer(renderer);
+    }
+    fn remove_view(&mut self, view: ViewID) -> bool {
+        self.drawer.get().remove_view(view)
+    }
+    fn set_view_transform(
+        &mut self,
+        view: ViewID,
+        width: u32,
+        height: u32,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) {
+        self.drawer
+            .get()
+            .set_view_transform(view, width, height, x, y, scale);
+    }
+    fn render_view(&mut self, view: ViewID, time: u32) {
+        self.drawer.get().render_view(view, time);
+    }
+    fn get_view_nodes(
+        &self,
+        view: ViewID,
+        area: Rectangle,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID> {
+        self.drawer
+            .read()
+            .get_view_nodes(view, area, max_group_expansion)
+    }
+
+    fn get_history(&self) -> Vec<HistoryEntryData> {
+        self.drawer.read().get_history()
+    }
+    fn scrub_history(&mut self, index: Option<usize>) -> bool {
+        self.drawer.get().scrub_history(index)
+    }
 
     fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
         self.drawer.read().get_nodes(area, max_group_expansion)
     }
+    fn get_elements(&self, area: Rectangle, max_group_expansion: usize) -> ElementsData {
+        self.drawer.read().get_elements(area, max_group_expansion)
+    }
 
     fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
         self.drawer.get().select_nodes(selected_ids, hovered_ids);
     }
+    fn save_selection(&mut self, name: String) {
+        self.drawer.get().save_selection(name);
+    }
+    fn apply_selection(&mut self, name: String) -> Option<Vec<NodeID>> {
+        self.drawer.get().apply_selection(&name)
+    }
+    fn get_selection_names(&self) -> Vec<String> {
+        self.drawer.read().get_selection_names()
+    }
+    fn set_edge_style(&mut self, edge: GroupEdgeID, style: EdgeStyle) {
+        self.drawer.get().set_edge_style(edge, style);
+    }
+    fn set_selected_edges(&mut self, edges: &[GroupEdgeID]) {
+        self.drawer.get().select_edges(edges);
+    }
+    fn set_edge_weights(&mut self, weights: HashMap<NodeEdgeID, Complex>) {
+        update_weight_styles(&self.drawer, &weights, self.weight_display.get());
+        *self.edge_weights.get() = weights;
+    }
+    fn get_matrix(&self, node: NodeID, max_dim: usize) -> Option<Matrix> {
+        let mut graph = self.graph.clone();
+        let weights = self.edge_weights.read();
+        let weight = |from: NodeID, to: NodeID, edge_type: i32| {
+            weights
+                .get(&(from, to, edge_type))
+                .copied()
+                .unwrap_or(Complex::ONE)
+        };
+
+        // A pointer node represents the matrix of the node it points to
+        let (root, root_weight) = match graph.get_node_label(node).original_label {
+            PointerLabel::Pointer(_) => {
+                let (edge_type, child) = graph.get_children(node).into_iter().next()?;
+                (child, weight(node, child, edge_type.index))
+            }
+            _ => (node, Complex::ONE),
+        };
+        let terminal = *graph.get_terminals().first()?;
+        let terminal_level = graph.get_level(terminal);
+        let root_level = graph.get_level(root);
+        let variables = terminal_level.saturating_sub(root_level) as usize;
+        Some(reconstruct_matrix(variables, max_dim, |assignment| {
+            root_weight * evaluate_path(&mut graph, &weight, root, root_level, assignment)
+        }))
+    }
+    fn add_annotation(
+        &mut self,
+        anchor: Option<NodeID>,
+        position: Point,
+        kind: AnnotationKind,
+    ) -> AnnotationID {
+        self.drawer.get().add_annotation(anchor, position, kind)
+    }
+    fn remove_annotation(&mut self, id: AnnotationID) -> bool {
+        self.drawer.get().remove_annotation(id)
+    }
+    fn navigate(&mut self, direction: NavigationDirection) -> Option<FocusData> {
+        self.drawer.get().navigate(direction)
+    }
 
     fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
         self.graph
@@ -865,14 +2444,234 @@ impl DiagramSectionDrawer for QDDDiagramDrawer {
         self.graph
             .source_nodes_to_local(nodes.iter().cloned().collect())
     }
+
+    fn get_node_hash(&mut self, node: NodeID) -> Option<u64> {
+        let source = *self.graph.local_nodes_to_sources(vec![node]).first()?;
+        Some(self.structural_hasher.get_node_hash(source))
+    }
+
+    fn get_node_details(&mut self, node: NodeID) -> NodeDetailsData {
+        let mut graph = self.graph.clone();
+        let level = graph.get_level(node);
+        let sources = graph.local_nodes_to_sources(vec![node]);
+        let (sat_count, support) = self.get_source_details(&sources);
+        NodeDetailsData {
+            level,
+            label: graph.get_level_label(level),
+            out_degree: graph.get_children(node).len(),
+            in_degree: graph.get_known_parents(node).len(),
+            sat_count,
+            support,
+            group: self.group_manager.read().get_group(node),
+            sources,
+        }
+    }
+
+    fn export_region_svg(&mut self, area: Rectangle) -> String {
+        let legend = self.get_export_legend();
+        let mut svg_renderer = self.svg_renderer.get();
+        svg_renderer.set_region(Some(area));
+        svg_renderer.set_legend(legend);
+        let drawer = self.drawer.read();
+        svg_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+        svg_renderer.set_annotations(&drawer.get_annotation_shapes());
+        svg_renderer.update_layout(&drawer.get_export_layout());
+        svg_renderer.render(u32::MAX);
+        svg_renderer.get_output()
+    }
+
+    fn export_region_png(&mut self, area: Rectangle, pixels_per_unit: f32) -> String {
+        let time = *self.time.get();
+        let legend = self.get_export_legend();
+        self.drawer
+            .get()
+            .export_region_png(area, pixels_per_unit, legend.as_ref(), time)
+            .unwrap_or_default()
+    }
+
+    fn export_paged_svg(&mut self, paper: PaperSize, overlap: f32) -> Vec<String> {
+        let mut svg_renderer = self.svg_renderer.get();
+        let drawer = self.drawer.read();
+        svg_renderer.set_legend(None);
+        svg_renderer.set_edge_styles(drawer.get_edge_styles(), &HashSet::new());
+        svg_renderer.set_annotations(&drawer.get_annotation_shapes());
+        svg_renderer.update_layout(&drawer.get_export_layout());
+        let pages = paginate(&svg_renderer.get_bounds(u32::MAX), paper, overlap);
+        let out = pages
+            .into_iter()
+            .map(|page| {
+                svg_renderer.set_page(Some(page));
+                svg_renderer.render(u32::MAX);
+                svg_renderer.get_output()
+            })
+            .collect();
+        svg_renderer.set_page(None);
+        out
+    }
+
+    fn export_paged_tikz(&mut self, paper: PaperSize, overlap: f32) -> String {
+        let bounds = {
+            let mut svg_renderer = self.svg_renderer.get();
+            svg_renderer.update_layout(&self.drawer.read().get_export_layout());
+            svg_renderer.get_bounds(u32::MAX)
+        };
+        let pages = paginate(&bounds, paper, overlap);
+        render_latex(&self.drawer, &self.latex_renderer, None, pages, false)
+    }
+
+    fn get_legend(&self) -> Legend {
+        get_legend(
+            &self.svg_renderer.read(),
+            &self.variable_colors.read(),
+            &self.node_metric.read(),
+        )
+    }
+
+    fn set_export_grid(&mut self, grid: Option<f32>) {
+        self.drawer.get().set_export_grid(grid);
+    }
+
+    fn set_export_legend(&mut self, enabled: bool) {
+        *self.export_legend.get() = enabled;
+    }
+
+    fn set_tikz_node_style(&mut self, node: NodeID, style: String) {
+        self.latex_renderer.get().set_node_style(node, style);
+    }
+
+    fn set_tikz_level_style(&mut self, level: LevelNo, style: String) {
+        self.latex_renderer.get().set_level_style(level, style);
+    }
+
+    fn set_tikz_edge_style(&mut self, edge_type: i32, style: String) {
+        self.latex_renderer.get().set_edge_style(edge_type, style);
+    }
+
+    fn record_tikz_stage(&mut self) -> usize {
+        let layout = self.drawer.read().get_export_layout();
+        self.latex_renderer.get().record_stage(&layout)
+    }
+
+    fn clear_tikz_stages(&mut self) {
+        self.latex_renderer.get().clear_stages();
+    }
+
+    fn export_staged_tikz(&mut self) -> String {
+        let legend = self.get_export_legend();
+        render_latex(&self.drawer, &self.latex_renderer, legend, Vec::new(), true)
+    }
+
+    fn get_adjacency(&self) -> AdjacencyData {
+        self.drawer.read().get_adjacency()
+    }
+    fn get_crossing_count(&self) -> usize {
+        self.drawer.read().get_crossing_count()
+    }
+    fn get_layout_metrics(&self) -> LayoutMetricsData {
+        self.drawer.read().get_layout_metrics()
+    }
+    fn compute_layout(&mut self, time: u32) -> Result<LayoutData, VizError> {
+        self.layout_graph(time, false)?;
+        Ok(self.drawer.read().get_layout_data())
+    }
+    fn get_geometry(&self) -> SectionGeometry {
+        self.drawer.read().get_geometry()
+    }
+    fn export_outline(&self) -> String {
+        self.drawer.read().export_outline()
+    }
     fn serialize_state(&self) -> Vec<u8> {
         let mut out = Vec::new();
-        let _ = self.group_manager.read().write(&mut Cursor::new(&mut out));
+        let mut stream = Cursor::new(&mut out);
+        let _ = write_state_header(&mut stream)
+            .and_then(|_| self.group_manager.read().write(&mut stream))
+            .and_then(|_| self.drawer.read().write(&mut stream));
         out
     }
 
     fn deserialize_state(&mut self, state: Vec<u8>) -> () {
-        let _ = self.group_manager.get().read(&mut Cursor::new(&state));
+        let mut stream = Cursor::new(&state);
+        let version = match read_state_header(&mut stream) {
+            Ok(version) => version,
+            Err(error) => {
+                console::log_error!("The state could not be restored: {}", error);
+                return;
+            }
+        };
+        let _ = self.group_manager.get().read(&mut stream, version);
+        let _ = self.drawer.get().read(&mut stream, version);
+        self.entered_groups.clear();
+        self.update_focus();
+    }
+
+    fn recover(&mut self) -> bool {
+        let Some(state) = self.consistent_state.clone() else {
+            return false;
+        };
+        self.deserialize_state(state);
+        let time = *self.time.get();
+        self.layout(time).is_ok()
+    }
+
+    fn restore_context(&mut self) -> bool {
+        match self.drawer.get().restore_context() {
+            Ok(()) => true,
+            Err(error) => {
+                console::log_error!("The rendering context could not be restored: {:?}", error);
+                false
+            }
+        }
+    }
+
+    fn export_view_patch(&self) -> String {
+        let group_manager = self.group_manager.read();
+        let hidden = group_manager.get_hidden();
+        let groups = group_manager
+            .get_all_groups()
+            .into_iter()
+            .filter(|group| !hidden.contains(group))
+            .filter_map(|group| {
+                let nodes = group_manager.get_nodes_of_group(group);
+                let name = group_manager.get_group_name(group).cloned();
+                if nodes.len() < 2 && name.is_none() {
+                    return None;
+                }
+                let nodes = self.graph.local_nodes_to_sources(nodes);
+                Some(ViewPatchGroup { nodes, name })
+            })
+            .collect();
+        ViewPatch {
+            presence: self.presence_adjuster.read().get_source_presences(),
+            groups,
+        }
+        .to_json()
+    }
+
+    fn import_view_patch(&mut self, patch: String) -> Result<(), String> {
+        let patch = ViewPatch::from_json(&patch)?;
+        {
+            let mut adjuster = self.presence_adjuster.get();
+            for (source, remainder) in patch.presence {
+                adjuster.set_source_presence(source, remainder);
+            }
+        }
+        // Groups whose nodes are all absent from this diagram are skipped
+        for group in patch.groups {
+            let targets = self
+                .graph
+                .source_nodes_to_local(group.nodes)
+                .into_iter()
+                .map(|node| TargetID(TargetIDType::NodeID, node))
+                .collect_vec();
+            if targets.is_empty() {
+                continue;
+            }
+            let id = self.create_group(targets);
+            if let Some(name) = group.name {
+                self.set_group_label(id, name.name, name.note);
+            }
+        }
+        Ok(())
     }
 
     fn get_configuration(&self) -> AbstractConfigurationObject {
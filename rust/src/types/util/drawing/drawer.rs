@@ -1,44 +1,76 @@
 use std::{
     cell::RefCell,
     collections::{HashMap, HashSet},
+    io::{self, Cursor},
     ops::Deref,
     rc::Rc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use itertools::{Either, Itertools};
 use js_sys::Date;
-use oxidd::{Function, Manager, NodeID};
+use oxidd::{Function, LevelNo, Manager, NodeID};
 use oxidd_core::Tag;
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
+    interface::HorizontalAlignment,
     types::util::{
         graph_structure::{
             graph_structure::DrawTag,
-            grouped_graph_structure::{GroupedGraphStructure, NodeTracker, SourceReader},
+            grouped_graph_structure::{EdgeData, GroupedGraphStructure, NodeTracker, SourceReader},
         },
         group_manager::GroupManager,
+        storage::state_storage::{Serializable, StateStorage, LEGACY_STATE_FORMAT_VERSION},
     },
     util::{
         logging::console,
-        point::Point,
+        point::{segments_cross, Point},
         rc_refcell::{MutRcRefCell, RcRefCell},
         rectangle::Rectangle,
         transformation::Transformation,
-        transition::Interpolatable,
+        transition::{Interpolatable, Transition},
+        viz_error::VizError,
+    },
+    wasm_interface::{
+        AdjacencyData, ElementsData, FocusData, HistoryEntryData, LayoutData, LayoutMetricsData,
+        NavigationDirection, NodeGroupID,
     },
-    wasm_interface::NodeGroupID,
 };
 
 use super::{
-    diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+    annotations::{Annotation, AnnotationID, AnnotationKind, AnnotationShape},
+    change_history::ChangeHistory,
+    diagram_layout::{DiagramLayout, EdgeLayout, LayerStyle, NodeStyle},
+    layer_window::LayerWindow,
     layout_rules::LayoutRules,
-    renderer::{GroupSelection, Renderer},
+    legend::Legend,
+    renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+    renderers::{
+        latex_renderer::{LatexLayerStyle, LatexNodeStyle},
+        screen_renderer::ScreenRenderer,
+        webgl_renderer::{WebglLayerStyle, WebglNodeStyle},
+    },
 };
 
+/// The maximum width and height of exported images, to adhere to hardware limitations
+const MAX_EXPORT_SIZE: f32 = 4096.;
+/// The distance (in diagram units) around an edge within which it is still hit
+const EDGE_HIT_MARGIN: f32 = 0.1;
+
+/// Identifies an additional view of a drawer
+pub type ViewID = usize;
+
+/// An additional rendering of the layout of a drawer with its own camera, such as an overview next to a detailed view
+struct View<R> {
+    renderer: R,
+    transform: Transformation,
+}
+
 pub struct Drawer<
-    R: Renderer<L>,
+    R: DiagramRenderer<L>,
     L: LayoutRules<G = G, T = G::T, LS = G::LL, NS = G::GL, Tracker = G::Tracker>,
     G: GroupedGraphStructure,
 > where
@@ -52,12 +84,135 @@ pub struct Drawer<
     sources: L::Tracker,
     transform: Transformation,
     selection: SelectionData,
+    /// The node that has the keyboard focus
+    focus: Option<NodeID>,
+    edge_styles: HashMap<GroupEdgeID, EdgeStyle>,
+    /// The edge styles derived from the diagram's data, which the user's overrides take precedence over
+    default_edge_styles: HashMap<GroupEdgeID, EdgeStyle>,
+    /// The combination of the user's overrides and the default styles, as sent to the renderer
+    resolved_edge_styles: HashMap<GroupEdgeID, EdgeStyle>,
+    selected_edges: HashSet<GroupEdgeID>,
+    annotations: HashMap<AnnotationID, Annotation>,
+    next_annotation_id: AnnotationID,
+    /// The markers derived from the diagram's data, such as stubs towards hidden nodes, which are drawn like annotations but not stored
+    markers: Vec<Annotation>,
+    /// The horizontal center that the group of each node is moved to after every layout, for groups that were aligned or distributed manually
+    position_overrides: HashMap<NodeID, f32>,
+    /// The spacing of the grid that coordinates are snapped to in exports, if any
+    export_grid: Option<f32>,
+    views: HashMap<ViewID, View<R>>,
+    next_view_id: ViewID,
+    /// The settings that are applied to the renderers of the drawer and its views by name, such that views added later are configured the same
+    renderer_settings: HashMap<String, Box<dyn Fn(&mut R)>>,
+    /// The layouts after every change of the graph, which earlier states can be shown from
+    history: ChangeHistory<L::T, L::NS, L::LS>,
+    /// The sets of selected nodes that were saved under a name, such that they can be selected again
+    named_selections: HashMap<String, Vec<NodeID>>,
+    /// The horizontal window that the geometry of giant layers sent to the renderer is limited to, where views always receive the whole layout
+    layer_window: LayerWindow,
 }
 
 type SelectionData = (Vec<NodeGroupID>, Vec<NodeGroupID>);
 
 impl<
-        R: Renderer<L>,
+        R: DiagramRenderer<L>,
+        L: LayoutRules<G = G, T = G::T, LS = G::LL, NS = G::GL, Tracker = G::Tracker>,
+        G: GroupedGraphStructure,
+    > StateStorage for Drawer<R, L, G>
+where
+    G::GL: NodeStyle,
+    G::LL: LayerStyle,
+{
+    fn write(&self, stream: &mut Cursor<&mut Vec<u8>>) -> io::Result<()> {
+        stream.write_u32::<LittleEndian>(self.edge_styles.len() as u32)?;
+        for (&(from, to, edge_type), style) in &self.edge_styles {
+            stream.write_u32::<LittleEndian>(from as u32)?;
+            stream.write_u32::<LittleEndian>(to as u32)?;
+            stream.write_i32::<LittleEndian>(edge_type)?;
+            style.serialize(stream)?;
+        }
+        stream.write_u32::<LittleEndian>(self.annotations.len() as u32)?;
+        for (&id, annotation) in &self.annotations {
+            stream.write_u32::<LittleEndian>(id as u32)?;
+            annotation.serialize(stream)?;
+        }
+        stream.write_u32::<LittleEndian>(self.position_overrides.len() as u32)?;
+        for (&node, &x) in &self.position_overrides {
+            stream.write_u32::<LittleEndian>(node as u32)?;
+            stream.write_f32::<LittleEndian>(x)?;
+        }
+        stream.write_u32::<LittleEndian>(self.named_selections.len() as u32)?;
+        for (name, nodes) in &self.named_selections {
+            name.serialize(stream)?;
+            stream.write_u32::<LittleEndian>(nodes.len() as u32)?;
+            for &node in nodes {
+                stream.write_u32::<LittleEndian>(node as u32)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, stream: &mut Cursor<&Vec<u8>>, version: u32) -> io::Result<()> {
+        // Legacy states were stored before the drawer had a state, hence the current one is kept
+        if version == LEGACY_STATE_FORMAT_VERSION {
+            return Ok(());
+        }
+        self.edge_styles.clear();
+        self.selected_edges.clear();
+        let style_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..style_count {
+            let from = stream.read_u32::<LittleEndian>()? as usize;
+            let to = stream.read_u32::<LittleEndian>()? as usize;
+            let edge_type = stream.read_i32::<LittleEndian>()?;
+            let style = EdgeStyle::deserialize(stream)?;
+            self.edge_styles.insert((from, to, edge_type), style);
+        }
+        self.update_edge_styles();
+
+        self.annotations.clear();
+        let annotation_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..annotation_count {
+            let id = stream.read_u32::<LittleEndian>()? as AnnotationID;
+            self.annotations.insert(id, Annotation::deserialize(stream)?);
+            self.next_annotation_id = self.next_annotation_id.max(id + 1);
+        }
+        self.update_annotations();
+
+        // Manual positions are absent in states stored by older versions, and only take effect on the next layout
+        self.position_overrides.clear();
+        let override_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..override_count {
+            let node = stream.read_u32::<LittleEndian>()? as NodeID;
+            let x = stream.read_f32::<LittleEndian>()?;
+            self.position_overrides.insert(node, x);
+        }
+
+        // Named selections are absent in states stored by older versions
+        self.named_selections.clear();
+        let selection_count = stream.read_u32::<LittleEndian>()?;
+        for _ in 0..selection_count {
+            let name = String::deserialize(stream)?;
+            let node_count = stream.read_u32::<LittleEndian>()?;
+            let nodes = (0..node_count)
+                .map(|_| Ok(stream.read_u32::<LittleEndian>()? as NodeID))
+                .collect::<io::Result<Vec<_>>>()?;
+            self.named_selections.insert(name, nodes);
+        }
+        Ok(())
+    }
+}
+
+/// The positions of the visible elements of a laid out diagram, in diagram coordinates
+pub struct SectionGeometry {
+    pub bounds: Rectangle,
+    /// The vertical center of every visible level
+    pub levels: HashMap<LevelNo, f32>,
+    /// The center of the group containing each visible node
+    pub nodes: HashMap<NodeID, Point>,
+}
+
+impl<
+        R: DiagramRenderer<L>,
         L: LayoutRules<G = G, T = G::T, LS = G::LL, NS = G::GL, Tracker = G::Tracker>,
         G: GroupedGraphStructure,
     > Drawer<R, L, G>
@@ -77,6 +232,22 @@ where
             },
             transform: Transformation::default(),
             selection: (Vec::new(), Vec::new()),
+            focus: None,
+            edge_styles: HashMap::new(),
+            default_edge_styles: HashMap::new(),
+            resolved_edge_styles: HashMap::new(),
+            selected_edges: HashSet::new(),
+            annotations: HashMap::new(),
+            next_annotation_id: 0,
+            markers: Vec::new(),
+            position_overrides: HashMap::new(),
+            export_grid: None,
+            views: HashMap::new(),
+            next_view_id: 0,
+            renderer_settings: HashMap::new(),
+            history: ChangeHistory::new(),
+            named_selections: HashMap::new(),
+            layer_window: LayerWindow::new(),
         }
     }
 
@@ -84,25 +255,362 @@ where
         &mut self.layout_rules
     }
 
+    pub fn get_renderer(&mut self) -> &mut R {
+        &mut self.renderer
+    }
+
+    /// Applies the given setting to the renderers of the drawer and all of its views, replacing the setting of the same name for views added later
+    pub fn configure_renderers(&mut self, name: &str, configure: impl Fn(&mut R) + 'static) {
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            configure(renderer);
+        }
+        self.renderer_settings
+            .insert(name.to_string(), Box::new(configure));
+    }
+
+    /// Replaces the renderer that draws the layout of this drawer, sending it the settings, transform, styles and shown layout of the drawer
+    pub fn set_renderer(&mut self, mut renderer: R) {
+        for configure in self.renderer_settings.values() {
+            configure(&mut renderer);
+        }
+        renderer.set_transform(self.transform.clone());
+        renderer.set_edge_styles(&self.resolved_edge_styles, &self.selected_edges);
+        self.renderer = renderer;
+        match self.history.get_shown() {
+            Some(layout) if self.history.get_position().is_some() => {
+                self.renderer.update_layout(layout);
+                self.update_annotations();
+            }
+            _ => self.update_rendered_layout(),
+        }
+    }
+
+    /// Adds a view that renders the layout of this drawer using the given renderer, sharing the selection and styles of the drawer
+    pub fn add_view(&mut self, mut renderer: R) -> ViewID {
+        for configure in self.renderer_settings.values() {
+            configure(&mut renderer);
+        }
+        renderer.update_layout(&self.layout);
+        let (selected, partially_selected) = self.get_selection_groups(&self.selection.0[..]);
+        let (hovered, partially_hovered) = self.get_selection_groups(&self.selection.1[..]);
+        renderer.select_groups(
+            (
+                &selected[..],
+                &partially_selected[..],
+                &hovered[..],
+                &partially_hovered[..],
+            ),
+            (&[], &[], &[], &[]),
+        );
+        renderer.set_edge_styles(&self.resolved_edge_styles, &self.selected_edges);
+
+        let id = self.next_view_id;
+        self.next_view_id += 1;
+        self.views.insert(
+            id,
+            View {
+                renderer,
+                transform: Transformation::default(),
+            },
+        );
+        self.update_annotations();
+        id
+    }
+
+    pub fn remove_view(&mut self, view: ViewID) -> bool {
+        self.views.remove(&view).is_some()
+    }
+
+    pub fn set_view_transform(
+        &mut self,
+        view: ViewID,
+        width: u32,
+        height: u32,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) {
+        let Some(view) = self.views.get_mut(&view) else {
+            return;
+        };
+        let transform = Transformation {
+            width: width as f32,
+            height: height as f32,
+            scale,
+            position: Point { x, y },
+            angle: 0.0,
+        };
+        view.transform = transform.clone();
+        view.renderer.set_transform(transform);
+    }
+
+    pub fn render_view(&mut self, view: ViewID, time: u32) {
+        if let Some(view) = self.views.get_mut(&view) {
+            view.renderer.render(time);
+        }
+    }
+
     pub fn get_current_layout(&self) -> DiagramLayout<L::T, L::NS, L::LS> {
         self.layout.clone()
     }
 
-    pub fn layout(&mut self, time: u32) {
-        self.graph.get().refresh();
+    /// Sets the spacing of the grid that the coordinates of exported layouts are snapped to, or disables snapping if no positive spacing is given
+    pub fn set_export_grid(&mut self, grid: Option<f32>) {
+        self.export_grid = grid.filter(|&grid| grid > 0.);
+    }
+
+    /// Retrieves the current layout as it should be exported, with its coordinates snapped to the export grid
+    pub fn get_export_layout(&self) -> DiagramLayout<L::T, L::NS, L::LS> {
+        let mut layout = self.layout.clone();
+        if let Some(grid) = self.export_grid {
+            layout.snap_to_grid(grid);
+        }
+        layout
+    }
+
+    /// Lays out the graph again and sends the new layout to the renderers, where the current layout is kept if the layout fails
+    pub fn layout(&mut self, time: u32) -> Result<(), VizError> {
+        self.compute_layout(time)?;
+        self.update_rendered_layout();
+        Ok(())
+    }
+
+    /// Lays out the graph again without sending the new layout to the renderers, where the current layout is kept if the layout fails
+    pub fn compute_layout(&mut self, time: u32) -> Result<(), VizError> {
+        let changes = self.graph.get().refresh();
         self.layout =
             self.layout_rules
-                .layout(&*self.graph.read(), &self.layout, &self.sources, time);
+                .layout(&*self.graph.read(), &self.layout, &self.sources, time)?;
         let used_ids = self.layout.groups.keys().collect::<HashSet<_>>();
 
         self.sources.retain(|group_id| used_ids.contains(&group_id));
         self.sources.remove_sources();
 
+        self.apply_position_overrides();
+        if !changes.is_empty() || self.history.is_empty() {
+            self.history.record(time, changes, self.layout.clone());
+        }
+        self.history.scrub(None);
+        Ok(())
+    }
+
+    /// Retrieves the recorded states of the diagram, together with the changes that led to them
+    pub fn get_history(&self) -> Vec<HistoryEntryData> {
+        self.history
+            .get_entries()
+            .iter()
+            .map(|entry| HistoryEntryData {
+                time: entry.time,
+                changes: entry
+                    .changes
+                    .iter()
+                    .map(|change| change.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Shows the state at the given index of the history instead of the current layout, or the current layout again if no index (or the last index) is given. Returns whether an earlier state is shown.
+    pub fn scrub_history(&mut self, index: Option<usize>) -> bool {
+        self.history.scrub(index);
+        if self.history.get_position().is_none() {
+            self.update_rendered_layout();
+            return false;
+        }
+
+        // The groups of earlier states need not correspond to the current groups, hence the selection is hidden until the current layout is shown again
+        let selection = self.selection.clone();
+        self.select_nodes(&[], &[]);
+        self.selection = selection;
+        if let Some(layout) = self.history.get_shown() {
+            for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+                renderer.update_layout(layout);
+            }
+        }
+        true
+    }
+
+    /// Sends the current layout to the renderer, while preserving the selection
+    fn update_rendered_layout(&mut self) {
+        self.layer_window.index(&self.layout);
+        let old_selection = self.selection.clone();
+        self.select_nodes(&[], &[]);
+        let visible = self.transform.get_visible_x_range();
+        match self.layer_window.materialize(&self.layout, visible) {
+            Some(layout) => self.renderer.update_layout(&layout),
+            None => self.renderer.update_layout(&self.layout),
+        }
+        for view in self.views.values_mut() {
+            view.renderer.update_layout(&self.layout);
+        }
+        self.select_nodes(&old_selection.0[..], &old_selection.1[..]);
+        self.update_annotations();
+    }
+
+    /// Sets the number of groups that a layer needs to exceed before the renderer only receives the geometry of its groups around the camera, or disables this if no threshold is given
+    pub fn set_layer_window_threshold(&mut self, threshold: Option<usize>) {
+        self.layer_window.set_threshold(threshold);
+        if self.history.get_position().is_none() {
+            self.update_rendered_layout();
+        }
+    }
+
+    /// Materializes the groups of windowed layers around the camera again, once it left the window whose geometry the renderer holds
+    fn update_layer_window(&mut self) {
+        let visible = self.transform.get_visible_x_range();
+        if self.history.get_position().is_some() || !self.layer_window.needs_update(visible) {
+            return;
+        }
         let old_selection = self.selection.clone();
         self.select_nodes(&[], &[]);
-        self.renderer.update_layout(&self.layout);
+        if let Some(layout) = self.layer_window.materialize(&self.layout, visible) {
+            self.renderer.update_layout(&layout);
+        }
         self.select_nodes(&old_selection.0[..], &old_selection.1[..]);
     }
+
+    /// Moves the given group by the given offset in the current layout, where the bend points of its edges move along proportionally to their distance from the group. The next layout positions the group according to the layout rules again.
+    pub fn move_group(&mut self, group: NodeGroupID, dx: f32, dy: f32) {
+        self.shift_group(group, Point { x: dx, y: dy });
+        self.update_rendered_groups(&HashSet::from([group]));
+    }
+
+    /// Updates only the given groups in the renderers, falling back to updating the whole layout if a renderer does not support this
+    fn update_rendered_groups(&mut self, groups: &HashSet<NodeGroupID>) {
+        // The moved groups may enter or leave the window of a giant layer, which requires indexing it again
+        let mut updated = !self.layer_window.is_active();
+        if updated {
+            for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+                updated &= renderer.update_groups(&self.layout, groups);
+            }
+        }
+        if updated {
+            self.update_annotations();
+        } else {
+            self.update_rendered_layout();
+        }
+    }
+
+    /// Moves the target position of the given group in the current layout by the given offset, together with the bend points of its edges
+    fn shift_group(&mut self, group: NodeGroupID, delta: Point) {
+        if !self.layout.groups.contains_key(&group) {
+            return;
+        }
+        for (&from, group_layout) in self.layout.groups.iter_mut() {
+            if from == group {
+                group_layout.position.new = group_layout.position.new + delta;
+            }
+            for (edge_data, edge) in group_layout.edges.iter_mut() {
+                let (moves_start, moves_end) = (from == group, edge_data.to == group);
+                if !moves_start && !moves_end {
+                    continue;
+                }
+                shift_edge_points(edge, |fraction| {
+                    let start_weight = if moves_start { 1. - fraction } else { 0. };
+                    let end_weight = if moves_end { fraction } else { 0. };
+                    (start_weight + end_weight) * delta
+                });
+            }
+        }
+    }
+
+    /// Aligns the groups containing the given nodes horizontally, and keeps them at these positions in subsequent layouts
+    pub fn align_groups(&mut self, nodes: &[NodeID], alignment: HorizontalAlignment) {
+        let rects = self.get_group_rects(nodes);
+        let left = rects.iter().map(|(_, rect)| rect.x).fold(f32::INFINITY, f32::min);
+        let right = rects
+            .iter()
+            .map(|(_, rect)| rect.x + rect.width)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let positions = rects
+            .iter()
+            .map(|(group, rect)| {
+                let x = match alignment {
+                    HorizontalAlignment::Left => left + 0.5 * rect.width,
+                    HorizontalAlignment::Center => 0.5 * (left + right),
+                    HorizontalAlignment::Right => right - 0.5 * rect.width,
+                };
+                (*group, x)
+            })
+            .collect_vec();
+        self.override_positions(positions);
+    }
+
+    /// Spreads the groups containing the given nodes such that their centers are evenly spaced between the leftmost and rightmost of these groups, and keeps them at these positions in subsequent layouts
+    pub fn distribute_groups(&mut self, nodes: &[NodeID]) {
+        let centers = self
+            .get_group_rects(nodes)
+            .into_iter()
+            .map(|(group, rect)| (group, rect.x + 0.5 * rect.width))
+            .sorted_by(|(_, a), (_, b)| a.total_cmp(b))
+            .collect_vec();
+        if centers.len() < 3 {
+            return;
+        }
+        let first = centers[0].1;
+        let step = (centers[centers.len() - 1].1 - first) / (centers.len() - 1) as f32;
+        let positions = centers
+            .iter()
+            .enumerate()
+            .map(|(index, &(group, _))| (group, first + index as f32 * step))
+            .collect_vec();
+        self.override_positions(positions);
+    }
+
+    /// Removes the manual positions of the groups containing the given nodes, such that the next layout positions them according to the layout rules again
+    pub fn reset_group_positions(&mut self, nodes: &[NodeID]) {
+        let graph = self.graph.read();
+        let groups = nodes
+            .iter()
+            .map(|&node| graph.get_group(node))
+            .collect::<HashSet<_>>();
+        self.position_overrides
+            .retain(|&node, _| !groups.contains(&graph.get_group(node)));
+    }
+
+    /// Retrieves the bounds of the shown groups that contain the given nodes
+    fn get_group_rects(&self, nodes: &[NodeID]) -> Vec<(NodeGroupID, Rectangle)> {
+        let graph = self.graph.read();
+        nodes
+            .iter()
+            .map(|&node| graph.get_group(node))
+            .unique()
+            .filter_map(|group| Some((group, self.layout.groups.get(&group)?.get_rect(None))))
+            .collect()
+    }
+
+    /// Moves the given groups to the given horizontal centers, and stores these such that they are reapplied after every layout
+    fn override_positions(&mut self, positions: Vec<(NodeGroupID, f32)>) {
+        for (group, x) in positions {
+            // The node with the lowest ID represents the group, such that the position is kept when the group is altered
+            let Some(node) = self.graph.read().get_nodes_of_group(group).into_iter().min() else {
+                continue;
+            };
+            self.position_overrides.insert(node, x);
+        }
+        self.apply_position_overrides();
+        self.update_rendered_layout();
+    }
+
+    /// Moves the groups of all nodes with manual positions to these positions in the current layout
+    fn apply_position_overrides(&mut self) {
+        let shifts = {
+            let graph = self.graph.read();
+            self.position_overrides
+                .iter()
+                .sorted_by_key(|(&node, _)| node)
+                .filter_map(|(&node, &x)| {
+                    let group = graph.get_group(node);
+                    Some((group, x - self.layout.groups.get(&group)?.position.new.x))
+                })
+                .unique_by(|&(group, _)| group)
+                .collect_vec()
+        };
+        for (group, dx) in shifts {
+            self.shift_group(group, Point { x: dx, y: 0. });
+        }
+    }
+
     pub fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) {
         let transform = Transformation {
             width: width as f32,
@@ -113,23 +621,387 @@ where
         };
         self.transform = transform.clone();
         self.renderer.set_transform(transform);
+        self.update_layer_window();
     }
 
-    pub fn render(&mut self, time: u32) {
-        self.renderer.render(time);
+    pub fn get_transform(&self) -> Transformation {
+        self.transform.clone()
     }
 
-    pub fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
-        let area = area.transform(self.transform.get_inverse_matrix());
+    /// Sets the style overrides of the given edge, or removes them if the style is empty
+    pub fn set_edge_style(&mut self, edge: GroupEdgeID, style: EdgeStyle) {
+        if style.is_empty() {
+            self.edge_styles.remove(&edge);
+        } else {
+            self.edge_styles.insert(edge, style);
+        }
+        self.update_edge_styles();
+    }
+
+    /// Sets the styles of edges that are derived from the diagram's data, such as edge weights
+    pub fn set_default_edge_styles(&mut self, styles: HashMap<GroupEdgeID, EdgeStyle>) {
+        self.default_edge_styles = styles;
+        self.update_edge_styles();
+    }
+
+    /// Retrieves the styles of all edges, in which the user's overrides are combined with the default styles
+    pub fn get_edge_styles(&self) -> &HashMap<GroupEdgeID, EdgeStyle> {
+        &self.resolved_edge_styles
+    }
+
+    fn update_edge_styles(&mut self) {
+        let mut styles = self.default_edge_styles.clone();
+        for (edge, style) in &self.edge_styles {
+            let resolved = match styles.get(edge) {
+                Some(default) => style.or(default),
+                None => style.clone(),
+            };
+            styles.insert(*edge, resolved);
+        }
+        self.resolved_edge_styles = styles;
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            renderer.set_edge_styles(&self.resolved_edge_styles, &self.selected_edges);
+        }
+    }
+
+    /// Adds an annotation at the given position in diagram coordinates, which moves along with the group of the anchor node if given
+    pub fn add_annotation(
+        &mut self,
+        anchor: Option<NodeID>,
+        position: Point,
+        kind: AnnotationKind,
+    ) -> AnnotationID {
+        // Anchors that are not shown can not provide a reference position, hence the position is kept fixed instead
+        let anchor_center = anchor.and_then(|node| Some((node, self.get_anchor_center(node)?)));
+        let (anchor, offset) = match anchor_center {
+            Some((node, center)) => (Some(node), position - center.new),
+            None => (None, position),
+        };
+        let id = self.next_annotation_id;
+        self.next_annotation_id += 1;
+        self.annotations.insert(
+            id,
+            Annotation {
+                anchor,
+                offset,
+                kind,
+            },
+        );
+        self.update_annotations();
+        id
+    }
+
+    pub fn remove_annotation(&mut self, id: AnnotationID) -> bool {
+        let removed = self.annotations.remove(&id).is_some();
+        if removed {
+            self.update_annotations();
+        }
+        removed
+    }
+
+    /// Replaces the markers that are drawn along with the annotations
+    pub fn set_markers(&mut self, markers: Vec<Annotation>) {
+        self.markers = markers;
+        self.update_annotations();
+    }
+
+    /// Retrieves the center of the group that contains the given node, if this group is shown
+    fn get_anchor_center(&self, node: NodeID) -> Option<Transition<Point>> {
+        let group = self.layout.groups.get(&self.graph.read().get_group(node))?;
+        let half_height = Transition {
+            old: Point {
+                x: 0.,
+                y: 0.5 * group.size.old.y,
+            },
+            new: Point {
+                x: 0.,
+                y: 0.5 * group.size.new.y,
+            },
+            ..group.size
+        };
+        Some(&group.position + &half_height)
+    }
+
+    /// Resolves the positions of all annotations in the current layout, and sends them to the renderer
+    fn update_annotations(&mut self) {
+        let shapes = self.get_annotation_shapes();
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            renderer.set_annotations(&shapes);
+        }
+    }
+
+    /// Retrieves the annotations and markers resolved to their positions in the current layout, including the breaks marking elided levels, such that exporters can draw them as well
+    pub fn get_annotation_shapes(&self) -> Vec<AnnotationShape> {
+        self.annotations
+            .iter()
+            .sorted_by_key(|&(&id, _)| id)
+            .map(|(_, annotation)| annotation)
+            .chain(self.markers.iter())
+            .filter_map(|annotation| {
+                let (position, exists) = match annotation.anchor {
+                    Some(node) => {
+                        let group = self.graph.read().get_group(node);
+                        let exists = self.layout.groups.get(&group)?.exists;
+                        let center = self.get_anchor_center(node)?;
+                        (&center + &Transition::plain(annotation.offset), exists)
+                    }
+                    None => (Transition::plain(annotation.offset), Transition::plain(1.)),
+                };
+                Some(AnnotationShape {
+                    position,
+                    exists,
+                    kind: annotation.kind.clone(),
+                })
+            })
+            .chain(self.get_break_shapes())
+            .collect()
+    }
+
+    /// Obtains a zig-zag line across the width of the diagram for every layer of elided levels, labeled with the number of levels
+    fn get_break_shapes(&self) -> Vec<AnnotationShape> {
+        let Some((min_x, max_x)) = self
+            .layout
+            .groups
+            .values()
+            .filter(|group| group.exists.new > 0.)
+            .map(|group| {
+                let rect = group.get_rect(None);
+                (rect.x, rect.x + rect.width)
+            })
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+        else {
+            return Vec::new();
+        };
+        let center = |top: f32, bottom: f32| Point {
+            x: 0.5 * (min_x + max_x),
+            y: 0.5 * (top + bottom),
+        };
+        self.layout
+            .layers
+            .iter()
+            .filter(|layer| layer.elided)
+            .map(|layer| AnnotationShape {
+                position: Transition {
+                    old: center(layer.top.old, layer.bottom.old),
+                    new: center(layer.top.new, layer.bottom.new),
+                    old_time: layer.top.old_time,
+                    duration: layer.top.duration,
+                },
+                exists: layer.exists,
+                kind: AnnotationKind::Break(
+                    max_x - min_x + 1.,
+                    format!("{} levels", layer.end_layer - layer.start_layer),
+                ),
+            })
+            .collect()
+    }
+
+    /// Selects the given edges themselves, independent of the selection of nodes
+    pub fn select_edges(&mut self, edges: &[GroupEdgeID]) {
+        self.selected_edges = edges.iter().cloned().collect();
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            renderer.set_edge_styles(&self.resolved_edge_styles, &self.selected_edges);
+        }
+    }
+
+    /// Retrieves the group that currently contains the given node
+    pub fn get_node_group(&self, node: NodeID) -> NodeGroupID {
+        self.graph.read().get_group(node)
+    }
+
+    /// Retrieves the visible groups and the edges between them as a compressed sparse adjacency structure
+    pub fn get_adjacency(&self) -> AdjacencyData {
         let groups = self
             .layout
             .groups
             .iter()
-            .filter(|(_, node_layout)| node_layout.get_rect(None).overlaps(&area))
-            .map(|(&group_id, _)| group_id);
+            .filter(|(_, group)| group.exists.new > 0.)
+            .map(|(&group_id, _)| group_id)
+            .sorted()
+            .collect_vec();
+        let indices_of: HashMap<NodeGroupID, u32> = groups
+            .iter()
+            .enumerate()
+            .map(|(index, &group_id)| (group_id, index as u32))
+            .collect();
+
+        let mut out = AdjacencyData {
+            groups: groups.iter().map(|&group_id| group_id as u32).collect(),
+            indptr: vec![0],
+            indices: Vec::new(),
+            edge_types: Vec::new(),
+        };
+        for group_id in groups {
+            let edges = self.layout.groups[&group_id]
+                .edges
+                .iter()
+                .filter(|(_, edge)| edge.exists.new > 0.)
+                .filter_map(|(edge_data, _)| {
+                    let &target = indices_of.get(&edge_data.to)?;
+                    Some((target, edge_data.edge_type.index))
+                })
+                .sorted();
+            for (target, edge_type) in edges {
+                out.indices.push(target);
+                out.edge_types.push(edge_type);
+            }
+            out.indptr.push(out.indices.len() as u32);
+        }
+        out
+    }
+
+    /// Retrieves the positions of the visible levels and nodes, such that the diagram can be aligned with other diagrams
+    pub fn get_geometry(&self) -> SectionGeometry {
+        let graph = self.graph.read();
+        let mut out = SectionGeometry {
+            bounds: Rectangle::new(0., 0., 0., 0.),
+            levels: HashMap::new(),
+            nodes: HashMap::new(),
+        };
+        let mut min = Point {
+            x: f32::INFINITY,
+            y: f32::INFINITY,
+        };
+        let mut max = -1. * min;
+        for (&group_id, group) in self
+            .layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.)
+        {
+            let rect = group.get_rect(None);
+            min.x = min.x.min(rect.x);
+            min.y = min.y.min(rect.y);
+            max.x = max.x.max(rect.x + rect.width);
+            max.y = max.y.max(rect.y + rect.height);
+
+            let center = rect.pos() + 0.5 * rect.size();
+            for node in graph.get_nodes_of_group(group_id) {
+                out.nodes.insert(node, center);
+            }
+        }
+        if !out.nodes.is_empty() {
+            out.bounds = Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y);
+        }
+
+        for layer in self.layout.layers.iter().filter(|layer| layer.exists.new > 0.) {
+            let center = 0.5 * (layer.top.new + layer.bottom.new);
+            for level in layer.start_layer..=layer.end_layer {
+                out.levels.insert(level, center);
+            }
+        }
+        out
+    }
+
+    /// Moves the focus from the focused node to a neighbouring visible node in the given direction, or to the leftmost root if no visible node is focused yet
+    pub fn navigate(&mut self, direction: NavigationDirection) -> Option<FocusData> {
+        let graph = self.graph.read();
+        let groups = &self.layout.groups;
+        let is_visible = |group: &NodeGroupID| {
+            groups
+                .get(group)
+                .map_or(false, |layout| layout.exists.new > 0.)
+        };
+        let leftmost = |candidates: Vec<NodeGroupID>| {
+            candidates
+                .into_iter()
+                .filter(is_visible)
+                .min_by(|a, b| groups[a].position.new.x.total_cmp(&groups[b].position.new.x))
+        };
+
+        let current = self.focus.map(|node| graph.get_group(node)).filter(is_visible);
+        let target = match (current, direction) {
+            (None, _) => leftmost(graph.get_roots()),
+            (Some(group), NavigationDirection::Parent) => {
+                leftmost(graph.get_parents(group).iter().map(|edge| edge.to).collect())
+            }
+            (Some(group), NavigationDirection::LowChild | NavigationDirection::HighChild) => {
+                // Edge index 0 represents the true edge, and edge index 1 the false edge
+                let index = if direction == NavigationDirection::LowChild { 1 } else { 0 };
+                graph
+                    .get_children(group)
+                    .iter()
+                    .filter(|edge| edge.edge_type.index == index)
+                    .map(|edge| edge.to)
+                    .find(is_visible)
+            }
+            (Some(group), _) => {
+                let level = graph.get_level_range(group).0;
+                let siblings = groups
+                    .iter()
+                    .filter(|(_, layout)| layout.exists.new > 0. && layout.level_range.0 == level)
+                    .sorted_by(|(_, a), (_, b)| a.position.new.x.total_cmp(&b.position.new.x))
+                    .map(|(&group_id, _)| group_id)
+                    .collect_vec();
+                let index = siblings.iter().position(|&sibling| sibling == group)?;
+                let count = siblings.len();
+                Some(if direction == NavigationDirection::NextSibling {
+                    siblings[(index + 1) % count]
+                } else {
+                    siblings[(index + count - 1) % count]
+                })
+            }
+        }?;
+        let node = graph.get_nodes_of_group(target).into_iter().min()?;
+        drop(graph);
+
+        let rect = self.layout.groups[&target].get_rect(None);
+        let center = rect.pos() + 0.5 * rect.size();
+        let (x, y, _) = self
+            .transform
+            .get_matrix()
+            .mul_vec3((center.x, center.y, 0.));
+        self.focus = Some(node);
+        Some(FocusData {
+            node,
+            group: target,
+            x,
+            y,
+        })
+    }
+
+    pub fn render(&mut self, time: u32) {
+        self.renderer.render(time);
+    }
+
+    pub fn get_nodes(&self, area: Rectangle, max_group_expansion: usize) -> Vec<NodeID> {
+        let area = area.transform(self.transform.get_inverse_matrix());
+        self.get_nodes_in(&self.renderer, area, max_group_expansion)
+    }
+
+    /// Retrieves the nodes in the given area in screen space of the given view
+    pub fn get_view_nodes(
+        &self,
+        view: ViewID,
+        area: Rectangle,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID> {
+        let Some(view) = self.views.get(&view) else {
+            return Vec::new();
+        };
+        let area = area.transform(view.transform.get_inverse_matrix());
+        self.get_nodes_in(&view.renderer, area, max_group_expansion)
+    }
+
+    /// Retrieves the nodes in the given area in diagram coordinates, as picked by the given renderer
+    fn get_nodes_in(
+        &self,
+        renderer: &R,
+        area: Rectangle,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID> {
+        let groups = renderer.pick(&area).unwrap_or_else(|| {
+            self.layout
+                .groups
+                .iter()
+                .filter(|(_, node_layout)| node_layout.get_rect(None).overlaps(&area))
+                .map(|(&group_id, _)| group_id)
+                .collect()
+        });
         groups
+            .into_iter()
             .flat_map(|group_id| {
-                console::log!("Selected group: {}", group_id);
+                console::log_trace!("Selected group: {}", group_id);
                 self.graph
                     .read()
                     .get_nodes_of_group(group_id)
@@ -139,6 +1011,221 @@ where
             .collect()
     }
 
+    /// Retrieves the nodes, group boxes and edges in the given area, expanding each group up to at most max_group_expansion nodes
+    pub fn get_elements(&self, area: Rectangle, max_group_expansion: usize) -> ElementsData {
+        let area = area.transform(self.transform.get_inverse_matrix());
+        let graph = self.graph.read();
+        let visible_groups = self
+            .layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.)
+            .sorted_by_key(|&(&group_id, _)| group_id)
+            .collect_vec();
+
+        let mut out = ElementsData {
+            nodes: Vec::new(),
+            groups: Vec::new(),
+            edge_from: Vec::new(),
+            edge_to: Vec::new(),
+            edge_types: Vec::new(),
+            edge_tags: Vec::new(),
+        };
+        for &(&group_id, group) in &visible_groups {
+            if group.get_rect(None).overlaps(&area) {
+                out.groups.push(group_id);
+                out.nodes.extend(
+                    graph
+                        .get_nodes_of_group(group_id)
+                        .into_iter()
+                        .take(max_group_expansion),
+                );
+            }
+        }
+
+        let edge_area = Rectangle::new(
+            area.x - EDGE_HIT_MARGIN,
+            area.y - EDGE_HIT_MARGIN,
+            area.width + 2. * EDGE_HIT_MARGIN,
+            area.height + 2. * EDGE_HIT_MARGIN,
+        );
+        for (group_id, edge_data, points) in self.get_edge_paths() {
+            let hit = points
+                .iter()
+                .tuple_windows()
+                .any(|(&start, &end)| edge_area.overlaps_segment(start, end));
+            if hit {
+                out.edge_from.push(group_id);
+                out.edge_to.push(edge_data.to);
+                out.edge_types.push(edge_data.edge_type.index);
+                out.edge_tags.push(edge_data.edge_type.tag.as_usize());
+            }
+        }
+        out
+    }
+
+    /// Retrieves the points along every visible edge of the current layout, from its start to its end, in the order of their source groups
+    fn get_edge_paths(&self) -> Vec<(NodeGroupID, &EdgeData<G::T>, Vec<Point>)> {
+        let mut out = Vec::new();
+        for (&group_id, group) in self
+            .layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.)
+            .sorted_by_key(|&(&group_id, _)| group_id)
+        {
+            for (edge_data, edge) in &group.edges {
+                if edge.exists.new <= 0. {
+                    continue;
+                }
+                let Some(target) = self.layout.groups.get(&edge_data.to) else {
+                    continue;
+                };
+                if target.exists.new <= 0. {
+                    continue;
+                }
+                let mut points = vec![group.position.new + edge.start_offset.new];
+                points.extend(
+                    edge.points
+                        .iter()
+                        .filter(|point| point.exists.new > 0.)
+                        .map(|point| point.point.new),
+                );
+                points.push(target.position.new + edge.end_offset.new);
+                out.push((group_id, edge_data, points));
+            }
+        }
+        out
+    }
+
+    /// Counts the pairs of edge segments that cross each other in the current layout, such that the readability of layouts can be compared
+    pub fn get_crossing_count(&self) -> usize {
+        let segments = self
+            .get_edge_paths()
+            .into_iter()
+            .flat_map(|(_, _, points)| points.into_iter().tuple_windows::<(_, _)>().collect_vec())
+            .sorted_by(|(a1, a2), (b1, b2)| a1.x.min(a2.x).total_cmp(&b1.x.min(b2.x)))
+            .collect_vec();
+
+        // Segments are sorted by their left end, such that only segments starting before the right end of a segment have to be compared with it
+        let mut count = 0;
+        for (index, &(start, end)) in segments.iter().enumerate() {
+            let right = start.x.max(end.x);
+            count += segments[index + 1..]
+                .iter()
+                .take_while(|(a, b)| a.x.min(b.x) <= right)
+                .filter(|&&other| segments_cross((start, end), other))
+                .count();
+        }
+        count
+    }
+
+    /// Measures the readability of the current layout, such that layouts and layout algorithms can be compared
+    pub fn get_layout_metrics(&self) -> LayoutMetricsData {
+        let paths = self.get_edge_paths();
+        let total_edge_length = paths
+            .iter()
+            .flat_map(|(_, _, points)| points.iter().tuple_windows::<(_, _)>())
+            .map(|(start, end)| start.distance(end))
+            .sum();
+        // Points at which an edge continues in the same direction, such as those of straight dummy chains, are not bends
+        let bends = paths
+            .iter()
+            .flat_map(|(_, _, points)| points.iter().tuple_windows::<(_, _, _)>())
+            .filter(|&(&a, &b, &c)| {
+                let (in_dir, out_dir) = (b - a, c - b);
+                let turn = in_dir.x * out_dir.y - in_dir.y * out_dir.x;
+                turn.abs() > 1e-3 * in_dir.length() * out_dir.length()
+            })
+            .count();
+
+        let rects = self
+            .layout
+            .groups
+            .values()
+            .filter(|group| group.exists.new > 0.)
+            .map(|group| group.get_rect(None))
+            .sorted_by(|a, b| a.x.total_cmp(&b.x))
+            .collect_vec();
+        let mut node_overlaps = 0;
+        for (index, rect) in rects.iter().enumerate() {
+            // Only rectangles that share some area overlap, rather than those that merely touch
+            node_overlaps += rects[index + 1..]
+                .iter()
+                .take_while(|other| other.x < rect.x + rect.width)
+                .filter(|other| {
+                    other.y < rect.y + rect.height && rect.y < other.y + other.height
+                })
+                .count();
+        }
+
+        let bounds = self.get_geometry().bounds;
+        LayoutMetricsData {
+            crossings: self.get_crossing_count(),
+            total_edge_length,
+            bends,
+            aspect_ratio: if bounds.height > 0. {
+                bounds.width / bounds.height
+            } else {
+                0.
+            },
+            node_overlaps,
+        }
+    }
+
+    /// Retrieves the geometry of the visible groups, edges and layers of the current layout, at the end of its transitions
+    pub fn get_layout_data(&self) -> LayoutData {
+        let graph = self.graph.read();
+        let mut out = LayoutData {
+            groups: Vec::new(),
+            group_rects: Vec::new(),
+            group_node_counts: Vec::new(),
+            group_nodes: Vec::new(),
+            edge_from: Vec::new(),
+            edge_to: Vec::new(),
+            edge_types: Vec::new(),
+            edge_point_counts: Vec::new(),
+            edge_points: Vec::new(),
+            edge_curve_offsets: Vec::new(),
+            layer_start_levels: Vec::new(),
+            layer_end_levels: Vec::new(),
+            layer_tops: Vec::new(),
+            layer_bottoms: Vec::new(),
+        };
+        for (&group_id, group) in self
+            .layout
+            .groups
+            .iter()
+            .filter(|(_, group)| group.exists.new > 0.)
+            .sorted_by_key(|&(&group_id, _)| group_id)
+        {
+            let rect = group.get_rect(None);
+            out.groups.push(group_id);
+            out.group_rects
+                .extend([rect.x, rect.y, rect.width, rect.height]);
+            let nodes = graph.get_nodes_of_group(group_id);
+            out.group_node_counts.push(nodes.len());
+            out.group_nodes.extend(nodes);
+        }
+        for (group_id, edge_data, points) in self.get_edge_paths() {
+            out.edge_from.push(group_id);
+            out.edge_to.push(edge_data.to);
+            out.edge_types.push(edge_data.edge_type.index);
+            out.edge_point_counts.push(points.len());
+            out.edge_points
+                .extend(points.iter().flat_map(|point| [point.x, point.y]));
+            let curve_offset = self.layout.groups[&group_id].edges[edge_data].curve_offset.new;
+            out.edge_curve_offsets.push(curve_offset);
+        }
+        for layer in self.layout.layers.iter().filter(|layer| layer.exists.new > 0.) {
+            out.layer_start_levels.push(layer.start_layer);
+            out.layer_end_levels.push(layer.end_layer);
+            out.layer_tops.push(layer.top.new);
+            out.layer_bottoms.push(layer.bottom.new);
+        }
+        out
+    }
+
     pub fn select_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
         if selected_ids == &self.selection.0[..] && hovered_ids == &self.selection.1[..] {
             return;
@@ -166,10 +1253,29 @@ where
             &old_hovered_group_ids[..],
             &old_partially_hovered_group_ids[..],
         );
-        self.renderer.select_groups(selection, old_selection);
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            renderer.select_groups(selection, old_selection);
+        }
 
         self.selection = (Vec::from(selected_ids), Vec::from(hovered_ids));
     }
+    pub fn get_selected_nodes(&self) -> Vec<NodeID> {
+        self.selection.0.clone()
+    }
+    /// Saves the selected nodes under the given name, replacing the selection saved under this name before
+    pub fn save_selection(&mut self, name: String) {
+        self.named_selections.insert(name, self.selection.0.clone());
+    }
+    /// Selects the nodes saved under the given name, returning them if a selection of this name exists
+    pub fn apply_selection(&mut self, name: &str) -> Option<Vec<NodeID>> {
+        let nodes = self.named_selections.get(name)?.clone();
+        let hovered = self.selection.1.clone();
+        self.select_nodes(&nodes, &hovered);
+        Some(nodes)
+    }
+    pub fn get_selection_names(&self) -> Vec<String> {
+        self.named_selections.keys().cloned().sorted().collect()
+    }
     fn get_selection_groups(&self, node_ids: &[NodeID]) -> (Vec<NodeGroupID>, Vec<NodeGroupID>) {
         // TODO: make the graph track sources, and use this info for selection (such that duplicate nodes select all duplications)
 
@@ -192,3 +1298,202 @@ where
         })
     }
 }
+
+impl<
+        R: DiagramRenderer<L>,
+        L: LayoutRules<G = G, T = G::T, LS = G::LL, NS = G::GL, Tracker = G::Tracker>,
+        G: GroupedGraphStructure,
+    > Drawer<R, L, G>
+where
+    G::GL: NodeStyle + LatexNodeStyle,
+    G::LL: LayerStyle + LatexLayerStyle,
+{
+    /// Describes the visible graph as an indented outline, in which every node lists the variable it tests and its low and high subtrees. Nodes that were described before are referred to instead of being repeated.
+    pub fn export_outline(&self) -> String {
+        let graph = self.graph.read();
+        let groups = &self.layout.groups;
+        let mut roots = graph
+            .get_roots()
+            .into_iter()
+            .filter(|group| groups.get(group).map_or(false, |layout| layout.exists.new > 0.))
+            .unique()
+            .collect_vec();
+        roots.sort_by(|a, b| groups[a].position.new.x.total_cmp(&groups[b].position.new.x));
+
+        let mut out = String::new();
+        let mut described = HashSet::new();
+        for (index, &root) in roots.iter().enumerate() {
+            self.write_outline(
+                &*graph,
+                root,
+                &format!("Root {}", index + 1),
+                0,
+                &mut described,
+                &mut out,
+            );
+        }
+        out
+    }
+
+    fn write_outline(
+        &self,
+        graph: &G,
+        group: NodeGroupID,
+        prefix: &str,
+        depth: usize,
+        described: &mut HashSet<NodeGroupID>,
+        out: &mut String,
+    ) {
+        let indent = "  ".repeat(depth);
+        let name = get_outline_name(graph, group);
+        if !described.insert(group) {
+            out.push_str(&format!("{}{}: see {}\n", indent, prefix, name));
+            return;
+        }
+
+        let style = graph.get_group_label(group);
+        if style.is_terminal().is_some() {
+            out.push_str(&format!("{}{}: {}\n", indent, prefix, name));
+            return;
+        }
+        let (start, end) = graph.get_level_range(group);
+        let variables = if start == end {
+            graph.get_level_label(start).get_label()
+        } else {
+            format!(
+                "{} to {}",
+                graph.get_level_label(start).get_label(),
+                graph.get_level_label(end).get_label()
+            )
+        };
+        out.push_str(&format!("{}{}: {}, tests {}\n", indent, prefix, name, variables));
+
+        // Edge index 1 represents the false edge, which is listed first
+        let edges = self.layout.groups[&group]
+            .edges
+            .iter()
+            .filter(|(edge_data, edge)| {
+                edge.exists.new > 0.
+                    && self
+                        .layout
+                        .groups
+                        .get(&edge_data.to)
+                        .map_or(false, |layout| layout.exists.new > 0.)
+            })
+            .map(|(edge_data, _)| (edge_data.edge_type.index, edge_data.to))
+            .sorted_by_key(|&(index, to)| (-index, to));
+        for (index, to) in edges {
+            let edge_name = match index {
+                0 => "high".to_string(),
+                1 => "low".to_string(),
+                _ => format!("edge {}", index),
+            };
+            self.write_outline(graph, to, &edge_name, depth + 1, described, out);
+        }
+    }
+}
+
+fn get_outline_name<G: GroupedGraphStructure>(graph: &G, group: NodeGroupID) -> String
+where
+    G::GL: LatexNodeStyle,
+{
+    let style = graph.get_group_label(group);
+    if let Some((kind, label)) = style.is_terminal() {
+        return match label {
+            Some(label) => format!("{} {}", kind, label),
+            None => kind,
+        };
+    }
+    let nodes = graph.get_nodes_of_group(group);
+    let name = if nodes.len() == 1 && !style.is_group() {
+        format!("node {}", nodes[0])
+    } else {
+        format!("group {} of {} nodes", group, nodes.len())
+    };
+    match style.get_label() {
+        Some(label) if !label.is_empty() => format!("{} \"{}\"", name, label),
+        _ => name,
+    }
+}
+
+impl<
+        L: LayoutRules<G = G, T = G::T, LS = G::LL, NS = G::GL, Tracker = G::Tracker>,
+        G: GroupedGraphStructure,
+    > Drawer<ScreenRenderer<L::T>, L, G>
+where
+    G::GL: NodeStyle + WebglNodeStyle,
+    G::LL: LayerStyle + WebglLayerStyle,
+{
+    /// Renders the given area of the diagram (in diagram coordinates) with the given number of pixels per unit, and retrieves the image as a png data url. The legend is placed to the right of the area if given. The canvas and transform are restored afterwards.
+    pub fn export_region_png(
+        &mut self,
+        area: Rectangle,
+        pixels_per_unit: f32,
+        legend: Option<&Legend>,
+        time: u32,
+    ) -> Option<String> {
+        let area = match legend {
+            Some(legend) => {
+                let (shapes, area) = legend.place_next_to(&area);
+                self.renderer.set_legend(&shapes);
+                area
+            }
+            None => area,
+        };
+        let scale = pixels_per_unit.min(MAX_EXPORT_SIZE / area.width.max(area.height));
+        let width = (area.width * scale).ceil() as u32;
+        let height = (area.height * scale).ceil() as u32;
+        let old_transform = self.transform.clone();
+
+        self.renderer.set_canvas_size(width, height);
+        self.set_transform(
+            width,
+            height,
+            -(area.x + 0.5 * area.width),
+            -(area.y + 0.5 * area.height),
+            scale,
+        );
+        self.render(time);
+        let out = self.renderer.get_png_data_url();
+        if legend.is_some() {
+            self.renderer.set_legend(&Vec::new());
+        }
+
+        let (old_width, old_height) = (old_transform.width as u32, old_transform.height as u32);
+        self.renderer.set_canvas_size(old_width, old_height);
+        self.set_transform(
+            old_width,
+            old_height,
+            old_transform.position.x,
+            old_transform.position.y,
+            old_transform.scale,
+        );
+        self.render(time);
+        out
+    }
+
+    /// Recreates the resources of the renderers after their webgl context was lost and restored
+    pub fn restore_context(&mut self) -> Result<(), JsValue> {
+        for renderer in get_renderers(&mut self.renderer, &mut self.views) {
+            renderer.restore_context()?;
+        }
+        Ok(())
+    }
+}
+
+/// Moves the bend points of the edge by the offset for their relative position along the edge, where 0 is the start and 1 the end of the edge
+fn shift_edge_points(edge: &mut EdgeLayout, get_offset: impl Fn(f32) -> Point) {
+    let count = edge.points.len();
+    for (index, edge_point) in edge.points.iter_mut().enumerate() {
+        let fraction = (index + 1) as f32 / (count + 1) as f32;
+        edge_point.point.new = edge_point.point.new + get_offset(fraction);
+    }
+}
+
+/// Retrieves the renderer of a drawer together with the renderers of its views
+fn get_renderers<'a, R>(
+    renderer: &'a mut R,
+    views: &'a mut HashMap<ViewID, View<R>>,
+) -> impl Iterator<Item = &'a mut R> {
+    std::iter::once(renderer).chain(views.values_mut().map(|view| &mut view.renderer))
+}
@@ -1,6 +1,4 @@
-pub mod diagram_layout;
+pub use oxidd_viz_core::types::util::drawing::*;
+
 pub mod drawer;
-pub mod layout_rules;
-pub mod layouts;
-pub mod renderer;
 pub mod renderers;
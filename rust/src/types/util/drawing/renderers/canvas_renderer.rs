@@ -0,0 +1,683 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use crate::{
+    types::util::{
+        drawing::{
+            annotations::{
+                get_arrow_outline, get_break_zigzag, get_stub_dashes, AnnotationKind,
+                AnnotationShape, ANNOTATION_COLOR, ARROW_WIDTH, BREAK_LABEL_GAP, HIGHLIGHT_OPACITY,
+                STUB_OPACITY,
+            },
+            diagram_layout::DiagramLayout,
+            layout_rules::LayoutRules,
+            legend::LegendShape,
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        },
+        graph_structure::graph_structure::{DrawTag, EdgeType},
+    },
+    util::{
+        color::{Color, TransparentColor},
+        matrix4::Matrix4,
+        point::Point,
+        transformation::Transformation,
+    },
+    wasm_interface::NodeGroupID,
+};
+
+use super::{
+    util::{edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides, Font::Font},
+    webgl::{
+        edge_renderer::{Edge, EdgeRenderingType},
+        layers::layer_renderer::Layer,
+        node_renderer::{Node, NodeRenderingColorConfig},
+    },
+    webgl_renderer::{
+        create_edges, create_node, get_selection_state, LayerRenderingColorConfig,
+        WebglLayerStyle, WebglNodeStyle,
+    },
+};
+
+/// The size of the rounded corners of nodes in world units, matching the webgl node shader
+const CORNER_SIZE: f32 = 0.3;
+/// The distance between a node and its outline, and the width of the outline, in world units
+const OUTLINE_OFFSET: f32 = 0.3;
+const OUTLINE_WIDTH: f32 = 0.2;
+/// The size of the arrowheads of edges relative to the width of the edge
+const EDGE_ARROW_SIZE: f32 = 4.;
+/// The distance of layer labels from the border of the screen in pixels
+const LAYER_LABEL_MARGIN: f64 = 8.;
+
+/// A renderer that uses the 2d context of a canvas to draw the diagram, for environments in which webgl is not available. It is considerably slower than the webgl renderer for large diagrams, since everything is redrawn by the browser in every frame.
+pub struct CanvasRenderer<T: DrawTag> {
+    context: CanvasRenderingContext2d,
+    font: Rc<Font>,
+    node_colors: NodeRenderingColorConfig,
+    layer_colors: LayerRenderingColorConfig,
+    edge_type_ids: HashMap<EdgeType<T>, usize>,
+    edge_types: Vec<EdgeRenderingType>,
+    hidden_edge_types: HashSet<usize>,
+    arrows: EdgeArrows,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    layers: Vec<Layer>,
+    annotations: Vec<AnnotationShape>,
+    legend: Vec<LegendShape>,
+    styles: HashMap<GroupEdgeID, EdgeStyle>,
+    selected_edges: HashSet<GroupEdgeID>,
+    /// The selected, partially selected, hovered and partially hovered groups
+    selection: [HashSet<NodeGroupID>; 4],
+    layer_label_sides: LayerLabelSides,
+    alternating_layer_backgrounds: bool,
+    transform: Transformation,
+    matrix: Matrix4,
+    /// Whether anything changed since the last frame, other than the time
+    dirty: bool,
+    /// The time at which the transitions of the shown elements have finished
+    animation_end: u32,
+    /// The time of the last frame that was drawn, if any
+    rendered_time: Option<u32>,
+}
+
+impl<T: DrawTag> CanvasRenderer<T> {
+    /// Creates a renderer that draws to the given canvas, which fails if the canvas already has a context of another kind
+    pub fn from_canvas(
+        canvas: HtmlCanvasElement,
+        edge_types: HashMap<EdgeType<T>, EdgeRenderingType>,
+        node_colors: NodeRenderingColorConfig,
+        layer_colors: LayerRenderingColorConfig,
+        font: Rc<Font>,
+    ) -> Result<CanvasRenderer<T>, JsValue> {
+        let context = canvas
+            .get_context("2d")?
+            .ok_or("The canvas does not provide a 2d context")?
+            .dyn_into::<CanvasRenderingContext2d>()
+            .map_err(|_| "The canvas does not provide a 2d context")?;
+        let (edge_type_ids, edge_types): (HashMap<EdgeType<T>, usize>, Vec<EdgeRenderingType>) =
+            edge_types
+                .into_iter()
+                .enumerate()
+                .map(|(index, (edge_type, edge_rendering))| ((edge_type, index), edge_rendering))
+                .unzip();
+        let transform = Transformation::default();
+        Ok(CanvasRenderer {
+            context,
+            font,
+            node_colors,
+            layer_colors,
+            edge_type_ids,
+            edge_types,
+            hidden_edge_types: HashSet::new(),
+            arrows: EdgeArrows::None,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            layers: Vec::new(),
+            annotations: Vec::new(),
+            legend: Vec::new(),
+            styles: HashMap::new(),
+            selected_edges: HashSet::new(),
+            selection: Default::default(),
+            layer_label_sides: LayerLabelSides::Left,
+            alternating_layer_backgrounds: true,
+            matrix: transform.get_matrix(),
+            transform,
+            dirty: true,
+            animation_end: 0,
+            rendered_time: None,
+        })
+    }
+
+    /// Hides or shows all edges of the given type, without requiring a new layout
+    pub fn set_edge_type_visibility(&mut self, edge_type: EdgeType<T>, visible: bool) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            if visible {
+                self.hidden_edge_types.remove(&id);
+            } else {
+                self.hidden_edge_types.insert(id);
+            }
+            self.dirty = true;
+        }
+    }
+
+    /// Sets the stroke width of all edges of the given type, in world units
+    pub fn set_edge_type_width(&mut self, edge_type: EdgeType<T>, width: f32) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            self.edge_types[id].width = width;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_edge_arrows(&mut self, arrows: EdgeArrows) {
+        self.arrows = arrows;
+        self.dirty = true;
+    }
+
+    pub fn set_layer_label_sides(&mut self, sides: LayerLabelSides) {
+        self.layer_label_sides = sides;
+        self.dirty = true;
+    }
+
+    pub fn set_alternating_layer_backgrounds(&mut self, alternating: bool) {
+        self.alternating_layer_backgrounds = alternating;
+        self.dirty = true;
+    }
+
+    /// Sets the shapes of the legend drawn next to the diagram, which is only used while exporting images
+    pub fn set_legend(&mut self, shapes: &Vec<LegendShape>) {
+        self.legend = shapes.clone();
+        self.dirty = true;
+    }
+
+    /// Resizes the drawing buffer of the canvas, e.g. to temporarily render an export at a different resolution
+    pub fn set_canvas_size(&mut self, width: u32, height: u32) {
+        if let Some(canvas) = self.context.canvas() {
+            canvas.set_width(width);
+            canvas.set_height(height);
+        }
+        self.dirty = true;
+    }
+
+    /// Ensures that the next frame is drawn, even if nothing changed, e.g. because the canvas was cleared externally
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Retrieves the current contents of the canvas as a png data url
+    pub fn get_png_data_url(&self) -> Option<String> {
+        self.context
+            .canvas()?
+            .to_data_url_with_type("image/png")
+            .ok()
+    }
+
+    /// Converts the given point in diagram coordinates to pixel coordinates on the canvas
+    fn to_screen(&self, point: Point) -> (f64, f64) {
+        // The transform maps the screen to [-0.5, 0.5], with the y-axis pointing up
+        let (x, y, _) = self.matrix.mul_vec3((point.x, point.y, 0.));
+        let (width, height) = (self.transform.width, self.transform.height);
+        (((x + 0.5) * width) as f64, ((0.5 - y) * height) as f64)
+    }
+
+    /// Converts the given distance in diagram coordinates to a distance in pixels
+    fn to_pixels(&self, distance: f32) -> f64 {
+        (distance * self.transform.scale) as f64
+    }
+
+    fn set_font(&self, size: f32) {
+        self.context
+            .set_font(&format!("{}px sans-serif", self.to_pixels(size)));
+    }
+
+    fn draw_layers(&self, time: u32) {
+        let context = &self.context;
+        let width = self.transform.width as f64;
+        self.set_font(self.font.text_size());
+        context.set_text_baseline("middle");
+        for layer in &self.layers {
+            let exists = layer.exists.get(time);
+            if exists <= 0. {
+                continue;
+            }
+            let (_, top) = self.to_screen(Point {
+                x: 0.,
+                y: layer.top.get(time),
+            });
+            let (_, bottom) = self.to_screen(Point {
+                x: 0.,
+                y: layer.bottom.get(time),
+            });
+            let even = layer.index.get(time).round() as i32 % 2 == 0;
+            let background = if even || !self.alternating_layer_backgrounds {
+                self.layer_colors.background1
+            } else {
+                self.layer_colors.background2
+            };
+            context.set_global_alpha((exists * background.3) as f64);
+            context.set_fill_style(&to_css_color(&to_opaque(&background)).into());
+            context.fill_rect(0., top, width, bottom - top);
+
+            context.set_global_alpha(exists as f64);
+            context.set_fill_style(&to_css_color(&self.layer_colors.text).into());
+            let center = 0.5 * (top + bottom);
+            if self.layer_label_sides.has_left() {
+                context.set_text_align("left");
+                let _ = context.fill_text(&layer.label, LAYER_LABEL_MARGIN, center);
+            }
+            if self.layer_label_sides.has_right() {
+                context.set_text_align("right");
+                let _ = context.fill_text(&layer.label, width - LAYER_LABEL_MARGIN, center);
+            }
+        }
+        context.set_global_alpha(1.);
+    }
+
+    fn draw_edges(&self, time: u32) {
+        let context = &self.context;
+        context.set_line_cap("round");
+        for edge in &self.edges {
+            let exists = edge.exists.get(time);
+            if exists <= 0. || self.hidden_edge_types.contains(&edge.edge_type) {
+                continue;
+            }
+            let edge_type = &self.edge_types[edge.edge_type];
+            let id = (edge.start_node, edge.end_node, edge.type_index);
+            let style = self.styles.get(&id);
+            let width = style
+                .and_then(|style| style.width)
+                .unwrap_or(edge_type.width);
+            if width <= 0. {
+                continue;
+            }
+            let state = if self.selected_edges.contains(&id) {
+                4.
+            } else {
+                get_selection_state(&self.selection, &[edge.start_node, edge.end_node])
+            };
+            let color = match state as u8 {
+                4 => edge_type.select_color,
+                3 => edge_type.partial_select_color,
+                2 => edge_type.hover_color,
+                1 => edge_type.partial_hover_color,
+                _ => style
+                    .and_then(|style| style.color)
+                    .unwrap_or(edge_type.color),
+            };
+            let color = to_css_color(&color);
+            context.set_global_alpha(exists as f64);
+            context.set_stroke_style(&color.clone().into());
+            context.set_fill_style(&color.into());
+            context.set_line_width(self.to_pixels(width));
+            let dash = if edge_type.dash_transparent > 0. {
+                [edge_type.dash_solid, edge_type.dash_transparent]
+                    .iter()
+                    .map(|&length| JsValue::from_f64(self.to_pixels(length)))
+                    .collect::<js_sys::Array>()
+            } else {
+                js_sys::Array::new()
+            };
+            let _ = context.set_line_dash(&dash);
+
+            let points = std::iter::once(&edge.start)
+                .chain(edge.points.iter())
+                .chain(std::iter::once(&edge.end))
+                .map(|point| point.get(time))
+                .collect::<Vec<_>>();
+            let shift = edge.shift.get(time);
+            context.begin_path();
+            let (x, y) = self.to_screen(points[0]);
+            context.move_to(x, y);
+            for pair in points.windows(2) {
+                let (x, y) = self.to_screen(pair[1]);
+                match get_curve_control(pair[0], pair[1], shift) {
+                    Some(control) => {
+                        let (cx, cy) = self.to_screen(control);
+                        context.quadratic_curve_to(cx, cy, x, y);
+                    }
+                    None => context.line_to(x, y),
+                }
+            }
+            context.stroke();
+
+            let _ = context.set_line_dash(&js_sys::Array::new());
+            let head = match self.arrows {
+                EdgeArrows::None => None,
+                EdgeArrows::End => {
+                    let count = points.len();
+                    Some((points[count - 1], points[count - 1] - points[count - 2]))
+                }
+                EdgeArrows::Middle => {
+                    let index = (points.len() - 1) / 2;
+                    let (from, to) = (points[index], points[index + 1]);
+                    Some((from + (to - from) * 0.5, to - from))
+                }
+            };
+            if let Some((tip, direction)) = head {
+                self.draw_arrow_head(tip, direction, EDGE_ARROW_SIZE * width);
+            }
+        }
+        context.set_global_alpha(1.);
+    }
+
+    /// Fills a triangular arrowhead with its tip at the given point, pointing in the given direction
+    fn draw_arrow_head(&self, tip: Point, direction: Point, size: f32) {
+        let length = direction.length();
+        if length <= 0. {
+            return;
+        }
+        let back = direction * (-size / length);
+        let side = Point {
+            x: -back.y,
+            y: back.x,
+        } * 0.5;
+        let corners = [tip, tip + back + side, tip + back - side];
+        self.fill_polygon(&corners);
+    }
+
+    fn fill_polygon(&self, corners: &[Point]) {
+        let context = &self.context;
+        context.begin_path();
+        for (index, &corner) in corners.iter().enumerate() {
+            let (x, y) = self.to_screen(corner);
+            if index == 0 {
+                context.move_to(x, y);
+            } else {
+                context.line_to(x, y);
+            }
+        }
+        context.close_path();
+        context.fill();
+    }
+
+    fn draw_nodes(&self, time: u32) {
+        let context = &self.context;
+        for node in &self.nodes {
+            let exists = node.exists.get(time);
+            if exists <= 0. {
+                continue;
+            }
+            let center = node.center_position.get(time);
+            let size = node.size.get(time);
+            context.set_global_alpha(exists as f64);
+
+            let outline = node.outline_color.get(time);
+            if outline.3 > 0. {
+                let outline_size = size + Point { x: 2., y: 2. } * OUTLINE_OFFSET;
+                context.set_global_alpha((exists * outline.3) as f64);
+                context.set_stroke_style(&to_css_color(&to_opaque(&outline)).into());
+                context.set_line_width(self.to_pixels(OUTLINE_WIDTH));
+                self.trace_rounded_rect(center, outline_size, CORNER_SIZE + OUTLINE_OFFSET);
+                context.stroke();
+                context.set_global_alpha(exists as f64);
+            }
+
+            let state = get_selection_state(&self.selection, &[node.ID]);
+            let color = node.color.get(time);
+            let color = match state as u8 {
+                4 => color.mix_transparent(&self.node_colors.select),
+                3 => color.mix_transparent(&self.node_colors.partial_select),
+                2 => color.mix_transparent(&self.node_colors.hover),
+                1 => color.mix_transparent(&self.node_colors.partial_hover),
+                _ => color,
+            };
+            context.set_fill_style(&to_css_color(&color).into());
+            self.trace_rounded_rect(center, size, CORNER_SIZE);
+            context.fill();
+
+            if let Some(label) = &node.label {
+                let label_exists = node.label_exists.get(time);
+                if label_exists > 0. {
+                    let (x, y) = self.to_screen(center + node.label_offset.get(time));
+                    context.set_global_alpha((exists * label_exists) as f64);
+                    context.set_fill_style(&to_css_color(&self.node_colors.text).into());
+                    self.set_font(self.font.text_size());
+                    context.set_text_align("center");
+                    context.set_text_baseline("middle");
+                    let _ = context.fill_text(label, x, y);
+                }
+            }
+        }
+        context.set_global_alpha(1.);
+    }
+
+    /// Starts a new path with a rectangle around the given center, whose corners are rounded with the given radius
+    fn trace_rounded_rect(&self, center: Point, size: Point, radius: f32) {
+        let context = &self.context;
+        let (cx, cy) = self.to_screen(center);
+        let (width, height) = (self.to_pixels(size.x), self.to_pixels(size.y));
+        let radius = self.to_pixels(radius).min(0.5 * width).min(0.5 * height).max(0.);
+        let (left, top) = (cx - 0.5 * width, cy - 0.5 * height);
+        let (right, bottom) = (left + width, top + height);
+        context.begin_path();
+        context.move_to(left + radius, top);
+        let _ = context.arc_to(right, top, right, bottom, radius);
+        let _ = context.arc_to(right, bottom, left, bottom, radius);
+        let _ = context.arc_to(left, bottom, left, top, radius);
+        let _ = context.arc_to(left, top, right, top, radius);
+        context.close_path();
+    }
+
+    fn draw_annotations(&self, time: u32) {
+        let context = &self.context;
+        let color = to_css_color(&ANNOTATION_COLOR);
+        context.set_fill_style(&color.into());
+        for annotation in &self.annotations {
+            let exists = annotation.exists.get(time);
+            if exists <= 0. {
+                continue;
+            }
+            let position = annotation.position.get(time);
+            match &annotation.kind {
+                AnnotationKind::Note(text) => {
+                    context.set_global_alpha(exists as f64);
+                    self.draw_text(text, position, "middle");
+                }
+                AnnotationKind::Arrow(delta) => {
+                    context.set_global_alpha(exists as f64);
+                    let outline = get_arrow_outline(position, *delta);
+                    self.fill_polygon(&outline);
+                }
+                AnnotationKind::Highlight(size) => {
+                    context.set_global_alpha((exists * HIGHLIGHT_OPACITY) as f64);
+                    let corner = position - *size * 0.5;
+                    self.fill_polygon(&[
+                        corner,
+                        corner + Point { x: size.x, y: 0. },
+                        corner + *size,
+                        corner + Point { x: 0., y: size.y },
+                    ]);
+                }
+                AnnotationKind::Stub(delta, label) => {
+                    context.set_global_alpha((exists * STUB_OPACITY) as f64);
+                    context.set_stroke_style(&to_css_color(&ANNOTATION_COLOR).into());
+                    context.set_line_width(self.to_pixels(0.5 * ARROW_WIDTH));
+                    context.begin_path();
+                    for (from, to) in get_stub_dashes(position, *delta) {
+                        let (x, y) = self.to_screen(from);
+                        context.move_to(x, y);
+                        let (x, y) = self.to_screen(to);
+                        context.line_to(x, y);
+                    }
+                    context.stroke();
+                    context.set_global_alpha(exists as f64);
+                    self.draw_text(label, position + *delta, "top");
+                }
+                AnnotationKind::Break(width, label) => {
+                    context.set_global_alpha(exists as f64);
+                    context.set_stroke_style(&to_css_color(&ANNOTATION_COLOR).into());
+                    context.set_line_width(self.to_pixels(0.5 * ARROW_WIDTH));
+                    context.begin_path();
+                    let zigzag = get_break_zigzag(position, *width);
+                    for (index, corner) in zigzag.into_iter().enumerate() {
+                        let (x, y) = self.to_screen(corner);
+                        if index == 0 {
+                            context.move_to(x, y);
+                        } else {
+                            context.line_to(x, y);
+                        }
+                    }
+                    context.stroke();
+                    let (x, y) = self.to_screen(
+                        position
+                            + Point {
+                                x: 0.5 * width + BREAK_LABEL_GAP,
+                                y: 0.,
+                            },
+                    );
+                    self.set_font(self.font.text_size());
+                    context.set_text_align("left");
+                    context.set_text_baseline("middle");
+                    let _ = context.fill_text(label, x, y);
+                }
+            }
+        }
+        context.set_global_alpha(1.);
+    }
+
+    fn draw_legend(&self) {
+        let context = &self.context;
+        for shape in &self.legend {
+            match shape {
+                LegendShape::Rect { rect, color } => {
+                    context.set_fill_style(&to_css_color(color).into());
+                    let corner = Point {
+                        x: rect.x,
+                        y: rect.y,
+                    };
+                    self.fill_polygon(&[
+                        corner,
+                        corner + Point {
+                            x: rect.width,
+                            y: 0.,
+                        },
+                        corner + Point {
+                            x: rect.width,
+                            y: rect.height,
+                        },
+                        corner + Point {
+                            x: 0.,
+                            y: rect.height,
+                        },
+                    ]);
+                }
+                LegendShape::Text { position, text } => {
+                    context.set_fill_style(&to_css_color(&self.layer_colors.text).into());
+                    let (x, y) = self.to_screen(*position);
+                    self.set_font(self.font.text_size());
+                    context.set_text_align("left");
+                    context.set_text_baseline("middle");
+                    let _ = context.fill_text(text, x, y);
+                }
+            }
+        }
+    }
+
+    /// Draws the given text horizontally centered at the given point, using the current fill style
+    fn draw_text(&self, text: &str, position: Point, baseline: &str) {
+        let (x, y) = self.to_screen(position);
+        self.set_font(self.font.text_size());
+        self.context.set_text_align("center");
+        self.context.set_text_baseline(baseline);
+        let _ = self.context.fill_text(text, x, y);
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for CanvasRenderer<L::T>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {
+        self.matrix = transform.get_matrix();
+        self.transform = transform;
+        self.dirty = true;
+    }
+
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.animation_end = layout.get_animation_end();
+        self.dirty = true;
+        self.nodes = layout
+            .groups
+            .iter()
+            .map(|(&id, group)| create_node(id, group))
+            .collect();
+        let edge_type_ids = &self.edge_type_ids;
+        self.edges = layout
+            .groups
+            .keys()
+            .flat_map(|&id| create_edges(layout, edge_type_ids, id))
+            .collect();
+        self.layers = layout
+            .layers
+            .iter()
+            .map(|layer| Layer {
+                top: layer.top,
+                bottom: layer.bottom,
+                label: layer.style.new.get_label(),
+                index: layer.index,
+                exists: layer.exists,
+            })
+            .collect();
+    }
+
+    fn select_groups(&mut self, selection: GroupSelection, _old_selection: GroupSelection) {
+        let (select, partial_select, hover, partial_hover) = selection;
+        self.selection = [select, partial_select, hover, partial_hover]
+            .map(|groups| groups.iter().cloned().collect());
+        self.dirty = true;
+    }
+
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.styles = styles.clone();
+        self.selected_edges = selected.clone();
+        self.dirty = true;
+    }
+
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>) {
+        self.annotations = annotations.clone();
+        let annotation_end = annotations
+            .iter()
+            .map(|shape| shape.position.get_end_time().max(shape.exists.get_end_time()))
+            .max()
+            .unwrap_or(0);
+        self.animation_end = self.animation_end.max(annotation_end);
+        self.dirty = true;
+    }
+
+    fn render(&mut self, time: u32) {
+        // Drawing the same state again yields the same image, which is skipped to save power while idle
+        let settled = self
+            .rendered_time
+            .is_some_and(|rendered| rendered >= self.animation_end && time >= self.animation_end);
+        if !self.dirty && settled {
+            return;
+        }
+        self.dirty = false;
+        self.rendered_time = Some(time);
+
+        let (width, height) = (self.transform.width as f64, self.transform.height as f64);
+        self.context.clear_rect(0., 0., width, height);
+        self.draw_layers(time);
+        self.draw_edges(time);
+        self.draw_nodes(time);
+        self.draw_annotations(time);
+        self.draw_legend();
+    }
+}
+
+/// Obtains the control point of a quadratic curve approximating the arc that the webgl edge shader draws for a segment with the given sideways shift, or None if the segment is straight
+fn get_curve_control(start: Point, end: Point, shift: f32) -> Option<Point> {
+    let delta = end - start;
+    let length = delta.length();
+    if shift == 0. || length <= 0. {
+        return None;
+    }
+    let curve_width = shift.abs().min(0.5 * length) * shift.signum();
+    let normal = Point {
+        x: -delta.y / length,
+        y: delta.x / length,
+    };
+    // A quadratic curve reaches half of the distance of its control point from the chord
+    Some(start + delta * 0.5 + normal * (2. * curve_width))
+}
+
+fn to_css_color(color: &Color) -> String {
+    format!(
+        "rgb({}, {}, {})",
+        (color.0 * 255.).round(),
+        (color.1 * 255.).round(),
+        (color.2 * 255.).round()
+    )
+}
+
+fn to_opaque(color: &TransparentColor) -> Color {
+    Color(color.0, color.1, color.2)
+}
@@ -1,4 +1,11 @@
-pub mod latex_renderer;
-pub mod util;
+pub use oxidd_viz_core::types::util::drawing::renderers::*;
+
+pub mod canvas_renderer;
+pub mod screen_renderer;
 pub mod webgl;
 pub mod webgl_renderer;
+#[cfg(feature = "webgpu")]
+pub mod webgpu_renderer;
+
+#[cfg(all(feature = "webgpu", not(target_arch = "wasm32")))]
+compile_error!("The webgpu feature draws to canvases, hence it requires compiling to WebAssembly");
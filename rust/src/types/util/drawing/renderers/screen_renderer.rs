@@ -0,0 +1,322 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use wasm_bindgen::JsValue;
+
+use crate::{
+    types::util::{
+        drawing::{
+            annotations::AnnotationShape,
+            diagram_layout::DiagramLayout,
+            layout_rules::LayoutRules,
+            legend::LegendShape,
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        },
+        graph_structure::graph_structure::{DrawTag, EdgeType},
+    },
+    util::{logging::console, transformation::Transformation},
+    wasm_interface::NodeGroupID,
+};
+
+use super::{
+    canvas_renderer::CanvasRenderer,
+    util::{
+        edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides,
+        layer_pairing::LayerPairing, Font::Font,
+    },
+    webgl::{edge_renderer::EdgeRenderingType, node_renderer::NodeRenderingColorConfig},
+    webgl_renderer::{
+        LayerRenderingColorConfig, RenderSurface, WebglLayerStyle, WebglNodeStyle, WebglRenderer,
+    },
+};
+#[cfg(feature = "webgpu")]
+use super::webgpu_renderer::WebgpuRenderer;
+
+/// The renderer that draws a visualization to the screen, which uses webgl when available and otherwise falls back to the 2d context of the canvas, or uses webgpu for surfaces that were set up for it
+pub enum ScreenRenderer<T: DrawTag> {
+    Webgl(WebglRenderer<T>),
+    Canvas(CanvasRenderer<T>),
+    #[cfg(feature = "webgpu")]
+    Webgpu(WebgpuRenderer<T>),
+}
+
+impl<T: DrawTag> ScreenRenderer<T> {
+    /// Creates a webgl renderer for the given surface, or a canvas renderer if webgl is not available and the surface is a canvas, or a webgpu renderer if the surface is set up for webgpu
+    pub fn from_surface(
+        surface: RenderSurface,
+        edge_types: HashMap<EdgeType<T>, EdgeRenderingType>,
+        node_colors: NodeRenderingColorConfig,
+        layer_colors: LayerRenderingColorConfig,
+        font: Rc<Font>,
+    ) -> Result<ScreenRenderer<T>, JsValue> {
+        let canvas = match surface {
+            RenderSurface::Canvas(ref canvas) => Some(canvas.clone()),
+            RenderSurface::Framebuffer { .. } => None,
+            #[cfg(feature = "webgpu")]
+            RenderSurface::Webgpu(context) => {
+                let background = layer_colors.background1;
+                let renderer = WebgpuRenderer::new(context, edge_types, node_colors, background);
+                return Ok(ScreenRenderer::Webgpu(renderer));
+            }
+        };
+        let webgl = WebglRenderer::from_surface(
+            surface,
+            edge_types.clone(),
+            node_colors.clone(),
+            layer_colors.clone(),
+            font.clone(),
+        );
+        match (webgl, canvas) {
+            (Ok(renderer), _) => Ok(ScreenRenderer::Webgl(renderer)),
+            (Err(error), Some(canvas)) => {
+                console::log_warn!(
+                    "WebGL could not be used, falling back to the slower canvas renderer: {:?}",
+                    error
+                );
+                CanvasRenderer::from_canvas(canvas, edge_types, node_colors, layer_colors, font)
+                    .map(ScreenRenderer::Canvas)
+            }
+            (Err(error), None) => Err(error),
+        }
+    }
+
+    /// Hides or shows the geometry of all edges of the given type, without requiring a new layout
+    pub fn set_edge_type_visibility(&mut self, edge_type: EdgeType<T>, visible: bool) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                renderer.set_edge_type_visibility(edge_type, visible)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                renderer.set_edge_type_visibility(edge_type, visible)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                renderer.set_edge_type_visibility(edge_type, visible)
+            }
+        }
+    }
+
+    /// Sets the stroke width of all edges of the given type, in world units
+    pub fn set_edge_type_width(&mut self, edge_type: EdgeType<T>, width: f32) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.set_edge_type_width(edge_type, width),
+            ScreenRenderer::Canvas(renderer) => renderer.set_edge_type_width(edge_type, width),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => renderer.set_edge_type_width(edge_type, width),
+        }
+    }
+
+    pub fn set_edge_arrows(&mut self, arrows: EdgeArrows) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.set_edge_arrows(arrows),
+            ScreenRenderer::Canvas(renderer) => renderer.set_edge_arrows(arrows),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => {}
+        }
+    }
+
+    pub fn set_layer_label_sides(&mut self, sides: LayerLabelSides) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.set_layer_label_sides(sides),
+            ScreenRenderer::Canvas(renderer) => renderer.set_layer_label_sides(sides),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => {}
+        }
+    }
+
+    /// Sets whether the layer labels stay at the edges of the screen while panning, which the canvas renderer always does
+    pub fn set_sticky_layer_labels(&mut self, sticky: bool) {
+        if let ScreenRenderer::Webgl(renderer) = self {
+            renderer.set_sticky_layer_labels(sticky);
+        }
+    }
+
+    /// Sets whether the layer labels are rotated, which is not supported by the canvas renderer
+    pub fn set_rotated_layer_labels(&mut self, rotated: bool) {
+        if let ScreenRenderer::Webgl(renderer) = self {
+            renderer.set_rotated_layer_labels(rotated);
+        }
+    }
+
+    pub fn set_alternating_layer_backgrounds(&mut self, alternating: bool) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                renderer.set_alternating_layer_backgrounds(alternating)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                renderer.set_alternating_layer_backgrounds(alternating)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => {}
+        }
+    }
+
+    /// Sets whether adjacent layers are paired up, which is not supported by the canvas renderer
+    pub fn set_layer_pairing(&mut self, pairing: LayerPairing) {
+        if let ScreenRenderer::Webgl(renderer) = self {
+            renderer.set_layer_pairing(pairing);
+        }
+    }
+
+    /// Sets the shapes of the legend drawn next to the diagram, which is only used while exporting images and hence ignored by the webgpu renderer
+    pub fn set_legend(&mut self, shapes: &Vec<LegendShape>) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.set_legend(shapes),
+            ScreenRenderer::Canvas(renderer) => renderer.set_legend(shapes),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => {}
+        }
+    }
+
+    /// Resizes the drawing buffer of the canvas, e.g. to temporarily render an export at a different resolution
+    pub fn set_canvas_size(&mut self, width: u32, height: u32) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.set_canvas_size(width, height),
+            ScreenRenderer::Canvas(renderer) => renderer.set_canvas_size(width, height),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => renderer.set_canvas_size(width, height),
+        }
+    }
+
+    /// Recreates all resources after the webgl context was lost and restored, where the canvas and webgpu renderers have nothing to restore
+    pub fn restore_context(&mut self) -> Result<(), JsValue> {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.restore_context(),
+            ScreenRenderer::Canvas(renderer) => {
+                renderer.invalidate();
+                Ok(())
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => Ok(()),
+        }
+    }
+
+    /// Ensures that the next frame is drawn, even if nothing changed, e.g. because the canvas was cleared externally, which the webgpu renderer does every frame regardless
+    pub fn invalidate(&mut self) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.invalidate(),
+            ScreenRenderer::Canvas(renderer) => renderer.invalidate(),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => {}
+        }
+    }
+
+    /// Retrieves the current contents of the canvas as a png data url, which is not supported by the webgpu renderer
+    pub fn get_png_data_url(&self) -> Option<String> {
+        match self {
+            ScreenRenderer::Webgl(renderer) => renderer.get_png_data_url(),
+            ScreenRenderer::Canvas(renderer) => renderer.get_png_data_url(),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(_) => None,
+        }
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for ScreenRenderer<L::T>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::set_transform(renderer, transform)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::set_transform(renderer, transform)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::set_transform(renderer, transform)
+            }
+        }
+    }
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::update_layout(renderer, layout)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::update_layout(renderer, layout)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::update_layout(renderer, layout)
+            }
+        }
+    }
+    fn update_groups(
+        &mut self,
+        layout: &DiagramLayout<L::T, L::NS, L::LS>,
+        groups: &HashSet<NodeGroupID>,
+    ) -> bool {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::update_groups(renderer, layout, groups)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::update_groups(renderer, layout, groups)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::update_groups(renderer, layout, groups)
+            }
+        }
+    }
+    fn render(&mut self, time: u32) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => DiagramRenderer::<L>::render(renderer, time),
+            ScreenRenderer::Canvas(renderer) => DiagramRenderer::<L>::render(renderer, time),
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => DiagramRenderer::<L>::render(renderer, time),
+        }
+    }
+    fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::select_groups(renderer, selection, old_selection)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::select_groups(renderer, selection, old_selection)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::select_groups(renderer, selection, old_selection)
+            }
+        }
+    }
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::set_edge_styles(renderer, styles, selected)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::set_edge_styles(renderer, styles, selected)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::set_edge_styles(renderer, styles, selected)
+            }
+        }
+    }
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>) {
+        match self {
+            ScreenRenderer::Webgl(renderer) => {
+                DiagramRenderer::<L>::set_annotations(renderer, annotations)
+            }
+            ScreenRenderer::Canvas(renderer) => {
+                DiagramRenderer::<L>::set_annotations(renderer, annotations)
+            }
+            #[cfg(feature = "webgpu")]
+            ScreenRenderer::Webgpu(renderer) => {
+                DiagramRenderer::<L>::set_annotations(renderer, annotations)
+            }
+        }
+    }
+}
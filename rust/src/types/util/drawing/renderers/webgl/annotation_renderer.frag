@@ -0,0 +1,10 @@
+#version 300 es
+precision highp float;
+
+in vec4 curColor;
+
+out vec4 outColor;
+
+void main() {
+    outColor = vec4(curColor.rgb * curColor.a, curColor.a);
+}
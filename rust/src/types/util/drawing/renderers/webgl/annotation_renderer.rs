@@ -0,0 +1,229 @@
+use std::rc::Rc;
+
+use itertools::Itertools;
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+
+use crate::{
+    types::util::drawing::{
+        annotations::{
+            get_arrow_outline, get_break_zigzag, get_stub_dashes, AnnotationKind, AnnotationShape,
+            ANNOTATION_COLOR, ARROW_WIDTH, BREAK_LABEL_GAP, HIGHLIGHT_OPACITY, STUB_OPACITY,
+        },
+        renderers::util::Font::Font,
+    },
+    util::{matrix4::Matrix4, point::Point, transition::Transition},
+};
+
+use super::{
+    node_renderer::TextRenderingConfig,
+    text::text_renderer::{Text, TextRenderer},
+    util::{set_animated_data::set_animated_data, vertex_renderer::VertexRenderer},
+};
+
+/// Draws the user placed notes, arrows and highlighted regions, and the stubs marking hidden structure and breaks marking elided levels, on top of the diagram
+pub struct AnnotationRenderer {
+    vertex_renderer: VertexRenderer,
+    text_renderer: TextRenderer,
+    font: Rc<Font>,
+}
+
+impl AnnotationRenderer {
+    pub fn new(context: &WebGl2RenderingContext, text: TextRenderingConfig) -> AnnotationRenderer {
+        let vertex_renderer = VertexRenderer::new(
+            context,
+            include_str!("annotation_renderer.vert"),
+            include_str!("annotation_renderer.frag"),
+        )
+        .unwrap();
+        AnnotationRenderer {
+            vertex_renderer,
+            text_renderer: TextRenderer::new(
+                context,
+                text.font.clone(),
+                text.font_settings,
+                text.screen_height,
+            ),
+            font: text.font,
+        }
+    }
+
+    pub fn set_annotations(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        annotations: &Vec<AnnotationShape>,
+    ) {
+        // Every shape is triangulated relative to its position, such that it moves along with its anchor
+        let triangles = annotations
+            .iter()
+            .flat_map(|annotation| {
+                let (corners, opacity) = match annotation.kind {
+                    AnnotationKind::Note(_) => return Vec::new(),
+                    AnnotationKind::Arrow(delta) => {
+                        let outline = get_arrow_outline(Point::default(), delta);
+                        let corners = [0, 1, 5, 0, 5, 6, 2, 3, 4].map(|index| outline[index]);
+                        (corners.to_vec(), 1.)
+                    }
+                    AnnotationKind::Highlight(size) => {
+                        let (x, y) = (0.5 * size.x, 0.5 * size.y);
+                        let corners = [(x, y), (x, -y), (-x, -y), (x, y), (-x, -y), (-x, y)]
+                            .map(|(x, y)| Point { x, y });
+                        (corners.to_vec(), HIGHLIGHT_OPACITY)
+                    }
+                    AnnotationKind::Stub(delta, _) => {
+                        let length = delta.length().max(f32::EPSILON);
+                        let side = Point {
+                            x: -delta.y,
+                            y: delta.x,
+                        } * (0.25 * ARROW_WIDTH / length);
+                        let corners = get_stub_dashes(Point::default(), delta)
+                            .into_iter()
+                            .flat_map(|(from, to)| {
+                                [
+                                    from + side,
+                                    to + side,
+                                    to - side,
+                                    from + side,
+                                    to - side,
+                                    from - side,
+                                ]
+                            });
+                        (corners.collect(), STUB_OPACITY)
+                    }
+                    AnnotationKind::Break(width, _) => {
+                        let corners = get_break_zigzag(Point::default(), width)
+                            .into_iter()
+                            .tuple_windows()
+                            .flat_map(|(from, to)| {
+                                let delta = to - from;
+                                let side = Point {
+                                    x: -delta.y,
+                                    y: delta.x,
+                                } * (0.25 * ARROW_WIDTH / delta.length().max(f32::EPSILON));
+                                [
+                                    from + side,
+                                    to + side,
+                                    to - side,
+                                    from + side,
+                                    to - side,
+                                    from - side,
+                                ]
+                            });
+                        (corners.collect(), 1.)
+                    }
+                };
+                corners
+                    .into_iter()
+                    .map(|corner| {
+                        let position = Transition {
+                            old: annotation.position.old + corner,
+                            new: annotation.position.new + corner,
+                            ..annotation.position
+                        };
+                        (position, annotation.exists, opacity)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect_vec();
+
+        set_animated_data(
+            "position",
+            triangles.iter().map(|(position, _, _)| *position),
+            |position| [position.x, position.y],
+            context,
+            &mut self.vertex_renderer,
+        );
+        set_animated_data(
+            "exists",
+            triangles.iter().map(|(_, exists, _)| *exists),
+            |exists| [exists],
+            context,
+            &mut self.vertex_renderer,
+        );
+        self.vertex_renderer.set_data(
+            context,
+            "color",
+            &triangles
+                .iter()
+                .flat_map(|(_, _, opacity)| {
+                    [ANNOTATION_COLOR.0, ANNOTATION_COLOR.1, ANNOTATION_COLOR.2, *opacity]
+                })
+                .collect::<Box<_>>(),
+            4,
+        );
+        self.vertex_renderer.send_data(context);
+
+        self.text_renderer.set_texts(
+            context,
+            &annotations
+                .iter()
+                .filter_map(|annotation| {
+                    // Notes are centered at their position, while the labels of stubs are placed below their ends and those of breaks to the right of them
+                    let text = match &annotation.kind {
+                        AnnotationKind::Note(text)
+                        | AnnotationKind::Stub(_, text)
+                        | AnnotationKind::Break(_, text) => text.clone(),
+                        _ => return None,
+                    };
+                    let height = self.font.measure_height(&text);
+                    let width = self.font.measure_width(&text);
+                    let offset = match &annotation.kind {
+                        AnnotationKind::Stub(end, _) => *end + Point {
+                            x: -0.5 * width,
+                            y: -height,
+                        },
+                        AnnotationKind::Break(break_width, _) => Point {
+                            x: 0.5 * break_width + BREAK_LABEL_GAP,
+                            y: -0.5 * height,
+                        },
+                        _ => Point {
+                            x: -0.5 * width,
+                            y: -0.5 * height,
+                        },
+                    };
+                    Some(Text {
+                        text,
+                        position: Transition {
+                            old: annotation.position.old + offset,
+                            new: annotation.position.new + offset,
+                            ..annotation.position
+                        },
+                        exists: annotation.exists,
+                        rotated: false,
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    pub fn set_transform_and_screen_height(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        transform: &Matrix4,
+        screen_height: usize,
+    ) {
+        self.vertex_renderer.set_uniform(context, "transform", |u| {
+            context.uniform_matrix4fv_with_f32_array(u, true, &transform.0)
+        });
+        self.text_renderer
+            .set_transform_and_screen_height(context, transform, screen_height);
+    }
+
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+        self.vertex_renderer
+            .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .render(context, WebGl2RenderingContext::TRIANGLES);
+        self.text_renderer.render(context, time);
+    }
+
+    pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
+        self.vertex_renderer.dispose(context);
+        self.text_renderer.dispose(context);
+    }
+
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)?;
+        self.text_renderer.restore(context)
+    }
+}
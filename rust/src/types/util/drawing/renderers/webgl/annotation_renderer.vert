@@ -0,0 +1,29 @@
+#version 300 es
+precision highp float;
+
+in vec2 position;
+in vec2 positionOld;
+in vec2 positionTransition;
+
+in float exists;
+in float existsOld;
+in vec2 existsTransition;
+
+in vec4 color;
+
+uniform mat4 transform;
+uniform float time;
+
+out vec4 curColor;
+
+float getPer(vec2 transition) {
+    return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
+}
+
+void main() {
+    vec2 curPosition = mix(positionOld, position, getPer(positionTransition));
+    float curExists = mix(existsOld, exists, getPer(existsTransition));
+    curColor = vec4(color.rgb, color.a * curExists);
+
+    gl_Position = transform * vec4(curPosition, 0.0f, 1.0f) * vec4(vec3(2.0f), 1.0f); // 2 to to make the default width and height of the screen 1, instead of 2
+}
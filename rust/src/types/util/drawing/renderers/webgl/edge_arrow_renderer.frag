@@ -0,0 +1,37 @@
+#version 300 es
+precision highp float;
+
+struct EdgeType {
+    vec3 color;
+    vec3 hoverColor;
+    vec3 selectColor;
+    vec3 partialHoverColor;
+    vec3 partialSelectColor;
+    float width;
+    float dashSolid;
+    float dashTransparent;
+};
+
+out vec4 outColor;
+
+in float outType;
+in float outState;
+in vec4 outStyleColor;
+in float curExists;
+
+uniform EdgeType edgeTypes[/*$type_count {*/1/*}*/];
+
+void main() {
+    EdgeType typeData = edgeTypes[int(outType)];
+
+    vec3 typeColor = outStyleColor.a > 0.0f ? outStyleColor.rgb : typeData.color;
+    if(outState >= 1.f)
+        typeColor = typeData.partialHoverColor;
+    if(outState >= 2.f)
+        typeColor = typeData.hoverColor;
+    if(outState >= 3.f)
+        typeColor = typeData.partialSelectColor;
+    if(outState >= 4.f)
+        typeColor = typeData.selectColor;
+    outColor = vec4(typeColor * curExists, curExists);
+}
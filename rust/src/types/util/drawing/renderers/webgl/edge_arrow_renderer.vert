@@ -0,0 +1,115 @@
+#version 300 es
+#define M_PI 3.1415926535897932384626433832795
+
+struct EdgeType {
+    vec3 color;
+    vec3 hoverColor;
+    vec3 selectColor;
+    vec3 partialHoverColor;
+    vec3 partialSelectColor;
+    float width;
+    float dashSolid;
+    float dashTransparent;
+};
+
+in vec2 start;
+in vec2 startOld;
+in vec2 startTransition;
+
+in vec2 end;
+in vec2 endOld;
+in vec2 endTransition;
+
+in float curveOffset;
+in float curveOffsetOld;
+in vec2 curveOffsetTransition;
+
+in float exists;
+in float existsOld;
+in vec2 existsTransition;
+
+in float type;
+in float state;
+in float arrowPer; // The fraction along the segment at which the arrow is placed, where 1 represents the end of the edge
+in vec4 styleColor; // The override color of this edge, where an alpha of 0 indicates the type's color is used
+in float styleWidth; // The override width of this edge, where 0 indicates the type's width is used
+in float selected; // Whether this edge itself is selected, independent of the selection of its nodes
+out float outType;
+out float outState;
+out vec4 outStyleColor;
+out float curExists;
+
+uniform EdgeType edgeTypes[/*$type_count {*/1/*}*/];
+uniform mat4 transform;
+uniform float time;
+uniform float arrowSize;
+uniform float endOffset; // The distance between the end of the edge and the tip of its arrow, such that the arrow is not covered by the node
+
+float getPer(vec2 transition) {
+    return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
+}
+
+// Ensures that the output angle is specified such that it's greater than the reference angle
+float getAngle(vec2 point, vec2 center, float refAngle) {
+    vec2 delta = point - center;
+    float angle = atan(delta.y, delta.x);
+    return mod(angle - refAngle + 2.f * M_PI, 2.f * M_PI) + refAngle;
+}
+
+void main() {
+    outType = type;
+    outState = max(state, 4.0f * selected);
+    outStyleColor = styleColor;
+
+    vec2 curStart = mix(startOld, start, getPer(startTransition));
+    vec2 curEnd = mix(endOld, end, getPer(endTransition));
+    float curCurveOffset = mix(curveOffsetOld, curveOffset, getPer(curveOffsetTransition));
+    curExists = mix(existsOld, exists, getPer(existsTransition));
+
+    vec2 delta = curEnd - curStart;
+    vec2 dir = normalize(delta);
+    float halfLength = 0.5f * length(delta);
+
+    // Find the point and direction of the edge at the arrow's position, following the same arc as the edge itself
+    vec2 point;
+    vec2 pointDir;
+    if(abs(curCurveOffset) > 0.0f) {
+        bool p = curCurveOffset > 0.f;
+        vec2 dirOrth = vec2(-dir.y, dir.x);
+        float curveWidth = min(abs(curCurveOffset), halfLength);
+        float centerDeltaX = ((curveWidth * curveWidth) - (halfLength * halfLength)) / (2.0f * curveWidth);
+        vec2 center = 0.5f * (curEnd + curStart) + dirOrth * centerDeltaX * (p ? 1.f : -1.f);
+        float radius = abs(centerDeltaX) + curveWidth;
+
+        float startAngle;
+        float endAngle;
+        if(p) {
+            endAngle = getAngle(curEnd, center, 0.0f);
+            startAngle = getAngle(curStart, center, endAngle);
+        } else {
+            startAngle = getAngle(curStart, center, 0.0f);
+            endAngle = getAngle(curEnd, center, startAngle);
+        }
+        float arcLength = abs(endAngle - startAngle) * radius;
+        float per = arrowPer >= 1.0f ? 1.0f - endOffset / arcLength : arrowPer;
+        float angle = mix(startAngle, endAngle, per);
+        point = center + radius * vec2(cos(angle), sin(angle));
+        pointDir = vec2(-sin(angle), cos(angle)) * sign(endAngle - startAngle);
+    } else {
+        float per = arrowPer >= 1.0f ? 1.0f - endOffset / (2.0f * halfLength) : arrowPer;
+        point = mix(curStart, curEnd, per);
+        pointDir = dir;
+    }
+
+    // Arrows at the end of an edge point at the node, other arrows are centered on the edge
+    float width = edgeTypes[int(type)].width;
+    if(width > 0.0f && styleWidth > 0.0f)
+        width = styleWidth;
+    float size = width > 0.0f ? arrowSize + width : 0.0f;
+    vec2 tip = point + pointDir * (arrowPer >= 1.0f ? 0.0f : 0.5f * size);
+    vec2 pointOrth = vec2(-pointDir.y, pointDir.x);
+
+    int corner = gl_VertexID % 3;
+    vec2 outPos = corner == 0 ? tip : tip - pointDir * size + pointOrth * (corner == 1 ? 0.5f : -0.5f) * size;
+    gl_Position = transform * vec4(outPos, 0.0f, 1.0f) * vec4(vec3(2.0f), 1.0f); // 2 to to make the default width and height of the screen 1, instead of 2
+}
@@ -23,15 +23,20 @@ in vec2 outPos;
 
 in float outType;
 in float outState;
+in vec4 outStyleColor;
+in float outWidth;
 in float curCurveOffset;
 in float radius;
 in vec2 center;
 
 uniform EdgeType edgeTypes[/*$type_count {*/1/*}*/];
 uniform mat4 transform;
+uniform float pixelSize; // The size of a pixel on the screen, over which the border of the line is blended
 
-// float fuzziness = 0.003; // A form of anti-aliasing by making the circle border a slight gradient
-float fuzziness = 0.0f;
+// Obtains how much of the pixel is covered by a line, given the distance of the pixel from the center of the line
+float getCoverage(float dist, float halfWidth) {
+    return clamp((halfWidth - dist) / pixelSize + 0.5f, 0.0f, 1.0f);
+}
 
 // Ensures that the output angle is specified such that it's greater than the reference angle
 float getAngle(vec2 point, float refAngle) {
@@ -44,13 +49,13 @@ float getAngle(vec2 point, float refAngle) {
 
 void main() {
     EdgeType typeData = edgeTypes[int(outType)];
-    float halfWidth = 0.5f * typeData.width;
+    float halfWidth = 0.5f * outWidth;
+    if(halfWidth <= 0.0f)
+        discard;
     float alpha = 1.0f;
-    float scaledFuzziness = fuzziness / transform[0][0];
-    float cor = 0.5f * scaledFuzziness;
-    float halfWidthSquared = (halfWidth - cor) * (halfWidth - cor);
 
     float proj;
+    float lineDist;
     float projPer;
     bool onLine;
 
@@ -77,20 +82,20 @@ void main() {
 
         projPer = proj / arcLength;
         onLine = projPer >= 0.0f && projPer <= 1.0f;
+        lineDist = distDelta;
 
         // if(startAngle < pointAngle) {{
         //     onLine = false;
         // }}
-        if(distDelta > halfWidth) {
-            onLine = false;
-        }
     } else {
         vec2 line = curEnd - curStart;
         vec2 point = outPos - curStart;
+        vec2 dir = normalize(line);
 
-        proj = dot(point, normalize(line));
+        proj = dot(point, dir);
         projPer = proj / length(line);
         onLine = projPer >= 0.0f && projPer <= 1.0f;
+        lineDist = abs(dot(point, vec2(-dir.y, dir.x)));
     }
 
     if(!onLine) {
@@ -101,19 +106,17 @@ void main() {
             vec2 delta1 = curStart - outPos;
             vec2 delta2 = curEnd - outPos;
             float distSquared = min(dot(delta1, delta1), dot(delta2, delta2));
-
-            if(distSquared >= halfWidthSquared) 
-                // alpha = 1.0 - max(0.0, (sqrt(distSquared) - (width - cor)) / scaledFuzziness);
-                alpha = 0.0f;
+            alpha = getCoverage(sqrt(distSquared), halfWidth);
         }
     } else {
+        alpha = getCoverage(lineDist, halfWidth);
         float period = typeData.dashSolid + typeData.dashTransparent;
         float offset = mod(proj, period);
         if(offset > typeData.dashSolid)
             alpha = 0.0f;
     }
 
-    vec3 typeColor = typeData.color;
+    vec3 typeColor = outStyleColor.a > 0.0f ? outStyleColor.rgb : typeData.color;
     if(outState >= 1.f)
         typeColor = typeData.partialHoverColor;
     if(outState >= 2.f)
@@ -1,16 +1,21 @@
 use std::{
     collections::{HashMap, HashSet},
     iter::repeat,
+    rc::Rc,
 };
 
 use itertools::Itertools;
 use multimap::MultiMap;
+use wasm_bindgen::JsValue;
 use web_sys::{WebGl2RenderingContext, WebGlTexture};
 
 use crate::{
     types::util::drawing::{
-        renderer::GroupSelection,
-        renderers::webgl::util::set_animated_data::{self, set_animated_data},
+        renderer::{EdgeStyle, GroupEdgeID, GroupSelection},
+        renderers::{
+            util::{edge_arrows::EdgeArrows, Font::Font},
+            webgl::util::set_animated_data::{self, set_animated_data, update_animated_data},
+        },
     },
     util::{
         color::Color, logging::console, matrix4::Matrix4, point::Point, transition::Transition,
@@ -18,14 +23,40 @@ use crate::{
     wasm_interface::NodeGroupID,
 };
 
-use super::util::vertex_renderer::VertexRenderer;
+pub use crate::types::util::drawing::renderers::render_styles::EdgeRenderingType;
+
+use super::{
+    node_renderer::TextRenderingConfig,
+    text::text_renderer::{Text, TextRenderer},
+    util::vertex_renderer::VertexRenderer,
+};
+
+/// The size of arrowheads in world units
+const ARROW_SIZE: f32 = 0.3;
+/// The distance by which end arrows are moved back from the node center, such that they touch the node border
+const ARROW_END_OFFSET: f32 = 0.5;
 
 pub struct EdgeRenderer {
     vertex_renderer: VertexRenderer,
+    arrow_renderer: VertexRenderer,
+    label_renderer: TextRenderer,
+    font: Rc<Font>,
     edge_types: Vec<EdgeRenderingType>,
+    hidden_edge_types: HashSet<usize>,
     node_edge_indices: MultiMap<NodeGroupID, usize>,
+    node_arrow_indices: MultiMap<NodeGroupID, usize>,
+    edges: Vec<Edge>,
+    arrows: EdgeArrows,
+    styles: HashMap<GroupEdgeID, EdgeStyle>,
+    selected_edges: HashSet<GroupEdgeID>,
+    /// The index of the edge that every segment and arrow belongs to
+    segment_edges: Vec<usize>,
+    arrow_edges: Vec<usize>,
+    /// The index of the first segment of every edge
+    edge_segment_starts: Vec<usize>,
 }
 
+#[derive(Clone)]
 pub struct Edge {
     pub start: Transition<Point>,
     pub start_node: NodeGroupID,
@@ -34,20 +65,11 @@ pub struct Edge {
     pub end_node: NodeGroupID,
     pub exists: Transition<f32>,
     pub edge_type: usize,
+    /// The index of the edge's type in the diagram, used to identify the edge for style overrides
+    pub type_index: i32,
     pub shift: Transition<f32>, // Some sideways shift
 }
 
-#[derive(Clone)]
-pub struct EdgeRenderingType {
-    pub color: Color,
-    pub select_color: Color,
-    pub partial_select_color: Color,
-    pub hover_color: Color,
-    pub partial_hover_color: Color,
-    pub width: f32,
-    pub dash_solid: f32, // The distance per period over which this dash should be solid
-    pub dash_transparent: f32, // The distance per
-}
 type Segment = (
     Transition<Point>,
     Transition<Point>,
@@ -60,6 +82,7 @@ impl EdgeRenderer {
     pub fn new(
         context: &WebGl2RenderingContext,
         edge_types: Vec<EdgeRenderingType>,
+        text: TextRenderingConfig,
     ) -> EdgeRenderer {
         let type_count = edge_types.len();
         let vertex_renderer = VertexRenderer::new_advanced(
@@ -72,34 +95,95 @@ impl EdgeRenderer {
             )])),
         )
         .unwrap();
+        let arrow_renderer = VertexRenderer::new_advanced(
+            context,
+            &include_str!("edge_arrow_renderer.vert"),
+            &include_str!("edge_arrow_renderer.frag"),
+            Some(&HashMap::from([(
+                "type_count",
+                type_count.to_string().as_str(),
+            )])),
+        )
+        .unwrap();
 
         EdgeRenderer {
             vertex_renderer,
+            arrow_renderer,
+            label_renderer: TextRenderer::new(
+                context,
+                text.font.clone(),
+                text.font_settings,
+                text.screen_height,
+            ),
+            font: text.font,
             edge_types,
+            hidden_edge_types: HashSet::new(),
             node_edge_indices: MultiMap::new(),
+            node_arrow_indices: MultiMap::new(),
+            edges: Vec::new(),
+            arrows: EdgeArrows::None,
+            styles: HashMap::new(),
+            selected_edges: HashSet::new(),
+            segment_edges: Vec::new(),
+            arrow_edges: Vec::new(),
+            edge_segment_starts: Vec::new(),
+        }
+    }
+
+    pub fn set_type_width(&mut self, edge_type: usize, width: f32) {
+        if let Some(edge_type) = self.edge_types.get_mut(edge_type) {
+            edge_type.width = width;
         }
     }
 
+    pub fn set_arrows(&mut self, context: &WebGl2RenderingContext, arrows: EdgeArrows) {
+        self.arrows = arrows;
+        self.set_arrow_data(context);
+    }
+
+    /// Hides or shows all edges of the given type, without affecting the edge data itself
+    pub fn set_type_visibility(&mut self, edge_type: usize, visible: bool) {
+        if visible {
+            self.hidden_edge_types.remove(&edge_type);
+        } else {
+            self.hidden_edge_types.insert(edge_type);
+        }
+    }
+
+    /// Sets the style overrides of individual edges, and the edges that are selected themselves
+    pub fn set_styles(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.styles = styles.clone();
+        self.selected_edges = selected.clone();
+        self.set_style_data(context);
+    }
+
+    pub fn get_edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
     pub fn set_edges(&mut self, context: &WebGl2RenderingContext, edges: &Vec<Edge>) {
         let segments = edges
             .iter()
-            .flat_map(|edge| {
-                let mut points = edge.points.clone();
-                let edge_type = edge.edge_type;
-                let curve_offset = edge.shift;
-                let exists = edge.exists;
-                points.push(edge.end);
-                let edge_segments = points
-                    .iter()
-                    .scan(edge.start, |prev, item| {
-                        let out = (*prev, *item, edge_type as f32, curve_offset, exists);
-                        *prev = *item;
-                        Some((out, edge))
-                    })
-                    .collect::<Vec<_>>();
-                edge_segments
-            })
+            .flat_map(|edge| get_segments(edge).into_iter().map(move |segment| (segment, edge)))
             .collect::<Vec<(Segment, &Edge)>>();
+        self.segment_edges = edges
+            .iter()
+            .enumerate()
+            .flat_map(|(index, edge)| repeat(index).take(edge.points.len() + 1))
+            .collect();
+        self.edge_segment_starts = edges
+            .iter()
+            .scan(0, |start, edge| {
+                let out = *start;
+                *start += edge.points.len() + 1;
+                Some(out)
+            })
+            .collect();
 
         self.node_edge_indices = segments
             .iter()
@@ -154,12 +238,296 @@ impl EdgeRenderer {
         );
 
         self.vertex_renderer.send_data(context);
+
+        self.edges = edges.clone();
+        self.set_arrow_data(context);
     }
 
-    pub fn set_transform(&mut self, context: &WebGl2RenderingContext, transform: &Matrix4) {
-        self.vertex_renderer.set_uniform(context, "transform", |u| {
-            context.uniform_matrix4fv_with_f32_array(u, true, &transform.0)
-        });
+    /// Updates the geometry of the given edges in place, such that only their ranges of the buffers are sent again. Edges are identified by their nodes and type index. Returns false if an edge was not drawn before, is ambiguous, or changed its number of bend points or its type, in which case all edges have to be set instead.
+    pub fn update_edges(&mut self, context: &WebGl2RenderingContext, edges: &[Edge]) -> bool {
+        let mut indices = HashMap::new();
+        let mut ambiguous = HashSet::new();
+        for (index, edge) in self.edges.iter().enumerate() {
+            let id = (edge.start_node, edge.end_node, edge.type_index);
+            if indices.insert(id, index).is_some() {
+                ambiguous.insert(id);
+            }
+        }
+        let Some(updates) = edges
+            .iter()
+            .map(|edge| {
+                let id = (edge.start_node, edge.end_node, edge.type_index);
+                let index = *indices.get(&id).filter(|_| !ambiguous.contains(&id))?;
+                let old = &self.edges[index];
+                (old.points.len() == edge.points.len() && old.edge_type == edge.edge_type)
+                    .then(|| (index, edge))
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        let mut labels_changed = false;
+        for (index, edge) in updates {
+            let start = self.edge_segment_starts[index];
+            for (offset, (from, to, _, curve_offset, exists)) in
+                get_segments(edge).into_iter().enumerate()
+            {
+                let segment = start + offset;
+                for vertex in segment * 6..(segment + 1) * 6 {
+                    let renderer = &mut self.vertex_renderer;
+                    update_animated_data("start", vertex, from, |v| [v.x, v.y], context, renderer);
+                    update_animated_data("end", vertex, to, |v| [v.x, v.y], context, renderer);
+                    update_animated_data(
+                        "curveOffset",
+                        vertex,
+                        curve_offset,
+                        |v| [v],
+                        context,
+                        renderer,
+                    );
+                    update_animated_data("exists", vertex, exists, |v| [v], context, renderer);
+                }
+            }
+            let id = (edge.start_node, edge.end_node, edge.type_index);
+            labels_changed |= self
+                .styles
+                .get(&id)
+                .map_or(false, |style| style.label.is_some());
+            self.edges[index] = edge.clone();
+        }
+        self.vertex_renderer.send_data(context);
+
+        if self.arrows != EdgeArrows::None {
+            // Arrows depend on the segment that they are drawn on, hence these are recreated, which is cheap compared to the segments
+            self.set_arrow_data(context);
+        } else if labels_changed {
+            self.set_label_data(context);
+        }
+        true
+    }
+
+    fn set_arrow_data(&mut self, context: &WebGl2RenderingContext) {
+        // Select the segment of every edge on which the arrow is drawn, together with the fraction along this segment
+        let arrows = self.arrows;
+        let arrow_segments = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                let mut points = Vec::with_capacity(edge.points.len() + 2);
+                points.push(edge.start);
+                points.extend(edge.points.iter().cloned());
+                points.push(edge.end);
+                let segment_count = points.len() - 1;
+                let (index, per) = match arrows {
+                    EdgeArrows::None => return None,
+                    EdgeArrows::End => (segment_count - 1, 1.),
+                    EdgeArrows::Middle if segment_count % 2 == 1 => (segment_count / 2, 0.5),
+                    EdgeArrows::Middle => (segment_count / 2, 0.),
+                };
+                let segment: Segment = (
+                    points[index],
+                    points[index + 1],
+                    edge.edge_type as f32,
+                    if segment_count == 1 {
+                        edge.shift
+                    } else {
+                        Transition::plain(0.)
+                    },
+                    edge.exists,
+                );
+                Some((segment, per, edge))
+            })
+            .collect::<Vec<(Segment, f32, &Edge)>>();
+        self.arrow_edges = if arrows == EdgeArrows::None {
+            Vec::new()
+        } else {
+            (0..self.edges.len()).collect()
+        };
+
+        self.node_arrow_indices = arrow_segments
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (_, _, edge))| [(edge.start_node, index), (edge.end_node, index)])
+            .collect();
+
+        let segments3 = arrow_segments
+            .iter()
+            .flat_map(|arrow| repeat(arrow).take(3));
+        set_animated_data(
+            "start",
+            segments3.clone().map(|((start, _, _, _, _), _, _)| start.clone()),
+            |start| [start.x, start.y],
+            context,
+            &mut self.arrow_renderer,
+        );
+        set_animated_data(
+            "end",
+            segments3.clone().map(|((_, end, _, _, _), _, _)| end.clone()),
+            |end| [end.x, end.y],
+            context,
+            &mut self.arrow_renderer,
+        );
+        set_animated_data(
+            "curveOffset",
+            segments3
+                .clone()
+                .map(|((_, _, _, offset, _), _, _)| offset.clone()),
+            |offset| [offset],
+            context,
+            &mut self.arrow_renderer,
+        );
+        set_animated_data(
+            "exists",
+            segments3
+                .clone()
+                .map(|((_, _, _, _, exists), _, _)| exists.clone()),
+            |exists| [exists],
+            context,
+            &mut self.arrow_renderer,
+        );
+
+        self.arrow_renderer.set_data(
+            context,
+            "type",
+            &segments3
+                .clone()
+                .map(|((_, _, edge_type, _, _), _, _)| edge_type.clone())
+                .collect::<Box<_>>(),
+            1,
+        );
+        self.arrow_renderer.set_data(
+            context,
+            "arrowPer",
+            &segments3
+                .clone()
+                .map(|(_, per, _)| per.clone())
+                .collect::<Box<_>>(),
+            1,
+        );
+        self.arrow_renderer.set_data(
+            context,
+            "state",
+            &segments3.map(|_| 0.).collect::<Box<_>>(),
+            1,
+        );
+
+        self.arrow_renderer.send_data(context);
+        self.set_style_data(context);
+    }
+
+    /// Sends the style overrides and edge selection of every segment and arrow
+    fn set_style_data(&mut self, context: &WebGl2RenderingContext) {
+        let styles = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let id = (edge.start_node, edge.end_node, edge.type_index);
+                let style = self.styles.get(&id);
+                let color = style
+                    .and_then(|style| style.color)
+                    .map_or([0.; 4], |Color(r, g, b)| [r, g, b, 1.]);
+                let width = style.and_then(|style| style.width).unwrap_or(0.);
+                let selected = if self.selected_edges.contains(&id) {
+                    1.
+                } else {
+                    0.
+                };
+                (color, width, selected)
+            })
+            .collect_vec();
+
+        let renderers = [
+            (&mut self.vertex_renderer, &self.segment_edges, 6),
+            (&mut self.arrow_renderer, &self.arrow_edges, 3),
+        ];
+        for (renderer, edge_indices, vertex_count) in renderers {
+            let vertices = edge_indices
+                .iter()
+                .flat_map(|&edge| repeat(&styles[edge]).take(vertex_count));
+            renderer.set_data(
+                context,
+                "styleColor",
+                &vertices
+                    .clone()
+                    .flat_map(|(color, _, _)| *color)
+                    .collect::<Box<_>>(),
+                4,
+            );
+            renderer.set_data(
+                context,
+                "styleWidth",
+                &vertices
+                    .clone()
+                    .map(|(_, width, _)| *width)
+                    .collect::<Box<_>>(),
+                1,
+            );
+            renderer.set_data(
+                context,
+                "selected",
+                &vertices.map(|(_, _, selected)| *selected).collect::<Box<_>>(),
+                1,
+            );
+            renderer.send_data(context);
+        }
+
+        self.set_label_data(context);
+    }
+
+    /// Places the label of every edge that has one at the middle of the edge
+    fn set_label_data(&mut self, context: &WebGl2RenderingContext) {
+        let labels = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                let id = (edge.start_node, edge.end_node, edge.type_index);
+                let text = self.styles.get(&id)?.label.clone()?;
+                let mut points = Vec::with_capacity(edge.points.len() + 2);
+                points.push(edge.start);
+                points.extend(edge.points.iter().cloned());
+                points.push(edge.end);
+                // For an odd number of points the middle point is used, otherwise the middle of the middle segment
+                let first = points[(points.len() - 1) / 2];
+                let second = points[points.len() / 2];
+                let text_width = self.font.measure_width(&text);
+                let text_height = self.font.measure_height(&text);
+                let center = |first: Point, second: Point| Point {
+                    x: 0.5 * (first.x + second.x) - 0.5 * text_width,
+                    y: 0.5 * (first.y + second.y) - 0.5 * text_height,
+                };
+                Some(Text {
+                    text,
+                    position: Transition {
+                        old: center(first.old, second.old),
+                        new: center(first.new, second.new),
+                        ..first
+                    },
+                    exists: edge.exists,
+                    rotated: false,
+                })
+            })
+            .collect();
+        self.label_renderer.set_texts(context, &labels);
+    }
+
+    /// Sets the transform, together with the size of a pixel in world coordinates which is used for anti-aliasing
+    pub fn set_transform(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        transform: &Matrix4,
+        pixel_size: f32,
+        screen_height: usize,
+    ) {
+        for renderer in [&mut self.vertex_renderer, &mut self.arrow_renderer] {
+            renderer.set_uniform(context, "transform", |u| {
+                context.uniform_matrix4fv_with_f32_array(u, true, &transform.0)
+            });
+        }
+        self.vertex_renderer
+            .set_uniform(context, "pixelSize", |u| context.uniform1f(u, pixel_size));
+        self.label_renderer
+            .set_transform_and_screen_height(context, transform, screen_height);
     }
 
     pub fn update_selection(
@@ -168,34 +536,157 @@ impl EdgeRenderer {
         selection: &GroupSelection,
         old_selection: &GroupSelection,
     ) {
-        let to_indices = |ids: &[NodeGroupID]| {
-            ids.iter()
-                .filter_map(|id| self.node_edge_indices.get_vec(&(*id as usize)))
-                .flatten()
-                .cloned()
-                .collect::<HashSet<usize>>()
+        let edge_states = get_state_updates(&self.node_edge_indices, selection, old_selection);
+        for (index, state) in edge_states {
+            let data_index = index * 6;
+            for i in 0..6 {
+                self.vertex_renderer
+                    .update_data(context, "state", data_index + i, [state as f32]);
+            }
+        }
+        self.vertex_renderer.send_data(context);
+
+        let arrow_states = get_state_updates(&self.node_arrow_indices, selection, old_selection);
+        for (index, state) in arrow_states {
+            let data_index = index * 3;
+            for i in 0..3 {
+                self.arrow_renderer
+                    .update_data(context, "state", data_index + i, [state as f32]);
+            }
+        }
+        self.arrow_renderer.send_data(context);
+    }
+
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+        set_type_uniforms(
+            &mut self.vertex_renderer,
+            context,
+            &self.edge_types,
+            &self.hidden_edge_types,
+            time,
+        );
+        self.vertex_renderer
+            .render(context, WebGl2RenderingContext::TRIANGLES);
+
+        if self.arrows != EdgeArrows::None {
+            set_type_uniforms(
+                &mut self.arrow_renderer,
+                context,
+                &self.edge_types,
+                &self.hidden_edge_types,
+                time,
+            );
+            self.arrow_renderer
+                .set_uniform(context, "arrowSize", |u| context.uniform1f(u, ARROW_SIZE));
+            self.arrow_renderer
+                .set_uniform(context, "endOffset", |u| context.uniform1f(u, ARROW_END_OFFSET));
+            self.arrow_renderer
+                .render(context, WebGl2RenderingContext::TRIANGLES);
+        }
+        self.label_renderer.render(context, time);
+    }
+
+    pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
+        self.vertex_renderer.dispose(context);
+        self.arrow_renderer.dispose(context);
+        self.label_renderer.dispose(context);
+    }
+
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)?;
+        self.arrow_renderer.restore(context)?;
+        self.label_renderer.restore(context)
+    }
+}
+
+/// Splits the given edge into the segments between its consecutive points
+fn get_segments(edge: &Edge) -> Vec<Segment> {
+    let edge_type = edge.edge_type as f32;
+    edge.points
+        .iter()
+        .chain([edge.end].iter())
+        .scan(edge.start, |prev, &point| {
+            let out = (*prev, point, edge_type, edge.shift, edge.exists);
+            *prev = point;
+            Some(out)
+        })
+        .collect()
+}
+
+/// Sets the time and edge type uniforms shared by the edge and arrow shaders
+fn set_type_uniforms(
+    renderer: &mut VertexRenderer,
+    context: &WebGl2RenderingContext,
+    edge_types: &Vec<EdgeRenderingType>,
+    hidden_edge_types: &HashSet<usize>,
+    time: u32,
+) {
+    renderer.set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+    for (index, edge_type) in edge_types.iter().enumerate() {
+        let colors = [
+            ("color", &edge_type.color),
+            ("hoverColor", &edge_type.hover_color),
+            ("selectColor", &edge_type.select_color),
+            ("partialHoverColor", &edge_type.partial_hover_color),
+            ("partialSelectColor", &edge_type.partial_select_color),
+        ];
+        for (name, c) in colors {
+            renderer.set_uniform(context, &format!("edgeTypes[{index}].{name}"), |u| {
+                context.uniform3f(u, c.0, c.1, c.2)
+            });
+        }
+        let width = if hidden_edge_types.contains(&index) {
+            0.0
+        } else {
+            edge_type.width
         };
+        renderer.set_uniform(context, &format!("edgeTypes[{index}].width"), |u| {
+            context.uniform1f(u, width)
+        });
+        renderer.set_uniform(context, &format!("edgeTypes[{index}].dashSolid"), |u| {
+            context.uniform1f(u, edge_type.dash_solid)
+        });
+        renderer.set_uniform(context, &format!("edgeTypes[{index}].dashTransparent"), |u| {
+            context.uniform1f(u, edge_type.dash_transparent)
+        });
+    }
+}
 
-        let new_selected_indices = to_indices(selection.0);
-        let new_partially_selected_indices = to_indices(selection.1);
-        let new_hover_indices = to_indices(selection.2);
-        let new_partially_hover_indices = to_indices(selection.3);
-        let old_selected_indices = to_indices(old_selection.0);
-        let old_partially_selected_indices = to_indices(old_selection.1);
-        let old_hover_indices = to_indices(old_selection.2);
-        let old_partially_hover_indices = to_indices(old_selection.3);
+/// Obtains the new selection states of all rendered elements whose state changed, given the elements connected to each node
+fn get_state_updates(
+    node_indices: &MultiMap<NodeGroupID, usize>,
+    selection: &GroupSelection,
+    old_selection: &GroupSelection,
+) -> Vec<(usize, usize)> {
+    let to_indices = |ids: &[NodeGroupID]| {
+        ids.iter()
+            .filter_map(|id| node_indices.get_vec(&(*id as usize)))
+            .flatten()
+            .cloned()
+            .collect::<HashSet<usize>>()
+    };
 
-        let indices = new_selected_indices
-            .iter()
-            .chain(old_selected_indices.iter())
-            .chain(new_partially_selected_indices.iter())
-            .chain(old_partially_selected_indices.iter())
-            .chain(new_hover_indices.iter())
-            .chain(old_hover_indices.iter())
-            .chain(new_partially_hover_indices.iter())
-            .chain(old_partially_hover_indices.iter());
-
-        let state_updates = indices.filter_map(|index| {
+    let new_selected_indices = to_indices(selection.0);
+    let new_partially_selected_indices = to_indices(selection.1);
+    let new_hover_indices = to_indices(selection.2);
+    let new_partially_hover_indices = to_indices(selection.3);
+    let old_selected_indices = to_indices(old_selection.0);
+    let old_partially_selected_indices = to_indices(old_selection.1);
+    let old_hover_indices = to_indices(old_selection.2);
+    let old_partially_hover_indices = to_indices(old_selection.3);
+
+    let indices = new_selected_indices
+        .iter()
+        .chain(old_selected_indices.iter())
+        .chain(new_partially_selected_indices.iter())
+        .chain(old_partially_selected_indices.iter())
+        .chain(new_hover_indices.iter())
+        .chain(old_hover_indices.iter())
+        .chain(new_partially_hover_indices.iter())
+        .chain(old_partially_hover_indices.iter());
+
+    indices
+        .filter_map(|&index| {
             let new_state = if new_selected_indices.contains(&index) {
                 4
             } else if new_partially_selected_indices.contains(&index) {
@@ -225,72 +716,6 @@ impl EdgeRenderer {
             } else {
                 None
             }
-        });
-
-        for (index, state) in state_updates {
-            let data_index = index * 6;
-            for i in 0..6 {
-                self.vertex_renderer
-                    .update_data(context, "state", data_index + i, [state as f32]);
-            }
-        }
-        self.vertex_renderer.send_data(context);
-    }
-
-    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
-        self.vertex_renderer
-            .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
-        for (index, edge_type) in self.edge_types.iter().enumerate() {
-            let c = edge_type.color.clone();
-            self.vertex_renderer
-                .set_uniform(context, &format!("edgeTypes[{index}].color"), |u| {
-                    context.uniform3f(u, c.0, c.1, c.2)
-                });
-            let c = edge_type.hover_color.clone();
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].hoverColor"),
-                |u| context.uniform3f(u, c.0, c.1, c.2),
-            );
-            let c = edge_type.select_color.clone();
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].selectColor"),
-                |u| context.uniform3f(u, c.0, c.1, c.2),
-            );
-            let c = edge_type.partial_hover_color.clone();
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].partialHoverColor"),
-                |u| context.uniform3f(u, c.0, c.1, c.2),
-            );
-            let c = edge_type.partial_select_color.clone();
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].partialSelectColor"),
-                |u| context.uniform3f(u, c.0, c.1, c.2),
-            );
-            self.vertex_renderer
-                .set_uniform(context, &format!("edgeTypes[{index}].width"), |u| {
-                    context.uniform1f(u, edge_type.width)
-                });
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].dashSolid"),
-                |u| context.uniform1f(u, edge_type.dash_solid),
-            );
-            self.vertex_renderer.set_uniform(
-                context,
-                &format!("edgeTypes[{index}].dashTransparent"),
-                |u| context.uniform1f(u, edge_type.dash_transparent),
-            );
-        }
-
-        self.vertex_renderer
-            .render(context, WebGl2RenderingContext::TRIANGLES);
-    }
-
-    pub fn dispose(&self, context: &WebGl2RenderingContext) {
-        self.vertex_renderer.dispose(context);
-    }
+        })
+        .collect()
 }
@@ -28,8 +28,13 @@ in vec2 existsTransition;
 
 in float type;
 in float state;
+in vec4 styleColor; // The override color of this edge, where an alpha of 0 indicates the type's color is used
+in float styleWidth; // The override width of this edge, where 0 indicates the type's width is used
+in float selected; // Whether this edge itself is selected, independent of the selection of its nodes
 out float outType;
 out float outState;
+out vec4 outStyleColor;
+out float outWidth;
 
 out float curExists;
 out vec2 curStart;
@@ -42,6 +47,7 @@ out vec2 center;
 uniform EdgeType edgeTypes[/*$type_count {*/1/*}*/];
 uniform mat4 transform;
 uniform float time;
+uniform float pixelSize; // The size of a pixel on the screen, used to leave space for anti-aliasing
 
 float getPer(vec2 transition) {
     return max(0.0f, min((time - transition.x) / transition.y, 1.0f));
@@ -49,11 +55,17 @@ float getPer(vec2 transition) {
 
 void main() {
     outType = type;
-    outState = state;
+    outState = max(state, 4.0f * selected);
+    outStyleColor = styleColor;
 
     float startPer = getPer(startTransition);
     curStart = startPer * start + (1.0f - startPer) * startOld;
-    float halfWidth = 0.5f * edgeTypes[int(type)].width;
+    float width = edgeTypes[int(type)].width;
+    // Hidden edge types remain hidden, regardless of the width override
+    if(width > 0.0f && styleWidth > 0.0f)
+        width = styleWidth;
+    outWidth = width;
+    float halfWidth = width > 0.0f ? 0.5f * width + pixelSize : 0.0f;
 
     float endPer = getPer(endTransition);
     curEnd = mix(endOld, end, endPer);
@@ -6,10 +6,16 @@ in float curExists;
 
 uniform vec4 color1;
 uniform vec4 color2;
+uniform vec4 bracketColor;
 
 out vec4 outColor;
 
 void main() {
+    // Types above 1 denote the brackets of paired layers
+    if(curType > 1.5f) {
+        outColor = vec4(bracketColor.rgb * curExists * bracketColor.a, curExists * bracketColor.a);
+        return;
+    }
     vec3 color = sqrt(mix(color1.rgb * color1.rgb, color2.rgb * color2.rgb, curType));
     float alpha = curExists * mix(color1.a, color2.a, curType);
     outColor = vec4(color * alpha, alpha);
@@ -1,10 +1,14 @@
 use itertools::Itertools;
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
-    types::util::drawing::renderers::webgl::{
-        text::text_renderer::Text,
-        util::{set_animated_data::set_animated_data, vertex_renderer::VertexRenderer},
+    types::util::drawing::renderers::{
+        util::layer_pairing::LayerPairing,
+        webgl::{
+            text::text_renderer::Text,
+            util::{set_animated_data::set_animated_data, vertex_renderer::VertexRenderer},
+        },
     },
     util::{color::TransparentColor, logging::console, matrix4::Matrix4, transition::Transition},
 };
@@ -18,6 +22,26 @@ pub struct LayerBgRenderer {
     bg_renderer: VertexRenderer,
     bg_color1: TransparentColor,
     bg_color2: TransparentColor,
+    bracket_color: TransparentColor,
+    alternating: bool,
+    pairing: LayerPairing,
+}
+
+/// The width of the vertical bar of a bracket, in clip space
+const BRACKET_WIDTH: f32 = 0.004;
+/// The length of the horizontal ticks at the ends of a bracket, in clip space
+const BRACKET_TICK_LENGTH: f32 = 0.012;
+/// The thickness of the horizontal ticks at the ends of a bracket, in clip space
+const BRACKET_TICK_THICKNESS: f32 = 0.004;
+
+/// A rectangle to be drawn, consisting of its top and bottom in world coordinates, offsets of its top and bottom in clip space, and its horizontal range in clip space
+struct Quad {
+    top: Transition<f32>,
+    bottom: Transition<f32>,
+    offsets: (f32, f32),
+    x_range: (f32, f32),
+    band: Transition<f32>,
+    exists: Transition<f32>,
 }
 
 impl LayerBgRenderer {
@@ -25,6 +49,7 @@ impl LayerBgRenderer {
         context: &WebGl2RenderingContext,
         color1: TransparentColor,
         color2: TransparentColor,
+        bracket_color: TransparentColor,
     ) -> LayerBgRenderer {
         let vertex_renderer = VertexRenderer::new(
             context,
@@ -36,55 +61,125 @@ impl LayerBgRenderer {
             bg_renderer: vertex_renderer,
             bg_color1: color1,
             bg_color2: color2,
+            bracket_color,
+            alternating: true,
+            pairing: LayerPairing::Separate,
         }
     }
 }
 impl LayerDivisionRenderer for LayerBgRenderer {
     fn set_layers(&mut self, context: &WebGl2RenderingContext, layers: &Vec<Layer>) {
-        fn map(layers: &Vec<Layer>, map: impl Fn(&Transition<f32>) -> f32) -> Box<[f32]> {
-            layers
-                .iter()
-                .flat_map(|layer| {
-                    let top = map(&layer.top);
-                    let bottom = map(&layer.bottom);
-                    [top, bottom, bottom, top, bottom, top]
-                })
-                .collect()
-        }
-
         let mut layers = layers.clone();
         layers.sort_by_key(|layer| layer.exists.new > 0.5);
 
-        let layer_vertices = layers.iter().flat_map(|layer| {
-            [
-                layer.top,
-                layer.bottom,
-                layer.bottom,
-                layer.top,
-                layer.bottom,
-                layer.top,
-            ]
-        });
+        let pairing = self.pairing;
+        let band = |index: f32| pairing.get_band(index.round() as usize) as f32 % 2.;
+        let mut quads = layers
+            .iter()
+            .map(|layer| Quad {
+                top: layer.top,
+                bottom: layer.bottom,
+                offsets: (0., 0.),
+                x_range: (-1., 1.),
+                band: Transition {
+                    old: band(layer.index.old),
+                    new: band(layer.index.new),
+                    ..layer.index
+                },
+                exists: layer.exists,
+            })
+            .collect_vec();
+
+        // Brackets are drawn on top of the backgrounds at both sides of the screen, and are marked by band 2
+        if self.pairing.has_brackets() {
+            let index = |layer: &Layer| layer.index.new.round() as usize;
+            let shown = layers.iter().filter(|layer| layer.exists.new > 0.5);
+            let pairs = shown
+                .clone()
+                .filter(|&layer| index(layer) % 2 == 0)
+                .filter_map(|first| {
+                    let second = shown
+                        .clone()
+                        .find(|&layer| index(layer) == index(first) + 1)?;
+                    Some((first, second))
+                })
+                .collect_vec();
+            for (first, second) in pairs {
+                for side in [-1f32, 1.] {
+                    let quad = |top, bottom, offsets, width: f32| Quad {
+                        top,
+                        bottom,
+                        offsets,
+                        x_range: if side < 0. { (-1., width - 1.) } else { (1. - width, 1.) },
+                        band: Transition::plain(2.),
+                        exists: first.exists,
+                    };
+                    quads.extend([
+                        quad(first.top, second.bottom, (0., 0.), BRACKET_WIDTH),
+                        quad(
+                            first.top,
+                            first.top,
+                            (0., -BRACKET_TICK_THICKNESS),
+                            BRACKET_TICK_LENGTH,
+                        ),
+                        quad(
+                            second.bottom,
+                            second.bottom,
+                            (BRACKET_TICK_THICKNESS, 0.),
+                            BRACKET_TICK_LENGTH,
+                        ),
+                    ]);
+                }
+            }
+        }
+
+        // The vertices of the two triangles, as top (true) or bottom (false) and right (true) or left (false)
+        const CORNERS: [(bool, bool); 6] = [
+            (true, true),
+            (false, true),
+            (false, false),
+            (true, true),
+            (false, false),
+            (true, false),
+        ];
+        let vertices = quads
+            .iter()
+            .flat_map(|quad| CORNERS.iter().map(move |corner| (quad, corner)));
         set_animated_data(
             "yPosition",
-            layer_vertices.clone(),
+            vertices
+                .clone()
+                .map(|(quad, &(top, _))| if top { quad.top } else { quad.bottom }),
             |v| [v],
             context,
             &mut self.bg_renderer,
         );
 
-        let layers6 = layers.iter().flat_map(|layer| [layer; 6]);
+        let offsets: Box<[f32]> = vertices
+            .clone()
+            .map(|(quad, &(top, _))| if top { quad.offsets.0 } else { quad.offsets.1 })
+            .collect();
+        self.bg_renderer
+            .set_data(context, "yOffset", &offsets, 1);
+
+        let x_positions: Box<[f32]> = vertices
+            .clone()
+            .map(|(quad, &(_, right))| if right { quad.x_range.1 } else { quad.x_range.0 })
+            .collect();
+        self.bg_renderer
+            .set_data(context, "xPosition", &x_positions, 1);
+
         set_animated_data(
             "type",
-            layers6.clone().map(|l| l.index),
-            |v| [v % 2.],
+            vertices.clone().map(|(quad, _)| quad.band),
+            |v| [v],
             context,
             &mut self.bg_renderer,
         );
 
         set_animated_data(
             "exists",
-            layers6.clone().map(|l| l.exists),
+            vertices.clone().map(|(quad, _)| quad.exists),
             |v| [v],
             context,
             &mut self.bg_renderer,
@@ -107,10 +202,19 @@ impl LayerDivisionRenderer for LayerBgRenderer {
         self.bg_renderer
             .set_uniform(context, "color1", |u| context.uniform4f(u, r1, g1, b1, a1));
 
-        let TransparentColor(r2, g2, b2, a2) = self.bg_color2;
+        let TransparentColor(r2, g2, b2, a2) = if self.alternating {
+            self.bg_color2
+        } else {
+            self.bg_color1
+        };
         self.bg_renderer
             .set_uniform(context, "color2", |u| context.uniform4f(u, r2, g2, b2, a2));
 
+        let TransparentColor(r3, g3, b3, a3) = self.bracket_color;
+        self.bg_renderer.set_uniform(context, "bracketColor", |u| {
+            context.uniform4f(u, r3, g3, b3, a3)
+        });
+
         self.bg_renderer
             .render(context, WebGl2RenderingContext::TRIANGLES);
     }
@@ -118,4 +222,16 @@ impl LayerDivisionRenderer for LayerBgRenderer {
     fn dispose(&mut self, context: &WebGl2RenderingContext) {
         self.bg_renderer.dispose(context);
     }
+
+    fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.bg_renderer.restore(context)
+    }
+
+    fn set_alternating(&mut self, alternating: bool) {
+        self.alternating = alternating;
+    }
+
+    fn set_pairing(&mut self, pairing: LayerPairing) {
+        self.pairing = pairing;
+    }
 }
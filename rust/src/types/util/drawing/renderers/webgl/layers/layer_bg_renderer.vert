@@ -2,6 +2,8 @@
 precision highp float;
 
 in float yPosition;
+in float yOffset;
+in float xPosition;
 in float yPositionOld;
 in vec2 yPositionTransition;
 
@@ -33,15 +35,9 @@ void main() {
     float existsPer = getPer(existsTransition);
     curExists = existsPer * exists + (1.0f - existsPer) * existsOld;
 
-    int corner = gl_VertexID % 6; // two triangles
-    vec2 cornerPos = (
-    /**/corner == 0 || corner == 3 ?  /**/ vec2(1.f, 1.f)  //
-    /**/: corner == 1 ?               /**/ vec2(1.f, -1.f) //
-    /**/: corner == 2 || corner == 4 ?/**/ vec2(-1.f, -1.f) //
-    /**/:                             /**/ vec2(-1.f, 1.f));
-
+    // The horizontal position and the vertical offset are given in clip space, such that brackets keep their size while zooming
     float transformedYPos = (transform * vec4(0.0f, curYPosition, 0.0f, 1.0f) *
-        vec4(vec3(2.0f), 1.0f)).y;
-    gl_Position = vec4(cornerPos.x, transformedYPos, -0.1f, 1.0f);
+        vec4(vec3(2.0f), 1.0f)).y + yOffset;
+    gl_Position = vec4(xPosition, transformedYPos, -0.1f, 1.0f);
     // gl_Position = vec4(cornerPos, 0.0f, 1.0f);
 }
\ No newline at end of file
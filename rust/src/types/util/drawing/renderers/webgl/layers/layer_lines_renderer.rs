@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::{Either, Itertools};
 use ordered_float::OrderedFloat;
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext as Gl;
 
 use crate::{
@@ -129,4 +130,8 @@ impl LayerDivisionRenderer for LayerLinesRenderer {
     fn dispose(&mut self, context: &Gl) {
         self.vertex_renderer.dispose(context);
     }
+
+    fn restore(&mut self, context: &Gl) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)
+    }
 }
@@ -1,10 +1,14 @@
 use std::rc::Rc;
 
 use itertools::Itertools;
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
-    types::util::drawing::renderers::{util::Font::Font, webgl::text::text_renderer::Text},
+    types::util::drawing::renderers::{
+        util::{layer_label_sides::LayerLabelSides, layer_pairing::LayerPairing, Font::Font},
+        webgl::text::text_renderer::Text,
+    },
     util::{
         color::Color, logging::console, matrix4::Matrix4, point::Point, transition::Transition,
     },
@@ -15,7 +19,18 @@ use super::super::text::text_renderer::{TextRenderer, TextRendererSettings};
 pub struct LayerRenderer {
     division_renderer: Box<dyn LayerDivisionRenderer>,
     text_renderer: TextRenderer,
+    right_text_renderer: TextRenderer,
+    font: Rc<Font>,
     text_size: f32,
+    layers: Vec<Layer>,
+    /// The horizontal range covered by the diagram, used to position labels that are not sticky
+    bounds: (f32, f32),
+    label_sides: LayerLabelSides,
+    /// Whether the labels stay at the side of the screen while panning
+    sticky_labels: bool,
+    /// Whether the labels are rotated to read from bottom to top, such that they take up less horizontal space
+    rotated_labels: bool,
+    transform: Option<(Matrix4, usize)>,
 }
 
 #[derive(Clone)]
@@ -38,32 +53,117 @@ impl LayerRenderer {
         LayerRenderer {
             division_renderer: Box::new(layer_divider),
             text_size: font.text_size(),
-            text_renderer: TextRenderer::new(context, font, font_settings, screen_height),
+            text_renderer: TextRenderer::new(
+                context,
+                font.clone(),
+                font_settings.clone(),
+                screen_height,
+            ),
+            right_text_renderer: TextRenderer::new(
+                context,
+                font.clone(),
+                font_settings,
+                screen_height,
+            ),
+            font,
+            layers: Vec::new(),
+            bounds: (0., 0.),
+            label_sides: LayerLabelSides::Left,
+            sticky_labels: true,
+            rotated_labels: false,
+            transform: None,
         }
     }
 
-    pub fn set_layers(&mut self, context: &WebGl2RenderingContext, layers: &Vec<Layer>) {
+    /// Sets the layers to be drawn, together with the horizontal range that the diagram covers
+    pub fn set_layers(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        layers: &Vec<Layer>,
+        bounds: (f32, f32),
+    ) {
         self.division_renderer.set_layers(context, layers);
+        self.layers = layers.clone();
+        self.bounds = bounds;
+        self.update_texts(context);
+    }
 
-        self.text_renderer.set_texts(
-            context,
-            &layers
+    pub fn set_label_sides(&mut self, context: &WebGl2RenderingContext, sides: LayerLabelSides) {
+        self.label_sides = sides;
+        self.update_texts(context);
+    }
+
+    pub fn set_sticky_labels(&mut self, context: &WebGl2RenderingContext, sticky: bool) {
+        self.sticky_labels = sticky;
+        self.update_texts(context);
+        self.update_text_transforms(context);
+    }
+
+    pub fn set_rotated_labels(&mut self, context: &WebGl2RenderingContext, rotated: bool) {
+        self.rotated_labels = rotated;
+        self.update_texts(context);
+    }
+
+    pub fn set_alternating_backgrounds(&mut self, alternating: bool) {
+        self.division_renderer.set_alternating(alternating);
+    }
+
+    pub fn set_pairing(&mut self, context: &WebGl2RenderingContext, pairing: LayerPairing) {
+        self.division_renderer.set_pairing(pairing);
+        self.division_renderer.set_layers(context, &self.layers);
+    }
+
+    fn update_texts(&mut self, context: &WebGl2RenderingContext) {
+        let margin = 0.5 * self.text_size;
+        let rotated = self.rotated_labels;
+        let texts = |x: &dyn Fn(f32) -> f32| -> Vec<Text> {
+            self.layers
                 .iter()
                 .map(|layer| {
                     let b = layer.bottom;
+                    let t = layer.top;
+                    // Rotated labels are centered in the layer, and use the text height as horizontal size
+                    let (x, y_old, y_new) = if rotated {
+                        let width = self.font.measure_width(&layer.label);
+                        let x = x(self.font.measure_height(&layer.label));
+                        (x, 0.5 * (b.old + t.old - width), 0.5 * (b.new + t.new - width))
+                    } else {
+                        (x(self.font.measure_width(&layer.label)), b.old, b.new)
+                    };
                     Text {
                         text: layer.label.clone(),
                         position: Transition {
                             old_time: b.old_time,
                             duration: b.duration,
-                            old: Point { x: 0., y: b.old },
-                            new: Point { x: 0., y: b.new },
+                            old: Point { x, y: y_old },
+                            new: Point { x, y: y_new },
                         },
                         exists: layer.exists,
+                        rotated,
                     }
                 })
-                .collect(),
-        );
+                .collect()
+        };
+
+        // Sticky labels are positioned relative to the screen edges by the transform, other labels are placed next to the diagram.
+        // The x position of rotated text specifies its right side, rather than its left side.
+        let (min_x, max_x) = self.bounds;
+        let left_texts = if !self.label_sides.has_left() {
+            Vec::new()
+        } else if self.sticky_labels {
+            texts(&|size| if rotated { size } else { 0. })
+        } else {
+            texts(&|size| min_x - margin - if rotated { 0. } else { size })
+        };
+        let right_texts = if !self.label_sides.has_right() {
+            Vec::new()
+        } else if self.sticky_labels {
+            texts(&|size| if rotated { 0. } else { -size })
+        } else {
+            texts(&|size| max_x + margin + if rotated { size } else { 0. })
+        };
+        self.text_renderer.set_texts(context, &left_texts);
+        self.right_text_renderer.set_texts(context, &right_texts);
     }
 
     pub fn set_transform_and_screen_height(
@@ -73,15 +173,30 @@ impl LayerRenderer {
         screen_height: usize,
     ) {
         self.division_renderer.set_transform(context, transform);
+        self.transform = Some((transform.clone(), screen_height));
+        self.update_text_transforms(context);
+    }
+
+    fn update_text_transforms(&mut self, context: &WebGl2RenderingContext) {
+        let Some((transform, screen_height)) = &self.transform else {
+            return;
+        };
 
         let margin = 0.5 * self.text_size;
-        let modified_transform = &mut transform.clone();
-        modified_transform.0[3] = -0.5 + margin * transform.0[0]; // Shift by -1 on the x-axis + margin
-        modified_transform.0[7] += margin * transform.0[5]; // Shift up by margin
-        self.text_renderer.set_transform_and_screen_height(
+        let left_transform = &mut transform.clone();
+        let right_transform = &mut transform.clone();
+        if self.sticky_labels {
+            left_transform.0[3] = -0.5 + margin * transform.0[0]; // Shift by -1 on the x-axis + margin
+            right_transform.0[3] = 0.5 - margin * transform.0[0]; // Shift by 1 on the x-axis - margin
+        }
+        left_transform.0[7] += margin * transform.0[5]; // Shift up by margin
+        right_transform.0[7] += margin * transform.0[5];
+        self.text_renderer
+            .set_transform_and_screen_height(context, left_transform, *screen_height);
+        self.right_text_renderer.set_transform_and_screen_height(
             context,
-            modified_transform,
-            screen_height,
+            right_transform,
+            *screen_height,
         );
     }
 
@@ -93,11 +208,19 @@ impl LayerRenderer {
         self.division_renderer.render(context, time);
 
         self.text_renderer.render(context, time);
+        self.right_text_renderer.render(context, time);
     }
 
     pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
         self.division_renderer.dispose(context);
         self.text_renderer.dispose(context);
+        self.right_text_renderer.dispose(context);
+    }
+
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.division_renderer.restore(context)?;
+        self.text_renderer.restore(context)?;
+        self.right_text_renderer.restore(context)
     }
 }
 
@@ -106,4 +229,10 @@ pub trait LayerDivisionRenderer {
     fn set_transform(&mut self, context: &WebGl2RenderingContext, transform: &Matrix4);
     fn render(&mut self, context: &WebGl2RenderingContext, time: u32);
     fn dispose(&mut self, context: &WebGl2RenderingContext);
+    /// Recreates the resources in the given context, e.g. after it was lost
+    fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue>;
+    /// Sets whether consecutive layers should be distinguishable
+    fn set_alternating(&mut self, alternating: bool) {}
+    /// Sets how adjacent layers are paired up, which takes effect when the layers are set next
+    fn set_pairing(&mut self, pairing: LayerPairing) {}
 }
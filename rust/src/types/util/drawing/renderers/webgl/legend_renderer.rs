@@ -0,0 +1,146 @@
+use std::rc::Rc;
+
+use itertools::Itertools;
+use wasm_bindgen::JsValue;
+use web_sys::WebGl2RenderingContext;
+
+use crate::{
+    types::util::drawing::{legend::LegendShape, renderers::util::Font::Font},
+    util::{matrix4::Matrix4, point::Point, transition::Transition},
+};
+
+use super::{
+    node_renderer::TextRenderingConfig,
+    text::text_renderer::{Text, TextRenderer},
+    util::{set_animated_data::set_animated_data, vertex_renderer::VertexRenderer},
+};
+
+/// Draws a legend of the visual encodings, which is only shown in exported images
+pub struct LegendRenderer {
+    vertex_renderer: VertexRenderer,
+    text_renderer: TextRenderer,
+    font: Rc<Font>,
+}
+
+impl LegendRenderer {
+    pub fn new(context: &WebGl2RenderingContext, text: TextRenderingConfig) -> LegendRenderer {
+        // The legend consists of plain colored triangles, like the annotations
+        let vertex_renderer = VertexRenderer::new(
+            context,
+            include_str!("annotation_renderer.vert"),
+            include_str!("annotation_renderer.frag"),
+        )
+        .unwrap();
+        LegendRenderer {
+            vertex_renderer,
+            text_renderer: TextRenderer::new(
+                context,
+                text.font.clone(),
+                text.font_settings,
+                text.screen_height,
+            ),
+            font: text.font,
+        }
+    }
+
+    pub fn set_shapes(&mut self, context: &WebGl2RenderingContext, shapes: &Vec<LegendShape>) {
+        let triangles = shapes
+            .iter()
+            .flat_map(|shape| {
+                let LegendShape::Rect { rect, color } = shape else {
+                    return Vec::new();
+                };
+                let (left, right) = (rect.x, rect.x + rect.width);
+                let (bottom, top) = (rect.y, rect.y + rect.height);
+                [
+                    (right, top),
+                    (right, bottom),
+                    (left, bottom),
+                    (right, top),
+                    (left, bottom),
+                    (left, top),
+                ]
+                .map(|(x, y)| (Point { x, y }, *color))
+                .to_vec()
+            })
+            .collect_vec();
+
+        set_animated_data(
+            "position",
+            triangles.iter().map(|(position, _)| Transition::plain(*position)),
+            |position| [position.x, position.y],
+            context,
+            &mut self.vertex_renderer,
+        );
+        set_animated_data(
+            "exists",
+            triangles.iter().map(|_| Transition::plain(1.)),
+            |exists| [exists],
+            context,
+            &mut self.vertex_renderer,
+        );
+        self.vertex_renderer.set_data(
+            context,
+            "color",
+            &triangles
+                .iter()
+                .flat_map(|(_, color)| [color.0, color.1, color.2, 1.])
+                .collect::<Box<_>>(),
+            4,
+        );
+        self.vertex_renderer.send_data(context);
+
+        self.text_renderer.set_texts(
+            context,
+            &shapes
+                .iter()
+                .filter_map(|shape| {
+                    let LegendShape::Text { position, text } = shape else {
+                        return None;
+                    };
+                    let offset = Point {
+                        x: 0.,
+                        y: -0.5 * self.font.measure_height(text),
+                    };
+                    Some(Text {
+                        text: text.clone(),
+                        position: Transition::plain(*position + offset),
+                        exists: Transition::plain(1.),
+                        rotated: false,
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    pub fn set_transform_and_screen_height(
+        &mut self,
+        context: &WebGl2RenderingContext,
+        transform: &Matrix4,
+        screen_height: usize,
+    ) {
+        self.vertex_renderer.set_uniform(context, "transform", |u| {
+            context.uniform_matrix4fv_with_f32_array(u, true, &transform.0)
+        });
+        self.text_renderer
+            .set_transform_and_screen_height(context, transform, screen_height);
+    }
+
+    pub fn render(&mut self, context: &WebGl2RenderingContext, time: u32) {
+        self.vertex_renderer
+            .set_uniform(context, "time", |u| context.uniform1f(u, time as f32));
+        self.vertex_renderer
+            .render(context, WebGl2RenderingContext::TRIANGLES);
+        self.text_renderer.render(context, time);
+    }
+
+    pub fn dispose(&mut self, context: &WebGl2RenderingContext) {
+        self.vertex_renderer.dispose(context);
+        self.text_renderer.dispose(context);
+    }
+
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)?;
+        self.text_renderer.restore(context)
+    }
+}
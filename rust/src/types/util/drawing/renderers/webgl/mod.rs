@@ -1,5 +1,7 @@
+pub mod annotation_renderer;
 pub mod edge_renderer;
 pub mod layers;
+pub mod legend_renderer;
 pub mod node_renderer;
 pub mod text;
 pub mod util;
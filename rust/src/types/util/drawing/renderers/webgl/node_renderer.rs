@@ -4,12 +4,16 @@ use std::{
     rc::Rc,
 };
 
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
     types::util::drawing::{
         renderer::GroupSelection,
-        renderers::{util::Font::Font, webgl::util::set_animated_data::set_animated_data},
+        renderers::{
+            util::Font::Font,
+            webgl::util::set_animated_data::{set_animated_data, update_animated_data},
+        },
     },
     util::{
         color::{Color, TransparentColor},
@@ -32,6 +36,10 @@ pub struct NodeRenderer {
     text_renderer: TextRenderer,
     font: Rc<Font>,
     node_indices: HashMap<NodeGroupID, NodeData>,
+    /// The index of every node that has an outline among the outlined nodes
+    outline_indices: HashMap<NodeGroupID, usize>,
+    /// The nodes that are drawn, from which the labels are created
+    nodes: Vec<Node>,
     colors: NodeRenderingColorConfig,
 }
 pub struct NodeData {
@@ -47,9 +55,12 @@ pub struct Node {
     pub color: Transition<Color>,
     pub outline_color: Transition<TransparentColor>,
     pub label: Option<String>,
+    pub label_offset: Transition<Point>, // The offset of the center of the label relative to the center of the node
+    pub label_exists: Transition<f32>,
     pub exists: Transition<f32>, // A number between 0 and 1 of whether this node is visible (0-1)
 }
 
+#[derive(Clone)]
 pub struct NodeRenderingColorConfig {
     pub select: TransparentColor,
     pub partial_select: TransparentColor,
@@ -86,6 +97,8 @@ impl NodeRenderer {
             vertex_renderer,
             outline_vertex_renderer,
             node_indices: HashMap::new(),
+            outline_indices: HashMap::new(),
+            nodes: Vec::new(),
             colors,
             font: text.font.clone(),
             text_renderer: TextRenderer::new(
@@ -145,9 +158,12 @@ impl NodeRenderer {
         self.vertex_renderer.send_data(context);
 
         // Outline shape
-        let outline_nodes = nodes
-            .iter()
-            .filter(|node| node.outline_color.new.3 != 0. || node.outline_color.old.3 != 0.);
+        let outline_nodes = nodes.iter().filter(|node| has_outline(node));
+        self.outline_indices = outline_nodes
+            .clone()
+            .enumerate()
+            .map(|(index, node)| (node.ID, index))
+            .collect();
         let outline_nodes6 = outline_nodes.flat_map(|node| repeat(node).take(6));
         set_animated_data(
             "position",
@@ -179,10 +195,47 @@ impl NodeRenderer {
         );
         self.outline_vertex_renderer.send_data(context);
 
-        // Text
+        self.nodes = nodes.clone();
+        self.set_label_data(context);
+    }
+
+    /// Updates the positions, sizes and presence of the given nodes in place, such that only their ranges of the buffers are sent again. The colors are kept, since these include the selection. Returns false if a node was not drawn before or gained or lost its outline, in which case all nodes have to be set instead.
+    pub fn update_nodes(&mut self, context: &WebGl2RenderingContext, nodes: &[Node]) -> bool {
+        let updatable = nodes.iter().all(|node| {
+            self.node_indices.contains_key(&node.ID)
+                && has_outline(node) == self.outline_indices.contains_key(&node.ID)
+        });
+        if !updatable {
+            return false;
+        }
+
+        let mut labels_changed = false;
+        for node in nodes {
+            let index = self.node_indices[&node.ID].index;
+            for vertex in index * 6..(index + 1) * 6 {
+                update_node_vertex(context, &mut self.vertex_renderer, vertex, node);
+            }
+            if let Some(&outline_index) = self.outline_indices.get(&node.ID) {
+                for vertex in outline_index * 6..(outline_index + 1) * 6 {
+                    update_node_vertex(context, &mut self.outline_vertex_renderer, vertex, node);
+                }
+            }
+            labels_changed |= node.label.is_some() || self.nodes[index].label.is_some();
+            self.nodes[index] = node.clone();
+        }
+        self.vertex_renderer.send_data(context);
+        self.outline_vertex_renderer.send_data(context);
+        if labels_changed {
+            self.set_label_data(context);
+        }
+        true
+    }
+
+    fn set_label_data(&mut self, context: &WebGl2RenderingContext) {
         self.text_renderer.set_texts(
             context,
-            &nodes
+            &self
+                .nodes
                 .iter()
                 .filter_map(|node| {
                     node.label.clone().map(|text| {
@@ -190,7 +243,7 @@ impl NodeRenderer {
                         let text_height = self.font.measure_height(&text);
                         Text {
                             text,
-                            position: &node.center_position
+                            position: &(&node.center_position + &node.label_offset)
                                 + &Transition {
                                     old_time: node.size.old_time,
                                     duration: node.size.duration,
@@ -203,7 +256,12 @@ impl NodeRenderer {
                                         y: -0.5 * text_height,
                                     },
                                 },
-                            exists: node.exists,
+                            exists: Transition {
+                                old: node.exists.old * node.label_exists.old,
+                                new: node.exists.new * node.label_exists.new,
+                                ..node.exists
+                            },
+                            rotated: false,
                         }
                     })
                 })
@@ -361,4 +419,32 @@ impl NodeRenderer {
         self.outline_vertex_renderer.dispose(context);
         self.text_renderer.dispose(context);
     }
+
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)?;
+        self.outline_vertex_renderer.restore(context)?;
+        self.text_renderer.restore(context)
+    }
+}
+
+fn has_outline(node: &Node) -> bool {
+    node.outline_color.new.3 != 0. || node.outline_color.old.3 != 0.
+}
+
+fn update_node_vertex(
+    context: &WebGl2RenderingContext,
+    renderer: &mut VertexRenderer,
+    vertex: usize,
+    node: &Node,
+) {
+    update_animated_data(
+        "position",
+        vertex,
+        node.center_position,
+        |v| [v.x, v.y],
+        context,
+        renderer,
+    );
+    update_animated_data("size", vertex, node.size, |v| [v.x, v.y], context, renderer);
+    update_animated_data("exists", vertex, node.exists, |v| [v], context, renderer);
 }
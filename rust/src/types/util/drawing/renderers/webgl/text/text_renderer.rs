@@ -19,6 +19,7 @@ use swash::{
     zeno::{Command, PathData, Vector},
     Charmap, FontRef, GlyphId,
 };
+use wasm_bindgen::JsValue;
 use web_sys::WebGl2RenderingContext;
 
 use crate::{
@@ -38,6 +39,9 @@ use crate::{
     },
 };
 
+/// The screen height (in device pixels) that the glyph outlines are scaled for, atlases for other screen heights are obtained through the zoom buckets
+const REFERENCE_SCREEN_HEIGHT: f32 = 1000.;
+
 pub struct TextRenderer {
     vertex_renderer: VertexRenderer,
     char_renderer: VertexRenderer,
@@ -65,6 +69,7 @@ pub struct Text {
     pub text: String,
     pub position: Transition<Point>,
     pub exists: Transition<f32>, // A number between 0 and 1 of whether this node is visible (0-1)
+    pub rotated: bool, // Whether the text is rotated by 90 degrees, such that it reads from bottom to top
 }
 
 /**
@@ -95,8 +100,7 @@ impl TextRenderer {
 
         // let charmap = CharmapProxy::from_font(&font).materialize(&font);
 
-        let (scaler_context, scaler) =
-            create_scaler(screen_height, (*font).as_ref().clone(), &settings);
+        let (scaler_context, scaler) = create_scaler((*font).as_ref().clone(), &settings);
         TextRenderer {
             vertex_renderer,
             char_renderer,
@@ -127,7 +131,7 @@ impl TextRenderer {
         self.font.text_size()
     }
     fn get_atlas_resolution(&self) -> f32 {
-        self.screen_height * self.settings.resolution * self.settings.scale_factor_group_size
+        REFERENCE_SCREEN_HEIGHT * self.settings.resolution * self.settings.scale_factor_group_size
     }
     fn get_scale_index(&self, scale: f32) -> i32 {
         (scale.log2() / self.settings.scale_factor_group_size.log2()).floor() as i32
@@ -194,7 +198,7 @@ impl TextRenderer {
         let finish_texture = |atlas: &mut Atlas, width: f32, height: f32| {
             let width = f32::ceil(width) as usize;
             let height = f32::ceil(height) as usize;
-            console::log!("atlas: ({}, {})", width, height);
+            console::log_debug!("atlas: ({}, {})", width, height);
 
             atlas
                 .textures
@@ -349,11 +353,14 @@ impl TextRenderer {
                     for glyph in cluster.glyphs {
                         chars.push((
                             glyph.id,
-                            &text.position
-                                + &Transition::plain(Point {
+                            (
+                                text.position,
+                                Point {
                                     x: glyph.x + x,
                                     y: glyph.y,
-                                }),
+                                },
+                                text.rotated,
+                            ),
                             text.exists,
                         ));
                         x += glyph.advance;
@@ -410,37 +417,65 @@ impl TextRenderer {
                 p4.x, p4.y,
             ]
         };
+        // Creates the square of a character, relative to the origin of its text which may be rotated
+        let make_char_square = |origin: Point, p: Point, size: Point, rotated: bool| {
+            let map = |p: Point| {
+                origin
+                    + if rotated {
+                        Point { x: -p.y, y: p.x }
+                    } else {
+                        p
+                    }
+            };
+            let p1 = map(p);
+            let p2 = map(p + Point { x: 0., y: size.y });
+            let p3 = map(p + size);
+            let p4 = map(p + Point { x: size.x, y: 0. });
+            [
+                p1.x, p1.y, //
+                p2.x, p2.y, //
+                p4.x, p4.y, //
+                /* */
+                p3.x, p3.y, //
+                p2.x, p2.y, //
+                p4.x, p4.y,
+            ]
+        };
 
         let char_to_draw_scale =
             self.get_draw_scale() / self.get_char_scale() / self.get_atlas_resolution();
 
         let positions_old = char_data
             .iter()
-            .flat_map(|((glyph_pos, offset, _), text_pos, _)| {
-                make_square(
-                    text_pos.old + *offset * char_to_draw_scale,
+            .flat_map(|((glyph_pos, offset, _), (text_pos, char_pos, rotated), _)| {
+                make_char_square(
+                    text_pos.old,
+                    *char_pos + *offset * char_to_draw_scale,
                     glyph_pos.size() * char_to_draw_scale,
+                    *rotated,
                 )
             })
             .collect::<Vec<_>>();
 
         let positions_new = char_data
             .iter()
-            .flat_map(|((glyph_pos, offset, _), text_pos, _)| {
-                make_square(
-                    text_pos.new + *offset * char_to_draw_scale,
+            .flat_map(|((glyph_pos, offset, _), (text_pos, char_pos, rotated), _)| {
+                make_char_square(
+                    text_pos.new,
+                    *char_pos + *offset * char_to_draw_scale,
                     glyph_pos.size() * char_to_draw_scale,
+                    *rotated,
                 )
             })
             .collect::<Vec<_>>();
 
         let positions_start_time = char_data
             .iter()
-            .flat_map(|(_, text_pos, _)| [text_pos.old_time as f32; 6])
+            .flat_map(|(_, (text_pos, _, _), _)| [text_pos.old_time as f32; 6])
             .collect::<Vec<_>>();
         let positions_duration = char_data
             .iter()
-            .flat_map(|(_, text_pos, _)| [text_pos.duration as f32; 6])
+            .flat_map(|(_, (text_pos, _, _), _)| [text_pos.duration as f32; 6])
             .collect::<Vec<_>>();
 
         let char_coords = char_data
@@ -508,16 +543,9 @@ impl TextRenderer {
         transform: &Matrix4,
         screen_height: usize,
     ) {
-        let height = screen_height as f32;
-        let height_change = self.screen_height != height;
-        if height_change {
-            self.atlases.clear();
-            let (scaler_context, scaler) =
-                create_scaler(screen_height, (*self.font).as_ref().clone(), &self.settings);
-            self.char_scaler = scaler;
-            self._char_scaler_context = scaler_context;
-            self.screen_height = height;
-        }
+        // The atlases only depend on the number of pixels per unit, so they remain valid when the screen height changes
+        let was_hidden = self.screen_height == 0.;
+        self.screen_height = screen_height as f32;
         if self.screen_height == 0. {
             return;
         }
@@ -532,10 +560,11 @@ impl TextRenderer {
         let exact_scale = Point { x: p1.0, y: p1.1 }
             .distance(&Point { x: p2.0, y: p2.1 })
             .min(self.settings.max_scale);
-        let scale_index = self.get_scale_index(exact_scale);
+        let pixel_scale = exact_scale * self.screen_height / REFERENCE_SCREEN_HEIGHT;
+        let scale_index = self.get_scale_index(pixel_scale);
         let cur_index = self.cur_scale_index;
 
-        if cur_index != scale_index || height_change {
+        if cur_index != scale_index || was_hidden {
             self.cur_scale_index = scale_index;
 
             let charmap = (*self.font).as_ref().charmap();
@@ -601,6 +630,20 @@ impl TextRenderer {
             }
         }
     }
+
+    /// Recreates all resources in the given context, where the atlases are drawn again from the glyph outlines of the current texts
+    pub fn restore(&mut self, context: &WebGl2RenderingContext) -> Result<(), JsValue> {
+        self.vertex_renderer.restore(context)?;
+        self.char_renderer.restore(context)?;
+        for (_, char_atlas) in &self.atlases {
+            for atlas in &char_atlas.textures {
+                atlas.dispose(context);
+            }
+        }
+        self.atlases.clear();
+        self.set_texts(context, &self.cur_text.clone());
+        Ok(())
+    }
 }
 
 type CharDataMap = HashMap<u16, ((f32, f32), Outline)>;
@@ -688,7 +731,6 @@ impl TextRendererSettings {
 }
 
 fn create_scaler(
-    screen_height: usize,
     font: FontRef<'static>,
     settings: &TextRendererSettings,
 ) -> (Box<ScaleContext>, Scaler<'static>) {
@@ -698,7 +740,7 @@ fn create_scaler(
     };
     let scaler = scaler_context_ref
         .builder(font)
-        .size(screen_height as f32 * settings.resolution * settings.scale_factor_group_size)
+        .size(REFERENCE_SCREEN_HEIGHT * settings.resolution * settings.scale_factor_group_size)
         .build();
     (scaler_context, scaler)
 }
@@ -114,6 +114,8 @@ impl RenderTarget for RenderTexture {
 
 // Screen texture
 pub struct ScreenTexture {
+    /// The framebuffer provided by the embedding application, or none to draw to the canvas
+    framebuffer: Option<WebGlFramebuffer>,
     width: usize,
     height: usize,
     color: (f32, f32, f32, f32),
@@ -122,6 +124,21 @@ pub struct ScreenTexture {
 impl ScreenTexture {
     pub fn new(width: usize, height: usize, color: (f32, f32, f32, f32)) -> ScreenTexture {
         ScreenTexture {
+            framebuffer: None,
+            width,
+            height,
+            color,
+        }
+    }
+    /// Creates a target that draws into a framebuffer owned by another application, such that the result can be composited into its scene
+    pub fn from_framebuffer(
+        framebuffer: Option<WebGlFramebuffer>,
+        width: usize,
+        height: usize,
+        color: (f32, f32, f32, f32),
+    ) -> ScreenTexture {
+        ScreenTexture {
+            framebuffer,
             width,
             height,
             color,
@@ -135,7 +152,7 @@ impl ScreenTexture {
 impl RenderTarget for ScreenTexture {
     fn bind_buffer(&self, context: &Gl) {
         context.viewport(0, 0, self.width as i32, self.height as i32);
-        context.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        context.bind_framebuffer(Gl::FRAMEBUFFER, self.framebuffer.as_ref());
     }
 
     fn clear(&self, context: &Gl) {
@@ -33,3 +33,27 @@ pub fn set_animated_data<
         2,
     );
 }
+
+/// Overwrites the animated data of the element at the given index, such that only the changed range of the buffer is sent again
+pub fn update_animated_data<const L: usize, T: Interpolatable, V: Fn(T) -> [f32; L]>(
+    name: &str,
+    element_index: usize,
+    data: Transition<T>,
+    values: V,
+    context: &WebGl2RenderingContext,
+    renderer: &mut VertexRenderer,
+) {
+    renderer.update_data(
+        context,
+        &format!("{}Old", name)[..],
+        element_index,
+        values(data.old),
+    );
+    renderer.update_data(context, name, element_index, values(data.new));
+    renderer.update_data(
+        context,
+        &format!("{}Transition", name)[..],
+        element_index,
+        [data.old_time as f32, data.duration as f32],
+    );
+}
@@ -13,6 +13,9 @@ use super::setup::{compile_shader, link_program};
 
 pub struct VertexRenderer {
     program: WebGlProgram,
+    /// The sources of the shaders with the template variables replaced, from which the program is recreated after a context loss
+    vertex_shader: String,
+    fragment_shader: String,
     attributes: Vec<AttributeData>,
     buffer: WebGlBuffer,
     vao: WebGlVertexArrayObject,
@@ -43,29 +46,13 @@ impl VertexRenderer {
         fragment_shader: &str,
         template_vars: Option<&HashMap<&str, &str>>,
     ) -> Result<VertexRenderer, JsValue> {
-        let vert_shader = compile_shader(
-            &context,
-            Gl::VERTEX_SHADER,
-            &replace_template_vars(vertex_shader, template_vars).as_str(),
-        )?;
-
-        let frag_shader = compile_shader(
-            &context,
-            Gl::FRAGMENT_SHADER,
-            &replace_template_vars(fragment_shader, template_vars).as_str(),
-        )?;
-
-        let program = link_program(&context, &vert_shader, &frag_shader)?;
-        context.use_program(Some(&program));
-
-        let buffer = context.create_buffer().ok_or("Failed to create buffer")?;
-        context.bind_buffer(Gl::ARRAY_BUFFER, Some(&buffer));
-
-        let vao = context
-            .create_vertex_array()
-            .ok_or("Could not create vertex array object")?;
+        let vertex_shader = replace_template_vars(vertex_shader, template_vars);
+        let fragment_shader = replace_template_vars(fragment_shader, template_vars);
+        let (program, buffer, vao) = create_objects(context, &vertex_shader, &fragment_shader)?;
         Ok(VertexRenderer {
             program,
+            vertex_shader,
+            fragment_shader,
             vao,
             buffer,
             attributes: Vec::new(),
@@ -254,6 +241,47 @@ impl VertexRenderer {
         context.delete_program(Some(&self.program));
         context.delete_vertex_array(Some(&self.vao));
     }
+
+    /// Recreates the program, buffer and vertex array in the given context and sends all data again, e.g. after the context was lost. Uniforms have to be set again afterwards.
+    pub fn restore(&mut self, context: &Gl) -> Result<(), JsValue> {
+        // Deleting objects of a lost context has no effect
+        self.dispose(context);
+        let (program, buffer, vao) =
+            create_objects(context, &self.vertex_shader, &self.fragment_shader)?;
+        for attribute in &mut self.attributes {
+            attribute.attribute_location =
+                context.get_attrib_location(&program, &attribute.name) as u32;
+        }
+        self.program = program;
+        self.buffer = buffer;
+        self.vao = vao;
+        self.uniforms.clear();
+        self.dirty_ranges.clear();
+        self.size_changed = true;
+        self.send_data(context);
+        Ok(())
+    }
+}
+
+/// Compiles the program of the given shaders, and creates the buffer and vertex array for its attributes
+fn create_objects(
+    context: &Gl,
+    vertex_shader: &str,
+    fragment_shader: &str,
+) -> Result<(WebGlProgram, WebGlBuffer, WebGlVertexArrayObject), JsValue> {
+    let vert_shader = compile_shader(context, Gl::VERTEX_SHADER, vertex_shader)?;
+    let frag_shader = compile_shader(context, Gl::FRAGMENT_SHADER, fragment_shader)?;
+
+    let program = link_program(context, &vert_shader, &frag_shader)?;
+    context.use_program(Some(&program));
+
+    let buffer = context.create_buffer().ok_or("Failed to create buffer")?;
+    context.bind_buffer(Gl::ARRAY_BUFFER, Some(&buffer));
+
+    let vao = context
+        .create_vertex_array()
+        .ok_or("Could not create vertex array object")?;
+    Ok((program, buffer, vao))
 }
 
 fn replace_template_vars(template: &str, vars: Option<&HashMap<&str, &str>>) -> String {
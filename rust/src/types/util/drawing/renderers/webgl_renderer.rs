@@ -1,23 +1,28 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
+use itertools::Itertools;
 use oxidd_core::Tag;
 use wasm_bindgen::prelude::*;
 use web_sys::{
-    HtmlCanvasElement, WebGl2RenderingContext, WebGlBuffer, WebGlProgram, WebGlShader,
-    WebGlVertexArrayObject,
+    HtmlCanvasElement, WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram,
+    WebGlShader, WebGlVertexArrayObject,
 };
 
 use crate::{
     types::util::{
         drawing::{
-            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            annotations::AnnotationShape,
+            diagram_layout::{DiagramLayout, LayerStyle, NodeGroupLayout, NodeStyle},
             layout_rules::LayoutRules,
-            renderer::{GroupSelection, Renderer},
+            legend::LegendShape,
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
         },
         graph_structure::graph_structure::{DrawTag, EdgeType},
     },
     util::{
-        color::{Color, TransparentColor},
         logging::console,
         point::Point,
         transformation::Transformation,
@@ -26,29 +31,63 @@ use crate::{
     wasm_interface::NodeGroupID,
 };
 
+// The styles are shared with the SVG exporter of the core
+pub use super::render_styles::{LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle};
+#[cfg(feature = "webgpu")]
+use super::webgpu_renderer::WebgpuContext;
 use super::{
-    util::Font::Font,
+    util::{
+        edge_arrows::EdgeArrows, layer_label_sides::LayerLabelSides,
+        layer_pairing::LayerPairing, Font::Font,
+    },
     webgl::{
+        annotation_renderer::AnnotationRenderer,
         edge_renderer::{Edge, EdgeRenderer, EdgeRenderingType},
         layers::{
             layer_bg_renderer::LayerBgRenderer,
             layer_lines_renderer::LayerLinesRenderer,
             layer_renderer::{Layer, LayerRenderer},
         },
+        legend_renderer::LegendRenderer,
         node_renderer::{Node, NodeRenderer, NodeRenderingColorConfig, TextRenderingConfig},
         text::text_renderer::{Text, TextRenderer, TextRendererSettings},
         util::render_texture::{RenderTarget, ScreenTexture},
     },
 };
 
+/// The surface that a screen renderer draws to
+pub enum RenderSurface {
+    Canvas(HtmlCanvasElement),
+    /// A canvas that is drawn to through webgpu, which the webgl renderer can not draw to
+    #[cfg(feature = "webgpu")]
+    Webgpu(WebgpuContext),
+    /// A framebuffer of a context owned by another webgl application, where none refers to the default framebuffer of that context
+    Framebuffer {
+        context: WebGl2RenderingContext,
+        framebuffer: Option<WebGlFramebuffer>,
+        width: u32,
+        height: u32,
+    },
+}
+
 /// A simple renderer that uses webgl to draw nodes and edges
 pub struct WebglRenderer<T: DrawTag> {
     webgl_context: WebGl2RenderingContext,
     node_renderer: NodeRenderer,
     edge_renderer: EdgeRenderer,
     layer_renderer: LayerRenderer,
+    annotation_renderer: AnnotationRenderer,
+    legend_renderer: LegendRenderer,
     edge_type_ids: HashMap<EdgeType<T>, usize>,
     screen_texture: ScreenTexture,
+    /// Whether anything changed since the last frame, other than the time
+    dirty: bool,
+    /// The time at which the transitions of the shown elements have finished
+    animation_end: u32,
+    /// The time of the last frame that was drawn, if any
+    rendered_time: Option<u32>,
+    /// The last transform that was set, which is applied again after the context was restored
+    transform: Option<Transformation>,
 }
 
 impl<T: DrawTag> WebglRenderer<T> {
@@ -81,12 +120,7 @@ impl<T: DrawTag> WebglRenderer<T> {
             .max_scale(1.5)
             .color(node_colors.text);
 
-        // context.enable(WebGl2RenderingContext::DEPTH_TEST);
-        context.enable(WebGl2RenderingContext::BLEND);
-        context.blend_func(
-            WebGl2RenderingContext::ONE,
-            WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
-        );
+        setup_context(&context);
 
         Ok(WebglRenderer {
             node_renderer: NodeRenderer::new(
@@ -98,10 +132,39 @@ impl<T: DrawTag> WebglRenderer<T> {
                     font_settings: font_settings.clone(),
                 },
             ),
-            edge_renderer: EdgeRenderer::new(&context, edge_rendering_types),
+            edge_renderer: EdgeRenderer::new(
+                &context,
+                edge_rendering_types,
+                TextRenderingConfig {
+                    screen_height,
+                    font: font.clone(),
+                    font_settings: font_settings.clone(),
+                },
+            ),
+            annotation_renderer: AnnotationRenderer::new(
+                &context,
+                TextRenderingConfig {
+                    screen_height,
+                    font: font.clone(),
+                    font_settings: font_settings.clone(),
+                },
+            ),
+            legend_renderer: LegendRenderer::new(
+                &context,
+                TextRenderingConfig {
+                    screen_height,
+                    font: font.clone(),
+                    font_settings: font_settings.clone(),
+                },
+            ),
             layer_renderer: LayerRenderer::new(
                 &context,
-                LayerBgRenderer::new(&context, layer_colors.background1, layer_colors.background2),
+                LayerBgRenderer::new(
+                    &context,
+                    layer_colors.background1,
+                    layer_colors.background2,
+                    layer_colors.text.into(),
+                ),
                 // LayerLinesRenderer::new(&context),
                 screen_height,
                 font,
@@ -110,6 +173,10 @@ impl<T: DrawTag> WebglRenderer<T> {
             webgl_context: context,
             screen_texture,
             edge_type_ids,
+            dirty: true,
+            animation_end: 0,
+            rendered_time: None,
+            transform: None,
         })
     }
     pub fn from_canvas(
@@ -119,12 +186,12 @@ impl<T: DrawTag> WebglRenderer<T> {
         layer_colors: LayerRenderingColorConfig,
         font: Rc<Font>,
     ) -> Result<WebglRenderer<T>, JsValue> {
+        // The context is missing if webgl is disabled or not supported by the browser
         let context = canvas
-            .get_context("webgl2")
-            .unwrap()
-            .unwrap()
+            .get_context("webgl2")?
+            .ok_or("WebGL 2 is not available")?
             .dyn_into::<WebGl2RenderingContext>()
-            .unwrap();
+            .map_err(|_| "WebGL 2 is not available")?;
         let c = layer_colors.background1;
         WebglRenderer::new(
             context,
@@ -139,14 +206,143 @@ impl<T: DrawTag> WebglRenderer<T> {
             font,
         )
     }
-}
+    pub fn from_surface(
+        surface: RenderSurface,
+        edge_types: HashMap<EdgeType<T>, EdgeRenderingType>,
+        node_colors: NodeRenderingColorConfig,
+        layer_colors: LayerRenderingColorConfig,
+        font: Rc<Font>,
+    ) -> Result<WebglRenderer<T>, JsValue> {
+        match surface {
+            RenderSurface::Canvas(canvas) => {
+                WebglRenderer::from_canvas(canvas, edge_types, node_colors, layer_colors, font)
+            }
+            RenderSurface::Framebuffer {
+                context,
+                framebuffer,
+                width,
+                height,
+            } => WebglRenderer::new(
+                context,
+                // Clear to transparent, such that the scene of the embedding application shows through
+                ScreenTexture::from_framebuffer(
+                    framebuffer,
+                    width as usize,
+                    height as usize,
+                    (0., 0., 0., 0.),
+                ),
+                edge_types,
+                node_colors,
+                layer_colors,
+                font,
+            ),
+            #[cfg(feature = "webgpu")]
+            RenderSurface::Webgpu(_) => {
+                Err("A webgpu surface can not be drawn to with webgl".into())
+            }
+        }
+    }
 
-impl<L: LayoutRules> Renderer<L> for WebglRenderer<L::T>
-where
-    L::NS: WebglNodeStyle,
-    L::LS: WebglLayerStyle,
-{
-    fn set_transform(&mut self, transform: Transformation) {
+    /// Hides or shows the geometry of all edges of the given type, without requiring a new layout
+    pub fn set_edge_type_visibility(&mut self, edge_type: EdgeType<T>, visible: bool) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            self.edge_renderer.set_type_visibility(id, visible);
+            self.dirty = true;
+        }
+    }
+
+    /// Sets the stroke width of all edges of the given type, in world units
+    pub fn set_edge_type_width(&mut self, edge_type: EdgeType<T>, width: f32) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            self.edge_renderer.set_type_width(id, width);
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_edge_arrows(&mut self, arrows: EdgeArrows) {
+        self.edge_renderer
+            .set_arrows(&self.webgl_context, arrows);
+        self.dirty = true;
+    }
+
+    pub fn set_layer_label_sides(&mut self, sides: LayerLabelSides) {
+        self.layer_renderer
+            .set_label_sides(&self.webgl_context, sides);
+        self.dirty = true;
+    }
+
+    /// Sets whether the layer labels stay at the edges of the screen while panning, or are placed next to the diagram
+    pub fn set_sticky_layer_labels(&mut self, sticky: bool) {
+        self.layer_renderer
+            .set_sticky_labels(&self.webgl_context, sticky);
+        self.dirty = true;
+    }
+
+    pub fn set_rotated_layer_labels(&mut self, rotated: bool) {
+        self.layer_renderer
+            .set_rotated_labels(&self.webgl_context, rotated);
+        self.dirty = true;
+    }
+
+    pub fn set_alternating_layer_backgrounds(&mut self, alternating: bool) {
+        self.layer_renderer.set_alternating_backgrounds(alternating);
+        self.dirty = true;
+    }
+
+    /// Sets whether adjacent layers are paired up by brackets, and possibly merged into one background band
+    pub fn set_layer_pairing(&mut self, pairing: LayerPairing) {
+        self.layer_renderer
+            .set_pairing(&self.webgl_context, pairing);
+        self.dirty = true;
+    }
+
+    /// Sets the shapes of the legend drawn next to the diagram, which is only used while exporting images
+    pub fn set_legend(&mut self, shapes: &Vec<LegendShape>) {
+        self.legend_renderer
+            .set_shapes(&self.webgl_context, shapes);
+        self.dirty = true;
+    }
+
+    /// Resizes the drawing buffer of the canvas, e.g. to temporarily render an export at a different resolution
+    pub fn set_canvas_size(&mut self, width: u32, height: u32) {
+        if let Some(canvas) = self.get_canvas() {
+            canvas.set_width(width);
+            canvas.set_height(height);
+        }
+        // Resizing clears the drawing buffer
+        self.dirty = true;
+    }
+
+    /// Sets the layers of the given layout, spanning the horizontal extent of all groups
+    fn set_layers<S: NodeStyle, LS: WebglLayerStyle>(&mut self, layout: &DiagramLayout<T, S, LS>) {
+        let bounds = layout
+            .groups
+            .values()
+            .map(|group| {
+                let x = group.position.new.x;
+                let half_width = 0.5 * group.size.new.x;
+                (x - half_width, x + half_width)
+            })
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+            .unwrap_or((0., 0.));
+        self.layer_renderer.set_layers(
+            &self.webgl_context,
+            &layout
+                .layers
+                .iter()
+                .map(|layer| Layer {
+                    top: layer.top,
+                    bottom: layer.bottom,
+                    label: layer.style.new.get_label(),
+                    index: layer.index,
+                    exists: layer.exists,
+                })
+                .collect(),
+            bounds,
+        );
+    }
+
+    fn apply_transform(&mut self, transform: &Transformation) {
         let height = transform.height as usize;
         // if self.screen_texture.get_size().1 != height {
         //     self.layer_renderer
@@ -158,91 +354,118 @@ where
         let matrix = transform.get_matrix();
         self.node_renderer
             .set_transform_and_screen_height(&self.webgl_context, &matrix, height);
-        self.edge_renderer
-            .set_transform(&self.webgl_context, &matrix);
+        self.edge_renderer.set_transform(
+            &self.webgl_context,
+            &matrix,
+            1.0 / transform.scale,
+            height,
+        );
         self.layer_renderer
             .set_transform_and_screen_height(&self.webgl_context, &matrix, height);
+        self.annotation_renderer
+            .set_transform_and_screen_height(&self.webgl_context, &matrix, height);
+        self.legend_renderer
+            .set_transform_and_screen_height(&self.webgl_context, &matrix, height);
+        self.dirty = true;
+    }
+
+    /// Recreates all resources after the webgl context was lost and restored, from the copies of the geometry and texts that are kept in memory
+    pub fn restore_context(&mut self) -> Result<(), JsValue> {
+        let context = &self.webgl_context;
+        if context.is_context_lost() {
+            return Err("The webgl context has not been restored yet".into());
+        }
+        setup_context(context);
+        self.node_renderer.restore(context)?;
+        self.edge_renderer.restore(context)?;
+        self.layer_renderer.restore(context)?;
+        self.annotation_renderer.restore(context)?;
+        self.legend_renderer.restore(context)?;
+        // Uniforms are not kept by the restored programs
+        if let Some(transform) = self.transform.clone() {
+            self.apply_transform(&transform);
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Ensures that the next frame is drawn, even if nothing changed, e.g. because the canvas was cleared externally
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Retrieves the current contents of the canvas as a png data url
+    pub fn get_png_data_url(&self) -> Option<String> {
+        self.get_canvas()?.to_data_url_with_type("image/png").ok()
+    }
+
+    fn get_canvas(&self) -> Option<HtmlCanvasElement> {
+        self.webgl_context
+            .canvas()?
+            .dyn_into::<HtmlCanvasElement>()
+            .ok()
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for WebglRenderer<L::T>
+where
+    L::NS: WebglNodeStyle,
+    L::LS: WebglLayerStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {
+        self.apply_transform(&transform);
+        self.transform = Some(transform);
     }
     fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.animation_end = layout.get_animation_end();
+        self.dirty = true;
         self.node_renderer.set_nodes(
             &self.webgl_context,
             &layout
                 .groups
                 .iter()
-                .map(|(id, group)| {
-                    let style = &group.style;
-                    // console::log!("pos: {}, {}", group.position, group.size * 0.5);
-                    Node {
-                        ID: *id,
-                        center_position: &group.position
-                            + &Transition {
-                                new: Point {
-                                    y: 0.5 * group.size.new.y,
-                                    x: 0.,
-                                },
-                                old: Point {
-                                    y: 0.5 * group.size.old.y,
-                                    x: 0.,
-                                },
-                                ..group.size
-                            },
-                        size: group.size,
-                        label: style.new.get_label().clone(),
-                        exists: group.exists,
-                        color: Transition {
-                            old_time: style.old_time,
-                            duration: style.duration,
-                            old: style.old.get_color(),
-                            new: style.new.get_color(),
-                        },
-                        outline_color: Transition {
-                            old_time: style.old_time,
-                            duration: style.duration,
-                            old: style.old.get_outline_color(),
-                            new: style.new.get_outline_color(),
-                        },
-                    }
-                })
-                .collect(),
-        );
-        let edge_type_ids = self.edge_type_ids.clone();
-        self.edge_renderer.set_edges(
-            &self.webgl_context,
-            &layout
-                .groups
-                .iter()
-                .flat_map(|(&id, group)| {
-                    let start = group.position;
-                    let edge_type_ids = &edge_type_ids;
-                    group.edges.iter().filter_map(move |(edge_data, edge)| {
-                        Some(Edge {
-                            start: &start + &edge.start_offset,
-                            start_node: id,
-                            points: edge.points.iter().map(|point| point.point).collect(),
-                            end: &layout.groups.get(&edge_data.to)?.position + &edge.end_offset,
-                            end_node: edge_data.to,
-                            edge_type: *edge_type_ids.get(&edge_data.edge_type)?,
-                            shift: edge.curve_offset,
-                            exists: edge.exists,
-                        })
-                    })
-                })
-                .collect(),
-        );
-        self.layer_renderer.set_layers(
-            &self.webgl_context,
-            &layout
-                .layers
-                .iter()
-                .map(|layer| Layer {
-                    top: layer.top,
-                    bottom: layer.bottom,
-                    label: layer.style.new.get_label(),
-                    index: layer.index,
-                    exists: layer.exists,
-                })
+                .map(|(&id, group)| create_node(id, group))
                 .collect(),
         );
+        let edge_type_ids = &self.edge_type_ids;
+        let edges = layout
+            .groups
+            .keys()
+            .flat_map(|&id| create_edges(layout, edge_type_ids, id))
+            .collect();
+        self.edge_renderer.set_edges(&self.webgl_context, &edges);
+        self.set_layers(layout);
+    }
+
+    fn update_groups(
+        &mut self,
+        layout: &DiagramLayout<L::T, L::NS, L::LS>,
+        groups: &HashSet<NodeGroupID>,
+    ) -> bool {
+        let nodes = groups
+            .iter()
+            .filter_map(|&id| Some(create_node(id, layout.groups.get(&id)?)))
+            .collect_vec();
+        let edge_type_ids = &self.edge_type_ids;
+        let edges = layout
+            .groups
+            .iter()
+            .filter(|&(id, group)| {
+                groups.contains(id) || group.edges.keys().any(|edge| groups.contains(&edge.to))
+            })
+            .flat_map(|(&id, _)| create_edges(layout, edge_type_ids, id))
+            .filter(|edge| groups.contains(&edge.start_node) || groups.contains(&edge.end_node))
+            .collect_vec();
+        if !self.node_renderer.update_nodes(&self.webgl_context, &nodes)
+            || !self.edge_renderer.update_edges(&self.webgl_context, &edges)
+        {
+            return false;
+        }
+        // Moved groups may extend the layers
+        self.set_layers(layout);
+        self.animation_end = self.animation_end.max(layout.get_animation_end());
+        self.dirty = true;
+        true
     }
 
     fn select_groups(&mut self, selection: GroupSelection, old_selection: GroupSelection) {
@@ -250,19 +473,133 @@ where
             .update_selection(&self.webgl_context, &selection, &old_selection);
         self.edge_renderer
             .update_selection(&self.webgl_context, &selection, &old_selection);
+        self.dirty = true;
+    }
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.edge_renderer
+            .set_styles(&self.webgl_context, styles, selected);
+        self.dirty = true;
+    }
+    fn set_annotations(&mut self, annotations: &Vec<AnnotationShape>) {
+        self.annotation_renderer
+            .set_annotations(&self.webgl_context, annotations);
+        let annotation_end = annotations
+            .iter()
+            .map(|shape| shape.position.get_end_time().max(shape.exists.get_end_time()))
+            .max()
+            .unwrap_or(0);
+        self.animation_end = self.animation_end.max(annotation_end);
+        self.dirty = true;
     }
     fn render(&mut self, time: u32) {
+        // Drawing the same state again yields the same image, which is skipped to save power while idle
+        let settled = self
+            .rendered_time
+            .is_some_and(|rendered| rendered >= self.animation_end && time >= self.animation_end);
+        if !self.dirty && settled {
+            return;
+        }
+        self.dirty = false;
+        self.rendered_time = Some(time);
+
+        // The context may be shared with another application that changed its state in the meantime
+        setup_context(&self.webgl_context);
         self.screen_texture.clear(&self.webgl_context);
         self.layer_renderer.render(&self.webgl_context, time);
         self.edge_renderer.render(&self.webgl_context, time);
         self.node_renderer.render(&self.webgl_context, time);
+        self.annotation_renderer.render(&self.webgl_context, time);
+        self.legend_renderer.render(&self.webgl_context, time);
     }
 }
 
-pub struct LayerRenderingColorConfig {
-    pub background1: TransparentColor,
-    pub background2: TransparentColor,
-    pub text: Color,
+pub(super) fn create_node<T: DrawTag, S: WebglNodeStyle>(
+    id: NodeGroupID,
+    group: &NodeGroupLayout<T, S>,
+) -> Node {
+    let style = &group.style;
+    Node {
+        ID: id,
+        center_position: &group.position
+            + &Transition {
+                new: Point {
+                    y: 0.5 * group.size.new.y,
+                    x: 0.,
+                },
+                old: Point {
+                    y: 0.5 * group.size.old.y,
+                    x: 0.,
+                },
+                ..group.size
+            },
+        size: group.size,
+        label: style.new.get_label().clone(),
+        label_offset: group.label_offset,
+        label_exists: group.label_exists,
+        exists: group.exists,
+        color: Transition {
+            old_time: style.old_time,
+            duration: style.duration,
+            old: style.old.get_color(),
+            new: style.new.get_color(),
+        },
+        outline_color: Transition {
+            old_time: style.old_time,
+            duration: style.duration,
+            old: style.old.get_outline_color(),
+            new: style.new.get_outline_color(),
+        },
+    }
+}
+
+/// Creates the outgoing edges of the given group, skipping edges to groups that are not laid out
+pub(super) fn create_edges<'a, T: DrawTag, S: NodeStyle, LS: LayerStyle>(
+    layout: &'a DiagramLayout<T, S, LS>,
+    edge_type_ids: &'a HashMap<EdgeType<T>, usize>,
+    id: NodeGroupID,
+) -> impl Iterator<Item = Edge> + 'a {
+    let group = layout.groups.get(&id);
+    group.into_iter().flat_map(move |group| {
+        let start = group.position;
+        group.edges.iter().filter_map(move |(edge_data, edge)| {
+            Some(Edge {
+                start: &start + &edge.start_offset,
+                start_node: id,
+                points: edge.points.iter().map(|point| point.point).collect(),
+                end: &layout.groups.get(&edge_data.to)?.position + &edge.end_offset,
+                end_node: edge_data.to,
+                edge_type: *edge_type_ids.get(&edge_data.edge_type)?,
+                type_index: edge_data.edge_type.index,
+                shift: edge.curve_offset,
+                exists: edge.exists,
+            })
+        })
+    })
+}
+
+/// Obtains the selection state of an element connected to the given groups, from 0 for none up to 4 for selected
+pub(super) fn get_selection_state(
+    selection: &[HashSet<NodeGroupID>; 4],
+    groups: &[NodeGroupID],
+) -> f32 {
+    selection
+        .iter()
+        .zip([4., 3., 2., 1.])
+        .find(|(selected, _)| groups.iter().any(|group| selected.contains(group)))
+        .map_or(0., |(_, state)| state)
+}
+
+fn setup_context(context: &WebGl2RenderingContext) {
+    // context.enable(WebGl2RenderingContext::DEPTH_TEST);
+    context.enable(WebGl2RenderingContext::BLEND);
+    context.blend_func(
+        WebGl2RenderingContext::ONE,
+        WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
+    );
 }
 
 impl<T: DrawTag> Drop for WebglRenderer<T> {
@@ -270,14 +607,7 @@ impl<T: DrawTag> Drop for WebglRenderer<T> {
         self.node_renderer.dispose(&self.webgl_context);
         self.edge_renderer.dispose(&self.webgl_context);
         self.layer_renderer.dispose(&self.webgl_context);
+        self.annotation_renderer.dispose(&self.webgl_context);
+        self.legend_renderer.dispose(&self.webgl_context);
     }
 }
-
-pub trait WebglNodeStyle: NodeStyle {
-    fn get_color(&self) -> Color;
-    fn get_outline_color(&self) -> TransparentColor;
-    fn get_label(&self) -> Option<String>;
-}
-pub trait WebglLayerStyle: LayerStyle {
-    fn get_label(&self) -> String;
-}
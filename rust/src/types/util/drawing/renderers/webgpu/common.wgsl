@@ -0,0 +1,41 @@
+// Definitions shared by all webgpu shaders, which are prepended to each of them
+
+struct Globals {
+    transform: mat4x4f,
+    // The time, the size of a pixel in world units, and the size of the rounded corners of nodes
+    params: vec4f,
+    // The number of nodes and the number of edge segments
+    counts: vec4u,
+    // The colors mixed into selected, partially selected, hovered and partially hovered nodes
+    select_colors: array<vec4f, 4>,
+}
+
+struct EdgeType {
+    // The colors for every selection state, from not selected up to selected
+    colors: array<vec4f, 5>,
+    // The width, and the lengths of the solid and transparent parts of dashes
+    params: vec4f,
+}
+
+struct EdgeVertex {
+    // The position, the distance along the segment, and the signed distance from the center of the line
+    position: vec4f,
+    // The type, the selection state, the presence and the width
+    style: vec4f,
+    // The override color, where an alpha of 0 indicates the type's color is used
+    color: vec4f,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+
+fn get_per(start: f32, duration: f32) -> f32 {
+    if (duration <= 0.0) {
+        return select(0.0, 1.0, globals.params.x >= start);
+    }
+    return clamp((globals.params.x - start) / duration, 0.0, 1.0);
+}
+
+fn to_screen(point: vec2f) -> vec4f {
+    // 2 to make the default width and height of the screen 1, instead of 2
+    return globals.transform * vec4f(point, 0.0, 1.0) * vec4f(2.0, 2.0, 2.0, 1.0);
+}
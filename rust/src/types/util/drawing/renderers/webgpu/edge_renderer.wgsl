@@ -0,0 +1,46 @@
+struct EdgeOutput {
+    @builtin(position) position: vec4f,
+    // The distance along the segment, and the signed distance from the center of the line
+    @location(0) line: vec2f,
+    @location(1) @interpolate(flat) style: vec4f,
+    @location(2) @interpolate(flat) color: vec4f,
+}
+
+@group(0) @binding(1) var<storage, read> edge_types: array<EdgeType>;
+@group(0) @binding(2) var<storage, read> vertices: array<EdgeVertex>;
+
+@vertex
+fn vertex_main(@builtin(vertex_index) index: u32) -> EdgeOutput {
+    let vertex = vertices[index];
+    var output: EdgeOutput;
+    output.position = to_screen(vertex.position.xy);
+    output.line = vertex.position.zw;
+    output.style = vertex.style;
+    output.color = vertex.color;
+    return output;
+}
+
+@fragment
+fn fragment_main(input: EdgeOutput) -> @location(0) vec4f {
+    let type_index = u32(input.style.x);
+    let half_width = 0.5 * input.style.w;
+    if (half_width <= 0.0) {
+        discard;
+    }
+
+    // Blend the border of the line over the width of a pixel
+    var alpha = clamp((half_width - abs(input.line.y)) / globals.params.y + 0.5, 0.0, 1.0);
+    let dash = edge_types[type_index].params;
+    let period = dash.y + dash.z;
+    if (period > 0.0 && input.line.x % period > dash.y) {
+        alpha = 0.0;
+    }
+
+    let state = u32(input.style.y);
+    var color = edge_types[type_index].colors[state].rgb;
+    if (state == 0u && input.color.a > 0.0) {
+        color = input.color.rgb;
+    }
+    let a = input.style.z * alpha;
+    return vec4f(color * a, a);
+}
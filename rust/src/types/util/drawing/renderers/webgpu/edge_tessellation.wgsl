@@ -0,0 +1,87 @@
+struct Segment {
+    // The old and new start point
+    start: vec4f,
+    // The old and new end point
+    end: vec4f,
+    // The start times and durations of the start and end transitions
+    timing: vec4f,
+    // The old and new presence, and the start time and duration of its transition
+    exists: vec4f,
+    // The old and new curve offset, and the start time and duration of its transition
+    curve_offset: vec4f,
+    // The type, the selection state, the width override, and whether the edge itself is selected
+    style: vec4f,
+    // The override color, where an alpha of 0 indicates the type's color is used
+    style_color: vec4f,
+}
+
+// The number of straight pieces that every segment is approximated by, which has to match `SEGMENT_PIECES`
+const PIECES: u32 = 8u;
+
+@group(0) @binding(1) var<storage, read> segments: array<Segment>;
+@group(0) @binding(2) var<storage, read> edge_types: array<EdgeType>;
+@group(0) @binding(3) var<storage, read_write> vertices: array<EdgeVertex>;
+
+// The curve is approximated by a parabola through both ends, which bulges out sideways by the given amount in the middle
+fn get_curve_point(start: vec2f, delta: vec2f, normal: vec2f, bulge: f32, per: f32) -> vec2f {
+    return start + per * delta + 4.0 * per * (1.0 - per) * bulge * normal;
+}
+
+fn get_curve_normal(delta: vec2f, normal: vec2f, bulge: f32, per: f32) -> vec2f {
+    let tangent = delta + 4.0 * (1.0 - 2.0 * per) * bulge * normal;
+    let tangent_length = length(tangent);
+    if (tangent_length <= 0.0) {
+        return normal;
+    }
+    return vec2f(-tangent.y, tangent.x) / tangent_length;
+}
+
+@compute @workgroup_size(64)
+fn tessellate(@builtin(global_invocation_id) id: vec3u) {
+    let index = id.x;
+    if (index >= globals.counts.y) {
+        return;
+    }
+    let segment = segments[index];
+    let start = mix(segment.start.xy, segment.start.zw, get_per(segment.timing.x, segment.timing.y));
+    let end = mix(segment.end.xy, segment.end.zw, get_per(segment.timing.z, segment.timing.w));
+    let exists = mix(segment.exists.x, segment.exists.y, get_per(segment.exists.z, segment.exists.w));
+    let curve_per = get_per(segment.curve_offset.z, segment.curve_offset.w);
+    let curve_offset = mix(segment.curve_offset.x, segment.curve_offset.y, curve_per);
+
+    var width = edge_types[u32(segment.style.x)].params.x;
+    // Hidden edge types remain hidden, regardless of the width override
+    if (width > 0.0 && segment.style.z > 0.0) {
+        width = segment.style.z;
+    }
+    // Leave space for anti-aliasing
+    let half_width = select(0.0, 0.5 * width + globals.params.y, width > 0.0);
+    let state = max(segment.style.y, 4.0 * segment.style.w);
+    let style = vec4f(segment.style.x, state, exists, width);
+
+    let delta = end - start;
+    let chord = length(delta);
+    let dir = select(vec2f(1.0, 0.0), delta / chord, chord > 0.0);
+    let normal = vec2f(-dir.y, dir.x);
+    let bulge = sign(curve_offset) * min(abs(curve_offset), 0.5 * chord);
+
+    var order = array<u32, 6>(0u, 1u, 2u, 2u, 1u, 3u);
+    for (var piece = 0u; piece < PIECES; piece += 1u) {
+        let per0 = f32(piece) / f32(PIECES);
+        let per1 = f32(piece + 1u) / f32(PIECES);
+        let point0 = get_curve_point(start, delta, normal, bulge, per0);
+        let point1 = get_curve_point(start, delta, normal, bulge, per1);
+        let side0 = half_width * get_curve_normal(delta, normal, bulge, per0);
+        let side1 = half_width * get_curve_normal(delta, normal, bulge, per1);
+        var corners = array<vec4f, 4>(
+            vec4f(point0 + side0, chord * per0, half_width),
+            vec4f(point0 - side0, chord * per0, -half_width),
+            vec4f(point1 + side1, chord * per1, half_width),
+            vec4f(point1 - side1, chord * per1, -half_width),
+        );
+        for (var i = 0u; i < 6u; i += 1u) {
+            vertices[(index * PIECES + piece) * 6u + i] =
+                EdgeVertex(corners[order[i]], style, segment.style_color);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+struct Node {
+    // The old and new center position
+    position: vec4f,
+    // The old and new size
+    size: vec4f,
+    color_old: vec4f,
+    color: vec4f,
+    // The start times and durations of the position and size transitions
+    timing: vec4f,
+    // The start times and durations of the color and presence transitions
+    timing2: vec4f,
+    // The old and new presence, and the selection state
+    exists: vec4f,
+}
+
+struct NodeOutput {
+    @builtin(position) position: vec4f,
+    @location(0) corner: vec2f,
+    @location(1) size: vec2f,
+    @location(2) color: vec3f,
+    @location(3) exists: f32,
+}
+
+@group(0) @binding(1) var<storage, read> nodes: array<Node>;
+
+@vertex
+fn vertex_main(
+    @builtin(vertex_index) vertex: u32,
+    @builtin(instance_index) instance: u32,
+) -> NodeOutput {
+    let node = nodes[instance];
+    let position = mix(node.position.xy, node.position.zw, get_per(node.timing.x, node.timing.y));
+    let size = mix(node.size.xy, node.size.zw, get_per(node.timing.z, node.timing.w));
+    let color_per = get_per(node.timing2.x, node.timing2.y);
+    var color = sqrt(mix(node.color_old.rgb * node.color_old.rgb, node.color.rgb * node.color.rgb, color_per));
+    let state = u32(node.exists.z);
+    if (state > 0u) {
+        let selection = globals.select_colors[4u - state];
+        color = sqrt(mix(color * color, selection.rgb * selection.rgb, selection.a));
+    }
+
+    // Two triangles
+    var corners = array<vec2f, 6>(
+        vec2f(0.5, 0.5),
+        vec2f(0.5, -0.5),
+        vec2f(-0.5, -0.5),
+        vec2f(0.5, 0.5),
+        vec2f(-0.5, -0.5),
+        vec2f(-0.5, 0.5),
+    );
+    let corner = size * corners[vertex];
+
+    var output: NodeOutput;
+    output.position = to_screen(position + corner);
+    output.corner = corner;
+    output.size = size;
+    output.color = color;
+    output.exists = mix(node.exists.x, node.exists.y, get_per(node.timing2.z, node.timing2.w));
+    return output;
+}
+
+@fragment
+fn fragment_main(input: NodeOutput) -> @location(0) vec4f {
+    let corner_size = globals.params.z;
+    let delta = abs(input.corner) - (0.5 * input.size - vec2f(corner_size));
+    var alpha = 1.0;
+    if (delta.x > 0.0 && delta.y > 0.0 && dot(delta, delta) >= corner_size * corner_size) {
+        alpha = 0.0;
+    }
+
+    let a = max(0.0, input.exists * alpha);
+    return vec4f(input.color * a, a);
+}
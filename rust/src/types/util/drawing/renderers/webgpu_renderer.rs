@@ -0,0 +1,634 @@
+// An alternative to the webgl renderer that draws through WebGPU, where the edges are tessellated by a compute shader every frame, such that curves remain smooth during transitions without tessellating them on the CPU. It draws the nodes and edges of the layout, but no labels, layers or annotations yet.
+
+use std::collections::{HashMap, HashSet};
+
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlCanvasElement;
+
+use crate::{
+    types::util::{
+        drawing::{
+            annotations::AnnotationShape,
+            diagram_layout::DiagramLayout,
+            layout_rules::LayoutRules,
+            renderer::{DiagramRenderer, EdgeStyle, GroupEdgeID, GroupSelection},
+        },
+        graph_structure::graph_structure::{DrawTag, EdgeType},
+    },
+    util::{
+        color::{Color, TransparentColor},
+        logging::console,
+        point::Point,
+        transformation::Transformation,
+        transition::Transition,
+    },
+    wasm_interface::NodeGroupID,
+};
+
+use super::{
+    webgl::{
+        edge_renderer::{Edge, EdgeRenderingType},
+        node_renderer::{Node, NodeRenderingColorConfig},
+    },
+    webgl_renderer::{create_edges, create_node, WebglNodeStyle},
+};
+
+/// The number of floats describing a node, see `Node` in `node_renderer.wgsl`
+const NODE_FLOATS: usize = 28;
+/// The number of floats describing a segment of an edge, see `Segment` in `edge_tessellation.wgsl`
+const SEGMENT_FLOATS: usize = 28;
+/// The number of floats describing an edge type, see `EdgeType` in `common.wgsl`
+const EDGE_TYPE_FLOATS: usize = 24;
+/// The number of floats describing a vertex of an edge, see `EdgeVertex` in `common.wgsl`
+const EDGE_VERTEX_FLOATS: usize = 12;
+/// The number of straight pieces that every segment is approximated by, which has to match `PIECES` in `edge_tessellation.wgsl`
+const SEGMENT_PIECES: usize = 8;
+/// The number of segments tessellated by a single workgroup of the compute shader
+const WORKGROUP_SIZE: usize = 64;
+/// The size of the global uniforms in bytes, see `Globals` in `common.wgsl`
+const GLOBALS_SIZE: u64 = 160;
+/// The size of the rounded corners of nodes in world units
+const CORNER_SIZE: f32 = 0.3;
+
+/// The device and canvas surface that a webgpu renderer draws with. Obtaining the device is asynchronous in the browser, hence it is requested before the renderer is created, see `request_webgpu_context`.
+#[wasm_bindgen]
+pub struct WebgpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+}
+
+impl WebgpuContext {
+    /// Requests a device that can draw to the given canvas, which fails if the browser does not support webgpu
+    pub async fn request(canvas: HtmlCanvasElement) -> Result<WebgpuContext, JsValue> {
+        let (width, height) = (canvas.width().max(1), canvas.height().max(1));
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::BROWSER_WEBGPU,
+            ..Default::default()
+        });
+        let surface = instance
+            .create_surface(wgpu::SurfaceTarget::Canvas(canvas))
+            .map_err(|error| JsValue::from(error.to_string()))?;
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                compatible_surface: Some(&surface),
+                ..Default::default()
+            })
+            .await
+            .ok_or("No webgpu adapter is available")?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await
+            .map_err(|error| JsValue::from(error.to_string()))?;
+        let surface_config = surface
+            .get_default_config(&adapter, width, height)
+            .ok_or("The canvas can not be drawn to with webgpu")?;
+        surface.configure(&device, &surface_config);
+        Ok(WebgpuContext {
+            device,
+            queue,
+            surface,
+            surface_config,
+        })
+    }
+}
+
+/// A renderer that uses webgpu to draw nodes and edges
+pub struct WebgpuRenderer<T: DrawTag> {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface: wgpu::Surface<'static>,
+    surface_config: wgpu::SurfaceConfiguration,
+    node_pipeline: wgpu::RenderPipeline,
+    tessellation_pipeline: wgpu::ComputePipeline,
+    edge_pipeline: wgpu::RenderPipeline,
+    globals: wgpu::Buffer,
+    node_buffer: StorageArray,
+    segment_buffer: StorageArray,
+    edge_type_buffer: StorageArray,
+    edge_vertex_buffer: StorageArray,
+    /// The bind groups of the node, tessellation and edge pipelines, which are recreated when a buffer is replaced
+    bind_groups: Option<[wgpu::BindGroup; 3]>,
+    clear_color: wgpu::Color,
+    node_colors: NodeRenderingColorConfig,
+    edge_type_ids: HashMap<EdgeType<T>, usize>,
+    edge_types: Vec<EdgeRenderingType>,
+    hidden_edge_types: HashSet<usize>,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    styles: HashMap<GroupEdgeID, EdgeStyle>,
+    selected_edges: HashSet<GroupEdgeID>,
+    /// The selected, partially selected, hovered and partially hovered groups
+    selection: [HashSet<NodeGroupID>; 4],
+    transform: Transformation,
+    segment_count: usize,
+}
+
+impl<T: DrawTag> WebgpuRenderer<T> {
+    /// Creates a renderer that draws with the given context, clearing the canvas to the given background color
+    pub fn new(
+        context: WebgpuContext,
+        edge_types: HashMap<EdgeType<T>, EdgeRenderingType>,
+        node_colors: NodeRenderingColorConfig,
+        background: TransparentColor,
+    ) -> WebgpuRenderer<T> {
+        let WebgpuContext {
+            device,
+            queue,
+            surface,
+            surface_config,
+        } = context;
+        let (edge_type_ids, edge_types): (HashMap<EdgeType<T>, usize>, Vec<EdgeRenderingType>) =
+            edge_types
+                .into_iter()
+                .enumerate()
+                .map(|(index, (edge_type, edge_rendering))| {
+                    ((edge_type, index), edge_rendering)
+                })
+                .unzip();
+
+        let format = surface_config.format;
+        let node_shader =
+            create_shader(&device, "nodes", include_str!("webgpu/node_renderer.wgsl"));
+        let edge_shader =
+            create_shader(&device, "edges", include_str!("webgpu/edge_renderer.wgsl"));
+        let tessellation_shader = create_shader(
+            &device,
+            "edge tessellation",
+            include_str!("webgpu/edge_tessellation.wgsl"),
+        );
+        let tessellation_pipeline =
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("edge tessellation"),
+                layout: None,
+                module: &tessellation_shader,
+                entry_point: "tessellate",
+                compilation_options: Default::default(),
+            });
+
+        let storage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST;
+        let mut renderer = WebgpuRenderer {
+            node_pipeline: create_render_pipeline(&device, "nodes", &node_shader, format),
+            edge_pipeline: create_render_pipeline(&device, "edges", &edge_shader, format),
+            tessellation_pipeline,
+            globals: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("globals"),
+                size: GLOBALS_SIZE,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            node_buffer: StorageArray::new(&device, "nodes", storage),
+            segment_buffer: StorageArray::new(&device, "segments", storage),
+            edge_type_buffer: StorageArray::new(&device, "edge types", storage),
+            edge_vertex_buffer: StorageArray::new(&device, "edge vertices", storage),
+            bind_groups: None,
+            clear_color: wgpu::Color {
+                r: background.0 as f64,
+                g: background.1 as f64,
+                b: background.2 as f64,
+                a: background.3 as f64,
+            },
+            node_colors,
+            edge_type_ids,
+            edge_types,
+            hidden_edge_types: HashSet::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            styles: HashMap::new(),
+            selected_edges: HashSet::new(),
+            selection: Default::default(),
+            transform: Transformation::default(),
+            segment_count: 0,
+            device,
+            queue,
+            surface,
+            surface_config,
+        };
+        renderer.send_edge_types();
+        renderer
+    }
+
+    /// Hides or shows the geometry of all edges of the given type, without requiring a new layout
+    pub fn set_edge_type_visibility(&mut self, edge_type: EdgeType<T>, visible: bool) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            if visible {
+                self.hidden_edge_types.remove(&id);
+            } else {
+                self.hidden_edge_types.insert(id);
+            }
+            self.send_edge_types();
+        }
+    }
+
+    /// Sets the stroke width of all edges of the given type, in world units
+    pub fn set_edge_type_width(&mut self, edge_type: EdgeType<T>, width: f32) {
+        if let Some(&id) = self.edge_type_ids.get(&edge_type) {
+            self.edge_types[id].width = width;
+            self.send_edge_types();
+        }
+    }
+
+    /// Resizes the surface that is drawn to, which the transform also does when the size of the screen changes
+    pub fn set_canvas_size(&mut self, width: u32, height: u32) {
+        let size = (self.surface_config.width, self.surface_config.height);
+        if width > 0 && height > 0 && size != (width, height) {
+            self.surface_config.width = width;
+            self.surface_config.height = height;
+            self.surface.configure(&self.device, &self.surface_config);
+        }
+    }
+
+    fn send_edge_types(&mut self) {
+        let data = self
+            .edge_types
+            .iter()
+            .enumerate()
+            .flat_map(|(index, edge_type)| {
+                let width = if self.hidden_edge_types.contains(&index) {
+                    0.
+                } else {
+                    edge_type.width
+                };
+                let colors = [
+                    &edge_type.color,
+                    &edge_type.partial_hover_color,
+                    &edge_type.hover_color,
+                    &edge_type.partial_select_color,
+                    &edge_type.select_color,
+                ];
+                let mut out = Vec::with_capacity(EDGE_TYPE_FLOATS);
+                for &Color(r, g, b) in colors {
+                    out.extend([r, g, b, 1.]);
+                }
+                out.extend([width, edge_type.dash_solid, edge_type.dash_transparent, 0.]);
+                out
+            })
+            .collect::<Vec<_>>();
+        if self.edge_type_buffer.write(&self.device, &self.queue, &data) {
+            self.bind_groups = None;
+        }
+    }
+
+    fn send_nodes(&mut self) {
+        let data = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                let state = get_selection_state(&self.selection, &[node.ID]);
+                pack_node(node, state)
+            })
+            .collect::<Vec<_>>();
+        if self.node_buffer.write(&self.device, &self.queue, &data) {
+            self.bind_groups = None;
+        }
+    }
+
+    fn send_segments(&mut self) {
+        let data = self
+            .edges
+            .iter()
+            .flat_map(|edge| {
+                let id = (edge.start_node, edge.end_node, edge.type_index);
+                let state = get_selection_state(&self.selection, &[edge.start_node, edge.end_node]);
+                let style = self.styles.get(&id);
+                let selected = self.selected_edges.contains(&id);
+                let points = std::iter::once(edge.start)
+                    .chain(edge.points.iter().cloned())
+                    .chain(std::iter::once(edge.end))
+                    .collect::<Vec<_>>();
+                points
+                    .windows(2)
+                    .flat_map(|pair| pack_segment(pair[0], pair[1], edge, state, style, selected))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        self.segment_count = data.len() / SEGMENT_FLOATS;
+        let replaced = self.segment_buffer.write(&self.device, &self.queue, &data);
+
+        // The vertices are only written by the tessellation shader
+        let vertex_count = self.segment_count * SEGMENT_PIECES * 6;
+        let size = (4 * EDGE_VERTEX_FLOATS * vertex_count) as u64;
+        if self.edge_vertex_buffer.reserve(&self.device, size) || replaced {
+            self.bind_groups = None;
+        }
+    }
+
+    fn send_globals(&mut self, time: u32) {
+        let matrix = self.transform.get_matrix();
+        let mut data = Vec::with_capacity(GLOBALS_SIZE as usize);
+        // The matrix is stored by row, while webgpu expects it by column
+        for column in 0..4 {
+            for row in 0..4 {
+                data.extend(matrix.0[4 * row + column].to_le_bytes());
+            }
+        }
+        let params = [time as f32, 1.0 / self.transform.scale, CORNER_SIZE, 0.];
+        for value in params {
+            data.extend(value.to_le_bytes());
+        }
+        for count in [self.nodes.len() as u32, self.segment_count as u32, 0, 0] {
+            data.extend(count.to_le_bytes());
+        }
+        let colors = [
+            &self.node_colors.select,
+            &self.node_colors.partial_select,
+            &self.node_colors.hover,
+            &self.node_colors.partial_hover,
+        ];
+        for &TransparentColor(r, g, b, a) in colors {
+            for value in [r, g, b, a] {
+                data.extend(value.to_le_bytes());
+            }
+        }
+        self.queue.write_buffer(&self.globals, 0, &data);
+    }
+
+    /// Creates the bind groups if the buffers changed since they were last created
+    fn update_bind_groups(&mut self) {
+        if self.bind_groups.is_some() {
+            return;
+        }
+        let device = &self.device;
+        let globals = &self.globals;
+        let create = |layout: wgpu::BindGroupLayout, buffers: &[&wgpu::Buffer]| {
+            let entries = std::iter::once(globals)
+                .chain(buffers.iter().cloned())
+                .enumerate()
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: binding as u32,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect::<Vec<_>>();
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &entries,
+            })
+        };
+        let (nodes, segments) = (&self.node_buffer.buffer, &self.segment_buffer.buffer);
+        let edge_types = &self.edge_type_buffer.buffer;
+        let vertices = &self.edge_vertex_buffer.buffer;
+        let bind_groups = [
+            create(self.node_pipeline.get_bind_group_layout(0), &[nodes]),
+            create(
+                self.tessellation_pipeline.get_bind_group_layout(0),
+                &[segments, edge_types, vertices],
+            ),
+            create(self.edge_pipeline.get_bind_group_layout(0), &[edge_types, vertices]),
+        ];
+        self.bind_groups = Some(bind_groups);
+    }
+}
+
+impl<L: LayoutRules> DiagramRenderer<L> for WebgpuRenderer<L::T>
+where
+    L::NS: WebglNodeStyle,
+{
+    fn set_transform(&mut self, transform: Transformation) {
+        self.set_canvas_size(transform.width as u32, transform.height as u32);
+        self.transform = transform;
+    }
+
+    fn update_layout(&mut self, layout: &DiagramLayout<L::T, L::NS, L::LS>) {
+        self.nodes = layout
+            .groups
+            .iter()
+            .map(|(&id, group)| create_node(id, group))
+            .collect();
+        let edge_type_ids = &self.edge_type_ids;
+        self.edges = layout
+            .groups
+            .keys()
+            .flat_map(|&id| create_edges(layout, edge_type_ids, id))
+            .collect();
+        self.send_nodes();
+        self.send_segments();
+    }
+
+    fn select_groups(&mut self, selection: GroupSelection, _old_selection: GroupSelection) {
+        let (select, partial_select, hover, partial_hover) = selection;
+        self.selection = [select, partial_select, hover, partial_hover]
+            .map(|groups| groups.iter().cloned().collect());
+        self.send_nodes();
+        self.send_segments();
+    }
+
+    fn set_edge_styles(
+        &mut self,
+        styles: &HashMap<GroupEdgeID, EdgeStyle>,
+        selected: &HashSet<GroupEdgeID>,
+    ) {
+        self.styles = styles.clone();
+        self.selected_edges = selected.clone();
+        self.send_segments();
+    }
+
+    fn set_annotations(&mut self, _annotations: &Vec<AnnotationShape>) {
+        // Annotations are not supported by this renderer yet
+    }
+
+    fn render(&mut self, time: u32) {
+        self.send_globals(time);
+        self.update_bind_groups();
+        let Some([node_bind_group, tessellation_bind_group, edge_bind_group]) = &self.bind_groups
+        else {
+            return;
+        };
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(error) => {
+                console::log_warn!("The webgpu surface could not be drawn to: {}", error);
+                return;
+            }
+        };
+        let view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let (node_count, segment_count) = (self.nodes.len() as u32, self.segment_count as u32);
+        let clear_color = self.clear_color;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        if segment_count > 0 {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.tessellation_pipeline);
+            pass.set_bind_group(0, tessellation_bind_group, &[]);
+            let workgroups = (segment_count as usize + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
+            pass.dispatch_workgroups(workgroups as u32, 1, 1);
+        }
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.edge_pipeline);
+            pass.set_bind_group(0, edge_bind_group, &[]);
+            pass.draw(0..segment_count * SEGMENT_PIECES as u32 * 6, 0..1);
+            pass.set_pipeline(&self.node_pipeline);
+            pass.set_bind_group(0, node_bind_group, &[]);
+            pass.draw(0..6, 0..node_count);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+        frame.present();
+    }
+}
+
+/// A storage buffer that grows to fit the data that is written to it
+struct StorageArray {
+    buffer: wgpu::Buffer,
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+}
+
+impl StorageArray {
+    fn new(device: &wgpu::Device, label: &'static str, usage: wgpu::BufferUsages) -> StorageArray {
+        StorageArray {
+            buffer: create_buffer(device, label, 0, usage),
+            label,
+            usage,
+        }
+    }
+
+    /// Ensures the buffer holds at least the given number of bytes, returning whether it had to be replaced
+    fn reserve(&mut self, device: &wgpu::Device, size: u64) -> bool {
+        if size <= self.buffer.size() {
+            return false;
+        }
+        self.buffer.destroy();
+        self.buffer = create_buffer(device, self.label, size.next_power_of_two(), self.usage);
+        true
+    }
+
+    /// Writes the given data to the start of the buffer, returning whether it had to be replaced to fit the data
+    fn write(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[f32]) -> bool {
+        let replaced = self.reserve(device, 4 * data.len() as u64);
+        if !data.is_empty() {
+            let bytes = data
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect::<Vec<u8>>();
+            queue.write_buffer(&self.buffer, 0, &bytes);
+        }
+        replaced
+    }
+}
+
+fn create_buffer(
+    device: &wgpu::Device,
+    label: &str,
+    size: u64,
+    usage: wgpu::BufferUsages,
+) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        // Empty bindings are not allowed
+        size: size.max(256),
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_shader(device: &wgpu::Device, label: &str, source: &str) -> wgpu::ShaderModule {
+    let common = include_str!("webgpu/common.wgsl");
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(format!("{}\n{}", common, source).into()),
+    })
+}
+
+fn create_render_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vertex_main",
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fragment_main",
+            compilation_options: Default::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                // The shaders output premultiplied colors, like the webgl shaders
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// Obtains the selection state of an element connected to the given groups, from 0 for none up to 4 for selected
+fn get_selection_state(selection: &[HashSet<NodeGroupID>; 4], groups: &[NodeGroupID]) -> f32 {
+    selection
+        .iter()
+        .zip([4., 3., 2., 1.])
+        .find(|(selected, _)| groups.iter().any(|group| selected.contains(group)))
+        .map_or(0., |(_, state)| state)
+}
+
+fn pack_node(node: &Node, state: f32) -> [f32; NODE_FLOATS] {
+    let (position, size) = (node.center_position, node.size);
+    let (color, exists) = (node.color, node.exists);
+    [
+        position.old.x, position.old.y, position.new.x, position.new.y, //
+        size.old.x, size.old.y, size.new.x, size.new.y, //
+        color.old.0, color.old.1, color.old.2, 1., //
+        color.new.0, color.new.1, color.new.2, 1., //
+        position.old_time as f32, position.duration as f32, //
+        size.old_time as f32, size.duration as f32, //
+        color.old_time as f32, color.duration as f32, //
+        exists.old_time as f32, exists.duration as f32, //
+        exists.old, exists.new, state, 0.,
+    ]
+}
+
+fn pack_segment(
+    start: Transition<Point>,
+    end: Transition<Point>,
+    edge: &Edge,
+    state: f32,
+    style: Option<&EdgeStyle>,
+    selected: bool,
+) -> [f32; SEGMENT_FLOATS] {
+    let (exists, shift) = (edge.exists, edge.shift);
+    let width = style.and_then(|style| style.width).unwrap_or(0.);
+    let Color(r, g, b) = style.and_then(|style| style.color).unwrap_or(Color(0., 0., 0.));
+    let alpha = if style.and_then(|style| style.color).is_some() {
+        1.
+    } else {
+        0.
+    };
+    [
+        start.old.x, start.old.y, start.new.x, start.new.y, //
+        end.old.x, end.old.y, end.new.x, end.new.y, //
+        start.old_time as f32, start.duration as f32, //
+        end.old_time as f32, end.duration as f32, //
+        exists.old, exists.new, exists.old_time as f32, exists.duration as f32, //
+        shift.old, shift.new, shift.old_time as f32, shift.duration as f32, //
+        edge.edge_type as f32, state, width, selected as u8 as f32, //
+        r, g, b, alpha,
+    ]
+}
@@ -1,5 +1,7 @@
+pub use oxidd_viz_core::types::util::*;
+
 pub mod drawing;
-pub mod graph_structure;
-pub mod group_manager;
-pub mod node_tracker_manager;
-pub mod storage;
+pub mod root_order;
+pub mod section_derivation;
+pub mod section_import;
+pub mod view_patch;
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::{
+    types::util::graph_structure::graph_structure::GraphStructure,
+    wasm_interface::{NodeID, RootSorting},
+};
+
+/// Retrieves the roots of the graph in the order given by the sorting, where roots without a name are placed last when sorting by name
+pub fn get_sorted_roots<G: GraphStructure>(
+    graph: &mut G,
+    sorting: RootSorting,
+    get_name: impl Fn(G::NL) -> Option<String>,
+) -> Vec<NodeID> {
+    let roots = graph.get_roots();
+    match sorting {
+        RootSorting::Name => roots
+            .into_iter()
+            .sorted_by_key(|&root| {
+                let name = get_name(graph.get_node_label(root));
+                (name.is_none(), name)
+            })
+            .collect(),
+        RootSorting::Size => roots
+            .into_iter()
+            .map(|root| (root, count_descendants(graph, root)))
+            .sorted_by_key(|&(_, size)| std::cmp::Reverse(size))
+            .map(|(root, _)| root)
+            .collect(),
+    }
+}
+
+/// Counts the nodes reachable from the given node, including the node itself
+fn count_descendants<G: GraphStructure>(graph: &mut G, node: NodeID) -> usize {
+    let mut seen = HashSet::new();
+    let mut queue = vec![node];
+    while let Some(node) = queue.pop() {
+        if seen.insert(node) {
+            queue.extend(graph.get_children(node).into_iter().map(|(_, child)| child));
+        }
+    }
+    seen.len()
+}
@@ -0,0 +1,346 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
+use oxidd::{Edge, Function, LevelNo};
+
+use crate::{
+    types::util::section_import::ImportManager,
+    wasm_interface::{NodeID, Quantifier},
+};
+
+/// The operations on the nodes of a manager that deriving new functions from the functions of a section relies on, which the managers of all diagram types provide, such that the drawers of all diagram types share the operations previewed on a section
+pub trait DeriveManager: ImportManager {
+    /// Retrieves the manager that the given function belongs to
+    fn of_function(function: &Self::Function) -> Self;
+    /// Creates a function of this manager that is represented by the given node
+    fn get_function(&self, node: NodeID) -> Self::Function;
+    /// Retrieves the level and children of the given node together with whether it is a terminal, or None if the node is not part of the manager
+    fn get_node(&self, node: NodeID) -> Option<(LevelNo, Vec<NodeID>, bool)>;
+    /// Adds an inner node with the given level and children to the manager under a new id
+    fn add_inner_node(&self, level: LevelNo, children: &[NodeID]) -> NodeID;
+    /// Retrieves the node that replaces an inner node with the given children by the reduction rules of the diagram type, or None if such a node is not redundant
+    fn get_reduced_child(children: &[NodeID]) -> Option<NodeID>;
+    /// Retrieves the terminal of the manager whose value combines the values of the two given terminals by the given quantifier, or None if the values can not be combined by it
+    fn quantify_terminals(&self, a: NodeID, b: NodeID, quantifier: Quantifier) -> Option<NodeID>;
+}
+
+/// Adds the nodes of functions derived from the roots of a section to the manager of the section. Every existing inner node of the manager is looked up before a node is added, such that derived functions share their nodes with the section and with earlier derivations. Added nodes follow the reduction rules of the section: sections in which every edge leads to the next level of the section and that contain redundant nodes are considered to be quasi-reduced, all other sections are considered to be reduced.
+struct Derivation<'a, M: DeriveManager> {
+    manager_ref: &'a M,
+    unique: HashMap<(LevelNo, Vec<NodeID>), NodeID>,
+    /// The levels of the section ordered from top to bottom if the section is quasi-reduced, in which case every path of a derived function visits every one of them
+    quasi_levels: Option<Vec<LevelNo>>,
+}
+
+impl<'a, M: DeriveManager> Derivation<'a, M> {
+    fn new(manager_ref: &'a M, roots: &[NodeID]) -> Derivation<'a, M> {
+        // Nodes that are already duplicated in the manager are represented by the one with the smallest id
+        let mut unique = HashMap::new();
+        for id in manager_ref.get_node_ids().into_iter().sorted() {
+            if let Some((level, children, false)) = manager_ref.get_node(id) {
+                unique.entry((level, children)).or_insert(id);
+            }
+        }
+        Derivation {
+            manager_ref,
+            unique,
+            quasi_levels: get_quasi_levels(manager_ref, roots),
+        }
+    }
+
+    fn get_level(&self, node: NodeID) -> Option<LevelNo> {
+        self.manager_ref.get_node(node).map(|(level, _, _)| level)
+    }
+
+    /// Retrieves the node with the given level and children, adding it if the manager does not contain it yet
+    fn get_unique_node(&mut self, level: LevelNo, children: Vec<NodeID>) -> NodeID {
+        if let Some(&id) = self.unique.get(&(level, children.clone())) {
+            return id;
+        }
+        let id = self.manager_ref.add_inner_node(level, &children);
+        self.unique.insert((level, children), id);
+        id
+    }
+
+    /// Retrieves the node representing the given node from the given level onwards, which is preceded by a chain of redundant nodes on the levels in between if the section is quasi-reduced
+    fn lift(&mut self, node: NodeID, level: LevelNo) -> NodeID {
+        let Some(node_level) = self.get_level(node) else {
+            return node;
+        };
+        let chain_levels = match &self.quasi_levels {
+            Some(levels) => levels
+                .iter()
+                .filter(|&&chain_level| chain_level >= level && chain_level < node_level)
+                .cloned()
+                .collect_vec(),
+            None => return node,
+        };
+        chain_levels
+            .into_iter()
+            .rev()
+            .fold(node, |child, chain_level| self.get_unique_node(chain_level, vec![child; 2]))
+    }
+
+    /// Retrieves the node with the given level and children, following the reduction rules of the section
+    fn get_or_add_node(&mut self, level: LevelNo, children: Vec<NodeID>) -> NodeID {
+        if self.quasi_levels.is_none() {
+            if let Some(child) = M::get_reduced_child(&children) {
+                return child;
+            }
+        }
+        let next_level = self
+            .quasi_levels
+            .as_ref()
+            .and_then(|levels| levels.iter().find(|&&next| next > level).cloned());
+        let children = match next_level {
+            Some(next_level) => children
+                .into_iter()
+                .map(|child| self.lift(child, next_level))
+                .collect(),
+            None => children,
+        };
+        self.get_unique_node(level, children)
+    }
+
+    fn cofactor(
+        &mut self,
+        node: NodeID,
+        level: LevelNo,
+        value: bool,
+        cache: &mut HashMap<NodeID, NodeID>,
+    ) -> NodeID {
+        if let Some(&result) = cache.get(&node) {
+            return result;
+        }
+        let Some((node_level, children, is_terminal)) = self.manager_ref.get_node(node) else {
+            return node;
+        };
+
+        let result = if is_terminal || node_level > level {
+            node // The variable can not occur below this node
+        } else if node_level == level {
+            // Child index 0 represents the true edge, and index 1 the false edge
+            let index = if value { 0 } else { 1 };
+            children.get(index).cloned().unwrap_or(node)
+        } else {
+            let new_children = children
+                .iter()
+                .map(|&child| self.cofactor(child, level, value, cache))
+                .collect_vec();
+            if new_children == children {
+                node
+            } else {
+                self.get_or_add_node(node_level, new_children)
+            }
+        };
+        cache.insert(node, result);
+        result
+    }
+
+    fn quantify(
+        &mut self,
+        node: NodeID,
+        levels: &HashSet<LevelNo>,
+        quantifier: Quantifier,
+        cache: &mut HashMap<NodeID, NodeID>,
+        apply_cache: &mut HashMap<(NodeID, NodeID), NodeID>,
+    ) -> Option<NodeID> {
+        if let Some(&result) = cache.get(&node) {
+            return Some(result);
+        }
+        let Some((node_level, children, is_terminal)) = self.manager_ref.get_node(node) else {
+            return Some(node);
+        };
+        if is_terminal {
+            return Some(node);
+        }
+
+        let new_children = children
+            .iter()
+            .map(|&child| self.quantify(child, levels, quantifier, cache, apply_cache))
+            .collect::<Option<Vec<_>>>()?;
+        let result = if levels.contains(&node_level) {
+            match (new_children.get(0), new_children.get(1)) {
+                (Some(&high), Some(&low)) => {
+                    self.apply_quantifier(high, low, quantifier, apply_cache)?
+                }
+                _ => node,
+            }
+        } else if new_children == children {
+            node
+        } else {
+            self.get_or_add_node(node_level, new_children)
+        };
+        cache.insert(node, result);
+        Some(result)
+    }
+
+    /// Retrieves the true and false cofactors of the given node with respect to the variable of the given level
+    fn get_level_cofactors(&self, node: NodeID, level: LevelNo) -> (NodeID, NodeID) {
+        match self.manager_ref.get_node(node) {
+            // Child index 0 represents the true edge, and index 1 the false edge
+            Some((node_level, children, false)) if node_level == level && children.len() >= 2 => {
+                (children[0], children[1])
+            }
+            _ => (node, node),
+        }
+    }
+
+    /// Combines the two given functions, using disjunction for existential quantification and conjunction for universal quantification, where the terminals are combined by the manager. Fails if the manager can not combine the values of two terminals.
+    fn apply_quantifier(
+        &mut self,
+        a: NodeID,
+        b: NodeID,
+        quantifier: Quantifier,
+        cache: &mut HashMap<(NodeID, NodeID), NodeID>,
+    ) -> Option<NodeID> {
+        if a == b {
+            return Some(a);
+        }
+        if let Some(&result) = cache.get(&(a, b)) {
+            return Some(result);
+        }
+        let (Some(a_data), Some(b_data)) =
+            (self.manager_ref.get_node(a), self.manager_ref.get_node(b))
+        else {
+            return Some(a);
+        };
+
+        let result = if a_data.2 && b_data.2 {
+            self.manager_ref.quantify_terminals(a, b, quantifier)?
+        } else {
+            let level = [&a_data, &b_data]
+                .iter()
+                .filter(|(_, _, is_terminal)| !is_terminal)
+                .map(|(level, _, _)| *level)
+                .min()
+                .unwrap_or(0);
+            let (a_high, a_low) = self.get_level_cofactors(a, level);
+            let (b_high, b_low) = self.get_level_cofactors(b, level);
+            let high = self.apply_quantifier(a_high, b_high, quantifier, cache)?;
+            let low = self.apply_quantifier(a_low, b_low, quantifier, cache)?;
+            self.get_or_add_node(level, vec![high, low])
+        };
+        cache.insert((a, b), result);
+        Some(result)
+    }
+}
+
+/// Retrieves the levels of the nodes reachable from the given roots ordered from top to bottom if these nodes are quasi-reduced, i.e. every edge leads to the next of these levels and some node is redundant. Diagrams without redundant nodes are also valid reduced diagrams, hence they are not considered to be quasi-reduced.
+fn get_quasi_levels<M: DeriveManager>(manager_ref: &M, roots: &[NodeID]) -> Option<Vec<LevelNo>> {
+    let mut levels = BTreeSet::new();
+    let mut edges = HashSet::new();
+    let mut redundant = false;
+    let mut visited = HashSet::new();
+    let mut stack = roots.to_vec();
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node) {
+            continue;
+        }
+        let Some((level, children, is_terminal)) = manager_ref.get_node(node) else {
+            continue;
+        };
+        levels.insert(level);
+        redundant |= !is_terminal && M::get_reduced_child(&children).is_some();
+        for child in children {
+            if let Some((child_level, _, _)) = manager_ref.get_node(child) {
+                edges.insert((level, child_level));
+            }
+            stack.push(child);
+        }
+    }
+
+    let next_levels: HashMap<LevelNo, LevelNo> = levels.iter().cloned().tuple_windows().collect();
+    let quasi_reduced = redundant
+        && edges
+            .iter()
+            .all(|(level, child_level)| next_levels.get(level) == Some(child_level));
+    quasi_reduced.then(|| levels.into_iter().collect())
+}
+
+/// Applies the given operation to the node of every root, merging roots that end up at the same node, where the names of the roots are given the suffix. The derived nodes are added to the manager of the roots. Fails if the operation fails for any root.
+fn derive_roots<M: DeriveManager>(
+    roots: &[(M::Function, Vec<String>)],
+    mut operation: impl FnMut(&mut Derivation<M>, NodeID) -> Option<NodeID>,
+    label_suffix: &str,
+) -> Option<Vec<(M::Function, Vec<String>)>> {
+    let Some((first, _)) = roots.first() else {
+        return Some(Vec::new());
+    };
+    let manager_ref = M::of_function(first);
+    let node_id = |f: &M::Function| f.with_manager_shared(|_, edge| edge.node_id());
+    let root_ids = roots.iter().map(|(root, _)| node_id(root)).collect_vec();
+    let mut derivation = Derivation::new(&manager_ref, &root_ids);
+
+    let mut derived: Vec<(NodeID, Vec<String>)> = Vec::new();
+    for (&root, (_, names)) in root_ids.iter().zip(roots) {
+        let result = operation(&mut derivation, root)?;
+        // The derived function is defined on all levels that the root is defined on
+        let id = match derivation.get_level(root) {
+            Some(level) => derivation.lift(result, level),
+            None => result,
+        };
+        let names = names
+            .iter()
+            .map(|name| format!("{}{}", name, label_suffix))
+            .collect_vec();
+        match derived.iter_mut().find(|(derived_id, _)| *derived_id == id) {
+            Some((_, root_names)) => root_names.extend(names),
+            None => derived.push((id, names)),
+        }
+    }
+    let derived = derived
+        .into_iter()
+        .map(|(id, names)| (manager_ref.get_function(id), names))
+        .collect();
+    Some(derived)
+}
+
+fn get_level_name(levels: &[String], var: LevelNo) -> String {
+    levels
+        .get(var as usize)
+        .cloned()
+        .unwrap_or_else(|| var.to_string())
+}
+
+/// Derives the roots of a section in which the variable of the given level is fixed to the given value in every root of the section with the given level labels. The names of the roots are given a suffix naming the assignment.
+pub fn cofactor_roots<M: DeriveManager>(
+    roots: &[(M::Function, Vec<String>)],
+    levels: &[String],
+    var: LevelNo,
+    value: bool,
+) -> Vec<(M::Function, Vec<String>)> {
+    let suffix = format!("|{}={}", get_level_name(levels, var), if value { 1 } else { 0 });
+    let mut cache = HashMap::new();
+    // Cofactors do not combine terminals, hence they can be computed for every diagram type
+    derive_roots::<M>(
+        roots,
+        |derivation, root| Some(derivation.cofactor(root, var, value, &mut cache)),
+        &suffix,
+    )
+    .unwrap_or_default()
+}
+
+/// Derives the roots of a section in which the variables of the given levels are quantified in every root of the section with the given level labels. The names of the roots are given a suffix naming the quantified variables. Fails if the manager can not combine the values of the terminals of the section by the quantifier.
+pub fn quantify_roots<M: DeriveManager>(
+    roots: &[(M::Function, Vec<String>)],
+    levels: &[String],
+    vars: &[LevelNo],
+    quantifier: Quantifier,
+) -> Option<Vec<(M::Function, Vec<String>)>> {
+    let var_names = vars.iter().map(|&var| get_level_name(levels, var)).join(",");
+    let suffix = match quantifier {
+        Quantifier::Exists => format!("|∃{}", var_names),
+        Quantifier::Forall => format!("|∀{}", var_names),
+    };
+    let quantified: HashSet<LevelNo> = vars.iter().cloned().collect();
+    let mut cache = HashMap::new();
+    let mut apply_cache = HashMap::new();
+    derive_roots::<M>(
+        roots,
+        |derivation, root| {
+            derivation.quantify(root, &quantified, quantifier, &mut cache, &mut apply_cache)
+        },
+        &suffix,
+    )
+}
@@ -0,0 +1,201 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
+use oxidd::{Function, LevelNo};
+
+use crate::{
+    util::{
+        import_batch::{ImportBatch, ImportFile},
+        normalization::Normalization,
+        variable_registry::{is_ordered, VariableRegistry},
+        viz_error::VizError,
+    },
+    wasm_interface::{NodeID, ReductionData, ValidationWarning, ValidationWarningKind},
+};
+
+/// The operations on the nodes of a manager that importing sections relies on, which the managers of all diagram types provide, such that the drawers of all diagram types share the steps of importing a section
+pub trait ImportManager: Clone {
+    type Function: Function;
+
+    /// Creates a reference to a new manager without any nodes
+    fn new_empty() -> Self;
+    fn get_node_ids(&self) -> HashSet<NodeID>;
+    /// Checks the structure of the graph of the given functions, where the nodes that are not among the given known nodes are considered to be imported together with the functions
+    fn validate(
+        &self,
+        functions: &[(Self::Function, Vec<String>)],
+        known: &HashSet<NodeID>,
+    ) -> Vec<ValidationWarning>;
+    /// Retrieves the levels of the nodes that are not among the given known nodes, ordered from top to bottom
+    fn get_levels(&self, known: &HashSet<NodeID>) -> BTreeSet<LevelNo>;
+    /// Moves the nodes that are not among the given known nodes from their level to the level the mapping assigns to it
+    fn remap_levels(&self, known: &HashSet<NodeID>, map: impl Fn(LevelNo) -> LevelNo);
+    /// Adds the nodes of the given manager to this manager under new ids, moving every node to the level that the mapping assigns to its level and whether it is a terminal, and retrieves the given functions of the other manager in terms of the new ids
+    fn absorb(
+        &self,
+        other: &Self,
+        functions: &[(Self::Function, Vec<String>)],
+        map: impl Fn(LevelNo, bool) -> LevelNo,
+    ) -> Vec<(Self::Function, Vec<String>)>;
+    /// Brings the graph of the nodes that are not among the given known nodes into the given normal form, and retrieves the given functions in terms of the remaining nodes together with the sizes of the graph before and after the normalization
+    fn normalize(
+        &self,
+        functions: Vec<(Self::Function, Vec<String>)>,
+        known: &HashSet<NodeID>,
+        normalization: Normalization,
+    ) -> (Vec<(Self::Function, Vec<String>)>, ReductionData);
+}
+
+/// The parts of a section that are obtained from importing its graph
+pub struct ImportedGraph<F> {
+    pub roots: Vec<(F, Vec<String>)>,
+    pub levels: Vec<String>,
+    pub warnings: Vec<ValidationWarning>,
+    pub repairs: Vec<String>,
+    pub reduction: Option<ReductionData>,
+}
+
+/// Brings the graph of the given imported functions, which consists of all nodes except for the given known nodes, into the given normal form, and retrieves the functions in terms of the remaining nodes together with the sizes of the graph before and after the normalization, unless the graph is kept verbatim
+pub fn normalize_import<M: ImportManager>(
+    manager_ref: &M,
+    roots: Vec<(M::Function, Vec<String>)>,
+    known: &HashSet<NodeID>,
+    normalization: Normalization,
+) -> (Vec<(M::Function, Vec<String>)>, Option<ReductionData>) {
+    if normalization == Normalization::Verbatim {
+        return (roots, None);
+    }
+    let (roots, reduction) = manager_ref.normalize(roots, known, normalization);
+    (roots, Some(reduction))
+}
+
+/// Renumbers the levels of the imported nodes, which are all nodes except for the given known nodes, such that levels without any of these nodes are skipped. The given labels are reduced to the remaining levels, and the level that each remaining level had in the dump is stored in the given true levels.
+pub fn compact_levels<M: ImportManager>(
+    manager_ref: &M,
+    known: &HashSet<NodeID>,
+    levels: &mut Vec<String>,
+    true_levels: &mut Vec<LevelNo>,
+) {
+    let used = manager_ref.get_levels(known).into_iter().collect_vec();
+    let compacted: HashMap<LevelNo, LevelNo> = used
+        .iter()
+        .enumerate()
+        .map(|(index, &level)| (level, index as LevelNo))
+        .collect();
+    manager_ref.remap_levels(known, |level| compacted.get(&level).cloned().unwrap_or(level));
+    // Levels without a label, such as the level of the terminals, are not given one
+    *levels = used
+        .iter()
+        .filter_map(|&level| levels.get(level as usize).cloned())
+        .collect();
+    *true_levels = used;
+}
+
+/// Registers the variables of the levels with the given labels, and numbers the levels of the imported nodes, which are all nodes except for the given known nodes, by their canonical levels if levels are unified. The labels are replaced by the shared variables, unless the variables are ordered differently than in the other sections, which is reported by a warning instead.
+pub fn register_variables<M: ImportManager>(
+    manager_ref: &M,
+    known: &HashSet<NodeID>,
+    variables: &mut VariableRegistry,
+    unify: bool,
+    levels: &mut Vec<String>,
+    warnings: &mut Vec<ValidationWarning>,
+) {
+    let canonical = variables.register(levels);
+    if !unify {
+        return;
+    }
+    if !is_ordered(&canonical) {
+        warnings.push(ValidationWarning {
+            kind: ValidationWarningKind::VariableOrderConflict,
+            nodes: Vec::new(),
+            description: "The variables are ordered differently than in the other sections, \
+                hence the levels are not aligned with them"
+                .to_string(),
+        });
+        return;
+    }
+    // Levels below the variables, such as the levels of terminals, stay below the shared variables
+    let (local_count, shared_count) = (canonical.len(), variables.len());
+    manager_ref.remap_levels(known, |level| {
+        let extra = (level as usize).saturating_sub(local_count);
+        canonical
+            .get(level as usize)
+            .cloned()
+            .unwrap_or((shared_count + extra) as LevelNo)
+    });
+    *levels = variables.get_names().to_vec();
+}
+
+/// Imports the files of the given batch into the given manager, where every file is parsed into a manager of its own by the given parser, such that the ids of different dumps do not collide. The parser retrieves the functions, level labels and repairs of the file with the given number. The variable orders of the files are merged by the names of the variables, and the nodes shared by the files are merged even if imports are kept verbatim otherwise.
+pub fn import_batch<M: ImportManager>(
+    manager_ref: &M,
+    batch: &ImportBatch,
+    normalization: Normalization,
+    mut parse: impl FnMut(
+        usize,
+        &ImportFile,
+        &mut M,
+    ) -> Result<(Vec<(M::Function, Vec<String>)>, Vec<String>, Vec<String>), VizError>,
+) -> Result<ImportedGraph<M::Function>, VizError> {
+    let mut variables = VariableRegistry::new();
+    let mut repairs = Vec::new();
+    let mut files = Vec::new();
+    for (index, file) in batch.get_files().iter().enumerate() {
+        let mut file_manager_ref = M::new_empty();
+        let (roots, levels, file_repairs) = parse(index, file, &mut file_manager_ref)?;
+        repairs.extend(
+            file_repairs.into_iter().map(|repair| format!("File {}: {}", index, repair)),
+        );
+        let canonical = variables.register(&levels);
+        files.push((file_manager_ref, roots, canonical));
+    }
+
+    // The terminals of all files lie below all of the merged variables, such that equal terminals of different files can be merged
+    let known = manager_ref.get_node_ids();
+    let var_count = variables.len() as LevelNo;
+    let mut roots = Vec::new();
+    let mut warnings = Vec::new();
+    let mut aligned = true;
+    for (index, (file_manager_ref, file_roots, canonical)) in files.into_iter().enumerate() {
+        let ordered = is_ordered(&canonical);
+        aligned &= ordered;
+        if !ordered {
+            warnings.push(ValidationWarning {
+                kind: ValidationWarningKind::VariableOrderConflict,
+                nodes: Vec::new(),
+                description: format!(
+                    "The variables of file {} are ordered differently than in the files \
+                    before it, hence its levels are not aligned with them",
+                    index
+                ),
+            });
+        }
+        let file_roots = manager_ref.absorb(&file_manager_ref, &file_roots, |level, terminal| {
+            match (terminal, canonical.get(level as usize)) {
+                (true, _) => var_count,
+                (false, Some(&level)) if ordered => level,
+                (false, _) => level,
+            }
+        });
+        roots.extend(file_roots);
+    }
+    // Nodes of files whose levels are not aligned may lie on the same level without testing the same variable, hence nodes are only merged if all files are aligned
+    let normalization = match normalization {
+        Normalization::Verbatim => Normalization::Merge,
+        normalization => normalization,
+    };
+    let (roots, reduction) = if aligned {
+        let (roots, reduction) = manager_ref.normalize(roots, &known, normalization);
+        (roots, Some(reduction))
+    } else {
+        (roots, None)
+    };
+    warnings.extend(manager_ref.validate(&roots, &known));
+    Ok(ImportedGraph {
+        roots,
+        levels: variables.get_names().to_vec(),
+        warnings,
+        repairs,
+        reduction,
+    })
+}
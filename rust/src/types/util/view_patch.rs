@@ -0,0 +1,132 @@
+use crate::{
+    types::util::{
+        graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
+        group_manager::GroupName,
+    },
+    util::json::JsonValue,
+    wasm_interface::NodeID,
+};
+
+/// The presence adjustments and groups of a section, keyed by source node IDs such that they can be applied to a freshly loaded copy of the same diagram. Unlike the serialized state, the patch is human readable and independent of the IDs that nodes and groups get within a session.
+pub struct ViewPatch {
+    pub presence: Vec<(NodeID, PresenceRemainder)>,
+    pub groups: Vec<ViewPatchGroup>,
+}
+
+/// A group of a view patch, given by the source nodes it contains
+pub struct ViewPatchGroup {
+    pub nodes: Vec<NodeID>,
+    pub name: Option<GroupName>,
+}
+
+impl ViewPatch {
+    pub fn to_json(&self) -> String {
+        let presence = self
+            .presence
+            .iter()
+            .map(|(node, remainder)| {
+                JsonValue::Object(vec![
+                    ("node".into(), JsonValue::Number(*node as f64)),
+                    (
+                        "remainder".into(),
+                        JsonValue::String(remainder_to_str(remainder).into()),
+                    ),
+                ])
+            })
+            .collect();
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                let nodes = group
+                    .nodes
+                    .iter()
+                    .map(|&node| JsonValue::Number(node as f64))
+                    .collect();
+                let mut entries = vec![("nodes".into(), JsonValue::Array(nodes))];
+                if let Some(name) = &group.name {
+                    entries.push(("name".into(), JsonValue::String(name.name.clone())));
+                    entries.push(("note".into(), JsonValue::String(name.note.clone())));
+                }
+                JsonValue::Object(entries)
+            })
+            .collect();
+        JsonValue::Object(vec![
+            ("presence".into(), JsonValue::Array(presence)),
+            ("groups".into(), JsonValue::Array(groups)),
+        ])
+        .to_string()
+    }
+
+    pub fn from_json(text: &str) -> Result<ViewPatch, String> {
+        let value = JsonValue::parse(text)?;
+        let presence = get_items(&value, "presence")?
+            .iter()
+            .map(|item| -> Result<_, String> {
+                let node = item.get("node").ok_or("Presence entry without node")?;
+                let remainder = item
+                    .get("remainder")
+                    .and_then(|remainder| remainder.as_str())
+                    .ok_or("Presence entry without remainder")?;
+                let remainder = remainder_from_str(remainder)
+                    .ok_or_else(|| format!("Unknown presence remainder \"{}\"", remainder))?;
+                Ok((get_node_id(node)?, remainder))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let groups = get_items(&value, "groups")?
+            .iter()
+            .map(|item| -> Result<_, String> {
+                let nodes = get_items(item, "nodes")?
+                    .iter()
+                    .map(get_node_id)
+                    .collect::<Result<Vec<_>, String>>()?;
+                let name = item.get("name").and_then(|name| name.as_str());
+                let note = item.get("note").and_then(|note| note.as_str());
+                let name = (name.is_some() || note.is_some()).then(|| GroupName {
+                    name: name.unwrap_or("").into(),
+                    note: note.unwrap_or("").into(),
+                });
+                Ok(ViewPatchGroup { nodes, name })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(ViewPatch { presence, groups })
+    }
+}
+
+/// Retrieves the items of the array under the given key, where a missing key is treated as an empty array
+fn get_items<'a>(value: &'a JsonValue, key: &str) -> Result<&'a [JsonValue], String> {
+    match value.get(key) {
+        None => Ok(&[]),
+        Some(items) => items
+            .as_array()
+            .map(|items| items.as_slice())
+            .ok_or_else(|| format!("Expected \"{}\" to be an array", key)),
+    }
+}
+
+fn get_node_id(value: &JsonValue) -> Result<NodeID, String> {
+    value
+        .as_f64()
+        .filter(|&id| id >= 0. && id.fract() == 0.)
+        .map(|id| id as NodeID)
+        .ok_or_else(|| format!("Invalid node ID {}", value))
+}
+
+fn remainder_to_str(remainder: &PresenceRemainder) -> &'static str {
+    match remainder {
+        PresenceRemainder::Show => "show",
+        PresenceRemainder::Hide => "hide",
+        PresenceRemainder::Duplicate => "duplicate",
+        PresenceRemainder::DuplicateParent => "duplicate_parent",
+    }
+}
+
+fn remainder_from_str(text: &str) -> Option<PresenceRemainder> {
+    match text {
+        "show" => Some(PresenceRemainder::Show),
+        "hide" => Some(PresenceRemainder::Hide),
+        "duplicate" => Some(PresenceRemainder::Duplicate),
+        "duplicate_parent" => Some(PresenceRemainder::DuplicateParent),
+        _ => None,
+    }
+}
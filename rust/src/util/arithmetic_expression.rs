@@ -0,0 +1,144 @@
+use std::{iter::Peekable, str::Chars};
+
+/// An arithmetic expression over Boolean variables, such as `2*a + 3*b - c`, where variables evaluate to 0 or 1
+pub enum ArithmeticExpression {
+    Constant(f32),
+    Variable(String),
+    Negate(Box<ArithmeticExpression>),
+    Binary(Box<ArithmeticExpression>, ArithmeticOperator, Box<ArithmeticExpression>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+impl ArithmeticOperator {
+    pub fn apply(&self, a: f32, b: f32) -> f32 {
+        match self {
+            ArithmeticOperator::Add => a + b,
+            ArithmeticOperator::Subtract => a - b,
+            ArithmeticOperator::Multiply => a * b,
+        }
+    }
+}
+
+impl ArithmeticExpression {
+    /// Parses expressions consisting of numbers, variable names, parentheses, unary minus and the binary operators `+`, `-` and `*`, where multiplication binds stronger than addition and subtraction
+    pub fn parse(text: &str) -> Result<ArithmeticExpression, String> {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+        };
+        let expression = parser.parse_sum()?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(c) => Err(format!("Unexpected character '{}'", c)),
+        }
+    }
+
+    /// Retrieves the names of the variables in order of their first occurrence
+    pub fn get_variables(&self) -> Vec<String> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<String>) {
+        match self {
+            ArithmeticExpression::Constant(_) => {}
+            ArithmeticExpression::Variable(name) => {
+                if !variables.contains(name) {
+                    variables.push(name.clone());
+                }
+            }
+            ArithmeticExpression::Negate(expression) => expression.collect_variables(variables),
+            ArithmeticExpression::Binary(a, _, b) => {
+                a.collect_variables(variables);
+                b.collect_variables(variables);
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    /// Retrieves the next character that is not whitespace, without consuming it
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().cloned()
+    }
+
+    fn parse_sum(&mut self) -> Result<ArithmeticExpression, String> {
+        let mut expression = self.parse_product()?;
+        loop {
+            let operator = match self.peek() {
+                Some('+') => ArithmeticOperator::Add,
+                Some('-') => ArithmeticOperator::Subtract,
+                _ => return Ok(expression),
+            };
+            self.chars.next();
+            let right = self.parse_product()?;
+            expression =
+                ArithmeticExpression::Binary(Box::new(expression), operator, Box::new(right));
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<ArithmeticExpression, String> {
+        let mut expression = self.parse_factor()?;
+        while self.peek() == Some('*') {
+            self.chars.next();
+            let right = self.parse_factor()?;
+            expression = ArithmeticExpression::Binary(
+                Box::new(expression),
+                ArithmeticOperator::Multiply,
+                Box::new(right),
+            );
+        }
+        Ok(expression)
+    }
+
+    fn parse_factor(&mut self) -> Result<ArithmeticExpression, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Ok(ArithmeticExpression::Negate(Box::new(self.parse_factor()?)))
+            }
+            Some('(') => {
+                self.chars.next();
+                let expression = self.parse_sum()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(expression)
+                    }
+                    _ => Err("Expected ')'".into()),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let text = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                text.parse()
+                    .map(ArithmeticExpression::Constant)
+                    .map_err(|_| format!("Invalid number '{}'", text))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Ok(ArithmeticExpression::Variable(name))
+            }
+            Some(c) => Err(format!("Unexpected character '{}'", c)),
+            None => Err("Unexpected end of expression".into()),
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.chars.next_if(|&c| predicate(c)) {
+            text.push(c);
+        }
+        text
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(target_arch = "wasm32")]
+use crate::util::{
+    clock,
+    logging::{self, LogLevel},
+};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    // Leveled messages go to the matching console methods, such that the browser can filter them
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn log(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn log_error(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn log_warn(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn log_debug(s: &str);
+}
+
+/// Writes the message to the browser console
+#[cfg(target_arch = "wasm32")]
+fn write_console(level: LogLevel, message: &str) {
+    match level {
+        LogLevel::Error => log_error(message),
+        LogLevel::Warn => log_warn(message),
+        LogLevel::Debug | LogLevel::Trace => log_debug(message),
+        _ => log(message),
+    }
+}
+
+/// Lets the engine log to the browser console and measure time using the clock of the browser, since the standard error and `Instant` are not available there. Outside of the browser, such as in native tests, the defaults of the engine are kept.
+pub fn connect() {
+    #[cfg(target_arch = "wasm32")]
+    {
+        logging::set_log_sink(write_console);
+        clock::set_clock(js_sys::Date::now);
+    }
+}
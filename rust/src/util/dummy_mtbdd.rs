@@ -28,7 +28,15 @@ use oxidd_core::ReducedOrNew;
 use oxidd_core::WorkerManager;
 use oxidd_core::{BroadcastContext, HasLevel};
 
+use crate::util::arithmetic_expression::{ArithmeticExpression, ArithmeticOperator};
+use crate::types::util::section_derivation::DeriveManager;
+use crate::types::util::section_import::ImportManager;
+use crate::util::graph_builder::GraphBuilder;
+use crate::util::graph_validation::validate_graph;
+use crate::util::import_repair::{canonicalize_dump, repair_dump};
 use crate::util::logging::console;
+use crate::util::normalization::{plan_normalization, Normalization};
+use crate::wasm_interface::{Quantifier, ReductionData, ValidationWarning};
 
 #[derive(Clone, Copy, PartialOrd)]
 pub struct MTBDDTerminal(pub f32);
@@ -68,6 +76,199 @@ impl<'a> From<&'a DummyMTBDDManager> for DummyMTBDDManagerRef {
         DummyMTBDDManagerRef(Rc::new(RefCell::new(value.clone())))
     }
 }
+impl ImportManager for DummyMTBDDManagerRef {
+    type Function = DummyMTBDDFunction;
+
+    fn new_empty() -> DummyMTBDDManagerRef {
+        DummyMTBDDManagerRef::from(&DummyMTBDDManager::new())
+    }
+    fn get_node_ids(&self) -> HashSet<NodeID> {
+        self.0.borrow().0.keys().cloned().collect()
+    }
+    /// Checks the structure of the graph of the given functions, where the nodes that are not among the given known nodes are considered to be imported together with the functions
+    fn validate(
+        &self,
+        functions: &[(DummyMTBDDFunction, Vec<String>)],
+        known: &HashSet<NodeID>,
+    ) -> Vec<ValidationWarning> {
+        let manager = self.0.borrow();
+        let roots = functions.iter().map(|(f, _)| f.0.node_id()).collect_vec();
+        let imported = manager
+            .0
+            .keys()
+            .filter(|id| !known.contains(id))
+            .cloned()
+            .collect();
+        validate_graph(&roots, &imported, |node| manager.get_node_data(node))
+    }
+    /// Retrieves the levels of the nodes that are not among the given known nodes, ordered from top to bottom
+    fn get_levels(&self, known: &HashSet<NodeID>) -> BTreeSet<LevelNo> {
+        self.0
+            .borrow()
+            .0
+            .iter()
+            .filter(|(id, _)| !known.contains(id))
+            .map(|(_, node)| node.0)
+            .collect()
+    }
+    /// Moves the nodes that are not among the given known nodes from their level to the level the mapping assigns to it, such that nodes imported afterwards can be numbered by a shared variable order
+    fn remap_levels(&self, known: &HashSet<NodeID>, map: impl Fn(LevelNo) -> LevelNo) {
+        let mut manager = self.0.borrow_mut();
+        for (id, node) in manager.0.iter_mut() {
+            if !known.contains(id) {
+                node.0 = map(node.0);
+            }
+        }
+    }
+    /// Adds the nodes of the given manager to this manager under new ids that do not collide with the nodes of this manager, moving every node to the level that the mapping assigns to its level and whether it is a terminal, and retrieves the given functions of the other manager in terms of the new ids. Dumps that use the same ids can hence be imported into one manager without sharing nodes by accident, except for terminals with a value that this manager already has, which are replaced by the existing terminal such that every value has a single terminal.
+    fn absorb(
+        &self,
+        other: &DummyMTBDDManagerRef,
+        functions: &[(DummyMTBDDFunction, Vec<String>)],
+        map: impl Fn(LevelNo, bool) -> LevelNo,
+    ) -> Vec<(DummyMTBDDFunction, Vec<String>)> {
+        let mut manager = self.0.borrow_mut();
+        let other = other.0.borrow();
+        let offset = manager.0.keys().next_back().map_or(0, |&id| id + 1);
+        let existing: HashMap<NodeID, NodeID> = other
+            .1
+            .iter()
+            .filter_map(|(value, terminal)| {
+                Some((terminal.node_id(), manager.1.get(value)?.node_id()))
+            })
+            .collect();
+        let new_id = |id: NodeID| existing.get(&id).cloned().unwrap_or(offset + id);
+        let edge = |id: NodeID| DummyMTBDDEdge::new(Arc::new(new_id(id)), self.clone());
+        for (&id, DummyMTBDDNode(level, children, terminal)) in &other.0 {
+            if existing.contains_key(&id) {
+                continue;
+            }
+            let children = children.iter().map(|child| edge(child.node_id())).collect();
+            let level = map(*level, terminal.is_some());
+            manager.0.insert(offset + id, DummyMTBDDNode(level, children, terminal.clone()));
+        }
+        for (value, terminal) in &other.1 {
+            if !existing.contains_key(&terminal.node_id()) {
+                manager.1.insert(value.clone(), edge(terminal.node_id()));
+            }
+        }
+        functions
+            .iter()
+            .map(|(function, names)| {
+                (DummyMTBDDFunction(edge(function.0.node_id())), names.clone())
+            })
+            .collect()
+    }
+    /// Brings the graph of the nodes that are not among the given known nodes into the given normal form, where the nodes are merged into structurally identical nodes that are not known either and redundant nodes are those whose edges all lead to the same child, as by the reduction rules of MTBDDs. Retrieves the given functions in terms of the remaining nodes, where functions of the same node are combined, together with the sizes of the graph before and after the normalization.
+    fn normalize(
+        &self,
+        functions: Vec<(DummyMTBDDFunction, Vec<String>)>,
+        known: &HashSet<NodeID>,
+        normalization: Normalization,
+    ) -> (Vec<(DummyMTBDDFunction, Vec<String>)>, ReductionData) {
+        let mut manager = self.0.borrow_mut();
+        let imported: HashSet<NodeID> = manager
+            .0
+            .keys()
+            .filter(|id| !known.contains(id))
+            .cloned()
+            .collect();
+        let next_id = manager.0.keys().next_back().map_or(0, |&id| id + 1);
+        let plan = plan_normalization(
+            &imported,
+            |id| {
+                manager.0.get(&id).map(|DummyMTBDDNode(level, children, terminal)| {
+                    let children = children.iter().map(|child| child.node_id()).collect();
+                    (*level, children, terminal.map(|value| value.to_string()))
+                })
+            },
+            normalization,
+            |children| Self::get_reduced_child(children),
+            next_id,
+        );
+        let replacements = &plan.replacements;
+        let resolve = |id: NodeID| replacements.get(&id).cloned().unwrap_or(id);
+        let edge = |id: NodeID| DummyMTBDDEdge::new(Arc::new(id), self.clone());
+
+        for id in replacements.keys() {
+            manager.0.remove(id);
+        }
+        let DummyMTBDDManager(nodes, terminals) = &mut *manager;
+        let edges = nodes
+            .values_mut()
+            .flat_map(|node| node.1.iter_mut())
+            .chain(terminals.values_mut());
+        for edge_to in edges {
+            if replacements.contains_key(&edge_to.node_id()) {
+                *edge_to = edge(resolve(edge_to.node_id()));
+            }
+        }
+        for &(id, level, child, arity) in &plan.inserted {
+            let children = (0..arity).map(|_| edge(child)).collect();
+            nodes.insert(id, DummyMTBDDNode(level, children, None));
+        }
+        for &(parent, index, child) in &plan.rewired {
+            if let Some(edge_to) = nodes.get_mut(&parent).and_then(|node| node.1.get_mut(index)) {
+                *edge_to = edge(child);
+            }
+        }
+        drop(manager);
+
+        let mut merged: Vec<(DummyMTBDDFunction, Vec<String>)> = Vec::new();
+        for (function, names) in functions {
+            let id = resolve(function.0.node_id());
+            match merged.iter_mut().find(|(other, _)| other.0.node_id() == id) {
+                Some((_, other_names)) => other_names.extend(names),
+                None => merged.push((DummyMTBDDFunction(edge(id)), names)),
+            }
+        }
+        let report = ReductionData {
+            nodes_before: imported.len(),
+            nodes_after: imported.len() - replacements.len() + plan.inserted.len(),
+            merged: replacements.len() - plan.removed,
+            removed: plan.removed,
+            inserted: plan.inserted.len(),
+        };
+        (merged, report)
+    }
+}
+impl DeriveManager for DummyMTBDDManagerRef {
+    fn of_function(function: &DummyMTBDDFunction) -> DummyMTBDDManagerRef {
+        function.0 .1.clone()
+    }
+    fn get_function(&self, node: NodeID) -> DummyMTBDDFunction {
+        DummyMTBDDFunction(DummyMTBDDEdge::new(Arc::new(node), self.clone()))
+    }
+    fn get_node(&self, node: NodeID) -> Option<(LevelNo, Vec<NodeID>, bool)> {
+        self.0.borrow().get_node_data(node)
+    }
+    fn add_inner_node(&self, level: LevelNo, children: &[NodeID]) -> NodeID {
+        let mut manager = self.0.borrow_mut();
+        let id = manager.0.keys().next_back().map_or(0, |&id| id + 1);
+        manager.add_node_level(id, level, None);
+        for &child in children {
+            manager.add_edge(id, child, self.clone());
+        }
+        id
+    }
+    /// Nodes whose children are all the same are redundant
+    fn get_reduced_child(children: &[NodeID]) -> Option<NodeID> {
+        children.iter().all_equal().then(|| children[0])
+    }
+    /// Combines the values by their maximum for existential quantification and their minimum for universal quantification
+    fn quantify_terminals(&self, a: NodeID, b: NodeID, quantifier: Quantifier) -> Option<NodeID> {
+        let manager = self.0.borrow();
+        let (a_term, b_term) = (manager.get_terminal_value(a)?, manager.get_terminal_value(b)?);
+        let result_term = match quantifier {
+            Quantifier::Exists => a_term.0.max(b_term.0),
+            Quantifier::Forall => a_term.0.min(b_term.0),
+        };
+        manager
+            .1
+            .get(&MTBDDTerminal(result_term))
+            .map(|terminal| terminal.node_id())
+    }
+}
 impl ManagerRef for DummyMTBDDManagerRef {
     type Manager<'id> = DummyMTBDDManager;
 
@@ -89,26 +290,35 @@ impl ManagerRef for DummyMTBDDManagerRef {
 #[derive(Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DummyMTBDDFunction(pub DummyMTBDDEdge);
 impl DummyMTBDDFunction {
+    /// Loads the functions of a dddmp dump, returning them together with the variable names and the fixes applied to the dump, or describes why the dump could not be parsed. Common defects of the dump are only fixed if `repair` is set, and the nodes are renumbered by a canonical traversal of the dump rather than keeping the ids of the dump if `canonical_ids` is set.
     pub fn from_dddmp(
         manager_ref: &mut DummyMTBDDManagerRef,
         data: &str,
-    ) -> (Vec<(DummyMTBDDFunction, Vec<String>)>, Vec<String>) {
+        repair: bool,
+        canonical_ids: bool,
+    ) -> Result<(Vec<(DummyMTBDDFunction, Vec<String>)>, Vec<String>, Vec<String>), String> {
         manager_ref.with_manager_exclusive(|manager| {
             let mut terminals = HashMap::new();
 
             let get_text = |from: &str, to: &str| {
-                let start = data.find(from).unwrap() + from.len();
-                Box::new(&data[start + 1..start + data[start..].find(to).unwrap()])
+                let start = data
+                    .find(from)
+                    .ok_or_else(|| format!("The dump does not contain {}", from))?
+                    + from.len();
+                let end = data[start..]
+                    .find(to)
+                    .ok_or_else(|| format!("The dump ends within {}", from))?;
+                Ok::<_, String>(data.get(start + 1..start + end).unwrap_or_default())
             };
 
-            let roots_text = get_text(".rootids", "\n");
-            let roots = roots_text
+            let roots_text = get_text(".rootids", "\n")?;
+            let mut roots = roots_text
                 .trim()
                 .split(" ")
                 .flat_map(|n| n.parse::<usize>())
                 .collect_vec();
             let root_names = if data.find(".rootnames").is_some() {
-                let roots_names_text = get_text(".rootnames", "\n");
+                let roots_names_text = get_text(".rootnames", "\n")?;
                 roots_names_text
                     .trim()
                     .split(" ")
@@ -122,18 +332,51 @@ impl DummyMTBDDFunction {
                     .collect_vec()
             };
 
-            let node_text = get_text(".nodes", ".end");
-            let nodes_data = node_text.split("\n").filter_map(|node| {
-                let parts = node.trim().split(" ").collect::<Vec<&str>>();
-                if parts.len() >= 4 {
-                    let id: NodeID = parts[0].parse().unwrap();
-                    let level = parts[1];
-                    let children = parts[2..].iter().map(|v| v.parse().unwrap()).collect_vec();
-                    Some((id, level, children))
+            let node_text = get_text(".nodes", ".end")?;
+            let nodes_data = node_text
+                .split("\n")
+                .map(|node| node.trim().split(" ").collect::<Vec<&str>>())
+                .filter(|parts| parts.len() >= 4)
+                .map(|parts| {
+                    let parse = |text: &str| {
+                        text.parse::<NodeID>().map_err(|_| {
+                            format!("Invalid node id {} in the line: {}", text, parts.join(" "))
+                        })
+                    };
+                    let id = parse(parts[0])?;
+                    let level = parts[1].to_string();
+                    let children = parts[2..].iter().map(|v| parse(v)).collect::<Result<_, _>>()?;
+                    Ok((id, level, children))
+                })
+                .collect::<Result<Vec<(NodeID, String, Vec<NodeID>)>, String>>()?;
+            let (nodes_data, repairs) = if repair {
+                repair_dump(
+                    nodes_data,
+                    &mut roots,
+                    |_, children| children.first() == Some(&0),
+                    |_| ("NaN".to_string(), vec![0, 0]),
+                )
+            } else {
+                (nodes_data, Vec::new())
+            };
+            let nodes_data = if canonical_ids {
+                canonicalize_dump(nodes_data, &mut roots, |_, children| {
+                    children.first() == Some(&0)
+                })
+            } else {
+                nodes_data
+            };
+            // Inner nodes are labelled by their level and terminals by their value
+            for (id, level, children) in &nodes_data {
+                let valid = if children.first() == Some(&0) {
+                    level.parse::<f32>().is_ok()
                 } else {
-                    None
+                    level.parse::<LevelNo>().is_ok()
+                };
+                if !valid {
+                    return Err(format!("Node {} has the invalid level or value {}", id, level));
                 }
-            });
+            }
             let mut max_level = 0;
             for (_, level, _) in nodes_data.clone() {
                 let Ok(level) = level.parse() else { continue };
@@ -146,7 +389,7 @@ impl DummyMTBDDFunction {
             for (id, level, children) in nodes_data.clone() {
                 let level_num = level.parse();
                 let term_num = (level.parse() as Result<f32, _>).map(|r| MTBDDTerminal(r));
-                let is_terminal = children[0] == 0;
+                let is_terminal = children.first() == Some(&0);
                 manager.add_node_level(
                     id.clone(),
                     if is_terminal {
@@ -209,21 +452,221 @@ impl DummyMTBDDFunction {
             let funcs = func_map.values().cloned().collect_vec();
 
             let var_names = if data.find(".suppvarnames").is_some() {
-                let var_names_text = get_text(".suppvarnames", ".orderedvarnames");
+                let var_names_text = get_text(".suppvarnames", ".orderedvarnames")?;
                 var_names_text
                     .trim()
                     .split(" ")
                     .map(|t| t.to_string())
                     .collect_vec()
             } else {
-                let var_count = get_text(".nsuppvars", ".").trim().parse().unwrap_or(0);
+                let var_count = get_text(".nsuppvars", ".")?.trim().parse().unwrap_or(0);
                 (0..var_count)
                     .into_iter()
                     .map(|i| format!("{}", i))
                     .collect_vec()
             };
 
-            (funcs, var_names)
+            Ok((funcs, var_names, repairs))
+        })
+    }
+
+    /// Constructs the functions described by the given graph, whose nodes are added to the manager under new ids. The labels of the terminals have to be numbers.
+    pub fn from_builder(
+        manager_ref: &mut DummyMTBDDManagerRef,
+        graph: &GraphBuilder,
+    ) -> Result<(Vec<(DummyMTBDDFunction, Vec<String>)>, Vec<String>), String> {
+        manager_ref.with_manager_exclusive(|manager| {
+            let offset = manager.0.keys().next_back().map_or(0, |&id| id + 1);
+            let terminal_level = graph.get_levels().len() as LevelNo;
+            let values = graph
+                .get_nodes()
+                .iter()
+                .map(|node| {
+                    let Some(label) = &node.terminal else {
+                        return Ok(None);
+                    };
+                    match label.trim().parse::<f32>() {
+                        Ok(value) => Ok(Some(MTBDDTerminal(value))),
+                        Err(_) => Err(format!("Terminal label {} is not a number", label)),
+                    }
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            let mut terminals = HashMap::new();
+            for (id, (node, value)) in graph.get_nodes().iter().zip(values).enumerate() {
+                match value {
+                    Some(value) => {
+                        manager.add_node_level(offset + id, terminal_level, Some(value));
+                        terminals.insert(
+                            value,
+                            DummyMTBDDEdge::new(Arc::new(offset + id), manager_ref.clone()),
+                        );
+                    }
+                    None => {
+                        manager.add_node_level(offset + id, node.level, None);
+                    }
+                }
+            }
+            for (id, node) in graph.get_nodes().iter().enumerate() {
+                for &child in node.children.iter().flatten() {
+                    manager.add_edge(offset + id, offset + child, manager_ref.clone());
+                }
+            }
+            manager.init_terminals(terminals);
+
+            let mut func_map = HashMap::<NodeID, (DummyMTBDDFunction, Vec<String>)>::new();
+            for (root, name) in graph.get_roots() {
+                func_map
+                    .entry(offset + root)
+                    .or_insert_with(|| {
+                        (
+                            DummyMTBDDFunction(DummyMTBDDEdge::new(
+                                Arc::new(offset + root),
+                                manager_ref.clone(),
+                            )),
+                            vec![],
+                        )
+                    })
+                    .1
+                    .push(name.clone());
+            }
+            Ok((func_map.values().cloned().collect_vec(), graph.get_levels().clone()))
+        })
+    }
+
+    /// Constructs the function described by an arithmetic expression over Boolean variables such as `2*a + 3*b - c`, returning it together with the names of its variables, which are ordered by their first occurrence
+    pub fn from_expression(
+        manager_ref: &mut DummyMTBDDManagerRef,
+        text: &str,
+    ) -> Result<(DummyMTBDDFunction, Vec<String>), String> {
+        let expression = ArithmeticExpression::parse(text)?;
+        let variables = expression.get_variables();
+        let manager_ref = manager_ref.clone();
+        manager_ref.with_manager_exclusive(|manager| {
+            let mut caches = HashMap::new();
+            let mut unique = HashMap::new();
+            let root = manager.build_expression(
+                &expression,
+                &variables,
+                &mut caches,
+                &mut unique,
+                &manager_ref,
+            );
+            let function =
+                DummyMTBDDFunction(DummyMTBDDEdge::new(Arc::new(root), manager_ref.clone()));
+            Ok((function, variables))
+        })
+    }
+
+    /// Searches an assignment for which this function and the given function evaluate differently, returning the assigned value per level. Levels that are not included in the assignment do not influence the difference. Pairs of nodes that the given predicate considers structurally identical represent the same subfunction, hence they are not searched. Fails if either function refers to a node that is not part of the diagram.
+    pub fn find_difference(
+        &self,
+        other: &DummyMTBDDFunction,
+        identical: &mut dyn FnMut(NodeID, NodeID) -> bool,
+    ) -> Result<Option<Vec<(LevelNo, bool)>>, String> {
+        let (a, b) = (self.0.node_id(), other.0.node_id());
+        self.0 .1.with_manager_shared(|manager| {
+            manager.find_difference(a, b, &mut HashSet::new(), identical)
+        })
+    }
+
+    /// Retrieves the nodes that are visited when evaluating this function for the given assignment, where unassigned levels are considered to be false
+    pub fn follow_assignment(&self, assignment: &[(LevelNo, bool)]) -> Vec<NodeID> {
+        let node = self.0.node_id();
+        let assignment = assignment.iter().cloned().collect();
+        self.0 .1.with_manager_shared(|manager| {
+            manager.follow_assignment(node, &assignment)
+        })
+    }
+
+    /// Searches up to max_count assignments for which this function evaluates to the given terminal, together with the nodes visited for each assignment
+    pub fn find_paths_to(
+        &self,
+        terminal: &MTBDDTerminal,
+        max_count: usize,
+    ) -> Vec<(Vec<(LevelNo, bool)>, Vec<NodeID>)> {
+        let root = self.0.node_id();
+        self.0 .1.with_manager_shared(|manager| {
+            let Some(target) = manager.1.get(terminal).map(|edge| edge.node_id()) else {
+                return Vec::new();
+            };
+            let mut out = Vec::new();
+            manager.find_paths(
+                root,
+                target,
+                max_count,
+                &mut HashSet::new(),
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut out,
+            );
+            out
+        })
+    }
+    /// Counts the inner nodes per level that can be reached from this function, skipping the nodes that were already visited such that the counts can be accumulated over several functions
+    pub fn count_level_nodes(
+        &self,
+        visited: &mut HashSet<NodeID>,
+        counts: &mut HashMap<LevelNo, usize>,
+    ) {
+        let root = self.0.node_id();
+        self.0 .1.with_manager_shared(|manager| {
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                if !visited.insert(node) {
+                    continue;
+                }
+                let Some((level, children, terminal)) = manager.get_node_data(node) else {
+                    continue;
+                };
+                if !terminal {
+                    *counts.entry(level).or_default() += 1;
+                }
+                stack.extend(children);
+            }
+        })
+    }
+    /// Counts the assignments of the given number of variables for which this function evaluates to a value other than 0
+    pub fn count_satisfying(&self, var_count: LevelNo) -> f64 {
+        let root = self.0.node_id();
+        self.0 .1.with_manager_shared(|manager| {
+            let level = manager.get_variable_level(root, var_count);
+            manager.count_satisfying(root, var_count, &mut HashMap::new()) * 2f64.powi(level as i32)
+        })
+    }
+
+    /// Retrieves the shortest or longest path from this function to any terminal, represented by its assignment and the visited nodes
+    pub fn find_extreme_path(&self, longest: bool) -> (Vec<(LevelNo, bool)>, Vec<NodeID>) {
+        let root = self.0.node_id();
+        self.0 .1.with_manager_shared(|manager| {
+            let mut lengths = HashMap::new();
+            manager.get_path_length(root, longest, &mut lengths);
+
+            let mut assignment = Vec::new();
+            let mut path = vec![root];
+            let mut current = root;
+            while let Some(&(_, Some(index))) = lengths.get(&current) {
+                let Some((level, children, _)) = manager.get_node_data(current) else {
+                    break;
+                };
+                // Child index 0 represents the true edge, and index 1 the false edge
+                assignment.push((level, index == 0));
+                current = children[index];
+                path.push(current);
+            }
+            (assignment, path)
+        })
+    }
+
+    /// Computes the expected terminal value of every node reachable from this function, given the probability of each level's variable being true. Levels without a probability are assumed to be true half of the time.
+    pub fn expected_values(
+        &self,
+        probabilities: &HashMap<LevelNo, f32>,
+        values: &mut HashMap<NodeID, f32>,
+    ) -> f32 {
+        let root = self.0.node_id();
+        self.0 .1.with_manager_shared(|manager| {
+            manager.get_expected_value(root, probabilities, values)
         })
     }
 }
@@ -426,6 +869,340 @@ impl DummyMTBDDManager {
         let from_children = &self.0.get(&node).unwrap().1;
         from_children.len() > 0
     }
+    fn get_node_data(&self, node: NodeID) -> Option<(LevelNo, Vec<NodeID>, bool)> {
+        self.0.get(&node).map(|DummyMTBDDNode(level, children, terminal)| {
+            (
+                *level,
+                children.iter().map(|child| child.node_id()).collect(),
+                terminal.is_some(),
+            )
+        })
+    }
+    fn get_terminal_value(&self, node: NodeID) -> Option<MTBDDTerminal> {
+        self.0.get(&node).and_then(|node| node.2.clone())
+    }
+    /// Retrieves the level of the variable tested by the given node, where terminals lie below all of the given number of variables
+    fn get_variable_level(&self, node: NodeID, var_count: LevelNo) -> LevelNo {
+        match self.get_node_data(node) {
+            Some((level, _, false)) => level.min(var_count),
+            _ => var_count,
+        }
+    }
+    /// Counts the assignments of the variables from the level of the given node up to the given number of variables for which the node evaluates to a value other than 0
+    fn count_satisfying(
+        &self,
+        node: NodeID,
+        var_count: LevelNo,
+        counts: &mut HashMap<NodeID, f64>,
+    ) -> f64 {
+        if let Some(&count) = counts.get(&node) {
+            return count;
+        }
+        let Some((_, children, terminal)) = self.get_node_data(node) else {
+            return 0.;
+        };
+        let count = if terminal {
+            if self.get_terminal_value(node).map_or(false, |value| value.0 != 0.) {
+                1.
+            } else {
+                0.
+            }
+        } else {
+            let level = self.get_variable_level(node, var_count);
+            children
+                .iter()
+                .map(|&child| {
+                    // The levels skipped by the edge can be assigned freely
+                    let skipped =
+                        self.get_variable_level(child, var_count) as i32 - level as i32 - 1;
+                    self.count_satisfying(child, var_count, counts) * 2f64.powi(skipped.max(0))
+                })
+                .sum()
+        };
+        counts.insert(node, count);
+        count
+    }
+    /// Retrieves a node with the given level and children, creating it if it was not created before. Nodes whose children are all the same are skipped, such that newly created parts of the diagram are reduced.
+    fn get_or_create_node(
+        &mut self,
+        level: LevelNo,
+        children: Vec<NodeID>,
+        unique: &mut HashMap<(LevelNo, Vec<NodeID>), NodeID>,
+        mr: &DummyMTBDDManagerRef,
+    ) -> NodeID {
+        if children.iter().all_equal() {
+            if let Some(&child) = children.first() {
+                return child;
+            }
+        }
+        if let Some(&id) = unique.get(&(level, children.clone())) {
+            return id;
+        }
+
+        let id = self.0.keys().next_back().map_or(0, |&id| id + 1);
+        self.add_node_level(id, level, None);
+        for &child in &children {
+            self.add_edge(id, child, mr.clone());
+        }
+        unique.insert((level, children), id);
+        id
+    }
+    /// Retrieves the lowest level of the given nodes that are not terminals
+    fn get_top_level(
+        &self,
+        a_data: &(LevelNo, Vec<NodeID>, bool),
+        b_data: &(LevelNo, Vec<NodeID>, bool),
+    ) -> LevelNo {
+        [a_data, b_data]
+            .iter()
+            .filter(|(_, _, is_terminal)| !is_terminal)
+            .map(|(level, _, _)| *level)
+            .min()
+            .unwrap_or(0)
+    }
+    /// Retrieves the true and false cofactors of the given node with respect to the variable of the given level
+    fn get_level_cofactors(&self, node: NodeID, level: LevelNo) -> (NodeID, NodeID) {
+        match self.get_node_data(node) {
+            // Child index 0 represents the true edge, and index 1 the false edge
+            Some((node_level, children, false)) if node_level == level && children.len() >= 2 => {
+                (children[0], children[1])
+            }
+            _ => (node, node),
+        }
+    }
+    fn find_difference(
+        &self,
+        a: NodeID,
+        b: NodeID,
+        equal: &mut HashSet<(NodeID, NodeID)>,
+        identical: &mut dyn FnMut(NodeID, NodeID) -> bool,
+    ) -> Result<Option<Vec<(LevelNo, bool)>>, String> {
+        // Unknown nodes do not represent any function, hence they can not be compared
+        let get_data = |node| {
+            self.get_node_data(node)
+                .ok_or_else(|| format!("The node {} is not part of the diagram", node))
+        };
+        let (a_data, b_data) = (get_data(a)?, get_data(b)?);
+        if a == b || equal.contains(&(a, b)) || identical(a, b) {
+            return Ok(None);
+        }
+        if let (Some(a_term), Some(b_term)) =
+            (self.get_terminal_value(a), self.get_terminal_value(b))
+        {
+            return Ok(if a_term == b_term {
+                None
+            } else {
+                Some(Vec::new())
+            });
+        }
+
+        let level = self.get_top_level(&a_data, &b_data);
+        let (a_high, a_low) = self.get_level_cofactors(a, level);
+        let (b_high, b_low) = self.get_level_cofactors(b, level);
+        for (value, a_child, b_child) in [(true, a_high, b_high), (false, a_low, b_low)] {
+            let difference = self.find_difference(a_child, b_child, equal, identical)?;
+            if let Some(mut assignment) = difference {
+                assignment.insert(0, (level, value));
+                return Ok(Some(assignment));
+            }
+        }
+        equal.insert((a, b));
+        Ok(None)
+    }
+    fn follow_assignment(
+        &self,
+        node: NodeID,
+        assignment: &HashMap<LevelNo, bool>,
+    ) -> Vec<NodeID> {
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some((level, children, false)) = self.get_node_data(current) {
+            let value = assignment.get(&level).cloned().unwrap_or(false);
+            let Some(&child) = children.get(if value { 0 } else { 1 }) else {
+                break;
+            };
+            path.push(child);
+            current = child;
+        }
+        path
+    }
+    fn find_paths(
+        &self,
+        node: NodeID,
+        target: NodeID,
+        max_count: usize,
+        dead_ends: &mut HashSet<NodeID>,
+        assignment: &mut Vec<(LevelNo, bool)>,
+        path: &mut Vec<NodeID>,
+        out: &mut Vec<(Vec<(LevelNo, bool)>, Vec<NodeID>)>,
+    ) -> bool {
+        if out.len() >= max_count || dead_ends.contains(&node) {
+            return false;
+        }
+        path.push(node);
+        let found = if node == target {
+            out.push((assignment.clone(), path.clone()));
+            true
+        } else if let Some((level, children, false)) = self.get_node_data(node) {
+            let mut found = false;
+            // Child index 0 represents the true edge, and index 1 the false edge
+            for (index, &child) in children.iter().enumerate().take(2) {
+                assignment.push((level, index == 0));
+                found |=
+                    self.find_paths(child, target, max_count, dead_ends, assignment, path, out);
+                assignment.pop();
+            }
+            found
+        } else {
+            false
+        };
+        path.pop();
+        if !found {
+            dead_ends.insert(node);
+        }
+        found
+    }
+    /// Computes the length of the shortest or longest path from the given node to a terminal, storing the length and the child to continue with per node
+    fn get_path_length(
+        &self,
+        node: NodeID,
+        longest: bool,
+        lengths: &mut HashMap<NodeID, (usize, Option<usize>)>,
+    ) -> usize {
+        if let Some(&(length, _)) = lengths.get(&node) {
+            return length;
+        }
+        let result = match self.get_node_data(node) {
+            Some((_, children, false)) if children.len() > 0 => {
+                let child_lengths = children
+                    .iter()
+                    .take(2)
+                    .map(|&child| self.get_path_length(child, longest, lengths))
+                    .enumerate();
+                let (index, length) = if longest {
+                    child_lengths.max_by_key(|&(_, length)| length)
+                } else {
+                    child_lengths.min_by_key(|&(_, length)| length)
+                }
+                .unwrap();
+                (length + 1, Some(index))
+            }
+            _ => (0, None),
+        };
+        lengths.insert(node, result);
+        result.0
+    }
+    /// Computes the expected terminal value of the given node, where the true edge (child index 0) is taken with the probability of its level
+    fn get_expected_value(
+        &self,
+        node: NodeID,
+        probabilities: &HashMap<LevelNo, f32>,
+        values: &mut HashMap<NodeID, f32>,
+    ) -> f32 {
+        if let Some(&value) = values.get(&node) {
+            return value;
+        }
+        let value = match self.get_node_data(node) {
+            Some((_, _, true)) => self.get_terminal_value(node).map_or(0.0, |t| t.0),
+            Some((level, children, false)) if children.len() >= 2 => {
+                let probability = probabilities.get(&level).cloned().unwrap_or(0.5);
+                let high = self.get_expected_value(children[0], probabilities, values);
+                let low = self.get_expected_value(children[1], probabilities, values);
+                probability * high + (1.0 - probability) * low
+            }
+            _ => 0.0,
+        };
+        values.insert(node, value);
+        value
+    }
+    /// Retrieves the terminal with the given value, creating it at the given level if it did not exist before
+    fn get_or_create_terminal(
+        &mut self,
+        value: f32,
+        level: LevelNo,
+        mr: &DummyMTBDDManagerRef,
+    ) -> NodeID {
+        if let Some(terminal) = self.1.get(&MTBDDTerminal(value)) {
+            return terminal.node_id();
+        }
+        let id = self.0.keys().next_back().map_or(0, |&id| id + 1);
+        self.add_node_level(id, level, Some(MTBDDTerminal(value)));
+        self.1.insert(
+            MTBDDTerminal(value),
+            DummyMTBDDEdge::new(Arc::new(id), mr.clone()),
+        );
+        id
+    }
+    /// Constructs the diagram of the given expression, in which the variables are assigned to levels in the given order
+    fn build_expression(
+        &mut self,
+        expression: &ArithmeticExpression,
+        variables: &[String],
+        caches: &mut HashMap<ArithmeticOperator, HashMap<(NodeID, NodeID), NodeID>>,
+        unique: &mut HashMap<(LevelNo, Vec<NodeID>), NodeID>,
+        mr: &DummyMTBDDManagerRef,
+    ) -> NodeID {
+        let terminal_level = variables.len() as LevelNo;
+        match expression {
+            ArithmeticExpression::Constant(value) => {
+                self.get_or_create_terminal(*value, terminal_level, mr)
+            }
+            ArithmeticExpression::Variable(name) => {
+                let level = variables.iter().position(|var| var == name).unwrap_or(0);
+                let one = self.get_or_create_terminal(1., terminal_level, mr);
+                let zero = self.get_or_create_terminal(0., terminal_level, mr);
+                // Child index 0 represents the true edge, and index 1 the false edge
+                self.get_or_create_node(level as LevelNo, vec![one, zero], unique, mr)
+            }
+            ArithmeticExpression::Negate(operand) => {
+                let zero = self.get_or_create_terminal(0., terminal_level, mr);
+                let operand = self.build_expression(operand, variables, caches, unique, mr);
+                let subtract = ArithmeticOperator::Subtract;
+                let cache = caches.entry(subtract).or_default();
+                self.apply_arithmetic(zero, operand, subtract, cache, unique, mr)
+            }
+            ArithmeticExpression::Binary(a, operator, b) => {
+                let a = self.build_expression(a, variables, caches, unique, mr);
+                let b = self.build_expression(b, variables, caches, unique, mr);
+                let cache = caches.entry(*operator).or_default();
+                self.apply_arithmetic(a, b, *operator, cache, unique, mr)
+            }
+        }
+    }
+    /// Combines the values of the given functions for every assignment using the given operator
+    fn apply_arithmetic(
+        &mut self,
+        a: NodeID,
+        b: NodeID,
+        operator: ArithmeticOperator,
+        cache: &mut HashMap<(NodeID, NodeID), NodeID>,
+        unique: &mut HashMap<(LevelNo, Vec<NodeID>), NodeID>,
+        mr: &DummyMTBDDManagerRef,
+    ) -> NodeID {
+        if let Some(&result) = cache.get(&(a, b)) {
+            return result;
+        }
+        let (Some(a_data), Some(b_data)) = (self.get_node_data(a), self.get_node_data(b)) else {
+            return a;
+        };
+
+        let result = if let (Some(a_term), Some(b_term)) =
+            (self.get_terminal_value(a), self.get_terminal_value(b))
+        {
+            // Terminals are placed at the level below the lowest variable of either diagram
+            let level = a_data.0.max(b_data.0);
+            self.get_or_create_terminal(operator.apply(a_term.0, b_term.0), level, mr)
+        } else {
+            let level = self.get_top_level(&a_data, &b_data);
+            let (a_high, a_low) = self.get_level_cofactors(a, level);
+            let (b_high, b_low) = self.get_level_cofactors(b, level);
+            let high = self.apply_arithmetic(a_high, b_high, operator, cache, unique, mr);
+            let low = self.apply_arithmetic(a_low, b_low, operator, cache, unique, mr);
+            self.get_or_create_node(level, vec![high, low], unique, mr)
+        };
+        cache.insert((a, b), result);
+        result
+    }
 }
 
 unsafe impl Manager for DummyMTBDDManager {
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+use seeded_random::{Random, Seed};
+use wasm_bindgen::prelude::*;
+
+use crate::wasm_interface::NodeID;
+
+/// A node of a graph that is built programmatically
+pub struct BuilderNode {
+    /// The level of an inner node, terminals are placed on the level below all other levels
+    pub level: LevelNo,
+    /// The children of the node, indexed by the kind of their edge
+    pub children: Vec<Option<NodeID>>,
+    pub terminal: Option<String>,
+}
+
+/// A decision diagram like graph that is described node by node, such that applications can visualize structures they compute themselves without serializing them to a dump format first. The ids of the builder are local to the builder, the section created from it uses ids that do not collide with other sections of the diagram.
+#[wasm_bindgen]
+pub struct GraphBuilder {
+    levels: Vec<String>,
+    nodes: Vec<BuilderNode>,
+    roots: Vec<(NodeID, String)>,
+}
+
+#[wasm_bindgen]
+impl GraphBuilder {
+    /// Adds a level with the given variable name below all previously added levels, returning its level number
+    pub fn add_level(&mut self, name: String) -> LevelNo {
+        self.levels.push(name);
+        (self.levels.len() - 1) as LevelNo
+    }
+
+    /// Adds an inner node on the given level, returning its id
+    pub fn add_node(&mut self, level: LevelNo) -> Result<NodeID, String> {
+        if level as usize >= self.levels.len() {
+            return Err(format!("Level {} has not been added", level));
+        }
+        self.nodes.push(BuilderNode {
+            level,
+            children: Vec::new(),
+            terminal: None,
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Adds a terminal node with the given label, which is its value for diagrams with numeric terminals, returning its id
+    pub fn add_terminal(&mut self, label: String) -> NodeID {
+        self.nodes.push(BuilderNode {
+            level: LevelNo::MAX,
+            children: Vec::new(),
+            terminal: Some(label),
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge of the given kind, which is the index of the child among the children of the node. For BDDs, kind 0 is the then-edge and kind 1 the else-edge. The child has to be a terminal or lie on a lower level than the parent.
+    pub fn add_edge(&mut self, from: NodeID, to: NodeID, kind: usize) -> Result<(), String> {
+        let to_level = self
+            .nodes
+            .get(to)
+            .ok_or_else(|| format!("Node {} does not exist", to))?
+            .level;
+        let node = self
+            .nodes
+            .get_mut(from)
+            .ok_or_else(|| format!("Node {} does not exist", from))?;
+        if node.terminal.is_some() {
+            return Err(format!("Terminal {} can not have children", from));
+        }
+        if to_level <= node.level {
+            return Err(format!(
+                "Child {} does not lie on a lower level than its parent {}",
+                to, from
+            ));
+        }
+        if node.children.len() <= kind {
+            node.children.resize(kind + 1, None);
+        }
+        node.children[kind] = Some(to);
+        Ok(())
+    }
+
+    /// Marks the given node as a root of the graph, representing the function of the given name
+    pub fn add_root(&mut self, node: NodeID, name: String) -> Result<(), String> {
+        if node >= self.nodes.len() {
+            return Err(format!("Node {} does not exist", node));
+        }
+        self.roots.push((node, name));
+        Ok(())
+    }
+}
+
+impl GraphBuilder {
+    pub fn new() -> GraphBuilder {
+        GraphBuilder {
+            levels: Vec::new(),
+            nodes: Vec::new(),
+            roots: Vec::new(),
+        }
+    }
+    pub fn get_levels(&self) -> &Vec<String> {
+        &self.levels
+    }
+    pub fn get_nodes(&self) -> &Vec<BuilderNode> {
+        &self.nodes
+    }
+    pub fn get_roots(&self) -> &Vec<(NodeID, String)> {
+        &self.roots
+    }
+
+    /// Generates a reduced BDD over the given number of variables with at most the given number of inner nodes, spread evenly over the levels. The structure is fully determined by the seed, such that tests and demos can reproduce diagrams of any size without dump files. The inner nodes without parents become the roots, and the true terminal is the root if there are no inner nodes.
+    pub fn generate_random_bdd(vars: LevelNo, nodes: usize, seed: u32) -> GraphBuilder {
+        let mut rng = Random::from_seed(Seed::unsafe_new(seed as u64));
+        let mut builder = GraphBuilder::new();
+        let falsum = builder.add_terminal("F".to_string());
+        let verum = builder.add_terminal("T".to_string());
+        for var in 0..vars {
+            builder.add_level(format!("x{}", var));
+        }
+
+        // The levels are filled bottom up, such that the children of every node already exist
+        let mut below = vec![falsum, verum];
+        let mut has_parent = HashSet::<NodeID>::new();
+        for level in (0..vars).rev() {
+            let extra = if (level as usize) < nodes % vars as usize { 1 } else { 0 };
+            let count = nodes / vars as usize + extra;
+            let mut used = HashSet::<(NodeID, NodeID)>::new();
+            let mut created = Vec::new();
+            for _ in 0..count {
+                // The children differ and no other node of the level has the same children, such that the diagram stays reduced. The lower levels may not allow for enough distinct nodes, hence a node is left out if no unused pair is found quickly.
+                let children = (0..10).find_map(|_| {
+                    let then_index = rng.range(0, below.len() as u32) as usize;
+                    let offset = 1 + rng.range(0, below.len() as u32 - 1) as usize;
+                    let children = (below[then_index], below[(then_index + offset) % below.len()]);
+                    used.insert(children).then_some(children)
+                });
+                let Some((then_child, else_child)) = children else {
+                    continue;
+                };
+                let node = builder.add_node(level).unwrap();
+                builder.add_edge(node, then_child, 0).unwrap();
+                builder.add_edge(node, else_child, 1).unwrap();
+                has_parent.insert(then_child);
+                has_parent.insert(else_child);
+                created.push(node);
+            }
+            below.extend(created);
+        }
+
+        let roots = builder
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(id, node)| node.terminal.is_none() && !has_parent.contains(id))
+            .map(|(id, node)| (node.level, id))
+            .sorted()
+            .collect_vec();
+        for (index, (_, root)) in roots.into_iter().enumerate() {
+            builder.roots.push((root, format!("f{}", index)));
+        }
+        if builder.roots.is_empty() {
+            builder.roots.push((verum, "f0".to_string()));
+        }
+        builder
+    }
+}
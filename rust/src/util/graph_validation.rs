@@ -0,0 +1,86 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+use crate::wasm_interface::{NodeID, ValidationWarning, ValidationWarningKind};
+
+/// Checks the structure of an imported graph, where the nodes of the graph are retrieved by their level, children and whether they are terminals. The imported nodes that can not be reached from the roots are reported as unreachable.
+pub fn validate_graph(
+    roots: &[NodeID],
+    imported: &HashSet<NodeID>,
+    get_node: impl Fn(NodeID) -> Option<(LevelNo, Vec<NodeID>, bool)>,
+) -> Vec<ValidationWarning> {
+    let mut offenders = HashMap::<ValidationWarningKind, Vec<NodeID>>::new();
+    let mut visited = HashSet::<NodeID>::new();
+    let mut stack = roots.iter().cloned().collect_vec();
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node) {
+            continue;
+        }
+        let Some((level, children, terminal)) = get_node(node) else {
+            continue;
+        };
+        if terminal && !children.is_empty() {
+            offenders
+                .entry(ValidationWarningKind::TerminalWithChildren)
+                .or_default()
+                .push(node);
+        }
+        for child in children {
+            match get_node(child) {
+                None => offenders
+                    .entry(ValidationWarningKind::DanglingEdge)
+                    .or_default()
+                    .push(node),
+                Some((child_level, _, _)) if child_level <= level => offenders
+                    .entry(ValidationWarningKind::ChildNotBelow)
+                    .or_default()
+                    .push(node),
+                _ => {}
+            }
+            stack.push(child);
+        }
+    }
+    let unreachable = imported
+        .iter()
+        .filter(|node| !visited.contains(node))
+        .cloned()
+        .collect_vec();
+    if !unreachable.is_empty() {
+        offenders.insert(ValidationWarningKind::UnreachableNode, unreachable);
+    }
+
+    offenders
+        .into_iter()
+        .sorted_by_key(|&(kind, _)| kind as u32)
+        .map(|(kind, nodes)| {
+            let nodes = nodes.into_iter().sorted().dedup().collect_vec();
+            let description = match kind {
+                ValidationWarningKind::ChildNotBelow => format!(
+                    "{} nodes have children on the same or a higher level",
+                    nodes.len()
+                ),
+                ValidationWarningKind::DanglingEdge => format!(
+                    "{} nodes have edges to nodes that do not exist, which are not drawn",
+                    nodes.len()
+                ),
+                ValidationWarningKind::UnreachableNode => format!(
+                    "{} nodes can not be reached from any root, and are not drawn",
+                    nodes.len()
+                ),
+                ValidationWarningKind::TerminalWithChildren => {
+                    format!("{} terminals have children", nodes.len())
+                }
+                ValidationWarningKind::VariableOrderConflict => {
+                    "The variables are ordered differently than in the other sections".to_string()
+                }
+            };
+            ValidationWarning {
+                kind,
+                nodes,
+                description,
+            }
+        })
+        .collect()
+}
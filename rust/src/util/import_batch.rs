@@ -0,0 +1,51 @@
+use wasm_bindgen::prelude::*;
+
+/// A dump file of an import batch
+pub enum ImportFile {
+    Dddmp(String),
+    Buddy { data: String, vars: Option<String> },
+}
+
+/// A set of dump files that are imported into a single section, such that related functions that were dumped separately can be viewed together. The files share the manager of the diagram, their variable orders are merged by the names of the variables, and the files are numbered in the order in which they were added, which is also the order in which their functions are listed.
+#[wasm_bindgen]
+pub struct ImportBatch {
+    files: Vec<ImportFile>,
+}
+
+#[wasm_bindgen]
+impl ImportBatch {
+    /// Adds the contents of a dddmp dump, returning the number of the file
+    pub fn add_dddmp(&mut self, dddmp: String) -> usize {
+        self.files.push(ImportFile::Dddmp(dddmp));
+        self.files.len() - 1
+    }
+
+    /// Adds the contents of a buddy dump together with its optional variable names, returning the number of the file
+    pub fn add_buddy(&mut self, data: String, vars: Option<String>) -> usize {
+        self.files.push(ImportFile::Buddy { data, vars });
+        self.files.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl Default for ImportBatch {
+    fn default() -> Self {
+        ImportBatch::new()
+    }
+}
+
+impl ImportBatch {
+    pub fn new() -> ImportBatch {
+        ImportBatch { files: Vec::new() }
+    }
+    pub fn get_files(&self) -> &Vec<ImportFile> {
+        &self.files
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::wasm_interface::NodeID;
+
+/// A node as listed in a dump: its id, its level (or the value of a terminal) and its children
+pub type DumpNode = (NodeID, String, Vec<NodeID>);
+
+/// Repairs common defects of the nodes of a dump that lists children before their parents, returning the repaired nodes together with descriptions of the applied fixes:
+/// - A node that reuses the id of a node defined before gets a new id, which subsequent references and the roots refer to
+/// - Edges from nodes to themselves are dropped
+/// - Children that are never defined are added as terminals, as created by the given function
+pub fn repair_dump(
+    nodes: Vec<DumpNode>,
+    roots: &mut Vec<NodeID>,
+    is_terminal: impl Fn(&str, &[NodeID]) -> bool,
+    create_terminal: impl Fn(NodeID) -> (String, Vec<NodeID>),
+) -> (Vec<DumpNode>, Vec<String>) {
+    let mut fixes = Vec::new();
+    let mut next_id = nodes
+        .iter()
+        .flat_map(|(id, _, children)| children.iter().chain(Some(id)))
+        .chain(roots.iter())
+        .max()
+        .map_or(0, |&id| id + 1);
+
+    let mut renamed = HashMap::<NodeID, NodeID>::new();
+    let mut defined = HashSet::<NodeID>::new();
+    let mut referenced = Vec::<NodeID>::new();
+    let mut repaired = Vec::with_capacity(nodes.len());
+    for (id, level, children) in nodes {
+        if is_terminal(&level, &children) {
+            // The children of terminals are placeholders rather than references
+            repaired.push((id, level, children));
+            defined.insert(id);
+            continue;
+        }
+
+        let children = children
+            .into_iter()
+            .map(|child| renamed.get(&child).cloned().unwrap_or(child))
+            .collect_vec();
+        let new_id = if defined.contains(&id) {
+            let new_id = next_id;
+            next_id += 1;
+            renamed.insert(id, new_id);
+            fixes.push(format!("Renumbered the duplicate node {} to {}", id, new_id));
+            new_id
+        } else {
+            id
+        };
+        defined.insert(id);
+
+        let loop_count = children.iter().filter(|&&child| child == new_id).count();
+        if loop_count > 0 {
+            fixes.push(format!("Dropped {} self-loops of node {}", loop_count, id));
+        }
+        let children = children
+            .into_iter()
+            .filter(|&child| child != new_id)
+            .collect_vec();
+        referenced.extend(children.iter().cloned());
+        repaired.push((new_id, level, children));
+    }
+
+    for root in roots.iter_mut() {
+        *root = renamed.get(root).cloned().unwrap_or(*root);
+    }
+
+    let defined = repaired.iter().map(|(id, _, _)| *id).collect::<HashSet<_>>();
+    let missing = referenced
+        .into_iter()
+        .filter(|child| !defined.contains(child))
+        .sorted()
+        .dedup()
+        .collect_vec();
+    let terminals = missing.into_iter().map(|id| {
+        fixes.push(format!("Added a terminal for the undefined node {}", id));
+        let (level, children) = create_terminal(id);
+        (id, level, children)
+    });
+    // Terminals are listed first, since dumps list children before their parents
+    let repaired = terminals.collect_vec().into_iter().chain(repaired).collect();
+    (repaired, fixes)
+}
+
+/// Renumbers the nodes of a dump by a canonical traversal, such that the same diagram obtains the same ids regardless of the order in which the dump lists its nodes and of the ids that it uses. The new ids start at 1 and are assigned in the following order, such that children are numbered before their parents:
+/// - The terminals, ordered by their values
+/// - The nodes reachable from the roots, in depth-first postorder, visiting the roots and children in the order of the dump
+/// - The remaining unreachable nodes, in the order of the dump
+///
+/// The roots are renumbered accordingly, and the nodes are returned ordered by their new ids.
+pub fn canonicalize_dump(
+    nodes: Vec<DumpNode>,
+    roots: &mut Vec<NodeID>,
+    is_terminal: impl Fn(&str, &[NodeID]) -> bool,
+) -> Vec<DumpNode> {
+    let terminal = nodes
+        .iter()
+        .map(|(_, level, children)| is_terminal(level, children))
+        .collect_vec();
+    let mut index_by_id = HashMap::<NodeID, usize>::new();
+    for (index, (id, _, _)) in nodes.iter().enumerate() {
+        index_by_id.entry(*id).or_insert(index);
+    }
+
+    let mut new_ids = HashMap::<NodeID, NodeID>::new();
+    let number = |new_ids: &mut HashMap<NodeID, NodeID>, id: NodeID| {
+        let next_id = new_ids.len() + 1;
+        new_ids.entry(id).or_insert(next_id);
+    };
+
+    for index in (0..nodes.len())
+        .filter(|&index| terminal[index])
+        .sorted_by_key(|&index| &nodes[index].1)
+    {
+        number(&mut new_ids, nodes[index].0);
+    }
+
+    let mut visited = HashSet::<NodeID>::new();
+    for &root in roots.iter() {
+        if !visited.insert(root) {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        while let Some((id, position)) = stack.pop() {
+            // The children of terminals are placeholders rather than references
+            let children: &[NodeID] = match index_by_id.get(&id) {
+                Some(&index) if !terminal[index] => &nodes[index].2[..],
+                _ => &[],
+            };
+            match children.get(position) {
+                Some(&child) => {
+                    stack.push((id, position + 1));
+                    if visited.insert(child) {
+                        stack.push((child, 0));
+                    }
+                }
+                None => number(&mut new_ids, id),
+            }
+        }
+    }
+
+    for (index, (id, _, children)) in nodes.iter().enumerate() {
+        if !terminal[index] {
+            for &child in children {
+                number(&mut new_ids, child);
+            }
+        }
+        number(&mut new_ids, *id);
+    }
+
+    for root in roots.iter_mut() {
+        *root = new_ids.get(root).cloned().unwrap_or(*root);
+    }
+    nodes
+        .into_iter()
+        .zip(terminal)
+        .map(|((id, level, children), terminal)| {
+            let children = if terminal {
+                children
+            } else {
+                children.into_iter().map(|child| new_ids[&child]).collect()
+            };
+            (new_ids[&id], level, children)
+        })
+        .sorted_by_key(|(id, _, _)| *id)
+        .collect()
+}
@@ -1,13 +1,15 @@
-pub mod color;
+pub use oxidd_viz_core::util::*;
+
+pub mod arithmetic_expression;
+pub mod browser;
 pub mod convert_file;
 pub mod dummy_bdd;
 pub mod dummy_mtbdd;
-pub mod free_id_manager;
-pub mod logging;
-pub mod matrix4;
+pub mod graph_builder;
+pub mod graph_validation;
+pub mod import_batch;
+pub mod import_repair;
+pub mod normalization;
 pub mod panic_hook;
-pub mod point;
-pub mod rc_refcell;
-pub mod rectangle;
-pub mod transformation;
-pub mod transition;
+pub mod profiler;
+pub mod variable_registry;
@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+use wasm_bindgen::prelude::*;
+
+use crate::wasm_interface::NodeID;
+
+/// The form into which the graph of a section is brought after importing it
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Normalization {
+    /// The graph is drawn as it was imported
+    Verbatim,
+    /// Structurally identical nodes are merged, but no nodes are removed or added
+    Merge,
+    /// Structurally identical nodes are merged, and edges skipping levels are replaced by chains of redundant nodes, such that every path visits every level
+    QuasiReduce,
+    /// Structurally identical nodes are merged, and redundant nodes are removed by the reduction rules of the diagram type
+    Reduce,
+}
+
+/// The changes that bring an imported graph into a normal form
+pub struct NormalizationPlan {
+    /// The node that replaces every replaced node
+    pub replacements: HashMap<NodeID, NodeID>,
+    /// The number of replaced nodes that were removed as redundant tests rather than merged into an identical node
+    pub removed: usize,
+    /// The redundant nodes to add, given by their new id, their level, the child that all of their edges lead to and their number of edges, where children are listed before their parents
+    pub inserted: Vec<(NodeID, LevelNo, NodeID, usize)>,
+    /// The edges that lead to an inserted node instead, given by their parent, the index of the edge among the edges of the parent and the inserted node, where the parents are given by their ids after replacing nodes
+    pub rewired: Vec<(NodeID, usize, NodeID)>,
+}
+
+/// Plans the normalization of an imported graph, whose nodes are retrieved by their level, children and terminal value. The given rule retrieves the node that replaces a redundant inner node with the given children, which is only applied when reducing, e.g. the common child of a BDD node whose edges all lead to the same child. Inserted nodes are numbered from the given id onwards. Managers of all diagram types apply the plan to their own nodes, such that every importer supports the same normal forms.
+pub fn plan_normalization(
+    imported: &HashSet<NodeID>,
+    get_node: impl Fn(NodeID) -> Option<(LevelNo, Vec<NodeID>, Option<String>)>,
+    normalization: Normalization,
+    redundant: impl Fn(&[NodeID]) -> Option<NodeID>,
+    next_id: NodeID,
+) -> NormalizationPlan {
+    let mut plan = NormalizationPlan {
+        replacements: HashMap::new(),
+        removed: 0,
+        inserted: Vec::new(),
+        rewired: Vec::new(),
+    };
+    if normalization == Normalization::Verbatim {
+        return plan;
+    }
+    let reduce = normalization == Normalization::Reduce;
+    let unique = find_reductions(&mut plan, imported, &get_node, |children| {
+        reduce.then(|| redundant(children)).flatten()
+    });
+    if normalization == Normalization::QuasiReduce {
+        fill_levels(&mut plan, imported, &get_node, unique, next_id);
+    }
+    plan
+}
+
+/// Finds the imported nodes that are replaced when merging structurally identical nodes, which are inner nodes on the same level with the same children or terminals with the same value, and when removing the inner nodes that the given rule considers redundant. Children are compared after replacing them, such that identical subgraphs are merged entirely rather than only at their bottom. Retrieves the remaining nodes by their level, children and terminal value.
+fn find_reductions(
+    plan: &mut NormalizationPlan,
+    imported: &HashSet<NodeID>,
+    get_node: &impl Fn(NodeID) -> Option<(LevelNo, Vec<NodeID>, Option<String>)>,
+    redundant: impl Fn(&[NodeID]) -> Option<NodeID>,
+) -> HashMap<(LevelNo, Vec<NodeID>, Option<String>), NodeID> {
+    let mut unique = HashMap::<(LevelNo, Vec<NodeID>, Option<String>), NodeID>::new();
+    let mut visited = HashSet::<NodeID>::new();
+    // The nodes are visited in depth-first postorder, such that children are merged before their parents. Nodes on cycles are compared by the children that were merged before reaching them.
+    for &start in imported.iter().sorted() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut stack = vec![(start, 0)];
+        while let Some((node, position)) = stack.pop() {
+            let Some((level, children, terminal)) = get_node(node) else {
+                continue;
+            };
+            match children.get(position) {
+                Some(&child) => {
+                    stack.push((node, position + 1));
+                    if imported.contains(&child) && visited.insert(child) {
+                        stack.push((child, 0));
+                    }
+                }
+                None => {
+                    let children = children
+                        .into_iter()
+                        .map(|child| plan.replacements.get(&child).cloned().unwrap_or(child))
+                        .collect_vec();
+                    let skipped = match terminal {
+                        None if !children.is_empty() => redundant(&children),
+                        _ => None,
+                    };
+                    if let Some(child) = skipped {
+                        plan.replacements.insert(node, child);
+                        plan.removed += 1;
+                        continue;
+                    }
+                    let representative = *unique.entry((level, children, terminal)).or_insert(node);
+                    if representative != node {
+                        plan.replacements.insert(node, representative);
+                    }
+                }
+            }
+        }
+    }
+    unique
+}
+
+/// Plans the redundant nodes that replace the edges of the remaining imported nodes that skip levels, such that every path visits every level between its parent and child. Redundant nodes with the same level and child are shared, and existing nodes are reused if they are identical to a redundant node.
+fn fill_levels(
+    plan: &mut NormalizationPlan,
+    imported: &HashSet<NodeID>,
+    get_node: &impl Fn(NodeID) -> Option<(LevelNo, Vec<NodeID>, Option<String>)>,
+    mut unique: HashMap<(LevelNo, Vec<NodeID>, Option<String>), NodeID>,
+    mut next_id: NodeID,
+) {
+    let resolve = |id: NodeID| plan.replacements.get(&id).cloned().unwrap_or(id);
+    let mut rewired = Vec::new();
+    let mut inserted = Vec::new();
+    for &node in imported.iter().filter(|id| !plan.replacements.contains_key(id)).sorted() {
+        let Some((level, children, None)) = get_node(node) else {
+            continue;
+        };
+        let arity = children.len();
+        for (index, child) in children.into_iter().enumerate() {
+            let child = resolve(child);
+            let Some((child_level, _, _)) = get_node(child) else {
+                continue;
+            };
+            // The chain is built from the bottom up, such that children are inserted before their parents
+            let mut target = child;
+            for chain_level in (level + 1..child_level).rev() {
+                let below = target;
+                target = *unique.entry((chain_level, vec![below; arity], None)).or_insert_with(|| {
+                    inserted.push((next_id, chain_level, below, arity));
+                    next_id += 1;
+                    next_id - 1
+                });
+            }
+            if target != child {
+                rewired.push((node, index, target));
+            }
+        }
+    }
+    plan.inserted = inserted;
+    plan.rewired = rewired;
+}
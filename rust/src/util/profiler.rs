@@ -0,0 +1,132 @@
+// Measures the durations of the phases of every frame, such that performance issues on the machines of users can be diagnosed from the profile attached to a bug report. The measurements are only taken when the `profiling` feature is enabled, and are free otherwise.
+
+#[cfg(feature = "profiling")]
+use std::{cell::RefCell, collections::VecDeque};
+
+#[cfg(feature = "profiling")]
+use super::clock::now;
+use super::json::JsonValue;
+
+/// The number of frames that are kept, older frames are dropped first
+#[cfg(feature = "profiling")]
+const MAX_FRAMES: usize = 120;
+
+/// A phase that was measured, relative to the start of its frame
+#[cfg(feature = "profiling")]
+struct Span {
+    name: &'static str,
+    start: f64,
+    duration: f64,
+}
+
+#[cfg(feature = "profiling")]
+struct Frame {
+    start: f64,
+    spans: Vec<Span>,
+}
+
+#[cfg(feature = "profiling")]
+struct ProfileState {
+    /// The frame that is currently being measured, which is started by its first span
+    current: Option<Frame>,
+    frames: VecDeque<Frame>,
+}
+
+#[cfg(feature = "profiling")]
+thread_local! {
+    static PROFILE_STATE: RefCell<ProfileState> = RefCell::new(ProfileState {
+        current: None,
+        frames: VecDeque::new(),
+    });
+}
+
+/// Runs the given phase, and records its duration under the given name in the current frame
+#[cfg(feature = "profiling")]
+pub fn span<T>(name: &'static str, phase: impl FnOnce() -> T) -> T {
+    let start = now();
+    let out = phase();
+    let duration = now() - start;
+    PROFILE_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let frame = state.current.get_or_insert_with(|| Frame {
+            start,
+            spans: Vec::new(),
+        });
+        frame.spans.push(Span {
+            name,
+            start: start - frame.start,
+            duration,
+        });
+    });
+    out
+}
+#[cfg(not(feature = "profiling"))]
+pub fn span<T>(_name: &'static str, phase: impl FnOnce() -> T) -> T {
+    phase()
+}
+
+/// Completes the current frame, such that the following spans are recorded in a new frame
+pub fn end_frame() {
+    #[cfg(feature = "profiling")]
+    PROFILE_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let Some(frame) = state.current.take() else {
+            return;
+        };
+        if state.frames.len() >= MAX_FRAMES {
+            state.frames.pop_front();
+        }
+        state.frames.push_back(frame);
+    });
+}
+
+/// Describes the recorded frames as JSON, from the oldest to the newest, together with whether profiling is enabled at all
+pub fn get_profile() -> String {
+    #[cfg(feature = "profiling")]
+    let frames = PROFILE_STATE.with(|state| {
+        let state = state.borrow();
+        state
+            .frames
+            .iter()
+            .chain(state.current.iter())
+            .map(|frame| {
+                JsonValue::Object(vec![
+                    ("start".into(), JsonValue::Number(frame.start)),
+                    (
+                        "spans".into(),
+                        JsonValue::Array(
+                            frame
+                                .spans
+                                .iter()
+                                .map(|span| {
+                                    JsonValue::Object(vec![
+                                        ("name".into(), JsonValue::String(span.name.into())),
+                                        ("start".into(), JsonValue::Number(span.start)),
+                                        ("duration".into(), JsonValue::Number(span.duration)),
+                                    ])
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ])
+            })
+            .collect()
+    });
+    #[cfg(not(feature = "profiling"))]
+    let frames = Vec::new();
+
+    JsonValue::Object(vec![
+        ("enabled".into(), JsonValue::Bool(cfg!(feature = "profiling"))),
+        ("frames".into(), JsonValue::Array(frames)),
+    ])
+    .to_string()
+}
+
+pub fn clear_profile() {
+    #[cfg(feature = "profiling")]
+    PROFILE_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.current = None;
+        state.frames.clear();
+    });
+}
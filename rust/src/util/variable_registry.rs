@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use oxidd::LevelNo;
+
+/// The variables of all sections imported into a diagram, which are identified by their names and numbered by a canonical order shared by these sections. Unnamed variables are never shared.
+#[derive(Clone, Default)]
+pub struct VariableRegistry {
+    /// The names of the variables, ordered by their canonical levels
+    names: Vec<String>,
+    levels: HashMap<String, LevelNo>,
+}
+
+impl VariableRegistry {
+    pub fn new() -> VariableRegistry {
+        VariableRegistry::default()
+    }
+
+    /// Registers the variables of the levels with the given labels, appending the variables that are not known yet to the canonical order, and retrieves the canonical level of every given level
+    pub fn register(&mut self, labels: &[String]) -> Vec<LevelNo> {
+        labels
+            .iter()
+            .map(|label| {
+                if let Some(&level) = self.levels.get(label) {
+                    return level;
+                }
+                let level = self.names.len() as LevelNo;
+                self.names.push(label.clone());
+                if !label.is_empty() {
+                    self.levels.insert(label.clone(), level);
+                }
+                level
+            })
+            .collect()
+    }
+
+    /// Retrieves the canonical level of the variable with the given name
+    pub fn get_level(&self, name: &str) -> Option<LevelNo> {
+        self.levels.get(name).cloned()
+    }
+
+    /// Retrieves the name of the variable on the given canonical level
+    pub fn get_name(&self, level: LevelNo) -> Option<&String> {
+        self.names.get(level as usize)
+    }
+
+    /// Retrieves the names of all variables, ordered by their canonical levels
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// Checks whether the given canonical levels of consecutive levels keep their order, such that nodes can be moved to their canonical levels without changing the structure of the diagram
+pub fn is_ordered(levels: &[LevelNo]) -> bool {
+    levels.iter().tuple_windows().all(|(a, b)| a < b)
+}
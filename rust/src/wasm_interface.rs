@@ -2,14 +2,34 @@ use std::rc::Rc;
 
 use crate::{
     configuration::configuration_object::AbstractConfigurationObject,
-    types::util::graph_structure::graph_manipulators::node_presence_adjuster::PresenceRemainder,
-    util::rectangle::Rectangle,
+    interface,
+    types::{
+        qdd::edge_weights::{Complex, Matrix},
+        util::{
+            drawing::{
+                annotations::AnnotationKind,
+                drawer::ViewID,
+                legend::{Legend, LegendSymbol},
+                pagination::PaperSize,
+                renderer::EdgeStyle,
+            },
+            graph_structure::graph_manipulators::node_presence_adjuster,
+        },
+    },
+    util::{
+        color::Color, graph_builder::GraphBuilder, import_batch::ImportBatch, logging,
+        normalization::Normalization, point::Point, profiler, rectangle::Rectangle,
+        viz_error::VizError,
+    },
 };
 
 use super::traits::{Diagram, DiagramSection, DiagramSectionDrawer};
+#[cfg(feature = "webgpu")]
+use crate::types::util::drawing::renderers::webgpu_renderer::WebgpuContext;
 use itertools::Itertools;
+use oxidd::LevelNo;
 use wasm_bindgen::prelude::*;
-use web_sys::HtmlCanvasElement;
+use web_sys::{HtmlCanvasElement, WebGl2RenderingContext, WebGlFramebuffer};
 
 #[wasm_bindgen]
 pub struct DiagramBox(Box<dyn Diagram>);
@@ -22,26 +42,178 @@ impl DiagramBox {
 // Mirror Diagram trait in terms of interface, but using non-dynamic structs
 #[wasm_bindgen()]
 impl DiagramBox {
-    pub fn create_section_from_dddmp(&mut self, dddmp: String) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(self.0.create_section_from_dddmp(dddmp)?))
+    /// Sets whether dumps imported afterwards are repaired rather than drawn as they are, in which case the applied fixes are reported by the created sections
+    pub fn set_import_repair(&mut self, repair: bool) {
+        self.0.set_import_repair(repair)
+    }
+    /// Sets whether the nodes of dumps imported afterwards are numbered by a canonical traversal rather than by the ids of the dump, such that states saved for a dump remain valid for the same diagram dumped with its nodes in a different order
+    pub fn set_import_canonical_ids(&mut self, canonical: bool) {
+        self.0.set_import_canonical_ids(canonical)
+    }
+    /// Sets whether the graphs imported afterwards are kept verbatim, or fully reduced, quasi-reduced or only stripped of duplicate nodes by the rules of the diagram type, in which case the created sections report the size of the graph before and after the normalization
+    pub fn set_import_normalization(&mut self, normalization: Normalization) {
+        self.0.set_import_normalization(normalization)
+    }
+    /// Sets whether the levels of sections imported afterwards skip the levels without any node, such that dumps with sparse levels are not drawn with many empty layers
+    pub fn set_level_compaction(&mut self, compact: bool) {
+        self.0.set_level_compaction(compact)
+    }
+    /// Sets whether sections imported afterwards number their levels by the variable order shared with the other sections imported this way, such that the levels of the same variables line up when these sections are shown side by side
+    pub fn set_level_unification(&mut self, unify: bool) {
+        self.0.set_level_unification(unify)
+    }
+    /// Retrieves the canonical level of the variable with the given name among all sections imported into this diagram
+    pub fn get_variable_level(&self, name: &str) -> Option<u32> {
+        self.0.get_variables().get_level(name)
+    }
+    /// Retrieves the names of all variables imported into this diagram, ordered by their canonical levels
+    pub fn get_variable_names(&self) -> Vec<String> {
+        self.0.get_variables().get_names().to_vec()
+    }
+    /// Creates a section of the functions of a dddmp dump, throwing a `ParseError` if the dump is malformed
+    pub fn create_section_from_dddmp(
+        &mut self,
+        dddmp: String,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || self.0.create_section_from_dddmp(dddmp))
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
     }
     pub fn create_section_from_other(
         &mut self,
         data: String,
         vars: Option<String>,
-    ) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(
-            self.0.create_section_from_other(data, vars)?,
-        ))
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || self.0.create_section_from_other(data, vars))
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Creates a single section of the functions of all dumps of the given batch, whose variable orders are merged by the names of the variables, throwing a `ParseError` naming the file if one of the dumps is malformed
+    pub fn create_section_from_batch(
+        &mut self,
+        batch: &ImportBatch,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || self.0.create_section_from_batch(batch))
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Creates a section of the function described by an arithmetic expression over Boolean variables such as `2*a + 3*b - c`, failing with a description of the problem if the expression is invalid
+    pub fn create_section_from_expression(
+        &mut self,
+        expression: String,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || {
+            self.0.create_section_from_expression(expression)
+        })
+        .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Creates a section of the zero-suppressed diagram of a family of sets, given as one set per line with items separated by spaces, where `{}` denotes the empty set
+    pub fn create_section_from_family(
+        &mut self,
+        family: String,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || self.0.create_section_from_family(family))
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Creates a section of the graph that was built node by node, failing with a description of the problem if the graph does not fit this diagram type
+    pub fn create_section_from_graph(
+        &mut self,
+        graph: &GraphBuilder,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = profiler::span("parse", || self.0.create_section_from_graph(graph))
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
     }
     pub fn create_section_from_ids(
         &self,
         ids: &[NodeID],
         section: &DiagramSectionBox,
-    ) -> Option<DiagramSectionBox> {
-        Some(DiagramSectionBox(self.0.create_section_from_ids(
-            &ids.iter().map(|&id| (id, &section.0)).collect_vec(),
-        )?))
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = self
+            .0
+            .create_section_from_ids(&ids.iter().map(|&id| (id, &section.0)).collect_vec())
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Creates a section of the given nodes that initially only reveals the nodes within the given depth and number of nodes from the roots
+    pub fn create_preview_section_from_ids(
+        &self,
+        ids: &[NodeID],
+        section: &DiagramSectionBox,
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Result<DiagramSectionBox, JsValue> {
+        let section = self
+            .0
+            .create_preview_section_from_ids(
+                &ids.iter().map(|&id| (id, &section.0)).collect_vec(),
+                max_depth,
+                max_nodes,
+            )
+            .map_err(to_js_error)?;
+        Ok(DiagramSectionBox(section))
+    }
+    /// Checks whether the given (source) nodes represent the same functions, where both lists are compared pairwise. Throws if the lists differ in length or contain unknown nodes.
+    pub fn check_equivalence(
+        &self,
+        nodes_a: &[NodeID],
+        nodes_b: &[NodeID],
+    ) -> Result<EquivalenceData, JsValue> {
+        self.0
+            .check_equivalence(nodes_a, nodes_b)
+            .map_err(to_js_error)
+    }
+    /// Retrieves up to max_count assignments for which the given (source) root evaluates to the given terminal
+    pub fn find_path_to_terminal(
+        &self,
+        root: NodeID,
+        terminal: String,
+        max_count: usize,
+    ) -> Vec<PathData> {
+        self.0.find_path_to_terminal(root, terminal, max_count)
+    }
+    /// Retrieves the shortest and longest paths from the given (source) root to any terminal
+    pub fn get_path_metrics(&self, root: NodeID) -> PathMetricsData {
+        self.0.get_path_metrics(root)
+    }
+
+    /// Outlines the inner nodes of the given drawer in a color per variable, which is the same in all drawers of this diagram, or removes the outlines. This is shown after the next layout.
+    pub fn set_variable_coloring(&self, drawer: &mut DiagramSectionDrawerBox, enabled: bool) {
+        self.0.set_variable_coloring(&mut *drawer.0, enabled)
+    }
+
+    /** Linked views */
+    /// Retrieves the local nodes of the `to` drawer that correspond to the given local nodes of the `from` drawer, such that selections can be shared between sections of this diagram
+    pub fn link_nodes(
+        &self,
+        nodes: &[NodeID],
+        from: &DiagramSectionDrawerBox,
+        to: &DiagramSectionDrawerBox,
+    ) -> Vec<NodeID> {
+        self.0.link_nodes(nodes, &*from.0, &*to.0)
+    }
+    /// Selects and hovers the nodes of the `to` drawer that correspond to the given selected and hovered local nodes of the `from` drawer. Both drawers must be distinct objects.
+    pub fn synchronize_selection(
+        &self,
+        from: &DiagramSectionDrawerBox,
+        selected_ids: &[NodeID],
+        hovered_ids: &[NodeID],
+        to: &mut DiagramSectionDrawerBox,
+    ) {
+        let selected = self.0.link_nodes(selected_ids, &*from.0, &*to.0);
+        let hovered = self.0.link_nodes(hovered_ids, &*from.0, &*to.0);
+        to.0.set_selected_nodes(&selected, &hovered);
+    }
+    /// Computes how to translate the `right` drawer such that it is shown next to the `left` drawer with their shared levels aligned, together with the correspondence edges between matching nodes
+    pub fn get_comparison_layout(
+        &self,
+        left: &DiagramSectionDrawerBox,
+        right: &DiagramSectionDrawerBox,
+        gap: f32,
+    ) -> ComparisonData {
+        self.0.get_comparison_layout(&*left.0, &*right.0, gap)
     }
 }
 
@@ -59,36 +231,299 @@ impl DiagramSectionBox {
     pub fn create_drawer(&self, canvas: HtmlCanvasElement) -> DiagramSectionDrawerBox {
         DiagramSectionDrawerBox(self.0.create_drawer(canvas))
     }
+    /// Creates a drawer that initially only shows the roots, such that gigantic diagrams can be explored by expanding nodes
+    pub fn create_lazy_drawer(&self, canvas: HtmlCanvasElement) -> DiagramSectionDrawerBox {
+        DiagramSectionDrawerBox(self.0.create_lazy_drawer(canvas))
+    }
+    /// Retrieves the structural problems found while importing this section, which explain why a file may be drawn unexpectedly
+    pub fn get_warnings(&self) -> Vec<ValidationWarning> {
+        self.0.get_warnings()
+    }
+    /// Retrieves descriptions of the fixes applied to the imported dump, such as renumbered duplicate nodes
+    pub fn get_repairs(&self) -> Vec<String> {
+        self.0.get_repairs()
+    }
+    /// Retrieves how much the graph of this section was changed by normalizing it after importing it, e.g. how many nodes were shared between the files of an import batch, if it was normalized
+    pub fn get_reduction(&self) -> Option<ReductionData> {
+        self.0.get_reduction()
+    }
+    /// Retrieves the level in the dump of every level of this section, which is empty unless levels were compacted on import
+    pub fn get_true_levels(&self) -> Vec<u32> {
+        self.0.get_true_levels()
+    }
+    /// Retrieves the data of every level, such as its variable name and the number of nodes on it, from which a panel of the variables can be built
+    pub fn get_level_info(&self) -> Vec<LevelInfoData> {
+        self.0.get_level_info()
+    }
+    /// Renames the given level for the drawers and sections created from this section afterwards
+    pub fn set_level_label(&mut self, level: u32, name: String) {
+        self.0.set_level_label(level, name)
+    }
+
+    /** Operations */
+    /// Creates a new section in which the variable of the given level is fixed to the given value, such that it can be shown next to this section
+    pub fn preview_cofactor(&self, var: u32, value: bool) -> Option<DiagramSectionBox> {
+        Some(DiagramSectionBox(self.0.preview_cofactor(var, value)?))
+    }
+    /// Creates a new section in which the variables of the given levels are quantified, such that it can be shown next to this section. Fails if the terminals of this section can not be combined by the quantifier, e.g. BDD terminals that do not name a truth value.
+    pub fn preview_quantify(
+        &self,
+        vars: &[u32],
+        quantifier: Quantifier,
+    ) -> Option<DiagramSectionBox> {
+        Some(DiagramSectionBox(self.0.preview_quantify(vars, quantifier)?))
+    }
 }
 #[wasm_bindgen]
 pub struct DiagramSectionDrawerBox(Box<dyn DiagramSectionDrawer>);
+#[cfg(feature = "webgpu")]
+#[wasm_bindgen]
+impl DiagramSectionDrawerBox {
+    /// Draws the main canvas through the given webgpu context from now on, which has to be requested for the canvas that the drawer was created with, see `request_webgpu_context`
+    pub fn use_webgpu(&mut self, context: WebgpuContext) {
+        self.0.use_webgpu(context);
+    }
+}
 #[wasm_bindgen]
 impl DiagramSectionDrawerBox {
+    /// Renders the diagram, which completes the profiled frame
     pub fn render(&mut self, time: u32) -> () {
-        self.0.render(time);
+        profiler::span("render", || self.0.render(time));
+        profiler::end_frame();
     }
-    pub fn layout(&mut self, time: u32) -> () {
-        self.0.layout(time);
+    /// Lays out the diagram again, throwing an error if this failed, after which `recover` can be used to restore a consistent state
+    pub fn layout(&mut self, time: u32) -> Result<(), JsValue> {
+        profiler::span("layout", || self.0.layout(time)).map_err(to_js_error)
     }
-    pub fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> () {
-        self.0.set_transform(width, height, x, y, scale);
+    /// Returns whether the diagram has to be laid out again, because subfunctions were summarized or restored due to the zoom
+    pub fn set_transform(&mut self, width: u32, height: u32, x: f32, y: f32, scale: f32) -> bool {
+        self.0.set_transform(width, height, x, y, scale)
+    }
+    pub fn set_semantic_zoom(&mut self, threshold: f32) -> bool {
+        self.0.set_semantic_zoom(threshold)
+    }
+    /// Summarizes runs of adjacent groups of layers with more groups than the budget while zoomed out further than the threshold, returning whether the diagram has to be laid out again
+    pub fn set_layer_summaries(&mut self, budget: usize, threshold: f32) -> bool {
+        self.0.set_layer_summaries(budget, threshold)
+    }
+    /// Limits the geometry of layers with more groups than the threshold to the groups around the camera
+    pub fn set_layer_virtualization(&mut self, threshold: Option<usize>) {
+        self.0.set_layer_virtualization(threshold);
+    }
+    /// Sets the animations of groups that are inserted or removed by subsequent layouts, which may be the same to animate both alike
+    pub fn set_appearance_animations(
+        &mut self,
+        entrance: AppearanceAnimation,
+        exit: AppearanceAnimation,
+    ) {
+        self.0.set_appearance_animations(entrance.into(), exit.into());
     }
     pub fn set_step(&mut self, step: i32) -> Option<StepData> {
         self.0.set_step(step)
     }
 
+    /** Views */
+    /// Adds a view of the diagram on another canvas, such as an overview next to a detailed view. The view shares the layout and selection with this drawer, but has its own transform.
+    pub fn add_view(&mut self, canvas: HtmlCanvasElement) -> ViewID {
+        self.0.add_view(canvas)
+    }
+    /// Adds a view that renders into a framebuffer of the given context, such that the diagram can be composited into a larger webgl application. The framebuffer is cleared to transparent before every render of the view, and the default framebuffer is used if none is given.
+    pub fn add_framebuffer_view(
+        &mut self,
+        context: WebGl2RenderingContext,
+        framebuffer: Option<WebGlFramebuffer>,
+        width: u32,
+        height: u32,
+    ) -> ViewID {
+        self.0
+            .add_framebuffer_view(context, framebuffer, width, height)
+    }
+    pub fn remove_view(&mut self, view: ViewID) -> bool {
+        self.0.remove_view(view)
+    }
+    pub fn set_view_transform(
+        &mut self,
+        view: ViewID,
+        width: u32,
+        height: u32,
+        x: f32,
+        y: f32,
+        scale: f32,
+    ) {
+        self.0.set_view_transform(view, width, height, x, y, scale);
+    }
+    pub fn render_view(&mut self, view: ViewID, time: u32) {
+        self.0.render_view(view, time);
+    }
+    /// Coordinates in screen space (-0.5 to 0.5) of the given view, retrieves the nodes in the given area
+    pub fn get_view_nodes(
+        &self,
+        view: ViewID,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        max_group_expansion: usize,
+    ) -> Vec<NodeID> {
+        self.0
+            .get_view_nodes(view, Rectangle::new(x, y, width, height), max_group_expansion)
+    }
+
+    /** History */
+    /// Retrieves the states the diagram went through as its graph changed, the last of which is the current state
+    pub fn get_history(&self) -> Vec<HistoryEntryData> {
+        self.0.get_history()
+    }
+    /// Shows the state at the given index of the history, or returns to the current state if no index is given, returning whether an earlier state is shown. The next layout always returns to the current state.
+    pub fn scrub_history(&mut self, index: Option<usize>) -> bool {
+        self.0.scrub_history(index)
+    }
+
     /** Grouping */
     pub fn set_group(&mut self, from: Vec<TargetID>, to: NodeGroupID) -> bool {
-        self.0.set_group(from, to)
+        self.0.set_group(from.into_iter().map_into().collect(), to)
     }
     pub fn create_group(&mut self, from: Vec<TargetID>) -> NodeGroupID {
-        self.0.create_group(from)
+        self.0.create_group(from.into_iter().map_into().collect())
+    }
+    pub fn set_group_label(&mut self, group: NodeGroupID, name: String, note: String) -> bool {
+        self.0.set_group_label(group, name, note)
+    }
+    /// Renames the given level, such as to give the variables of an imported dump meaningful names. The name is not stored in the section this drawer was created from, which has to be renamed separately to keep the name for new drawers.
+    pub fn set_level_label(&mut self, level: u32, name: String) {
+        self.0.set_level_label(level, name)
+    }
+    /// Sets whether the labels of the layers include the level that the dump assigned to them, if levels were compacted on import
+    pub fn set_true_level_labels(&mut self, show: bool) {
+        self.0.set_true_level_labels(show)
+    }
+    /// Sets the template of the labels of inner nodes such as `{var}@{level} (#{id})`, where `{func_names}` lists the functions rooted in a node, or removes the labels if no template is given
+    pub fn set_node_label_template(&mut self, template: Option<String>) {
+        self.0.set_node_label_template(template)
+    }
+    /// Sets the value of every given source node, which is drawn as a heat map overlay normalized between the smallest and largest value after the next layout, or removes the overlay if no nodes are given
+    pub fn set_node_metric(&mut self, nodes: &[NodeID], values: &[f64]) {
+        self.0
+            .set_node_metric(nodes.iter().cloned().zip(values.iter().cloned()).collect())
+    }
+    pub fn expand_group(&mut self, group: NodeGroupID, depth: u32) -> Vec<NodeGroupID> {
+        self.0.expand_group(group, depth)
+    }
+    pub fn expand_node(&mut self, node: NodeID, depth: usize) -> Vec<NodeGroupID> {
+        self.0.expand_node(node, depth)
+    }
+    pub fn group_shared_subfunctions(
+        &mut self,
+        min_occurrences: usize,
+        min_size: usize,
+    ) -> Vec<NodeGroupID> {
+        self.0.group_shared_subfunctions(min_occurrences, min_size)
+    }
+    /// Groups the nodes on the levels whose variable name matches the given regular expression, such as `^state_` to separate the state from the input variables of a transition relation
+    pub fn group_by_level_pattern(
+        &mut self,
+        pattern: String,
+    ) -> Result<Option<NodeGroupID>, String> {
+        self.0.group_by_level_pattern(pattern)
+    }
+    pub fn set_group_parent(&mut self, group: NodeGroupID, parent: Option<NodeGroupID>) -> bool {
+        self.0.set_group_parent(group, parent)
+    }
+    pub fn enter_group(&mut self, group: NodeGroupID) -> bool {
+        self.0.enter_group(group)
+    }
+    pub fn exit_group(&mut self) -> bool {
+        self.0.exit_group()
+    }
+    pub fn get_entered_groups(&self) -> Vec<NodeGroupID> {
+        self.0.get_entered_groups()
+    }
+    pub fn collapse_low(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        self.0.collapse_low(node)
+    }
+    pub fn collapse_high(&mut self, node: NodeID) -> Option<NodeGroupID> {
+        self.0.collapse_high(node)
     }
 
     /** Tools */
+    pub fn set_edge_visibility(&mut self, edge_type: i32, visible: bool, relayout: bool) {
+        self.0.set_edge_visibility(edge_type, visible, relayout);
+    }
     pub fn split_edges(&mut self, nodes: &[NodeID], fully: bool) {
         self.0.split_edges(nodes, fully);
     }
+    /// Retrieves the nodes whose low and high children are identical, which are drawn in a distinct color
+    pub fn get_redundant_nodes(&mut self) -> Vec<NodeID> {
+        self.0.get_redundant_nodes()
+    }
+    /// Sets whether the nodes whose children are identical are bypassed in the view
+    pub fn set_redundant_bypass(&mut self, bypass: bool) {
+        self.0.set_redundant_bypass(bypass);
+    }
+    /// Sets whether stubs with counts mark where hidden nodes connect to shown nodes
+    pub fn set_hidden_markers(&mut self, enabled: bool) {
+        self.0.set_hidden_markers(enabled);
+    }
+    /// Sets whether edges to hidden nodes are drawn as stubs ending in an ellipsis
+    pub fn set_hidden_edge_stubs(&mut self, enabled: bool) {
+        self.0.set_hidden_edge_stubs(enabled);
+    }
+    /// Sets whether runs of levels without visible nodes are collapsed into a band marked by a zig-zag line
+    pub fn set_empty_layer_elision(&mut self, enabled: bool) {
+        self.0.set_empty_layer_elision(enabled);
+    }
+    /// Hides, shows, duplicates, groups or splits all selected nodes at once, returning the groups created by the operation
+    pub fn apply_to_selection(&mut self, operation: SelectionOperation) -> Vec<NodeGroupID> {
+        self.0.apply_to_selection(operation)
+    }
+    /// Hides, shows or duplicates all nodes matching the predicate in a single call, returning the number of adjusted nodes
+    pub fn set_presence_where(
+        &mut self,
+        predicate: PresencePredicate,
+        remainder: PresenceRemainder,
+    ) -> Result<usize, String> {
+        self.0.set_presence_where(predicate.0, remainder.into())
+    }
+    /// Duplicates the inner nodes with multiple but fewer than `threshold` incoming edges, where a threshold of at most 2 removes the duplication
+    pub fn set_duplication_threshold(&mut self, threshold: usize) -> DuplicationData {
+        self.0.set_duplication_threshold(threshold)
+    }
+    /// Merges the terminals per value range, where the ranges are formed by consecutive boundaries (e.g. 0, 0.1, 0.2 forms [0, 0.1) and [0.1, 0.2)), and an empty list removes the merging
+    pub fn set_terminal_bins(&mut self, bounds: Vec<f32>) {
+        self.0.set_terminal_bins(bounds);
+    }
+    /// Keeps the given roots in the given order from left to right during layouts, or removes this constraint if no roots are given
+    pub fn set_root_order(&mut self, roots: &[NodeID]) {
+        self.0.set_root_order(roots);
+    }
+    /// Keeps the roots ordered by name or size during layouts, returning the roots in this order
+    pub fn sort_roots(&mut self, sorting: RootSorting) -> Vec<NodeID> {
+        self.0.sort_roots(sorting)
+    }
+    /// Keeps the groups of the given nodes at their current horizontal positions during subsequent layouts, or releases them if pinned is false
+    pub fn pin_nodes(&mut self, nodes: &[NodeID], pinned: bool) {
+        self.0.pin_nodes(nodes, pinned);
+    }
+    /// Keeps the groups of the given terminals at the given side of the bottom layer during subsequent layouts, until they are released using pin_nodes
+    pub fn pin_terminals(&mut self, terminals: &[NodeID], column: HorizontalAlignment) {
+        self.0.pin_terminals(terminals, column.into());
+    }
+    /// Moves the given group by the given offset in diagram coordinates without performing a layout, which is kept until the next layout unless the group contains pinned nodes
+    pub fn move_group(&mut self, group: NodeGroupID, dx: f32, dy: f32) {
+        self.0.move_group(group, dx, dy);
+    }
+    /// Aligns the groups of the given nodes horizontally, where they are kept at these positions during subsequent layouts until reset
+    pub fn align_groups(&mut self, nodes: &[NodeID], alignment: HorizontalAlignment) {
+        self.0.align_groups(nodes, alignment.into());
+    }
+    /// Spaces the groups of the given nodes evenly between the leftmost and rightmost of these groups, where they are kept at these positions during subsequent layouts until reset
+    pub fn distribute_groups(&mut self, nodes: &[NodeID]) {
+        self.0.distribute_groups(nodes);
+    }
+    /// Lets the layout position the groups of the given nodes again after they were aligned or distributed, which takes effect on the next layout
+    pub fn reset_group_positions(&mut self, nodes: &[NodeID]) {
+        self.0.reset_group_positions(nodes);
+    }
 
     /** Node interaction */
     /// Coordinates in screen space (-0.5 to 0.5), not in world space. Additionally the max_group_expansion should be provided for determining the maximum number of nodes to select for every given group
@@ -103,9 +538,124 @@ impl DiagramSectionDrawerBox {
         self.0
             .get_nodes(Rectangle::new(x, y, width, height), max_group_expansion)
     }
+    /// Coordinates in screen space (-0.5 to 0.5), retrieves the nodes, group boxes and edges in the given area, such that edges can be hovered and given context menus
+    pub fn get_elements(
+        &self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        max_group_expansion: usize,
+    ) -> ElementsData {
+        self.0
+            .get_elements(Rectangle::new(x, y, width, height), max_group_expansion)
+    }
     pub fn set_selected_nodes(&mut self, selected_ids: &[NodeID], hovered_ids: &[NodeID]) {
         self.0.set_selected_nodes(selected_ids, hovered_ids);
     }
+    /// Saves the selected nodes under the given name, such that they can be selected again during the analysis
+    pub fn save_selection(&mut self, name: String) {
+        self.0.save_selection(name)
+    }
+    /// Selects the nodes saved under the given name, returning them such that the selection of the application can be updated accordingly
+    pub fn apply_selection(&mut self, name: String) -> Option<Vec<NodeID>> {
+        self.0.apply_selection(name)
+    }
+    pub fn get_selection_names(&self) -> Vec<String> {
+        self.0.get_selection_names()
+    }
+    /// Sets the style overrides of the edges of the given type between two groups, where the color consists of rgb values between 0 and 1, and omitted values use the edge type's defaults
+    pub fn set_edge_style(
+        &mut self,
+        from: NodeGroupID,
+        to: NodeGroupID,
+        edge_type: i32,
+        color: Option<Vec<f32>>,
+        width: Option<f32>,
+        label: Option<String>,
+    ) {
+        let color = color
+            .filter(|color| color.len() == 3)
+            .map(|color| Color(color[0], color[1], color[2]));
+        self.0.set_edge_style(
+            (from, to, edge_type),
+            EdgeStyle {
+                color,
+                width,
+                label: label.filter(|label| !label.is_empty()),
+            },
+        );
+    }
+    /// Highlights the edges given by their source groups, target groups and edge types
+    pub fn set_selected_edges(
+        &mut self,
+        from: &[NodeGroupID],
+        to: &[NodeGroupID],
+        edge_types: &[i32],
+    ) {
+        let edges = from
+            .iter()
+            .zip(to)
+            .zip(edge_types)
+            .map(|((&from, &to), &edge_type)| (from, to, edge_type))
+            .collect_vec();
+        self.0.set_selected_edges(&edges);
+    }
+    /// Sets the complex weights of the edges given by their source nodes, target nodes and edge types, which QDDs show according to their edge configuration
+    pub fn set_edge_weights(
+        &mut self,
+        from: &[NodeID],
+        to: &[NodeID],
+        edge_types: &[i32],
+        re: &[f32],
+        im: &[f32],
+    ) {
+        let weights = from
+            .iter()
+            .zip(to)
+            .zip(edge_types)
+            .zip(re.iter().zip(im))
+            .map(|(((&from, &to), &edge_type), (&re, &im))| {
+                ((from, to, edge_type), Complex { re, im })
+            })
+            .collect();
+        self.0.set_edge_weights(weights);
+    }
+    /// Reconstructs the matrix or vector represented by the given node, truncated to at most max_dim rows and columns
+    pub fn get_matrix(&self, node: NodeID, max_dim: usize) -> Option<MatrixData> {
+        Some(MatrixData::new(&self.0.get_matrix(node, max_dim)?))
+    }
+    /// Adds a text note centered at the given position in diagram coordinates, which moves along with the anchor node if given, returning the id of the annotation
+    pub fn add_note(&mut self, anchor: Option<NodeID>, x: f32, y: f32, text: String) -> usize {
+        self.0.add_annotation(anchor, Point { x, y }, AnnotationKind::Note(text))
+    }
+    /// Adds an arrow from the given position towards the given offset, in diagram coordinates
+    pub fn add_arrow(&mut self, anchor: Option<NodeID>, x: f32, y: f32, dx: f32, dy: f32) -> usize {
+        let direction = Point { x: dx, y: dy };
+        self.0.add_annotation(anchor, Point { x, y }, AnnotationKind::Arrow(direction))
+    }
+    /// Adds a highlighted region centered at the given position, in diagram coordinates
+    pub fn add_highlight(
+        &mut self,
+        anchor: Option<NodeID>,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) -> usize {
+        let size = Point {
+            x: width,
+            y: height,
+        };
+        self.0.add_annotation(anchor, Point { x, y }, AnnotationKind::Highlight(size))
+    }
+    pub fn remove_annotation(&mut self, id: usize) -> bool {
+        self.0.remove_annotation(id)
+    }
+    /// Moves the keyboard focus in the given direction, returning the newly focused node and its position in screen space (-0.5 to 0.5)
+    pub fn navigate(&mut self, direction: NavigationDirection) -> Option<FocusData> {
+        self.0.navigate(direction)
+    }
     /// Retrieves the sources (nodes of the source diagram) of the modified diagram
     pub fn local_nodes_to_sources(&self, nodes: &[NodeID]) -> Vec<NodeID> {
         self.0.local_nodes_to_sources(nodes)
@@ -114,6 +664,112 @@ impl DiagramSectionDrawerBox {
     pub fn source_nodes_to_local(&self, nodes: &[NodeID]) -> Vec<NodeID> {
         self.0.source_nodes_to_local(nodes)
     }
+    /// Retrieves the properties of the given node for rendering its tooltip
+    pub fn get_node_details(&mut self, node: NodeID) -> NodeDetailsData {
+        self.0.get_node_details(node)
+    }
+    /// Retrieves a canonical hash of the subfunction represented by the given node, by which the nodes of the same subfunction in different sections can be matched up
+    pub fn get_node_hash(&mut self, node: NodeID) -> Option<u64> {
+        self.0.get_node_hash(node)
+    }
+
+    /** Export */
+    /// Exports the given area of the diagram (in diagram coordinates, with x, y being the bottom left) as an svg document
+    pub fn export_region_svg(&mut self, x: f32, y: f32, width: f32, height: f32) -> String {
+        self.0
+            .export_region_svg(Rectangle::new(x, y, width, height))
+    }
+    /// Exports the given area of the diagram (in diagram coordinates, with x, y being the bottom left) as a png data url
+    pub fn export_region_png(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        pixels_per_unit: f32,
+    ) -> String {
+        self.0
+            .export_region_png(Rectangle::new(x, y, width, height), pixels_per_unit)
+    }
+    /// Exports the whole diagram split across pages with the given overlap (in cm), where the custom width and height (in cm) are only used for the custom paper format
+    pub fn export_paged_svg(
+        &mut self,
+        format: PaperFormat,
+        landscape: bool,
+        custom_width: f32,
+        custom_height: f32,
+        overlap: f32,
+    ) -> Vec<String> {
+        let paper = format.get_size(landscape, custom_width, custom_height);
+        self.0.export_paged_svg(paper, overlap)
+    }
+    /// Exports the whole diagram split across pages as TikZ pictures separated by page breaks
+    pub fn export_paged_tikz(
+        &mut self,
+        format: PaperFormat,
+        landscape: bool,
+        custom_width: f32,
+        custom_height: f32,
+        overlap: f32,
+    ) -> String {
+        let paper = format.get_size(landscape, custom_width, custom_height);
+        self.0.export_paged_tikz(paper, overlap)
+    }
+    pub fn get_legend(&self) -> LegendData {
+        LegendData::new(&self.0.get_legend())
+    }
+    /// Snaps the coordinates in svg and TikZ exports to a grid with the given spacing (e.g. 0.1 or 0.5), or disables snapping if no spacing is given
+    pub fn set_export_grid(&mut self, grid: Option<f32>) {
+        self.0.set_export_grid(grid)
+    }
+    /// Sets whether the svg, png and TikZ exports include a legend next to the diagram
+    pub fn set_export_legend(&mut self, enabled: bool) {
+        self.0.set_export_legend(enabled)
+    }
+    /// Sets the TikZ options used for the given node in latex exports, or removes them if the style is empty
+    pub fn set_tikz_node_style(&mut self, node: NodeID, style: String) {
+        self.0.set_tikz_node_style(node, style)
+    }
+    /// Sets the TikZ options used for all nodes of the given level in latex exports, or removes them if the style is empty
+    pub fn set_tikz_level_style(&mut self, level: u32, style: String) {
+        self.0.set_tikz_level_style(level, style)
+    }
+    /// Sets the TikZ options used for all edges of the given type in latex exports, or removes them if the style is empty
+    pub fn set_tikz_edge_style(&mut self, edge_type: i32, style: String) {
+        self.0.set_tikz_edge_style(edge_type, style)
+    }
+    /// Records the currently shown nodes as the next stage of a staged TikZ export, returning the number of recorded stages
+    pub fn record_tikz_stage(&mut self) -> usize {
+        self.0.record_tikz_stage()
+    }
+    pub fn clear_tikz_stages(&mut self) {
+        self.0.clear_tikz_stages()
+    }
+    /// Exports the diagram as TikZ with beamer overlays that reveal the nodes in the order of the recorded stages
+    pub fn export_staged_tikz(&mut self) -> String {
+        self.0.export_staged_tikz()
+    }
+
+    /// Retrieves the visible graph as a compressed sparse adjacency structure
+    pub fn get_adjacency(&self) -> AdjacencyData {
+        self.0.get_adjacency()
+    }
+    /// Counts the edge crossings in the current layout
+    pub fn get_crossing_count(&self) -> usize {
+        self.0.get_crossing_count()
+    }
+    /// Measures the crossings, edge length, bends, aspect ratio and node overlaps of the current layout, such that layout algorithms can be compared
+    pub fn get_layout_metrics(&self) -> LayoutMetricsData {
+        self.0.get_layout_metrics()
+    }
+    /// Lays out the diagram and retrieves the resulting geometry, such that the diagram can be drawn by other means than the built-in renderers
+    pub fn compute_layout(&mut self, time: u32) -> Result<LayoutData, JsValue> {
+        profiler::span("layout", || self.0.compute_layout(time)).map_err(to_js_error)
+    }
+    /// Describes the visible graph as an indented text outline, suitable for screen readers
+    pub fn export_outline(&self) -> String {
+        self.0.export_outline()
+    }
 
     /** Storage */
     pub fn serialize_state(&self) -> Vec<u8> {
@@ -122,6 +778,22 @@ impl DiagramSectionDrawerBox {
     pub fn deserialize_state(&mut self, state: Vec<u8>) {
         self.0.deserialize_state(state)
     }
+    /// Resets the section to the state of its last successful layout, e.g. after the layout threw an error, returning whether it could be laid out again
+    pub fn recover(&mut self) -> bool {
+        self.0.recover()
+    }
+    /// Redraws the diagram into the canvas after its webgl context was restored from a context loss, returning whether this succeeded
+    pub fn restore_context(&mut self) -> bool {
+        self.0.restore_context()
+    }
+    /// Exports the presence adjustments and groups as a JSON patch keyed by source node IDs
+    pub fn export_view_patch(&self) -> String {
+        self.0.export_view_patch()
+    }
+    /// Applies a view patch to this section, which may stem from another session of the same dump
+    pub fn import_view_patch(&mut self, patch: String) -> Result<(), String> {
+        self.0.import_view_patch(patch)
+    }
 
     /** Settings */
     pub fn get_configuration(&self) -> AbstractConfigurationObject {
@@ -135,6 +807,336 @@ pub struct TerminalData {
     pub name: String,
 }
 
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct EquivalenceData {
+    pub equivalent: bool,
+    /// The levels of the variables that are true or false in the witness assignment, if the functions differ
+    pub witness_true: Vec<u32>,
+    pub witness_false: Vec<u32>,
+    /// The source nodes visited by the witness assignment in both functions, such that the differing paths can be highlighted
+    pub path_a: Vec<NodeID>,
+    pub path_b: Vec<NodeID>,
+}
+
+impl EquivalenceData {
+    pub fn equivalent() -> EquivalenceData {
+        EquivalenceData {
+            equivalent: true,
+            witness_true: Vec::new(),
+            witness_false: Vec::new(),
+            path_a: Vec::new(),
+            path_b: Vec::new(),
+        }
+    }
+    pub fn different(
+        witness: &[(u32, bool)],
+        path_a: Vec<NodeID>,
+        path_b: Vec<NodeID>,
+    ) -> EquivalenceData {
+        EquivalenceData {
+            equivalent: false,
+            witness_true: witness
+                .iter()
+                .filter(|(_, value)| *value)
+                .map(|&(level, _)| level)
+                .collect(),
+            witness_false: witness
+                .iter()
+                .filter(|(_, value)| !*value)
+                .map(|&(level, _)| level)
+                .collect(),
+            path_a,
+            path_b,
+        }
+    }
+}
+
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct PathData {
+    /// The levels of the variables that are assigned true or false along the path
+    pub true_levels: Vec<u32>,
+    pub false_levels: Vec<u32>,
+    /// The source nodes visited along the path, such that the path can be highlighted
+    pub nodes: Vec<NodeID>,
+}
+
+impl PathData {
+    pub fn new(assignment: &[(u32, bool)], nodes: Vec<NodeID>) -> PathData {
+        PathData {
+            true_levels: assignment
+                .iter()
+                .filter(|(_, value)| *value)
+                .map(|&(level, _)| level)
+                .collect(),
+            false_levels: assignment
+                .iter()
+                .filter(|(_, value)| !*value)
+                .map(|&(level, _)| level)
+                .collect(),
+            nodes,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct PathMetricsData {
+    /// The number of edges on the shortest and longest root to terminal paths
+    pub shortest_length: usize,
+    pub longest_length: usize,
+    pub shortest: PathData,
+    pub longest: PathData,
+}
+
+impl PathMetricsData {
+    pub fn new(shortest: PathData, longest: PathData) -> PathMetricsData {
+        PathMetricsData {
+            shortest_length: shortest.nodes.len().saturating_sub(1),
+            longest_length: longest.nodes.len().saturating_sub(1),
+            shortest,
+            longest,
+        }
+    }
+}
+
+/// The visible graph in compressed sparse row format, the edges of the group at row i are stored in indices[indptr[i]..indptr[i + 1]]
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct AdjacencyData {
+    /// The node group that each row and column represents
+    pub groups: Vec<u32>,
+    pub indptr: Vec<u32>,
+    /// The column (target group index) of each edge
+    pub indices: Vec<u32>,
+    /// The edge type index of each edge
+    pub edge_types: Vec<i32>,
+}
+
+/// The placement of two sections next to each other, in the diagram coordinates of the left section
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct ComparisonData {
+    /// The translation to apply to the right section, such that the shared levels are aligned
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// The local nodes of both sections connected by correspondence edges, compared pairwise
+    pub left_nodes: Vec<NodeID>,
+    pub right_nodes: Vec<NodeID>,
+    /// The start and end point of each correspondence edge, stored as x1, y1, x2, y2
+    pub lines: Vec<f32>,
+}
+
+/// The outcome of duplicating the nodes with a low in-degree
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct DuplicationData {
+    /// The number of nodes that are duplicated, and the number of copies shown in their place
+    pub duplicated_nodes: usize,
+    pub created_copies: usize,
+}
+
+/// Measures of the readability of a layout
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct LayoutMetricsData {
+    /// The number of pairs of edge segments that cross
+    pub crossings: usize,
+    pub total_edge_length: f32,
+    /// The number of points at which edges change direction
+    pub bends: usize,
+    /// The width of the layout divided by its height
+    pub aspect_ratio: f32,
+    /// The number of pairs of node groups whose boxes overlap
+    pub node_overlaps: usize,
+}
+
+/// The geometry of a laid out diagram in diagram coordinates, in which the elements of every kind are stored as parallel lists
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct LayoutData {
+    pub groups: Vec<NodeGroupID>,
+    /// The left, bottom, width and height of the box of every group
+    pub group_rects: Vec<f32>,
+    /// The number of nodes in every group, whose ids are stored consecutively in group_nodes
+    pub group_node_counts: Vec<usize>,
+    pub group_nodes: Vec<NodeID>,
+    /// The source group, target group and type index of every edge
+    pub edge_from: Vec<NodeGroupID>,
+    pub edge_to: Vec<NodeGroupID>,
+    pub edge_types: Vec<i32>,
+    /// The number of points of the polyline of every edge including its start and end, whose coordinates are stored consecutively in edge_points as x, y pairs
+    pub edge_point_counts: Vec<usize>,
+    pub edge_points: Vec<f32>,
+    /// The sideways offset by which every edge is curved
+    pub edge_curve_offsets: Vec<f32>,
+    /// The first and last level, and the top and bottom of every layer band
+    pub layer_start_levels: Vec<u32>,
+    pub layer_end_levels: Vec<u32>,
+    pub layer_tops: Vec<f32>,
+    pub layer_bottoms: Vec<f32>,
+}
+
+/// The elements of the diagram in an area, in which the edges are stored as parallel lists
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct ElementsData {
+    pub nodes: Vec<NodeID>,
+    /// The groups whose boxes overlap the area
+    pub groups: Vec<NodeGroupID>,
+    /// The source group, target group, type index and tag of every edge passing through the area
+    pub edge_from: Vec<NodeGroupID>,
+    pub edge_to: Vec<NodeGroupID>,
+    pub edge_types: Vec<i32>,
+    pub edge_tags: Vec<usize>,
+}
+
+/// The visual encodings currently in effect, in which the entries are stored as parallel lists
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct LegendData {
+    /// The kind of encoding of every entry, being either "edge", "node" or "scale"
+    pub kinds: Vec<String>,
+    pub meanings: Vec<String>,
+    /// The color of every entry stored as r, g, b, which for scales is the color of the minimum
+    pub colors: Vec<f32>,
+    /// The color of the maximum of every scale stored as r, g, b, which for other entries equals the color
+    pub end_colors: Vec<f32>,
+    /// The line width of every edge entry, and 0 for other entries
+    pub widths: Vec<f32>,
+    /// The value range of every scale, and 0 for other entries
+    pub min_values: Vec<f32>,
+    pub max_values: Vec<f32>,
+}
+
+impl LegendData {
+    pub fn new(legend: &Legend) -> LegendData {
+        let mut data = LegendData {
+            kinds: Vec::new(),
+            meanings: Vec::new(),
+            colors: Vec::new(),
+            end_colors: Vec::new(),
+            widths: Vec::new(),
+            min_values: Vec::new(),
+            max_values: Vec::new(),
+        };
+        for entry in &legend.entries {
+            let (kind, from, to, width, min, max) = match entry.symbol {
+                LegendSymbol::Edge { color, width, .. } => ("edge", color, color, width, 0., 0.),
+                LegendSymbol::Node { color } => ("node", color, color, 0., 0., 0.),
+                LegendSymbol::Scale { from, to, min, max } => ("scale", from, to, 0., min, max),
+            };
+            data.kinds.push(kind.into());
+            data.meanings.push(entry.meaning.clone());
+            data.colors.extend([from.0, from.1, from.2]);
+            data.end_colors.extend([to.0, to.1, to.2]);
+            data.widths.push(width);
+            data.min_values.push(min);
+            data.max_values.push(max);
+        }
+        data
+    }
+}
+
+/// The entries of a matrix or vector represented by a node, stored row by row as parallel lists of real and imaginary parts
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct MatrixData {
+    pub rows: usize,
+    pub columns: usize,
+    pub re: Vec<f32>,
+    pub im: Vec<f32>,
+}
+
+impl MatrixData {
+    pub fn new(matrix: &Matrix) -> MatrixData {
+        MatrixData {
+            rows: matrix.rows,
+            columns: matrix.columns,
+            re: matrix.entries.iter().map(|entry| entry.re).collect(),
+            im: matrix.entries.iter().map(|entry| entry.im).collect(),
+        }
+    }
+}
+
+/// The node that received the keyboard focus, together with the center of its group in screen space
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct FocusData {
+    pub node: NodeID,
+    pub group: NodeGroupID,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A recorded state of the diagram, with descriptions of the changes of the graph that led to it
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct HistoryEntryData {
+    /// The time at which the state was laid out, in ms
+    pub time: u32,
+    pub changes: Vec<String>,
+}
+
+/// A structural problem of an imported graph, which may cause it to be drawn differently than expected
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct ValidationWarning {
+    pub kind: ValidationWarningKind,
+    /// The offending nodes, which are the parents of the offending edges for edge problems
+    pub nodes: Vec<NodeID>,
+    pub description: String,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationWarningKind {
+    /// An edge leads to a node on the same or a higher level
+    ChildNotBelow,
+    /// An edge leads to a node that is not defined
+    DanglingEdge,
+    /// A node is defined, but not reachable from any root
+    UnreachableNode,
+    TerminalWithChildren,
+    /// The variables are ordered differently than in the order shared by the other sections, such that the levels are not unified
+    VariableOrderConflict,
+}
+
+/// The properties of a node, as shown in its tooltip
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct NodeDetailsData {
+    pub level: u32,
+    /// The name of the variable of the level
+    pub label: String,
+    pub in_degree: usize,
+    pub out_degree: usize,
+    /// The number of assignments of all variables for which the function of the node evaluates to true, or to a value other than 0 for numeric terminals
+    pub sat_count: f64,
+    /// The levels of the variables that the function of the node depends on
+    pub support: Vec<u32>,
+    /// The group containing the node
+    pub group: NodeGroupID,
+    /// The nodes of the source diagram that the node represents
+    pub sources: Vec<NodeID>,
+}
+
+/// The data of a level of a section, describing the variable of the level
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct LevelInfoData {
+    pub name: String,
+    /// The index of the variable in the imported file, which is also the number of the level
+    pub index: u32,
+    /// Whether any root of the section depends on the variable
+    pub in_support: bool,
+    /// The number of inner nodes reachable from the roots of the section that lie on the level
+    pub node_count: usize,
+}
+
+/// The effect of normalizing the graph of a section after importing it
+#[derive(Clone)]
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct ReductionData {
+    /// The number of imported nodes before the normalization
+    pub nodes_before: usize,
+    /// The number of imported nodes after the normalization
+    pub nodes_after: usize,
+    /// The number of nodes that were merged into a structurally identical node, which is the sharing recovered between the imported functions
+    pub merged: usize,
+    /// The number of redundant nodes that were removed because all of their edges lead to the same child
+    pub removed: usize,
+    /// The number of redundant nodes that were added on the levels skipped by edges when quasi-reducing
+    pub inserted: usize,
+}
+
 #[wasm_bindgen(getter_with_clone, inspectable)]
 pub struct StepData {
     pub description: String,
@@ -167,6 +1169,191 @@ pub enum TargetIDType {
     NodeID,
     NodeGroupID,
 }
+impl From<TargetIDType> for interface::TargetIDType {
+    fn from(id_type: TargetIDType) -> interface::TargetIDType {
+        match id_type {
+            TargetIDType::NodeID => interface::TargetIDType::NodeID,
+            TargetIDType::NodeGroupID => interface::TargetIDType::NodeGroupID,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Quantifier {
+    Exists,
+    Forall,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum NavigationDirection {
+    Parent,
+    LowChild,
+    HighChild,
+    NextSibling,
+    PreviousSibling,
+}
+
+/// The criterion by which the roots of a section are ordered from left to right
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum RootSorting {
+    Name,
+    /// By the number of nodes reachable from the root, largest first
+    Size,
+}
+
+/// An operation that is applied to all selected nodes at once
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum SelectionOperation {
+    Show,
+    Hide,
+    /// Shows a separate instance of the nodes for every edge to them
+    Duplicate,
+    /// Moves the nodes into a new group
+    Group,
+    /// Splits the edges of the groups of the nodes such that every edge type leads to its own group
+    Split,
+    /// Splits the edges such that every edge leads to a group of a single node
+    SplitFully,
+}
+
+/// The animation of groups that appear in or disappear from the diagram
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum AppearanceAnimation {
+    Fade,
+    /// Grows from or shrinks to the center of the group
+    Grow,
+    /// Flies in from above while fading in, or flies up while fading out
+    Fly,
+}
+impl From<AppearanceAnimation> for interface::AppearanceAnimation {
+    fn from(animation: AppearanceAnimation) -> interface::AppearanceAnimation {
+        match animation {
+            AppearanceAnimation::Fade => interface::AppearanceAnimation::Fade,
+            AppearanceAnimation::Grow => interface::AppearanceAnimation::Grow,
+            AppearanceAnimation::Fly => interface::AppearanceAnimation::Fly,
+        }
+    }
+}
+
+/// The side of a group that is aligned with the other groups, which is its center for Center
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum HorizontalAlignment {
+    Left,
+    Center,
+    Right,
+}
+impl From<HorizontalAlignment> for interface::HorizontalAlignment {
+    fn from(alignment: HorizontalAlignment) -> interface::HorizontalAlignment {
+        match alignment {
+            HorizontalAlignment::Left => interface::HorizontalAlignment::Left,
+            HorizontalAlignment::Center => interface::HorizontalAlignment::Center,
+            HorizontalAlignment::Right => interface::HorizontalAlignment::Right,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PresenceRemainder {
+    Show,
+    Hide,
+    /// Shows a separate instance of the node for every edge to it
+    Duplicate,
+    /// Shows a separate instance of the node for every parent, which multiple edges from the same parent share
+    DuplicateParent,
+}
+impl From<PresenceRemainder> for node_presence_adjuster::PresenceRemainder {
+    fn from(remainder: PresenceRemainder) -> node_presence_adjuster::PresenceRemainder {
+        match remainder {
+            PresenceRemainder::Show => node_presence_adjuster::PresenceRemainder::Show,
+            PresenceRemainder::Hide => node_presence_adjuster::PresenceRemainder::Hide,
+            PresenceRemainder::Duplicate => node_presence_adjuster::PresenceRemainder::Duplicate,
+            PresenceRemainder::DuplicateParent => {
+                node_presence_adjuster::PresenceRemainder::DuplicateParent
+            }
+        }
+    }
+}
+
+/// A condition on nodes, by which presence adjustments are applied in bulk. Nodes match if they meet every given criterion.
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct PresencePredicate(node_presence_adjuster::PresencePredicate);
+#[wasm_bindgen]
+impl PresencePredicate {
+    pub fn new() -> PresencePredicate {
+        PresencePredicate::default()
+    }
+    /// Only matches the nodes in the given inclusive range of levels
+    pub fn with_level_range(self, min: LevelNo, max: LevelNo) -> PresencePredicate {
+        PresencePredicate(self.0.with_level_range(min, max))
+    }
+    /// Only matches the nodes whose level label, the name of their variable, matches the given regular expression
+    pub fn with_label_pattern(self, pattern: String) -> PresencePredicate {
+        PresencePredicate(self.0.with_label_pattern(pattern))
+    }
+    /// Only matches the nodes with at least the given number of incoming edges
+    pub fn with_min_in_degree(self, degree: usize) -> PresencePredicate {
+        PresencePredicate(self.0.with_min_in_degree(degree))
+    }
+}
+
+/// The severity of a log message, ordered from the most to the least severe
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum LogLevel {
+    /// Writes no messages at all
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+impl From<LogLevel> for logging::LogLevel {
+    fn from(level: LogLevel) -> logging::LogLevel {
+        match level {
+            LogLevel::Off => logging::LogLevel::Off,
+            LogLevel::Error => logging::LogLevel::Error,
+            LogLevel::Warn => logging::LogLevel::Warn,
+            LogLevel::Info => logging::LogLevel::Info,
+            LogLevel::Debug => logging::LogLevel::Debug,
+            LogLevel::Trace => logging::LogLevel::Trace,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PaperFormat {
+    A4,
+    Letter,
+    Custom,
+}
+
+impl PaperFormat {
+    fn get_size(self, landscape: bool, custom_width: f32, custom_height: f32) -> PaperSize {
+        let paper = match self {
+            PaperFormat::A4 => PaperSize::A4,
+            PaperFormat::Letter => PaperSize::LETTER,
+            PaperFormat::Custom => PaperSize {
+                width: custom_width,
+                height: custom_height,
+            },
+        };
+        if landscape {
+            paper.landscape()
+        } else {
+            paper
+        }
+    }
+}
 
 #[derive(PartialEq)]
 #[wasm_bindgen]
@@ -177,6 +1364,17 @@ impl TargetID {
         TargetID(id_type, id)
     }
 }
+impl From<TargetID> for interface::TargetID {
+    fn from(TargetID(id_type, id): TargetID) -> interface::TargetID {
+        interface::TargetID(id_type.into(), id)
+    }
+}
+
+/// Converts the error into a JS error whose name is the kind of the error, such that frontends can show actionable messages
+fn to_js_error(error: VizError) -> JsValue {
+    let exception = js_sys::Error::new(&error.to_string());
+    exception.set_name(error.get_kind());
+    exception.into()
+}
 
-pub type NodeGroupID = usize;
-pub type NodeID = usize;
+pub use crate::interface::{NodeGroupID, NodeID};
@@ -0,0 +1,277 @@
+//! Golden-file tests of the SVG and TikZ exports of the dumps in `tests/golden/corpus`. The
+//! expected outputs are stored in `tests/golden/expected`, and are only (re)generated by running
+//! the tests with `UPDATE_GOLDEN=1`, such that a missing expected output fails the tests.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{collections::HashMap, env, fs, path::Path};
+
+use itertools::Itertools;
+use oxidd_viz_rust::{
+    types::util::{
+        drawing::{
+            diagram_layout::{DiagramLayout, LayerStyle, NodeStyle},
+            layout_rules::LayoutRules,
+            layouts::{
+                layer_group_sorting::ordering_group_alignment::OrderingGroupAlignment,
+                layer_orderings::{
+                    combinators::sequence_ordering::SequenceOrdering,
+                    edge_layer_ordering::EdgeLayerOrdering,
+                    pseudo_random_layer_ordering::PseudoRandomLayerOrdering,
+                    sugiyama_ordering::SugiyamaOrdering,
+                },
+                layer_positionings::brandes_kopf_positioning_corrected::BrandesKopfPositioningCorrected,
+                layered_layout::LayeredLayout,
+                layered_layout_traits::WidthLabel,
+            },
+            renderer::DiagramRenderer,
+            renderers::{
+                latex_renderer::{LatexLayerStyle, LatexNodeStyle, LatexRenderer},
+                render_styles::{
+                    EdgeRenderingType, LayerRenderingColorConfig, WebglLayerStyle, WebglNodeStyle,
+                },
+                svg_renderer::SvgRenderer,
+            },
+        },
+        graph_structure::{
+            graph_manipulators::label_adjusters::group_label_adjuster::GroupLabelAdjuster,
+            graph_structure::EdgeType,
+            grouped_graph_structure::GroupedGraphStructure,
+            oxidd_graph_structure::{NodeType, OxiddGraphStructure},
+        },
+        group_manager::GroupManager,
+    },
+    util::{
+        color::{Color, TransparentColor},
+        dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+        transition::Interpolatable,
+    },
+};
+use regex::Regex;
+
+#[derive(Clone)]
+struct GoldenNode {
+    color: Color,
+    name: Option<String>,
+    terminal: Option<usize>,
+    is_group: bool,
+}
+impl Interpolatable for GoldenNode {
+    fn mix(&self, other: &Self, _frac: f32) -> Self {
+        other.clone()
+    }
+}
+impl NodeStyle for GoldenNode {}
+impl WidthLabel for GoldenNode {
+    fn get_width(&self) -> f32 {
+        1.
+    }
+}
+impl WebglNodeStyle for GoldenNode {
+    fn get_color(&self) -> Color {
+        self.color
+    }
+    fn get_outline_color(&self) -> TransparentColor {
+        TransparentColor(0., 0., 0., 0.)
+    }
+    fn get_label(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+impl LatexNodeStyle for GoldenNode {
+    fn is_terminal(&self) -> Option<(String, Option<String>)> {
+        self.terminal.map(|value| (format!("terminal{}", value), None))
+    }
+    fn is_group(&self) -> bool {
+        self.is_group
+    }
+    fn get_label(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+
+#[derive(Clone)]
+struct GoldenLayer {
+    name: String,
+}
+impl Interpolatable for GoldenLayer {
+    fn mix(&self, other: &Self, _frac: f32) -> Self {
+        other.clone()
+    }
+}
+impl LayerStyle for GoldenLayer {
+    fn squash(layers: Vec<Self>) -> Self {
+        GoldenLayer {
+            name: layers.into_iter().map(|layer| layer.name).join(", "),
+        }
+    }
+}
+impl WebglLayerStyle for GoldenLayer {
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
+impl LatexLayerStyle for GoldenLayer {
+    fn get_label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+type Graph = GroupLabelAdjuster<
+    GoldenNode,
+    GoldenLayer,
+    GroupManager<OxiddGraphStructure<(), DummyBDDFunction, String>>,
+>;
+type Layout = LayeredLayout<
+    Graph,
+    SequenceOrdering<
+        Graph,
+        PseudoRandomLayerOrdering,
+        SequenceOrdering<Graph, EdgeLayerOrdering, SugiyamaOrdering>,
+    >,
+    OrderingGroupAlignment,
+    BrandesKopfPositioningCorrected,
+>;
+
+/// Lays out the diagram of the given dump with every node in a group of its own, styled by whether it is a terminal
+fn layout_dump(dump: &str) -> DiagramLayout<(), GoldenNode, GoldenLayer> {
+    let manager = DummyBDDManager::new();
+    let (roots, levels, _) =
+        DummyBDDFunction::from_dddmp(&mut DummyBDDManagerRef::from(&manager), dump, false, false)
+            .unwrap();
+    let mut group_manager = GroupManager::new(OxiddGraphStructure::new(roots, levels));
+    group_manager.reveal_bounded(usize::MAX, usize::MAX);
+    let mut graph = GroupLabelAdjuster::new(
+        group_manager,
+        |_, nodes| {
+            let terminal = match &nodes[..] {
+                [node] => match &node.kind {
+                    NodeType::Terminal(terminal) => Some(terminal.clone()),
+                    NodeType::Inner(_) => None,
+                },
+                _ => None,
+            };
+            let color = match terminal {
+                Some(_) => Color(0.9, 0.9, 0.9),
+                None => Color(0.5, 0.6, 0.9),
+            };
+            GoldenNode {
+                color,
+                terminal: terminal.as_ref().map(|terminal| (terminal == "T") as usize),
+                name: terminal,
+                is_group: nodes.len() > 1,
+            }
+        },
+        |_, name| GoldenLayer { name },
+    );
+    let tracker = graph.create_node_tracker();
+    let mut layout: Layout = LayeredLayout::new(
+        SequenceOrdering::new(
+            PseudoRandomLayerOrdering::new(2, 0),
+            SequenceOrdering::new(EdgeLayerOrdering, SugiyamaOrdering::new(2, 2)),
+        ),
+        OrderingGroupAlignment,
+        BrandesKopfPositioningCorrected,
+        0.3,
+    );
+    let old = DiagramLayout {
+        groups: HashMap::new(),
+        layers: Vec::new(),
+    };
+    layout.layout(&graph, &old, &tracker, 0).unwrap()
+}
+
+fn export_svg(layout: &DiagramLayout<(), GoldenNode, GoldenLayer>) -> String {
+    let edge_type = |color: Color, dash_solid: f32, dash_transparent: f32| EdgeRenderingType {
+        color,
+        select_color: color,
+        partial_select_color: color,
+        hover_color: color,
+        partial_hover_color: color,
+        width: 0.15,
+        dash_solid,
+        dash_transparent,
+    };
+    let edge_types = HashMap::from([
+        (EdgeType::new((), 0), edge_type(Color(0., 0., 0.), 1., 0.)),
+        (EdgeType::new((), 1), edge_type(Color(0., 0., 0.), 0.3, 0.15)),
+    ]);
+    let layer_colors = LayerRenderingColorConfig {
+        background1: TransparentColor(0.95, 0.95, 0.95, 1.),
+        background2: TransparentColor(1., 1., 1., 1.),
+        text: Color(0., 0., 0.),
+    };
+    let mut renderer = SvgRenderer::<Layout>::new(edge_types, layer_colors, Color(0., 0., 0.), 0.5);
+    renderer.update_layout(layout);
+    renderer.render(u32::MAX);
+    renderer.get_output()
+}
+
+fn export_tikz(layout: &DiagramLayout<(), GoldenNode, GoldenLayer>) -> String {
+    let mut renderer = LatexRenderer::<Layout>::new();
+    renderer.update_layout(layout);
+    renderer.render(u32::MAX);
+    renderer.get_output()
+}
+
+/// Normalizes an export such that it only differs from the expected output if the export changed meaningfully. Floating-point numbers are rounded to 2 decimals to absorb rounding noise, while the order of the lines is kept, as the renderers emit the elements in a fixed order.
+fn normalize(output: &str) -> String {
+    let number = Regex::new(r"-?\d+\.\d+").unwrap();
+    let rounded = number.replace_all(output, |captures: &regex::Captures| {
+        let value: f64 = captures[0].parse().unwrap();
+        let text = format!("{:.2}", value);
+        if text == "-0.00" {
+            "0.00".to_string()
+        } else {
+            text
+        }
+    });
+    rounded.lines().map(|line| line.trim_end()).join("\n") + "\n"
+}
+
+/// Compares the normalized output with the expected output of the given file, or stores it as the expected output if requested
+fn check_golden(file: &str, output: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/expected")
+        .join(file);
+    let output = normalize(output);
+    if env::var("UPDATE_GOLDEN").is_ok() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &output).unwrap();
+        eprintln!("Stored the expected output {}", path.display());
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "The expected output {} is missing, rerun with UPDATE_GOLDEN=1 to create it",
+            path.display()
+        );
+    };
+    assert!(
+        expected == output,
+        "The export {} differs from the expected output, \
+        rerun with UPDATE_GOLDEN=1 if the change is intended\n--- expected\n{}\n--- actual\n{}",
+        file,
+        expected,
+        output
+    );
+}
+
+#[test]
+fn exports_match_golden_files() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/corpus");
+    let dumps = fs::read_dir(&corpus)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "dddmp"))
+        .sorted()
+        .collect_vec();
+    assert!(!dumps.is_empty(), "The corpus {} is empty", corpus.display());
+
+    for dump in dumps {
+        let name = dump.file_stem().unwrap().to_string_lossy().to_string();
+        let layout = layout_dump(&fs::read_to_string(&dump).unwrap());
+        check_golden(&format!("{}.svg", name), &export_svg(&layout));
+        check_golden(&format!("{}.tex", name), &export_tikz(&layout));
+    }
+}
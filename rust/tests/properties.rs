@@ -0,0 +1,194 @@
+//! Property-based tests of the graph manipulators on pseudo-random diagrams.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use oxidd_viz_rust::{
+    interface::{NodeID, TargetID, TargetIDType},
+    types::util::{
+        graph_structure::{
+            graph_manipulators::node_presence_adjuster::{
+                NodePresenceAdjuster, PresenceRemainder,
+            },
+            graph_structure::GraphStructure,
+            grouped_graph_structure::GroupedGraphStructure,
+            oxidd_graph_structure::OxiddGraphStructure,
+        },
+        group_manager::GroupManager,
+        section_import::ImportManager,
+    },
+    util::{
+        dummy_bdd::{DummyBDDFunction, DummyBDDManager, DummyBDDManagerRef},
+        graph_builder::GraphBuilder,
+    },
+};
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+type Graph = OxiddGraphStructure<(), DummyBDDFunction, String>;
+
+fn create_graph(vars: u32, nodes: usize, seed: u32) -> Graph {
+    let builder = GraphBuilder::generate_random_bdd(vars, nodes, seed);
+    let manager = DummyBDDManager::new();
+    let (roots, levels) =
+        DummyBDDFunction::from_builder(&mut DummyBDDManagerRef::from(&manager), &builder);
+    OxiddGraphStructure::new(roots, levels)
+}
+
+/// Collects the nodes reachable from the roots of the graph
+fn reachable_nodes<G: GraphStructure>(graph: &mut G) -> Vec<NodeID> {
+    let mut found: HashSet<NodeID> = graph.get_roots().into_iter().collect();
+    let mut stack = found.iter().cloned().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        for (_, child) in graph.get_children(node) {
+            if found.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
+    let mut nodes = found.into_iter().collect::<Vec<_>>();
+    nodes.sort();
+    nodes
+}
+
+/// Collects the edges reachable from the roots of the graph, in terms of the source nodes they represent
+fn source_edges<G: GraphStructure>(graph: &mut G) -> BTreeSet<(NodeID, i32, NodeID)> {
+    let mut edges = BTreeSet::new();
+    for node in reachable_nodes(graph) {
+        let parents = graph.local_nodes_to_sources(vec![node]);
+        for (edge_type, child) in graph.get_children(node) {
+            for &parent in &parents {
+                for child in graph.local_nodes_to_sources(vec![child]) {
+                    edges.insert((parent, edge_type.index, child));
+                }
+            }
+        }
+    }
+    edges
+}
+
+fn any_adjustment() -> impl Strategy<Value = PresenceRemainder> {
+    prop_oneof![
+        Just(PresenceRemainder::Hide),
+        Just(PresenceRemainder::Duplicate),
+        Just(PresenceRemainder::DuplicateParent),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn generated_bdds_are_deterministic_and_reduced(
+        vars in 1u32..8,
+        nodes in 1usize..60,
+        seed in any::<u32>(),
+    ) {
+        let builder = GraphBuilder::generate_random_bdd(vars, nodes, seed);
+        let describe = |builder: &GraphBuilder| {
+            builder
+                .get_nodes()
+                .iter()
+                .map(|node| (node.level, node.children.clone(), node.terminal.clone()))
+                .collect::<Vec<_>>()
+        };
+        let again = GraphBuilder::generate_random_bdd(vars, nodes, seed);
+        prop_assert_eq!(describe(&builder), describe(&again));
+        prop_assert_eq!(builder.get_roots(), again.get_roots());
+
+        let inner = builder
+            .get_nodes()
+            .iter()
+            .filter(|node| node.terminal.is_none())
+            .collect::<Vec<_>>();
+        prop_assert!(!inner.is_empty() && inner.len() <= nodes);
+        let mut unique = HashSet::new();
+        for node in inner {
+            prop_assert_eq!(node.children.len(), 2);
+            prop_assert_ne!(node.children[0], node.children[1]);
+            prop_assert!(unique.insert((node.level, node.children.clone())));
+        }
+
+        let mut manager_ref = DummyBDDManagerRef::from(&DummyBDDManager::new());
+        let (roots, _) = DummyBDDFunction::from_builder(&mut manager_ref, &builder);
+        prop_assert!(manager_ref.validate(&roots, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn presence_adjustments_preserve_source_edges(
+        vars in 1u32..6,
+        nodes in 1usize..40,
+        seed in any::<u32>(),
+        picks in vec(any::<Index>(), 1..8),
+        remainder in any_adjustment(),
+    ) {
+        let mut adjuster = NodePresenceAdjuster::new(create_graph(vars, nodes, seed));
+        let original = source_edges(&mut adjuster);
+        let roots = adjuster.local_nodes_to_sources(adjuster.get_roots());
+        let candidates = original
+            .iter()
+            .map(|&(parent, _, _)| parent)
+            .filter(|parent| !roots.contains(parent))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        prop_assume!(!candidates.is_empty());
+        let adjusted = picks
+            .iter()
+            .map(|pick| candidates[pick.index(candidates.len())])
+            .collect::<BTreeSet<_>>();
+
+        for &node in &adjusted {
+            adjuster.set_source_presence(node, remainder.clone());
+        }
+        let edges = source_edges(&mut adjuster);
+        if remainder == PresenceRemainder::Hide {
+            // Hidden nodes disappear together with their edges, without introducing other edges
+            prop_assert!(edges.is_subset(&original));
+            for &(parent, _, child) in &edges {
+                prop_assert!(!adjusted.contains(&parent) && !adjusted.contains(&child));
+            }
+        } else {
+            // Copies represent the same source nodes, hence all edges are still drawn
+            prop_assert_eq!(&edges, &original);
+        }
+
+        for &node in &adjusted {
+            adjuster.set_source_presence(node, PresenceRemainder::Show);
+        }
+        prop_assert_eq!(source_edges(&mut adjuster), original);
+    }
+
+    #[test]
+    fn groups_partition_the_nodes(
+        vars in 1u32..6,
+        nodes in 1usize..40,
+        seed in any::<u32>(),
+        groupings in vec(vec(any::<Index>(), 1..5), 0..5),
+    ) {
+        let mut graph = create_graph(vars, nodes, seed);
+        let all_nodes = reachable_nodes(&mut graph);
+        let mut manager = GroupManager::new(graph);
+        manager.reveal_bounded(usize::MAX, usize::MAX);
+        for picks in groupings {
+            let targets = picks
+                .iter()
+                .map(|pick| TargetID(TargetIDType::NodeID, all_nodes[pick.index(all_nodes.len())]))
+                .collect();
+            manager.create_group(targets);
+        }
+
+        let mut group_of_node = HashMap::new();
+        let (mut out_edges, mut in_edges) = (0, 0);
+        for group in manager.get_all_groups() {
+            for node in manager.get_nodes_of_group(group) {
+                prop_assert!(group_of_node.insert(node, group).is_none());
+                prop_assert_eq!(manager.get_group(node), group);
+            }
+            out_edges += manager.get_children(group).iter().map(|edge| edge.count).sum::<usize>();
+            in_edges += manager.get_parents(group).iter().map(|edge| edge.count).sum::<usize>();
+        }
+        let mut grouped_nodes = group_of_node.into_keys().collect::<Vec<_>>();
+        grouped_nodes.sort();
+        prop_assert_eq!(grouped_nodes, all_nodes);
+        prop_assert_eq!(out_edges, in_edges);
+    }
+}
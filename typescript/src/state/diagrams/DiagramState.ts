@@ -14,6 +14,7 @@ import {FileSource} from "./sources/FileSource";
 import {IDiagramVisualizationSerialization} from "./_types/IDiagramVisualizationSerialization";
 import {ReferenceSource} from "./sources/ReferenceSource";
 import {IDiagramType} from "./_types/IDiagramTypeSerialization";
+import {IBatchData} from "./sources/_types/IFileSourceSerialization";
 
 const sourceTypes: Record<string, IDiagramSectionType<unknown>> = {
     file: FileSource,
@@ -112,6 +113,46 @@ export class DiagramState extends ViewState {
         });
     }
 
+    /**
+     * Creates a single section for this diagram from multiple dumps, whose variable orders are merged by the names of the variables such that the sharing between their functions is visible
+     * @param files The contents of the dumps
+     * @param name The name of the section
+     * @returns The mutator to commit the change, resulting in the created section
+     */
+    public createSectionFromFiles(
+        files: IBatchData["files"],
+        name?: string
+    ): IMutator<FileSource> {
+        return chain(push => {
+            const section = new FileSource(this, this.diagram, {files});
+            push(this._sections.set([...this._sections.get(), section]));
+            if (name)
+                try {
+                    const viz = section.visualization.get();
+                    if (viz) push(viz.name.set(name));
+                } catch (e) {
+                    console.error(e);
+                }
+            return section;
+        });
+    }
+
+    /**
+     * Reads the given files asynchronously and imports them into a single section, where files with the `dddmp` extension are read as dddmp dumps and all other files as buddy dumps
+     * @param files The files to import
+     * @param name The name of the section
+     * @returns The created section, once all files have been read
+     */
+    public async importFiles(files: File[], name?: string): Promise<FileSource> {
+        const contents = await Promise.all(files.map(file => file.text()));
+        const data = files.map((file, i) =>
+            file.name.toLowerCase().endsWith(".dddmp")
+                ? {dddmp: contents[i]}
+                : {buddy: {data: contents[i]}}
+        );
+        return this.createSectionFromFiles(data, name).commit();
+    }
+
     /**
      * Creates a new section for this diagram, based on the passed nodes
      * @param nodes The nodes to make the section fro
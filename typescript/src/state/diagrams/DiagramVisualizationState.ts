@@ -51,6 +51,14 @@ export class DiagramVisualizationState extends ViewState {
     public readonly size = new Field({ x: 0, y: 0 });
     protected sizeObserver = new Observer(this.size).add(() => this.sendTransform());
 
+    /** The number of device pixels per css pixel of the screen the canvas is shown on */
+    public readonly pixelRatio = new Field(window.devicePixelRatio || 1);
+    protected pixelRatioObserver = new Observer(this.pixelRatio).add(() => this.sendTransform());
+    protected pixelRatioQuery: MediaQueryList | undefined;
+
+    /** Whether the webgl context of the canvas is currently lost, in which case nothing can be rendered */
+    protected contextLost = false;
+
     /** Visualization state shared between visualizations of this diagram */
     public readonly sharedState: ISharedVisualizationState;
     protected selectionObserver: Observer<{
@@ -86,7 +94,10 @@ export class DiagramVisualizationState extends ViewState {
         this.canvas = canvas;
         this.sharedState = sharedState;
 
-        this.drawer.layout(Date.now() - this.start);
+        this.layoutDrawer(Date.now() - this.start);
+        this.watchPixelRatio();
+        this.canvas.addEventListener("webglcontextlost", this.onContextLost);
+        this.canvas.addEventListener("webglcontextrestored", this.onContextRestored);
         this.selectionObserver = new Observer(
             new Derived(watch => ({
                 selected: watch(sharedState.selection),
@@ -99,17 +110,50 @@ export class DiagramVisualizationState extends ViewState {
     protected sendTransform() {
         const transform = this.transform.get();
         const size = this.size.get();
-        this.canvas.width = size.x;
-        this.canvas.height = size.y;
-        this.drawer.set_transform(
-            size.x,
-            size.y,
+        const ratio = this.pixelRatio.get();
+        // Render at the device resolution, while keeping the css size of the canvas
+        const width = Math.round(size.x * ratio);
+        const height = Math.round(size.y * ratio);
+        this.canvas.width = width;
+        this.canvas.height = height;
+        this.canvas.style.width = `${size.x}px`;
+        this.canvas.style.height = `${size.y}px`;
+        const regrouped = this.drawer.set_transform(
+            width,
+            height,
             transform.offset.x,
             transform.offset.y,
-            transform.scale
+            transform.scale * ratio
         );
+        // Subfunctions are summarized or restored when zooming past the semantic zoom threshold
+        if (regrouped) this.relayout();
+    }
+
+    /** Keeps the pixel ratio up to date, e.g. when the browser zoom changes or the window moves to another screen */
+    protected watchPixelRatio() {
+        const ratio = window.devicePixelRatio || 1;
+        this.pixelRatio.set(ratio).commit();
+        this.pixelRatioQuery = window.matchMedia(`(resolution: ${ratio}dppx)`);
+        this.pixelRatioQuery.addEventListener("change", this.onPixelRatioChange, { once: true });
     }
 
+    protected onPixelRatioChange = () => {
+        if (!this.drawer) return;
+        this.watchPixelRatio();
+    };
+
+    protected onContextLost = (event: Event) => {
+        // Signals that the context should be restored once possible
+        event.preventDefault();
+        this.contextLost = true;
+    };
+
+    protected onContextRestored = () => {
+        this.contextLost = false;
+        if (!this.drawer.restore_context())
+            console.error("The visualization could not be restored after losing the webgl context");
+    };
+
     protected sendHighlight() {
         const selectNodes = this.drawer.source_nodes_to_local(
             this.sharedState.selection.get()
@@ -120,12 +164,26 @@ export class DiagramVisualizationState extends ViewState {
         this.drawer.set_selected_nodes(selectNodes, highlightNodes);
     }
 
+    /**
+     * Lays out the diagram, and resets it to its last consistent state if an internal error occurs
+     * @param time The time of the layout, relative to the start
+     */
+    protected layoutDrawer(time: number) {
+        try {
+            this.drawer.layout(time);
+        } catch (e) {
+            console.error(e instanceof Error ? `${e.name}: ${e.message}` : e);
+            if (!this.drawer.recover())
+                console.error("The diagram could not be recovered from the error");
+        }
+    }
+
     /**
      * Updates the diagram's layout
      */
     protected relayout() {
         const layoutStart = Date.now();
-        this.drawer.layout(layoutStart - this.start);
+        this.layoutDrawer(layoutStart - this.start);
         const layoutTime = Date.now() - layoutStart;
         this.start += layoutTime;
     }
@@ -166,6 +224,7 @@ export class DiagramVisualizationState extends ViewState {
 
     /** Renders a frame to the canvas */
     public render() {
+        if (this.contextLost) return;
         const time = Date.now() - this.start;
         this.drawer?.render(time);
     }
@@ -177,6 +236,10 @@ export class DiagramVisualizationState extends ViewState {
     public dispose() {
         this.transformObserver.destroy();
         this.sizeObserver.destroy();
+        this.pixelRatioObserver.destroy();
+        this.pixelRatioQuery?.removeEventListener("change", this.onPixelRatioChange);
+        this.canvas.removeEventListener("webglcontextlost", this.onContextLost);
+        this.canvas.removeEventListener("webglcontextrestored", this.onContextRestored);
         this.selectionObserver.destroy();
         this.config.get().destroy();
         this.drawer.free();
@@ -1,4 +1,4 @@
-import {DiagramBox} from "oxidd-viz-rust";
+import {create_import_batch, DiagramBox} from "oxidd-viz-rust";
 import {Derived} from "../../../watchables/Derived";
 import {Field} from "../../../watchables/Field";
 import {IMutator} from "../../../watchables/mutator/_types/IMutator";
@@ -26,6 +26,14 @@ export class FileSource extends AbstractDiagramSectionState<IFileSourceSerializa
             new Derived(() => {
                 const data = this.data.get();
                 try {
+                    if ("files" in data) {
+                        const batch = create_import_batch();
+                        for (const file of data.files) {
+                            if ("dddmp" in file) batch.add_dddmp(file.dddmp);
+                            else batch.add_buddy(file.buddy.data, file.buddy.vars);
+                        }
+                        return diagramBox.create_section_from_batch(batch);
+                    }
                     const diagram =
                         "dddmp" in data
                             ? diagramBox.create_section_from_dddmp(data.dddmp)
@@ -33,11 +41,15 @@ export class FileSource extends AbstractDiagramSectionState<IFileSourceSerializa
                                   data.buddy.data,
                                   data.buddy.vars
                               );
-                    if (!diagram) console.error("Diagram could not be created from data");
                     return diagram;
                 } catch (e) {
-                    console.error(e);
-                    throw e;
+                    // The error's name is the kind of the failure, e.g. `ParseError`
+                    console.error(
+                        e instanceof Error
+                            ? `Diagram could not be created from data, ${e.name}: ${e.message}`
+                            : e
+                    );
+                    return undefined;
                 }
             })
         );
@@ -50,13 +50,16 @@ export class ReferenceSource extends AbstractDiagramSectionState<IReferenceSourc
                     source_section = watch(parent.source) ?? source_section;
                 }
                 const roots = watch(this.roots);
-                const diagram = diagramBox.create_section_from_ids(
-                    roots,
-                    source_section!
-                );
-                if (!diagram)
-                    console.error("Diagram could not be created from reference");
-                return diagram;
+                try {
+                    return diagramBox.create_section_from_ids(roots, source_section!);
+                } catch (e) {
+                    console.error(
+                        e instanceof Error
+                            ? `Diagram could not be created from reference, ${e.name}: ${e.message}`
+                            : e
+                    );
+                    return undefined;
+                }
             })
         );
 
@@ -1,3 +1,5 @@
-export type IFileSourceSerialization = IDddmpData | IBuddyData;
+export type IFileSourceSerialization = IDddmpData | IBuddyData | IBatchData;
 export type IDddmpData = {dddmp: string};
 export type IBuddyData = {buddy: {data: string; vars?: string}};
+/** Multiple dumps that are imported into a single section, sharing their nodes and variables */
+export type IBatchData = {files: (IDddmpData | IBuddyData)[]};